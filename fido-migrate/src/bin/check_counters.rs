@@ -0,0 +1,162 @@
+/// Consistency check for denormalized post counters
+///
+/// Compares each post's stored upvotes/downvotes/reply_count against the
+/// votes and replies they summarize, and optionally repairs any drift.
+/// Opening the database also installs the counter triggers if missing.
+///
+/// Run with: cargo run --bin check-counters [--database <path>] [--repair]
+use anyhow::{Context, Result};
+use clap::Parser;
+use fido_server::db::{repositories::PostRepository, Database};
+use rusqlite::Connection;
+use uuid::Uuid;
+
+/// Stored counters versus the values aggregated from source rows
+const ACTUAL_COUNTS_QUERY: &str =
+    "SELECT p.id, p.upvotes, p.downvotes, p.reply_count,
+            (SELECT COUNT(*) FROM votes v WHERE v.post_id = p.id AND v.direction = 'up') AS actual_up,
+            (SELECT COUNT(*) FROM votes v WHERE v.post_id = p.id AND v.direction = 'down') AS actual_down,
+            (SELECT COUNT(*) FROM posts r WHERE r.parent_post_id = p.id) AS actual_replies
+     FROM posts p";
+
+/// Denormalized counters stored on a post
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PostCounters {
+    upvotes: i32,
+    downvotes: i32,
+    reply_count: i32,
+}
+
+/// A post whose stored counters no longer match its votes and replies
+#[derive(Debug, Clone)]
+struct CounterDrift {
+    post_id: Uuid,
+    stored: PostCounters,
+    actual: PostCounters,
+}
+
+impl std::fmt::Display for CounterDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}  up {} -> {}  down {} -> {}  replies {} -> {}",
+            self.post_id,
+            self.stored.upvotes,
+            self.actual.upvotes,
+            self.stored.downvotes,
+            self.actual.downvotes,
+            self.stored.reply_count,
+            self.actual.reply_count,
+        )
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "check-counters")]
+#[command(about = "Detect and repair drifted vote and reply counters")]
+struct Args {
+    /// Path to the SQLite database file
+    #[arg(short, long, default_value = "./fido.db")]
+    database: String,
+
+    /// Recompute drifted counters instead of only reporting them
+    #[arg(long)]
+    repair: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    if !std::path::Path::new(&args.database).exists() {
+        anyhow::bail!("Database file not found: {}", args.database);
+    }
+
+    let db = Database::new(&args.database).context("Failed to open database connection")?;
+    db.initialize().context("Failed to apply schema migrations")?;
+    let post_repo = PostRepository::new(db.pool.clone());
+
+    let drift = find_counter_drift(&*db.pool.get()?)?;
+    if drift.is_empty() {
+        println!("All post counters are consistent.");
+        return Ok(());
+    }
+
+    println!("Found {} post(s) with drifted counters:", drift.len());
+    for entry in &drift {
+        println!("  {}", entry);
+    }
+
+    if args.repair {
+        let repaired = post_repo.repair_counters()?;
+        println!("Repaired {} post(s).", repaired);
+    } else {
+        println!();
+        println!("Run again with --repair to fix them.");
+        // Non-zero so scripts and CI can detect drift
+        std::process::exit(2);
+    }
+
+    Ok(())
+}
+
+/// Find posts whose stored vote or reply counters disagree with the
+/// `votes`/`posts` rows they summarize
+fn find_counter_drift(conn: &Connection) -> Result<Vec<CounterDrift>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, upvotes, downvotes, reply_count, actual_up, actual_down, actual_replies
+         FROM ({})
+         WHERE upvotes != actual_up OR downvotes != actual_down OR reply_count != actual_replies",
+        ACTUAL_COUNTS_QUERY
+    ))?;
+
+    let drift = stmt.query_map([], |row| {
+        Ok(CounterDrift {
+            post_id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+            stored: PostCounters {
+                upvotes: row.get(1)?,
+                downvotes: row.get(2)?,
+                reply_count: row.get(3)?,
+            },
+            actual: PostCounters {
+                upvotes: row.get(4)?,
+                downvotes: row.get(5)?,
+                reply_count: row.get(6)?,
+            },
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to check post counters")?;
+
+    Ok(drift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_counters_changed_outside_the_triggers() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let conn = db.pool.get()?;
+        let user_id = Uuid::new_v4().to_string();
+        let post_id = Uuid::new_v4();
+        conn.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, 'counter', ?, 1)",
+            (&user_id, "2024-01-01T00:00:00Z"),
+        )?;
+        conn.execute(
+            "INSERT INTO posts (id, author_id, content, created_at) VALUES (?, ?, 'hi', ?)",
+            (post_id.to_string(), &user_id, "2024-01-01T00:00:00Z"),
+        )?;
+        assert!(find_counter_drift(&conn)?.is_empty());
+
+        conn.execute("UPDATE posts SET upvotes = 42 WHERE id = ?", [post_id.to_string()])?;
+        let drift = find_counter_drift(&conn)?;
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].post_id, post_id);
+        assert_eq!(drift[0].stored.upvotes, 42);
+        assert_eq!(drift[0].actual.upvotes, 0);
+        Ok(())
+    }
+}
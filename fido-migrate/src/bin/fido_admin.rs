@@ -0,0 +1,371 @@
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use fido_server::db::{
+    backup::{self, BackupSummary},
+    repositories::{AuditEntry, AuditRepository, UserRepository},
+    Database,
+};
+use fido_server::migration;
+use rusqlite::{backup::Backup, Connection, OpenFlags};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Fido Admin Utility
+///
+/// Takes online backups of a Fido database and restores them. Backups use
+/// SQLite's backup API, so they are safe to take while the server is running.
+/// Also exports the log of admin actions, and accounts for moving them to
+/// another instance.
+#[derive(Parser, Debug)]
+#[command(name = "fido-admin")]
+#[command(about = "Back up and restore a Fido database, export its audit log and accounts", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Snapshot the database to a new file
+    Backup {
+        /// Path to the SQLite database file
+        #[arg(short, long, default_value = "./fido.db")]
+        database: PathBuf,
+
+        /// Where to write the backup (default: backups/fido-backup-<timestamp>.db)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Replace the database with a backup. Stop the server first.
+    Restore {
+        /// Backup file to restore from
+        backup: PathBuf,
+
+        /// Path to the SQLite database file to replace
+        #[arg(short, long, default_value = "./fido.db")]
+        database: PathBuf,
+
+        /// Only verify the backup, without restoring it
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+    /// Export the audit log of admin actions, newest first
+    Audit {
+        /// Path to the SQLite database file
+        #[arg(short, long, default_value = "./fido.db")]
+        database: PathBuf,
+
+        /// Only entries for this action, e.g. badge.grant
+        #[arg(short, long)]
+        action: Option<String>,
+
+        #[arg(short, long, value_enum, default_value_t = AuditFormat::Json)]
+        format: AuditFormat,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Export an account for importing on another instance with
+    /// `fido-import --format fido`
+    ExportAccount {
+        /// Username to export
+        user: String,
+
+        /// Path to the SQLite database file
+        #[arg(short, long, default_value = "./fido.db")]
+        database: PathBuf,
+
+        /// Where to write the export (default: fido-account-<user>.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Also mark the profile here as moved to this handle or URL
+        #[arg(long)]
+        moved_to: Option<String>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum AuditFormat {
+    Json,
+    Csv,
+}
+
+fn main() -> Result<()> {
+    match Args::parse().command {
+        Command::Backup { database, output } => run_backup(database, output),
+        Command::Restore {
+            backup,
+            database,
+            dry_run,
+            yes,
+        } => run_restore(backup, database, dry_run, yes),
+        Command::Audit {
+            database,
+            action,
+            format,
+            output,
+        } => run_audit(database, action, format, output),
+        Command::ExportAccount {
+            user,
+            database,
+            output,
+            moved_to,
+        } => run_export_account(user, database, output, moved_to),
+    }
+}
+
+fn run_backup(database: PathBuf, output: Option<PathBuf>) -> Result<()> {
+    if !database.exists() {
+        bail!("Database not found: {}", database.display());
+    }
+    let target = output.unwrap_or_else(|| {
+        PathBuf::from("backups").join(format!(
+            "fido-backup-{}.db",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+
+    println!("Backing up {} to {}...", database.display(), target.display());
+    let source = Connection::open_with_flags(&database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open {}", database.display()))?;
+    let summary = backup::backup_to(&source, &target)?;
+
+    println!(
+        "✓ Backup complete: {} ({} bytes)",
+        summary.path.display(),
+        summary.bytes
+    );
+    Ok(())
+}
+
+fn run_restore(backup_path: PathBuf, database: PathBuf, dry_run: bool, yes: bool) -> Result<()> {
+    println!("Verifying {}...", backup_path.display());
+    backup::verify(&backup_path).context("Backup failed verification")?;
+    println!("✓ Backup passed the integrity check");
+
+    if dry_run {
+        println!("Dry run - {} was not changed.", database.display());
+        return Ok(());
+    }
+
+    if !yes {
+        println!(
+            "This will replace {} with {}.",
+            database.display(),
+            backup_path.display()
+        );
+        println!("Make sure the Fido server is stopped first.");
+        println!("Do you want to continue? (y/N): ");
+
+        let mut input = String::new();
+        std::io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        let input = input.trim().to_lowercase();
+        if input != "y" && input != "yes" {
+            println!("Restore cancelled.");
+            return Ok(());
+        }
+    }
+
+    let safety_copy = restore_from(&backup_path, &database)?;
+    if let Some(copy) = safety_copy {
+        println!("✓ Previous database saved to {}", copy.path.display());
+    }
+    println!("✓ Restored {} from {}", database.display(), backup_path.display());
+    Ok(())
+}
+
+/// Replace the database at `target` with the contents of `backup_path`.
+///
+/// The backup is verified first. An existing target is snapshotted to
+/// `<target>.pre-restore-<timestamp>` before being overwritten, so a restore
+/// can itself be undone. Stop the server before restoring: open connections
+/// would keep serving their cached view of the old data.
+fn restore_from(backup_path: &Path, target: &Path) -> Result<Option<BackupSummary>> {
+    if !backup_path.exists() {
+        bail!("Backup file not found: {}", backup_path.display());
+    }
+    if backup_path.canonicalize()? == target.canonicalize().unwrap_or_default() {
+        bail!("Backup and target are the same file");
+    }
+    backup::verify(backup_path).context("Refusing to restore from an invalid backup")?;
+
+    let safety_copy = if target.exists() {
+        let current = Connection::open(target)
+            .with_context(|| format!("Failed to open {}", target.display()))?;
+        let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+        let mut name = target.as_os_str().to_owned();
+        name.push(format!(".pre-restore-{}", stamp));
+        let summary = backup::backup_to(&current, Path::new(&name))
+            .context("Failed to snapshot the current database")?;
+        Some(summary)
+    } else {
+        None
+    };
+
+    let source = Connection::open_with_flags(backup_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut dest = Connection::open(target)
+        .with_context(|| format!("Failed to open {}", target.display()))?;
+    Backup::new(&source, &mut dest)?
+        .run_to_completion(backup::PAGES_PER_STEP, backup::STEP_PAUSE, None)
+        .context("SQLite restore failed")?;
+
+    Ok(safety_copy)
+}
+
+fn run_audit(
+    database: PathBuf,
+    action: Option<String>,
+    format: AuditFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    if !database.exists() {
+        bail!("Database not found: {}", database.display());
+    }
+    let conn = Connection::open_with_flags(&database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open {}", database.display()))?;
+    let entries = AuditRepository::list_from(&conn, action.as_deref(), None, None)?;
+
+    let rendered = match format {
+        AuditFormat::Json => serde_json::to_string_pretty(&entries)? + "\n",
+        AuditFormat::Csv => audit_csv(&entries),
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("✓ Exported {} audit entries to {}", entries.len(), path.display());
+        }
+        None => std::io::stdout().write_all(rendered.as_bytes())?,
+    }
+    Ok(())
+}
+
+fn run_export_account(
+    username: String,
+    database: PathBuf,
+    output: Option<PathBuf>,
+    moved_to: Option<String>,
+) -> Result<()> {
+    if !database.exists() {
+        anyhow::bail!("Database not found: {}", database.display());
+    }
+    let db = Database::new(&database).context("Failed to open database connection")?;
+    let users = UserRepository::new(db.pool.clone());
+    let user = users
+        .get_by_username(&username)?
+        .with_context(|| format!("No Fido user named {}", username))?;
+
+    let export = migration::export_account(&db.pool, &user)?;
+    let target = output.unwrap_or_else(|| PathBuf::from(format!("fido-account-{}.json", user.username)));
+    std::fs::write(&target, serde_json::to_string_pretty(&export)? + "\n")
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    println!(
+        "✓ Exported @{}: {} posts, {} follows to {}",
+        user.username,
+        export.posts.len(),
+        export.follows.len(),
+        target.display()
+    );
+
+    if let Some(moved_to) = moved_to {
+        users.set_moved_to(&user.id, Some(moved_to.trim()))?;
+        println!("✓ Profile now points to {}", moved_to.trim());
+    }
+    Ok(())
+}
+
+fn audit_csv(entries: &[AuditEntry]) -> String {
+    let field = |value: &str| {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    };
+    let mut csv = String::from("id,created_at,actor_id,actor_username,action,target,reason\n");
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            entry.id,
+            entry.created_at.to_rfc3339(),
+            entry.actor_id,
+            field(&entry.actor_username),
+            field(&entry.action),
+            field(&entry.target),
+            field(entry.reason.as_deref().unwrap_or("")),
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fido_server::db::Database;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fido-restore-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn user_count(path: &Path) -> i64 {
+        Connection::open(path)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_backup_and_restore_round_trip() -> Result<()> {
+        let dir = temp_dir();
+        let live_path = dir.join("fido.db");
+        let db = Database::new(&live_path)?;
+        db.initialize()?;
+        let insert_user = |name: &str| {
+            db.pool.get().unwrap().execute(
+                "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, 0)",
+                (uuid::Uuid::new_v4().to_string(), name, "2024-01-01T00:00:00Z"),
+            )
+        };
+        insert_user("before_backup")?;
+        let users_at_backup = user_count(&live_path);
+
+        let backup_path = dir.join("snapshot.db");
+        backup::backup_to(&*db.pool.get()?, &backup_path)?;
+
+        insert_user("after_backup")?;
+        drop(db);
+
+        let safety = restore_from(&backup_path, &live_path)?.expect("existing db is kept");
+        assert_eq!(user_count(&live_path), users_at_backup);
+        assert_eq!(user_count(&safety.path), users_at_backup + 1);
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_rejects_files_that_arent_fido_databases() -> Result<()> {
+        let dir = temp_dir();
+        let bogus = dir.join("bogus.db");
+        Connection::open(&bogus)?.execute_batch("CREATE TABLE other (id INTEGER);")?;
+        let target = dir.join("fido.db");
+
+        let err = restore_from(&bogus, &target).unwrap_err();
+        assert!(format!("{:#}", err).contains("missing the users table"));
+        assert!(!target.exists());
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
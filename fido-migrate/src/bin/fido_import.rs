@@ -0,0 +1,629 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::{Parser, ValueEnum};
+use fido_server::db::{
+    repositories::{FriendRepository, HashtagRepository, PostRepository, UserRepository},
+    Database,
+};
+use fido_server::hashtag::extract_hashtags;
+use fido_server::migration;
+use fido_server::sanitize::{sanitize_content, ContentKind};
+use fido_types::{AccountExport, Post, ReplyPolicy, User};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+use uuid::Uuid;
+
+/// Fido Archive Import Utility
+///
+/// Imports a Mastodon export or Twitter archive zip into an existing Fido
+/// user's account: statuses become posts (keeping their timestamps and
+/// self-reply threads) and follows become follow records where the account
+/// exists on this instance. Also takes a Fido account export (`.json`, from
+/// `fido-admin export-account` or GET /account/export) for moving between
+/// instances.
+#[derive(Parser, Debug)]
+#[command(name = "fido-import")]
+#[command(about = "Import a Mastodon, Twitter or Fido archive into Fido", long_about = None)]
+struct Args {
+    /// Path to the archive zip, or a Fido account export (.json)
+    archive: String,
+
+    /// Fido username to import into (must already exist)
+    #[arg(short, long)]
+    user: String,
+
+    /// Path to the SQLite database file
+    #[arg(short, long, default_value = "./fido.db")]
+    database: String,
+
+    /// Archive format (detected from the zip contents by default)
+    #[arg(short, long, value_enum)]
+    format: Option<ArchiveFormat>,
+
+    /// Don't import follows
+    #[arg(long)]
+    skip_follows: bool,
+
+    /// Perform a dry run without making changes
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    yes: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ArchiveFormat {
+    Mastodon,
+    Twitter,
+    /// An account exported from another Fido instance
+    Fido,
+}
+
+/// A status from the archive, before it is mapped to a Fido post
+#[derive(Debug, Clone, PartialEq)]
+struct ImportedStatus {
+    /// Id of the status in the source archive
+    source_id: String,
+    content: String,
+    created_at: DateTime<Utc>,
+    /// Source id of the status this one replies to, if any
+    in_reply_to: Option<String>,
+}
+
+/// Everything read out of an archive
+#[derive(Debug, Default)]
+struct ArchiveData {
+    statuses: Vec<ImportedStatus>,
+    /// Usernames (without the instance part) the archive owner follows
+    follows: Vec<String>,
+    /// Follows that can't be matched because the archive has no usernames
+    unmatchable_follows: usize,
+}
+
+/// Statistics collected during import
+#[derive(Debug, Default)]
+struct ImportStats {
+    posts_imported: usize,
+    replies_threaded: usize,
+    duplicates_skipped: usize,
+    follows_imported: usize,
+    follows_unmatched: usize,
+    errors: Vec<String>,
+}
+
+/// Read a file from the zip, if present
+fn read_entry(archive: &mut zip::ZipArchive<std::fs::File>, name: &str) -> Result<Option<String>> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", name)),
+    };
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .with_context(|| format!("Failed to read {}", name))?;
+    Ok(Some(contents))
+}
+
+fn detect_format(archive: &zip::ZipArchive<std::fs::File>) -> Option<ArchiveFormat> {
+    let names: Vec<&str> = archive.file_names().collect();
+    if names.contains(&"outbox.json") {
+        Some(ArchiveFormat::Mastodon)
+    } else if names.iter().any(|name| *name == "data/tweets.js" || *name == "data/tweet.js") {
+        Some(ArchiveFormat::Twitter)
+    } else {
+        None
+    }
+}
+
+fn load_archive(path: &str, format: Option<ArchiveFormat>) -> Result<(ArchiveFormat, ArchiveData)> {
+    let file = std::fs::File::open(path).with_context(|| format!("Archive not found: {}", path))?;
+    let mut archive = zip::ZipArchive::new(file).context("Not a zip archive")?;
+
+    let format = match format.or_else(|| detect_format(&archive)) {
+        Some(format) => format,
+        None => anyhow::bail!(
+            "Couldn't tell the archive format (no outbox.json or data/tweets.js); pass --format"
+        ),
+    };
+
+    let data = match format {
+        ArchiveFormat::Fido => anyhow::bail!("Fido account exports are JSON files, not zips"),
+        ArchiveFormat::Mastodon => {
+            let outbox = read_entry(&mut archive, "outbox.json")?
+                .context("Mastodon export has no outbox.json")?;
+            let following = read_entry(&mut archive, "following_accounts.csv")?.unwrap_or_default();
+            ArchiveData {
+                statuses: parse_mastodon_outbox(&outbox)?,
+                follows: parse_mastodon_following(&following),
+                unmatchable_follows: 0,
+            }
+        }
+        ArchiveFormat::Twitter => {
+            let tweets = match read_entry(&mut archive, "data/tweets.js")? {
+                Some(tweets) => tweets,
+                None => read_entry(&mut archive, "data/tweet.js")?
+                    .context("Twitter archive has no data/tweets.js")?,
+            };
+            let following = read_entry(&mut archive, "data/following.js")?;
+            ArchiveData {
+                statuses: parse_twitter_tweets(&tweets)?,
+                follows: Vec::new(),
+                unmatchable_follows: match following {
+                    Some(following) => parse_twitter_js(&following)?.len(),
+                    None => 0,
+                },
+            }
+        }
+    };
+    Ok((format, data))
+}
+
+/// Turn status HTML into plain text: paragraphs and `<br>` become newlines,
+/// other tags are dropped and common entities decoded
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim().to_lowercase();
+        if tag.starts_with("br") {
+            text.push('\n');
+        } else if tag == "/p" {
+            text.push_str("\n\n");
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    decode_entities(text.trim_end())
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Statuses from a Mastodon `outbox.json`; boosts are skipped
+fn parse_mastodon_outbox(json: &str) -> Result<Vec<ImportedStatus>> {
+    let outbox: Value = serde_json::from_str(json).context("outbox.json is not valid JSON")?;
+    let items = outbox["orderedItems"]
+        .as_array()
+        .context("outbox.json has no orderedItems")?;
+
+    Ok(items
+        .iter()
+        .filter(|item| item["type"] == "Create")
+        .filter_map(|item| {
+            let object = &item["object"];
+            let published = object["published"].as_str().or_else(|| item["published"].as_str())?;
+            Some(ImportedStatus {
+                source_id: object["id"].as_str()?.to_string(),
+                content: html_to_text(object["content"].as_str()?),
+                created_at: DateTime::parse_from_rfc3339(published).ok()?.with_timezone(&Utc),
+                in_reply_to: object["inReplyTo"].as_str().map(str::to_string),
+            })
+        })
+        .collect())
+}
+
+/// Usernames from a Mastodon `following_accounts.csv` (`user@instance,...`)
+fn parse_mastodon_following(csv: &str) -> Vec<String> {
+    csv.lines()
+        .skip(1)
+        .filter_map(|line| line.split(',').next())
+        .map(|account| account.trim().trim_start_matches('@'))
+        .filter_map(|account| account.split('@').next())
+        .filter(|username| !username.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The JSON array inside a Twitter archive `.js` file
+/// (`window.YTD.tweets.part0 = [ ... ]`)
+fn parse_twitter_js(js: &str) -> Result<Vec<Value>> {
+    let start = js.find('[').context("Archive file has no JSON array")?;
+    serde_json::from_str(&js[start..]).context("Archive file is not valid JSON")
+}
+
+/// Tweets from `data/tweets.js`; retweets are skipped
+fn parse_twitter_tweets(js: &str) -> Result<Vec<ImportedStatus>> {
+    Ok(parse_twitter_js(js)?
+        .iter()
+        .filter_map(|entry| {
+            let tweet = entry.get("tweet").unwrap_or(entry);
+            let text = tweet["full_text"].as_str().or_else(|| tweet["text"].as_str())?;
+            if text.starts_with("RT @") {
+                return None;
+            }
+            let created_at =
+                DateTime::parse_from_str(tweet["created_at"].as_str()?, "%a %b %d %H:%M:%S %z %Y")
+                    .ok()?
+                    .with_timezone(&Utc);
+            Some(ImportedStatus {
+                source_id: tweet["id_str"].as_str()?.to_string(),
+                content: decode_entities(text),
+                created_at,
+                in_reply_to: tweet["in_reply_to_status_id_str"].as_str().map(str::to_string),
+            })
+        })
+        .collect())
+}
+
+/// Map statuses to posts, oldest first, so a reply's parent is always mapped
+/// before it. Replies to statuses outside the archive become top-level posts.
+fn build_posts(
+    mut statuses: Vec<ImportedStatus>,
+    author_id: Uuid,
+    author_username: &str,
+    stats: &mut ImportStats,
+) -> Vec<Post> {
+    statuses.sort_by_key(|status| status.created_at);
+    let mut ids: HashMap<String, Uuid> = HashMap::new();
+    let mut posts = Vec::new();
+
+    for status in statuses {
+        let content = match sanitize_content(ContentKind::Post, &status.content) {
+            Ok(content) => content,
+            Err(e) => {
+                stats.errors.push(format!("Skipped status {}: {}", status.source_id, e));
+                continue;
+            }
+        };
+        let parent_post_id = status
+            .in_reply_to
+            .as_ref()
+            .and_then(|parent| ids.get(parent))
+            .copied();
+        if parent_post_id.is_some() {
+            stats.replies_threaded += 1;
+        }
+
+        let id = Uuid::new_v4();
+        ids.insert(status.source_id, id);
+        posts.push(Post {
+            id,
+            author_id,
+            author_username: author_username.to_string(),
+            content,
+            created_at: status.created_at,
+            upvotes: 0,
+            downvotes: 0,
+            hashtags: Vec::new(),
+            user_vote: None,
+            parent_post_id,
+            reply_count: 0,
+            reply_to_user_id: parent_post_id.map(|_| author_id),
+            reply_to_username: parent_post_id.map(|_| author_username.to_string()),
+            is_deleted: false,
+            author_badges: Vec::new(),
+            author_status: None,
+            kind: None,
+            is_anonymous: false,
+            reply_policy: ReplyPolicy::Everyone,
+            can_reply: true,
+            is_accepted: false,
+            author_karma: 0,
+        });
+    }
+    posts
+}
+
+/// An existing post by this user with the same content and timestamp, which
+/// makes re-running an import safe
+fn find_imported(db: &Database, post: &Post) -> Result<Option<Uuid>> {
+    let conn = db.pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT id FROM posts WHERE author_id = ? AND created_at = ? AND content = ? LIMIT 1",
+    )?;
+    let mut rows = stmt.query((post.author_id.to_string(), post.created_at.to_rfc3339(), &post.content))?;
+    match rows.next()? {
+        Some(row) => Ok(Uuid::parse_str(&row.get::<_, String>(0)?).ok()),
+        None => Ok(None),
+    }
+}
+
+fn import_posts(db: &Database, posts: &[Post], stats: &mut ImportStats, dry_run: bool) -> Result<()> {
+    let post_repo = PostRepository::new(db.pool.clone());
+    let hashtag_repo = HashtagRepository::new(db.pool.clone());
+    // Parents skipped as duplicates still exist under their earlier id
+    let mut remapped: HashMap<Uuid, Uuid> = HashMap::new();
+
+    for post in posts {
+        if let Some(existing) = find_imported(db, post)? {
+            remapped.insert(post.id, existing);
+            stats.duplicates_skipped += 1;
+            continue;
+        }
+        if !dry_run {
+            let mut post = post.clone();
+            post.parent_post_id = post
+                .parent_post_id
+                .map(|parent| remapped.get(&parent).copied().unwrap_or(parent));
+            post_repo
+                .create(&post)
+                .with_context(|| format!("Failed to import post from {}", post.created_at))?;
+            hashtag_repo
+                .store_hashtags(&post.id, &extract_hashtags(&post.content))
+                .with_context(|| format!("Failed to store hashtags for post {}", post.id))?;
+        }
+        stats.posts_imported += 1;
+    }
+
+    if !dry_run {
+        post_repo.repair_counters().context("Failed to update reply counts")?;
+    }
+    Ok(())
+}
+
+fn import_follows(
+    db: &Database,
+    user_id: Uuid,
+    usernames: &[String],
+    stats: &mut ImportStats,
+    dry_run: bool,
+) -> Result<()> {
+    let user_repo = UserRepository::new(db.pool.clone());
+    let friend_repo = FriendRepository::new(db.pool.clone());
+
+    for username in usernames {
+        match user_repo.get_by_username(username)? {
+            Some(user) if user.id != user_id => {
+                if !dry_run {
+                    friend_repo.follow_user(&user_id, &user.id)?;
+                }
+                stats.follows_imported += 1;
+            }
+            _ => stats.follows_unmatched += 1,
+        }
+    }
+    Ok(())
+}
+
+/// Display import statistics in a formatted way
+fn display_stats(stats: &ImportStats, dry_run: bool) {
+    println!();
+    println!("Import Summary");
+    println!("==============");
+    println!();
+    println!("Posts imported: {}", stats.posts_imported);
+    println!("Replies threaded: {}", stats.replies_threaded);
+    println!("Already imported (skipped): {}", stats.duplicates_skipped);
+    println!("Follows imported: {}", stats.follows_imported);
+    println!("Follows with no matching Fido user: {}", stats.follows_unmatched);
+
+    if !stats.errors.is_empty() {
+        println!();
+        println!("Statuses skipped: {}", stats.errors.len());
+        for (i, error) in stats.errors.iter().enumerate() {
+            println!("  {}. {}", i + 1, error);
+        }
+    }
+
+    println!();
+    if dry_run {
+        println!("This was a dry run - no changes were made to the database.");
+    } else {
+        println!("Import completed successfully!");
+    }
+}
+
+fn open_database(args: &Args) -> Result<(Database, User)> {
+    if !std::path::Path::new(&args.database).exists() {
+        anyhow::bail!("Database file not found: {}", args.database);
+    }
+    let db = Database::new(&args.database).context("Failed to open database connection")?;
+    let user = UserRepository::new(db.pool.clone())
+        .get_by_username(&args.user)?
+        .with_context(|| format!("No Fido user named {}", args.user))?;
+    Ok((db, user))
+}
+
+/// Ask before writing; `true` to go ahead
+fn confirm(prompt: &str) -> Result<bool> {
+    println!("{}", prompt);
+    println!("Do you want to continue? (y/N): ");
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .context("Failed to read user input")?;
+
+    let input = input.trim().to_lowercase();
+    Ok(input == "y" || input == "yes")
+}
+
+/// A Fido account export goes through the same import as
+/// POST /account/import, which also copies the bio
+fn run_fido_import(args: &Args) -> Result<()> {
+    let json = std::fs::read_to_string(&args.archive)
+        .with_context(|| format!("Archive not found: {}", args.archive))?;
+    let mut export: AccountExport =
+        serde_json::from_str(&json).context("Not a Fido account export")?;
+    println!(
+        "Read Fido export of @{}: {} posts, {} follows",
+        export.username,
+        export.posts.len(),
+        export.follows.len()
+    );
+
+    let (db, user) = open_database(args)?;
+    if args.skip_follows {
+        export.follows.clear();
+    }
+    if args.dry_run {
+        println!();
+        println!("This was a dry run - no changes were made to the database.");
+        return Ok(());
+    }
+    if !args.yes
+        && !confirm(&format!(
+            "This will import @{}'s account as @{}.",
+            export.username, user.username
+        ))?
+    {
+        println!("Import cancelled.");
+        return Ok(());
+    }
+
+    let summary = migration::import_account(&db.pool, &user, &export)?;
+    let stats = ImportStats {
+        posts_imported: summary.posts_imported,
+        replies_threaded: summary.replies_threaded,
+        duplicates_skipped: summary.duplicates_skipped,
+        follows_imported: summary.follows_imported,
+        follows_unmatched: summary.follows_unmatched,
+        errors: Vec::new(),
+    };
+    display_stats(&stats, false);
+    if summary.posts_rejected > 0 {
+        println!("Posts rejected by this instance's limits: {}", summary.posts_rejected);
+    }
+    if summary.bio_imported {
+        println!("Bio copied from the export.");
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    println!("Fido Archive Import Utility");
+    println!("===========================");
+    println!();
+    println!("Archive: {}", args.archive);
+    println!("Database: {}", args.database);
+    println!("Dry run: {}", args.dry_run);
+    println!();
+
+    if args.format == Some(ArchiveFormat::Fido)
+        || (args.format.is_none() && args.archive.ends_with(".json"))
+    {
+        return run_fido_import(&args);
+    }
+
+    let (format, data) = load_archive(&args.archive, args.format)?;
+    println!(
+        "Read {:?} archive: {} statuses, {} follows",
+        format,
+        data.statuses.len(),
+        data.follows.len() + data.unmatchable_follows
+    );
+
+    let (db, user) = open_database(&args)?;
+
+    let mut stats = ImportStats {
+        follows_unmatched: data.unmatchable_follows,
+        ..Default::default()
+    };
+    let posts = build_posts(data.statuses, user.id, &user.username, &mut stats);
+
+    // Show confirmation prompt unless --yes flag is provided
+    if !args.yes
+        && !args.dry_run
+        && !confirm(&format!("This will import {} posts as @{}.", posts.len(), user.username))?
+    {
+        println!("Import cancelled.");
+        return Ok(());
+    }
+
+    import_posts(&db, &posts, &mut stats, args.dry_run)?;
+    if !args.skip_follows {
+        import_follows(&db, user.id, &data.follows, &mut stats, args.dry_run)?;
+    }
+
+    display_stats(&stats, args.dry_run);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mastodon_outbox_keeps_statuses_and_skips_boosts() {
+        let outbox = r#"{"orderedItems": [
+            {"type": "Create", "object": {
+                "id": "https://m.example/statuses/1",
+                "published": "2023-05-01T10:00:00Z",
+                "content": "<p>Hello &amp; welcome</p><p>line<br>two</p>",
+                "inReplyTo": null}},
+            {"type": "Announce", "object": "https://elsewhere/statuses/9"},
+            {"type": "Create", "object": {
+                "id": "https://m.example/statuses/2",
+                "published": "2023-05-01T11:00:00Z",
+                "content": "<p>Follow-up</p>",
+                "inReplyTo": "https://m.example/statuses/1"}}
+        ]}"#;
+
+        let statuses = parse_mastodon_outbox(outbox).unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].content, "Hello & welcome\n\nline\ntwo");
+        assert_eq!(
+            statuses[1].in_reply_to.as_deref(),
+            Some("https://m.example/statuses/1")
+        );
+        assert_eq!(
+            parse_mastodon_following("Account address,Show boosts\nalice@m.example,true\n@bob@x.test,false\n"),
+            vec!["alice", "bob"]
+        );
+    }
+
+    #[test]
+    fn test_parse_twitter_tweets_reads_ytd_wrapper() {
+        let js = r#"window.YTD.tweets.part0 = [
+            {"tweet": {"id_str": "10", "full_text": "a &lt;b&gt; tweet",
+                "created_at": "Wed Oct 10 20:19:24 +0000 2018"}},
+            {"tweet": {"id_str": "11", "full_text": "RT @someone: boosted",
+                "created_at": "Wed Oct 10 20:20:24 +0000 2018"}}
+        ]"#;
+
+        let tweets = parse_twitter_tweets(js).unwrap();
+        assert_eq!(tweets.len(), 1);
+        assert_eq!(tweets[0].content, "a <b> tweet");
+        assert_eq!(tweets[0].created_at.to_rfc3339(), "2018-10-10T20:19:24+00:00");
+    }
+
+    #[test]
+    fn test_build_posts_threads_self_replies_and_skips_long_statuses() {
+        let at = |minute: u32| {
+            DateTime::parse_from_rfc3339(&format!("2023-05-01T10:{:02}:00Z", minute))
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let status = |id: &str, minute, reply: Option<&str>, content: String| ImportedStatus {
+            source_id: id.to_string(),
+            content,
+            created_at: at(minute),
+            in_reply_to: reply.map(str::to_string),
+        };
+        let statuses = vec![
+            status("2", 2, Some("1"), "reply".to_string()),
+            status("1", 1, None, "root".to_string()),
+            status("3", 3, Some("elsewhere"), "reply to a stranger".to_string()),
+            status("4", 4, None, "x".repeat(400)),
+        ];
+
+        let author = Uuid::new_v4();
+        let mut stats = ImportStats::default();
+        let posts = build_posts(statuses, author, "alice", &mut stats);
+
+        assert_eq!(posts.len(), 3);
+        assert_eq!(posts[0].content, "root");
+        assert_eq!(posts[1].parent_post_id, Some(posts[0].id));
+        assert_eq!(posts[1].reply_to_user_id, Some(author));
+        assert_eq!(posts[2].parent_post_id, None);
+        assert_eq!(stats.replies_threaded, 1);
+        assert_eq!(stats.errors.len(), 1);
+    }
+}
@@ -0,0 +1,246 @@
+//! Database health reports and compaction for maintenance tools.
+//!
+//! Sizes come from SQLite's `dbstat` table, so they count whole pages the
+//! way the file does. SQLite keeps no record of which indexes queries use;
+//! what it can say after `ANALYZE` is how selective each one is, and an
+//! index matching dozens of rows per key is rarely worth its pages.
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+/// Whether a sized object is a table or one of its indexes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Table,
+    Index,
+}
+
+/// Space taken by one table or index
+#[derive(Debug, Clone)]
+pub struct ObjectSize {
+    pub name: String,
+    pub kind: ObjectKind,
+    /// The table itself, or the table an index belongs to
+    pub table: String,
+    pub bytes: u64,
+    /// Bytes of its pages holding nothing
+    pub unused_bytes: u64,
+    /// Rows in a table; `None` for indexes
+    pub rows: Option<u64>,
+    /// Average rows matching one key of an index, from the last `ANALYZE`;
+    /// `None` for tables and indexes not analyzed yet
+    pub rows_per_key: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    pub page_size: u64,
+    pub page_count: u64,
+    /// Pages left empty by deletes; `VACUUM` gives them back
+    pub free_pages: u64,
+    /// Largest first
+    pub objects: Vec<ObjectSize>,
+    /// Full-text search tables, which `maintain` can rebuild
+    pub fts_tables: Vec<String>,
+}
+
+impl HealthReport {
+    pub fn file_bytes(&self) -> u64 {
+        self.page_size * self.page_count
+    }
+
+    pub fn free_bytes(&self) -> u64 {
+        self.page_size * self.free_pages
+    }
+
+    /// Share of the file that is free pages or unused space in pages, 0–100
+    pub fn fragmentation_percent(&self) -> f64 {
+        if self.page_count == 0 {
+            return 0.0;
+        }
+        let unused: u64 = self.objects.iter().map(|o| o.unused_bytes).sum();
+        (self.free_bytes() + unused) as f64 * 100.0 / self.file_bytes() as f64
+    }
+}
+
+/// What `maintain` did
+#[derive(Debug, Clone)]
+pub struct MaintenanceSummary {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    /// Full-text search tables rebuilt, when asked to
+    pub fts_rebuilt: Vec<String>,
+}
+
+fn pragma(conn: &Connection, name: &str) -> Result<u64> {
+    conn.query_row(&format!("PRAGMA {}", name), [], |row| row.get::<_, i64>(0))
+        .map(|value| value as u64)
+        .with_context(|| format!("Failed to read PRAGMA {}", name))
+}
+
+fn fts_tables(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master
+         WHERE type = 'table' AND sql LIKE 'CREATE VIRTUAL TABLE%USING fts%'
+         ORDER BY name",
+    )?;
+    let tables = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to list full-text search tables")?;
+    Ok(tables)
+}
+
+/// Average rows per full key, the last number of a `sqlite_stat1` entry
+fn rows_per_key(conn: &Connection, index: &str) -> Result<Option<u64>> {
+    let analyzed: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = 'sqlite_stat1')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !analyzed {
+        return Ok(None);
+    }
+    let stat: Option<String> = conn
+        .query_row("SELECT stat FROM sqlite_stat1 WHERE idx = ?", [index], |row| row.get(0))
+        .ok();
+    Ok(stat.and_then(|stat| {
+        stat.split_whitespace()
+            .take_while(|field| field.parse::<u64>().is_ok())
+            .last()
+            .and_then(|field| field.parse().ok())
+    }))
+}
+
+/// Size every table and index, and count the free space
+pub fn report(conn: &Connection) -> Result<HealthReport> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.name, COALESCE(m.type, 'table'), COALESCE(m.tbl_name, s.name),
+                    SUM(s.pgsize), SUM(s.unused)
+             FROM dbstat s
+             LEFT JOIN sqlite_master m ON m.name = s.name
+             GROUP BY s.name
+             ORDER BY SUM(s.pgsize) DESC, s.name",
+        )
+        .context("Failed to read table sizes (is dbstat available?)")?;
+    let sized = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)? as u64,
+                row.get::<_, i64>(4)? as u64,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to read table sizes")?;
+
+    let mut objects = Vec::new();
+    for (name, kind, table, bytes, unused_bytes) in sized {
+        let kind = if kind == "index" { ObjectKind::Index } else { ObjectKind::Table };
+        let (rows, rows_per_key) = match kind {
+            ObjectKind::Table => {
+                let count: i64 = conn.query_row(
+                    &format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")),
+                    [],
+                    |row| row.get(0),
+                )?;
+                (Some(count as u64), None)
+            }
+            ObjectKind::Index => (None, rows_per_key(conn, &name)?),
+        };
+        objects.push(ObjectSize {
+            name,
+            kind,
+            table,
+            bytes,
+            unused_bytes,
+            rows,
+            rows_per_key,
+        });
+    }
+
+    Ok(HealthReport {
+        page_size: pragma(conn, "page_size")?,
+        page_count: pragma(conn, "page_count")?,
+        free_pages: pragma(conn, "freelist_count")?,
+        objects,
+        fts_tables: fts_tables(conn)?,
+    })
+}
+
+/// Refresh the planner's statistics, optionally rebuild every index and
+/// full-text search table, then compact the file. `VACUUM` rewrites the
+/// whole database and needs as much free disk again while it runs.
+pub fn maintain(conn: &Connection, rebuild_indexes: bool) -> Result<MaintenanceSummary> {
+    let bytes_before = pragma(conn, "page_count")? * pragma(conn, "page_size")?;
+
+    let mut fts_rebuilt = Vec::new();
+    if rebuild_indexes {
+        conn.execute_batch("REINDEX").context("Failed to rebuild indexes")?;
+        for table in fts_tables(conn)? {
+            let quoted = table.replace('"', "\"\"");
+            conn.execute(&format!("INSERT INTO \"{0}\"(\"{0}\") VALUES ('rebuild')", quoted), [])
+                .with_context(|| format!("Failed to rebuild {}", table))?;
+            fts_rebuilt.push(table);
+        }
+    }
+    conn.execute_batch("ANALYZE").context("Failed to analyze the database")?;
+    conn.execute_batch("VACUUM").context("Failed to vacuum the database")?;
+
+    Ok(MaintenanceSummary {
+        bytes_before,
+        bytes_after: pragma(conn, "page_count")? * pragma(conn, "page_size")?,
+        fts_rebuilt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_sizes_tables_and_vacuum_returns_free_pages() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("fido-health-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        let conn = Connection::open(dir.join("fido.db"))?;
+        conn.execute_batch(
+            "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT, tag TEXT);
+             CREATE INDEX idx_notes_tag ON notes(tag);
+             CREATE VIRTUAL TABLE notes_fts USING fts5(body);",
+        )?;
+        for i in 0..500 {
+            conn.execute(
+                "INSERT INTO notes (body, tag) VALUES (?, ?)",
+                (format!("{:0>400}", i), if i % 2 == 0 { "even" } else { "odd" }),
+            )?;
+        }
+
+        let before = report(&conn)?;
+        let notes = before.objects.iter().find(|o| o.name == "notes").unwrap();
+        assert_eq!(notes.kind, ObjectKind::Table);
+        assert_eq!(notes.rows, Some(500));
+        let index = before.objects.iter().find(|o| o.name == "idx_notes_tag").unwrap();
+        assert_eq!(index.kind, ObjectKind::Index);
+        assert_eq!(index.table, "notes");
+        assert_eq!(index.rows_per_key, None, "not analyzed yet");
+        assert_eq!(before.fts_tables, vec!["notes_fts".to_string()]);
+
+        conn.execute("DELETE FROM notes WHERE id > 50", [])?;
+        assert!(report(&conn)?.free_pages > 0);
+
+        let summary = maintain(&conn, true)?;
+        assert!(summary.bytes_after < summary.bytes_before);
+        assert_eq!(summary.fts_rebuilt, vec!["notes_fts".to_string()]);
+        let after = report(&conn)?;
+        assert_eq!(after.free_pages, 0);
+        let index = after.objects.iter().find(|o| o.name == "idx_notes_tag").unwrap();
+        assert_eq!(index.rows_per_key, Some(25));
+
+        drop(conn);
+        let _ = std::fs::remove_dir_all(&dir);
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+//! Referential integrity checks for maintenance tools.
+//!
+//! The schema declares foreign keys, but SQLite only enforces them on
+//! connections that turn them on. The server's build does by default; the
+//! `sqlite3` shell and other tools writing the file don't, and tables
+//! created before a key was added never had it checked. Rows can therefore
+//! outlive what they point at. Each check finds one kind of such row, and
+//! repairing deletes them, which is what `ON DELETE CASCADE` would have done.
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+/// Rows listed per kind of inconsistency; the count covers all of them
+const MAX_EXAMPLES: usize = 10;
+/// Deleting a reply can orphan replies to it, so repairs go round again
+/// until nothing is left, up to the deepest thread this many levels
+const MAX_REPAIR_PASSES: usize = 64;
+
+/// A kind of row left pointing at something that no longer exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// A reply whose parent post is gone
+    OrphanedReply,
+    /// A post whose author is gone
+    PostWithoutAuthor,
+    /// A vote on a missing post or by a missing user
+    DanglingVote,
+    /// A hashtag association for a missing post
+    HashtagLinkWithoutPost,
+    /// A hashtag association for a missing hashtag
+    HashtagLinkWithoutHashtag,
+}
+
+impl IssueKind {
+    pub const ALL: [IssueKind; 5] = [
+        IssueKind::OrphanedReply,
+        IssueKind::PostWithoutAuthor,
+        IssueKind::DanglingVote,
+        IssueKind::HashtagLinkWithoutPost,
+        IssueKind::HashtagLinkWithoutHashtag,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            IssueKind::OrphanedReply => "replies whose parent post is gone",
+            IssueKind::PostWithoutAuthor => "posts whose author is gone",
+            IssueKind::DanglingVote => "votes on missing posts or by missing users",
+            IssueKind::HashtagLinkWithoutPost => "hashtag associations without a post",
+            IssueKind::HashtagLinkWithoutHashtag => "hashtag associations without a hashtag",
+        }
+    }
+
+    /// Selects a readable key for every offending row
+    fn find_sql(self) -> &'static str {
+        match self {
+            IssueKind::OrphanedReply => {
+                "SELECT id || ' (parent ' || parent_post_id || ')' FROM posts
+                 WHERE parent_post_id IS NOT NULL
+                   AND parent_post_id NOT IN (SELECT id FROM posts)"
+            }
+            IssueKind::PostWithoutAuthor => {
+                "SELECT id || ' (author ' || author_id || ')' FROM posts
+                 WHERE author_id NOT IN (SELECT id FROM users)"
+            }
+            IssueKind::DanglingVote => {
+                "SELECT 'user ' || user_id || ' on post ' || post_id FROM votes
+                 WHERE post_id NOT IN (SELECT id FROM posts)
+                    OR user_id NOT IN (SELECT id FROM users)"
+            }
+            IssueKind::HashtagLinkWithoutPost => {
+                "SELECT 'post ' || post_id || ' -> hashtag ' || hashtag_id FROM post_hashtags
+                 WHERE post_id NOT IN (SELECT id FROM posts)"
+            }
+            IssueKind::HashtagLinkWithoutHashtag => {
+                "SELECT 'post ' || post_id || ' -> hashtag ' || hashtag_id FROM post_hashtags
+                 WHERE hashtag_id NOT IN (SELECT id FROM hashtags)"
+            }
+        }
+    }
+
+    fn repair_sql(self) -> &'static str {
+        match self {
+            IssueKind::OrphanedReply => {
+                "DELETE FROM posts
+                 WHERE parent_post_id IS NOT NULL
+                   AND parent_post_id NOT IN (SELECT id FROM posts)"
+            }
+            IssueKind::PostWithoutAuthor => {
+                "DELETE FROM posts WHERE author_id NOT IN (SELECT id FROM users)"
+            }
+            IssueKind::DanglingVote => {
+                "DELETE FROM votes
+                 WHERE post_id NOT IN (SELECT id FROM posts)
+                    OR user_id NOT IN (SELECT id FROM users)"
+            }
+            IssueKind::HashtagLinkWithoutPost => {
+                "DELETE FROM post_hashtags WHERE post_id NOT IN (SELECT id FROM posts)"
+            }
+            IssueKind::HashtagLinkWithoutHashtag => {
+                "DELETE FROM post_hashtags WHERE hashtag_id NOT IN (SELECT id FROM hashtags)"
+            }
+        }
+    }
+}
+
+/// Rows found by one check
+#[derive(Debug, Clone)]
+pub struct Inconsistency {
+    pub kind: IssueKind,
+    pub count: usize,
+    /// The first few offending rows, for the report
+    pub examples: Vec<String>,
+}
+
+/// Run every check, returning only the kinds that found something
+pub fn check(conn: &Connection) -> Result<Vec<Inconsistency>> {
+    let mut found = Vec::new();
+    for kind in IssueKind::ALL {
+        let mut stmt = conn
+            .prepare(kind.find_sql())
+            .with_context(|| format!("Failed to check for {}", kind.description()))?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Failed to check for {}", kind.description()))?;
+        if !rows.is_empty() {
+            found.push(Inconsistency {
+                kind,
+                count: rows.len(),
+                examples: rows.into_iter().take(MAX_EXAMPLES).collect(),
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Delete every offending row in one transaction. Posts go before what
+/// hangs off them, so votes and hashtag associations of deleted posts are
+/// cleaned up in the same pass.
+pub fn repair(conn: &mut Connection) -> Result<()> {
+    let tx = conn.transaction().context("Failed to start repair")?;
+    for _ in 0..MAX_REPAIR_PASSES {
+        let mut removed = 0;
+        for kind in IssueKind::ALL {
+            removed += tx
+                .execute(kind.repair_sql(), [])
+                .with_context(|| format!("Failed to remove {}", kind.description()))?;
+        }
+        if removed == 0 {
+            break;
+        }
+    }
+    tx.commit().context("Failed to commit repair")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fido_server::db::Database;
+
+    fn insert_post(conn: &Connection, id: &str, author: &str, parent: Option<&str>) {
+        conn.execute(
+            "INSERT INTO posts (id, author_id, content, created_at, parent_post_id)
+             VALUES (?, ?, 'hello #rust', '2024-01-01T00:00:00Z', ?)",
+            (id, author, parent),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_dangling_rows_are_found_and_removed_with_their_replies() {
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        let mut conn = db.connection().unwrap();
+        // As if written by a tool that doesn't enforce foreign keys
+        conn.execute_batch("PRAGMA foreign_keys = OFF").unwrap();
+        conn.execute(
+            "INSERT INTO users (id, username, join_date) VALUES ('u1', 'alice', '2024-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO hashtags (id, name, created_at) VALUES ('h1', 'rust', 0)",
+            [],
+        )
+        .unwrap();
+        insert_post(&conn, "p1", "u1", None);
+        insert_post(&conn, "r1", "u1", Some("gone"));
+        insert_post(&conn, "r2", "u1", Some("r1"));
+        conn.execute(
+            "INSERT INTO votes (user_id, post_id, direction, created_at)
+             VALUES ('u1', 'r2', 'up', '2024-01-01T00:00:00Z'),
+                    ('u1', 'p1', 'up', '2024-01-01T00:00:00Z'),
+                    ('nobody', 'p1', 'down', '2024-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO post_hashtags (post_id, hashtag_id)
+             VALUES ('p1', 'h1'), ('p1', 'h2'), ('deleted', 'h1')",
+            [],
+        )
+        .unwrap();
+
+        let found = check(&conn).unwrap();
+        let count = |kind| found.iter().find(|i| i.kind == kind).map_or(0, |i| i.count);
+        assert_eq!(count(IssueKind::OrphanedReply), 1);
+        assert_eq!(count(IssueKind::PostWithoutAuthor), 0);
+        assert_eq!(count(IssueKind::DanglingVote), 1);
+        assert_eq!(count(IssueKind::HashtagLinkWithoutPost), 1);
+        assert_eq!(count(IssueKind::HashtagLinkWithoutHashtag), 1);
+
+        // r1 goes, and with it r2 (a reply to it) and r2's vote
+        conn.execute_batch("PRAGMA foreign_keys = ON").unwrap();
+        repair(&mut conn).unwrap();
+        assert!(check(&conn).unwrap().is_empty());
+        let posts: i64 = conn.query_row("SELECT COUNT(*) FROM posts", [], |row| row.get(0)).unwrap();
+        let votes: i64 = conn.query_row("SELECT COUNT(*) FROM votes", [], |row| row.get(0)).unwrap();
+        assert_eq!(posts, 1);
+        assert_eq!(votes, 1);
+    }
+}
@@ -1,18 +1,67 @@
+mod health;
+mod integrity;
+
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Args as ClapArgs, Parser, Subcommand};
 use fido_server::db::{repositories::HashtagRepository, Database};
 use fido_server::hashtag::extract_hashtags;
 use std::collections::HashSet;
 use uuid::Uuid;
 
-/// Fido Hashtag Migration Utility
+/// Fido Database Maintenance Utility
 /// 
-/// This tool backfills hashtag data for existing posts by extracting hashtags
-/// from post content and storing them in the database tables.
+/// Backfills hashtag data for existing posts (the default, with no
+/// subcommand), checks the database for rows left pointing at posts, users
+/// or hashtags that no longer exist, and reports and compacts its storage.
 #[derive(Parser, Debug)]
 #[command(name = "fido-migrate")]
-#[command(about = "Backfill hashtag data for existing Fido posts", long_about = None)]
+#[command(about = "Backfill hashtags and check the integrity of a Fido database", long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    backfill: BackfillArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Backfill hashtag data for existing posts (the default)
+    BackfillHashtags(BackfillArgs),
+    /// Check referential integrity: orphaned replies, dangling votes and
+    /// hashtag associations without posts
+    Verify {
+        /// Path to the SQLite database file
+        #[arg(short, long, default_value = "./fido.db")]
+        database: String,
+
+        /// Delete the inconsistent rows instead of only reporting them
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Report the size of every table and index, how selective each index
+    /// is, and how much of the file is free space
+    Health {
+        /// Path to the SQLite database file
+        #[arg(short, long, default_value = "./fido.db")]
+        database: String,
+    },
+    /// Run ANALYZE and VACUUM to refresh query statistics and give free
+    /// space back. Best run while the server is quiet.
+    Vacuum {
+        /// Path to the SQLite database file
+        #[arg(short, long, default_value = "./fido.db")]
+        database: String,
+
+        /// Also rebuild every index and full-text search table first
+        #[arg(long)]
+        reindex: bool,
+    },
+}
+
+#[derive(ClapArgs, Debug)]
+struct BackfillArgs {
     /// Path to the SQLite database file
     #[arg(short, long, default_value = "./fido.db")]
     database: String,
@@ -226,7 +275,148 @@ fn display_stats(stats: &MigrationStats, dry_run: bool) {
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    
+    match args.command {
+        Some(Command::BackfillHashtags(backfill)) => run_backfill(backfill),
+        Some(Command::Verify { database, fix }) => run_verify(&database, fix),
+        Some(Command::Health { database }) => run_health(&database),
+        Some(Command::Vacuum { database, reindex }) => run_vacuum(&database, reindex),
+        None => run_backfill(args.backfill),
+    }
+}
+
+/// Report rows pointing at things that no longer exist, and delete them
+/// with `--fix`
+fn run_verify(database: &str, fix: bool) -> Result<()> {
+    let db = open_existing(database)?;
+    let mut conn = db.connection()?;
+
+    let found = integrity::check(&conn)?;
+    if found.is_empty() {
+        println!("No integrity problems found.");
+        return Ok(());
+    }
+
+    println!("Found {} kind(s) of inconsistency:", found.len());
+    for issue in &found {
+        println!();
+        println!("{} {}", issue.count, issue.kind.description());
+        for example in &issue.examples {
+            println!("  {}", example);
+        }
+        if issue.count > issue.examples.len() {
+            println!("  ...and {} more", issue.count - issue.examples.len());
+        }
+    }
+
+    println!();
+    if fix {
+        integrity::repair(&mut conn)?;
+        let left = integrity::check(&conn)?;
+        if left.is_empty() {
+            println!("Removed them all.");
+        } else {
+            anyhow::bail!("{} kind(s) of inconsistency are left after repairing", left.len());
+        }
+    } else {
+        println!("Run again with --fix to delete them.");
+        // Non-zero so scripts and CI can detect problems
+        std::process::exit(2);
+    }
+
+    Ok(())
+}
+
+/// Open an existing database without creating one at a mistyped path
+fn open_existing(database: &str) -> Result<Database> {
+    if !std::path::Path::new(database).exists() {
+        anyhow::bail!("Database file not found: {}", database);
+    }
+    Database::new(database).context("Failed to open database connection")
+}
+
+/// Human-readable byte count
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn run_health(database: &str) -> Result<()> {
+    let db = open_existing(database)?;
+    let report = health::report(&*db.connection()?)?;
+
+    println!("Database: {} ({})", database, format_bytes(report.file_bytes()));
+    println!(
+        "Free pages: {} ({}), fragmentation {:.1}%",
+        report.free_pages,
+        format_bytes(report.free_bytes()),
+        report.fragmentation_percent()
+    );
+
+    println!();
+    println!("{:<40} {:>10} {:>12}", "Table", "Rows", "Size");
+    for table in report.objects.iter().filter(|o| o.kind == health::ObjectKind::Table) {
+        println!(
+            "{:<40} {:>10} {:>12}",
+            table.name,
+            table.rows.unwrap_or_default(),
+            format_bytes(table.bytes)
+        );
+    }
+
+    println!();
+    println!("{:<40} {:<24} {:>12} {:>13}", "Index", "Table", "Size", "Rows per key");
+    let mut analyzed = false;
+    for index in report.objects.iter().filter(|o| o.kind == health::ObjectKind::Index) {
+        analyzed |= index.rows_per_key.is_some();
+        println!(
+            "{:<40} {:<24} {:>12} {:>13}",
+            index.name,
+            index.table,
+            format_bytes(index.bytes),
+            index.rows_per_key.map_or("-".to_string(), |rows| rows.to_string())
+        );
+    }
+    if !analyzed {
+        println!("(run `fido-migrate vacuum` to analyze how selective the indexes are)");
+    }
+
+    if !report.fts_tables.is_empty() {
+        println!();
+        println!("Full-text search tables: {}", report.fts_tables.join(", "));
+    }
+    Ok(())
+}
+
+fn run_vacuum(database: &str, reindex: bool) -> Result<()> {
+    let db = open_existing(database)?;
+    let conn = db.connection()?;
+
+    println!("Compacting {}...", database);
+    let summary = health::maintain(&conn, reindex)?;
+    for table in &summary.fts_rebuilt {
+        println!("Rebuilt full-text search table {}", table);
+    }
+    println!(
+        "Done: {} -> {} ({} freed)",
+        format_bytes(summary.bytes_before),
+        format_bytes(summary.bytes_after),
+        format_bytes(summary.bytes_before.saturating_sub(summary.bytes_after))
+    );
+    Ok(())
+}
+
+/// Extract hashtags from every post and store the missing associations
+fn run_backfill(args: BackfillArgs) -> Result<()> {
     println!("Fido Hashtag Migration Utility");
     println!("================================");
     println!();
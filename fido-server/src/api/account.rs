@@ -0,0 +1,94 @@
+use axum::{extract::State, http::HeaderMap, Json};
+
+use crate::{
+    api::{get_user_from_headers, ApiError, ApiResult},
+    db::repositories::UserRepository,
+    migration,
+    state::AppState,
+};
+use fido_types::{
+    AccountExport, AccountImportSummary, SetAccountMovedRequest, User, ACCOUNT_EXPORT_VERSION,
+};
+
+/// Largest request body POST /account/import accepts
+pub const MAX_IMPORT_BYTES: usize = 20 * 1024 * 1024;
+
+/// Longest `moved_to` marker, e.g. a profile URL
+const MAX_MOVED_TO_CHARS: usize = 200;
+
+fn current_user(state: &AppState, headers: &HeaderMap) -> Result<User, ApiError> {
+    let user_id = get_user_from_headers(state, headers)?;
+    UserRepository::new(state.db.pool.clone())
+        .get_by_id(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("User not found".to_string()))
+}
+
+/// GET /account/export - The caller's posts, follows and bio, for moving
+/// to another instance
+pub async fn export_account(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<AccountExport>> {
+    let user = current_user(&state, &headers)?;
+    let export = migration::export_account(&state.db.pool, &user)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(export))
+}
+
+/// POST /account/import - Bring an export from another instance into the
+/// caller's account. Safe to repeat: posts already imported are skipped.
+pub async fn import_account(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(export): Json<AccountExport>,
+) -> ApiResult<Json<AccountImportSummary>> {
+    let user = current_user(&state, &headers)?;
+    if export.version > ACCOUNT_EXPORT_VERSION {
+        return Err(ApiError::BadRequest(format!(
+            "This export is version {}; this server reads up to version {}",
+            export.version, ACCOUNT_EXPORT_VERSION
+        )));
+    }
+
+    let summary = migration::import_account(&state.db.pool, &user, &export)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    tracing::info!(
+        "Imported account {} into {}: {} posts, {} follows",
+        export.username,
+        user.username,
+        summary.posts_imported,
+        summary.follows_imported
+    );
+    Ok(Json(summary))
+}
+
+/// PUT /account/moved - Point the caller's profile at their new home, or
+/// clear the marker with `{"moved_to": null}`
+pub async fn set_moved(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<SetAccountMovedRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let moved_to = payload
+        .moved_to
+        .map(|moved_to| moved_to.trim().to_string())
+        .filter(|moved_to| !moved_to.is_empty());
+    if let Some(moved_to) = &moved_to {
+        if moved_to.chars().count() > MAX_MOVED_TO_CHARS
+            || moved_to.chars().any(|c| c.is_whitespace() || c.is_control())
+        {
+            return Err(ApiError::BadRequest(format!(
+                "moved_to must be a handle or URL of at most {} characters, without spaces",
+                MAX_MOVED_TO_CHARS
+            )));
+        }
+    }
+
+    UserRepository::new(state.db.pool.clone())
+        .set_moved_to(&user_id, moved_to.as_deref())
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "moved_to": moved_to })))
+}
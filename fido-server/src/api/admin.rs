@@ -0,0 +1,401 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use fido_types::Badge;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::{
+    api::{get_user_from_headers, ApiError, ApiResult},
+    db::{
+        backup,
+        repositories::{
+            AuditEntry, AuditRepository, ModerationItem, ModerationRepository, PostRepository,
+            UserRepository,
+        },
+    },
+    profile_cache::ProfileCacheStats,
+    session::{CleanupStats, UserSessionCount},
+    state::AppState,
+};
+
+/// Comma-separated usernames allowed to administer the instance
+const ADMIN_USERS_ENV: &str = "FIDO_ADMIN_USERS";
+/// Directory online backups are written to
+const BACKUP_DIR_ENV: &str = "FIDO_BACKUP_DIR";
+const DEFAULT_BACKUP_DIR: &str = "backups";
+/// Audit log entries returned per page, and the most a caller may ask for
+const DEFAULT_AUDIT_PAGE: usize = 50;
+const MAX_AUDIT_PAGE: usize = 500;
+
+/// Whether `username` appears in the admin list
+fn is_admin<'a>(admins: impl IntoIterator<Item = &'a str>, username: &str) -> bool {
+    admins
+        .into_iter()
+        .map(str::trim)
+        .any(|admin| !admin.is_empty() && admin.eq_ignore_ascii_case(username))
+}
+
+/// Resolve the caller and make sure they're an admin; `action` completes
+/// the "Only admins can …" error
+pub(crate) fn require_admin(
+    state: &AppState,
+    headers: &HeaderMap,
+    action: &str,
+) -> Result<Uuid, ApiError> {
+    let user_id = get_user_from_headers(state, headers)?;
+
+    let user = UserRepository::new(state.db.pool.clone())
+        .get_by_id(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))?;
+
+    // Tenants list their own admins; the main community keeps the env list
+    let allowed = match &state.admins {
+        Some(admins) => is_admin(admins.iter().map(String::as_str), &user.username),
+        None => is_admin(
+            std::env::var(ADMIN_USERS_ENV).unwrap_or_default().split(','),
+            &user.username,
+        ),
+    };
+    if !allowed {
+        return Err(ApiError::Forbidden(format!("Only admins can {}", action)));
+    }
+
+    Ok(user_id)
+}
+
+/// `?reason=` accepted by every admin action and kept in the audit log
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditReason {
+    pub reason: Option<String>,
+}
+
+/// Append an admin action to the audit log before carrying it out. If the
+/// entry can't be written the request fails and the action never happens.
+/// Callers check what they can first (the user exists, the post is queued)
+/// so the log rarely holds an action that then failed.
+pub(crate) fn audit(
+    state: &AppState,
+    actor_id: &Uuid,
+    action: &str,
+    target: &str,
+    reason: &AuditReason,
+) -> Result<(), ApiError> {
+    AuditRepository::new(state.db.pool.clone())
+        .record(actor_id, action, target, reason.reason.as_deref())
+        .map_err(|e| {
+            tracing::error!("Failed to audit {} on {}: {:#}", action, target, e);
+            ApiError::InternalError(e.to_string())
+        })
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupResponse {
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// POST /admin/backup - Snapshot the live database into the backup directory
+pub async fn create_backup(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(reason): Query<AuditReason>,
+) -> ApiResult<Json<BackupResponse>> {
+    let admin_id = require_admin(&state, &headers, "create backups")?;
+
+    let dir = std::env::var(BACKUP_DIR_ENV).unwrap_or_else(|_| DEFAULT_BACKUP_DIR.to_string());
+    let target = PathBuf::from(dir).join(format!(
+        "fido-backup-{}.db",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    audit(
+        &state,
+        &admin_id,
+        "backup.create",
+        &format!("file:{}", target.display()),
+        &reason,
+    )?;
+
+    let pool = state.db.pool.clone();
+    let summary = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        backup::backup_to(&conn, &target)
+    })
+    .await
+    .map_err(|e| ApiError::InternalError(e.to_string()))?
+    .map_err(|e| ApiError::InternalError(format!("Backup failed: {:#}", e)))?;
+
+    tracing::info!("Database backup written to {}", summary.path.display());
+    Ok(Json(BackupResponse {
+        path: summary.path.display().to_string(),
+        bytes: summary.bytes,
+    }))
+}
+
+/// Parse the `:id` and `:badge` path segments of the badge routes
+fn parse_badge_path(user_id: &str, badge: &str) -> Result<(Uuid, Badge), ApiError> {
+    let user_id = Uuid::parse_str(user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
+    let badge = Badge::parse(badge).ok_or_else(|| {
+        let known: Vec<&str> = Badge::ALL.iter().map(Badge::as_str).collect();
+        ApiError::BadRequest(format!("Unknown badge; expected one of {}", known.join(", ")))
+    })?;
+    Ok((user_id, badge))
+}
+
+fn badge_target(user_id: &Uuid, badge: Badge) -> String {
+    format!("user:{} badge:{}", user_id, badge.as_str())
+}
+
+/// PUT /admin/users/:id/badges/:badge - Grant a badge, returning the user's badges
+pub async fn grant_badge(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user_id, badge)): Path<(String, String)>,
+    Query(reason): Query<AuditReason>,
+) -> ApiResult<Json<Vec<Badge>>> {
+    let admin_id = require_admin(&state, &headers, "manage badges")?;
+    let (user_id, badge) = parse_badge_path(&user_id, &badge)?;
+
+    let user_repo = UserRepository::new(state.db.pool.clone());
+    user_repo
+        .get_by_id(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+    audit(&state, &admin_id, "badge.grant", &badge_target(&user_id, badge), &reason)?;
+    user_repo
+        .grant_badge(&user_id, badge)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    let badges = user_repo
+        .get_badges(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(badges))
+}
+
+/// DELETE /admin/users/:id/badges/:badge - Revoke a badge, returning the user's badges
+pub async fn revoke_badge(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((user_id, badge)): Path<(String, String)>,
+    Query(reason): Query<AuditReason>,
+) -> ApiResult<Json<Vec<Badge>>> {
+    let admin_id = require_admin(&state, &headers, "manage badges")?;
+    let (user_id, badge) = parse_badge_path(&user_id, &badge)?;
+
+    let user_repo = UserRepository::new(state.db.pool.clone());
+    let has_badge = user_repo
+        .get_badges(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .contains(&badge);
+    if !has_badge {
+        return Err(ApiError::NotFound(format!(
+            "User doesn't have the {} badge",
+            badge.as_str()
+        )));
+    }
+    audit(&state, &admin_id, "badge.revoke", &badge_target(&user_id, badge), &reason)?;
+    user_repo
+        .revoke_badge(&user_id, badge)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    let badges = user_repo
+        .get_badges(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(badges))
+}
+
+/// GET /admin/moderation - Posts the content filters flagged or limited, oldest first
+pub async fn moderation_queue(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<ModerationItem>>> {
+    require_admin(&state, &headers, "review the moderation queue")?;
+
+    let items = ModerationRepository::new(state.db.pool.clone())
+        .queue()
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(items))
+}
+
+/// GET /admin/profile-cache - Hit and miss counts for the profile counts cache
+pub async fn profile_cache_stats(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<ProfileCacheStats>> {
+    require_admin(&state, &headers, "view cache metrics")?;
+    Ok(Json(state.profile_cache.stats()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    /// Only entries for this action, e.g. `badge.grant`
+    pub action: Option<String>,
+    /// Entries older than this id, to page back through the log
+    pub before: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// GET /admin/audit - Admin actions, newest first
+pub async fn audit_log(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AuditQuery>,
+) -> ApiResult<Json<Vec<AuditEntry>>> {
+    require_admin(&state, &headers, "read the audit log")?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_AUDIT_PAGE).clamp(1, MAX_AUDIT_PAGE);
+    let entries = AuditRepository::new(state.db.pool.clone())
+        .list(query.action.as_deref(), query.before, limit)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(entries))
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionStats {
+    pub lifetime_days: i64,
+    pub active_sessions: u64,
+    pub by_user: Vec<UserSessionCount>,
+    pub cleanup: CleanupStats,
+}
+
+/// GET /admin/sessions - Live sessions per user and expired-session sweep counts
+pub async fn session_stats(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<SessionStats>> {
+    require_admin(&state, &headers, "view session metrics")?;
+
+    let manager = &state.session_manager;
+    let by_user = manager
+        .active_sessions_by_user()
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(SessionStats {
+        lifetime_days: manager.lifetime().num_days(),
+        active_sessions: by_user.iter().map(|user| user.sessions).sum(),
+        by_user,
+        cleanup: manager.cleanup_stats(),
+    }))
+}
+
+/// POST /admin/sessions/cleanup - Sweep expired sessions now instead of
+/// waiting for the next scheduled run
+pub async fn force_session_cleanup(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(reason): Query<AuditReason>,
+) -> ApiResult<Json<CleanupStats>> {
+    let admin_id = require_admin(&state, &headers, "clean up sessions")?;
+
+    // Logged before the sweep, so how many it removed is in `cleanup` rather
+    // than the entry
+    audit(&state, &admin_id, "sessions.cleanup", "sessions:expired", &reason)?;
+    state
+        .session_manager
+        .cleanup_expired_sessions()
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(state.session_manager.cleanup_stats()))
+}
+
+fn parse_post_id(post_id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(post_id).map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))
+}
+
+/// Not found unless the post is waiting for review
+fn require_queued(moderation_repo: &ModerationRepository, post_id: &Uuid) -> Result<(), ApiError> {
+    let queued = moderation_repo
+        .is_queued(post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !queued {
+        return Err(ApiError::NotFound("Post isn't in the moderation queue".to_string()));
+    }
+    Ok(())
+}
+
+/// POST /admin/moderation/:post_id/approve - Clear a post from the queue,
+/// showing it to everyone if it was limited
+pub async fn approve_moderated_post(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+    Query(reason): Query<AuditReason>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let admin_id = require_admin(&state, &headers, "review the moderation queue")?;
+    let post_id = parse_post_id(&post_id)?;
+
+    let moderation_repo = ModerationRepository::new(state.db.pool.clone());
+    require_queued(&moderation_repo, &post_id)?;
+    audit(&state, &admin_id, "moderation.approve", &format!("post:{}", post_id), &reason)?;
+    moderation_repo
+        .approve(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Post approved"
+    })))
+}
+
+/// DELETE /admin/moderation/:post_id - Remove a queued post
+pub async fn remove_moderated_post(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+    Query(reason): Query<AuditReason>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let admin_id = require_admin(&state, &headers, "review the moderation queue")?;
+    let post_id = parse_post_id(&post_id)?;
+
+    let pool = state.db.pool.clone();
+    let moderation_repo = ModerationRepository::new(pool.clone());
+    require_queued(&moderation_repo, &post_id)?;
+    audit(&state, &admin_id, "moderation.remove", &format!("post:{}", post_id), &reason)?;
+    moderation_repo
+        .approve(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let post_repo = PostRepository::new(pool);
+    let author_id = post_repo
+        .get_by_id(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .map(|post| post.author_id);
+    post_repo
+        .delete_post(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if let Some(author_id) = author_id {
+        state.profile_cache.invalidate(&[author_id]);
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Post removed"
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_admin_matches_listed_usernames() {
+        assert!(is_admin("alice, Bob".split(','), "bob"));
+        assert!(!is_admin("alice,bob".split(','), "carol"));
+        assert!(!is_admin("".split(','), ""));
+        assert!(is_admin(["carol"], "Carol"));
+        assert!(!is_admin([], "carol"));
+    }
+
+    #[test]
+    fn test_parse_badge_path_accepts_known_badges() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            parse_badge_path(&id.to_string(), "early-adopter").unwrap(),
+            (id, Badge::EarlyAdopter)
+        );
+        assert!(parse_badge_path(&id.to_string(), "owner").is_err());
+        assert!(parse_badge_path("nope", "bot").is_err());
+    }
+}
@@ -1,13 +1,15 @@
 use axum::{
     extract::State,
+    http::{header, HeaderMap},
     Json,
 };
-use fido_types::{LoginRequest, LoginResponse, User};
+use fido_types::{LoginRequest, LoginResponse, PowChallenge, PowSolution, User};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-use crate::db::repositories::UserRepository;
+use crate::cookie_auth;
+use crate::db::repositories::{GitHubFollowingRepository, GitHubTokenRepository, UserRepository};
 use crate::oauth::GitHubOAuthConfig;
 use crate::state::AppState;
 use super::{ApiError, ApiResult};
@@ -20,6 +22,16 @@ lazy_static::lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
 }
 
+/// How long a proof-of-work challenge can be redeemed for
+const POW_CHALLENGE_TTL_SECS: i64 = 300;
+/// Unredeemed challenges kept at once, so the store can't be grown without bound
+const MAX_OUTSTANDING_CHALLENGES: usize = 10_000;
+
+// Proof-of-work challenges handed out and not yet used: challenge -> expiry
+lazy_static::lazy_static! {
+    static ref POW_CHALLENGES: Mutex<HashMap<String, i64>> = Mutex::new(HashMap::new());
+}
+
 /// Response for GitHub Device Flow initiation
 #[derive(Serialize)]
 pub struct GitHubDeviceFlowResponse {
@@ -43,6 +55,26 @@ pub struct ValidateSessionResponse {
     pub valid: bool,
 }
 
+/// Response for a session heartbeat
+#[derive(Serialize)]
+pub struct HeartbeatResponse {
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Response for a cookie-based web login. The session token only travels in
+/// the HttpOnly cookie; the page gets the CSRF token to echo back.
+#[derive(Serialize)]
+pub struct WebLoginResponse {
+    pub user: User,
+    pub csrf_token: String,
+}
+
+/// Response for a CSRF token request
+#[derive(Serialize)]
+pub struct CsrfTokenResponse {
+    pub csrf_token: String,
+}
+
 /// GET /users/test - List all test users
 pub async fn list_test_users(
     State(state): State<AppState>,
@@ -54,11 +86,67 @@ pub async fn list_test_users(
     Ok(Json(users))
 }
 
+/// GET /auth/challenge - A single-use proof-of-work challenge for `/auth/login`
+///
+/// Only offered when `login_pow_difficulty` is set; see `fido_types::pow`.
+pub async fn pow_challenge(State(state): State<AppState>) -> ApiResult<Json<PowChallenge>> {
+    let difficulty = state.limits.login_pow_difficulty;
+    if difficulty == 0 {
+        return Err(ApiError::NotFound(
+            "This server doesn't require proof-of-work".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now();
+    let expires_at = now + chrono::Duration::seconds(POW_CHALLENGE_TTL_SECS);
+    let challenge = uuid::Uuid::new_v4().simple().to_string();
+
+    let mut challenges = POW_CHALLENGES.lock().unwrap();
+    challenges.retain(|_, expiry| *expiry > now.timestamp());
+    if challenges.len() >= MAX_OUTSTANDING_CHALLENGES {
+        return Err(ApiError::TooManyRequests(
+            "Too many logins in progress. Try again shortly.".to_string(),
+        ));
+    }
+    challenges.insert(challenge.clone(), expires_at.timestamp());
+
+    Ok(Json(PowChallenge {
+        challenge,
+        difficulty,
+        expires_at,
+    }))
+}
+
+/// Redeem the proof-of-work a login has to carry when the instance asks for
+/// one. The challenge is used up even when the nonce is wrong.
+fn check_login_pow(state: &AppState, pow: Option<&PowSolution>) -> Result<(), ApiError> {
+    let difficulty = state.limits.login_pow_difficulty;
+    if difficulty == 0 {
+        return Ok(());
+    }
+    let pow = pow.ok_or_else(|| {
+        ApiError::Forbidden("Proof-of-work required; solve GET /auth/challenge first".to_string())
+    })?;
+
+    let expiry = POW_CHALLENGES.lock().unwrap().remove(&pow.challenge);
+    if expiry.is_none_or(|expiry| expiry <= chrono::Utc::now().timestamp()) {
+        return Err(ApiError::Forbidden("Unknown or expired challenge".to_string()));
+    }
+    if !fido_types::pow::is_solution(&pow.challenge, pow.nonce, difficulty) {
+        return Err(ApiError::Forbidden(
+            "Proof-of-work doesn't meet the difficulty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// POST /auth/login - Login with test user
 pub async fn login(
     State(state): State<AppState>,
     Json(payload): Json<LoginRequest>,
 ) -> ApiResult<Json<LoginResponse>> {
+    check_login_pow(&state, payload.pow.as_ref())?;
+
     let repo = UserRepository::new(state.db.pool.clone());
     
     // Find user by username
@@ -81,6 +169,79 @@ pub async fn login(
     }))
 }
 
+/// POST /auth/web/login - Login with a test user using cookie auth
+/// 
+/// Same checks as `/auth/login`, but the session token is set as an HttpOnly,
+/// SameSite=Strict cookie instead of being returned, alongside a CSRF cookie.
+pub async fn web_login(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> ApiResult<(HeaderMap, Json<WebLoginResponse>)> {
+    let lifetime = state.session_manager.lifetime();
+    let cookie_path = state.cookie_path.clone();
+    let Json(LoginResponse { user, session_token }) = login(State(state), Json(payload)).await?;
+
+    let csrf_token = cookie_auth::new_csrf_token();
+    let mut headers = HeaderMap::new();
+    headers.append(
+        header::SET_COOKIE,
+        cookie_auth::session_cookie(&session_token, lifetime, &cookie_path),
+    );
+    headers.append(header::SET_COOKIE, cookie_auth::csrf_cookie(&csrf_token, lifetime, &cookie_path));
+
+    Ok((headers, Json(WebLoginResponse { user, csrf_token })))
+}
+
+/// GET /auth/csrf - Issue a fresh CSRF token for a cookie session
+/// 
+/// Lets a reloaded page recover the token it has to send in `X-CSRF-Token`.
+/// Requires a valid session (cookie or header).
+pub async fn csrf_token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<(HeaderMap, Json<CsrfTokenResponse>)> {
+    let token = headers
+        .get("X-Session-Token")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
+    state.session_manager.validate_session(token)
+        .map_err(|_| ApiError::Unauthorized("Invalid or expired session".to_string()))?;
+
+    let csrf_token = cookie_auth::new_csrf_token();
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        header::SET_COOKIE,
+        cookie_auth::csrf_cookie(&csrf_token, state.session_manager.lifetime(), &state.cookie_path),
+    );
+
+    Ok((response_headers, Json(CsrfTokenResponse { csrf_token })))
+}
+
+/// POST /auth/web/logout - End a cookie session and clear its cookies
+pub async fn web_logout(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<(HeaderMap, Json<serde_json::Value>)> {
+    if let Some(token) = headers.get("X-Session-Token").and_then(|v| v.to_str().ok()) {
+        state.session_manager.delete_session(token)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    }
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.append(
+        header::SET_COOKIE,
+        cookie_auth::expired_cookie(cookie_auth::SESSION_COOKIE, &state.cookie_path),
+    );
+    response_headers.append(
+        header::SET_COOKIE,
+        cookie_auth::expired_cookie(cookie_auth::CSRF_COOKIE, &state.cookie_path),
+    );
+
+    Ok((response_headers, Json(serde_json::json!({
+        "message": "Logged out successfully"
+    }))))
+}
+
 /// POST /auth/logout - Logout current user
 pub async fn logout(
     State(state): State<AppState>,
@@ -191,7 +352,7 @@ pub async fn github_device_poll(
     };
     
     // Fetch GitHub user profile
-    let github_user = oauth_config.get_user(access_token)
+    let github_user = oauth_config.get_user(access_token.token.clone())
         .await
         .map_err(|e| ApiError::InternalError(format!("Failed to fetch GitHub user: {}", e)))?;
     
@@ -205,6 +366,30 @@ pub async fn github_device_poll(
         github_user.name.as_deref(),
     )
     .map_err(|e| ApiError::InternalError(format!("Failed to create/update user: {}", e)))?;
+
+    // Refresh who they follow on GitHub for follow suggestions. This only
+    // happens here at login; a failure just leaves the previous list in
+    // place.
+    match oauth_config.get_following_ids(&access_token.token).await {
+        Ok(github_ids) => {
+            if let Err(e) = GitHubFollowingRepository::new(state.db.pool.clone()).replace(&user.id, &github_ids) {
+                tracing::warn!("Failed to store GitHub following list for {}: {}", user.username, e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to fetch GitHub following list for {}: {}", user.username, e),
+    }
+
+    // Keep the token only if it can create gists, for posting overlong
+    // text as one; logging in without that scope forgets an older token
+    let tokens = GitHubTokenRepository::new(state.db.pool.clone());
+    let stored = if access_token.has_scope("gist") {
+        tokens.save(&user.id, &access_token.token)
+    } else {
+        tokens.delete(&user.id)
+    };
+    if let Err(e) = stored {
+        tracing::warn!("Failed to update GitHub token for {}: {}", user.username, e);
+    }
     
     // Create session
     let session_token = state.session_manager.create_session(user.id)
@@ -250,3 +435,54 @@ pub async fn validate_session(
         valid: true,
     }))
 }
+
+/// POST /auth/heartbeat - Keep a session alive
+/// 
+/// Validates the session token from the X-Session-Token header and pushes
+/// its expiry a full session lifetime into the future. A 401 means the
+/// session is gone and the client must log in again.
+pub async fn heartbeat(
+    State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+) -> ApiResult<Json<HeartbeatResponse>> {
+    let token = headers
+        .get("X-Session-Token")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
+
+    let (_, expires_at) = state.session_manager.extend_session(token)
+        .map_err(|_| ApiError::Unauthorized("Invalid or expired session".to_string()))?;
+
+    Ok(Json(HeartbeatResponse { expires_at }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Limits;
+    use crate::db::Database;
+
+    #[tokio::test]
+    async fn test_login_pow_challenge_is_single_use() -> anyhow::Result<()> {
+        let state = AppState::new(Database::in_memory()?).with_limits(Limits {
+            login_pow_difficulty: 4,
+            ..Limits::default()
+        });
+
+        assert!(check_login_pow(&state, None).is_err());
+
+        let Json(challenge) = pow_challenge(State(state.clone())).await.unwrap();
+        let solution = PowSolution {
+            nonce: fido_types::pow::solve(&challenge.challenge, challenge.difficulty),
+            challenge: challenge.challenge,
+        };
+        assert!(check_login_pow(&state, Some(&solution)).is_ok());
+        assert!(check_login_pow(&state, Some(&solution)).is_err());
+
+        // Instances without a difficulty don't ask for anything
+        let open = AppState::new(Database::in_memory()?);
+        assert!(check_login_pow(&open, None).is_ok());
+        assert!(pow_challenge(State(open)).await.is_err());
+        Ok(())
+    }
+}
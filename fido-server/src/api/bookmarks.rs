@@ -0,0 +1,88 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{get_user_from_headers, posts::hide_limited_posts, ApiError, ApiResult},
+    db::repositories::{BookmarkRepository, HashtagRepository, PostRepository},
+    state::AppState,
+};
+use fido_types::{BatchResult, BookmarkPostsRequest, Post};
+
+/// Most posts one batch request may name
+pub const MAX_BATCH: usize = 100;
+
+/// GET /bookmarks - The caller's bookmarked posts, most recent bookmark first
+pub async fn list_bookmarks(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<Post>>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let pool = state.db.pool.clone();
+    let post_repo = PostRepository::new(pool.clone());
+    let hashtag_repo = HashtagRepository::new(pool.clone());
+    let post_ids = BookmarkRepository::new(pool)
+        .post_ids(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    let mut posts = Vec::with_capacity(post_ids.len());
+    for post_id in post_ids {
+        if let Some(mut post) = post_repo
+            .get_by_id(&post_id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?
+        {
+            post.hashtags = hashtag_repo
+                .get_by_post(&post.id)
+                .map_err(|e| ApiError::InternalError(e.to_string()))?;
+            posts.push(post);
+        }
+    }
+    hide_limited_posts(&state, &mut posts, Some(user_id))?;
+
+    Ok(Json(posts))
+}
+
+/// POST /bookmarks/batch - Bookmark several posts at once
+pub async fn bookmark_posts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<BookmarkPostsRequest>,
+) -> ApiResult<Json<BatchResult>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    if payload.post_ids.len() > MAX_BATCH {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} posts per request",
+            MAX_BATCH
+        )));
+    }
+
+    let applied = BookmarkRepository::new(state.db.pool.clone())
+        .add_many(&user_id, &payload.post_ids)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(BatchResult { applied }))
+}
+
+/// DELETE /bookmarks/:post_id - Remove a bookmark
+pub async fn remove_bookmark(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let post_id = Uuid::parse_str(&post_id)
+        .map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+
+    let removed = BookmarkRepository::new(state.db.pool.clone())
+        .remove(&user_id, &post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !removed {
+        return Err(ApiError::NotFound("Post not bookmarked".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "bookmarked": false })))
+}
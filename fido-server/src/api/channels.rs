@@ -0,0 +1,201 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        get_user_from_headers,
+        posts::{self, GetPostsQuery},
+        ApiError, ApiResult,
+    },
+    db::repositories::ChannelRepository,
+    sanitize::{sanitize_content, ContentKind},
+    state::AppState,
+};
+use fido_types::{Channel, CreateChannelRequest, CreatePostRequest, Post};
+
+/// Longest channel name, not counting the `#`
+const MAX_CHANNEL_NAME_LEN: usize = 32;
+
+/// `#Rust-Help` and `rust-help` name the same channel
+pub(crate) fn normalize_channel_name(name: &str) -> Result<String, ApiError> {
+    let name = name.trim().trim_start_matches('#').to_lowercase();
+    let valid = (2..=MAX_CHANNEL_NAME_LEN).contains(&name.len())
+        && name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        && !name.starts_with('-')
+        && !name.ends_with('-');
+    if valid {
+        Ok(name)
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "Channel names are 2-{} lowercase letters, digits and dashes",
+            MAX_CHANNEL_NAME_LEN
+        )))
+    }
+}
+
+fn channel_id(state: &AppState, name: &str) -> Result<Uuid, ApiError> {
+    let name = normalize_channel_name(name)?;
+    ChannelRepository::new(state.db.pool.clone())
+        .id_by_name(&name)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Channel not found".to_string()))
+}
+
+/// GET /channels - Every channel; with a session, which ones the caller
+/// joined and how many unread posts each has
+pub async fn list_channels(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<Channel>>> {
+    let viewer = get_user_from_headers(&state, &headers).ok();
+    let channels = ChannelRepository::new(state.db.pool.clone())
+        .list(viewer.as_ref())
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(channels))
+}
+
+/// POST /channels - Create a channel and join it
+pub async fn create_channel(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateChannelRequest>,
+) -> ApiResult<Json<Channel>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let name = normalize_channel_name(&payload.name)?;
+    let description = sanitize_content(ContentKind::ChannelDescription, &payload.description)?;
+
+    let repo = ChannelRepository::new(state.db.pool.clone());
+    let created = repo
+        .create(&name, &description, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !created {
+        return Err(ApiError::BadRequest(format!("#{} already exists", name)));
+    }
+
+    repo.list(Some(&user_id))
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .into_iter()
+        .find(|channel| channel.name == name)
+        .map(Json)
+        .ok_or_else(|| ApiError::InternalError("Created channel not found".to_string()))
+}
+
+/// PUT /channels/:name/membership - Join a channel
+pub async fn join_channel(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let channel_id = channel_id(&state, &name)?;
+
+    ChannelRepository::new(state.db.pool.clone())
+        .join(&channel_id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "joined": true })))
+}
+
+/// DELETE /channels/:name/membership - Leave a channel
+pub async fn leave_channel(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let channel_id = channel_id(&state, &name)?;
+
+    ChannelRepository::new(state.db.pool.clone())
+        .leave(&channel_id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "joined": false })))
+}
+
+/// POST /channels/:name/read - The caller has read the channel up to now
+pub async fn mark_channel_read(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let channel_id = channel_id(&state, &name)?;
+
+    let member = ChannelRepository::new(state.db.pool.clone())
+        .mark_read(&channel_id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    if member {
+        Ok(Json(serde_json::json!({
+            "success": true,
+            "message": "Channel marked as read"
+        })))
+    } else {
+        Err(ApiError::NotFound("Not a member of this channel".to_string()))
+    }
+}
+
+/// GET /channels/:name/posts - The channel's feed; takes `limit` and `sort`
+/// like `GET /posts`
+pub async fn get_channel_posts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Query(mut query): Query<GetPostsQuery>,
+) -> ApiResult<Json<Vec<Post>>> {
+    query.channel = Some(normalize_channel_name(&name)?);
+    posts::get_posts(State(state), headers, Query(query)).await
+}
+
+/// POST /channels/:name/posts - Post in a channel; members only
+pub async fn create_channel_post(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Json(mut payload): Json<CreatePostRequest>,
+) -> ApiResult<Json<Post>> {
+    payload.content = sanitize_content(ContentKind::Post, &payload.content)?;
+    if payload.anonymous {
+        return Err(ApiError::BadRequest("Channel posts can't be anonymous".to_string()));
+    }
+    let author_id = get_user_from_headers(&state, &headers)?;
+    let channel_id = channel_id(&state, &name)?;
+
+    let repo = ChannelRepository::new(state.db.pool.clone());
+    let member = repo
+        .is_member(&channel_id, &author_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !member {
+        return Err(ApiError::Forbidden("Join the channel to post in it".to_string()));
+    }
+
+    posts::check_post_rate_limit(&state, &author_id)?;
+    posts::check_daily_post_quota(&state, &author_id)?;
+    let verdict = posts::screen_content(&state, &author_id, &payload.content).await?;
+
+    let post =
+        posts::publish_post(&state, author_id, payload.content, false, payload.reply_policy)?;
+    repo.add_post(&channel_id, &post.id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    posts::queue_for_moderation(&state, &post.id, verdict.as_ref())?;
+    Ok(Json(post))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_channel_name() {
+        assert_eq!(normalize_channel_name("#Rust-Help").unwrap(), "rust-help");
+        assert_eq!(normalize_channel_name(" show-and-tell ").unwrap(), "show-and-tell");
+        assert!(normalize_channel_name("a").is_err());
+        assert!(normalize_channel_name("-rust").is_err());
+        assert!(normalize_channel_name("rust help").is_err());
+        assert!(normalize_channel_name(&"x".repeat(MAX_CHANNEL_NAME_LEN + 1)).is_err());
+    }
+}
@@ -1,19 +1,22 @@
-use axum::{extract::State, Json};
-use uuid::Uuid;
+use axum::{extract::State, http::HeaderMap, Json};
 
 use crate::{
-    api::{ApiError, ApiResult},
+    api::{get_user_from_headers, ApiError, ApiResult},
     db::repositories::ConfigRepository,
     state::AppState,
 };
-use fido_types::{ColorScheme, SortOrder, UpdateConfigRequest, UserConfig};
+use fido_types::{
+    AnnounceOutput, ColorScheme, DigestFrequency, FeedDensity, SortOrder, TimestampStyle,
+    UpdateConfigRequest, UserConfig,
+};
 
-/// GET /config - Get user configuration
-pub async fn get_config(State(state): State<AppState>) -> ApiResult<Json<UserConfig>> {
-    // For MVP, we'll use a hardcoded user ID (alice)
-    // In production, this would come from the authenticated session
-    let user_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001")
-        .expect("Invalid hardcoded UUID");
+/// GET /config - Get the authenticated user's configuration
+pub async fn get_config(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<UserConfig>> {
+    // Settings include privacy choices, so they must belong to the caller
+    let user_id = get_user_from_headers(&state, &headers)?;
 
     let pool = state.db.pool.clone();
     let config_repo = ConfigRepository::new(pool);
@@ -26,15 +29,13 @@ pub async fn get_config(State(state): State<AppState>) -> ApiResult<Json<UserCon
     Ok(Json(config))
 }
 
-/// PUT /config - Update user configuration
+/// PUT /config - Update the authenticated user's configuration
 pub async fn update_config(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateConfigRequest>,
 ) -> ApiResult<Json<UserConfig>> {
-    // For MVP, we'll use a hardcoded user ID (alice)
-    // In production, this would come from the authenticated session
-    let user_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001")
-        .expect("Invalid hardcoded UUID");
+    let user_id = get_user_from_headers(&state, &headers)?;
 
     let pool = state.db.pool.clone();
     let config_repo = ConfigRepository::new(pool);
@@ -48,7 +49,7 @@ pub async fn update_config(
     if let Some(color_scheme_str) = payload.color_scheme {
         let color_scheme = ColorScheme::parse(&color_scheme_str).ok_or_else(|| {
             ApiError::BadRequest(format!(
-                "Invalid color scheme '{}'. Valid options: Default, Dark, Light, Solarized",
+                "Invalid color scheme '{}'. Valid options: Default, Dark, Light, Solarized, HighContrast, Deuteranopia, Protanopia",
                 color_scheme_str
             ))
         })?;
@@ -79,6 +80,108 @@ pub async fn update_config(
         config.emoji_enabled = emoji_enabled;
     }
 
+    if let Some(visible) = payload.votes_visible_to_authors {
+        config.votes_visible_to_authors = visible;
+    }
+
+    if let Some(do_not_disturb) = payload.do_not_disturb {
+        config.do_not_disturb = do_not_disturb;
+    }
+
+    if let Some(enabled) = payload.quiet_hours_enabled {
+        config.quiet_hours_enabled = enabled;
+    }
+
+    for (field, hour) in [
+        ("quiet_hours_start", payload.quiet_hours_start),
+        ("quiet_hours_end", payload.quiet_hours_end),
+    ] {
+        if hour.is_some_and(|h| h > 23) {
+            return Err(ApiError::BadRequest(format!("{} must be an hour from 0 to 23", field)));
+        }
+    }
+    if let Some(start) = payload.quiet_hours_start {
+        config.quiet_hours_start = start;
+    }
+    if let Some(end) = payload.quiet_hours_end {
+        config.quiet_hours_end = end;
+    }
+
+    if let Some(show) = payload.show_unread_counts {
+        config.show_unread_counts = show;
+    }
+
+    if let Some(accessible) = payload.accessible_layout {
+        config.accessible_layout = accessible;
+    }
+
+    if let Some(output_str) = payload.announcements {
+        config.announcements = AnnounceOutput::parse(&output_str).ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Invalid announcements '{}'. Valid options: Off, Terminal, Speech",
+                output_str
+            ))
+        })?;
+    }
+
+    if let Some(reduced) = payload.reduced_motion {
+        config.reduced_motion = reduced;
+    }
+
+    if let Some(style_str) = payload.timestamp_style {
+        config.timestamp_style = TimestampStyle::parse(&style_str).ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Invalid timestamp style '{}'. Valid options: Absolute, Relative",
+                style_str
+            ))
+        })?;
+    }
+
+    if let Some(density_str) = payload.feed_density {
+        config.feed_density = FeedDensity::parse(&density_str).ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Invalid feed density '{}'. Valid options: Comfortable, Compact",
+                density_str
+            ))
+        })?;
+    }
+
+    if let Some(lines) = payload.preview_lines {
+        if lines > UserConfig::MAX_PREVIEW_LINES {
+            return Err(ApiError::BadRequest(format!(
+                "preview_lines must be from 0 (no limit) to {}",
+                UserConfig::MAX_PREVIEW_LINES
+            )));
+        }
+        config.preview_lines = lines;
+    }
+
+    if let Some(show) = payload.show_vote_counts {
+        config.show_vote_counts = show;
+    }
+
+    if let Some(show) = payload.show_karma {
+        config.show_karma = show;
+    }
+
+    if let Some(tags) = payload.pinned_hashtags {
+        config.pinned_hashtags =
+            UserConfig::normalize_pinned_hashtags(&tags).map_err(ApiError::BadRequest)?;
+    }
+
+    if let Some(frequency_str) = payload.digest_frequency {
+        config.digest_frequency = DigestFrequency::parse(&frequency_str).ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Invalid digest frequency '{}'. Valid options: Off, Daily, Weekly",
+                frequency_str
+            ))
+        })?;
+    }
+
+    if let Some(discoverable) = payload.discoverable {
+        config.discoverable = discoverable;
+    }
+
     // Save updated config
     config_repo
         .update(&config)
@@ -0,0 +1,137 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        get_user_from_headers,
+        posts::{
+            check_daily_post_quota, check_post_rate_limit, publish_post, queue_for_moderation,
+            screen_content,
+        },
+        ApiError, ApiResult,
+    },
+    db::repositories::{CrosspostRepository, DirectMessageRepository, UserRepository},
+    sanitize::{sanitize_content, ContentKind},
+    state::AppState,
+};
+use fido_types::{CreateCrosspostRequest, Crosspost, CrosspostStatus, Post, ReplyPolicy};
+
+fn parse_crosspost_id(id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(id).map_err(|_| ApiError::BadRequest("Invalid cross-post ID".to_string()))
+}
+
+/// POST /dms/crossposts - Ask the other participant of a conversation to
+/// approve publishing a post that quotes one of its messages
+pub async fn request_crosspost(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateCrosspostRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let content = sanitize_content(ContentKind::Post, &payload.content)?;
+
+    let pool = state.db.pool.clone();
+    let message = DirectMessageRepository::new(pool.clone())
+        .get_message_for(&payload.message_id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Message not found".to_string()))?;
+    let approver_id = if message.from_user_id == user_id {
+        message.to_user_id
+    } else {
+        message.from_user_id
+    };
+
+    let repo = CrosspostRepository::new(pool.clone());
+    let already_asked = repo
+        .has_pending(&message.id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if already_asked {
+        return Err(ApiError::BadRequest(
+            "You already asked to cross-post this message".to_string(),
+        ));
+    }
+
+    // The post is published as soon as it's approved, so it has to fit the
+    // limits now
+    check_post_rate_limit(&state, &user_id)?;
+    check_daily_post_quota(&state, &user_id)?;
+
+    repo.create(&message.id, &user_id, &approver_id, &content)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    let approver = UserRepository::new(pool)
+        .get_by_id(&approver_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .map(|user| user.username)
+        .unwrap_or_default();
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": format!("Asked @{} to approve the cross-post", approver)
+    })))
+}
+
+/// GET /dms/crossposts - Requests awaiting the caller's approval and the
+/// caller's own recent requests
+pub async fn list_crossposts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<Crosspost>>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let crossposts = CrosspostRepository::new(state.db.pool.clone())
+        .list_for_user(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(crossposts))
+}
+
+/// POST /dms/crossposts/:id/approve - Consent to the cross-post, publishing
+/// it as the requester's post
+pub async fn approve_crosspost(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Post>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let id = parse_crosspost_id(&id)?;
+
+    let repo = CrosspostRepository::new(state.db.pool.clone());
+    let (crosspost, requester_id) = repo
+        .get_pending_for_approver(&id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Cross-post request not found".to_string()))?;
+
+    let verdict = screen_content(&state, &requester_id, &crosspost.content).await?;
+    let post = publish_post(&state, requester_id, crosspost.content, false, ReplyPolicy::Everyone)?;
+    queue_for_moderation(&state, &post.id, verdict.as_ref())?;
+    repo.decide(&id, CrosspostStatus::Approved, Some(&post.id))
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(post))
+}
+
+/// POST /dms/crossposts/:id/decline - Refuse the cross-post
+pub async fn decline_crosspost(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let id = parse_crosspost_id(&id)?;
+
+    let repo = CrosspostRepository::new(state.db.pool.clone());
+    repo.get_pending_for_approver(&id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Cross-post request not found".to_string()))?;
+    repo.decide(&id, CrosspostStatus::Declined, None)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Cross-post declined"
+    })))
+}
@@ -0,0 +1,131 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::{get_user_from_headers, posts, ApiError, ApiResult},
+    db::repositories::{DigestRepository, HashtagRepository, MuteRepository},
+    digest,
+    state::AppState,
+};
+use fido_types::{Catchup, Digest, HashtagDigestEntry, UpdateHashtagDigestRequest};
+
+/// GET /digest - The caller's newest digest they haven't dismissed
+pub async fn get_digest(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Digest>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    DigestRepository::new(state.db.pool.clone())
+        .latest_unseen(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound("No new digest".to_string()))
+}
+
+/// POST /digest/:id/seen - Dismiss a digest and any older ones
+pub async fn mark_digest_seen(
+    State(state): State<AppState>,
+    Path(digest_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let found = DigestRepository::new(state.db.pool.clone())
+        .mark_seen(&user_id, &digest_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    if found {
+        Ok(Json(serde_json::json!({
+            "success": true,
+            "message": "Digest dismissed"
+        })))
+    } else {
+        Err(ApiError::NotFound("Digest not found".to_string()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CatchupQuery {
+    pub since: DateTime<Utc>,
+}
+
+/// GET /catchup?since= - New followers, top followed-hashtag posts and
+/// unread DMs since the given RFC 3339 time
+pub async fn get_catchup(
+    State(state): State<AppState>,
+    Query(query): Query<CatchupQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Catchup>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let now = Utc::now();
+    if query.since > now {
+        return Err(ApiError::BadRequest("since is in the future".to_string()));
+    }
+
+    let catchup = digest::catchup(&state.db.pool, &user_id, query.since, now)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(catchup))
+}
+
+/// GET /digest/hashtags - The caller's followed hashtags with their latest
+/// weekly changelog, leaving out posts by people they muted
+pub async fn get_hashtag_digest(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<HashtagDigestEntry>>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let settings = HashtagRepository::new(state.db.pool.clone())
+        .digest_settings(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let muted = MuteRepository::new(state.db.pool.clone())
+        .muted_ids(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let digests = DigestRepository::new(state.db.pool.clone());
+
+    let mut entries = Vec::with_capacity(settings.len());
+    for (hashtag, enabled) in settings {
+        let mut changelog = if enabled {
+            digests
+                .latest_changelog(&hashtag)
+                .map_err(|e| ApiError::InternalError(e.to_string()))?
+        } else {
+            None
+        };
+        if let Some(changelog) = &mut changelog {
+            changelog.top_posts.retain(|post| !muted.contains(&post.author_id));
+            posts::hide_limited_posts(&state, &mut changelog.top_posts, Some(user_id))?;
+        }
+        entries.push(HashtagDigestEntry {
+            hashtag,
+            enabled,
+            changelog,
+        });
+    }
+    Ok(Json(entries))
+}
+
+/// PUT /digest/hashtags/:name - Turn a followed hashtag's weekly changelog
+/// on or off; 404 if the caller doesn't follow it
+pub async fn set_hashtag_digest(
+    State(state): State<AppState>,
+    Path(hashtag): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateHashtagDigestRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let found = HashtagRepository::new(state.db.pool.clone())
+        .set_digest_enabled(&user_id, &hashtag, payload.enabled)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !found {
+        return Err(ApiError::NotFound("You don't follow that hashtag".to_string()));
+    }
+    Ok(Json(serde_json::json!({ "enabled": payload.enabled })))
+}
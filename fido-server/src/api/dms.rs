@@ -1,29 +1,20 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::HeaderMap,
     Json,
 };
 use chrono::Utc;
+use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
-    api::{ApiError, ApiResult},
+    api::{get_user_from_headers, ApiError, ApiResult},
     db::repositories::{DirectMessageRepository, UserRepository},
+    export,
+    sanitize::{sanitize_attachment, sanitize_content, ContentKind},
     state::AppState,
 };
-use fido_types::{DirectMessage, SendMessageRequest};
-
-/// Extract user ID from session token header
-fn get_user_from_headers(state: &AppState, headers: &HeaderMap) -> Result<Uuid, ApiError> {
-    let token = headers
-        .get("X-Session-Token")
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
-    
-    state
-        .get_authenticated_user_id_from_token(token)
-        .ok_or_else(|| ApiError::Unauthorized("Invalid session token".to_string()))
-}
+use fido_types::{ConversationExport, DirectMessage, DmAttachment, ExportFormat, SendMessageRequest};
 
 /// GET /dms/conversations - List conversations for current user
 pub async fn get_conversations(
@@ -37,10 +28,13 @@ pub async fn get_conversations(
     let dm_repo = DirectMessageRepository::new(pool.clone());
     let user_repo = UserRepository::new(pool);
 
-    // Get list of user IDs with conversations
+    // Get list of user IDs with conversations (pinned first, then by activity)
     let conversation_user_ids = dm_repo
         .get_conversations_list(&user_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let pinned = dm_repo
+        .get_pinned(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
 
     // Build conversation list with user info and unread count
     let mut conversations = Vec::new();
@@ -67,7 +61,11 @@ pub async fn get_conversations(
 
         // Get last message info
         let (last_message, last_message_time) = if let Some(last_msg) = messages.last() {
-            (last_msg.content.clone(), last_msg.created_at.to_rfc3339())
+            let preview = match &last_msg.attachment {
+                Some(file) if last_msg.content.is_empty() => format!("📎 {}", file.filename),
+                _ => last_msg.content.clone(),
+            };
+            (preview, last_msg.created_at.to_rfc3339())
         } else {
             ("No messages yet".to_string(), Utc::now().to_rfc3339())
         };
@@ -77,7 +75,8 @@ pub async fn get_conversations(
             "other_username": user.username,
             "last_message": last_message,
             "last_message_time": last_message_time,
-            "unread_count": unread_count
+            "unread_count": unread_count,
+            "pinned": pinned.contains(&other_user_id)
         }));
     }
 
@@ -139,6 +138,58 @@ pub async fn get_conversation(
     Ok(Json(messages))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// GET /dms/conversations/:user_id/export?format=markdown|json - The whole
+/// conversation (as the caller sees it) rendered as a file for archiving
+pub async fn export_conversation(
+    State(state): State<AppState>,
+    Path(other_user_id): Path<String>,
+    Query(query): Query<ExportQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Json<ConversationExport>> {
+    let other_user_id = Uuid::parse_str(&other_user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let pool = state.db.pool.clone();
+    let user_repo = UserRepository::new(pool.clone());
+    let username = |id: &Uuid| -> ApiResult<String> {
+        user_repo
+            .get_by_id(id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?
+            .map(|user| user.username)
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))
+    };
+    let me = username(&user_id)?;
+    let other = username(&other_user_id)?;
+
+    let mut messages = DirectMessageRepository::new(pool)
+        .get_conversation(&user_id, &other_user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if messages.is_empty() {
+        return Err(ApiError::NotFound("No messages to export".to_string()));
+    }
+    for msg in &mut messages {
+        let (from, to) = if msg.from_user_id == user_id { (&me, &other) } else { (&other, &me) };
+        msg.from_username = from.clone();
+        msg.to_username = to.clone();
+    }
+
+    let now = Utc::now();
+    let content = export::render(&me, &other, &messages, query.format, now)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(ConversationExport {
+        filename: export::filename(&other, query.format, now),
+        format: query.format,
+        content,
+    }))
+}
+
 /// POST /dms/mark-read/:user_id - Mark messages as read for a specific user
 pub async fn mark_messages_read(
     State(state): State<AppState>,
@@ -170,12 +221,22 @@ pub async fn mark_messages_read(
 pub async fn send_message(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Json(payload): Json<SendMessageRequest>,
+    Json(mut payload): Json<SendMessageRequest>,
 ) -> ApiResult<Json<DirectMessage>> {
-    // Validate content
-    if payload.content.is_empty() {
-        return Err(ApiError::BadRequest("Message content cannot be empty".to_string()));
-    }
+    // Strip terminal escapes, normalize and check length. A message may be
+    // just an attachment, with no text of its own.
+    let attachment = match payload.attachment.take() {
+        Some(file) => {
+            let (filename, content) = sanitize_attachment(&file.filename, &file.content)?;
+            Some(DmAttachment { filename, content })
+        }
+        None => None,
+    };
+    payload.content = if attachment.is_some() && payload.content.trim().is_empty() {
+        String::new()
+    } else {
+        sanitize_content(ContentKind::Message, &payload.content)?
+    };
 
     // Get authenticated user from session token
     let from_user_id = get_user_from_headers(&state, &headers)?;
@@ -216,6 +277,7 @@ pub async fn send_message(
         content: payload.content,
         created_at: Utc::now(),
         is_read: false,
+        attachment,
     };
 
     // Store message (old deleted messages stay hidden)
@@ -246,9 +308,57 @@ pub async fn delete_conversation(
     dm_repo
         .delete_conversation(&user_id, &other_user_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    // A hidden conversation shouldn't come back pinned
+    dm_repo
+        .unpin_conversation(&user_id, &other_user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
 
     Ok(Json(serde_json::json!({
         "success": true,
         "message": "Conversation deleted"
     })))
 }
+
+/// PUT /dms/conversations/:user_id/pin - Pin a conversation to the top of the list
+pub async fn pin_conversation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(other_user_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let other_user_id = Uuid::parse_str(&other_user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID format".to_string()))?;
+
+    let dm_repo = DirectMessageRepository::new(state.db.pool.clone());
+    let has_conversation = dm_repo
+        .get_conversations_list(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .contains(&other_user_id);
+    if !has_conversation {
+        return Err(ApiError::NotFound("Conversation not found".to_string()));
+    }
+
+    dm_repo
+        .pin_conversation(&user_id, &other_user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "pinned": true })))
+}
+
+/// DELETE /dms/conversations/:user_id/pin - Unpin a conversation
+pub async fn unpin_conversation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(other_user_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let other_user_id = Uuid::parse_str(&other_user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID format".to_string()))?;
+
+    let dm_repo = DirectMessageRepository::new(state.db.pool.clone());
+    dm_repo
+        .unpin_conversation(&user_id, &other_user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "pinned": false })))
+}
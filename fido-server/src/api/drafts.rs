@@ -0,0 +1,95 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{get_user_from_headers, ApiError, ApiResult},
+    db::repositories::DraftRepository,
+    sanitize::{sanitize_content, ContentKind},
+    state::AppState,
+};
+use fido_types::{PostDraft, SaveDraftRequest};
+
+/// Drafts kept per user; saving another past this asks them to clean up
+const MAX_DRAFTS_PER_USER: usize = 50;
+
+/// GET /drafts - The caller's drafts, most recently edited first
+pub async fn list_drafts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<PostDraft>>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let drafts = DraftRepository::new(state.db.pool.clone())
+        .list(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(drafts))
+}
+
+/// POST /drafts - Save a new draft
+pub async fn create_draft(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<SaveDraftRequest>,
+) -> ApiResult<Json<PostDraft>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let content = sanitize_content(ContentKind::Draft, &payload.content)?;
+
+    let repo = DraftRepository::new(state.db.pool.clone());
+    let count = repo
+        .count(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if count >= MAX_DRAFTS_PER_USER {
+        return Err(ApiError::BadRequest(format!(
+            "You already have {} drafts; delete some before saving more",
+            MAX_DRAFTS_PER_USER
+        )));
+    }
+
+    let draft = repo
+        .create(&user_id, &content)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(draft))
+}
+
+/// PUT /drafts/:id - Replace a draft's content
+pub async fn update_draft(
+    State(state): State<AppState>,
+    Path(draft_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<SaveDraftRequest>,
+) -> ApiResult<Json<PostDraft>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let content = sanitize_content(ContentKind::Draft, &payload.content)?;
+
+    DraftRepository::new(state.db.pool.clone())
+        .update(&user_id, &draft_id, &content)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound("Draft not found".to_string()))
+}
+
+/// DELETE /drafts/:id - Discard a draft
+pub async fn delete_draft(
+    State(state): State<AppState>,
+    Path(draft_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let removed = DraftRepository::new(state.db.pool.clone())
+        .delete(&user_id, &draft_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    if removed {
+        Ok(Json(serde_json::json!({
+            "success": true,
+            "message": "Draft deleted"
+        })))
+    } else {
+        Err(ApiError::NotFound("Draft not found".to_string()))
+    }
+}
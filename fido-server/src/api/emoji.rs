@@ -0,0 +1,142 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use crate::{
+    api::{
+        admin::{audit, require_admin, AuditReason},
+        ApiError, ApiResult,
+    },
+    db::repositories::EmojiRepository,
+    state::AppState,
+};
+use fido_types::CustomEmoji;
+
+const MAX_SHORTCODE_LEN: usize = 32;
+const MAX_REPLACEMENT_CHARS: usize = 16;
+
+/// Check a shortcode/replacement pair, normalizing the shortcode
+fn validate_emoji(emoji: CustomEmoji) -> Result<CustomEmoji, ApiError> {
+    let shortcode = emoji.shortcode.trim().trim_matches(':').to_lowercase();
+    if shortcode.is_empty() || shortcode.len() > MAX_SHORTCODE_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Shortcode must be 1-{} characters",
+            MAX_SHORTCODE_LEN
+        )));
+    }
+    if !shortcode
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '+')
+    {
+        return Err(ApiError::BadRequest(
+            "Shortcode may only contain letters, digits, '_', '-' and '+'".to_string(),
+        ));
+    }
+
+    let replacement = emoji.replacement.trim().to_string();
+    let replacement_chars = replacement.chars().count();
+    if replacement_chars == 0 || replacement_chars > MAX_REPLACEMENT_CHARS {
+        return Err(ApiError::BadRequest(format!(
+            "Replacement must be 1-{} characters",
+            MAX_REPLACEMENT_CHARS
+        )));
+    }
+    if replacement.chars().any(char::is_control) {
+        return Err(ApiError::BadRequest(
+            "Replacement cannot contain control characters".to_string(),
+        ));
+    }
+
+    Ok(CustomEmoji {
+        shortcode,
+        replacement,
+    })
+}
+
+/// GET /emoji - List the instance's custom emoji
+pub async fn list_emoji(State(state): State<AppState>) -> ApiResult<Json<Vec<CustomEmoji>>> {
+    let emoji = EmojiRepository::new(state.db.pool.clone())
+        .list()
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(emoji))
+}
+
+/// PUT /emoji - Register or update a custom emoji (admin only)
+pub async fn upsert_emoji(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(reason): Query<AuditReason>,
+    Json(payload): Json<CustomEmoji>,
+) -> ApiResult<Json<CustomEmoji>> {
+    let admin_id = require_admin(&state, &headers, "manage custom emoji")?;
+    let emoji = validate_emoji(payload)?;
+
+    audit(&state, &admin_id, "emoji.upsert", &format!("emoji:{}", emoji.shortcode), &reason)?;
+    EmojiRepository::new(state.db.pool.clone())
+        .upsert(&emoji, &admin_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(emoji))
+}
+
+/// DELETE /emoji/:shortcode - Remove a custom emoji (admin only)
+pub async fn delete_emoji(
+    State(state): State<AppState>,
+    Path(shortcode): Path<String>,
+    headers: HeaderMap,
+    Query(reason): Query<AuditReason>,
+) -> ApiResult<StatusCode> {
+    let admin_id = require_admin(&state, &headers, "manage custom emoji")?;
+    let shortcode = shortcode.to_lowercase();
+
+    let emoji_repo = EmojiRepository::new(state.db.pool.clone());
+    let exists = emoji_repo
+        .exists(&shortcode)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !exists {
+        return Err(ApiError::NotFound("Emoji not found".to_string()));
+    }
+    audit(&state, &admin_id, "emoji.delete", &format!("emoji:{}", shortcode), &reason)?;
+
+    let removed = emoji_repo
+        .delete(&shortcode)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    if removed {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::NotFound("Emoji not found".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_emoji_normalizes_and_rejects_bad_input() {
+        let ok = validate_emoji(CustomEmoji {
+            shortcode: ":Ship_It:".to_string(),
+            replacement: " 🚢 ".to_string(),
+        })
+        .unwrap();
+        assert_eq!(ok.shortcode, "ship_it");
+        assert_eq!(ok.replacement, "🚢");
+
+        for (shortcode, replacement) in [
+            ("has space", "x"),
+            ("", "x"),
+            ("fine", ""),
+            ("fine", "a replacement that is far too long"),
+            ("fine", "line\nbreak"),
+        ] {
+            assert!(validate_emoji(CustomEmoji {
+                shortcode: shortcode.to_string(),
+                replacement: replacement.to_string(),
+            })
+            .is_err());
+        }
+    }
+}
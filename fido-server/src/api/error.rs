@@ -5,6 +5,8 @@ use axum::{
 };
 use fido_types::ErrorResponse;
 
+use crate::sanitize::ContentError;
+
 pub type ApiResult<T> = Result<T, ApiError>;
 
 #[derive(Debug)]
@@ -14,6 +16,8 @@ pub enum ApiError {
     Unauthorized(String),
     Forbidden(String),
     TooManyRequests(String),
+    ServiceUnavailable(String),
+    InvalidContent(ContentError),
     InternalError(String),
 }
 
@@ -25,6 +29,8 @@ impl IntoResponse for ApiError {
             ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "Unauthorized", Some(msg)),
             ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, "Forbidden", Some(msg)),
             ApiError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, "Too Many Requests", Some(msg)),
+            ApiError::ServiceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, "Service Unavailable", Some(msg)),
+            ApiError::InvalidContent(err) => (StatusCode::BAD_REQUEST, "Invalid Content", Some(err.to_string())),
             ApiError::InternalError(msg) => {
                 tracing::error!("Internal error: {}", msg);
                 (
@@ -44,6 +50,12 @@ impl IntoResponse for ApiError {
     }
 }
 
+impl From<ContentError> for ApiError {
+    fn from(err: ContentError) -> Self {
+        ApiError::InvalidContent(err)
+    }
+}
+
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
         ApiError::InternalError(err.to_string())
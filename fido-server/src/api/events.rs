@@ -0,0 +1,162 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    api::{get_user_from_headers, posts, ApiError, ApiResult},
+    db::repositories::{EventRepository, HashtagRepository, PostRepository},
+    sanitize::{sanitize_content, ContentKind},
+    state::AppState,
+};
+use fido_types::{CreateEventRequest, Post, PostKind, ReplyPolicy, RsvpRequest};
+
+/// Most events listed by `GET /events`
+const UPCOMING_EVENTS_LIMIT: i32 = 50;
+
+/// Titles and locations are shown on one line
+fn single_line(kind: ContentKind, input: &str) -> Result<String, ApiError> {
+    let text = sanitize_content(kind, input)?;
+    Ok(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Fill in `my_rsvp` on the events among `posts`
+pub(crate) fn attach_my_rsvps(state: &AppState, posts: &mut [Post], user_id: &Uuid) -> Result<(), ApiError> {
+    let event_repo = EventRepository::new(state.db.pool.clone());
+    for post in posts {
+        if let Some(PostKind::Event(event)) = &mut post.kind {
+            event.my_rsvp = event_repo
+                .rsvp_of(&post.id, user_id)
+                .map_err(|e| ApiError::InternalError(e.to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+/// POST /events - Post an event. The text is its description; posting
+/// limits apply as for any post.
+pub async fn create_event(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateEventRequest>,
+) -> ApiResult<Json<Post>> {
+    let title = single_line(ContentKind::EventTitle, &payload.title)?;
+    let location = single_line(ContentKind::EventLocation, &payload.location)?;
+    let content = sanitize_content(ContentKind::Post, &payload.content)?;
+    if payload.starts_at <= Utc::now() {
+        return Err(ApiError::BadRequest("Events must start in the future".to_string()));
+    }
+    let author_id = get_user_from_headers(&state, &headers)?;
+
+    posts::check_post_rate_limit(&state, &author_id)?;
+    posts::check_daily_post_quota(&state, &author_id)?;
+    let screened = format!("{}\n{}\n{}", title, location, content);
+    let verdict = posts::screen_content(&state, &author_id, &screened).await?;
+
+    let mut post = posts::publish_post(&state, author_id, content, false, ReplyPolicy::Everyone)?;
+    EventRepository::new(state.db.pool.clone())
+        .create(&post.id, &title, payload.starts_at, &location)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    posts::queue_for_moderation(&state, &post.id, verdict.as_ref())?;
+
+    post.kind = Some(PostKind::Event(fido_types::EventDetails {
+        title,
+        starts_at: payload.starts_at,
+        location,
+        going_count: 0,
+        interested_count: 0,
+        my_rsvp: None,
+    }));
+    Ok(Json(post))
+}
+
+/// GET /events - Upcoming events from the caller, people and hashtags they
+/// follow, and events they answered; soonest first
+pub async fn upcoming_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<Post>>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let pool = state.db.pool.clone();
+    let hashtag_repo = HashtagRepository::new(pool.clone());
+
+    let mut events = PostRepository::new(pool)
+        .upcoming_events(&user_id, Utc::now(), UPCOMING_EVENTS_LIMIT)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    posts::hide_limited_posts(&state, &mut events, Some(user_id))?;
+    for event in &mut events {
+        event.hashtags = hashtag_repo
+            .get_by_post(&event.id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    }
+    attach_my_rsvps(&state, &mut events, &user_id)?;
+    Ok(Json(events))
+}
+
+fn event_id(state: &AppState, post_id: &str) -> Result<Uuid, ApiError> {
+    let post_id =
+        Uuid::parse_str(post_id).map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+    let is_event = EventRepository::new(state.db.pool.clone())
+        .is_event(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if is_event {
+        Ok(post_id)
+    } else {
+        Err(ApiError::NotFound("Event not found".to_string()))
+    }
+}
+
+/// PUT /posts/:id/rsvp - Answer `going` or `interested`, replacing any
+/// earlier answer
+pub async fn set_rsvp(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+    Json(payload): Json<RsvpRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let post_id = event_id(&state, &post_id)?;
+
+    EventRepository::new(state.db.pool.clone())
+        .set_rsvp(&post_id, &user_id, payload.status)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "rsvp": payload.status })))
+}
+
+/// DELETE /posts/:id/rsvp - Withdraw an RSVP
+pub async fn clear_rsvp(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let post_id = event_id(&state, &post_id)?;
+
+    let removed = EventRepository::new(state.db.pool.clone())
+        .clear_rsvp(&post_id, &user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !removed {
+        return Err(ApiError::NotFound("No RSVP to withdraw".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "rsvp": null })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_titles_and_locations_fit_on_one_line() {
+        assert_eq!(
+            single_line(ContentKind::EventTitle, "Rust\nmeetup  #3").unwrap(),
+            "Rust meetup #3"
+        );
+        assert!(single_line(ContentKind::EventLocation, "   ").is_err());
+        assert!(single_line(ContentKind::EventTitle, &"x".repeat(101)).is_err());
+    }
+}
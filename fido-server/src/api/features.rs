@@ -0,0 +1,41 @@
+use axum::{extract::State, http::HeaderMap, Json};
+
+use crate::{
+    api::{posts::posts_remaining_today, ApiResult},
+    state::AppState,
+};
+use fido_types::InstanceFeatures;
+
+/// GET /features - Instance limits, plus the caller's remaining daily posts
+///
+/// Works without a session; a valid `X-Session-Token` adds
+/// `posts_remaining_today`.
+pub async fn get_features(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<InstanceFeatures>> {
+    let limits = &state.limits;
+    let nonzero = |value: u32| (value > 0).then_some(value);
+
+    let caller = headers
+        .get("X-Session-Token")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|token| state.get_authenticated_user_id_from_token(token));
+    let posts_remaining_today = match caller {
+        Some(user_id) => posts_remaining_today(&state, &user_id)?,
+        None => None,
+    };
+
+    Ok(Json(InstanceFeatures {
+        max_posts_per_day: nonzero(limits.max_posts_per_day),
+        post_retention_days: nonzero(limits.post_retention_days),
+        posts_remaining_today,
+        translation_enabled: state.translation.enabled(),
+        login_pow_difficulty: nonzero(limits.login_pow_difficulty),
+        anonymous_posting: state.anonymous_posts.enabled,
+        max_anonymous_posts_per_day: nonzero(state.anonymous_posts.max_per_day)
+            .filter(|_| state.anonymous_posts.enabled),
+        leaderboard: state.leaderboard.enabled,
+        contributor_leaderboard: state.leaderboard.enabled && state.leaderboard.rank_contributors,
+    }))
+}
@@ -1,29 +1,20 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     Json,
 };
+use fido_types::{Badge, GitHubSuggestion, UserStatus};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-    api::{ApiError, ApiResult},
-    db::repositories::{FriendRepository, PostRepository, UserRepository},
+    api::{get_user_from_headers, ApiError, ApiResult},
+    db::repositories::{
+        Connections, FriendRepository, GitHubFollowingRepository, UserRepository,
+    },
     state::AppState,
 };
 
-/// Extract user ID from session token header
-fn get_user_from_headers(state: &AppState, headers: &HeaderMap) -> Result<Uuid, ApiError> {
-    let token = headers
-        .get("X-Session-Token")
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
-
-    state
-        .get_authenticated_user_id_from_token(token)
-        .ok_or_else(|| ApiError::Unauthorized("Invalid session token".to_string()))
-}
-
 /// Extract optional user ID from session token header (for public endpoints)
 fn get_optional_user_from_headers(state: &AppState, headers: &HeaderMap) -> Option<Uuid> {
     let token = headers.get("X-Session-Token")?.to_str().ok()?;
@@ -44,7 +35,7 @@ pub struct UserSearchResponse {
 
 pub async fn search_users(
     State(state): State<AppState>,
-    axum::extract::Query(query): axum::extract::Query<SearchQuery>,
+    Query(query): Query<SearchQuery>,
 ) -> ApiResult<Json<Vec<UserSearchResponse>>> {
     let user_repo = UserRepository::new(state.db.pool.clone());
 
@@ -92,6 +83,11 @@ pub struct UserProfileResponse {
     pub following_count: usize,
     pub post_count: usize,
     pub relationship: RelationshipStatus,
+    pub badges: Vec<Badge>,
+    pub status: Option<UserStatus>,
+    /// Where the account has moved to, if it left this instance
+    pub moved_to: Option<String>,
+    pub karma: i32,
 }
 
 #[derive(Debug, Serialize)]
@@ -126,25 +122,11 @@ pub async fn get_user_profile(
         .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
 
     let friend_repo = FriendRepository::new(state.db.pool.clone());
-    let post_repo = PostRepository::new(state.db.pool.clone());
-
-    let follower_count = friend_repo
-        .get_follower_count(&profile_user_id)
-        .unwrap_or(0);
-    let following_count = friend_repo
-        .get_following_count(&profile_user_id)
-        .unwrap_or(0);
-
-    // Count posts by this user
-    let post_count = post_repo
-        .get_posts(fido_types::SortOrder::Newest, 1000)
-        .map(|posts| {
-            posts
-                .iter()
-                .filter(|p| p.author_id == profile_user_id)
-                .count()
-        })
-        .unwrap_or(0);
+
+    let counts = state
+        .profile_counts(&profile_user_id)
+        .map_err(|e| ApiError::InternalError(format!("Failed to count: {}", e)))?
+        .unwrap_or_default();
 
     // Determine relationship status
     let relationship = if let Some(viewer) = viewer_id {
@@ -177,10 +159,14 @@ pub async fn get_user_profile(
         username: user.username,
         bio: user.bio,
         join_date: user.join_date.to_rfc3339(),
-        follower_count,
-        following_count,
-        post_count,
+        follower_count: counts.follower_count,
+        following_count: counts.following_count,
+        post_count: counts.post_count,
         relationship,
+        badges: user.badges,
+        status: user_repo.get_status(&profile_user_id).unwrap_or(None),
+        moved_to: user_repo.get_moved_to(&profile_user_id).unwrap_or(None),
+        karma: user.karma,
     }))
 }
 
@@ -210,6 +196,7 @@ pub async fn follow_user(
     friend_repo
         .follow_user(&follower_id, &following_id)
         .map_err(|e| ApiError::InternalError(format!("Failed to follow user: {}", e)))?;
+    state.profile_cache.invalidate(&[follower_id, following_id]);
 
     Ok(StatusCode::OK)
 }
@@ -233,6 +220,7 @@ pub async fn unfollow_user(
     if rows_deleted == 0 {
         return Err(ApiError::NotFound("Not following this user".to_string()));
     }
+    state.profile_cache.invalidate(&[follower_id, following_id]);
 
     Ok(StatusCode::OK)
 }
@@ -246,96 +234,142 @@ pub struct SocialUserResponse {
     pub following_count: usize,
 }
 
-pub async fn get_following_list(
-    State(state): State<AppState>,
-    headers: HeaderMap,
+/// Paging and search for the social lists: `?limit=50&cursor=<last user id>&q=bo`.
+/// Without `limit` the whole list comes back, as before
+#[derive(Debug, Default, Deserialize)]
+pub struct SocialListQuery {
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub cursor: Option<Uuid>,
+    #[serde(default)]
+    pub q: Option<String>,
+}
+
+const MAX_SOCIAL_PAGE: usize = 200;
+
+/// One page of the caller's connections with their counts
+fn list_social(
+    state: &AppState,
+    headers: &HeaderMap,
+    query: &SocialListQuery,
+    connections: Connections,
 ) -> ApiResult<Json<Vec<SocialUserResponse>>> {
-    let user_id = get_user_from_headers(&state, &headers)?;
-    
+    let user_id = get_user_from_headers(state, headers)?;
+
     let friend_repo = FriendRepository::new(state.db.pool.clone());
     let user_repo = UserRepository::new(state.db.pool.clone());
-    
-    let following_ids = friend_repo.get_following(&user_id)
-        .map_err(|e| ApiError::InternalError(format!("Failed to get following: {}", e)))?;
-    
+
+    let ids = friend_repo
+        .list_connections(
+            &user_id,
+            connections,
+            query.q.as_deref(),
+            query.cursor.as_ref(),
+            query.limit.map(|limit| limit.clamp(1, MAX_SOCIAL_PAGE)),
+        )
+        .map_err(|e| ApiError::InternalError(format!("Failed to list connections: {}", e)))?;
+
     let mut users = Vec::new();
-    for user_id in following_ids {
+    for user_id in ids {
         if let Ok(Some(user)) = user_repo.find_by_id(&user_id) {
-            let follower_count = friend_repo.get_follower_count(&user_id).unwrap_or(0);
-            let following_count = friend_repo.get_following_count(&user_id).unwrap_or(0);
-            
+            let counts = state.profile_counts(&user_id).ok().flatten().unwrap_or_default();
+
             users.push(SocialUserResponse {
                 id: user.id.to_string(),
                 username: user.username,
-                follower_count,
-                following_count,
+                follower_count: counts.follower_count,
+                following_count: counts.following_count,
             });
         }
     }
-    
+
     Ok(Json(users))
 }
 
+pub async fn get_following_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<SocialListQuery>,
+) -> ApiResult<Json<Vec<SocialUserResponse>>> {
+    list_social(&state, &headers, &query, Connections::Following)
+}
+
 /// GET /social/followers - Get list of users following the current user
 pub async fn get_followers_list(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Query(query): Query<SocialListQuery>,
 ) -> ApiResult<Json<Vec<SocialUserResponse>>> {
+    list_social(&state, &headers, &query, Connections::Followers)
+}
+
+/// GET /social/github-suggestions - Fido users the caller follows on GitHub
+/// (as of their last GitHub login) and doesn't follow here yet
+pub async fn get_github_suggestions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<GitHubSuggestion>>> {
     let user_id = get_user_from_headers(&state, &headers)?;
-    
+
+    let suggestions = GitHubFollowingRepository::new(state.db.pool.clone())
+        .suggestions(&user_id)
+        .map_err(|e| ApiError::InternalError(format!("Failed to get suggestions: {}", e)))?;
+    Ok(Json(suggestions))
+}
+
+/// POST /social/github-suggestions/follow-all - Follow every suggestion
+pub async fn follow_github_suggestions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let suggestions = GitHubFollowingRepository::new(state.db.pool.clone())
+        .suggestions(&user_id)
+        .map_err(|e| ApiError::InternalError(format!("Failed to get suggestions: {}", e)))?;
     let friend_repo = FriendRepository::new(state.db.pool.clone());
-    let user_repo = UserRepository::new(state.db.pool.clone());
-    
-    let follower_ids = friend_repo.get_followers(&user_id)
-        .map_err(|e| ApiError::InternalError(format!("Failed to get followers: {}", e)))?;
-    
-    let mut users = Vec::new();
-    for user_id in follower_ids {
-        if let Ok(Some(user)) = user_repo.find_by_id(&user_id) {
-            let follower_count = friend_repo.get_follower_count(&user_id).unwrap_or(0);
-            let following_count = friend_repo.get_following_count(&user_id).unwrap_or(0);
-            
-            users.push(SocialUserResponse {
-                id: user.id.to_string(),
-                username: user.username,
-                follower_count,
-                following_count,
-            });
-        }
+    let mut followed = vec![user_id];
+    for suggestion in &suggestions {
+        friend_repo
+            .follow_user(&user_id, &suggestion.user_id)
+            .map_err(|e| ApiError::InternalError(format!("Failed to follow user: {}", e)))?;
+        followed.push(suggestion.user_id);
     }
-    
-    Ok(Json(users))
+    state.profile_cache.invalidate(&followed);
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": format!("Followed {} people from GitHub", suggestions.len()),
+        "followed": suggestions.len()
+    })))
+}
+
+/// POST /social/github-suggestions/dismiss - Stop suggesting the current
+/// matches; people who join later are still suggested
+pub async fn dismiss_github_suggestions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    GitHubFollowingRepository::new(state.db.pool.clone())
+        .dismiss(&user_id)
+        .map_err(|e| ApiError::InternalError(format!("Failed to dismiss suggestions: {}", e)))?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Suggestions dismissed"
+    })))
 }
 
 /// GET /social/mutual - Get list of mutual friends
 pub async fn get_mutual_friends_list(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Query(query): Query<SocialListQuery>,
 ) -> ApiResult<Json<Vec<SocialUserResponse>>> {
-    let user_id = get_user_from_headers(&state, &headers)?;
-    
-    let friend_repo = FriendRepository::new(state.db.pool.clone());
-    let user_repo = UserRepository::new(state.db.pool.clone());
-    
-    let friend_ids = friend_repo.get_mutual_friends(&user_id)
-        .map_err(|e| ApiError::InternalError(format!("Failed to get mutual friends: {}", e)))?;
-    
-    let mut users = Vec::new();
-    for user_id in friend_ids {
-        if let Ok(Some(user)) = user_repo.find_by_id(&user_id) {
-            let follower_count = friend_repo.get_follower_count(&user_id).unwrap_or(0);
-            let following_count = friend_repo.get_following_count(&user_id).unwrap_or(0);
-            
-            users.push(SocialUserResponse {
-                id: user.id.to_string(),
-                username: user.username,
-                follower_count,
-                following_count,
-            });
-        }
-    }
-    
-    Ok(Json(users))
+    list_social(&state, &headers, &query, Connections::Mutual)
 }
 
 #[cfg(test)]
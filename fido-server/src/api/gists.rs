@@ -0,0 +1,58 @@
+use axum::{extract::State, http::HeaderMap, Json};
+
+use crate::{
+    api::{get_user_from_headers, posts, ApiError, ApiResult},
+    db::repositories::GitHubTokenRepository,
+    gist::{self, GistError},
+    sanitize::{sanitize_content, ContentKind, MAX_POST_CHARS},
+    state::AppState,
+};
+use fido_types::{CreateGistPostRequest, Post, ReplyPolicy};
+
+fn needs_gist_login() -> ApiError {
+    ApiError::Forbidden(
+        "Log in with GitHub again and allow gists to post text this long".to_string(),
+    )
+}
+
+/// POST /gists - Publish text too long for a post as a secret gist on the
+/// author's GitHub account, then post its opening words and the link.
+/// Screening and posting limits apply as for any post; 403 until the author
+/// has granted the gist scope at GitHub login.
+pub async fn create_gist_post(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateGistPostRequest>,
+) -> ApiResult<Json<Post>> {
+    let content = sanitize_content(ContentKind::LongPost, &payload.content)?;
+    let author_id = get_user_from_headers(&state, &headers)?;
+
+    posts::check_post_rate_limit(&state, &author_id)?;
+    posts::check_daily_post_quota(&state, &author_id)?;
+    let tokens = GitHubTokenRepository::new(state.db.pool.clone());
+    let access_token = tokens
+        .get(&author_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(needs_gist_login)?;
+    // Screen the whole text before it's published anywhere
+    let verdict = posts::screen_content(&state, &author_id, &content).await?;
+
+    let url = match gist::create(&access_token, &content).await {
+        Ok(url) => url,
+        Err(GistError::TokenRejected) => {
+            tokens
+                .delete(&author_id)
+                .map_err(|e| ApiError::InternalError(e.to_string()))?;
+            return Err(needs_gist_login());
+        }
+        Err(e) => {
+            tracing::warn!("Creating a gist for {} failed: {}", author_id, e);
+            return Err(ApiError::ServiceUnavailable("GitHub is unavailable".to_string()));
+        }
+    };
+
+    let summary = sanitize_content(ContentKind::Post, &gist::summarize(&content, &url, MAX_POST_CHARS))?;
+    let post = posts::publish_post(&state, author_id, summary, false, ReplyPolicy::Everyone)?;
+    posts::queue_for_moderation(&state, &post.id, verdict.as_ref())?;
+    Ok(Json(post))
+}
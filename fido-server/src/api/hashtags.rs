@@ -4,26 +4,13 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 use crate::{
-    api::{ApiError, ApiResult},
+    api::{get_user_from_headers, ApiError, ApiResult},
     db::repositories::HashtagRepository,
     state::AppState,
 };
 
-/// Extract user ID from session token header
-fn get_user_from_headers(state: &AppState, headers: &HeaderMap) -> Result<Uuid, ApiError> {
-    let token = headers
-        .get("X-Session-Token")
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
-    
-    state
-        .get_authenticated_user_id_from_token(token)
-        .ok_or_else(|| ApiError::Unauthorized("Invalid session token".to_string()))
-}
-
 #[derive(Debug, Deserialize)]
 pub struct SearchQuery {
     pub q: String,
@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::{
+        get_user_from_headers,
+        posts::{self, GetPostsQuery},
+        ApiError, ApiResult,
+    },
+    db::repositories::JobRepository,
+    sanitize::{sanitize_content, sanitize_link, ContentKind},
+    state::AppState,
+};
+use fido_types::{CreateJobRequest, JobDetails, Post, PostKind, ReplyPolicy, WorkMode};
+
+/// Titles and company names are shown on one line
+fn single_line(kind: ContentKind, input: &str) -> Result<String, ApiError> {
+    let text = sanitize_content(kind, input)?;
+    Ok(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// POST /jobs - Post a job opening. The text describes the role; posting
+/// limits apply as for any post.
+pub async fn create_job(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateJobRequest>,
+) -> ApiResult<Json<Post>> {
+    let job = JobDetails {
+        title: single_line(ContentKind::JobTitle, &payload.title)?,
+        company: single_line(ContentKind::Company, &payload.company)?,
+        work_mode: payload.work_mode,
+        url: sanitize_link(ContentKind::JobUrl, &payload.url)?,
+    };
+    let content = sanitize_content(ContentKind::Post, &payload.content)?;
+    let author_id = get_user_from_headers(&state, &headers)?;
+
+    posts::check_post_rate_limit(&state, &author_id)?;
+    posts::check_daily_post_quota(&state, &author_id)?;
+    let screened = format!("{}\n{}\n{}\n{}", job.title, job.company, job.url, content);
+    let verdict = posts::screen_content(&state, &author_id, &screened).await?;
+
+    let mut post = posts::publish_post(&state, author_id, content, false, ReplyPolicy::Everyone)?;
+    JobRepository::new(state.db.pool.clone())
+        .create(&post.id, &job)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    posts::queue_for_moderation(&state, &post.id, verdict.as_ref())?;
+
+    post.kind = Some(PostKind::Job(job));
+    Ok(Json(post))
+}
+
+#[derive(Deserialize)]
+pub struct JobsQuery {
+    #[serde(default)]
+    work_mode: Option<WorkMode>,
+}
+
+/// GET /jobs - Job posts, newest first; takes `limit` and `sort` like
+/// `GET /posts`, and `work_mode=remote|onsite` to list only one kind.
+pub async fn get_jobs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(filter): Query<JobsQuery>,
+    Query(mut query): Query<GetPostsQuery>,
+) -> ApiResult<Json<Vec<Post>>> {
+    query.jobs = true;
+    query.work_mode = filter.work_mode;
+    posts::get_posts(State(state), headers, Query(query)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_fields_fit_on_one_line() {
+        assert_eq!(
+            single_line(ContentKind::JobTitle, "Senior\nRust   Engineer").unwrap(),
+            "Senior Rust Engineer"
+        );
+        assert!(single_line(ContentKind::Company, " \n ").is_err());
+        assert!(single_line(ContentKind::Company, &"x".repeat(101)).is_err());
+    }
+}
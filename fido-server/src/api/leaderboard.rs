@@ -0,0 +1,89 @@
+use axum::{extract::State, http::HeaderMap, Json};
+use chrono::{Duration, Utc};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::{
+    api::{get_user_from_headers, posts, ApiError, ApiResult},
+    db::repositories::{HashtagRepository, MuteRepository, PostRepository, UserRepository},
+    state::AppState,
+};
+use fido_types::{Post, TopContributor};
+
+/// The leaderboard looks back this many days
+const LEADERBOARD_DAYS: i64 = 7;
+
+/// Most posts or people listed
+const LEADERBOARD_LIMIT: usize = 10;
+
+fn check_enabled(state: &AppState) -> Result<(), ApiError> {
+    if state.leaderboard.enabled {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound(
+            "This instance doesn't have a leaderboard".to_string(),
+        ))
+    }
+}
+
+/// People the caller muted, who stay off their leaderboard
+fn muted_by(state: &AppState, user_id: &Uuid) -> Result<HashSet<Uuid>, ApiError> {
+    MuteRepository::new(state.db.pool.clone())
+        .muted_ids(user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))
+}
+
+/// GET /leaderboard/posts - The best-voted top-level posts of the past week,
+/// leaving out channel and anonymous posts and people the caller muted
+pub async fn top_posts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<Post>>> {
+    check_enabled(&state)?;
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let muted = muted_by(&state, &user_id)?;
+
+    let now = Utc::now();
+    // Fetch enough that dropping muted authors still fills the list
+    let limit = (LEADERBOARD_LIMIT + muted.len()) as i32;
+    let mut posts = PostRepository::new(state.db.pool.clone())
+        .top_posts_between(now - Duration::days(LEADERBOARD_DAYS), now, limit)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    posts.retain(|post| !muted.contains(&post.author_id));
+    posts::hide_limited_posts(&state, &mut posts, Some(user_id))?;
+    posts.truncate(LEADERBOARD_LIMIT);
+
+    let hashtag_repo = HashtagRepository::new(state.db.pool.clone());
+    for post in &mut posts {
+        post.hashtags = hashtag_repo
+            .get_by_post(&post.id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    }
+    Ok(Json(posts))
+}
+
+/// GET /leaderboard/contributors - Who earned the most karma over the past
+/// week, leaving out people the caller muted; 404 where the instance
+/// doesn't rank people
+pub async fn top_contributors(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<TopContributor>>> {
+    check_enabled(&state)?;
+    if !state.leaderboard.rank_contributors {
+        return Err(ApiError::NotFound(
+            "This instance doesn't rank contributors".to_string(),
+        ));
+    }
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let muted = muted_by(&state, &user_id)?;
+
+    let now = Utc::now();
+    let limit = (LEADERBOARD_LIMIT + muted.len()) as i32;
+    let mut contributors = UserRepository::new(state.db.pool.clone())
+        .top_contributors_between(now - Duration::days(LEADERBOARD_DAYS), now, limit)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    contributors.retain(|contributor| !muted.contains(&contributor.user_id));
+    contributors.truncate(LEADERBOARD_LIMIT);
+    Ok(Json(contributors))
+}
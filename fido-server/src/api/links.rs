@@ -0,0 +1,112 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::{get_user_from_headers, ApiError, ApiResult},
+    gist,
+    github_card::{self, CardError},
+    link_preview,
+    oauth::GitHubOAuthConfig,
+    state::AppState,
+};
+use fido_types::{Gist, GitHubCard, LinkPreview};
+
+#[derive(Debug, Deserialize)]
+pub struct LinkPreviewQuery {
+    pub url: String,
+}
+
+/// GET /links/preview?url= - Resolve a link before the client opens it
+///
+/// Requires a session so the server can't be used as an anonymous redirect
+/// prober.
+pub async fn preview_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<LinkPreviewQuery>,
+) -> ApiResult<Json<LinkPreview>> {
+    get_user_from_headers(&state, &headers)?;
+
+    let preview = link_preview::preview(&query.url, &state.blocked_domains)
+        .await
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(preview))
+}
+
+/// GET /links/github?url= - Card for a GitHub repository, issue or pull
+/// request linked from a post: stars, or title and status
+///
+/// 404 for other links and for things GitHub doesn't have (or won't show),
+/// 503 when GitHub can't be reached or the rate limit ran out.
+pub async fn github_card(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<LinkPreviewQuery>,
+) -> ApiResult<Json<GitHubCard>> {
+    get_user_from_headers(&state, &headers)?;
+
+    let reference = github_card::parse_github_url(&query.url).ok_or_else(|| {
+        ApiError::NotFound("Not a GitHub repository, issue or pull request link".to_string())
+    })?;
+    let card = match state.github_cards.get(&reference) {
+        Some(card) => card,
+        None => {
+            let oauth = GitHubOAuthConfig::from_env().ok();
+            let credentials = oauth.as_ref().and_then(|oauth| oauth.app_credentials());
+            let card = github_card::fetch(&reference, credentials)
+                .await
+                .map_err(|e| match e {
+                    CardError::Unavailable(_) => {
+                        tracing::warn!("GitHub card for {} failed: {}", query.url, e);
+                        ApiError::ServiceUnavailable("GitHub is unavailable".to_string())
+                    }
+                })?;
+            state.github_cards.insert(reference, card.clone());
+            card
+        }
+    };
+
+    let mut card = card.ok_or_else(|| ApiError::NotFound("Not found on GitHub".to_string()))?;
+    card.url = query.url;
+    Ok(Json(card))
+}
+
+/// GET /links/gist?url= - A gist linked from a post, with its files cut
+/// down for reading inline
+///
+/// 404 for other links and gists that don't exist, 503 when GitHub can't be
+/// reached.
+pub async fn gist(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<LinkPreviewQuery>,
+) -> ApiResult<Json<Gist>> {
+    get_user_from_headers(&state, &headers)?;
+
+    let id = gist::parse_gist_url(&query.url)
+        .ok_or_else(|| ApiError::NotFound("Not a gist link".to_string()))?;
+    let gist = match state.gists.get(&id) {
+        Some(gist) => gist,
+        None => {
+            let oauth = GitHubOAuthConfig::from_env().ok();
+            let credentials = oauth.as_ref().and_then(|oauth| oauth.app_credentials());
+            let gist = gist::fetch(&id, credentials).await.map_err(|e| match e {
+                CardError::Unavailable(_) => {
+                    tracing::warn!("Gist {} failed: {}", query.url, e);
+                    ApiError::ServiceUnavailable("GitHub is unavailable".to_string())
+                }
+            })?;
+            state.gists.insert(id, gist.clone());
+            gist
+        }
+    };
+
+    let mut gist = gist.ok_or_else(|| ApiError::NotFound("Not found on GitHub".to_string()))?;
+    gist.url = query.url;
+    Ok(Json(gist))
+}
@@ -1,3 +1,5 @@
+pub mod account;
+pub mod admin;
 pub mod auth;
 pub mod posts;
 pub mod profile;
@@ -6,5 +8,42 @@ pub mod config;
 pub mod error;
 pub mod hashtags;
 pub mod friends;
+pub mod emoji;
+pub mod features;
+pub mod links;
+pub mod digest;
+pub mod drafts;
+pub mod watches;
+pub mod crossposts;
+pub mod permalink;
+pub mod bookmarks;
+pub mod channels;
+pub mod events;
+pub mod reviews;
+pub mod gists;
+pub mod jobs;
+pub mod leaderboard;
+pub mod mutes;
+pub mod version;
 
 pub use error::{ApiError, ApiResult};
+
+use axum::http::HeaderMap;
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+/// Extract user ID from session token header
+pub(crate) fn get_user_from_headers(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<Uuid, ApiError> {
+    let token = headers
+        .get("X-Session-Token")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
+
+    state
+        .get_authenticated_user_id_from_token(token)
+        .ok_or_else(|| ApiError::Unauthorized("Invalid session token".to_string()))
+}
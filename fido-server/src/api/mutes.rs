@@ -0,0 +1,72 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{bookmarks::MAX_BATCH, get_user_from_headers, ApiError, ApiResult},
+    db::repositories::MuteRepository,
+    state::AppState,
+};
+use fido_types::{BatchResult, MuteUsersRequest};
+
+/// GET /mutes - IDs of the users the caller has muted
+pub async fn list_mutes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<Uuid>>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let mut muted: Vec<Uuid> = MuteRepository::new(state.db.pool.clone())
+        .muted_ids(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .into_iter()
+        .collect();
+    muted.sort();
+
+    Ok(Json(muted))
+}
+
+/// POST /mutes/batch - Mute several users at once; their posts leave the
+/// caller's feed
+pub async fn mute_users(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<MuteUsersRequest>,
+) -> ApiResult<Json<BatchResult>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    if payload.user_ids.len() > MAX_BATCH {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} users per request",
+            MAX_BATCH
+        )));
+    }
+
+    let applied = MuteRepository::new(state.db.pool.clone())
+        .mute_many(&user_id, &payload.user_ids)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(BatchResult { applied }))
+}
+
+/// DELETE /mutes/:user_id - Unmute a user
+pub async fn unmute_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(muted_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let muted_id = Uuid::parse_str(&muted_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
+
+    let removed = MuteRepository::new(state.db.pool.clone())
+        .unmute(&user_id, &muted_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !removed {
+        return Err(ApiError::NotFound("User not muted".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "muted": false })))
+}
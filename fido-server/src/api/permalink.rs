@@ -0,0 +1,446 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    response::{Html, IntoResponse},
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{posts::hide_anonymous_authors, ApiError, ApiResult},
+    db::repositories::{ConfigRepository, PostRepository, UserRepository},
+    state::AppState,
+};
+use chrono::{DateTime, Utc};
+use fido_types::{Post, User};
+
+/// Posts listed on a profile page
+const PROFILE_PAGE_POSTS: i32 = 10;
+
+/// Posts listed in /sitemap.xml, newest first; a sitemap holds at most
+/// 50,000 URLs and the rest are profiles
+const SITEMAP_POSTS: usize = 40_000;
+
+/// Longest OpenGraph description; link previews cut off around here anyway
+const PREVIEW_CHARS: usize = 200;
+
+/// Shared by every page: monospace, narrow, no scripts
+const PAGE_STYLE: &str = "body{font-family:monospace;max-width:40rem;margin:3rem auto;padding:0 1rem}\
+     .content{white-space:pre-wrap}.deleted,.meta{color:#888}\
+     ul{list-style:none;padding:0}li{margin:0 0 1rem}a{color:inherit}";
+
+/// What link previews (Slack, Discord, ...) show for a page
+struct OpenGraph<'a> {
+    title: &'a str,
+    description: &'a str,
+    /// `article` for posts, `profile` for people, `website` otherwise
+    kind: &'a str,
+    /// Whether search engines may index the page; the person it belongs to
+    /// decides with their "discoverable" setting
+    indexable: bool,
+}
+
+/// Whether `user_id` lets search engines index their pages
+fn is_discoverable(state: &AppState, user_id: &Uuid) -> Result<bool, ApiError> {
+    ConfigRepository::new(state.db.pool.clone())
+        .get(user_id)
+        .map(|config| config.discoverable)
+        .map_err(|e| ApiError::InternalError(e.to_string()))
+}
+
+/// The instance's address for absolute links: `public_url` when set,
+/// otherwise whatever Host the request came in on
+fn base_url(state: &AppState, headers: &HeaderMap) -> String {
+    if let Some(url) = &state.public_url {
+        return url.clone();
+    }
+    let host = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("localhost");
+    format!("http://{}", host)
+}
+
+/// GET /p/:id - A post as a plain web page, for permalinks shared from the TUI
+///
+/// Posts are public in the API too, so this needs no session.
+pub async fn post_page(
+    State(state): State<AppState>,
+    Path(post_id): Path<String>,
+) -> ApiResult<Html<String>> {
+    let post_id = Uuid::parse_str(&post_id)
+        .map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+
+    let mut post = PostRepository::new(state.db.pool.clone())
+        .get_by_id(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+
+    let indexable = !post.is_anonymous && is_discoverable(&state, &post.author_id)?;
+    hide_anonymous_authors(std::slice::from_mut(&mut post), None);
+    Ok(Html(render_post_page(&post, indexable)))
+}
+
+/// GET /u/:username - A profile as a plain web page with its latest posts
+pub async fn profile_page(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> ApiResult<Html<String>> {
+    let username = username.trim_start_matches('@');
+    let user_repo = UserRepository::new(state.db.pool.clone());
+    let user = user_repo
+        .get_by_username(username)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+    let counts = state
+        .profile_counts(&user.id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .unwrap_or_default();
+    let moved_to = user_repo
+        .get_moved_to(&user.id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let posts = PostRepository::new(state.db.pool.clone())
+        .get_by_user(&user.id, PROFILE_PAGE_POSTS, 0)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let indexable = is_discoverable(&state, &user.id)?;
+
+    Ok(Html(render_profile_page(
+        &user,
+        counts.follower_count,
+        counts.post_count,
+        moved_to.as_deref(),
+        &posts,
+        indexable,
+    )))
+}
+
+/// Accounts that opted in to being found, alphabetically
+fn discoverable_users(state: &AppState) -> Result<Vec<User>, ApiError> {
+    let discoverable = ConfigRepository::new(state.db.pool.clone())
+        .discoverable_user_ids()
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let users = UserRepository::new(state.db.pool.clone())
+        .list_all()
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(users
+        .into_iter()
+        .filter(|user| discoverable.contains(&user.id))
+        .collect())
+}
+
+/// GET /u - Everyone on this instance who chose to be discoverable,
+/// linking to their profile pages
+pub async fn directory_page(State(state): State<AppState>) -> ApiResult<Html<String>> {
+    Ok(Html(render_directory_page(&discoverable_users(&state)?)))
+}
+
+/// GET /robots.txt - Crawlers may read the public pages and nothing else;
+/// pages of people who aren't discoverable also say `noindex` themselves
+pub async fn robots_txt(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        render_robots_txt(&base_url(&state, &headers)),
+    )
+}
+
+/// GET /sitemap.xml - Profile and post pages of discoverable accounts
+pub async fn sitemap(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<impl IntoResponse> {
+    let users = discoverable_users(&state)?;
+    let posts = PostRepository::new(state.db.pool.clone())
+        .discoverable_posts(SITEMAP_POSTS)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok((
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        render_sitemap(&base_url(&state, &headers), &users, &posts),
+    ))
+}
+
+/// A complete page around `body`, with OpenGraph and Twitter card tags
+fn render_page(og: &OpenGraph, body: &str) -> String {
+    let title = escape_html(og.title);
+    let description = escape_html(og.description);
+    let robots = if og.indexable {
+        ""
+    } else {
+        "<meta name=\"robots\" content=\"noindex\">\n"
+    };
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n\
+         {robots}\
+         <meta name=\"description\" content=\"{description}\">\n\
+         <meta property=\"og:site_name\" content=\"Fido\">\n\
+         <meta property=\"og:type\" content=\"{kind}\">\n\
+         <meta property=\"og:title\" content=\"{title}\">\n\
+         <meta property=\"og:description\" content=\"{description}\">\n\
+         <meta name=\"twitter:card\" content=\"summary\">\n\
+         <style>{style}</style>\n\
+         </head>\n\
+         <body>\n\
+         {body}\
+         </body>\n\
+         </html>\n",
+        kind = og.kind,
+        style = PAGE_STYLE,
+    )
+}
+
+/// `text` cut to fit a link preview, on one line
+fn preview(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= PREVIEW_CHARS {
+        return line;
+    }
+    let cut: String = line.chars().take(PREVIEW_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn render_post_page(post: &Post, indexable: bool) -> String {
+    // Tombstones keep their place in a thread, not their author
+    let (title, heading, description) = if post.is_deleted {
+        (
+            "[deleted] on Fido".to_string(),
+            "[deleted]".to_string(),
+            "[deleted]".to_string(),
+        )
+    } else {
+        let author = escape_html(&post.author_username);
+        (
+            format!("@{} on Fido", post.author_username),
+            format!("<a href=\"/u/{author}\">@{author}</a>"),
+            preview(&post.content),
+        )
+    };
+    let body = if post.is_deleted {
+        "<p class=\"deleted\">This post was deleted.</p>".to_string()
+    } else {
+        format!("<p class=\"content\">{}</p>", escape_html(&post.content))
+    };
+    render_page(
+        &OpenGraph {
+            title: &title,
+            description: &description,
+            kind: "article",
+            indexable,
+        },
+        &format!(
+            "<h1>{heading}</h1>\n\
+             {body}\n\
+             <p class=\"meta\">{time} · ↑ {upvotes} ↓ {downvotes} · {replies} replies</p>\n",
+            time = post.created_at.format("%Y-%m-%d %H:%M UTC"),
+            upvotes = post.upvotes,
+            downvotes = post.downvotes,
+            replies = post.reply_count,
+        ),
+    )
+}
+
+fn render_profile_page(
+    user: &User,
+    follower_count: usize,
+    post_count: usize,
+    moved_to: Option<&str>,
+    posts: &[Post],
+    indexable: bool,
+) -> String {
+    let username = escape_html(&user.username);
+    let bio = user.bio.as_deref().filter(|bio| !bio.is_empty());
+
+    let mut body = format!("<h1>@{}</h1>\n", username);
+    if let Some(moved_to) = moved_to {
+        body.push_str(&format!(
+            "<p class=\"meta\">This account has moved to {}</p>\n",
+            escape_html(moved_to)
+        ));
+    }
+    if let Some(bio) = bio {
+        body.push_str(&format!("<p class=\"content\">{}</p>\n", escape_html(bio)));
+    }
+    body.push_str(&format!(
+        "<p class=\"meta\">{} posts · {} followers · joined {}</p>\n<ul>\n",
+        post_count,
+        follower_count,
+        user.join_date.format("%Y-%m-%d"),
+    ));
+    for post in posts {
+        body.push_str(&format!(
+            "<li><p class=\"content\">{}</p><a class=\"meta\" href=\"/p/{}\">{}</a></li>\n",
+            escape_html(&post.content),
+            post.id,
+            post.created_at.format("%Y-%m-%d %H:%M UTC"),
+        ));
+    }
+    body.push_str("</ul>\n");
+
+    let title = format!("@{} on Fido", user.username);
+    let description = match (moved_to, bio) {
+        (Some(moved_to), _) => format!("Moved to {}", moved_to),
+        (None, Some(bio)) => preview(bio),
+        (None, None) => format!("{} posts · {} followers", post_count, follower_count),
+    };
+    render_page(
+        &OpenGraph {
+            title: &title,
+            description: &description,
+            kind: "profile",
+            indexable,
+        },
+        &body,
+    )
+}
+
+fn render_directory_page(users: &[User]) -> String {
+    let mut body = String::from("<h1>People on Fido</h1>\n<ul>\n");
+    for user in users {
+        let username = escape_html(&user.username);
+        body.push_str(&format!("<li><a href=\"/u/{0}\">@{0}</a>", username));
+        if let Some(bio) = user.bio.as_deref().filter(|bio| !bio.is_empty()) {
+            body.push_str(&format!(" <span class=\"meta\">{}</span>", escape_html(&preview(bio))));
+        }
+        body.push_str("</li>\n");
+    }
+    body.push_str("</ul>\n");
+
+    let description = format!("{} people", users.len());
+    render_page(
+        &OpenGraph {
+            title: "People on Fido",
+            description: &description,
+            kind: "website",
+            indexable: true,
+        },
+        &body,
+    )
+}
+
+fn render_robots_txt(base_url: &str) -> String {
+    format!(
+        "User-agent: *\n\
+         Allow: /p/\n\
+         Allow: /u/\n\
+         Allow: /u$\n\
+         Disallow: /\n\
+         \n\
+         Sitemap: {}/sitemap.xml\n",
+        base_url
+    )
+}
+
+fn render_sitemap(base_url: &str, users: &[User], posts: &[(Uuid, DateTime<Utc>)]) -> String {
+    let base_url = escape_html(base_url);
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for user in users {
+        xml.push_str(&format!(
+            "<url><loc>{}/u/{}</loc></url>\n",
+            base_url,
+            escape_html(&user.username)
+        ));
+    }
+    for (id, created_at) in posts {
+        xml.push_str(&format!(
+            "<url><loc>{}/p/{}</loc><lastmod>{}</lastmod></url>\n",
+            base_url,
+            id,
+            created_at.format("%Y-%m-%d")
+        ));
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::test_post;
+
+    #[test]
+    fn test_post_page_escapes_content() {
+        let post = Post {
+            author_username: "alice".to_string(),
+            upvotes: 3,
+            ..test_post(Uuid::new_v4(), "<script>alert('hi')</script> & #rust")
+        };
+
+        let page = render_post_page(&post, true);
+        assert!(page.contains("&lt;script&gt;alert(&#39;hi&#39;)&lt;/script&gt; &amp; #rust"));
+        assert!(!page.contains("<script>"));
+
+        let page = render_post_page(
+            &Post {
+                is_deleted: true,
+                ..post
+            },
+            true,
+        );
+        assert!(page.contains("<h1>[deleted]</h1>"));
+        assert!(!page.contains("alert"));
+        assert!(!page.contains("@alice"));
+    }
+
+    #[test]
+    fn test_pages_carry_opengraph_tags_for_link_previews() {
+        let user = User {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            bio: Some("Rust & \"terminals\"".to_string()),
+            join_date: chrono::Utc::now(),
+            is_test_user: false,
+            badges: Vec::new(),
+            karma: 0,
+        };
+        let post = Post {
+            author_username: "alice".to_string(),
+            ..test_post(user.id, &"x".repeat(300))
+        };
+
+        let page = render_post_page(&post, true);
+        assert!(page.contains("<meta property=\"og:type\" content=\"article\">"));
+        assert!(page.contains("<meta property=\"og:title\" content=\"@alice on Fido\">"));
+        assert!(page.contains(&format!("content=\"{}…\"", "x".repeat(PREVIEW_CHARS - 1))));
+
+        let page = render_profile_page(&user, 3, 1, None, std::slice::from_ref(&post), true);
+        assert!(page.contains("<meta property=\"og:type\" content=\"profile\">"));
+        assert!(page.contains("content=\"Rust &amp; &quot;terminals&quot;\""));
+        assert!(page.contains(&format!("href=\"/p/{}\"", post.id)));
+
+        assert!(!page.contains("noindex"));
+
+        let page = render_profile_page(&user, 3, 1, Some("alice@fido.example"), &[], false);
+        assert!(page.contains("content=\"Moved to alice@fido.example\""));
+        assert!(page.contains("<meta name=\"robots\" content=\"noindex\">"));
+
+        let page = render_directory_page(std::slice::from_ref(&user));
+        assert!(page.contains("<a href=\"/u/alice\">@alice</a>"));
+
+        let sitemap = render_sitemap("https://fido.example", &[user], &[(post.id, post.created_at)]);
+        assert!(sitemap.contains("<loc>https://fido.example/u/alice</loc>"));
+        assert!(sitemap.contains(&format!("<loc>https://fido.example/p/{}</loc>", post.id)));
+        assert!(render_robots_txt("https://fido.example")
+            .contains("Sitemap: https://fido.example/sitemap.xml"));
+    }
+}
@@ -9,27 +9,28 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
-    api::{ApiError, ApiResult},
-    db::repositories::{HashtagRepository, PostRepository, VoteRepository},
+    api::{events, get_user_from_headers, ApiError, ApiResult},
+    content_filter::{most_severe, FilterAction, Verdict},
+    db::repositories::{
+        ChannelRepository, DeleteOutcome, FriendRepository, HashtagRepository, ModerationRepository,
+        MuteRepository, PostRepository, ReviewScope, UserRepository, VoteAudience, VoteRepository,
+    },
     hashtag::extract_hashtags,
+    mention::extract_mentions,
+    sanitize::{sanitize_content, ContentKind},
     state::AppState,
+    translation::{self, TranslateError},
 };
-use fido_types::{CreatePostRequest, Post, SortOrder, VoteDirection, VoteRequest};
-
-/// Extract user ID from session token header
-fn get_user_from_headers(state: &AppState, headers: &HeaderMap) -> Result<Uuid, ApiError> {
-    let token = headers
-        .get("X-Session-Token")
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
-    
-    state
-        .get_authenticated_user_id_from_token(token)
-        .ok_or_else(|| ApiError::Unauthorized("Invalid session token".to_string()))
-}
+use fido_types::{
+    CreatePostRequest, Post, PostTranslation, PostVoteSummary, ReplyPolicy, SortOrder,
+    VoteDirection, VoteRequest, WorkMode,
+};
+
+/// Posts listed under "Related" in the full post view
+const RELATED_POSTS_LIMIT: i32 = 5;
 
 /// Check if user has exceeded post rate limit (1 post per 10 minutes)
-fn check_post_rate_limit(state: &AppState, user_id: &Uuid) -> Result<(), ApiError> {
+pub(crate) fn check_post_rate_limit(state: &AppState, user_id: &Uuid) -> Result<(), ApiError> {
     let conn = state.db.pool.get()
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
     
@@ -68,6 +69,173 @@ fn check_post_rate_limit(state: &AppState, user_id: &Uuid) -> Result<(), ApiErro
     Ok(())
 }
 
+/// Posts a user may still create in the current 24-hour window, or `None`
+/// when the instance has no daily limit
+pub(crate) fn posts_remaining_today(state: &AppState, user_id: &Uuid) -> Result<Option<u32>, ApiError> {
+    let limit = state.limits.max_posts_per_day;
+    if limit == 0 {
+        return Ok(None);
+    }
+    let since = Utc::now() - Duration::days(1);
+    let used = PostRepository::new(state.db.pool.clone())
+        .count_created_since(user_id, since)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Some(limit.saturating_sub(used.max(0) as u32)))
+}
+
+/// Enforce the instance's daily post quota
+pub(crate) fn check_daily_post_quota(state: &AppState, user_id: &Uuid) -> Result<(), ApiError> {
+    if posts_remaining_today(state, user_id)? == Some(0) {
+        return Err(ApiError::TooManyRequests(format!(
+            "Daily limit of {} posts reached. Try again later.",
+            state.limits.max_posts_per_day
+        )));
+    }
+    Ok(())
+}
+
+/// Username everyone but the author sees on an anonymous post
+pub const ANONYMOUS_USERNAME: &str = "anon";
+
+/// Enforce that the instance allows anonymous posts and the user hasn't
+/// used up their share for the day
+fn check_anonymous_post_allowed(state: &AppState, user_id: &Uuid) -> Result<(), ApiError> {
+    let config = &state.anonymous_posts;
+    if !config.enabled {
+        return Err(ApiError::Forbidden(
+            "This instance doesn't allow anonymous posts".to_string(),
+        ));
+    }
+    if config.max_per_day == 0 {
+        return Ok(());
+    }
+    let used = PostRepository::new(state.db.pool.clone())
+        .count_anonymous_since(user_id, Utc::now() - Duration::days(1))
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if used.max(0) as u32 >= config.max_per_day {
+        return Err(ApiError::TooManyRequests(format!(
+            "Daily limit of {} anonymous posts reached. Try again later.",
+            config.max_per_day
+        )));
+    }
+    Ok(())
+}
+
+/// Hide who wrote the anonymous posts among `posts` from everyone but
+/// their author
+pub(crate) fn hide_anonymous_authors(posts: &mut [Post], viewer_id: Option<Uuid>) {
+    for post in posts.iter_mut() {
+        if post.is_anonymous && Some(post.author_id) != viewer_id {
+            post.author_id = Uuid::nil();
+            post.author_username = ANONYMOUS_USERNAME.to_string();
+            post.author_badges.clear();
+            post.author_status = None;
+            post.author_karma = 0;
+        }
+    }
+}
+
+/// Whether `user_id` may reply in the thread started by `root`, under the
+/// root's reply policy. The author can always reply.
+pub(crate) fn may_reply(state: &AppState, root: &Post, user_id: &Uuid) -> Result<bool, ApiError> {
+    if root.author_id == *user_id {
+        return Ok(true);
+    }
+    match root.reply_policy {
+        ReplyPolicy::Everyone => Ok(true),
+        ReplyPolicy::Following => FriendRepository::new(state.db.pool.clone())
+            .is_following(&root.author_id, user_id)
+            .map_err(|e| ApiError::InternalError(e.to_string())),
+        ReplyPolicy::Mentioned => {
+            let user = UserRepository::new(state.db.pool.clone())
+                .get_by_id(user_id)
+                .map_err(|e| ApiError::InternalError(e.to_string()))?;
+            let mentioned = extract_mentions(&root.content);
+            Ok(user.is_some_and(|user| mentioned.contains(&user.username.to_lowercase())))
+        }
+    }
+}
+
+/// Fill in `can_reply` for the viewer on posts that limit replies; nobody
+/// signed out can reply to those
+fn mark_reply_permissions(
+    state: &AppState,
+    posts: &mut [Post],
+    viewer_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    for post in posts.iter_mut() {
+        if post.reply_policy != ReplyPolicy::Everyone {
+            post.can_reply = match viewer_id {
+                Some(viewer_id) => may_reply(state, post, &viewer_id)?,
+                None => false,
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Run content through the instance's filters, and its links past the
+/// author's karma. A reject is returned as an error; a flag or limit comes
+/// back to be queued once the post is stored.
+pub(crate) async fn screen_content(
+    state: &AppState,
+    author_id: &Uuid,
+    content: &str,
+) -> Result<Option<Verdict>, ApiError> {
+    let mut verdict = state.content_filter.check(content).await;
+    if let Some(rule) = state.content_filter.low_karma() {
+        let karma = UserRepository::new(state.db.pool.clone())
+            .get_karma(author_id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?;
+        verdict = most_severe(verdict, rule.check(karma, content));
+    }
+    match verdict {
+        Some(verdict) if verdict.action == FilterAction::Reject => Err(ApiError::BadRequest(format!(
+            "This post was blocked by the server's content filter ({})",
+            verdict.reason
+        ))),
+        verdict => Ok(verdict),
+    }
+}
+
+/// Put a stored post in the moderation queue if a filter flagged it
+pub(crate) fn queue_for_moderation(
+    state: &AppState,
+    post_id: &Uuid,
+    verdict: Option<&Verdict>,
+) -> Result<(), ApiError> {
+    let Some(verdict) = verdict else {
+        return Ok(());
+    };
+    tracing::info!(
+        "Post {} queued for moderation ({}: {})",
+        post_id,
+        verdict.action.as_str(),
+        verdict.reason
+    );
+    ModerationRepository::new(state.db.pool.clone())
+        .record(post_id, verdict)
+        .map_err(|e| ApiError::InternalError(e.to_string()))
+}
+
+/// Drop posts a filter limited, except for their own author, mark which
+/// posts the viewer may reply to, and hide the authors of anonymous posts
+pub(crate) fn hide_limited_posts(
+    state: &AppState,
+    posts: &mut Vec<Post>,
+    viewer_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let limited = ModerationRepository::new(state.db.pool.clone())
+        .limited_post_ids()
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    if !limited.is_empty() {
+        posts.retain(|post| !limited.contains(&post.id) || Some(post.author_id) == viewer_id);
+    }
+    mark_reply_permissions(state, posts, viewer_id)?;
+    hide_anonymous_authors(posts, viewer_id);
+    Ok(())
+}
+
 /// Update the rate limit timestamp after successful post creation
 fn update_post_rate_limit(state: &AppState, user_id: &Uuid) -> Result<(), ApiError> {
     let conn = state.db.pool.get()
@@ -95,6 +263,17 @@ pub struct GetPostsQuery {
     hashtag: Option<String>,
     #[serde(default)]
     username: Option<String>,
+    /// Set by `GET /channels/:name/posts`
+    #[serde(skip)]
+    pub(crate) channel: Option<String>,
+    /// Set by `GET /reviews`
+    #[serde(skip)]
+    pub(crate) reviews: Option<ReviewScope>,
+    /// Set by `GET /jobs`, with the work mode it's narrowed to if any
+    #[serde(skip)]
+    pub(crate) jobs: bool,
+    #[serde(skip)]
+    pub(crate) work_mode: Option<WorkMode>,
 }
 
 fn default_limit() -> i32 {
@@ -110,6 +289,7 @@ pub async fn get_posts(
     let pool = state.db.pool.clone();
     let post_repo = PostRepository::new(pool.clone());
     let hashtag_repo = HashtagRepository::new(pool.clone());
+    let channel_repo = ChannelRepository::new(pool.clone());
     let vote_repo = VoteRepository::new(pool);
 
     // Parse sort order
@@ -119,36 +299,66 @@ pub async fn get_posts(
         .and_then(SortOrder::parse)
         .unwrap_or(SortOrder::Newest);
 
-    // Get posts (filtered by hashtag and/or username if specified)
-    let mut posts = match (&query.hashtag, &query.username) {
-        (Some(hashtag), Some(username)) => {
-            // Both filters: posts must match both criteria
-            post_repo
-                .get_posts_by_hashtag_and_username(hashtag, username, sort_order, query.limit)
-                .map_err(|e| ApiError::InternalError(e.to_string()))?
-        }
-        (Some(hashtag), None) => {
-            // Only hashtag filter
-            post_repo
-                .get_posts_by_hashtag(hashtag, sort_order, query.limit)
-                .map_err(|e| ApiError::InternalError(e.to_string()))?
-        }
-        (None, Some(username)) => {
-            // Only username filter
-            post_repo
-                .get_posts_by_username(username, sort_order, query.limit)
-                .map_err(|e| ApiError::InternalError(e.to_string()))?
-        }
-        (None, None) => {
-            // No filters
-            post_repo
-                .get_posts(sort_order, query.limit)
-                .map_err(|e| ApiError::InternalError(e.to_string()))?
+    // Get posts (a channel's, open review requests, job posts, or filtered
+    // by hashtag and/or username if specified)
+    let mut posts = if let Some(channel) = &query.channel {
+        let channel_id = channel_repo
+            .id_by_name(channel)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?
+            .ok_or_else(|| ApiError::NotFound("Channel not found".to_string()))?;
+        post_repo
+            .get_channel_posts(&channel_id, sort_order, query.limit)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?
+    } else if let Some(scope) = &query.reviews {
+        post_repo
+            .get_open_review_requests(scope, sort_order, query.limit)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?
+    } else if query.jobs {
+        post_repo
+            .get_job_posts(query.work_mode, sort_order, query.limit)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?
+    } else {
+        match (&query.hashtag, &query.username) {
+            (Some(hashtag), Some(username)) => {
+                // Both filters: posts must match both criteria
+                post_repo
+                    .get_posts_by_hashtag_and_username(hashtag, username, sort_order, query.limit)
+                    .map_err(|e| ApiError::InternalError(e.to_string()))?
+            }
+            (Some(hashtag), None) => {
+                // Only hashtag filter
+                post_repo
+                    .get_posts_by_hashtag(hashtag, sort_order, query.limit)
+                    .map_err(|e| ApiError::InternalError(e.to_string()))?
+            }
+            (None, Some(username)) => {
+                // Only username filter
+                post_repo
+                    .get_posts_by_username(username, sort_order, query.limit)
+                    .map_err(|e| ApiError::InternalError(e.to_string()))?
+            }
+            (None, None) => {
+                // No filters
+                post_repo
+                    .get_posts(sort_order, query.limit)
+                    .map_err(|e| ApiError::InternalError(e.to_string()))?
+            }
         }
     };
 
     // Try to get authenticated user (optional for posts endpoint)
     let user_id = get_user_from_headers(&state, &headers).ok();
+    hide_limited_posts(&state, &mut posts, user_id)?;
+
+    // Muted authors stay out of the feed, unless asked for by name
+    if let (Some(uid), None) = (user_id, &query.username) {
+        let muted = MuteRepository::new(state.db.pool.clone())
+            .muted_ids(&uid)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?;
+        if !muted.is_empty() {
+            posts.retain(|post| !muted.contains(&post.author_id));
+        }
+    }
 
     // Track activity if viewing filtered posts and user is authenticated
     if let (Some(ref hashtag), Some(uid)) = (&query.hashtag, user_id) {
@@ -169,6 +379,9 @@ pub async fn get_posts(
             }
         }
     }
+    if let Some(uid) = user_id {
+        events::attach_my_rsvps(&state, &mut posts, &uid)?;
+    }
 
     Ok(Json(posts))
 }
@@ -177,25 +390,42 @@ pub async fn get_posts(
 pub async fn create_post(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Json(payload): Json<CreatePostRequest>,
+    Json(mut payload): Json<CreatePostRequest>,
 ) -> ApiResult<Json<Post>> {
-    // Validate content length
-    if payload.content.is_empty() {
-        return Err(ApiError::BadRequest("Post content cannot be empty".to_string()));
-    }
-    if payload.content.len() > 280 {
-        return Err(ApiError::BadRequest(format!(
-            "Post content exceeds 280 character limit (current: {})",
-            payload.content.len()
-        )));
-    }
+    // Strip terminal escapes, normalize and check length
+    payload.content = sanitize_content(ContentKind::Post, &payload.content)?;
 
     // Get authenticated user from session token
     let author_id = get_user_from_headers(&state, &headers)?;
 
     // Check rate limit (1 post per 10 minutes)
     check_post_rate_limit(&state, &author_id)?;
+    check_daily_post_quota(&state, &author_id)?;
+    if payload.anonymous {
+        check_anonymous_post_allowed(&state, &author_id)?;
+    }
+    let verdict = screen_content(&state, &author_id, &payload.content).await?;
+
+    let post = publish_post(
+        &state,
+        author_id,
+        payload.content,
+        payload.anonymous,
+        payload.reply_policy,
+    )?;
+    queue_for_moderation(&state, &post.id, verdict.as_ref())?;
+    Ok(Json(post))
+}
 
+/// Store an already-sanitized top-level post with its hashtags and start
+/// the author's rate limit window. Callers check the limits first.
+pub(crate) fn publish_post(
+    state: &AppState,
+    author_id: Uuid,
+    content: String,
+    anonymous: bool,
+    reply_policy: ReplyPolicy,
+) -> Result<Post, ApiError> {
     let pool = state.db.pool.clone();
     let post_repo = PostRepository::new(pool.clone());
     let hashtag_repo = HashtagRepository::new(pool.clone());
@@ -208,14 +438,14 @@ pub async fn create_post(
         .ok_or_else(|| ApiError::NotFound("Author not found".to_string()))?;
 
     // Extract hashtags using the new hashtag module
-    let hashtags = extract_hashtags(&payload.content);
+    let hashtags = extract_hashtags(&content);
 
     // Create post
     let post = Post {
         id: Uuid::new_v4(),
         author_id,
         author_username: author.username,
-        content: payload.content,
+        content,
         created_at: Utc::now(),
         upvotes: 0,
         downvotes: 0,
@@ -225,15 +455,25 @@ pub async fn create_post(
         reply_count: 0, // Will be calculated dynamically
         reply_to_user_id: None, // Top-level posts don't reply to anyone
         reply_to_username: None,
+        is_deleted: false,
+        author_badges: author.badges,
+        author_status: None,
+        kind: None,
+        is_anonymous: anonymous,
+        reply_policy,
+        can_reply: true,
+        is_accepted: false,
+        author_karma: author.karma,
     };
 
     // Store post
     post_repo
         .create(&post)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    state.profile_cache.invalidate(&[author_id]);
 
     // Update rate limit timestamp
-    update_post_rate_limit(&state, &author_id)?;
+    update_post_rate_limit(state, &author_id)?;
 
     // Store hashtags and track activity
     if !hashtags.is_empty() {
@@ -241,13 +481,16 @@ pub async fn create_post(
             .store_hashtags(&post.id, &hashtags)
             .map_err(|e| ApiError::InternalError(e.to_string()))?;
         
-        // Track user activity for each hashtag
-        for hashtag in &hashtags {
-            let _ = hashtag_repo.increment_activity(&author_id, hashtag);
+        // Track user activity for each hashtag, unless that would tie an
+        // anonymous post back to its author
+        if !anonymous {
+            for hashtag in &hashtags {
+                let _ = hashtag_repo.increment_activity(&author_id, hashtag);
+            }
         }
     }
 
-    Ok(Json(post))
+    Ok(post)
 }
 
 /// POST /posts/:id/vote - Vote on a post
@@ -273,22 +516,20 @@ pub async fn vote_on_post(
     let post_repo = PostRepository::new(pool.clone());
     let hashtag_repo = HashtagRepository::new(pool);
 
-    // Verify post exists
-    post_repo
+    // Verify post exists and hasn't been deleted
+    let post = post_repo
         .get_by_id(&post_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?
         .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+    if post.is_deleted {
+        return Err(ApiError::BadRequest("Cannot vote on a deleted post".to_string()));
+    }
 
-    // Upsert vote
+    // Upsert vote (triggers keep the post's vote counts in sync)
     vote_repo
         .upsert_vote(&user_id, &post_id, direction)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
 
-    // Update vote counts
-    post_repo
-        .update_vote_counts(&post_id)
-        .map_err(|e| ApiError::InternalError(e.to_string()))?;
-
     // Track hashtag activity for this vote
     let hashtags = hashtag_repo
         .get_by_post(&post_id)
@@ -333,6 +574,7 @@ pub async fn get_replies(
 
     // Try to get authenticated user (optional)
     let user_id = get_user_from_headers(&state, &headers).ok();
+    hide_limited_posts(&state, &mut replies, user_id)?;
 
     // Populate hashtags and user votes for each reply
     for reply in &mut replies {
@@ -356,28 +598,22 @@ pub async fn create_reply(
     State(state): State<AppState>,
     Path(post_id): Path<String>,
     headers: HeaderMap,
-    Json(payload): Json<fido_types::CreateReplyRequest>,
+    Json(mut payload): Json<fido_types::CreateReplyRequest>,
 ) -> ApiResult<Json<Post>> {
     // Parse post ID
     let parent_post_id = Uuid::parse_str(&post_id)
         .map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
 
-    // Validate content length
-    if payload.content.is_empty() {
-        return Err(ApiError::BadRequest("Reply content cannot be empty".to_string()));
-    }
-    if payload.content.len() > 280 {
-        return Err(ApiError::BadRequest(format!(
-            "Reply content exceeds 280 character limit (current: {})",
-            payload.content.len()
-        )));
-    }
+    // Strip terminal escapes, normalize and check length
+    payload.content = sanitize_content(ContentKind::Reply, &payload.content)?;
 
     // Get authenticated user from session token
     let author_id = get_user_from_headers(&state, &headers)?;
 
     // Check rate limit for replies (same as posts - 1 per 10 minutes)
     check_post_rate_limit(&state, &author_id)?;
+    check_daily_post_quota(&state, &author_id)?;
+    let verdict = screen_content(&state, &author_id, &payload.content).await?;
 
     let pool = state.db.pool.clone();
     let post_repo = PostRepository::new(pool.clone());
@@ -389,6 +625,23 @@ pub async fn create_reply(
         .get_by_id(&parent_post_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?
         .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+    if target_post.is_deleted {
+        return Err(ApiError::BadRequest("Cannot reply to a deleted post".to_string()));
+    }
+
+    // The thread's top-level post decides who may reply anywhere in it
+    let root = post_repo
+        .thread_root(&parent_post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+    if !may_reply(&state, &root, &author_id)? {
+        let root_author = if root.is_anonymous {
+            ANONYMOUS_USERNAME
+        } else {
+            root.author_username.as_str()
+        };
+        return Err(ApiError::Forbidden(root.reply_policy.explain(root_author)));
+    }
 
     // Use the actual parent_post_id for true nested replies
     let actual_parent_id = parent_post_id;
@@ -402,9 +655,13 @@ pub async fn create_reply(
     // Extract hashtags using the hashtag module
     let hashtags = extract_hashtags(&payload.content);
 
-    // Determine who is being replied to - always the direct parent's author
-    let reply_to_user_id = Some(target_post.author_id);
-    let reply_to_username = Some(target_post.author_username.clone());
+    // Determine who is being replied to - always the direct parent's author,
+    // unless they posted anonymously
+    let (reply_to_user_id, reply_to_username) = if target_post.is_anonymous {
+        (None, None)
+    } else {
+        (Some(target_post.author_id), Some(target_post.author_username.clone()))
+    };
     
     // Auto-mention the parent author ONLY if replying to a reply (nested reply)
     // Direct replies to the main post should NOT have mentions
@@ -436,12 +693,23 @@ pub async fn create_reply(
         reply_count: 0, // Will be calculated dynamically
         reply_to_user_id,
         reply_to_username,
+        is_deleted: false,
+        author_badges: author.badges,
+        author_status: None,
+        kind: None,
+        is_anonymous: false,
+        reply_policy: ReplyPolicy::Everyone,
+        can_reply: true,
+        is_accepted: false,
+        author_karma: author.karma,
     };
 
     // Store reply
     post_repo
         .create(&reply)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    state.profile_cache.invalidate(&[author_id]);
+    queue_for_moderation(&state, &reply.id, verdict.as_ref())?;
 
     // Update rate limit timestamp (replies count toward rate limit)
     update_post_rate_limit(&state, &author_id)?;
@@ -477,6 +745,10 @@ async fn verify_post_ownership(
         .map_err(|e| ApiError::InternalError(e.to_string()))?
         .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
     
+    if post.is_deleted {
+        return Err(ApiError::NotFound("Post has been deleted".to_string()));
+    }
+    
     if post.author_id != user_id {
         return Err(ApiError::Forbidden("You don't have permission to modify this post".to_string()));
     }
@@ -489,25 +761,19 @@ pub async fn update_post(
     State(state): State<AppState>,
     Path(post_id): Path<String>,
     headers: HeaderMap,
-    Json(payload): Json<fido_types::UpdatePostRequest>,
+    Json(mut payload): Json<fido_types::UpdatePostRequest>,
 ) -> ApiResult<Json<Post>> {
     // Parse post ID
     let post_id = Uuid::parse_str(&post_id)
         .map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
 
-    // Validate content length
-    if payload.content.is_empty() {
-        return Err(ApiError::BadRequest("Post content cannot be empty".to_string()));
-    }
-    if payload.content.len() > 280 {
-        return Err(ApiError::BadRequest(format!(
-            "Post content exceeds 280 character limit (current: {})",
-            payload.content.len()
-        )));
-    }
+    // Strip terminal escapes, normalize and check length
+    payload.content = sanitize_content(ContentKind::Post, &payload.content)?;
 
     // Verify post ownership
     verify_post_ownership(&state, &headers, &post_id).await?;
+    let author_id = get_user_from_headers(&state, &headers)?;
+    let verdict = screen_content(&state, &author_id, &payload.content).await?;
 
     let pool = state.db.pool.clone();
     let post_repo = PostRepository::new(pool.clone());
@@ -546,6 +812,7 @@ pub async fn update_post(
             .store_hashtags(&post_id, &new_hashtags)
             .map_err(|e| ApiError::InternalError(e.to_string()))?;
     }
+    queue_for_moderation(&state, &post_id, verdict.as_ref())?;
 
     Ok(Json(post))
 }
@@ -567,24 +834,22 @@ pub async fn delete_post(
     let post_repo = PostRepository::new(pool.clone());
 
     // Check if post exists
-    post_repo
+    let post = post_repo
         .get_by_id(&post_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?
         .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
 
-    // Delete post (cascade will handle replies, hashtags, and votes)
-    let conn = state.db.pool.get()
+    // Posts with replies are tombstoned so the thread stays intact
+    let outcome = post_repo
+        .delete_post(&post_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
-    
-    conn.execute(
-        "DELETE FROM posts WHERE id = ?",
-        [post_id.to_string()],
-    ).map_err(|e| ApiError::InternalError(e.to_string()))?;
+    state.profile_cache.invalidate(&[post.author_id]);
 
     Ok(Json(serde_json::json!({
         "success": true,
         "message": "Post deleted successfully",
-        "post_id": post_id
+        "post_id": post_id,
+        "tombstoned": outcome == DeleteOutcome::Tombstoned
     })))
 }
 
@@ -615,15 +880,108 @@ pub async fn get_post(
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
 
     // Try to get authenticated user (optional)
-    if let Ok(user_id) = get_user_from_headers(&state, &headers) {
+    let viewer_id = get_user_from_headers(&state, &headers).ok();
+    if let Some(user_id) = viewer_id {
         if let Ok(Some(vote)) = vote_repo.get_vote(&user_id, &post.id) {
             post.user_vote = Some(vote.direction.as_str().to_string());
         }
+        events::attach_my_rsvps(&state, std::slice::from_mut(&mut post), &user_id)?;
     }
+    mark_reply_permissions(&state, std::slice::from_mut(&mut post), viewer_id)?;
+    hide_anonymous_authors(std::slice::from_mut(&mut post), viewer_id);
 
     Ok(Json(post))
 }
 
+/// GET /posts/:id/related - Posts sharing the most hashtags with this one,
+/// then its author's recent posts
+pub async fn get_related_posts(
+    State(state): State<AppState>,
+    Path(post_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<Post>>> {
+    let post_id = Uuid::parse_str(&post_id)
+        .map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+
+    let pool = state.db.pool.clone();
+    let post_repo = PostRepository::new(pool.clone());
+    let hashtag_repo = HashtagRepository::new(pool.clone());
+    let vote_repo = VoteRepository::new(pool);
+
+    post_repo
+        .get_by_id(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+
+    let mut posts = post_repo
+        .get_related(&post_id, RELATED_POSTS_LIMIT)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    let user_id = get_user_from_headers(&state, &headers).ok();
+    hide_limited_posts(&state, &mut posts, user_id)?;
+    for post in &mut posts {
+        post.hashtags = hashtag_repo
+            .get_by_post(&post.id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?;
+        if let Some(uid) = user_id {
+            if let Ok(Some(vote)) = vote_repo.get_vote(&uid, &post.id) {
+                post.user_vote = Some(vote.direction.as_str().to_string());
+            }
+        }
+    }
+
+    Ok(Json(posts))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TranslateQuery {
+    /// Language to translate into; English when omitted
+    pub target: Option<String>,
+}
+
+/// GET /posts/:id/translate?target= - Translate a post with the instance's
+/// translation service
+///
+/// Requires a session so the service can't be used anonymously through this
+/// server. 503 when translation is off or the service fails.
+pub async fn translate_post(
+    State(state): State<AppState>,
+    Path(post_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<TranslateQuery>,
+) -> ApiResult<Json<PostTranslation>> {
+    get_user_from_headers(&state, &headers)?;
+
+    let post_id = Uuid::parse_str(&post_id)
+        .map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+    let target = query.target.unwrap_or_else(|| "en".to_string());
+
+    let post = PostRepository::new(state.db.pool.clone())
+        .get_by_id(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .filter(|post| !post.is_deleted)
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+
+    let translated = translation::translate(&state.translation, &post.content, &target)
+        .await
+        .map_err(|e| match e {
+            TranslateError::InvalidLanguage => ApiError::BadRequest(e.to_string()),
+            TranslateError::Disabled => ApiError::ServiceUnavailable(e.to_string()),
+            TranslateError::Backend(_) => {
+                tracing::warn!("Translating post {} failed: {}", post_id, e);
+                ApiError::ServiceUnavailable("Translation service unavailable".to_string())
+            }
+        })?;
+
+    Ok(Json(PostTranslation {
+        post_id,
+        translated_content: translated.text,
+        source_language: translated.source_language,
+        target_language: translation::normalize_language(&target).unwrap_or(target),
+        provider: translation::provider_name(&state.translation),
+    }))
+}
+
 /// GET /posts/:id/thread - Get a post with all its nested replies in tree structure
 pub async fn get_thread(
     State(state): State<AppState>,
@@ -663,6 +1021,7 @@ pub async fn get_thread(
     let mut replies = post_repo
         .get_replies(&post_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    hide_limited_posts(&state, &mut replies, user_id)?;
 
     // Populate hashtags and user votes for each reply
     for reply in &mut replies {
@@ -683,3 +1042,106 @@ pub async fn get_thread(
         "replies": replies
     })))
 }
+
+/// The thread a reply belongs to, after checking `user_id` started it and
+/// may accept or un-accept answers in it
+fn thread_for_accepting(state: &AppState, reply_id: &str, user_id: &Uuid) -> Result<(Post, Post), ApiError> {
+    let reply_id =
+        Uuid::parse_str(reply_id).map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+    let post_repo = PostRepository::new(state.db.pool.clone());
+    let reply = post_repo
+        .get_by_id(&reply_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+    if reply.parent_post_id.is_none() {
+        return Err(ApiError::BadRequest("Only replies can be accepted".to_string()));
+    }
+    let root = post_repo
+        .thread_root(&reply_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+    if root.author_id != *user_id {
+        return Err(ApiError::Forbidden(
+            "Only the thread's author can accept a reply".to_string(),
+        ));
+    }
+    Ok((root, reply))
+}
+
+/// PUT /posts/:id/accept - Mark a reply as the accepted answer in its
+/// thread, replacing any earlier one; thread author only. Earns the
+/// reply's author karma.
+pub async fn accept_reply(
+    State(state): State<AppState>,
+    Path(post_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let (root, reply) = thread_for_accepting(&state, &post_id, &user_id)?;
+    if reply.is_deleted {
+        return Err(ApiError::BadRequest("Cannot accept a deleted reply".to_string()));
+    }
+    if reply.author_id == user_id {
+        return Err(ApiError::BadRequest("You can't accept your own reply".to_string()));
+    }
+
+    PostRepository::new(state.db.pool.clone())
+        .accept_reply(&root.id, &reply.id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "accepted": reply.id })))
+}
+
+/// DELETE /posts/:id/accept - Take back the accepted answer of the thread
+/// the reply is in; thread author only
+pub async fn unaccept_reply(
+    State(state): State<AppState>,
+    Path(post_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let (root, reply) = thread_for_accepting(&state, &post_id, &user_id)?;
+    if !reply.is_accepted {
+        return Err(ApiError::NotFound("That reply isn't accepted".to_string()));
+    }
+
+    PostRepository::new(state.db.pool.clone())
+        .clear_accepted_reply(&root.id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "accepted": null })))
+}
+
+/// GET /posts/:id/votes - Vote totals for a post.
+///
+/// Everyone gets aggregate counts. Only the post's author additionally sees
+/// voters, and only those who enabled `votes_visible_to_authors`.
+pub async fn get_post_votes(
+    State(state): State<AppState>,
+    Path(post_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<PostVoteSummary>> {
+    // Parse post ID
+    let post_id = Uuid::parse_str(&post_id)
+        .map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+
+    let pool = state.db.pool.clone();
+    let post_repo = PostRepository::new(pool.clone());
+    let vote_repo = VoteRepository::new(pool);
+
+    let post = post_repo
+        .get_by_id(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+
+    let audience = match get_user_from_headers(&state, &headers) {
+        Ok(user_id) if user_id == post.author_id => VoteAudience::PostAuthor,
+        _ => VoteAudience::Public,
+    };
+
+    let summary = vote_repo
+        .get_vote_summary(&post_id, audience)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(summary))
+}
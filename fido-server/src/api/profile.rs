@@ -1,28 +1,19 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::HeaderMap,
     Json,
 };
 use uuid::Uuid;
 
 use crate::{
-    api::{ApiError, ApiResult},
+    api::{get_user_from_headers, ApiError, ApiResult},
     db::repositories::{HashtagRepository, PostRepository, UserRepository, VoteRepository},
+    sanitize::{sanitize_content, ContentKind},
     state::AppState,
+    stats::compute_stats,
 };
-use fido_types::{UpdateBioRequest, UserProfile};
-
-/// Extract user ID from session token header
-fn get_user_from_headers(state: &AppState, headers: &HeaderMap) -> Result<Uuid, ApiError> {
-    let token = headers
-        .get("X-Session-Token")
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| ApiError::Unauthorized("Missing session token".to_string()))?;
-    
-    state
-        .get_authenticated_user_id_from_token(token)
-        .ok_or_else(|| ApiError::Unauthorized("Invalid session token".to_string()))
-}
+use fido_types::{Post, SetStatusRequest, UpdateBioRequest, UserProfile, UserStats, UserStatus};
+use serde::Deserialize;
 
 /// GET /users/:id/profile - Get user profile with stats
 pub async fn get_profile(
@@ -35,25 +26,19 @@ pub async fn get_profile(
 
     let pool = state.db.pool.clone();
     let user_repo = UserRepository::new(pool.clone());
-    let vote_repo = VoteRepository::new(pool.clone());
-    let post_repo = PostRepository::new(pool.clone());
     let hashtag_repo = HashtagRepository::new(pool);
 
-    // Get user
+    // Get user, with their karma
     let user = user_repo
         .get_by_id(&user_id)
         .map_err(|e| ApiError::InternalError(e.to_string()))?
         .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
 
-    // Calculate karma (sum of upvotes on user's posts)
-    let karma = vote_repo
-        .calculate_karma(&user_id)
-        .map_err(|e| ApiError::InternalError(e.to_string()))?;
-
-    // Get post count
-    let post_count = post_repo
-        .get_post_count(&user_id)
-        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let post_count = state
+        .profile_counts(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .unwrap_or_default()
+        .post_count as i32;
 
     // Get most active hashtags (top 5)
     let active_hashtags = hashtag_repo
@@ -65,19 +50,54 @@ pub async fn get_profile(
         .map(|(name, _count)| name)
         .collect();
 
+    let status = user_repo
+        .get_status(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
     let profile = UserProfile {
         user_id: user.id,
         username: user.username,
         bio: user.bio,
-        karma,
+        karma: user.karma,
         post_count,
         join_date: user.join_date,
         recent_hashtags,
+        badges: user.badges,
+        status,
+        moved_to: user_repo
+            .get_moved_to(&user_id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?,
     };
 
     Ok(Json(profile))
 }
 
+/// GET /users/:id/stats - Posts per week, posting streaks, most used
+/// hashtags, votes received and word counts. Anonymous and deleted posts
+/// aren't counted.
+pub async fn get_user_stats(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> ApiResult<Json<UserStats>> {
+    let user_id = Uuid::parse_str(&user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
+
+    let pool = state.db.pool.clone();
+    UserRepository::new(pool.clone())
+        .get_by_id(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+    let history = PostRepository::new(pool.clone())
+        .writing_history(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    let votes = VoteRepository::new(pool)
+        .votes_received(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(compute_stats(&history, votes, chrono::Utc::now())))
+}
+
 /// PUT /users/:id/profile - Update user bio
 pub async fn update_profile(
     State(state): State<AppState>,
@@ -89,6 +109,9 @@ pub async fn update_profile(
     let user_id = Uuid::parse_str(&user_id)
         .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
 
+    // Strip terminal escapes, normalize and check length
+    let bio = sanitize_content(ContentKind::Bio, &payload.bio)?;
+
     // Get authenticated user from session token
     let authenticated_user_id = get_user_from_headers(&state, &headers)?;
 
@@ -110,7 +133,7 @@ pub async fn update_profile(
 
     // Update bio
     user_repo
-        .update_bio(&user_id, &payload.bio)
+        .update_bio(&user_id, &bio)
         .map_err(|e| ApiError::InternalError(e.to_string()))?;
 
     Ok(Json(serde_json::json!({
@@ -119,6 +142,81 @@ pub async fn update_profile(
     })))
 }
 
+/// Longest a status may be set for: 30 days
+const MAX_STATUS_MINUTES: u32 = 30 * 24 * 60;
+
+/// Resolve the `:id` path segment and make sure it's the caller's own account
+fn own_user_id(state: &AppState, headers: &HeaderMap, user_id: &str) -> Result<Uuid, ApiError> {
+    let user_id = Uuid::parse_str(user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
+    if get_user_from_headers(state, headers)? != user_id {
+        return Err(ApiError::Unauthorized(
+            "You can only change your own status".to_string(),
+        ));
+    }
+    Ok(user_id)
+}
+
+/// PUT /users/:id/status - Set the caller's status
+pub async fn set_status(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<SetStatusRequest>,
+) -> ApiResult<Json<UserStatus>> {
+    let user_id = own_user_id(&state, &headers, &user_id)?;
+
+    let text = sanitize_content(ContentKind::Status, &payload.text)?;
+    let emoji = payload
+        .emoji
+        .filter(|emoji| !emoji.trim().is_empty())
+        .map(|emoji| sanitize_content(ContentKind::StatusEmoji, &emoji))
+        .transpose()?;
+    let expires_at = match payload.expires_in_minutes {
+        Some(0) => {
+            return Err(ApiError::BadRequest(
+                "expires_in_minutes must be positive".to_string(),
+            ))
+        }
+        Some(minutes) if minutes > MAX_STATUS_MINUTES => {
+            return Err(ApiError::BadRequest(format!(
+                "A status can last at most {} minutes",
+                MAX_STATUS_MINUTES
+            )))
+        }
+        Some(minutes) => Some(chrono::Utc::now() + chrono::Duration::minutes(minutes as i64)),
+        None => None,
+    };
+
+    let status = UserStatus {
+        text,
+        emoji,
+        expires_at,
+    };
+    UserRepository::new(state.db.pool.clone())
+        .set_status(&user_id, &status)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(status))
+}
+
+/// DELETE /users/:id/status - Clear the caller's status
+pub async fn clear_status(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = own_user_id(&state, &headers, &user_id)?;
+
+    UserRepository::new(state.db.pool.clone())
+        .clear_status(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Status cleared"
+    })))
+}
+
 /// GET /users/:id/hashtags - Get recent hashtags for user
 pub async fn get_user_hashtags(
     State(state): State<AppState>,
@@ -143,3 +241,120 @@ pub async fn get_user_hashtags(
 
     Ok(Json(hashtags))
 }
+
+#[derive(Deserialize)]
+pub struct UserPostsQuery {
+    #[serde(default = "default_posts_limit")]
+    limit: i32,
+    #[serde(default)]
+    offset: i32,
+    /// List the user's replies instead of their top-level posts
+    #[serde(default)]
+    replies: bool,
+}
+
+fn default_posts_limit() -> i32 {
+    25
+}
+
+/// Largest page a client may request from GET /users/:id/posts
+const MAX_USER_POSTS_LIMIT: i32 = 100;
+
+/// GET /users/:id/posts - Get a page of a user's posts (or, with
+/// `replies=true`, their replies), newest first
+pub async fn get_user_posts(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<UserPostsQuery>,
+) -> ApiResult<Json<Vec<Post>>> {
+    // Parse user ID
+    let user_id = Uuid::parse_str(&user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
+
+    if query.limit < 1 || query.offset < 0 {
+        return Err(ApiError::BadRequest(
+            "limit must be positive and offset non-negative".to_string(),
+        ));
+    }
+    let limit = query.limit.min(MAX_USER_POSTS_LIMIT);
+
+    let pool = state.db.pool.clone();
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool);
+
+    // Verify user exists
+    user_repo
+        .get_by_id(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+    let posts = if query.replies {
+        post_repo.get_replies_by_user(&user_id, limit, query.offset)
+    } else {
+        post_repo.get_by_user(&user_id, limit, query.offset)
+    }
+    .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    // Viewer is optional; votes are only filled in when authenticated
+    let viewer_id = get_user_from_headers(&state, &headers).ok();
+    Ok(Json(finish_post_page(&state, posts, viewer_id)?))
+}
+
+/// GET /users/:id/upvoted - Get a page of the posts a user upvoted, most
+/// recently upvoted first. Votes are private, so only the user may ask.
+pub async fn get_user_upvoted(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<UserPostsQuery>,
+) -> ApiResult<Json<Vec<Post>>> {
+    let viewer_id = get_user_from_headers(&state, &headers)?;
+    let user_id = Uuid::parse_str(&user_id)
+        .map_err(|_| ApiError::BadRequest("Invalid user ID".to_string()))?;
+    if user_id != viewer_id {
+        return Err(ApiError::Forbidden(
+            "Only you can see the posts you upvoted".to_string(),
+        ));
+    }
+
+    if query.limit < 1 || query.offset < 0 {
+        return Err(ApiError::BadRequest(
+            "limit must be positive and offset non-negative".to_string(),
+        ));
+    }
+    let limit = query.limit.min(MAX_USER_POSTS_LIMIT);
+
+    let posts = PostRepository::new(state.db.pool.clone())
+        .get_upvoted_by_user(&user_id, limit, query.offset)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(finish_post_page(&state, posts, Some(viewer_id))?))
+}
+
+/// Drop posts the viewer may not see and fill in hashtags and the viewer's
+/// own votes
+fn finish_post_page(
+    state: &AppState,
+    mut posts: Vec<Post>,
+    viewer_id: Option<Uuid>,
+) -> ApiResult<Vec<Post>> {
+    let pool = state.db.pool.clone();
+    let hashtag_repo = HashtagRepository::new(pool.clone());
+    let vote_repo = VoteRepository::new(pool);
+    crate::api::posts::hide_limited_posts(state, &mut posts, viewer_id)?;
+
+    for post in &mut posts {
+        post.hashtags = hashtag_repo
+            .get_by_post(&post.id)
+            .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+        if let Some(uid) = viewer_id {
+            if let Ok(Some(vote)) = vote_repo.get_vote(&uid, &post.id) {
+                post.user_vote = Some(vote.direction.as_str().to_string());
+            }
+        }
+    }
+
+    Ok(posts)
+}
@@ -0,0 +1,183 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        get_user_from_headers,
+        posts::{self, GetPostsQuery},
+        ApiError, ApiResult,
+    },
+    db::repositories::{HashtagRepository, ReviewRepository, ReviewScope},
+    hashtag::extract_hashtags,
+    sanitize::{sanitize_content, sanitize_link, ContentKind},
+    state::AppState,
+};
+use fido_types::{
+    CreateReviewRequest, Post, PostKind, ReplyPolicy, ReviewRequestDetails, UpdateReviewRequest,
+};
+
+/// Hashtag every review request carries, so following it finds them all
+pub const REVIEW_REQUESTED_TAG: &str = "reviewrequested";
+
+/// Longest language name, which has to work as a hashtag
+const MAX_LANGUAGE_LEN: usize = 32;
+
+/// `#Rust` and `rust` are the same language, named like its hashtag
+pub(crate) fn normalize_language(language: &str) -> Result<String, ApiError> {
+    let language = language.trim().trim_start_matches('#').to_lowercase();
+    let valid = (2..=MAX_LANGUAGE_LEN).contains(&language.len())
+        && language.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+    if valid {
+        Ok(language)
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "Languages are named like their hashtag: 2-{} letters, digits and underscores (cpp, csharp)",
+            MAX_LANGUAGE_LEN
+        )))
+    }
+}
+
+/// The description with the review and language hashtags it's missing
+/// added at the end
+fn tag_review(content: &str, language: &str) -> String {
+    let present = extract_hashtags(content);
+    let missing: Vec<String> = [REVIEW_REQUESTED_TAG, language]
+        .iter()
+        .filter(|tag| !present.iter().any(|p| p == *tag))
+        .map(|tag| format!("#{}", tag))
+        .collect();
+    if missing.is_empty() {
+        content.to_string()
+    } else {
+        format!("{}\n\n{}", content, missing.join(" "))
+    }
+}
+
+/// POST /reviews - Ask for a code review. The post is tagged with
+/// #reviewrequested and the language; posting limits apply as for any post.
+pub async fn create_review_request(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateReviewRequest>,
+) -> ApiResult<Json<Post>> {
+    let url = sanitize_link(ContentKind::ReviewUrl, &payload.url)?;
+    let language = normalize_language(&payload.language)?;
+    let description = sanitize_content(ContentKind::Post, &payload.content)?;
+    // The added tags count toward the post's length too
+    let content = sanitize_content(ContentKind::Post, &tag_review(&description, &language))?;
+    let author_id = get_user_from_headers(&state, &headers)?;
+
+    posts::check_post_rate_limit(&state, &author_id)?;
+    posts::check_daily_post_quota(&state, &author_id)?;
+    let verdict = posts::screen_content(&state, &author_id, &format!("{}\n{}", url, content)).await?;
+
+    let mut post = posts::publish_post(&state, author_id, content, false, ReplyPolicy::Everyone)?;
+    ReviewRepository::new(state.db.pool.clone())
+        .create(&post.id, &url, &language)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    posts::queue_for_moderation(&state, &post.id, verdict.as_ref())?;
+
+    post.kind = Some(PostKind::ReviewRequest(ReviewRequestDetails {
+        url,
+        language,
+        is_open: true,
+    }));
+    Ok(Json(post))
+}
+
+#[derive(Deserialize)]
+pub struct ReviewsQuery {
+    #[serde(default)]
+    language: Option<String>,
+}
+
+/// GET /reviews - Open review requests, newest first; takes `limit` and
+/// `sort` like `GET /posts`. `language` picks one language; otherwise a
+/// signed-in caller sees the languages they follow as hashtags, or every
+/// language if they follow none.
+pub async fn get_review_requests(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(filter): Query<ReviewsQuery>,
+    Query(mut query): Query<GetPostsQuery>,
+) -> ApiResult<Json<Vec<Post>>> {
+    let scope = match (&filter.language, get_user_from_headers(&state, &headers)) {
+        (Some(language), _) => ReviewScope::Language(normalize_language(language)?),
+        (None, Ok(user_id)) => {
+            let follows_any = !HashtagRepository::new(state.db.pool.clone())
+                .get_followed_by_user(&user_id)
+                .map_err(|e| ApiError::InternalError(e.to_string()))?
+                .is_empty();
+            if follows_any {
+                ReviewScope::FollowedBy(user_id)
+            } else {
+                ReviewScope::All
+            }
+        }
+        (None, Err(_)) => ReviewScope::All,
+    };
+    query.reviews = Some(scope);
+    posts::get_posts(State(state), headers, Query(query)).await
+}
+
+/// PUT /posts/:id/review - Close a review request once it's been reviewed,
+/// or reopen it; author only
+pub async fn set_review_open(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+    Json(payload): Json<UpdateReviewRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let post_id =
+        Uuid::parse_str(&post_id).map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))?;
+
+    let repo = ReviewRepository::new(state.db.pool.clone());
+    let author_id = repo
+        .author_of(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Review request not found".to_string()))?;
+    if author_id != user_id {
+        return Err(ApiError::Forbidden(
+            "Only the author can close or reopen a review request".to_string(),
+        ));
+    }
+    repo.set_open(&post_id, payload.is_open)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "is_open": payload.is_open })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_languages_are_named_like_hashtags() {
+        assert_eq!(normalize_language(" #Rust ").unwrap(), "rust");
+        assert_eq!(normalize_language("objective_c").unwrap(), "objective_c");
+        assert!(normalize_language("c").is_err());
+        assert!(normalize_language("c++").is_err());
+    }
+
+    #[test]
+    fn test_review_requests_get_their_tags_once() {
+        assert_eq!(
+            tag_review("Please look at the parser", "rust"),
+            "Please look at the parser\n\n#reviewrequested #rust"
+        );
+        assert_eq!(
+            tag_review("#Rust parser, error paths", "rust"),
+            "#Rust parser, error paths\n\n#reviewrequested"
+        );
+        assert_eq!(
+            tag_review("#reviewrequested #rust", "rust"),
+            "#reviewrequested #rust"
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use axum::Json;
+
+use fido_types::ServerVersion;
+
+/// Oldest TUI release whose requests this server still understands. Raise it
+/// with any change that older clients can't parse; they then tell their
+/// users to upgrade instead of failing on every request.
+pub const MIN_CLIENT_VERSION: &str = "0.1.0";
+
+/// GET /version - Which build of the server is answering, and the oldest
+/// client it supports
+///
+/// Cheap and unauthenticated, so clients also use it to measure latency.
+pub async fn get_version() -> Json<ServerVersion> {
+    Json(ServerVersion {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        min_client_version: Some(MIN_CLIENT_VERSION.to_string()),
+    })
+}
@@ -0,0 +1,91 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{get_user_from_headers, ApiError, ApiResult},
+    db::repositories::{PostRepository, ThreadSubscriptionRepository},
+    state::AppState,
+};
+use fido_types::WatchedThread;
+
+fn parse_post_id(post_id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(post_id).map_err(|_| ApiError::BadRequest("Invalid post ID".to_string()))
+}
+
+/// GET /watches - Threads the caller watches, with their unseen reply counts
+pub async fn list_watched_threads(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<WatchedThread>>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+
+    let threads = ThreadSubscriptionRepository::new(state.db.pool.clone())
+        .watched_threads(&user_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(threads))
+}
+
+/// PUT /posts/:id/watch - Get notified of new replies anywhere in a thread
+pub async fn watch_thread(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let post_id = parse_post_id(&post_id)?;
+
+    let pool = state.db.pool.clone();
+    PostRepository::new(pool.clone())
+        .get_by_id(&post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+
+    ThreadSubscriptionRepository::new(pool)
+        .watch(&user_id, &post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "watching": true })))
+}
+
+/// DELETE /posts/:id/watch - Stop watching a thread
+pub async fn unwatch_thread(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let post_id = parse_post_id(&post_id)?;
+
+    ThreadSubscriptionRepository::new(state.db.pool.clone())
+        .unwatch(&user_id, &post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(serde_json::json!({ "watching": false })))
+}
+
+/// POST /posts/:id/watch/seen - The caller has read a watched thread's replies
+pub async fn mark_thread_seen(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(post_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let user_id = get_user_from_headers(&state, &headers)?;
+    let post_id = parse_post_id(&post_id)?;
+
+    let watching = ThreadSubscriptionRepository::new(state.db.pool.clone())
+        .mark_seen(&user_id, &post_id)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    if watching {
+        Ok(Json(serde_json::json!({
+            "success": true,
+            "message": "Thread marked as seen"
+        })))
+    } else {
+        Err(ApiError::NotFound("Not watching this thread".to_string()))
+    }
+}
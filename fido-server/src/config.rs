@@ -1,5 +1,6 @@
 use config::{Config, ConfigBuilder, ConfigError, File};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 // Configuration constants
@@ -13,17 +14,284 @@ const DEV_CONFIG_DIR: &str = "fido-server";
 pub struct Server {
     pub host: String,
     pub port: u16,
+    /// Address the instance is reached at, e.g. `https://fido.example`;
+    /// used for absolute links in the sitemap. Unset falls back to the
+    /// request's Host header.
+    #[serde(default)]
+    pub public_url: Option<String>,
+    /// Listen on this Unix domain socket instead of `host:port`
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    /// Use the socket systemd passes via socket activation, when there is one
+    #[serde(default)]
+    pub systemd_activation: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Database {
     pub path: String,
+    /// Write-ahead logging, so readers don't wait for a write to commit
+    #[serde(default = "default_wal")]
+    pub wal: bool,
+    /// Milliseconds a connection waits on a lock before failing with
+    /// "database is locked"
+    #[serde(default = "default_busy_timeout_ms")]
+    pub busy_timeout_ms: u32,
+    /// `PRAGMA synchronous`: off, normal, full or extra
+    #[serde(default = "default_synchronous")]
+    pub synchronous: String,
+}
+
+fn default_wal() -> bool {
+    true
+}
+
+fn default_busy_timeout_ms() -> u32 {
+    5000
+}
+
+fn default_synchronous() -> String {
+    "normal".to_string()
+}
+
+impl Database {
+    /// What every pooled connection is set up with; call after `validate`
+    pub fn connection_options(&self) -> crate::db::ConnectionOptions {
+        crate::db::ConnectionOptions {
+            wal: self.wal,
+            busy_timeout: std::time::Duration::from_millis(self.busy_timeout_ms as u64),
+            synchronous: crate::db::Synchronous::parse(&self.synchronous).unwrap_or_default(),
+        }
+    }
+}
+
+/// Where links posted on this instance may point
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct LinkPreviews {
+    /// Domains the instance refuses to link to; subdomains match too
+    pub blocked_domains: Vec<String>,
+}
+
+/// Instance-wide limits on what users can store; 0 turns a limit off
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Limits {
+    /// Posts and replies one user may create in any 24 hours
+    pub max_posts_per_day: u32,
+    /// Posts older than this many days are pruned in the background
+    pub post_retention_days: u32,
+    /// Leading zero bits a login's proof-of-work must reach
+    pub login_pow_difficulty: u32,
+}
+
+/// Posting without showing the author; off unless the instance turns it on.
+/// The author is still stored, for moderation.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct AnonymousPosts {
+    pub enabled: bool,
+    /// Anonymous posts one user may make in any 24 hours; 0 is unlimited
+    pub max_per_day: u32,
+}
+
+impl Default for AnonymousPosts {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_per_day: 3,
+        }
+    }
+}
+
+/// The weekly leaderboard of top posts and contributors; on by default.
+/// Instances that would rather not rank people can keep only the posts.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Leaderboard {
+    pub enabled: bool,
+    /// Also list the people who earned the most karma
+    pub rank_contributors: bool,
+}
+
+impl Default for Leaderboard {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rank_contributors: true,
+        }
+    }
+}
+
+/// How long sessions last and how often expired ones are swept
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Sessions {
+    /// Days a session lasts after login or its latest heartbeat
+    pub lifetime_days: u32,
+    /// Minutes between sweeps of expired sessions
+    pub cleanup_interval_minutes: u32,
+}
+
+impl Default for Sessions {
+    fn default() -> Self {
+        Self {
+            lifetime_days: 30,
+            cleanup_interval_minutes: 60,
+        }
+    }
+}
+
+/// Per-minute request budgets for the API; 0 turns a budget off
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RateLimits {
+    /// Requests each session token may make per minute
+    pub authenticated_per_minute: u32,
+    /// Requests each client IP may make per minute without a session. Off
+    /// by default: behind a proxy every client has the proxy's address
+    /// until it's listed in `trusted_proxies`
+    pub anonymous_per_minute: u32,
+    /// Proxy addresses whose `X-Forwarded-For` is believed; `"*"` trusts
+    /// whatever connects (only when nothing can reach the server directly)
+    pub trusted_proxies: Vec<String>,
+    /// Budgets that replace the defaults for a username or an IP address
+    pub overrides: HashMap<String, u32>,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            authenticated_per_minute: 100,
+            anonymous_per_minute: 0,
+            trusted_proxies: Vec::new(),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+/// Optional LibreTranslate-compatible backend for translating posts
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Translation {
+    /// Base URL of the service, e.g. `https://libretranslate.example`;
+    /// empty turns translation off
+    pub url: String,
+    /// Sent as `api_key` when the service requires one
+    pub api_key: String,
+}
+
+impl Translation {
+    pub fn enabled(&self) -> bool {
+        !self.url.trim().is_empty()
+    }
+}
+
+/// Instance content filtering for new posts, replies and edits
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ContentFilter {
+    /// Words or phrases matched case-insensitively against whole words
+    pub keywords: Vec<String>,
+    /// What a keyword match does: `reject`, `flag` or `limit` (flag when unset)
+    pub keyword_action: String,
+    /// Optional classifier service posts are sent to; empty turns it off
+    pub classifier_url: String,
+    /// Sent as a bearer token when the classifier requires one
+    pub classifier_api_key: String,
+    /// Links from accounts with this much karma or less get `low_karma_action`
+    pub low_karma: i32,
+    /// `flag`, `limit` or `reject`; `allow` turns the karma check off
+    pub low_karma_action: String,
+}
+
+impl Default for ContentFilter {
+    fn default() -> Self {
+        Self {
+            keywords: Vec::new(),
+            keyword_action: String::new(),
+            classifier_url: String::new(),
+            classifier_api_key: String::new(),
+            low_karma: -5,
+            low_karma_action: "flag".to_string(),
+        }
+    }
+}
+
+/// Which browser origins may call the API from other sites, and with what.
+/// Reloaded on SIGHUP.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Cors {
+    /// Origins like `https://fido.example`; a `:*` port matches any port.
+    /// `"*"` lets every origin in, but never with cookies
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["http://localhost:*".to_string(), "http://127.0.0.1:*".to_string()],
+            allowed_methods: ["GET", "POST", "PUT", "PATCH", "DELETE"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            allowed_headers: ["Content-Type", "X-Session-Token", "X-CSRF-Token"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Another community served by this process, with its own database and
+/// sessions. Requests reach it by hostname, by path prefix, or both.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Tenant {
+    pub name: String,
+    /// Hostnames routed to this tenant, e.g. `rust.fido.example`
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    /// Path the tenant is mounted under, e.g. `/c/rust`
+    #[serde(default)]
+    pub path_prefix: Option<String>,
+    /// SQLite file holding the tenant's data
+    pub database: String,
+    /// Like `server.public_url`, including the path prefix if there is one
+    #[serde(default)]
+    pub public_url: Option<String>,
+    /// Usernames allowed to administer this tenant. `FIDO_ADMIN_USERS` only
+    /// covers the main community.
+    #[serde(default)]
+    pub admins: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Settings {
     pub server: Server,
     pub database: Database,
+    #[serde(default)]
+    pub link_previews: LinkPreviews,
+    #[serde(default)]
+    pub limits: Limits,
+    #[serde(default)]
+    pub rate_limits: RateLimits,
+    #[serde(default)]
+    pub anonymous_posts: AnonymousPosts,
+    #[serde(default)]
+    pub leaderboard: Leaderboard,
+    #[serde(default)]
+    pub sessions: Sessions,
+    #[serde(default)]
+    pub translation: Translation,
+    #[serde(default)]
+    pub content_filter: ContentFilter,
+    #[serde(default)]
+    pub cors: Cors,
+    #[serde(default)]
+    pub tenants: Vec<Tenant>,
 }
 
 impl Default for Settings {
@@ -32,10 +300,26 @@ impl Default for Settings {
             server: Server {
                 host: DEFAULT_HOST.to_string(),
                 port: DEFAULT_PORT,
+                public_url: None,
+                unix_socket: None,
+                systemd_activation: false,
             },
             database: Database {
                 path: DEFAULT_DB_PATH.to_string(),
+                wal: default_wal(),
+                busy_timeout_ms: default_busy_timeout_ms(),
+                synchronous: default_synchronous(),
             },
+            link_previews: LinkPreviews::default(),
+            limits: Limits::default(),
+            rate_limits: RateLimits::default(),
+            anonymous_posts: AnonymousPosts::default(),
+            leaderboard: Leaderboard::default(),
+            sessions: Sessions::default(),
+            translation: Translation::default(),
+            content_filter: ContentFilter::default(),
+            cors: Cors::default(),
+            tenants: Vec::new(),
         }
     }
 }
@@ -89,12 +373,96 @@ impl Settings {
         if let Ok(db_path) = std::env::var("DATABASE_PATH") {
             builder = builder.set_override("database.path", db_path)?;
         }
+        if let Ok(enabled) = std::env::var("FIDO_DB_WAL") {
+            builder = builder.set_override("database.wal", enabled)?;
+        }
+        if let Ok(timeout) = std::env::var("FIDO_DB_BUSY_TIMEOUT_MS") {
+            builder = builder.set_override("database.busy_timeout_ms", timeout)?;
+        }
+        if let Ok(level) = std::env::var("FIDO_DB_SYNCHRONOUS") {
+            builder = builder.set_override("database.synchronous", level)?;
+        }
         if let Ok(port) = std::env::var("PORT") {
             builder = builder.set_override("server.port", port)?;
         }
         if let Ok(host) = std::env::var("HOST") {
             builder = builder.set_override("server.host", host)?;
         }
+        if let Ok(domains) = std::env::var("FIDO_BLOCKED_DOMAINS") {
+            let domains: Vec<String> = domains
+                .split(',')
+                .map(str::trim)
+                .filter(|domain| !domain.is_empty())
+                .map(str::to_string)
+                .collect();
+            builder = builder.set_override("link_previews.blocked_domains", domains)?;
+        }
+        if let Ok(path) = std::env::var("FIDO_UNIX_SOCKET") {
+            builder = builder.set_override("server.unix_socket", path)?;
+        }
+        if let Ok(enabled) = std::env::var("FIDO_SYSTEMD_ACTIVATION") {
+            builder = builder.set_override("server.systemd_activation", enabled)?;
+        }
+        if let Ok(url) = std::env::var("FIDO_PUBLIC_URL") {
+            builder = builder.set_override("server.public_url", url)?;
+        }
+        if let Ok(max_posts) = std::env::var("FIDO_MAX_POSTS_PER_DAY") {
+            builder = builder.set_override("limits.max_posts_per_day", max_posts)?;
+        }
+        if let Ok(days) = std::env::var("FIDO_POST_RETENTION_DAYS") {
+            builder = builder.set_override("limits.post_retention_days", days)?;
+        }
+        if let Ok(difficulty) = std::env::var("FIDO_LOGIN_POW_DIFFICULTY") {
+            builder = builder.set_override("limits.login_pow_difficulty", difficulty)?;
+        }
+        if let Ok(enabled) = std::env::var("FIDO_ANONYMOUS_POSTS") {
+            builder = builder.set_override("anonymous_posts.enabled", enabled)?;
+        }
+        if let Ok(max_posts) = std::env::var("FIDO_MAX_ANONYMOUS_POSTS_PER_DAY") {
+            builder = builder.set_override("anonymous_posts.max_per_day", max_posts)?;
+        }
+        if let Ok(enabled) = std::env::var("FIDO_LEADERBOARD") {
+            builder = builder.set_override("leaderboard.enabled", enabled)?;
+        }
+        if let Ok(rank) = std::env::var("FIDO_LEADERBOARD_CONTRIBUTORS") {
+            builder = builder.set_override("leaderboard.rank_contributors", rank)?;
+        }
+        if let Ok(proxies) = std::env::var("FIDO_TRUSTED_PROXIES") {
+            let proxies: Vec<String> = proxies
+                .split(',')
+                .map(str::trim)
+                .filter(|proxy| !proxy.is_empty())
+                .map(str::to_string)
+                .collect();
+            builder = builder.set_override("rate_limits.trusted_proxies", proxies)?;
+        }
+        if let Ok(origins) = std::env::var("FIDO_CORS_ORIGINS") {
+            let origins: Vec<String> = origins
+                .split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .map(str::to_string)
+                .collect();
+            builder = builder.set_override("cors.allowed_origins", origins)?;
+        }
+        if let Ok(days) = std::env::var("FIDO_SESSION_LIFETIME_DAYS") {
+            builder = builder.set_override("sessions.lifetime_days", days)?;
+        }
+        if let Ok(minutes) = std::env::var("FIDO_SESSION_CLEANUP_MINUTES") {
+            builder = builder.set_override("sessions.cleanup_interval_minutes", minutes)?;
+        }
+        if let Ok(url) = std::env::var("FIDO_TRANSLATE_URL") {
+            builder = builder.set_override("translation.url", url)?;
+        }
+        if let Ok(key) = std::env::var("FIDO_TRANSLATE_API_KEY") {
+            builder = builder.set_override("translation.api_key", key)?;
+        }
+        if let Ok(url) = std::env::var("FIDO_CLASSIFIER_URL") {
+            builder = builder.set_override("content_filter.classifier_url", url)?;
+        }
+        if let Ok(key) = std::env::var("FIDO_CLASSIFIER_API_KEY") {
+            builder = builder.set_override("content_filter.classifier_api_key", key)?;
+        }
 
         Ok(builder)
     }
@@ -116,6 +484,60 @@ impl Settings {
             return Err(ConfigError::Message("Host cannot be empty".to_string()));
         }
 
+        if crate::db::Synchronous::parse(&self.database.synchronous).is_none() {
+            return Err(ConfigError::Message(
+                "database.synchronous must be off, normal, full or extra".to_string(),
+            ));
+        }
+
+        if self.sessions.lifetime_days == 0 {
+            return Err(ConfigError::Message("sessions.lifetime_days must be at least 1".to_string()));
+        }
+        if self.sessions.cleanup_interval_minutes == 0 {
+            return Err(ConfigError::Message(
+                "sessions.cleanup_interval_minutes must be at least 1".to_string(),
+            ));
+        }
+
+        self.validate_tenants()
+    }
+
+    /// Tenants need a name, a database of their own, and a host or path
+    /// prefix nobody else claims
+    fn validate_tenants(&self) -> Result<(), ConfigError> {
+        let mut names = std::collections::HashSet::new();
+        let mut databases = std::collections::HashSet::from([self.database.path.as_str()]);
+        let mut hosts = std::collections::HashSet::new();
+        let mut prefixes = std::collections::HashSet::new();
+        for tenant in &self.tenants {
+            let invalid = |reason: &str| {
+                Err(ConfigError::Message(format!("tenant {:?}: {}", tenant.name, reason)))
+            };
+            if tenant.name.trim().is_empty() || !names.insert(tenant.name.as_str()) {
+                return invalid("names must be present and unique");
+            }
+            if tenant.database.trim().is_empty() || !databases.insert(tenant.database.as_str()) {
+                return invalid("needs a database file no other tenant uses");
+            }
+            if tenant.hosts.is_empty() && tenant.path_prefix.is_none() {
+                return invalid("needs hosts, a path_prefix, or both");
+            }
+            for host in &tenant.hosts {
+                if host.trim().is_empty() || !hosts.insert(host.trim().to_lowercase()) {
+                    return invalid("hosts must be non-empty and not shared with another tenant");
+                }
+            }
+            if let Some(prefix) = &tenant.path_prefix {
+                let well_formed = prefix.len() > 1
+                    && prefix.starts_with('/')
+                    && !prefix.ends_with('/')
+                    && !prefix.contains(':')
+                    && !prefix.contains('*');
+                if !well_formed || !prefixes.insert(prefix.as_str()) {
+                    return invalid("path_prefix must look like /c/name and be unique");
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -145,6 +567,21 @@ mod tests {
         assert!(settings.validate().is_ok());
     }
 
+    #[test]
+    fn test_database_options() {
+        let mut settings = Settings::default();
+        let options = settings.database.connection_options();
+        assert!(options.wal);
+        assert_eq!(options.busy_timeout, std::time::Duration::from_secs(5));
+        assert_eq!(options.synchronous, crate::db::Synchronous::Normal);
+
+        settings.database.synchronous = "FULL".to_string();
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.database.connection_options().synchronous, crate::db::Synchronous::Full);
+        settings.database.synchronous = "sometimes".to_string();
+        assert!(settings.validate().is_err());
+    }
+
     #[test]
     fn test_validation_zero_port() {
         let mut settings = Settings::default();
@@ -159,6 +596,84 @@ mod tests {
         assert!(settings.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_rejects_zero_session_settings() {
+        let mut settings = Settings::default();
+        settings.sessions.lifetime_days = 0;
+        assert!(settings.validate().is_err());
+
+        let mut settings = Settings::default();
+        settings.sessions.cleanup_interval_minutes = 0;
+        assert!(settings.validate().is_err());
+    }
+
+    fn tenant(name: &str, database: &str) -> Tenant {
+        Tenant {
+            name: name.to_string(),
+            hosts: Vec::new(),
+            path_prefix: Some(format!("/c/{}", name)),
+            database: database.to_string(),
+            public_url: None,
+            admins: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tenant_validation() {
+        let mut settings = Settings {
+            tenants: vec![tenant("rust", "rust.db"), tenant("go", "go.db")],
+            ..Settings::default()
+        };
+        assert!(settings.validate().is_ok());
+
+        // Sharing the main database
+        settings.tenants = vec![tenant("rust", DEFAULT_DB_PATH)];
+        assert!(settings.validate().is_err());
+
+        // Nowhere to route requests from
+        let mut unreachable = tenant("rust", "rust.db");
+        unreachable.path_prefix = None;
+        settings.tenants = vec![unreachable];
+        assert!(settings.validate().is_err());
+
+        // Two tenants claiming one host
+        let mut first = tenant("rust", "rust.db");
+        first.hosts = vec!["Rust.fido.example".to_string()];
+        let mut second = tenant("go", "go.db");
+        second.hosts = vec!["rust.fido.example".to_string()];
+        settings.tenants = vec![first, second];
+        assert!(settings.validate().is_err());
+
+        let mut trailing = tenant("rust", "rust.db");
+        trailing.path_prefix = Some("/c/rust/".to_string());
+        settings.tenants = vec![trailing];
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_anonymous_posts_are_off_by_default() {
+        let settings = Settings::default();
+        assert!(!settings.anonymous_posts.enabled);
+        assert_eq!(settings.anonymous_posts.max_per_day, 3);
+    }
+
+    #[test]
+    fn test_leaderboard_ranks_posts_and_people_by_default() {
+        let settings = Settings::default();
+        assert!(settings.leaderboard.enabled);
+        assert!(settings.leaderboard.rank_contributors);
+    }
+
+    #[test]
+    fn test_cors_defaults_to_localhost_origins() {
+        let settings = Settings::default();
+        assert_eq!(
+            settings.cors.allowed_origins,
+            vec!["http://localhost:*", "http://127.0.0.1:*"]
+        );
+        assert!(settings.cors.allowed_headers.iter().any(|h| h == "X-Session-Token"));
+    }
+
     #[test]
     fn test_environment_variable_overrides() {
         // Set environment variables
@@ -0,0 +1,367 @@
+/// Content filtering for posts, replies and edits
+///
+/// Each filter looks at the sanitized text and may return a verdict; the
+/// pipeline keeps the most severe one. The built-in keyword list, the
+/// optional HTTP classifier and the check on links from low-karma accounts
+/// are configured under `[content_filter]`.
+use crate::config;
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CLASSIFIER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What happens to a post a filter objects to, least severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterAction {
+    /// Published as normal
+    Allow,
+    /// Published, and listed in the moderation queue
+    Flag,
+    /// Published but only shown to its author until an admin approves it
+    Limit,
+    /// Refused with an error
+    Reject,
+}
+
+impl FilterAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterAction::Allow => "allow",
+            FilterAction::Flag => "flag",
+            FilterAction::Limit => "limit",
+            FilterAction::Reject => "reject",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "allow" => Some(FilterAction::Allow),
+            "flag" => Some(FilterAction::Flag),
+            "limit" => Some(FilterAction::Limit),
+            "reject" => Some(FilterAction::Reject),
+            _ => None,
+        }
+    }
+}
+
+/// A filter's objection to a piece of content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub action: FilterAction,
+    /// Which filter decided, e.g. `keywords`
+    pub filter: String,
+    pub reason: String,
+}
+
+pub type VerdictFuture<'a> = Pin<Box<dyn Future<Output = Option<Verdict>> + Send + 'a>>;
+
+/// One stage of the pipeline; `None` means the content is fine
+pub trait ContentFilter: Send + Sync {
+    fn check<'a>(&'a self, content: &'a str) -> VerdictFuture<'a>;
+}
+
+/// Matches configured words and phrases, ignoring case and punctuation
+pub struct KeywordFilter {
+    keywords: Vec<Vec<String>>,
+    action: FilterAction,
+}
+
+/// Lowercase words of `text`, split on anything that isn't alphanumeric
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+impl KeywordFilter {
+    pub fn new(keywords: &[String], action: FilterAction) -> Self {
+        Self {
+            keywords: keywords
+                .iter()
+                .map(|keyword| words(keyword))
+                .filter(|keyword| !keyword.is_empty())
+                .collect(),
+            action,
+        }
+    }
+
+    /// The first configured keyword found in `content`
+    fn find_match(&self, content: &str) -> Option<String> {
+        let content = words(content);
+        self.keywords
+            .iter()
+            .find(|keyword| content.windows(keyword.len()).any(|window| window == keyword.as_slice()))
+            .map(|keyword| keyword.join(" "))
+    }
+}
+
+impl ContentFilter for KeywordFilter {
+    fn check<'a>(&'a self, content: &'a str) -> VerdictFuture<'a> {
+        let verdict = self.find_match(content).map(|keyword| Verdict {
+            action: self.action,
+            filter: "keywords".to_string(),
+            reason: format!("contains \"{}\"", keyword),
+        });
+        Box::pin(async move { verdict })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClassifierResponse {
+    action: FilterAction,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Sends `{"content": ...}` to an external service, which answers with
+/// `{"action": "allow|flag|limit|reject", "reason": ...}`. A service that is
+/// down or answers nonsense lets the post through, so an outage doesn't stop
+/// everyone from posting.
+pub struct HttpClassifier {
+    url: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl HttpClassifier {
+    pub fn new(url: &str, api_key: &str) -> Self {
+        Self {
+            url: url.trim().to_string(),
+            api_key: api_key.to_string(),
+            client: reqwest::Client::builder()
+                .timeout(CLASSIFIER_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    async fn classify(&self, content: &str) -> Result<ClassifierResponse, String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({ "content": content }));
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("classifier returned {}", response.status()));
+        }
+        response.json().await.map_err(|e| e.to_string())
+    }
+}
+
+impl ContentFilter for HttpClassifier {
+    fn check<'a>(&'a self, content: &'a str) -> VerdictFuture<'a> {
+        Box::pin(async move {
+            match self.classify(content).await {
+                Ok(response) if response.action != FilterAction::Allow => Some(Verdict {
+                    action: response.action,
+                    filter: "classifier".to_string(),
+                    reason: response
+                        .reason
+                        .unwrap_or_else(|| "flagged by classifier".to_string()),
+                }),
+                Ok(_) => None,
+                Err(e) => {
+                    tracing::warn!("Content classifier unavailable, allowing post: {}", e);
+                    None
+                }
+            }
+        })
+    }
+}
+
+/// Catches links posted by accounts with little or negative karma, which
+/// is where link spam usually comes from. Unlike the filters it looks at the
+/// author, so it runs beside the pipeline rather than in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowKarmaRule {
+    /// Karma at or below which links are caught
+    pub threshold: i32,
+    pub action: FilterAction,
+}
+
+impl LowKarmaRule {
+    pub fn check(&self, karma: i32, content: &str) -> Option<Verdict> {
+        let content = content.to_lowercase();
+        let has_link = content.contains("http://") || content.contains("https://");
+        (has_link && karma <= self.threshold).then(|| Verdict {
+            action: self.action,
+            filter: "karma".to_string(),
+            reason: format!("links from an account with {} karma", karma),
+        })
+    }
+}
+
+/// The more severe of two verdicts
+pub fn most_severe(a: Option<Verdict>, b: Option<Verdict>) -> Option<Verdict> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.action > a.action { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Every configured filter, run in order
+#[derive(Clone, Default)]
+pub struct FilterPipeline {
+    filters: Vec<Arc<dyn ContentFilter>>,
+    low_karma: Option<LowKarmaRule>,
+}
+
+impl FilterPipeline {
+    pub fn from_config(config: &config::ContentFilter) -> Self {
+        let mut pipeline = Self::default();
+        if !config.keywords.is_empty() {
+            let action = if config.keyword_action.trim().is_empty() {
+                FilterAction::Flag
+            } else {
+                FilterAction::parse(&config.keyword_action).unwrap_or_else(|| {
+                    tracing::warn!(
+                        "Unknown content_filter.keyword_action {:?}, flagging instead",
+                        config.keyword_action
+                    );
+                    FilterAction::Flag
+                })
+            };
+            pipeline = pipeline.with(KeywordFilter::new(&config.keywords, action));
+        }
+        if !config.classifier_url.trim().is_empty() {
+            pipeline = pipeline.with(HttpClassifier::new(
+                &config.classifier_url,
+                &config.classifier_api_key,
+            ));
+        }
+        let karma_action = if config.low_karma_action.trim().is_empty() {
+            FilterAction::Flag
+        } else {
+            FilterAction::parse(&config.low_karma_action).unwrap_or_else(|| {
+                tracing::warn!(
+                    "Unknown content_filter.low_karma_action {:?}, flagging instead",
+                    config.low_karma_action
+                );
+                FilterAction::Flag
+            })
+        };
+        if karma_action != FilterAction::Allow {
+            pipeline = pipeline.with_low_karma(LowKarmaRule {
+                threshold: config.low_karma,
+                action: karma_action,
+            });
+        }
+        pipeline
+    }
+
+    /// Add a filter after the existing ones
+    pub fn with(mut self, filter: impl ContentFilter + 'static) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Check links against the author's karma
+    pub fn with_low_karma(mut self, rule: LowKarmaRule) -> Self {
+        self.low_karma = Some(rule);
+        self
+    }
+
+    /// The karma rule, when it's on
+    pub fn low_karma(&self) -> Option<LowKarmaRule> {
+        self.low_karma
+    }
+
+    /// The most severe verdict any filter returns; a reject stops the rest
+    pub async fn check(&self, content: &str) -> Option<Verdict> {
+        let mut worst: Option<Verdict> = None;
+        for filter in &self.filters {
+            let Some(verdict) = filter.check(content).await else {
+                continue;
+            };
+            let more_severe = match &worst {
+                Some(current) => verdict.action > current.action,
+                None => true,
+            };
+            if more_severe {
+                let rejected = verdict.action == FilterAction::Reject;
+                worst = Some(verdict);
+                if rejected {
+                    break;
+                }
+            }
+        }
+        worst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(FilterAction);
+
+    impl ContentFilter for Fixed {
+        fn check<'a>(&'a self, _content: &'a str) -> VerdictFuture<'a> {
+            let verdict = Verdict {
+                action: self.0,
+                filter: "fixed".to_string(),
+                reason: self.0.as_str().to_string(),
+            };
+            Box::pin(async move { Some(verdict) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keywords_match_whole_words_and_phrases() {
+        let filter = KeywordFilter::new(
+            &["spam".to_string(), "Buy Now".to_string()],
+            FilterAction::Limit,
+        );
+        assert!(filter.check("SPAM!!").await.is_some());
+        assert!(filter.check("please buy   now, friends").await.is_some());
+        assert!(filter.check("spamalot is a musical").await.is_none());
+        let verdict = filter.check("buy now").await.unwrap();
+        assert_eq!(verdict.action, FilterAction::Limit);
+        assert_eq!(verdict.reason, "contains \"buy now\"");
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_keeps_the_most_severe_verdict() {
+        let pipeline = FilterPipeline::default()
+            .with(Fixed(FilterAction::Flag))
+            .with(Fixed(FilterAction::Limit))
+            .with(Fixed(FilterAction::Flag));
+        assert_eq!(pipeline.check("x").await.unwrap().action, FilterAction::Limit);
+        assert!(FilterPipeline::default().check("x").await.is_none());
+    }
+
+    #[test]
+    fn test_low_karma_accounts_are_caught_posting_links() {
+        let rule = LowKarmaRule {
+            threshold: -5,
+            action: FilterAction::Flag,
+        };
+        let verdict = rule.check(-5, "Great deals at HTTPS://shop.example").unwrap();
+        assert_eq!(verdict.action, FilterAction::Flag);
+        assert_eq!(verdict.reason, "links from an account with -5 karma");
+        assert!(rule.check(-4, "https://shop.example").is_none());
+        assert!(rule.check(-20, "no links here").is_none());
+    }
+
+    #[test]
+    fn test_most_severe_verdict_wins() {
+        let verdict = |action| Some(Verdict {
+            action,
+            filter: "fixed".to_string(),
+            reason: String::new(),
+        });
+        let worst = most_severe(verdict(FilterAction::Flag), verdict(FilterAction::Limit));
+        assert_eq!(worst.unwrap().action, FilterAction::Limit);
+        let worst = most_severe(None, verdict(FilterAction::Flag));
+        assert_eq!(worst.unwrap().action, FilterAction::Flag);
+        assert!(most_severe(None, None).is_none());
+    }
+}
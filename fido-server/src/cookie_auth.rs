@@ -0,0 +1,197 @@
+//! Cookie-based sessions for the browser web terminal.
+//!
+//! Native clients send `X-Session-Token`. Browser sessions instead carry the
+//! token in an HttpOnly, SameSite=Strict cookie, and every state-changing
+//! request must echo the CSRF cookie in an `X-CSRF-Token` header
+//! (double-submit). The middleware below turns a valid cookie session into
+//! an `X-Session-Token` header so handlers don't need to know which mode the
+//! caller used.
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+pub const SESSION_COOKIE: &str = "fido_session";
+pub const CSRF_COOKIE: &str = "fido_csrf";
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Set to `true` when browsers reach the server over HTTPS so cookies get
+/// the `Secure` attribute
+const COOKIE_SECURE_ENV: &str = "FIDO_COOKIE_SECURE";
+
+/// Endpoints that may be called with a stale cookie and no CSRF token
+const CSRF_EXEMPT_PATHS: &[&str] = &["/auth/web/login"];
+
+fn secure_attribute() -> &'static str {
+    match std::env::var(COOKIE_SECURE_ENV).as_deref() {
+        Ok("true") | Ok("1") => "; Secure",
+        _ => "",
+    }
+}
+
+/// `Set-Cookie` value holding the session token; never readable from scripts.
+/// `lifetime` should match the session's so the browser keeps it as long, and
+/// `path` is where the tenant is mounted so communities sharing a host don't
+/// overwrite each other's cookies.
+pub fn session_cookie(token: &str, lifetime: chrono::Duration, path: &str) -> HeaderValue {
+    format!(
+        "{}={}; Path={}; Max-Age={}; HttpOnly; SameSite=Strict{}",
+        SESSION_COOKIE,
+        token,
+        path,
+        lifetime.num_seconds(),
+        secure_attribute()
+    )
+    .parse()
+    .expect("session token is a valid header value")
+}
+
+/// `Set-Cookie` value holding the CSRF token. Not HttpOnly: the page has to
+/// read it to echo it back in the `X-CSRF-Token` header.
+pub fn csrf_cookie(token: &str, lifetime: chrono::Duration, path: &str) -> HeaderValue {
+    format!(
+        "{}={}; Path={}; Max-Age={}; SameSite=Strict{}",
+        CSRF_COOKIE,
+        token,
+        path,
+        lifetime.num_seconds(),
+        secure_attribute()
+    )
+    .parse()
+    .expect("CSRF token is a valid header value")
+}
+
+/// `Set-Cookie` value that removes `name` at `path`
+pub fn expired_cookie(name: &str, path: &str) -> HeaderValue {
+    format!("{}=; Path={}; Max-Age=0; SameSite=Strict{}", name, path, secure_attribute())
+        .parse()
+        .expect("cookie name is a valid header value")
+}
+
+/// A fresh random CSRF token
+pub fn new_csrf_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// Value of cookie `name` from the request's `Cookie` headers
+pub fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Compare without returning early on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// True when a cookie-authenticated request may go ahead: safe methods always
+/// can, anything else needs the CSRF header to match the CSRF cookie
+fn csrf_ok(method: &Method, headers: &HeaderMap) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+    let cookie = cookie_value(headers, CSRF_COOKIE);
+    let header = headers.get(CSRF_HEADER).and_then(|v| v.to_str().ok());
+    match (cookie, header) {
+        (Some(cookie), Some(header)) => constant_time_eq(cookie.as_bytes(), header.as_bytes()),
+        _ => false,
+    }
+}
+
+/// Accept a session cookie in place of `X-Session-Token`, enforcing CSRF on
+/// state-changing requests. Requests that already send the header (native
+/// clients) pass through untouched.
+pub async fn cookie_auth_middleware(mut request: Request, next: Next) -> Response {
+    if request.headers().contains_key("X-Session-Token") {
+        return next.run(request).await;
+    }
+
+    let Some(token) = cookie_value(request.headers(), SESSION_COOKIE).map(str::to_string) else {
+        return next.run(request).await;
+    };
+
+    if !CSRF_EXEMPT_PATHS.contains(&request.uri().path())
+        && !csrf_ok(request.method(), request.headers())
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            "{\"error\": \"Missing or invalid CSRF token\"}",
+        )
+            .into_response();
+    }
+
+    if let Ok(value) = HeaderValue::from_str(&token) {
+        request.headers_mut().insert("X-Session-Token", value);
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(
+                header::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn test_cookie_value_parses_multiple_cookies() {
+        let map = headers(&[
+            ("cookie", "theme=dark; fido_session=abc123"),
+            ("cookie", "fido_csrf=tok"),
+        ]);
+        assert_eq!(cookie_value(&map, SESSION_COOKIE), Some("abc123"));
+        assert_eq!(cookie_value(&map, CSRF_COOKIE), Some("tok"));
+        assert_eq!(cookie_value(&map, "missing"), None);
+    }
+
+    #[test]
+    fn test_csrf_required_for_state_changing_requests() {
+        let matching = headers(&[("cookie", "fido_csrf=tok"), ("x-csrf-token", "tok")]);
+        let mismatched = headers(&[("cookie", "fido_csrf=tok"), ("x-csrf-token", "other")]);
+        let missing = headers(&[("cookie", "fido_csrf=tok")]);
+
+        assert!(csrf_ok(&Method::GET, &missing));
+        assert!(csrf_ok(&Method::POST, &matching));
+        assert!(!csrf_ok(&Method::POST, &mismatched));
+        assert!(!csrf_ok(&Method::DELETE, &missing));
+    }
+
+    #[test]
+    fn test_session_cookie_is_http_only_and_same_site() {
+        let lifetime = chrono::Duration::days(30);
+        let cookie = session_cookie("abc123", lifetime, "/");
+        let cookie = cookie.to_str().unwrap();
+        assert!(cookie.starts_with("fido_session=abc123; Path=/;"));
+        assert!(cookie.contains("Max-Age=2592000;"));
+        assert!(cookie.contains("HttpOnly"));
+        assert!(cookie.contains("SameSite=Strict"));
+        assert!(!csrf_cookie("tok", lifetime, "/").to_str().unwrap().contains("HttpOnly"));
+    }
+
+    #[test]
+    fn test_cookies_are_scoped_to_the_tenant_path() {
+        let lifetime = chrono::Duration::days(1);
+        let cookie = session_cookie("abc123", lifetime, "/c/rust");
+        assert!(cookie.to_str().unwrap().contains("Path=/c/rust;"));
+        let expired = expired_cookie(CSRF_COOKIE, "/c/rust");
+        assert!(expired.to_str().unwrap().starts_with("fido_csrf=; Path=/c/rust;"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Cross-origin access for browser pages served from other sites.
+//!
+//! Only origins listed in `[cors]` get CORS headers back, and only they may
+//! send cookies along (`Access-Control-Allow-Credentials`). The policy sits
+//! behind a lock so `SIGHUP` can swap in a freshly loaded one without
+//! restarting the server.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::{Arc, RwLock};
+
+use crate::config::Cors;
+
+/// How long browsers may cache a preflight answer, in seconds
+const PREFLIGHT_MAX_AGE: &str = "600";
+
+/// Parsed form of the `[cors]` settings
+#[derive(Debug, Clone)]
+pub struct CorsPolicy {
+    /// Lowercased origins; a `:*` suffix matches any port
+    origins: Vec<String>,
+    /// `"*"` was listed: any origin may call, but without cookies
+    allow_any_origin: bool,
+    methods: HeaderValue,
+    headers: HeaderValue,
+}
+
+impl CorsPolicy {
+    pub fn new(config: &Cors) -> Self {
+        let mut origins = Vec::new();
+        let mut allow_any_origin = false;
+        for origin in &config.allowed_origins {
+            match origin.trim() {
+                "*" => allow_any_origin = true,
+                origin if origin.starts_with("http://") || origin.starts_with("https://") => {
+                    origins.push(origin.trim_end_matches('/').to_lowercase())
+                }
+                origin => tracing::warn!("Ignoring CORS origin {:?}: not an http(s) origin", origin),
+            }
+        }
+
+        let methods: Vec<String> = config
+            .allowed_methods
+            .iter()
+            .filter_map(|method| match Method::from_bytes(method.trim().to_uppercase().as_bytes()) {
+                Ok(method) => Some(method.to_string()),
+                Err(_) => {
+                    tracing::warn!("Ignoring CORS method {:?}: not an HTTP method", method);
+                    None
+                }
+            })
+            .collect();
+        let headers: Vec<String> = config
+            .allowed_headers
+            .iter()
+            .filter_map(|name| match HeaderName::from_bytes(name.trim().as_bytes()) {
+                Ok(name) => Some(name.to_string()),
+                Err(_) => {
+                    tracing::warn!("Ignoring CORS header {:?}: not a header name", name);
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            origins,
+            allow_any_origin,
+            methods: HeaderValue::from_str(&methods.join(", ")).expect("methods are valid header text"),
+            headers: HeaderValue::from_str(&headers.join(", ")).expect("header names are valid header text"),
+        }
+    }
+
+    /// Whether `origin` was listed by name (so it may also send cookies)
+    fn lists_origin(&self, origin: &str) -> bool {
+        let origin = origin.to_lowercase();
+        self.origins.iter().any(|allowed| {
+            if *allowed == origin {
+                return true;
+            }
+            match allowed.strip_suffix(":*") {
+                Some(base) => match origin.strip_prefix(base) {
+                    Some("") => true,
+                    Some(rest) => rest
+                        .strip_prefix(':')
+                        .is_some_and(|port| !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit())),
+                    None => false,
+                },
+                None => false,
+            }
+        })
+    }
+
+    /// Headers granting `origin` access, or None when it isn't allowed
+    fn grant(&self, origin: &HeaderValue) -> Option<Vec<(HeaderName, HeaderValue)>> {
+        let origin_text = origin.to_str().ok()?;
+        if self.lists_origin(origin_text) {
+            Some(vec![
+                (header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone()),
+                (header::ACCESS_CONTROL_ALLOW_CREDENTIALS, HeaderValue::from_static("true")),
+            ])
+        } else if self.allow_any_origin {
+            Some(vec![(header::ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"))])
+        } else {
+            None
+        }
+    }
+}
+
+/// The live policy, shared between the middleware and the reload task
+#[derive(Clone)]
+pub struct SharedCorsPolicy(Arc<RwLock<CorsPolicy>>);
+
+impl SharedCorsPolicy {
+    pub fn new(config: &Cors) -> Self {
+        Self(Arc::new(RwLock::new(CorsPolicy::new(config))))
+    }
+
+    /// Swap in a policy built from `config`; requests already past the
+    /// middleware keep the old one
+    pub fn replace(&self, config: &Cors) {
+        let policy = CorsPolicy::new(config);
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    fn current(&self) -> CorsPolicy {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Answers preflights and adds CORS headers for allowed origins. Requests
+/// without an `Origin` (native clients, same-origin pages) pass untouched.
+pub async fn cors_middleware(
+    State(policy): State<SharedCorsPolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(origin) = request.headers().get(header::ORIGIN).cloned() else {
+        return next.run(request).await;
+    };
+    let policy = policy.current();
+    let grant = policy.grant(&origin);
+
+    let is_preflight = request.method() == Method::OPTIONS
+        && request.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+    let mut response = if is_preflight {
+        let mut response = StatusCode::NO_CONTENT.into_response();
+        if grant.is_some() {
+            let headers = response.headers_mut();
+            headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, policy.methods.clone());
+            headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, policy.headers.clone());
+            headers.insert(header::ACCESS_CONTROL_MAX_AGE, HeaderValue::from_static(PREFLIGHT_MAX_AGE));
+        }
+        response
+    } else {
+        next.run(request).await
+    };
+
+    let headers = response.headers_mut();
+    headers.append(header::VARY, HeaderValue::from_static("Origin"));
+    for (name, value) in grant.into_iter().flatten() {
+        headers.insert(name, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(origins: &[&str]) -> CorsPolicy {
+        CorsPolicy::new(&Cors {
+            allowed_origins: origins.iter().map(|origin| origin.to_string()).collect(),
+            ..Cors::default()
+        })
+    }
+
+    #[test]
+    fn test_default_policy_allows_only_localhost() {
+        let policy = CorsPolicy::new(&Cors::default());
+        assert!(policy.lists_origin("http://localhost:8080"));
+        assert!(policy.lists_origin("http://127.0.0.1:5173"));
+        assert!(policy.lists_origin("http://localhost"));
+        assert!(!policy.lists_origin("http://localhost.evil.example"));
+        assert!(!policy.lists_origin("http://localhost:80@evil.example"));
+        assert!(!policy.lists_origin("https://evil.example"));
+        assert!(policy.grant(&HeaderValue::from_static("https://evil.example")).is_none());
+    }
+
+    #[test]
+    fn test_listed_origins_get_credentials_and_wildcard_does_not() {
+        let policy = policy(&["https://Fido.example/", "*", "ftp://nope"]);
+        let grant = policy.grant(&HeaderValue::from_static("https://fido.example")).unwrap();
+        assert!(grant
+            .iter()
+            .any(|(name, value)| name == header::ACCESS_CONTROL_ALLOW_CREDENTIALS && value == "true"));
+        assert!(grant.iter().any(|(_, value)| value == "https://fido.example"));
+
+        let grant = policy.grant(&HeaderValue::from_static("https://other.example")).unwrap();
+        assert_eq!(grant, vec![(header::ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"))]);
+    }
+
+    #[test]
+    fn test_replace_swaps_the_live_policy() {
+        let shared = SharedCorsPolicy::new(&Cors::default());
+        assert!(!shared.current().lists_origin("https://fido.example"));
+        shared.replace(&Cors {
+            allowed_origins: vec!["https://fido.example".to_string()],
+            ..Cors::default()
+        });
+        assert!(shared.current().lists_origin("https://fido.example"));
+        assert!(!shared.current().lists_origin("http://localhost:3000"));
+    }
+
+    #[test]
+    fn test_methods_and_headers_are_normalized() {
+        let policy = CorsPolicy::new(&Cors {
+            allowed_methods: vec!["get".to_string(), "bad method".to_string()],
+            allowed_headers: vec!["X-Session-Token".to_string(), "bad header".to_string()],
+            ..Cors::default()
+        });
+        assert_eq!(policy.methods, "GET");
+        assert_eq!(policy.headers, "x-session-token");
+    }
+}
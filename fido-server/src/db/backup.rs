@@ -0,0 +1,141 @@
+//! Online backups and restores using SQLite's backup API.
+//!
+//! The backup API copies pages from a live connection, so the server keeps
+//! serving while a snapshot is taken. `verify` is the integrity check a
+//! file must pass before fido-admin will restore from it.
+
+use anyhow::{bail, Context, Result};
+use rusqlite::{backup::Backup, Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Pages copied per step; the source is unlocked between steps
+pub const PAGES_PER_STEP: std::os::raw::c_int = 256;
+pub const STEP_PAUSE: Duration = Duration::from_millis(10);
+
+/// Tables a file must have to count as a Fido database
+const REQUIRED_TABLES: &[&str] = &["users", "posts"];
+
+/// What a finished backup or restore produced
+#[derive(Debug, Clone)]
+pub struct BackupSummary {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Snapshot the database behind `source` to `target`, which must not exist.
+/// The copy is written next to the target and renamed into place, so a
+/// failed backup never leaves a half-written file under the final name.
+pub fn backup_to(source: &Connection, target: &Path) -> Result<BackupSummary> {
+    if target.exists() {
+        bail!("Backup target already exists: {}", target.display());
+    }
+    if let Some(parent) = target.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut partial = target.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let result = (|| {
+        let mut dest = Connection::open(&partial)
+            .with_context(|| format!("Failed to create {}", partial.display()))?;
+        Backup::new(source, &mut dest)?
+            .run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None)
+            .context("SQLite backup failed")?;
+        drop(dest);
+        verify(&partial)?;
+        std::fs::rename(&partial, target)
+            .with_context(|| format!("Failed to move backup to {}", target.display()))
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result?;
+
+    Ok(BackupSummary {
+        path: target.to_path_buf(),
+        bytes: std::fs::metadata(target)?.len(),
+    })
+}
+
+/// Check that `path` is an intact Fido database
+pub fn verify(path: &Path) -> Result<()> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .context("Not a readable SQLite database")?;
+    if integrity != "ok" {
+        bail!("Integrity check failed: {}", integrity);
+    }
+
+    for table in REQUIRED_TABLES {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+            [table],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            bail!("Not a Fido database: missing the {} table", table);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fido-backup-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn user_count(path: &Path) -> i64 {
+        Connection::open(path)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_backup_snapshots_the_live_database() -> Result<()> {
+        let dir = temp_dir();
+        let live_path = dir.join("fido.db");
+        let db = Database::new(&live_path)?;
+        db.initialize()?;
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, 0)",
+            (uuid::Uuid::new_v4().to_string(), "before_backup", "2024-01-01T00:00:00Z"),
+        )?;
+
+        let backup_path = dir.join("backups/snapshot.db");
+        let summary = backup_to(&*db.pool.get()?, &backup_path)?;
+        assert!(summary.bytes > 0);
+        assert!(backup_to(&*db.pool.get()?, &backup_path).is_err(), "never overwrites");
+        assert_eq!(user_count(&backup_path), user_count(&live_path));
+        verify(&backup_path)?;
+
+        drop(db);
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_rejects_files_that_arent_fido_databases() -> Result<()> {
+        let dir = temp_dir();
+        let bogus = dir.join("bogus.db");
+        Connection::open(&bogus)?.execute_batch("CREATE TABLE other (id INTEGER);")?;
+
+        let err = verify(&bogus).unwrap_err();
+        assert!(format!("{:#}", err).contains("missing the users table"));
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
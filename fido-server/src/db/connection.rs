@@ -2,8 +2,9 @@ use anyhow::{Context, Result};
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
 use std::path::Path;
+use std::time::Duration;
 
-use super::schema::{SCHEMA, TEST_DATA};
+use super::schema::{COUNTER_TRIGGERS, RECOUNT_USER_COUNTERS, SCHEMA, TEST_DATA};
 
 /// SQLite in-memory database identifier
 const MEMORY_DB_PATH: &str = ":memory:";
@@ -11,6 +12,59 @@ const MEMORY_DB_PATH: &str = ":memory:";
 pub type DbPool = Pool<SqliteConnectionManager>;
 pub type DbConnection = PooledConnection<SqliteConnectionManager>;
 
+/// How long a commit waits for the disk (`PRAGMA synchronous`). With WAL,
+/// `Normal` can lose the last commits on a power cut but never corrupts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Synchronous {
+    Off,
+    #[default]
+    Normal,
+    Full,
+    Extra,
+}
+
+impl Synchronous {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "off" => Some(Self::Off),
+            "normal" => Some(Self::Normal),
+            "full" => Some(Self::Full),
+            "extra" => Some(Self::Extra),
+            _ => None,
+        }
+    }
+
+    fn as_pragma(self) -> &'static str {
+        match self {
+            Self::Off => "OFF",
+            Self::Normal => "NORMAL",
+            Self::Full => "FULL",
+            Self::Extra => "EXTRA",
+        }
+    }
+}
+
+/// Settings applied to every pooled connection as it's opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// Write-ahead logging, so readers keep going while a write commits
+    pub wal: bool,
+    /// How long a connection waits on a lock before failing with
+    /// "database is locked"
+    pub busy_timeout: Duration,
+    pub synchronous: Synchronous,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            wal: true,
+            busy_timeout: Duration::from_secs(5),
+            synchronous: Synchronous::Normal,
+        }
+    }
+}
+
 /// Database wrapper with connection pooling support
 #[derive(Clone)]
 pub struct Database {
@@ -18,9 +72,24 @@ pub struct Database {
 }
 
 impl Database {
-    /// Create a new database connection pool
+    /// Create a new database connection pool with the default options
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let manager = Self::create_connection_manager(path)?;
+        Self::with_options(path, ConnectionOptions::default())
+    }
+
+    /// Create a new database connection pool whose connections use `options`
+    pub fn with_options<P: AsRef<Path>>(path: P, options: ConnectionOptions) -> Result<Self> {
+        let manager = Self::create_connection_manager(path)?.with_init(move |conn| {
+            conn.busy_timeout(options.busy_timeout)?;
+            // In-memory databases answer "memory" and stay that way
+            if options.wal {
+                conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+            }
+            conn.execute_batch(&format!(
+                "PRAGMA synchronous = {}",
+                options.synchronous.as_pragma()
+            ))
+        });
         let pool = Pool::new(manager)
             .context("Failed to create database connection pool")?;
         Ok(Self { pool })
@@ -77,6 +146,12 @@ impl Database {
             [],
         );
         
+        // Deleted posts with replies are kept as tombstones to preserve threads
+        let _ = conn.execute(
+            "ALTER TABLE posts ADD COLUMN is_deleted INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        
         // Create index on parent_post_id for efficient reply queries
         let _ = conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_posts_parent_id ON posts(parent_post_id)",
@@ -109,6 +184,58 @@ impl Database {
             "CREATE UNIQUE INDEX IF NOT EXISTS idx_users_github_id ON users(github_id)",
             [],
         );
+
+        // Per-user vote privacy (hidden from post authors unless opted in)
+        let _ = conn.execute(
+            "ALTER TABLE user_configs ADD COLUMN votes_visible_to_authors INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+
+        // Do-not-disturb, quiet hours, unread count display, accessibility,
+        // reduced motion, timestamp style, feed layout, pinned hashtags and
+        // karma display (all applied by the client), the digest opt-in and
+        // search engine indexing of the public pages
+        for column in [
+            "do_not_disturb INTEGER NOT NULL DEFAULT 0",
+            "quiet_hours_enabled INTEGER NOT NULL DEFAULT 0",
+            "quiet_hours_start INTEGER NOT NULL DEFAULT 22",
+            "quiet_hours_end INTEGER NOT NULL DEFAULT 7",
+            "show_unread_counts INTEGER NOT NULL DEFAULT 1",
+            "accessible_layout INTEGER NOT NULL DEFAULT 0",
+            "announcements TEXT NOT NULL DEFAULT 'Off'",
+            "reduced_motion INTEGER NOT NULL DEFAULT 0",
+            "timestamp_style TEXT NOT NULL DEFAULT 'Absolute'",
+            "digest_frequency TEXT NOT NULL DEFAULT 'Off'",
+            "feed_density TEXT NOT NULL DEFAULT 'Comfortable'",
+            "preview_lines INTEGER NOT NULL DEFAULT 0",
+            "show_vote_counts INTEGER NOT NULL DEFAULT 1",
+            "pinned_hashtags TEXT NOT NULL DEFAULT ''",
+            "discoverable INTEGER NOT NULL DEFAULT 0",
+            "show_karma INTEGER NOT NULL DEFAULT 0",
+        ] {
+            let _ = conn.execute(&format!("ALTER TABLE user_configs ADD COLUMN {}", column), []);
+        }
+
+        // Follower, following and post counts kept on the user row so
+        // profiles don't count rows on every view
+        let mut added_user_counters = false;
+        for column in [
+            "follower_count INTEGER NOT NULL DEFAULT 0",
+            "following_count INTEGER NOT NULL DEFAULT 0",
+            "post_count INTEGER NOT NULL DEFAULT 0",
+        ] {
+            added_user_counters |= conn
+                .execute(&format!("ALTER TABLE users ADD COLUMN {}", column), [])
+                .is_ok();
+        }
+        if added_user_counters {
+            conn.execute_batch(RECOUNT_USER_COUNTERS)
+                .context("Failed to fill in user counters")?;
+        }
+
+        // Keep vote, reply and user counters maintained by the database itself
+        conn.execute_batch(COUNTER_TRIGGERS)
+            .context("Failed to create counter triggers")?;
         
         Ok(())
     }
@@ -155,6 +282,49 @@ mod tests {
         assert!(tables.contains(&"user_configs".to_string()));
     }
 
+    #[test]
+    fn test_connections_use_wal_and_wait_on_locks() {
+        let path = std::env::temp_dir().join(format!("fido-wal-{}.db", uuid::Uuid::new_v4()));
+        let db = Database::with_options(
+            &path,
+            ConnectionOptions {
+                synchronous: Synchronous::Full,
+                ..ConnectionOptions::default()
+            },
+        )
+        .expect("Failed to create database");
+        db.initialize().expect("Failed to initialize schema");
+        db.seed_test_data().expect("Failed to seed test data");
+
+        let conn = db.connection().expect("Failed to get connection");
+        let journal_mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        let synchronous: i64 = conn.query_row("PRAGMA synchronous", [], |row| row.get(0)).unwrap();
+        let busy_timeout: i64 = conn.query_row("PRAGMA busy_timeout", [], |row| row.get(0)).unwrap();
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(synchronous, 2, "FULL");
+        assert_eq!(busy_timeout, 5000);
+
+        // A write commits while a read is in progress, and the reader
+        // keeps its snapshot until it finishes
+        let count = |conn: &rusqlite::Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0)).unwrap()
+        };
+        conn.execute_batch("BEGIN").unwrap();
+        assert_eq!(count(&conn), 8);
+        let writer = db.connection().expect("Failed to get a second connection");
+        writer.execute("DELETE FROM users WHERE is_test_user = 1", []).unwrap();
+        assert_eq!(count(&conn), 8);
+        conn.execute_batch("COMMIT").unwrap();
+        assert_eq!(count(&conn), 0);
+
+        drop((conn, writer, db));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
     #[test]
     fn test_seed_test_data() {
         let db = Database::in_memory().expect("Failed to create database");
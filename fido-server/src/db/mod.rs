@@ -1,5 +1,6 @@
 pub mod schema;
 pub mod connection;
 pub mod repositories;
+pub mod backup;
 
-pub use connection::{Database, DbPool};
+pub use connection::{ConnectionOptions, Database, DbPool, Synchronous};
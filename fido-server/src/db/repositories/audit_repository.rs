@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+
+/// One admin action as recorded in the audit log
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub actor_id: Uuid,
+    pub actor_username: String,
+    /// Dotted name such as `badge.grant` or `moderation.remove`
+    pub action: String,
+    /// What was acted on, e.g. `post:<id>` or `user:<id> badge:bot`
+    pub target: String,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct AuditRepository {
+    pool: DbPool,
+}
+
+impl AuditRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Append an entry for `actor_id`, looking up their current username
+    pub fn record(&self, actor_id: &Uuid, action: &str, target: &str, reason: Option<&str>) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO admin_audit_log (actor_id, actor_username, action, target, reason, created_at)
+             SELECT ?1, COALESCE((SELECT username FROM users WHERE id = ?1), ''), ?2, ?3, ?4, ?5",
+            (
+                actor_id.to_string(),
+                action,
+                target,
+                reason.map(str::trim).filter(|reason| !reason.is_empty()),
+                Utc::now().to_rfc3339(),
+            ),
+        )
+        .context("Failed to write audit entry")?;
+        Ok(())
+    }
+
+    /// Newest entries first, optionally only one `action` and only entries
+    /// older than `before_id`
+    pub fn list(&self, action: Option<&str>, before_id: Option<i64>, limit: usize) -> Result<Vec<AuditEntry>> {
+        let conn = self.pool.get()?;
+        Self::list_from(&conn, action, before_id, Some(limit))
+    }
+
+    /// Read entries straight from a connection, for tools working on a database
+    /// file without a pool; `limit` of `None` reads everything
+    pub fn list_from(
+        conn: &Connection,
+        action: Option<&str>,
+        before_id: Option<i64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<AuditEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, actor_id, actor_username, action, target, reason, created_at
+             FROM admin_audit_log
+             WHERE (?1 IS NULL OR action = ?1) AND (?2 IS NULL OR id < ?2)
+             ORDER BY id DESC
+             LIMIT ?3",
+        )
+        .context("Failed to read audit log")?;
+        let limit = limit.map_or(-1, |limit| limit as i64);
+        let entries = stmt
+            .query_map((action, before_id, limit), |row| {
+                Ok(AuditEntry {
+                    id: row.get(0)?,
+                    actor_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+                    actor_username: row.get(2)?,
+                    action: row.get(3)?,
+                    target: row.get(4)?,
+                    reason: row.get(5)?,
+                    created_at: row
+                        .get::<_, String>(6)?
+                        .parse::<DateTime<Utc>>()
+                        .unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to read audit log")?;
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    #[test]
+    fn test_audit_log_is_append_only_and_filterable() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let admin = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (admin.to_string(), "alice", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = AuditRepository::new(db.pool.clone());
+        repo.record(&admin, "badge.grant", "user:bob badge:bot", Some("  "))?;
+        repo.record(&admin, "moderation.remove", "post:1", Some("spam"))?;
+        repo.record(&admin, "badge.revoke", "user:bob badge:bot", None)?;
+
+        let all = repo.list(None, None, 10)?;
+        let actions: Vec<_> = all.iter().map(|entry| entry.action.as_str()).collect();
+        assert_eq!(actions, vec!["badge.revoke", "moderation.remove", "badge.grant"]);
+        assert_eq!(all[1].actor_username, "alice");
+        assert_eq!(all[1].reason.as_deref(), Some("spam"));
+        assert_eq!(all[2].reason, None);
+
+        assert_eq!(repo.list(Some("badge.grant"), None, 10)?.len(), 1);
+        assert_eq!(repo.list(None, Some(all[0].id), 10)?.len(), 2);
+        assert_eq!(repo.list(None, None, 1)?.len(), 1);
+
+        // Neither edits nor deletes get through
+        let conn = db.pool.get()?;
+        assert!(conn.execute("UPDATE admin_audit_log SET reason = 'oops'", []).is_err());
+        assert!(conn.execute("DELETE FROM admin_audit_log", []).is_err());
+        assert_eq!(AuditRepository::list_from(&conn, None, None, None)?.len(), 3);
+        Ok(())
+    }
+}
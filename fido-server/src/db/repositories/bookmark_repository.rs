@@ -0,0 +1,101 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+
+pub struct BookmarkRepository {
+    pool: DbPool,
+}
+
+impl BookmarkRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Bookmark every post in `post_ids` that exists; returns how many
+    /// weren't bookmarked already
+    pub fn add_many(&self, user_id: &Uuid, post_ids: &[Uuid]) -> Result<usize> {
+        let now = Utc::now().to_rfc3339();
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+        let mut added = 0;
+        for post_id in post_ids {
+            added += tx.execute(
+                "INSERT OR IGNORE INTO bookmarks (user_id, post_id, created_at)
+                 SELECT ?, id, ? FROM posts WHERE id = ?",
+                (user_id.to_string(), &now, post_id.to_string()),
+            ).context("Failed to bookmark post")?;
+        }
+        tx.commit()?;
+        Ok(added)
+    }
+
+    /// Remove a bookmark; returns whether there was one
+    pub fn remove(&self, user_id: &Uuid, post_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn.execute(
+            "DELETE FROM bookmarks WHERE user_id = ? AND post_id = ?",
+            (user_id.to_string(), post_id.to_string()),
+        ).context("Failed to remove bookmark")?;
+        Ok(removed > 0)
+    }
+
+    /// The user's bookmarked posts, most recently bookmarked first
+    pub fn post_ids(&self, user_id: &Uuid) -> Result<Vec<Uuid>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT post_id FROM bookmarks WHERE user_id = ? ORDER BY rowid DESC",
+        )?;
+        let ids = stmt
+            .query_map([user_id.to_string()], |row| {
+                Ok(Uuid::parse_str(&row.get::<_, String>(0)?).unwrap())
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list bookmarks")?;
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(id)
+    }
+
+    fn insert_post(db: &Database, author_id: &Uuid) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO posts (id, author_id, content, created_at) VALUES (?, ?, ?, ?)",
+            (id.to_string(), author_id.to_string(), "hello", "2024-01-01T00:00:00Z"),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_add_many_skips_repeats_and_unknown_posts() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let repo = BookmarkRepository::new(db.pool.clone());
+        let alice = insert_user(&db, "alice")?;
+        let first = insert_post(&db, &alice)?;
+        let second = insert_post(&db, &alice)?;
+
+        assert_eq!(repo.add_many(&alice, &[first, Uuid::new_v4()])?, 1);
+        assert_eq!(repo.add_many(&alice, &[first, second])?, 1);
+        assert_eq!(repo.post_ids(&alice)?, vec![second, first]);
+
+        assert!(repo.remove(&alice, &first)?);
+        assert!(!repo.remove(&alice, &first)?);
+        assert_eq!(repo.post_ids(&alice)?, vec![second]);
+        Ok(())
+    }
+}
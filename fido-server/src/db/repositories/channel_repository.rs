@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::OptionalExtension;
+use uuid::Uuid;
+
+use fido_types::Channel;
+
+use crate::db::DbPool;
+
+pub struct ChannelRepository {
+    pool: DbPool,
+}
+
+impl ChannelRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a channel, with its creator as the first member. Returns
+    /// false when the name is taken.
+    pub fn create(&self, name: &str, description: &str, creator_id: &Uuid) -> Result<bool> {
+        let id = Uuid::new_v4();
+        let now = Utc::now().to_rfc3339();
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+        let inserted = tx
+            .execute(
+                "INSERT OR IGNORE INTO channels (id, name, description, created_by, created_at)
+                 VALUES (?, ?, ?, ?, ?)",
+                (id.to_string(), name, description, creator_id.to_string(), &now),
+            )
+            .context("Failed to create channel")?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+        tx.execute(
+            "INSERT INTO channel_members (channel_id, user_id, joined_at, last_read_at)
+             VALUES (?, ?, ?, ?)",
+            (id.to_string(), creator_id.to_string(), &now, &now),
+        )
+        .context("Failed to join new channel")?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// ID of the channel called `name`
+    pub fn id_by_name(&self, name: &str) -> Result<Option<Uuid>> {
+        let conn = self.pool.get()?;
+        let id: Option<String> = conn
+            .query_row("SELECT id FROM channels WHERE name = ?", [name], |row| row.get(0))
+            .optional()?;
+        Ok(id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// Every channel, joined ones first, then by size. `viewer_id` fills in
+    /// `joined` and `unread_count`.
+    pub fn list(&self, viewer_id: Option<&Uuid>) -> Result<Vec<Channel>> {
+        let conn = self.pool.get()?;
+        let viewer = viewer_id.map(|id| id.to_string()).unwrap_or_default();
+        let mut stmt = conn.prepare(
+            "SELECT c.name, c.description, c.created_at,
+                    (SELECT COUNT(*) FROM channel_members WHERE channel_id = c.id) AS members,
+                    m.user_id IS NOT NULL AS joined,
+                    CASE WHEN m.user_id IS NULL THEN 0 ELSE
+                        (SELECT COUNT(*) FROM channel_posts cp
+                         JOIN posts p ON p.id = cp.post_id
+                         WHERE cp.channel_id = c.id AND p.author_id != ?1
+                           AND p.is_deleted = 0 AND p.created_at > m.last_read_at)
+                    END AS unread
+             FROM channels c
+             LEFT JOIN channel_members m ON m.channel_id = c.id AND m.user_id = ?1
+             ORDER BY joined DESC, members DESC, c.name ASC",
+        )?;
+        let channels = stmt
+            .query_map([viewer], |row| {
+                Ok(Channel {
+                    name: row.get(0)?,
+                    description: row.get(1)?,
+                    created_at: row
+                        .get::<_, String>(2)?
+                        .parse::<DateTime<Utc>>()
+                        .unwrap_or_else(|_| Utc::now()),
+                    member_count: row.get(3)?,
+                    joined: row.get(4)?,
+                    unread_count: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list channels")?;
+        Ok(channels)
+    }
+
+    /// Join a channel; what was posted before now doesn't count as unread
+    pub fn join(&self, channel_id: &Uuid, user_id: &Uuid) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT OR IGNORE INTO channel_members (channel_id, user_id, joined_at, last_read_at)
+             VALUES (?, ?, ?, ?)",
+            (channel_id.to_string(), user_id.to_string(), &now, &now),
+        )
+        .context("Failed to join channel")?;
+        Ok(())
+    }
+
+    /// Leave a channel; returns whether the user was a member
+    pub fn leave(&self, channel_id: &Uuid, user_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn
+            .execute(
+                "DELETE FROM channel_members WHERE channel_id = ? AND user_id = ?",
+                (channel_id.to_string(), user_id.to_string()),
+            )
+            .context("Failed to leave channel")?;
+        Ok(removed > 0)
+    }
+
+    pub fn is_member(&self, channel_id: &Uuid, user_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let member = conn
+            .query_row(
+                "SELECT 1 FROM channel_members WHERE channel_id = ? AND user_id = ?",
+                (channel_id.to_string(), user_id.to_string()),
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(member.is_some())
+    }
+
+    /// The channel has been read up to now; returns whether the user is a member
+    pub fn mark_read(&self, channel_id: &Uuid, user_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let updated = conn
+            .execute(
+                "UPDATE channel_members SET last_read_at = ? WHERE channel_id = ? AND user_id = ?",
+                (Utc::now().to_rfc3339(), channel_id.to_string(), user_id.to_string()),
+            )
+            .context("Failed to mark channel read")?;
+        Ok(updated > 0)
+    }
+
+    /// File a new top-level post under a channel
+    pub fn add_post(&self, channel_id: &Uuid, post_id: &Uuid) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO channel_posts (post_id, channel_id) VALUES (?, ?)",
+            (post_id.to_string(), channel_id.to_string()),
+        )
+        .context("Failed to add post to channel")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{test_post, PostRepository};
+    use crate::db::Database;
+    use fido_types::SortOrder;
+
+    #[test]
+    fn test_channel_posts_stay_out_of_the_global_feed_and_count_as_unread() {
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        db.seed_test_data().unwrap();
+        let channels = ChannelRepository::new(db.pool.clone());
+        let posts = PostRepository::new(db.pool.clone());
+        let alice = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap();
+        let bob = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440002").unwrap();
+
+        assert!(channels.create("borrowck", "", &alice).unwrap());
+        assert!(!channels.create("borrowck", "taken", &bob).unwrap());
+        let channel_id = channels.id_by_name("borrowck").unwrap().unwrap();
+        channels.join(&channel_id, &bob).unwrap();
+        // Posts from before joining aren't unread
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        let in_channel = test_post(bob, "lifetimes question");
+        posts.create(&in_channel).unwrap();
+        channels.add_post(&channel_id, &in_channel.id).unwrap();
+
+        let feed = posts.get_posts(SortOrder::Newest, 100).unwrap();
+        assert!(feed.iter().all(|p| p.id != in_channel.id));
+        let channel_feed = posts.get_channel_posts(&channel_id, SortOrder::Newest, 10).unwrap();
+        assert_eq!(channel_feed.len(), 1);
+        assert_eq!(channel_feed[0].id, in_channel.id);
+
+        let listed = channels.list(Some(&alice)).unwrap();
+        let borrowck = listed.iter().find(|c| c.name == "borrowck").unwrap();
+        assert!(borrowck.joined);
+        assert_eq!(borrowck.member_count, 2);
+        assert_eq!(borrowck.unread_count, 1);
+        // The author's own post isn't unread for them
+        let for_bob = channels.list(Some(&bob)).unwrap();
+        assert_eq!(for_bob.iter().find(|c| c.name == "borrowck").unwrap().unread_count, 0);
+
+        assert!(channels.mark_read(&channel_id, &alice).unwrap());
+        let listed = channels.list(Some(&alice)).unwrap();
+        assert_eq!(listed.iter().find(|c| c.name == "borrowck").unwrap().unread_count, 0);
+
+        assert!(channels.leave(&channel_id, &bob).unwrap());
+        assert!(!channels.is_member(&channel_id, &bob).unwrap());
+        assert!(!channels.mark_read(&channel_id, &bob).unwrap());
+    }
+}
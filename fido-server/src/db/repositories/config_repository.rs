@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 use rusqlite::OptionalExtension;
+use std::collections::HashSet;
 use uuid::Uuid;
 
-use fido_types::{UserConfig, ColorScheme, SortOrder};
+use fido_types::{
+    AnnounceOutput, UserConfig, ColorScheme, DigestFrequency, FeedDensity, SortOrder, TimestampStyle,
+};
 
 use crate::db::DbPool;
 
@@ -19,7 +22,12 @@ impl ConfigRepository {
     pub fn get(&self, user_id: &Uuid) -> Result<UserConfig> {
         let conn = self.pool.get()?;
         let mut stmt = conn.prepare(
-            "SELECT user_id, color_scheme, sort_order, max_posts_display, emoji_enabled
+            "SELECT user_id, color_scheme, sort_order, max_posts_display, emoji_enabled,
+                    votes_visible_to_authors, do_not_disturb, quiet_hours_enabled,
+                    quiet_hours_start, quiet_hours_end, show_unread_counts,
+                    accessible_layout, announcements, reduced_motion, timestamp_style,
+                    digest_frequency, feed_density, preview_lines, show_vote_counts,
+                    pinned_hashtags, discoverable, show_karma
              FROM user_configs
              WHERE user_id = ?"
         )?;
@@ -34,6 +42,28 @@ impl ConfigRepository {
                 sort_order: SortOrder::parse(&sort_order_str).unwrap_or_default(),
                 max_posts_display: row.get(3)?,
                 emoji_enabled: row.get::<_, i32>(4)? == 1,
+                votes_visible_to_authors: row.get::<_, i32>(5)? == 1,
+                do_not_disturb: row.get::<_, i32>(6)? == 1,
+                quiet_hours_enabled: row.get::<_, i32>(7)? == 1,
+                quiet_hours_start: row.get(8)?,
+                quiet_hours_end: row.get(9)?,
+                show_unread_counts: row.get::<_, i32>(10)? == 1,
+                accessible_layout: row.get::<_, i32>(11)? == 1,
+                announcements: AnnounceOutput::parse(&row.get::<_, String>(12)?).unwrap_or_default(),
+                reduced_motion: row.get::<_, i32>(13)? == 1,
+                timestamp_style: TimestampStyle::parse(&row.get::<_, String>(14)?).unwrap_or_default(),
+                feed_density: FeedDensity::parse(&row.get::<_, String>(16)?).unwrap_or_default(),
+                preview_lines: row.get(17)?,
+                show_vote_counts: row.get::<_, i32>(18)? == 1,
+                show_karma: row.get::<_, i32>(21)? == 1,
+                // Space-separated; hashtags never contain spaces
+                pinned_hashtags: row
+                    .get::<_, String>(19)?
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+                digest_frequency: DigestFrequency::parse(&row.get::<_, String>(15)?).unwrap_or_default(),
+                discoverable: row.get::<_, i32>(20)? == 1,
             })
         }).optional()?;
 
@@ -50,26 +80,94 @@ impl ConfigRepository {
         let conn = self.pool.get()?;
         
         conn.execute(
-            "INSERT INTO user_configs (user_id, color_scheme, sort_order, max_posts_display, emoji_enabled)
-             VALUES (?, ?, ?, ?, ?)
+            "INSERT INTO user_configs (user_id, color_scheme, sort_order, max_posts_display, emoji_enabled, votes_visible_to_authors,
+                                       do_not_disturb, quiet_hours_enabled, quiet_hours_start, quiet_hours_end,
+                                       show_unread_counts, accessible_layout, announcements, reduced_motion,
+                                       timestamp_style, digest_frequency, feed_density, preview_lines,
+                                       show_vote_counts, pinned_hashtags, discoverable, show_karma)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
              ON CONFLICT(user_id) 
              DO UPDATE SET 
                 color_scheme = excluded.color_scheme,
                 sort_order = excluded.sort_order,
                 max_posts_display = excluded.max_posts_display,
-                emoji_enabled = excluded.emoji_enabled",
-            (
+                emoji_enabled = excluded.emoji_enabled,
+                votes_visible_to_authors = excluded.votes_visible_to_authors,
+                do_not_disturb = excluded.do_not_disturb,
+                quiet_hours_enabled = excluded.quiet_hours_enabled,
+                quiet_hours_start = excluded.quiet_hours_start,
+                quiet_hours_end = excluded.quiet_hours_end,
+                show_unread_counts = excluded.show_unread_counts,
+                accessible_layout = excluded.accessible_layout,
+                announcements = excluded.announcements,
+                reduced_motion = excluded.reduced_motion,
+                timestamp_style = excluded.timestamp_style,
+                digest_frequency = excluded.digest_frequency,
+                feed_density = excluded.feed_density,
+                preview_lines = excluded.preview_lines,
+                show_vote_counts = excluded.show_vote_counts,
+                pinned_hashtags = excluded.pinned_hashtags,
+                discoverable = excluded.discoverable,
+                show_karma = excluded.show_karma",
+            rusqlite::params![
                 config.user_id.to_string(),
                 config.color_scheme.as_str(),
                 config.sort_order.as_str(),
                 config.max_posts_display,
                 if config.emoji_enabled { 1 } else { 0 },
-            ),
+                if config.votes_visible_to_authors { 1 } else { 0 },
+                if config.do_not_disturb { 1 } else { 0 },
+                if config.quiet_hours_enabled { 1 } else { 0 },
+                config.quiet_hours_start,
+                config.quiet_hours_end,
+                if config.show_unread_counts { 1 } else { 0 },
+                if config.accessible_layout { 1 } else { 0 },
+                config.announcements.as_str(),
+                if config.reduced_motion { 1 } else { 0 },
+                config.timestamp_style.as_str(),
+                config.digest_frequency.as_str(),
+                config.feed_density.as_str(),
+                config.preview_lines,
+                if config.show_vote_counts { 1 } else { 0 },
+                config.pinned_hashtags.join(" "),
+                if config.discoverable { 1 } else { 0 },
+                if config.show_karma { 1 } else { 0 },
+            ],
         ).context("Failed to update user config")?;
         
         Ok(())
     }
 
+    /// Users who opted in to digests, with how often they want one
+    pub fn digest_subscribers(&self) -> Result<Vec<(Uuid, DigestFrequency)>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT user_id, digest_frequency FROM user_configs WHERE digest_frequency != 'Off'",
+        )?;
+        let subscribers = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(user_id, frequency)| {
+                Some((Uuid::parse_str(&user_id).ok()?, DigestFrequency::parse(&frequency)?))
+            })
+            .collect();
+        Ok(subscribers)
+    }
+
+    /// Users who let search engines index their public pages
+    pub fn discoverable_user_ids(&self) -> Result<HashSet<Uuid>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare("SELECT user_id FROM user_configs WHERE discoverable = 1")?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .filter_map(|id| id.ok().and_then(|id| Uuid::parse_str(&id).ok()))
+            .collect();
+        Ok(ids)
+    }
+
     /// Create default configuration for a user
     #[allow(dead_code)]
     pub fn create_default(&self, user_id: &Uuid) -> Result<()> {
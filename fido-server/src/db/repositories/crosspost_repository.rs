@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::OptionalExtension;
+use uuid::Uuid;
+
+use fido_types::{Crosspost, CrosspostStatus};
+
+use crate::db::DbPool;
+
+/// How long decided requests stay listed for the requester to see the outcome
+const DECIDED_VISIBLE_DAYS: i64 = 7;
+
+/// Columns for `crosspost_from_row`, followed by the requester's ID
+const SELECT_CROSSPOST: &str =
+    "SELECT c.id, c.message_id, r.username, a.username, c.content, c.status, c.created_at, c.post_id,
+            c.requester_id
+     FROM dm_crossposts c
+     JOIN users r ON r.id = c.requester_id
+     JOIN users a ON a.id = c.approver_id";
+
+fn crosspost_from_row(row: &rusqlite::Row) -> rusqlite::Result<Crosspost> {
+    let post_id: Option<String> = row.get(7)?;
+    Ok(Crosspost {
+        id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+        message_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+        requester_username: row.get(2)?,
+        approver_username: row.get(3)?,
+        content: row.get(4)?,
+        status: CrosspostStatus::parse(&row.get::<_, String>(5)?).unwrap_or(CrosspostStatus::Pending),
+        created_at: row.get::<_, String>(6)?.parse::<DateTime<Utc>>().unwrap(),
+        post_id: post_id.and_then(|id| Uuid::parse_str(&id).ok()),
+    })
+}
+
+pub struct CrosspostRepository {
+    pool: DbPool,
+}
+
+impl CrosspostRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a pending request and return its ID
+    pub fn create(
+        &self,
+        message_id: &Uuid,
+        requester_id: &Uuid,
+        approver_id: &Uuid,
+        content: &str,
+    ) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO dm_crossposts (id, message_id, requester_id, approver_id, content, status, created_at)
+             VALUES (?, ?, ?, ?, ?, 'pending', ?)",
+            (
+                id.to_string(),
+                message_id.to_string(),
+                requester_id.to_string(),
+                approver_id.to_string(),
+                content,
+                Utc::now().to_rfc3339(),
+            ),
+        ).context("Failed to create cross-post request")?;
+        Ok(id)
+    }
+
+    /// Whether the requester already has a request waiting on this message
+    pub fn has_pending(&self, message_id: &Uuid, requester_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let pending: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM dm_crossposts
+                           WHERE message_id = ? AND requester_id = ? AND status = 'pending')",
+            (message_id.to_string(), requester_id.to_string()),
+            |row| row.get(0),
+        )?;
+        Ok(pending)
+    }
+
+    /// Requests waiting on `user_id`'s approval, plus their own recent ones
+    /// (pending or decided in the last week), newest first
+    pub fn list_for_user(&self, user_id: &Uuid) -> Result<Vec<Crosspost>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(&format!(
+            "{SELECT_CROSSPOST}
+             WHERE (c.approver_id = ?1 AND c.status = 'pending')
+                OR (c.requester_id = ?1 AND c.created_at >= ?2)
+             ORDER BY c.created_at DESC"
+        ))?;
+        let since = (Utc::now() - Duration::days(DECIDED_VISIBLE_DAYS)).to_rfc3339();
+        let crossposts = stmt
+            .query_map((user_id.to_string(), since), crosspost_from_row)?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list cross-post requests")?;
+        Ok(crossposts)
+    }
+
+    /// A pending request awaiting `approver_id`, with the requester's ID
+    pub fn get_pending_for_approver(
+        &self,
+        id: &Uuid,
+        approver_id: &Uuid,
+    ) -> Result<Option<(Crosspost, Uuid)>> {
+        let conn = self.pool.get()?;
+        let found = conn
+            .query_row(
+                &format!(
+                    "{SELECT_CROSSPOST}
+                     WHERE c.id = ? AND c.approver_id = ? AND c.status = 'pending'"
+                ),
+                (id.to_string(), approver_id.to_string()),
+                |row| {
+                    let requester_id = Uuid::parse_str(&row.get::<_, String>(8)?).unwrap();
+                    Ok((crosspost_from_row(row)?, requester_id))
+                },
+            )
+            .optional()?;
+        Ok(found)
+    }
+
+    /// Settle a pending request. Returns false if it was already decided.
+    pub fn decide(&self, id: &Uuid, status: CrosspostStatus, post_id: Option<&Uuid>) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let updated = conn.execute(
+            "UPDATE dm_crossposts SET status = ?, post_id = ? WHERE id = ? AND status = 'pending'",
+            (status.as_str(), post_id.map(|id| id.to_string()), id.to_string()),
+        ).context("Failed to update cross-post request")?;
+        Ok(updated > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::DirectMessageRepository;
+    use crate::db::Database;
+    use fido_types::DirectMessage;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_crosspost_waits_on_the_other_participant() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+
+        let message = DirectMessage {
+            id: Uuid::new_v4(),
+            from_user_id: alice,
+            to_user_id: bob,
+            from_username: String::new(),
+            to_username: String::new(),
+            content: "the build is green".to_string(),
+            created_at: Utc::now(),
+            is_read: false,
+            attachment: None,
+        };
+        DirectMessageRepository::new(db.pool.clone()).create(&message)?;
+
+        let repo = CrosspostRepository::new(db.pool.clone());
+        let id = repo.create(&message.id, &bob, &alice, "> the build is green")?;
+        assert!(repo.has_pending(&message.id, &bob)?);
+
+        // Both sides see it; only the approver can act on it
+        assert_eq!(repo.list_for_user(&alice)?[0].requester_username, "bob");
+        assert_eq!(repo.list_for_user(&bob)?[0].status, CrosspostStatus::Pending);
+        assert!(repo.get_pending_for_approver(&id, &bob)?.is_none());
+        let (request, requester) = repo.get_pending_for_approver(&id, &alice)?.unwrap();
+        assert_eq!((request.content.as_str(), requester), ("> the build is green", bob));
+
+        assert!(repo.decide(&id, CrosspostStatus::Declined, None)?);
+        assert!(!repo.decide(&id, CrosspostStatus::Approved, None)?, "Decisions are final");
+        assert!(repo.list_for_user(&alice)?.is_empty(), "Nothing left to approve");
+        assert_eq!(repo.list_for_user(&bob)?[0].status, CrosspostStatus::Declined);
+        assert!(!repo.has_pending(&message.id, &bob)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::OptionalExtension;
+use uuid::Uuid;
+
+use fido_types::{Digest, HashtagChangelog};
+
+use crate::db::DbPool;
+
+pub struct DigestRepository {
+    pool: DbPool,
+}
+
+impl DigestRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store a freshly compiled digest. One with nothing in it is stored as
+    /// already seen so it only marks the period boundary.
+    pub fn store(&self, user_id: &Uuid, digest: &Digest) -> Result<()> {
+        let body = serde_json::to_string(digest).context("Failed to serialize digest")?;
+        let empty = digest.top_posts.is_empty() && digest.unread_messages.is_empty();
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO digests (id, user_id, period_end, body, seen) VALUES (?, ?, ?, ?, ?)",
+            (
+                digest.id.to_string(),
+                user_id.to_string(),
+                digest.period_end.to_rfc3339(),
+                body,
+                empty as i32,
+            ),
+        ).context("Failed to store digest")?;
+        Ok(())
+    }
+
+    /// End of the most recent digest period compiled for a user
+    pub fn last_period_end(&self, user_id: &Uuid) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.pool.get()?;
+        let period_end: Option<String> = conn
+            .query_row(
+                "SELECT MAX(period_end) FROM digests WHERE user_id = ?",
+                [user_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(period_end.and_then(|s| s.parse().ok()))
+    }
+
+    /// The newest digest the user hasn't dismissed yet
+    pub fn latest_unseen(&self, user_id: &Uuid) -> Result<Option<Digest>> {
+        let conn = self.pool.get()?;
+        let body: Option<String> = conn
+            .query_row(
+                "SELECT body FROM digests WHERE user_id = ? AND seen = 0
+                 ORDER BY period_end DESC LIMIT 1",
+                [user_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        body.map(|body| serde_json::from_str(&body).context("Stored digest is corrupt"))
+            .transpose()
+    }
+
+    /// Dismiss a digest along with any older ones. Returns false if the user
+    /// has no digest with that ID.
+    pub fn mark_seen(&self, user_id: &Uuid, digest_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let updated = conn.execute(
+            "UPDATE digests SET seen = 1
+             WHERE user_id = ?1 AND period_end <= (
+                 SELECT period_end FROM digests WHERE id = ?2 AND user_id = ?1
+             )",
+            (user_id.to_string(), digest_id.to_string()),
+        ).context("Failed to mark digest seen")?;
+        Ok(updated > 0)
+    }
+
+    /// Store a hashtag's weekly changelog, quiet weeks included so the next
+    /// week starts where this one ended
+    pub fn store_changelog(&self, changelog: &HashtagChangelog) -> Result<()> {
+        let body = serde_json::to_string(changelog).context("Failed to serialize changelog")?;
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO hashtag_changelogs (id, hashtag_id, period_end, body)
+             SELECT ?, id, ?, ? FROM hashtags WHERE name = ?",
+            (
+                changelog.id.to_string(),
+                changelog.period_end.to_rfc3339(),
+                body,
+                &changelog.hashtag,
+            ),
+        ).context("Failed to store changelog")?;
+        Ok(())
+    }
+
+    /// End of the most recent week compiled for a hashtag
+    pub fn last_changelog_end(&self, hashtag: &str) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.pool.get()?;
+        let period_end: Option<String> = conn
+            .query_row(
+                "SELECT MAX(c.period_end) FROM hashtag_changelogs c
+                 JOIN hashtags h ON h.id = c.hashtag_id
+                 WHERE h.name = ?",
+                [hashtag],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(period_end.and_then(|s| s.parse().ok()))
+    }
+
+    /// A hashtag's most recent changelog
+    pub fn latest_changelog(&self, hashtag: &str) -> Result<Option<HashtagChangelog>> {
+        let conn = self.pool.get()?;
+        let body: Option<String> = conn
+            .query_row(
+                "SELECT c.body FROM hashtag_changelogs c
+                 JOIN hashtags h ON h.id = c.hashtag_id
+                 WHERE h.name = ?
+                 ORDER BY c.period_end DESC LIMIT 1",
+                [hashtag],
+                |row| row.get(0),
+            )
+            .optional()?;
+        body.map(|body| serde_json::from_str(&body).context("Stored changelog is corrupt"))
+            .transpose()
+    }
+}
@@ -1,11 +1,38 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use rusqlite::OptionalExtension;
+use std::collections::HashSet;
 use uuid::Uuid;
 
-use fido_types::DirectMessage;
+use fido_types::{DirectMessage, DmAttachment, UnreadFrom};
 
 use crate::db::DbPool;
 
+/// Columns for `message_from_row`
+const SELECT_MESSAGE: &str =
+    "SELECT dm.id, dm.from_user_id, dm.to_user_id, dm.content, dm.created_at, dm.is_read,
+            a.filename, a.body
+     FROM direct_messages dm
+     LEFT JOIN dm_attachments a ON a.message_id = dm.id";
+
+fn message_from_row(row: &rusqlite::Row) -> rusqlite::Result<DirectMessage> {
+    let filename: Option<String> = row.get(6)?;
+    let body: Option<String> = row.get(7)?;
+    Ok(DirectMessage {
+        id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+        from_user_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+        to_user_id: Uuid::parse_str(&row.get::<_, String>(2)?).unwrap(),
+        from_username: String::new(), // Will be populated by API layer
+        to_username: String::new(),   // Will be populated by API layer
+        content: row.get(3)?,
+        created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
+        is_read: row.get::<_, i32>(5)? == 1,
+        attachment: filename
+            .zip(body)
+            .map(|(filename, content)| DmAttachment { filename, content }),
+    })
+}
+
 pub struct DirectMessageRepository {
     pool: DbPool,
 }
@@ -15,10 +42,11 @@ impl DirectMessageRepository {
         Self { pool }
     }
 
-    /// Create a new direct message
+    /// Create a new direct message, with its attachment if it has one
     pub fn create(&self, dm: &DirectMessage) -> Result<()> {
-        let conn = self.pool.get()?;
-        conn.execute(
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+        tx.execute(
             "INSERT INTO direct_messages (id, from_user_id, to_user_id, content, created_at, is_read, deleted_by_from_user, deleted_by_to_user) 
              VALUES (?, ?, ?, ?, ?, ?, 0, 0)",
             (
@@ -30,19 +58,25 @@ impl DirectMessageRepository {
                 if dm.is_read { 1 } else { 0 },
             ),
         ).context("Failed to create direct message")?;
+        if let Some(attachment) = &dm.attachment {
+            tx.execute(
+                "INSERT INTO dm_attachments (message_id, filename, body) VALUES (?, ?, ?)",
+                (dm.id.to_string(), &attachment.filename, &attachment.content),
+            ).context("Failed to store attachment")?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
     /// Get conversation between two users (excluding messages deleted by the requesting user)
     pub fn get_conversation(&self, user1_id: &Uuid, user2_id: &Uuid) -> Result<Vec<DirectMessage>> {
         let conn = self.pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, from_user_id, to_user_id, content, created_at, is_read
-             FROM direct_messages
-             WHERE ((from_user_id = ? AND to_user_id = ?) OR (from_user_id = ? AND to_user_id = ?))
-               AND ((from_user_id = ? AND deleted_by_from_user = 0) OR (to_user_id = ? AND deleted_by_to_user = 0))
-             ORDER BY created_at ASC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "{SELECT_MESSAGE}
+             WHERE ((dm.from_user_id = ? AND dm.to_user_id = ?) OR (dm.from_user_id = ? AND dm.to_user_id = ?))
+               AND ((dm.from_user_id = ? AND dm.deleted_by_from_user = 0) OR (dm.to_user_id = ? AND dm.deleted_by_to_user = 0))
+             ORDER BY dm.created_at ASC"
+        ))?;
 
         let messages = stmt.query_map(
             (
@@ -53,25 +87,33 @@ impl DirectMessageRepository {
                 user1_id.to_string(),
                 user1_id.to_string(),
             ),
-            |row| {
-                Ok(DirectMessage {
-                    id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
-                    from_user_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
-                    to_user_id: Uuid::parse_str(&row.get::<_, String>(2)?).unwrap(),
-                    from_username: String::new(), // Will be populated by API layer
-                    to_username: String::new(),   // Will be populated by API layer
-                    content: row.get(3)?,
-                    created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
-                    is_read: row.get::<_, i32>(5)? == 1,
-                })
-            }
+            message_from_row,
         )?
         .collect::<Result<Vec<_>, _>>()?;
 
         Ok(messages)
     }
 
-    /// Get list of users the current user has conversations with (excluding deleted conversations)
+    /// A single message, if `user_id` sent or received it and hasn't deleted it
+    pub fn get_message_for(&self, message_id: &Uuid, user_id: &Uuid) -> Result<Option<DirectMessage>> {
+        let conn = self.pool.get()?;
+        let message = conn
+            .query_row(
+                &format!(
+                    "{SELECT_MESSAGE}
+                     WHERE dm.id = ?1
+                       AND ((dm.from_user_id = ?2 AND dm.deleted_by_from_user = 0)
+                            OR (dm.to_user_id = ?2 AND dm.deleted_by_to_user = 0))"
+                ),
+                (message_id.to_string(), user_id.to_string()),
+                message_from_row,
+            )
+            .optional()?;
+        Ok(message)
+    }
+
+    /// Get list of users the current user has conversations with (excluding deleted conversations),
+    /// pinned conversations first and otherwise most recent activity first
     pub fn get_conversations_list(&self, user_id: &Uuid) -> Result<Vec<Uuid>> {
         let conn = self.pool.get()?;
         let mut stmt = conn.prepare(
@@ -83,7 +125,9 @@ impl DirectMessageRepository {
              FROM direct_messages
              WHERE ((from_user_id = ? AND deleted_by_from_user = 0) 
                 OR (to_user_id = ? AND deleted_by_to_user = 0))
-             ORDER BY (SELECT MAX(created_at) 
+             ORDER BY EXISTS(SELECT 1 FROM pinned_conversations p
+                             WHERE p.user_id = ? AND p.pinned_user_id = other_user_id) DESC,
+                      (SELECT MAX(created_at) 
                       FROM direct_messages dm2 
                       WHERE ((dm2.from_user_id = ? AND dm2.to_user_id = other_user_id AND dm2.deleted_by_from_user = 0)
                          OR (dm2.to_user_id = ? AND dm2.from_user_id = other_user_id AND dm2.deleted_by_to_user = 0))) DESC"
@@ -96,6 +140,7 @@ impl DirectMessageRepository {
                 user_id.to_string(),
                 user_id.to_string(),
                 user_id.to_string(),
+                user_id.to_string(),
             ),
             |row| {
                 let id_str: String = row.get(0)?;
@@ -107,6 +152,39 @@ impl DirectMessageRepository {
         Ok(user_ids)
     }
 
+    /// Conversations `user_id` pinned, by the other user's ID
+    pub fn get_pinned(&self, user_id: &Uuid) -> Result<HashSet<Uuid>> {
+        let conn = self.pool.get()?;
+        let mut stmt =
+            conn.prepare("SELECT pinned_user_id FROM pinned_conversations WHERE user_id = ?")?;
+        let pinned = stmt
+            .query_map([user_id.to_string()], |row| row.get::<_, String>(0))?
+            .filter_map(|id| id.ok().and_then(|id| Uuid::parse_str(&id).ok()))
+            .collect();
+        Ok(pinned)
+    }
+
+    /// Pin the conversation with `other_user_id` to the top of `user_id`'s list
+    pub fn pin_conversation(&self, user_id: &Uuid, other_user_id: &Uuid) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT OR IGNORE INTO pinned_conversations (user_id, pinned_user_id, pinned_at)
+             VALUES (?, ?, ?)",
+            (user_id.to_string(), other_user_id.to_string(), Utc::now().to_rfc3339()),
+        ).context("Failed to pin conversation")?;
+        Ok(())
+    }
+
+    /// Unpin a conversation; returns whether it was pinned
+    pub fn unpin_conversation(&self, user_id: &Uuid, other_user_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn.execute(
+            "DELETE FROM pinned_conversations WHERE user_id = ? AND pinned_user_id = ?",
+            (user_id.to_string(), other_user_id.to_string()),
+        ).context("Failed to unpin conversation")?;
+        Ok(removed > 0)
+    }
+
     /// Mark messages as read (only non-deleted messages)
     pub fn mark_as_read(&self, user_id: &Uuid, other_user_id: &Uuid) -> Result<()> {
         let conn = self.pool.get()?;
@@ -132,6 +210,28 @@ impl DirectMessageRepository {
         Ok(count)
     }
 
+    /// Unread messages for a user grouped by sender, busiest sender first
+    pub fn unread_by_sender(&self, user_id: &Uuid) -> Result<Vec<UnreadFrom>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT u.username, COUNT(*) AS unread
+             FROM direct_messages dm
+             JOIN users u ON dm.from_user_id = u.id
+             WHERE dm.to_user_id = ? AND dm.is_read = 0 AND dm.deleted_by_to_user = 0
+             GROUP BY dm.from_user_id
+             ORDER BY unread DESC, u.username",
+        )?;
+        let unread = stmt
+            .query_map([user_id.to_string()], |row| {
+                Ok(UnreadFrom {
+                    username: row.get(0)?,
+                    count: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(unread)
+    }
+
     /// Delete conversation for a specific user (soft delete - hides from their view only)
     pub fn delete_conversation(&self, user_id: &Uuid, other_user_id: &Uuid) -> Result<()> {
         let conn = self.pool.get()?;
@@ -179,3 +279,92 @@ impl DirectMessageRepository {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(user_id)
+    }
+
+    #[test]
+    fn test_pinned_conversations_come_first() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let me = insert_user(&db, "me")?;
+        let old_friend = insert_user(&db, "old_friend")?;
+        let chatty = insert_user(&db, "chatty")?;
+
+        let repo = DirectMessageRepository::new(db.pool.clone());
+        for (other, minutes_ago) in [(old_friend, 60), (chatty, 1)] {
+            repo.create(&DirectMessage {
+                id: Uuid::new_v4(),
+                from_user_id: other,
+                to_user_id: me,
+                from_username: String::new(),
+                to_username: String::new(),
+                content: "hi".to_string(),
+                created_at: Utc::now() - chrono::Duration::minutes(minutes_ago),
+                is_read: false,
+                attachment: None,
+            })?;
+        }
+        assert_eq!(repo.get_conversations_list(&me)?, vec![chatty, old_friend]);
+
+        repo.pin_conversation(&me, &old_friend)?;
+        repo.pin_conversation(&me, &old_friend)?;
+        assert_eq!(repo.get_conversations_list(&me)?, vec![old_friend, chatty]);
+        assert_eq!(repo.get_pinned(&me)?, HashSet::from([old_friend]));
+        assert!(repo.get_pinned(&old_friend)?.is_empty(), "Pins are per user");
+
+        assert!(repo.unpin_conversation(&me, &old_friend)?);
+        assert!(!repo.unpin_conversation(&me, &old_friend)?);
+        assert_eq!(repo.get_conversations_list(&me)?, vec![chatty, old_friend]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_attachment_is_stored_with_its_message() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+
+        let repo = DirectMessageRepository::new(db.pool.clone());
+        let message = |content: &str, attachment| DirectMessage {
+            id: Uuid::new_v4(),
+            from_user_id: alice,
+            to_user_id: bob,
+            from_username: String::new(),
+            to_username: String::new(),
+            content: content.to_string(),
+            created_at: Utc::now(),
+            is_read: false,
+            attachment,
+        };
+        let snippet = DmAttachment {
+            filename: "fix.rs".to_string(),
+            content: "fn fix() {}".to_string(),
+        };
+        // Created in send order: messages sort by their timestamps
+        let text = message("see below", None);
+        let with_file = message("", Some(snippet.clone()));
+        repo.create(&text)?;
+        repo.create(&with_file)?;
+
+        let conversation = repo.get_conversation(&bob, &alice)?;
+        assert_eq!(conversation.len(), 2);
+        assert_eq!(conversation[0].attachment, None);
+        assert_eq!(conversation[1].attachment.as_ref(), Some(&snippet));
+        let fetched = repo.get_message_for(&with_file.id, &bob)?.unwrap();
+        assert_eq!(fetched.attachment, Some(snippet));
+        Ok(())
+    }
+}
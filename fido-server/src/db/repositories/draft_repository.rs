@@ -0,0 +1,144 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use fido_types::PostDraft;
+
+use crate::db::DbPool;
+
+pub struct DraftRepository {
+    pool: DbPool,
+}
+
+impl DraftRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// A user's drafts, most recently edited first
+    pub fn list(&self, user_id: &Uuid) -> Result<Vec<PostDraft>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, content, updated_at FROM post_drafts
+             WHERE user_id = ? ORDER BY updated_at DESC",
+        )?;
+        let drafts = stmt
+            .query_map([user_id.to_string()], |row| {
+                Ok(PostDraft {
+                    id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                    content: row.get(1)?,
+                    updated_at: row.get::<_, String>(2)?.parse::<DateTime<Utc>>().unwrap(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list drafts")?;
+        Ok(drafts)
+    }
+
+    /// How many drafts a user has
+    pub fn count(&self, user_id: &Uuid) -> Result<usize> {
+        let conn = self.pool.get()?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM post_drafts WHERE user_id = ?",
+            [user_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    pub fn create(&self, user_id: &Uuid, content: &str) -> Result<PostDraft> {
+        let draft = PostDraft {
+            id: Uuid::new_v4(),
+            content: content.to_string(),
+            updated_at: Utc::now(),
+        };
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO post_drafts (id, user_id, content, updated_at) VALUES (?, ?, ?, ?)",
+            (
+                draft.id.to_string(),
+                user_id.to_string(),
+                &draft.content,
+                draft.updated_at.to_rfc3339(),
+            ),
+        ).context("Failed to save draft")?;
+        Ok(draft)
+    }
+
+    /// Replace a draft's content. Returns `None` if the user has no draft
+    /// with that ID.
+    pub fn update(&self, user_id: &Uuid, draft_id: &Uuid, content: &str) -> Result<Option<PostDraft>> {
+        let updated_at = Utc::now();
+        let conn = self.pool.get()?;
+        let updated = conn
+            .execute(
+                "UPDATE post_drafts SET content = ?, updated_at = ? WHERE id = ? AND user_id = ?",
+                (
+                    content,
+                    updated_at.to_rfc3339(),
+                    draft_id.to_string(),
+                    user_id.to_string(),
+                ),
+            )
+            .context("Failed to update draft")?;
+        Ok((updated > 0).then(|| PostDraft {
+            id: *draft_id,
+            content: content.to_string(),
+            updated_at,
+        }))
+    }
+
+    /// Remove a draft. Returns false if the user has no draft with that ID.
+    pub fn delete(&self, user_id: &Uuid, draft_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn
+            .execute(
+                "DELETE FROM post_drafts WHERE id = ? AND user_id = ?",
+                (draft_id.to_string(), user_id.to_string()),
+            )
+            .context("Failed to delete draft")?;
+        Ok(removed > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_drafts_are_private_and_listed_newest_first() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+
+        let repo = DraftRepository::new(db.pool.clone());
+        let first = repo.create(&alice, "first idea")?;
+        let second = repo.create(&alice, "second idea")?;
+        let edited = repo.update(&alice, &first.id, "first idea, edited")?.unwrap();
+        assert!(edited.updated_at >= second.updated_at);
+
+        let listed: Vec<_> = repo.list(&alice)?.into_iter().map(|d| d.content).collect();
+        assert_eq!(listed, vec!["first idea, edited", "second idea"]);
+        assert_eq!(repo.count(&alice)?, 2);
+
+        // Someone else's draft can't be seen, edited or deleted
+        assert!(repo.list(&bob)?.is_empty());
+        assert!(repo.update(&bob, &second.id, "hijacked")?.is_none());
+        assert!(!repo.delete(&bob, &second.id)?);
+
+        assert!(repo.delete(&alice, &second.id)?);
+        assert_eq!(repo.count(&alice)?, 1);
+        Ok(())
+    }
+}
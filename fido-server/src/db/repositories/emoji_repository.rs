@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use uuid::Uuid;
+
+use fido_types::CustomEmoji;
+
+use crate::db::DbPool;
+
+pub struct EmojiRepository {
+    pool: DbPool,
+}
+
+impl EmojiRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List all custom emoji, sorted by shortcode
+    pub fn list(&self) -> Result<Vec<CustomEmoji>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT shortcode, replacement FROM custom_emoji ORDER BY shortcode",
+        )?;
+        let emoji = stmt
+            .query_map([], |row| {
+                Ok(CustomEmoji {
+                    shortcode: row.get(0)?,
+                    replacement: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list custom emoji")?;
+        Ok(emoji)
+    }
+
+    /// Whether a shortcode is registered
+    pub fn exists(&self, shortcode: &str) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let exists = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM custom_emoji WHERE shortcode = ?)",
+            [shortcode],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
+    /// Register a shortcode, replacing any existing mapping for it
+    pub fn upsert(&self, emoji: &CustomEmoji, created_by: &Uuid) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO custom_emoji (shortcode, replacement, created_by, created_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(shortcode)
+             DO UPDATE SET replacement = excluded.replacement",
+            (
+                &emoji.shortcode,
+                &emoji.replacement,
+                created_by.to_string(),
+                Utc::now().to_rfc3339(),
+            ),
+        ).context("Failed to save custom emoji")?;
+        Ok(())
+    }
+
+    /// Remove a shortcode. Returns false if it wasn't registered.
+    pub fn delete(&self, shortcode: &str) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn
+            .execute("DELETE FROM custom_emoji WHERE shortcode = ?", [shortcode])
+            .context("Failed to delete custom emoji")?;
+        Ok(removed > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    #[test]
+    fn test_upsert_replaces_and_delete_removes() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let admin = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (admin.to_string(), "admin", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = EmojiRepository::new(db.pool.clone());
+        let mut ferris = CustomEmoji {
+            shortcode: "ferris".to_string(),
+            replacement: "🦀".to_string(),
+        };
+        repo.upsert(&ferris, &admin)?;
+        ferris.replacement = "(ferris)".to_string();
+        repo.upsert(&ferris, &admin)?;
+        repo.upsert(
+            &CustomEmoji {
+                shortcode: "ship_it".to_string(),
+                replacement: "🚢".to_string(),
+            },
+            &admin,
+        )?;
+
+        let listed = repo.list()?;
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0], ferris);
+
+        assert!(repo.exists("ferris")?);
+        assert!(repo.delete("ferris")?);
+        assert!(!repo.delete("ferris")?);
+        assert!(!repo.exists("ferris")?);
+        assert_eq!(repo.list()?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use fido_types::{EventDetails, RsvpStatus};
+
+use crate::db::DbPool;
+
+pub struct EventRepository {
+    pool: DbPool,
+}
+
+impl EventRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Make an existing post an event
+    pub fn create(
+        &self,
+        post_id: &Uuid,
+        title: &str,
+        starts_at: DateTime<Utc>,
+        location: &str,
+    ) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO post_events (post_id, title, starts_at, location) VALUES (?, ?, ?, ?)",
+            (post_id.to_string(), title, starts_at.to_rfc3339(), location),
+        )
+        .context("Failed to create event")?;
+        Ok(())
+    }
+
+    pub fn is_event(&self, post_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let found = conn
+            .query_row(
+                "SELECT 1 FROM post_events WHERE post_id = ?",
+                [post_id.to_string()],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    /// Record or change a user's RSVP
+    pub fn set_rsvp(&self, post_id: &Uuid, user_id: &Uuid, status: RsvpStatus) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO event_rsvps (post_id, user_id, status, updated_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(post_id, user_id) DO UPDATE SET status = excluded.status, updated_at = excluded.updated_at",
+            (
+                post_id.to_string(),
+                user_id.to_string(),
+                status.as_str(),
+                Utc::now().to_rfc3339(),
+            ),
+        )
+        .context("Failed to save RSVP")?;
+        Ok(())
+    }
+
+    /// Withdraw a user's RSVP; returns whether there was one
+    pub fn clear_rsvp(&self, post_id: &Uuid, user_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn
+            .execute(
+                "DELETE FROM event_rsvps WHERE post_id = ? AND user_id = ?",
+                (post_id.to_string(), user_id.to_string()),
+            )
+            .context("Failed to withdraw RSVP")?;
+        Ok(removed > 0)
+    }
+
+    /// The user's RSVP to an event, if any
+    pub fn rsvp_of(&self, post_id: &Uuid, user_id: &Uuid) -> Result<Option<RsvpStatus>> {
+        let conn = self.pool.get()?;
+        let status: Option<String> = conn
+            .query_row(
+                "SELECT status FROM event_rsvps WHERE post_id = ? AND user_id = ?",
+                (post_id.to_string(), user_id.to_string()),
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(status.as_deref().and_then(RsvpStatus::parse))
+    }
+}
+
+/// Event details with RSVP counts for each of `post_ids` that is an event.
+/// `my_rsvp` is left for the API layer, which knows who is asking.
+pub(crate) fn events_for(conn: &Connection, post_ids: &[Uuid]) -> Result<HashMap<Uuid, EventDetails>> {
+    let mut events = HashMap::new();
+    if post_ids.is_empty() {
+        return Ok(events);
+    }
+
+    let placeholders = vec!["?"; post_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT e.post_id, e.title, e.starts_at, e.location,
+                (SELECT COUNT(*) FROM event_rsvps r WHERE r.post_id = e.post_id AND r.status = 'going'),
+                (SELECT COUNT(*) FROM event_rsvps r WHERE r.post_id = e.post_id AND r.status = 'interested')
+         FROM post_events e WHERE e.post_id IN ({})",
+        placeholders
+    ))?;
+    let rows = stmt.query_map(
+        rusqlite::params_from_iter(post_ids.iter().map(|id| id.to_string())),
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i32>(4)?,
+                row.get::<_, i32>(5)?,
+            ))
+        },
+    )?;
+    for row in rows {
+        let (post_id, title, starts_at, location, going_count, interested_count) = row?;
+        if let (Ok(post_id), Ok(starts_at)) = (Uuid::parse_str(&post_id), starts_at.parse()) {
+            events.insert(
+                post_id,
+                EventDetails {
+                    title,
+                    starts_at,
+                    location,
+                    going_count,
+                    interested_count,
+                    my_rsvp: None,
+                },
+            );
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{test_post, PostRepository};
+    use crate::db::Database;
+    use fido_types::PostKind;
+
+    #[test]
+    fn test_rsvps_are_counted_on_the_post() {
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        db.seed_test_data().unwrap();
+        let events = EventRepository::new(db.pool.clone());
+        let posts = PostRepository::new(db.pool.clone());
+        let alice = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap();
+        let bob = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440002").unwrap();
+
+        let post = test_post(alice, "Bring a laptop #rust");
+        posts.create(&post).unwrap();
+        let starts_at = Utc::now() + chrono::Duration::days(3);
+        events.create(&post.id, "Rust meetup", starts_at, "Room 4").unwrap();
+
+        events.set_rsvp(&post.id, &alice, RsvpStatus::Going).unwrap();
+        events.set_rsvp(&post.id, &bob, RsvpStatus::Going).unwrap();
+        events.set_rsvp(&post.id, &bob, RsvpStatus::Interested).unwrap();
+        assert_eq!(events.rsvp_of(&post.id, &bob).unwrap(), Some(RsvpStatus::Interested));
+
+        let stored = posts.get_by_id(&post.id).unwrap().unwrap();
+        let Some(PostKind::Event(event)) = stored.kind else {
+            panic!("post should carry its event details");
+        };
+        assert_eq!(event.title, "Rust meetup");
+        assert_eq!(event.going_count, 1);
+        assert_eq!(event.interested_count, 1);
+
+        assert!(events.clear_rsvp(&post.id, &bob).unwrap());
+        assert!(!events.clear_rsvp(&post.id, &bob).unwrap());
+        assert_eq!(events.rsvp_of(&post.id, &bob).unwrap(), None);
+    }
+}
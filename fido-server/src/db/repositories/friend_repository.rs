@@ -4,6 +4,14 @@ use uuid::Uuid;
 
 use crate::db::DbPool;
 
+/// Which of a user's follow relationships to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connections {
+    Following,
+    Followers,
+    Mutual,
+}
+
 pub struct FriendRepository {
     pool: DbPool,
 }
@@ -54,83 +62,83 @@ impl FriendRepository {
         Ok(rows_affected)
     }
 
-    /// Get list of users that this user is following
-    pub fn get_following(&self, user_id: &Uuid) -> Result<Vec<Uuid>> {
-        let conn = self.pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT following_id FROM follows WHERE follower_id = ? ORDER BY created_at DESC",
-        )?;
+    /// One page of a user's connections, most recent follow first. `query`
+    /// matches anywhere in the username, ignoring case; `after` is the last
+    /// user of the previous page (a page after someone no longer in the list
+    /// is empty). No `limit` lists everyone.
+    pub fn list_connections(
+        &self,
+        user_id: &Uuid,
+        connections: Connections,
+        query: Option<&str>,
+        after: Option<&Uuid>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Uuid>> {
+        // `other` is the person listed; `f` is the follow that dates them
+        let (other, mut sql) = match connections {
+            Connections::Following | Connections::Mutual => (
+                "f.following_id",
+                "SELECT f.following_id FROM follows f
+                 JOIN users u ON u.id = f.following_id
+                 WHERE f.follower_id = ?"
+                    .to_string(),
+            ),
+            Connections::Followers => (
+                "f.follower_id",
+                "SELECT f.follower_id FROM follows f
+                 JOIN users u ON u.id = f.follower_id
+                 WHERE f.following_id = ?"
+                    .to_string(),
+            ),
+        };
+        let mut params = vec![user_id.to_string()];
+
+        if connections == Connections::Mutual {
+            sql.push_str(
+                " AND EXISTS (SELECT 1 FROM follows back
+                   WHERE back.follower_id = f.following_id AND back.following_id = f.follower_id)",
+            );
+        }
+        if let Some(query) = query.map(str::trim).filter(|q| !q.is_empty()) {
+            sql.push_str(" AND u.username LIKE ? ESCAPE '\\'");
+            let escaped = query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            params.push(format!("%{}%", escaped));
+        }
+        if let Some(after) = after {
+            let cursor_follow = match connections {
+                Connections::Followers => {
+                    "SELECT created_at FROM follows WHERE follower_id = ? AND following_id = ?"
+                }
+                _ => "SELECT created_at FROM follows WHERE following_id = ? AND follower_id = ?",
+            };
+            sql.push_str(&format!(
+                " AND (f.created_at < ({cursor}) OR (f.created_at = ({cursor}) AND {other} < ?))",
+                cursor = cursor_follow,
+                other = other,
+            ));
+            for _ in 0..2 {
+                params.push(after.to_string());
+                params.push(user_id.to_string());
+            }
+            params.push(after.to_string());
+        }
+        sql.push_str(&format!(" ORDER BY f.created_at DESC, {} DESC", other));
+        if let Some(limit) = limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
 
-        let following = stmt
-            .query_map([user_id.to_string()], |row| {
-                let following_id: String = row.get(0)?;
-                Ok(Uuid::parse_str(&following_id).unwrap())
-            })?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(following)
-    }
-
-    /// Get list of users that follow this user
-    pub fn get_followers(&self, user_id: &Uuid) -> Result<Vec<Uuid>> {
         let conn = self.pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT follower_id FROM follows WHERE following_id = ? ORDER BY created_at DESC",
-        )?;
-
-        let followers = stmt
-            .query_map([user_id.to_string()], |row| {
-                let follower_id: String = row.get(0)?;
-                Ok(Uuid::parse_str(&follower_id).unwrap())
+        let mut stmt = conn.prepare(&sql)?;
+        let ids = stmt
+            .query_map(rusqlite::params_from_iter(params), |row| {
+                row.get::<_, String>(0)
             })?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(followers)
-    }
-
-    /// Get mutual friends (users who follow each other)
-    pub fn get_mutual_friends(&self, user_id: &Uuid) -> Result<Vec<Uuid>> {
-        let conn = self.pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT f1.following_id 
-             FROM follows f1
-             INNER JOIN follows f2 
-                ON f1.follower_id = f2.following_id 
-                AND f1.following_id = f2.follower_id
-             WHERE f1.follower_id = ?
-             ORDER BY f1.created_at DESC",
-        )?;
-
-        let friends = stmt
-            .query_map([user_id.to_string()], |row| {
-                let friend_id: String = row.get(0)?;
-                Ok(Uuid::parse_str(&friend_id).unwrap())
-            })?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(friends)
-    }
-
-    /// Get follower count
-    pub fn get_follower_count(&self, user_id: &Uuid) -> Result<usize> {
-        let conn = self.pool.get()?;
-        let count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM follows WHERE following_id = ?",
-            [user_id.to_string()],
-            |row| row.get(0),
-        )?;
-        Ok(count as usize)
-    }
-
-    /// Get following count
-    pub fn get_following_count(&self, user_id: &Uuid) -> Result<usize> {
-        let conn = self.pool.get()?;
-        let count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM follows WHERE follower_id = ?",
-            [user_id.to_string()],
-            |row| row.get(0),
-        )?;
-        Ok(count as usize)
+            .filter_map(|id| id.ok().and_then(|id| Uuid::parse_str(&id).ok()))
+            .collect();
+        Ok(ids)
     }
 
     // ===== Legacy friendships table methods (for backward compatibility) =====
@@ -153,6 +161,24 @@ impl FriendRepository {
 
         Ok(friends)
     }
+    /// Usernames of users who followed `user_id` at or after `since` (Unix
+    /// seconds), newest first
+    pub fn get_followers_since(&self, user_id: &Uuid, since: i64) -> Result<Vec<String>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT u.username FROM follows f
+             JOIN users u ON f.follower_id = u.id
+             WHERE f.following_id = ? AND f.created_at >= ?
+             ORDER BY f.created_at DESC",
+        )?;
+
+        let followers = stmt
+            .query_map((user_id.to_string(), since), |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(followers)
+    }
+
     /// Check if users are friends (legacy)
     #[allow(dead_code)]
     pub fn are_friends(&self, user_id: &Uuid, friend_id: &Uuid) -> Result<bool> {
@@ -181,6 +207,73 @@ mod tests {
         (db, repo)
     }
 
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_connections_page_by_cursor_and_filter_by_name() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let repo = FriendRepository::new(db.pool.clone());
+        let alice = insert_user(&db, "alice")?;
+        let mut followed = Vec::new();
+        for (i, name) in ["bob", "carol", "dave", "bobby", "erin"].iter().enumerate() {
+            let id = insert_user(&db, name)?;
+            // Two follows share each second, so the cursor has to break ties
+            db.pool.get()?.execute(
+                "INSERT INTO follows (follower_id, following_id, created_at) VALUES (?, ?, ?)",
+                (alice.to_string(), id.to_string(), 1000 + i as i64 / 2),
+            )?;
+            followed.push(id);
+        }
+        repo.follow_user(&followed[1], &alice)?;
+
+        let mut paged = Vec::new();
+        let mut after = None;
+        loop {
+            let page = repo.list_connections(
+                &alice,
+                Connections::Following,
+                None,
+                after.as_ref(),
+                Some(2),
+            )?;
+            paged.extend(page.iter().copied());
+            match page.last() {
+                Some(last) if page.len() == 2 => after = Some(*last),
+                _ => break,
+            }
+        }
+        assert_eq!(paged.len(), 5);
+        assert_eq!(paged[0], followed[4]);
+        let mut sorted = paged.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 5);
+
+        let bobs =
+            repo.list_connections(&alice, Connections::Following, Some("BOB"), None, Some(10))?;
+        assert_eq!(bobs, vec![followed[3], followed[0]]);
+        assert!(repo
+            .list_connections(&alice, Connections::Following, Some("%"), None, Some(10))?
+            .is_empty());
+        assert_eq!(
+            repo.list_connections(&alice, Connections::Mutual, None, None, Some(10))?,
+            vec![followed[1]]
+        );
+        assert_eq!(
+            repo.list_connections(&alice, Connections::Followers, None, None, Some(10))?,
+            vec![followed[1]]
+        );
+        Ok(())
+    }
+
     // TODO: Fix this test - add_friend method doesn't exist
     // #[test]
     // fn test_are_friends_bidirectional_check() {
@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use fido_types::GitHubSuggestion;
+
+use crate::db::DbPool;
+
+pub struct GitHubFollowingRepository {
+    pool: DbPool,
+}
+
+impl GitHubFollowingRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replace the user's GitHub following list, remembering which accounts
+    /// were already offered
+    pub fn replace(&self, user_id: &Uuid, github_ids: &[i64]) -> Result<()> {
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+        let dismissed: HashSet<i64> = tx
+            .prepare("SELECT github_id FROM github_following WHERE user_id = ? AND dismissed = 1")?
+            .query_map([user_id.to_string()], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+
+        tx.execute(
+            "DELETE FROM github_following WHERE user_id = ?",
+            [user_id.to_string()],
+        )?;
+        for github_id in github_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO github_following (user_id, github_id, dismissed) VALUES (?, ?, ?)",
+                (user_id.to_string(), github_id, dismissed.contains(github_id)),
+            ).context("Failed to store GitHub following list")?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Fido users the user follows on GitHub but not here, who haven't been
+    /// offered yet, by username
+    pub fn suggestions(&self, user_id: &Uuid) -> Result<Vec<GitHubSuggestion>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT u.id, u.username, u.github_login
+             FROM github_following g
+             JOIN users u ON u.github_id = g.github_id
+             WHERE g.user_id = ?1 AND g.dismissed = 0 AND u.id != ?1
+               AND NOT EXISTS (SELECT 1 FROM follows f
+                               WHERE f.follower_id = ?1 AND f.following_id = u.id)
+             ORDER BY u.username",
+        )?;
+        let suggestions = stmt
+            .query_map([user_id.to_string()], |row| {
+                let username: String = row.get(1)?;
+                Ok(GitHubSuggestion {
+                    user_id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                    github_login: row.get::<_, Option<String>>(2)?.unwrap_or_else(|| username.clone()),
+                    username,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list GitHub suggestions")?;
+        Ok(suggestions)
+    }
+
+    /// Stop offering the accounts that are on Fido now; ones that join later
+    /// will still be offered
+    pub fn dismiss(&self, user_id: &Uuid) -> Result<usize> {
+        let conn = self.pool.get()?;
+        let dismissed = conn.execute(
+            "UPDATE github_following SET dismissed = 1
+             WHERE user_id = ? AND github_id IN (SELECT github_id FROM users WHERE github_id IS NOT NULL)",
+            [user_id.to_string()],
+        ).context("Failed to dismiss GitHub suggestions")?;
+        Ok(dismissed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::FriendRepository;
+    use crate::db::Database;
+
+    fn insert_user(db: &Database, username: &str, github_id: i64) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user, github_id, github_login)
+             VALUES (?, ?, ?, 0, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", github_id, username),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_suggests_github_follows_not_yet_followed_here() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice", 1)?;
+        let bob = insert_user(&db, "bob", 2)?;
+        let carol = insert_user(&db, "carol", 3)?;
+        let repo = GitHubFollowingRepository::new(db.pool.clone());
+
+        // 4 isn't on Fido, 1 is alice herself
+        repo.replace(&alice, &[1, 2, 3, 4])?;
+        FriendRepository::new(db.pool.clone()).follow_user(&alice, &carol)?;
+        let names: Vec<_> = repo.suggestions(&alice)?.into_iter().map(|s| s.username).collect();
+        assert_eq!(names, vec!["bob"]);
+        assert!(repo.suggestions(&bob)?.is_empty());
+
+        // Dismissed matches stay dismissed across the next login's refresh,
+        // but someone who joins later is offered
+        repo.dismiss(&alice)?;
+        assert!(repo.suggestions(&alice)?.is_empty());
+        repo.replace(&alice, &[2, 3, 4])?;
+        assert!(repo.suggestions(&alice)?.is_empty());
+        insert_user(&db, "dave", 4)?;
+        let names: Vec<_> = repo.suggestions(&alice)?.into_iter().map(|s| s.username).collect();
+        assert_eq!(names, vec!["dave"]);
+        Ok(())
+    }
+}
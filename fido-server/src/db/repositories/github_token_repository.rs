@@ -0,0 +1,52 @@
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::OptionalExtension;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+
+/// GitHub tokens of users who let Fido create gists for them
+pub struct GitHubTokenRepository {
+    pool: DbPool,
+}
+
+impl GitHubTokenRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Keep `access_token` for the user, replacing any earlier one
+    pub fn save(&self, user_id: &Uuid, access_token: &str) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO github_tokens (user_id, access_token, updated_at) VALUES (?, ?, ?)
+             ON CONFLICT(user_id) DO UPDATE SET access_token = excluded.access_token,
+                                                updated_at = excluded.updated_at",
+            (user_id.to_string(), access_token, Utc::now().to_rfc3339()),
+        )?;
+        Ok(())
+    }
+
+    pub fn get(&self, user_id: &Uuid) -> Result<Option<String>> {
+        let conn = self.pool.get()?;
+        let token = conn
+            .query_row(
+                "SELECT access_token FROM github_tokens WHERE user_id = ?",
+                [user_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(token)
+    }
+
+    /// Forget the user's token, after they logged in without the gist scope
+    /// or GitHub stopped accepting it
+    pub fn delete(&self, user_id: &Uuid) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "DELETE FROM github_tokens WHERE user_id = ?",
+            [user_id.to_string()],
+        )?;
+        Ok(())
+    }
+}
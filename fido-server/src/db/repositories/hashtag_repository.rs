@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use rusqlite::OptionalExtension;
 use uuid::Uuid;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -198,6 +199,72 @@ impl HashtagRepository {
         Ok(())
     }
 
+    /// The user's followed hashtags, newest follow first, each with whether
+    /// they get its weekly changelog
+    pub fn digest_settings(&self, user_id: &Uuid) -> Result<Vec<(String, bool)>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT h.name, NOT EXISTS (SELECT 1 FROM hashtag_digest_optouts o
+                                        WHERE o.user_id = uhf.user_id AND o.hashtag_id = h.id)
+             FROM hashtags h
+             JOIN user_hashtag_follows uhf ON h.id = uhf.hashtag_id
+             WHERE uhf.user_id = ?
+             ORDER BY uhf.followed_at DESC"
+        )?;
+
+        let settings = stmt.query_map([user_id.to_string()], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(settings)
+    }
+
+    /// Turn a followed hashtag's weekly changelog on or off for the user.
+    /// Returns false if they don't follow it.
+    pub fn set_digest_enabled(&self, user_id: &Uuid, hashtag_name: &str, enabled: bool) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let hashtag_id: Option<String> = conn.query_row(
+            "SELECT h.id FROM hashtags h
+             JOIN user_hashtag_follows uhf ON h.id = uhf.hashtag_id
+             WHERE uhf.user_id = ? AND h.name = ?",
+            (user_id.to_string(), hashtag_name.to_lowercase()),
+            |row| row.get(0),
+        ).optional()?;
+        let Some(hashtag_id) = hashtag_id else {
+            return Ok(false);
+        };
+
+        if enabled {
+            conn.execute(
+                "DELETE FROM hashtag_digest_optouts WHERE user_id = ? AND hashtag_id = ?",
+                (user_id.to_string(), hashtag_id),
+            )
+        } else {
+            conn.execute(
+                "INSERT OR IGNORE INTO hashtag_digest_optouts (user_id, hashtag_id) VALUES (?, ?)",
+                (user_id.to_string(), hashtag_id),
+            )
+        }
+        .context("Failed to update hashtag digest setting")?;
+        Ok(true)
+    }
+
+    /// Hashtags someone follows and gets the weekly changelog for
+    pub fn digest_hashtags(&self) -> Result<Vec<String>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT h.name FROM hashtags h
+             JOIN user_hashtag_follows uhf ON h.id = uhf.hashtag_id
+             WHERE NOT EXISTS (SELECT 1 FROM hashtag_digest_optouts o
+                               WHERE o.user_id = uhf.user_id AND o.hashtag_id = h.id)
+             ORDER BY h.name"
+        )?;
+
+        let hashtags = stmt.query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(hashtags)
+    }
+
     /// Get post count for a hashtag
     pub fn get_post_count(&self, hashtag_name: &str) -> Result<i32> {
         let conn = self.pool.get()?;
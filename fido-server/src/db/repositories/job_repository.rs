@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use fido_types::{JobDetails, WorkMode};
+
+use crate::db::DbPool;
+
+pub struct JobRepository {
+    pool: DbPool,
+}
+
+impl JobRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Make an existing post a job listing
+    pub fn create(&self, post_id: &Uuid, job: &JobDetails) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO post_jobs (post_id, title, company, work_mode, url) VALUES (?, ?, ?, ?, ?)",
+            (
+                post_id.to_string(),
+                &job.title,
+                &job.company,
+                job.work_mode.as_str(),
+                &job.url,
+            ),
+        )
+        .context("Failed to create job")?;
+        Ok(())
+    }
+}
+
+/// Job details for each of `post_ids` that is a job listing
+pub(crate) fn jobs_for(conn: &Connection, post_ids: &[Uuid]) -> Result<HashMap<Uuid, JobDetails>> {
+    let mut jobs = HashMap::new();
+    if post_ids.is_empty() {
+        return Ok(jobs);
+    }
+
+    let placeholders = vec!["?"; post_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT post_id, title, company, work_mode, url FROM post_jobs WHERE post_id IN ({})",
+        placeholders
+    ))?;
+    let rows = stmt.query_map(
+        rusqlite::params_from_iter(post_ids.iter().map(|id| id.to_string())),
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        },
+    )?;
+    for row in rows {
+        let (post_id, title, company, work_mode, url) = row?;
+        let (Ok(post_id), Some(work_mode)) = (Uuid::parse_str(&post_id), WorkMode::parse(&work_mode)) else {
+            continue;
+        };
+        jobs.insert(
+            post_id,
+            JobDetails {
+                title,
+                company,
+                work_mode,
+                url,
+            },
+        );
+    }
+    Ok(jobs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{test_post, PostRepository};
+    use crate::db::Database;
+    use fido_types::{PostKind, SortOrder};
+
+    #[test]
+    fn test_job_posts_keep_their_fields_and_filter_by_work_mode() {
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        db.seed_test_data().unwrap();
+        let jobs = JobRepository::new(db.pool.clone());
+        let posts = PostRepository::new(db.pool.clone());
+        let alice = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap();
+
+        let remote = test_post(alice, "Small team, async-first");
+        let onsite = test_post(alice, "Come build terminals with us");
+        for (post, work_mode) in [(&remote, WorkMode::Remote), (&onsite, WorkMode::Onsite)] {
+            posts.create(post).unwrap();
+            let job = JobDetails {
+                title: "Rust Engineer".to_string(),
+                company: "Fido Labs".to_string(),
+                work_mode,
+                url: "https://jobs.example.com/rust".to_string(),
+            };
+            jobs.create(&post.id, &job).unwrap();
+        }
+
+        let ids = |work_mode: Option<WorkMode>| -> Vec<Uuid> {
+            posts
+                .get_job_posts(work_mode, SortOrder::Newest, 10)
+                .unwrap()
+                .iter()
+                .map(|p| p.id)
+                .collect()
+        };
+        assert_eq!(ids(Some(WorkMode::Remote)), vec![remote.id]);
+        assert_eq!(ids(Some(WorkMode::Onsite)), vec![onsite.id]);
+        assert_eq!(ids(None).len(), 2);
+
+        let stored = posts.get_by_id(&remote.id).unwrap().unwrap();
+        let Some(PostKind::Job(details)) = stored.kind else {
+            panic!("post should carry its job details");
+        };
+        assert_eq!(details.company, "Fido Labs");
+        assert_eq!(details.work_mode, WorkMode::Remote);
+    }
+}
@@ -5,11 +5,43 @@ mod vote_repository;
 mod dm_repository;
 mod config_repository;
 mod friend_repository;
+mod emoji_repository;
+mod digest_repository;
+mod draft_repository;
+mod thread_subscription_repository;
+mod crosspost_repository;
+mod github_following_repository;
+mod github_token_repository;
+mod moderation_repository;
+mod bookmark_repository;
+mod mute_repository;
+mod audit_repository;
+mod channel_repository;
+mod event_repository;
+mod review_repository;
+mod job_repository;
 
-pub use user_repository::UserRepository;
-pub use post_repository::PostRepository;
+pub use user_repository::{ProfileCounts, UserRepository};
+pub use post_repository::{DeleteOutcome, PostRepository, ReviewScope};
+#[cfg(test)]
+pub(crate) use post_repository::test_post;
 pub use hashtag_repository::HashtagRepository;
-pub use vote_repository::VoteRepository;
+pub use vote_repository::{VoteAudience, VoteRepository};
 pub use dm_repository::DirectMessageRepository;
 pub use config_repository::ConfigRepository;
-pub use friend_repository::FriendRepository;
+pub use friend_repository::{Connections, FriendRepository};
+pub use emoji_repository::EmojiRepository;
+pub use digest_repository::DigestRepository;
+pub use draft_repository::DraftRepository;
+pub use thread_subscription_repository::ThreadSubscriptionRepository;
+pub use crosspost_repository::CrosspostRepository;
+pub use github_following_repository::GitHubFollowingRepository;
+pub use github_token_repository::GitHubTokenRepository;
+pub use moderation_repository::{ModerationItem, ModerationRepository};
+pub use bookmark_repository::BookmarkRepository;
+pub use mute_repository::MuteRepository;
+pub use channel_repository::ChannelRepository;
+pub use event_repository::EventRepository;
+pub use review_repository::ReviewRepository;
+pub use job_repository::JobRepository;
+pub use audit_repository::{AuditEntry, AuditRepository};
@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::content_filter::{FilterAction, Verdict};
+use crate::db::DbPool;
+
+/// A post waiting in the moderation queue
+#[derive(Debug, Clone, Serialize)]
+pub struct ModerationItem {
+    pub post_id: Uuid,
+    pub author_username: String,
+    pub content: String,
+    /// `flag` or `limit`
+    pub action: String,
+    pub filter: String,
+    pub reason: String,
+    pub flagged_at: DateTime<Utc>,
+}
+
+pub struct ModerationRepository {
+    pool: DbPool,
+}
+
+impl ModerationRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue a post for review, replacing an earlier verdict on it (edits are
+    /// filtered again)
+    pub fn record(&self, post_id: &Uuid, verdict: &Verdict) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO moderation_queue (post_id, action, filter, reason, flagged_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(post_id) DO UPDATE SET action = excluded.action, filter = excluded.filter,
+                 reason = excluded.reason, flagged_at = excluded.flagged_at",
+            (
+                post_id.to_string(),
+                verdict.action.as_str(),
+                &verdict.filter,
+                &verdict.reason,
+                Utc::now().to_rfc3339(),
+            ),
+        )
+        .context("Failed to queue post for moderation")?;
+        Ok(())
+    }
+
+    /// Everything waiting for review, oldest first
+    pub fn queue(&self) -> Result<Vec<ModerationItem>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT m.post_id, u.username, p.content, m.action, m.filter, m.reason, m.flagged_at
+             FROM moderation_queue m
+             JOIN posts p ON p.id = m.post_id
+             JOIN users u ON u.id = p.author_id
+             WHERE p.is_deleted = 0
+             ORDER BY m.flagged_at, m.rowid",
+        )?;
+        let items = stmt
+            .query_map([], |row| {
+                Ok(ModerationItem {
+                    post_id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                    author_username: row.get(1)?,
+                    content: row.get(2)?,
+                    action: row.get(3)?,
+                    filter: row.get(4)?,
+                    reason: row.get(5)?,
+                    flagged_at: row.get::<_, String>(6)?.parse::<DateTime<Utc>>().unwrap(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list moderation queue")?;
+        Ok(items)
+    }
+
+    /// Whether a post is waiting for review
+    pub fn is_queued(&self, post_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let queued = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM moderation_queue WHERE post_id = ?)",
+            [post_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(queued)
+    }
+
+    /// Clear a post from the queue, lifting any limit; false if it wasn't queued
+    pub fn approve(&self, post_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn.execute(
+            "DELETE FROM moderation_queue WHERE post_id = ?",
+            [post_id.to_string()],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Posts currently hidden from everyone but their author
+    pub fn limited_post_ids(&self) -> Result<HashSet<Uuid>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare("SELECT post_id FROM moderation_queue WHERE action = ?")?;
+        let ids = stmt
+            .query_map([FilterAction::Limit.as_str()], |row| row.get::<_, String>(0))?
+            .filter_map(|id| id.ok().and_then(|id| Uuid::parse_str(&id).ok()))
+            .collect();
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn insert_post(db: &Database, author: &Uuid, content: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO posts (id, author_id, content, created_at) VALUES (?, ?, ?, ?)",
+            (id.to_string(), author.to_string(), content, Utc::now().to_rfc3339()),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_limited_posts_stay_hidden_until_approved() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (alice.to_string(), "alice", "2024-01-01T00:00:00Z", 1),
+        )?;
+        let flagged = insert_post(&db, &alice, "mildly spammy")?;
+        let limited = insert_post(&db, &alice, "very spammy")?;
+
+        let repo = ModerationRepository::new(db.pool.clone());
+        let verdict = |action| Verdict {
+            action,
+            filter: "keywords".to_string(),
+            reason: "contains \"spammy\"".to_string(),
+        };
+        repo.record(&flagged, &verdict(FilterAction::Flag))?;
+        repo.record(&limited, &verdict(FilterAction::Limit))?;
+
+        let queued: Vec<_> = repo.queue()?.into_iter().map(|item| item.post_id).collect();
+        assert_eq!(queued, vec![flagged, limited]);
+        assert_eq!(repo.limited_post_ids()?, HashSet::from([limited]));
+
+        assert!(repo.is_queued(&limited)?);
+        assert!(repo.approve(&limited)?);
+        assert!(repo.limited_post_ids()?.is_empty());
+        assert_eq!(repo.queue()?.len(), 1);
+        assert!(!repo.is_queued(&limited)?);
+        assert!(!repo.approve(&limited)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+
+pub struct MuteRepository {
+    pool: DbPool,
+}
+
+impl MuteRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Mute every user in `user_ids` that exists, other than the user
+    /// themselves; returns how many weren't muted already
+    pub fn mute_many(&self, user_id: &Uuid, user_ids: &[Uuid]) -> Result<usize> {
+        let now = Utc::now().to_rfc3339();
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+        let mut muted = 0;
+        for muted_id in user_ids.iter().filter(|id| *id != user_id) {
+            muted += tx.execute(
+                "INSERT OR IGNORE INTO user_mutes (user_id, muted_user_id, created_at)
+                 SELECT ?, id, ? FROM users WHERE id = ?",
+                (user_id.to_string(), &now, muted_id.to_string()),
+            ).context("Failed to mute user")?;
+        }
+        tx.commit()?;
+        Ok(muted)
+    }
+
+    /// Unmute a user; returns whether they were muted
+    pub fn unmute(&self, user_id: &Uuid, muted_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn.execute(
+            "DELETE FROM user_mutes WHERE user_id = ? AND muted_user_id = ?",
+            (user_id.to_string(), muted_id.to_string()),
+        ).context("Failed to unmute user")?;
+        Ok(removed > 0)
+    }
+
+    /// Everyone the user has muted
+    pub fn muted_ids(&self, user_id: &Uuid) -> Result<HashSet<Uuid>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare("SELECT muted_user_id FROM user_mutes WHERE user_id = ?")?;
+        let ids = stmt
+            .query_map([user_id.to_string()], |row| {
+                Ok(Uuid::parse_str(&row.get::<_, String>(0)?).unwrap())
+            })?
+            .collect::<Result<HashSet<_>, _>>()
+            .context("Failed to list muted users")?;
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_mute_many_skips_self_repeats_and_unknown_users() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let repo = MuteRepository::new(db.pool.clone());
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+
+        assert_eq!(repo.mute_many(&alice, &[alice, bob, bob, Uuid::new_v4()])?, 1);
+        assert_eq!(repo.mute_many(&alice, &[bob])?, 0);
+        assert_eq!(repo.muted_ids(&alice)?, HashSet::from([bob]));
+
+        assert!(repo.unmute(&alice, &bob)?);
+        assert!(repo.muted_ids(&alice)?.is_empty());
+        Ok(())
+    }
+}
@@ -1,12 +1,35 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
-use fido_types::{Post, SortOrder};
+use fido_types::{Post, PostKind, ReplyPolicy, SortOrder, WorkMode};
 
+use super::event_repository::events_for;
+use super::job_repository::jobs_for;
+use super::review_repository::reviews_for;
+use super::user_repository::{badges_for, karma_for, statuses_for};
 use crate::db::DbPool;
 
+/// What `PostRepository::delete_post` did with the post
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteOutcome {
+    /// Removed from the database entirely
+    Purged,
+    /// Kept as a "[deleted]" placeholder because it still has replies
+    Tombstoned,
+}
+
+/// Which open review requests `PostRepository::get_open_review_requests` lists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewScope {
+    All,
+    Language(String),
+    /// Languages the user follows as hashtags
+    FollowedBy(Uuid),
+}
+
 pub struct PostRepository {
     pool: DbPool,
 }
@@ -16,10 +39,13 @@ impl PostRepository {
         Self { pool }
     }
 
-    /// Create a new post
+    /// Create a new post. Its anonymous flag and reply policy are stored in
+    /// the same transaction, so it's never briefly listed under its author or
+    /// open to replies it shouldn't take.
     pub fn create(&self, post: &Post) -> Result<()> {
-        let conn = self.pool.get()?;
-        conn.execute(
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+        tx.execute(
             "INSERT INTO posts (id, author_id, content, created_at, upvotes, downvotes, parent_post_id, reply_to_user_id) 
              VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
             (
@@ -33,6 +59,21 @@ impl PostRepository {
                 post.reply_to_user_id.map(|id| id.to_string()),
             ),
         ).context("Failed to create post")?;
+        if post.is_anonymous {
+            tx.execute(
+                "INSERT INTO anonymous_posts (post_id) VALUES (?)",
+                [post.id.to_string()],
+            )
+            .context("Failed to mark post anonymous")?;
+        }
+        if post.reply_policy != ReplyPolicy::Everyone {
+            tx.execute(
+                "INSERT INTO reply_policies (post_id, policy) VALUES (?, ?)",
+                (post.id.to_string(), post.reply_policy.as_str()),
+            )
+            .context("Failed to set reply policy")?;
+        }
+        tx.commit().context("Failed to commit post")?;
         Ok(())
     }
 
@@ -49,11 +90,12 @@ impl PostRepository {
         let query = format!(
             "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
                     (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
-                    p.reply_to_user_id, u2.username as reply_to_username
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
              FROM posts p
              JOIN users u ON p.author_id = u.id
              LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
              WHERE p.parent_post_id IS NULL
+               AND p.id NOT IN (SELECT post_id FROM channel_posts)
              {}
              LIMIT ?",
             order_clause
@@ -61,7 +103,7 @@ impl PostRepository {
 
         let mut stmt = conn.prepare(&query)?;
 
-        let posts = stmt.query_map([limit], |row| {
+        let mut posts = stmt.query_map([limit], |row| {
             let parent_post_id_str: Option<String> = row.get(7)?;
             let reply_to_user_id_str: Option<String> = row.get(9)?;
             Ok(Post {
@@ -78,29 +120,250 @@ impl PostRepository {
                 reply_count: row.get(8)?,
                 reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
                 reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
+        hide_tombstone_authors(&mut posts);
+        attach_post_info(&conn, &mut posts)?;
         Ok(posts)
     }
 
-    /// Get posts by a specific user
-    #[allow(dead_code)]
-    pub fn get_by_user(&self, user_id: &Uuid) -> Result<Vec<Post>> {
+    /// Get a page of a user's top-level posts, newest first, leaving out
+    /// the ones they posted anonymously
+    pub fn get_by_user(&self, user_id: &Uuid, limit: i32, offset: i32) -> Result<Vec<Post>> {
+        self.page_for_user(
+            "WHERE p.author_id = ?1 AND p.parent_post_id IS NULL AND p.is_deleted = 0
+               AND p.id NOT IN (SELECT post_id FROM anonymous_posts)
+             ORDER BY p.created_at DESC",
+            user_id,
+            limit,
+            offset,
+        )
+    }
+
+    /// Get a page of a user's replies, newest first
+    pub fn get_replies_by_user(
+        &self,
+        user_id: &Uuid,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<Post>> {
+        self.page_for_user(
+            "WHERE p.author_id = ?1 AND p.parent_post_id IS NOT NULL AND p.is_deleted = 0
+             ORDER BY p.created_at DESC",
+            user_id,
+            limit,
+            offset,
+        )
+    }
+
+    /// Get a page of the posts a user upvoted, most recently upvoted first
+    pub fn get_upvoted_by_user(
+        &self,
+        user_id: &Uuid,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<Post>> {
+        self.page_for_user(
+            "JOIN votes v ON v.post_id = p.id AND v.user_id = ?1 AND v.direction = 'up'
+             WHERE p.is_deleted = 0
+             ORDER BY v.created_at DESC, p.created_at DESC",
+            user_id,
+            limit,
+            offset,
+        )
+    }
+
+    /// When and what a user wrote, for their profile stats: every live,
+    /// signed post and reply, oldest first
+    pub fn writing_history(&self, user_id: &Uuid) -> Result<Vec<(DateTime<Utc>, String)>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT created_at, content FROM posts
+             WHERE author_id = ? AND is_deleted = 0
+               AND id NOT IN (SELECT post_id FROM anonymous_posts)
+             ORDER BY created_at ASC",
+        )?;
+        let history = stmt
+            .query_map([user_id.to_string()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(created_at, content)| Some((created_at.parse().ok()?, content)))
+            .collect();
+        Ok(history)
+    }
+
+    /// Everything a user has posted or replied that isn't deleted, oldest
+    /// first
+    pub fn get_all_by_author(&self, user_id: &Uuid) -> Result<Vec<Post>> {
+        // A negative LIMIT means no limit in SQLite
+        self.page_for_user(
+            "WHERE p.author_id = ?1 AND p.is_deleted = 0
+             ORDER BY p.created_at ASC",
+            user_id,
+            -1,
+            0,
+        )
+    }
+
+    /// Top-level posts by users who opted in to search engine indexing, as
+    /// `(id, created_at)`, newest first
+    pub fn discoverable_posts(&self, limit: usize) -> Result<Vec<(Uuid, DateTime<Utc>)>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT p.id, p.created_at FROM posts p
+             JOIN user_configs c ON c.user_id = p.author_id AND c.discoverable = 1
+             WHERE p.parent_post_id IS NULL AND p.is_deleted = 0
+               AND p.id NOT IN (SELECT post_id FROM anonymous_posts)
+             ORDER BY p.created_at DESC
+             LIMIT ?",
+        )?;
+        let posts = stmt
+            .query_map([limit as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(id, created_at)| {
+                Some((Uuid::parse_str(&id).ok()?, created_at.parse().ok()?))
+            })
+            .collect();
+        Ok(posts)
+    }
+
+    /// A post by `author_id` with exactly this timestamp and content, which
+    /// is how a repeated import recognizes what it already brought in
+    pub fn find_by_author_at(
+        &self,
+        author_id: &Uuid,
+        created_at: DateTime<Utc>,
+        content: &str,
+    ) -> Result<Option<Uuid>> {
+        let conn = self.pool.get()?;
+        let id: Option<String> = conn
+            .query_row(
+                "SELECT id FROM posts WHERE author_id = ? AND created_at = ? AND content = ? LIMIT 1",
+                (author_id.to_string(), created_at.to_rfc3339(), content),
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// A page of posts picked by `filter` (joins, WHERE and ORDER BY, with
+    /// the user, or the channel, as `?1`)
+    fn page_for_user(
+        &self,
+        filter: &str,
+        user_id: &Uuid,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<Post>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
+                    (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
+             FROM posts p
+             JOIN users u ON p.author_id = u.id
+             LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
+             {}
+             LIMIT ?2 OFFSET ?3",
+            filter
+        ))?;
+
+        let mut posts = stmt.query_map((user_id.to_string(), limit, offset), |row| {
+            let parent_post_id_str: Option<String> = row.get(7)?;
+            let reply_to_user_id_str: Option<String> = row.get(9)?;
+            Ok(Post {
+                id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                author_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+                author_username: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
+                upvotes: row.get(5)?,
+                downvotes: row.get(6)?,
+                hashtags: Vec::new(),
+                user_vote: None, // Will be populated by API layer if user is authenticated
+                parent_post_id: parent_post_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_count: row.get(8)?,
+                reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to get posts by user")?;
+
+        attach_post_info(&conn, &mut posts)?;
+        Ok(posts)
+    }
+
+    /// Top-level posts made in a channel
+    pub fn get_channel_posts(&self, channel_id: &Uuid, sort_order: SortOrder, limit: i32) -> Result<Vec<Post>> {
+        let order_clause = match sort_order {
+            SortOrder::Newest => "ORDER BY p.created_at DESC",
+            SortOrder::Popular => "ORDER BY p.upvotes DESC, p.created_at DESC",
+            SortOrder::Controversial => "ORDER BY ABS(p.upvotes - p.downvotes) ASC, p.created_at DESC",
+        };
+        self.page_for_user(
+            &format!(
+                "JOIN channel_posts cp ON cp.post_id = p.id
+                 WHERE cp.channel_id = ?1 AND p.parent_post_id IS NULL
+                 {}",
+                order_clause
+            ),
+            channel_id,
+            limit,
+            0,
+        )
+    }
+
+    /// Events starting from `from` that `user_id` would care about: their
+    /// own, those of people they follow, those tagged with hashtags they
+    /// follow and those they answered. Soonest first.
+    pub fn upcoming_events(&self, user_id: &Uuid, from: DateTime<Utc>, limit: i32) -> Result<Vec<Post>> {
         let conn = self.pool.get()?;
         let mut stmt = conn.prepare(
             "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
                     (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
-                    p.reply_to_user_id, u2.username as reply_to_username
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
              FROM posts p
+             JOIN post_events e ON e.post_id = p.id
              JOIN users u ON p.author_id = u.id
              LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
-             WHERE p.author_id = ?
-             ORDER BY p.created_at DESC"
+             WHERE p.is_deleted = 0 AND e.starts_at >= ?2
+               AND (p.author_id = ?1
+                    OR p.author_id IN (SELECT following_id FROM follows WHERE follower_id = ?1)
+                    OR p.id IN (SELECT ph.post_id FROM post_hashtags ph
+                                JOIN user_hashtag_follows f ON f.hashtag_id = ph.hashtag_id
+                                WHERE f.user_id = ?1)
+                    OR p.id IN (SELECT post_id FROM event_rsvps WHERE user_id = ?1))
+             ORDER BY e.starts_at ASC
+             LIMIT ?3",
         )?;
 
-        let posts = stmt.query_map([user_id.to_string()], |row| {
+        let mut posts = stmt.query_map((user_id.to_string(), from.to_rfc3339(), limit), |row| {
             let parent_post_id_str: Option<String> = row.get(7)?;
             let reply_to_user_id_str: Option<String> = row.get(9)?;
             Ok(Post {
@@ -117,10 +380,157 @@ impl PostRepository {
                 reply_count: row.get(8)?,
                 reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
                 reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
             })
         })?
-        .collect::<Result<Vec<_>, _>>()?;
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to get upcoming events")?;
 
+        attach_post_info(&conn, &mut posts)?;
+        Ok(posts)
+    }
+
+    /// Review requests nobody has closed yet, in the languages `scope` picks
+    pub fn get_open_review_requests(
+        &self,
+        scope: &ReviewScope,
+        sort_order: SortOrder,
+        limit: i32,
+    ) -> Result<Vec<Post>> {
+        let order_clause = match sort_order {
+            SortOrder::Newest => "ORDER BY p.created_at DESC",
+            SortOrder::Popular => "ORDER BY p.upvotes DESC, p.created_at DESC",
+            SortOrder::Controversial => "ORDER BY ABS(p.upvotes - p.downvotes) ASC, p.created_at DESC",
+        };
+        let (language, follower) = match scope {
+            ReviewScope::All => (String::new(), String::new()),
+            ReviewScope::Language(language) => (language.clone(), String::new()),
+            ReviewScope::FollowedBy(user_id) => (String::new(), user_id.to_string()),
+        };
+
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
+                    (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
+             FROM posts p
+             JOIN review_requests r ON r.post_id = p.id
+             JOIN users u ON p.author_id = u.id
+             LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
+             WHERE p.is_deleted = 0 AND r.closed_at IS NULL
+               AND (?1 = '' OR r.language = ?1)
+               AND (?2 = '' OR r.language IN (SELECT h.name FROM hashtags h
+                                              JOIN user_hashtag_follows f ON f.hashtag_id = h.id
+                                              WHERE f.user_id = ?2))
+             {}
+             LIMIT ?3",
+            order_clause
+        ))?;
+
+        let mut posts = stmt.query_map((language, follower, limit), |row| {
+            let parent_post_id_str: Option<String> = row.get(7)?;
+            let reply_to_user_id_str: Option<String> = row.get(9)?;
+            Ok(Post {
+                id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                author_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+                author_username: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
+                upvotes: row.get(5)?,
+                downvotes: row.get(6)?,
+                hashtags: Vec::new(),
+                user_vote: None, // Will be populated by API layer if user is authenticated
+                parent_post_id: parent_post_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_count: row.get(8)?,
+                reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to get review requests")?;
+
+        attach_post_info(&conn, &mut posts)?;
+        Ok(posts)
+    }
+
+    /// Job posts, optionally only the remote or only the onsite ones
+    pub fn get_job_posts(
+        &self,
+        work_mode: Option<WorkMode>,
+        sort_order: SortOrder,
+        limit: i32,
+    ) -> Result<Vec<Post>> {
+        let order_clause = match sort_order {
+            SortOrder::Newest => "ORDER BY p.created_at DESC",
+            SortOrder::Popular => "ORDER BY p.upvotes DESC, p.created_at DESC",
+            SortOrder::Controversial => "ORDER BY ABS(p.upvotes - p.downvotes) ASC, p.created_at DESC",
+        };
+        let work_mode = work_mode.map(|mode| mode.as_str()).unwrap_or("");
+
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
+                    (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
+             FROM posts p
+             JOIN post_jobs j ON j.post_id = p.id
+             JOIN users u ON p.author_id = u.id
+             LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
+             WHERE p.is_deleted = 0 AND (?1 = '' OR j.work_mode = ?1)
+             {}
+             LIMIT ?2",
+            order_clause
+        ))?;
+
+        let mut posts = stmt.query_map((work_mode, limit), |row| {
+            let parent_post_id_str: Option<String> = row.get(7)?;
+            let reply_to_user_id_str: Option<String> = row.get(9)?;
+            Ok(Post {
+                id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                author_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+                author_username: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
+                upvotes: row.get(5)?,
+                downvotes: row.get(6)?,
+                hashtags: Vec::new(),
+                user_vote: None, // Will be populated by API layer if user is authenticated
+                parent_post_id: parent_post_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_count: row.get(8)?,
+                reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to get job posts")?;
+
+        attach_post_info(&conn, &mut posts)?;
         Ok(posts)
     }
 
@@ -130,14 +540,14 @@ impl PostRepository {
         let mut stmt = conn.prepare(
             "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
                     (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
-                    p.reply_to_user_id, u2.username as reply_to_username
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
              FROM posts p
              JOIN users u ON p.author_id = u.id
              LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
              WHERE p.id = ?"
         )?;
 
-        let post = stmt.query_row([post_id.to_string()], |row| {
+        let mut post = stmt.query_row([post_id.to_string()], |row| {
             let parent_post_id_str: Option<String> = row.get(7)?;
             let reply_to_user_id_str: Option<String> = row.get(9)?;
             Ok(Post {
@@ -154,39 +564,449 @@ impl PostRepository {
                 reply_count: row.get(8)?,
                 reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
                 reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
             })
         }).optional()?;
 
+        if let Some(post) = post.as_mut() {
+            hide_tombstone_authors(std::slice::from_mut(post));
+            attach_post_info(&conn, std::slice::from_mut(post))?;
+        }
         Ok(post)
     }
 
-    /// Update vote counts for a post
-    pub fn update_vote_counts(&self, post_id: &Uuid) -> Result<()> {
+    /// Recompute every drifted counter from its source rows, returning how many posts changed
+    pub fn repair_counters(&self) -> Result<usize> {
+        let conn = self.pool.get()?;
+        let repaired = conn.execute(
+            "UPDATE posts
+             SET upvotes = (SELECT COUNT(*) FROM votes WHERE post_id = posts.id AND direction = 'up'),
+                 downvotes = (SELECT COUNT(*) FROM votes WHERE post_id = posts.id AND direction = 'down'),
+                 reply_count = (SELECT COUNT(*) FROM posts r WHERE r.parent_post_id = posts.id)
+             WHERE upvotes != (SELECT COUNT(*) FROM votes WHERE post_id = posts.id AND direction = 'up')
+                OR downvotes != (SELECT COUNT(*) FROM votes WHERE post_id = posts.id AND direction = 'down')
+                OR reply_count != (SELECT COUNT(*) FROM posts r WHERE r.parent_post_id = posts.id)",
+            [],
+        ).context("Failed to repair post counters")?;
+
+        Ok(repaired)
+    }
+
+    /// Delete a post without breaking the thread around it.
+    ///
+    /// A post that still has replies is replaced by a tombstone (content,
+    /// votes and hashtags removed, row kept) so the reply tree stays intact.
+    /// A post without replies is removed outright, along with any tombstoned
+    /// ancestors that are left with no remaining replies.
+    pub fn delete_post(&self, post_id: &Uuid) -> Result<DeleteOutcome> {
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+
+        let reply_count: i32 = tx.query_row(
+            "SELECT COUNT(*) FROM posts WHERE parent_post_id = ?",
+            [post_id.to_string()],
+            |row| row.get(0),
+        )?;
+
+        let outcome = if reply_count > 0 {
+            tx.execute(
+                "UPDATE posts SET is_deleted = 1, content = '' WHERE id = ?",
+                [post_id.to_string()],
+            ).context("Failed to tombstone post")?;
+            tx.execute("DELETE FROM votes WHERE post_id = ?", [post_id.to_string()])?;
+            tx.execute("DELETE FROM post_hashtags WHERE post_id = ?", [post_id.to_string()])?;
+            DeleteOutcome::Tombstoned
+        } else {
+            let mut parent_id: Option<String> = tx.query_row(
+                "SELECT parent_post_id FROM posts WHERE id = ?",
+                [post_id.to_string()],
+                |row| row.get(0),
+            )?;
+            tx.execute("DELETE FROM posts WHERE id = ?", [post_id.to_string()])
+                .context("Failed to delete post")?;
+
+            // Purge tombstones that no longer hold any replies together
+            while let Some(id) = parent_id {
+                let ancestor: Option<(bool, Option<String>, i32)> = tx
+                    .query_row(
+                        "SELECT is_deleted, parent_post_id,
+                                (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id)
+                         FROM posts p WHERE id = ?",
+                        [&id],
+                        |row| Ok((row.get::<_, i32>(0)? != 0, row.get(1)?, row.get(2)?)),
+                    )
+                    .optional()?;
+                match ancestor {
+                    Some((true, grandparent_id, 0)) => {
+                        tx.execute("DELETE FROM posts WHERE id = ?", [&id])?;
+                        parent_id = grandparent_id;
+                    }
+                    _ => break,
+                }
+            }
+            DeleteOutcome::Purged
+        };
+
+        tx.commit().context("Failed to commit post deletion")?;
+        Ok(outcome)
+    }
+
+    /// Anonymous posts (deleted or not) a user has created since `since`
+    pub fn count_anonymous_since(&self, user_id: &Uuid, since: DateTime<Utc>) -> Result<i32> {
+        let conn = self.pool.get()?;
+        let count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM posts p JOIN anonymous_posts a ON a.post_id = p.id
+             WHERE p.author_id = ? AND p.created_at >= ?",
+            (user_id.to_string(), since.to_rfc3339()),
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Mark `reply_id` as the accepted answer in the thread under `root_id`,
+    /// replacing any earlier one
+    pub fn accept_reply(&self, root_id: &Uuid, reply_id: &Uuid) -> Result<()> {
         let conn = self.pool.get()?;
-        
-        // Recalculate vote counts from votes table
         conn.execute(
-            "UPDATE posts 
-             SET upvotes = (SELECT COUNT(*) FROM votes WHERE post_id = ? AND direction = 'up'),
-                 downvotes = (SELECT COUNT(*) FROM votes WHERE post_id = ? AND direction = 'down')
-             WHERE id = ?",
-            (post_id.to_string(), post_id.to_string(), post_id.to_string()),
-        ).context("Failed to update vote counts")?;
-        
+            "INSERT OR REPLACE INTO accepted_replies (post_id, reply_id, accepted_at) VALUES (?, ?, ?)",
+            (root_id.to_string(), reply_id.to_string(), Utc::now().to_rfc3339()),
+        )
+        .context("Failed to accept reply")?;
         Ok(())
     }
 
-    /// Get post count for a user
-    pub fn get_post_count(&self, user_id: &Uuid) -> Result<i32> {
+    /// Take back the accepted answer in the thread under `root_id`,
+    /// returning whether there was one
+    pub fn clear_accepted_reply(&self, root_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn
+            .execute(
+                "DELETE FROM accepted_replies WHERE post_id = ?",
+                [root_id.to_string()],
+            )
+            .context("Failed to clear accepted reply")?;
+        Ok(removed > 0)
+    }
+
+    /// The top-level post a post or reply belongs to (the post itself if
+    /// it's top-level)
+    pub fn thread_root(&self, post_id: &Uuid) -> Result<Option<Post>> {
+        let root_id: Option<String> = {
+            let conn = self.pool.get()?;
+            conn.query_row(
+                "WITH RECURSIVE ancestors(id, parent_post_id) AS (
+                    SELECT id, parent_post_id FROM posts WHERE id = ?
+                    UNION ALL
+                    SELECT p.id, p.parent_post_id FROM posts p
+                    JOIN ancestors a ON p.id = a.parent_post_id
+                )
+                SELECT id FROM ancestors WHERE parent_post_id IS NULL",
+                [post_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?
+        };
+        match root_id.and_then(|id| Uuid::parse_str(&id).ok()) {
+            Some(root_id) => self.get_by_id(&root_id),
+            None => Ok(None),
+        }
+    }
+
+    /// Posts and replies (deleted or not) a user has created since `since`
+    pub fn count_created_since(&self, user_id: &Uuid, since: DateTime<Utc>) -> Result<i32> {
         let conn = self.pool.get()?;
         let count: i32 = conn.query_row(
-            "SELECT COUNT(*) FROM posts WHERE author_id = ?",
-            [user_id.to_string()],
+            "SELECT COUNT(*) FROM posts WHERE author_id = ? AND created_at >= ?",
+            (user_id.to_string(), since.to_rfc3339()),
             |row| row.get(0),
         )?;
         Ok(count)
     }
 
+    /// Best-voted top-level posts created in `[since, until)` by users `user_id`
+    /// follows or carrying a hashtag they follow, excluding their own posts
+    pub fn top_followed_between(
+        &self,
+        user_id: &Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<Post>> {
+        self.top_between(user_id, since, until, limit, true)
+    }
+
+    /// Like `top_followed_between`, but only posts carrying a followed hashtag
+    pub fn top_followed_hashtags_between(
+        &self,
+        user_id: &Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<Post>> {
+        self.top_between(user_id, since, until, limit, false)
+    }
+
+    /// Best-voted top-level posts from between `since` and `until`, across
+    /// the instance; channel and anonymous posts aren't ranked
+    pub fn top_posts_between(&self, since: DateTime<Utc>, until: DateTime<Utc>, limit: i32) -> Result<Vec<Post>> {
+        self.top_ranked_between(None, since, until, limit)
+    }
+
+    /// Best-voted top-level posts carrying `hashtag` from between `since`
+    /// and `until`, for its weekly changelog. Unlike the leaderboard, posts
+    /// nobody voted on count, so a quiet hashtag still has a week to show.
+    pub fn top_with_hashtag_between(
+        &self,
+        hashtag: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<Post>> {
+        self.top_ranked_between(Some(hashtag), since, until, limit)
+    }
+
+    /// How many top-level posts carrying `hashtag` were made between `since`
+    /// and `until`, and by how many people
+    pub fn hashtag_activity_between(
+        &self,
+        hashtag: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<(i64, i64)> {
+        let conn = self.pool.get()?;
+        let activity = conn.query_row(
+            "SELECT COUNT(*), COUNT(DISTINCT p.author_id)
+             FROM posts p
+             JOIN post_hashtags ph ON ph.post_id = p.id
+             JOIN hashtags h ON h.id = ph.hashtag_id
+             WHERE h.name = ?1 AND p.parent_post_id IS NULL AND p.is_deleted = 0
+               AND p.created_at >= ?2 AND p.created_at < ?3
+               AND p.id NOT IN (SELECT post_id FROM channel_posts)",
+            (hashtag, since.to_rfc3339(), until.to_rfc3339()),
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(activity)
+    }
+
+    fn top_ranked_between(
+        &self,
+        hashtag: Option<&str>,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<Post>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
+                    (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
+             FROM posts p
+             JOIN users u ON p.author_id = u.id
+             LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
+             WHERE p.parent_post_id IS NULL AND p.is_deleted = 0
+               AND p.created_at >= ?1 AND p.created_at < ?2
+               AND (?4 IS NOT NULL OR p.upvotes - p.downvotes > 0)
+               AND (?4 IS NULL OR EXISTS (SELECT 1 FROM post_hashtags ph
+                                          JOIN hashtags h ON h.id = ph.hashtag_id
+                                          WHERE ph.post_id = p.id AND h.name = ?4))
+               AND p.id NOT IN (SELECT post_id FROM channel_posts)
+               AND p.id NOT IN (SELECT post_id FROM anonymous_posts)
+             ORDER BY p.upvotes - p.downvotes DESC, p.created_at DESC
+             LIMIT ?3",
+        )?;
+
+        let mut posts = stmt
+            .query_map((since.to_rfc3339(), until.to_rfc3339(), limit, hashtag), |row| {
+                let parent_post_id_str: Option<String> = row.get(7)?;
+                let reply_to_user_id_str: Option<String> = row.get(9)?;
+                Ok(Post {
+                    id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                    author_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+                    author_username: row.get(2)?,
+                    content: row.get(3)?,
+                    created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
+                    upvotes: row.get(5)?,
+                    downvotes: row.get(6)?,
+                    hashtags: Vec::new(),
+                    user_vote: None,
+                    parent_post_id: parent_post_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                    reply_count: row.get(8)?,
+                    reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                    reply_to_username: row.get(10)?,
+                    is_deleted: row.get::<_, i32>(11)? != 0,
+                    author_badges: Vec::new(),
+                    author_status: None,
+                    kind: None,
+                    is_anonymous: false,
+                    reply_policy: ReplyPolicy::Everyone,
+                    can_reply: true,
+                    is_accepted: false,
+                    author_karma: 0,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to get top posts")?;
+
+        attach_post_info(&conn, &mut posts)?;
+        Ok(posts)
+    }
+
+    fn top_between(
+        &self,
+        user_id: &Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: i32,
+        include_followed_users: bool,
+    ) -> Result<Vec<Post>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
+                    (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
+             FROM posts p
+             JOIN users u ON p.author_id = u.id
+             LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
+             WHERE p.parent_post_id IS NULL AND p.is_deleted = 0
+               AND p.author_id != ?1
+               AND p.created_at >= ?2 AND p.created_at < ?3
+               AND ((?5 AND p.author_id IN (SELECT following_id FROM follows WHERE follower_id = ?1)
+                        AND p.id NOT IN (SELECT post_id FROM anonymous_posts))
+                    OR EXISTS (SELECT 1 FROM post_hashtags ph
+                               JOIN user_hashtag_follows hf ON hf.hashtag_id = ph.hashtag_id
+                               WHERE ph.post_id = p.id AND hf.user_id = ?1))
+             ORDER BY p.upvotes - p.downvotes DESC, p.created_at DESC
+             LIMIT ?4",
+        )?;
+
+        let mut posts = stmt.query_map(
+            (user_id.to_string(), since.to_rfc3339(), until.to_rfc3339(), limit, include_followed_users),
+            |row| {
+                let parent_post_id_str: Option<String> = row.get(7)?;
+                let reply_to_user_id_str: Option<String> = row.get(9)?;
+                Ok(Post {
+                    id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                    author_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+                    author_username: row.get(2)?,
+                    content: row.get(3)?,
+                    created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
+                    upvotes: row.get(5)?,
+                    downvotes: row.get(6)?,
+                    hashtags: Vec::new(),
+                    user_vote: None,
+                    parent_post_id: parent_post_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                    reply_count: row.get(8)?,
+                    reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                    reply_to_username: row.get(10)?,
+                    is_deleted: row.get::<_, i32>(11)? != 0,
+                    author_badges: Vec::new(),
+                    author_status: None,
+                    kind: None,
+                    is_anonymous: false,
+                    reply_policy: ReplyPolicy::Everyone,
+                    can_reply: true,
+                    is_accepted: false,
+                    author_karma: 0,
+                })
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        attach_post_info(&conn, &mut posts)?;
+        Ok(posts)
+    }
+
+    /// Top-level posts related to `post_id`: those sharing the most hashtags
+    /// with it first, then the same author's, newest first within each
+    pub fn get_related(&self, post_id: &Uuid, limit: i32) -> Result<Vec<Post>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT * FROM (
+                SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
+                       (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
+                       p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted,
+                       (SELECT COUNT(*) FROM post_hashtags mine
+                        JOIN post_hashtags theirs ON theirs.hashtag_id = mine.hashtag_id
+                        WHERE mine.post_id = ?1 AND theirs.post_id = p.id) AS shared_hashtags,
+                       p.author_id = (SELECT author_id FROM posts WHERE id = ?1)
+                           AND NOT EXISTS (SELECT 1 FROM anonymous_posts
+                                           WHERE post_id IN (p.id, ?1)) AS same_author
+                FROM posts p
+                JOIN users u ON p.author_id = u.id
+                LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
+                WHERE p.parent_post_id IS NULL AND p.is_deleted = 0 AND p.id != ?1
+             )
+             WHERE shared_hashtags > 0 OR same_author
+             ORDER BY shared_hashtags DESC, same_author DESC, created_at DESC
+             LIMIT ?2",
+        )?;
+
+        let mut posts = stmt.query_map((post_id.to_string(), limit), |row| {
+            let parent_post_id_str: Option<String> = row.get(7)?;
+            let reply_to_user_id_str: Option<String> = row.get(9)?;
+            Ok(Post {
+                id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                author_id: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap(),
+                author_username: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get::<_, String>(4)?.parse::<DateTime<Utc>>().unwrap(),
+                upvotes: row.get(5)?,
+                downvotes: row.get(6)?,
+                hashtags: Vec::new(),
+                user_vote: None,
+                parent_post_id: parent_post_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_count: row.get(8)?,
+                reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+                reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        attach_post_info(&conn, &mut posts)?;
+        Ok(posts)
+    }
+
+    /// Delete every post created before `cutoff`, returning how many were
+    /// removed or tombstoned. Goes through `delete_post`, newest first, so
+    /// old replies go before their parents and a thread with recent replies
+    /// keeps its structure.
+    pub fn prune_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let ids: Vec<String> = {
+            let conn = self.pool.get()?;
+            let mut stmt = conn.prepare(
+                "SELECT id FROM posts WHERE created_at < ? AND is_deleted = 0
+                 ORDER BY created_at DESC",
+            )?;
+            let rows = stmt.query_map([cutoff.to_rfc3339()], |row| row.get(0))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let mut pruned = 0;
+        for id in ids {
+            let post_id = Uuid::parse_str(&id).context("Invalid post ID in database")?;
+            self.delete_post(&post_id)?;
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+
     /// Extract hashtags from post content using regex
     #[allow(dead_code)]
     pub fn extract_hashtags(content: &str) -> Vec<String> {
@@ -214,7 +1034,7 @@ impl PostRepository {
             "WITH RECURSIVE reply_tree AS (
                 -- Base case: direct replies to the parent post
                 SELECT p.id, p.author_id, p.content, p.created_at, p.upvotes, p.downvotes, 
-                       p.parent_post_id, p.reply_to_user_id, 0 as depth
+                       p.parent_post_id, p.reply_to_user_id, p.is_deleted, 0 as depth
                 FROM posts p
                 WHERE p.parent_post_id = ?
                 
@@ -222,21 +1042,21 @@ impl PostRepository {
                 
                 -- Recursive case: replies to replies
                 SELECT p.id, p.author_id, p.content, p.created_at, p.upvotes, p.downvotes,
-                       p.parent_post_id, p.reply_to_user_id, rt.depth + 1
+                       p.parent_post_id, p.reply_to_user_id, p.is_deleted, rt.depth + 1
                 FROM posts p
                 INNER JOIN reply_tree rt ON p.parent_post_id = rt.id
             )
             SELECT rt.id, rt.author_id, u.username, rt.content, rt.created_at, 
                    rt.upvotes, rt.downvotes, rt.parent_post_id,
                    (SELECT COUNT(*) FROM posts WHERE parent_post_id = rt.id) as reply_count,
-                   rt.reply_to_user_id, u2.username as reply_to_username, rt.depth
+                   rt.reply_to_user_id, u2.username as reply_to_username, rt.is_deleted, rt.depth
             FROM reply_tree rt
             JOIN users u ON rt.author_id = u.id
             LEFT JOIN users u2 ON rt.reply_to_user_id = u2.id
             ORDER BY rt.depth ASC, rt.created_at ASC"
         )?;
 
-        let replies = stmt.query_map([parent_post_id.to_string()], |row| {
+        let mut replies = stmt.query_map([parent_post_id.to_string()], |row| {
             let parent_post_id_str: Option<String> = row.get(7)?;
             let reply_to_user_id_str: Option<String> = row.get(9)?;
             Ok(Post {
@@ -253,10 +1073,21 @@ impl PostRepository {
                 reply_count: row.get(8)?,
                 reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
                 reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
+        hide_tombstone_authors(&mut replies);
+        attach_post_info(&conn, &mut replies)?;
         Ok(replies)
     }
 
@@ -285,7 +1116,7 @@ impl PostRepository {
         let query = format!(
             "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
                     (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
-                    p.reply_to_user_id, u2.username as reply_to_username
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
              FROM posts p
              JOIN users u ON p.author_id = u.id
              LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
@@ -299,7 +1130,7 @@ impl PostRepository {
 
         let mut stmt = conn.prepare(&query)?;
 
-        let posts = stmt.query_map([hashtag_name, &limit.to_string()], |row| {
+        let mut posts = stmt.query_map([hashtag_name, &limit.to_string()], |row| {
             let parent_post_id_str: Option<String> = row.get(7)?;
             let reply_to_user_id_str: Option<String> = row.get(9)?;
             Ok(Post {
@@ -316,10 +1147,20 @@ impl PostRepository {
                 reply_count: row.get(8)?,
                 reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
                 reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
+        attach_post_info(&conn, &mut posts)?;
         Ok(posts)
     }
 
@@ -336,11 +1177,12 @@ impl PostRepository {
         let query = format!(
             "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
                     (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
-                    p.reply_to_user_id, u2.username as reply_to_username
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
              FROM posts p
              JOIN users u ON p.author_id = u.id
              LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
-             WHERE LOWER(u.username) = LOWER(?) AND p.parent_post_id IS NULL
+             WHERE LOWER(u.username) = LOWER(?) AND p.parent_post_id IS NULL AND p.is_deleted = 0
+               AND p.id NOT IN (SELECT post_id FROM anonymous_posts)
              {}
              LIMIT ?",
             order_clause
@@ -348,7 +1190,7 @@ impl PostRepository {
 
         let mut stmt = conn.prepare(&query)?;
 
-        let posts = stmt.query_map([username, &limit.to_string()], |row| {
+        let mut posts = stmt.query_map([username, &limit.to_string()], |row| {
             let parent_post_id_str: Option<String> = row.get(7)?;
             let reply_to_user_id_str: Option<String> = row.get(9)?;
             Ok(Post {
@@ -365,10 +1207,20 @@ impl PostRepository {
                 reply_count: row.get(8)?,
                 reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
                 reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
+        attach_post_info(&conn, &mut posts)?;
         Ok(posts)
     }
 
@@ -385,13 +1237,14 @@ impl PostRepository {
         let query = format!(
             "SELECT p.id, p.author_id, u.username, p.content, p.created_at, p.upvotes, p.downvotes, p.parent_post_id,
                     (SELECT COUNT(*) FROM posts WHERE parent_post_id = p.id) as reply_count,
-                    p.reply_to_user_id, u2.username as reply_to_username
+                    p.reply_to_user_id, u2.username as reply_to_username, p.is_deleted
              FROM posts p
              JOIN users u ON p.author_id = u.id
              LEFT JOIN users u2 ON p.reply_to_user_id = u2.id
              JOIN post_hashtags ph ON p.id = ph.post_id
              JOIN hashtags h ON ph.hashtag_id = h.id
              WHERE LOWER(h.name) = LOWER(?) AND LOWER(u.username) = LOWER(?) AND p.parent_post_id IS NULL
+               AND p.id NOT IN (SELECT post_id FROM anonymous_posts)
              {}
              LIMIT ?",
             order_clause
@@ -399,7 +1252,7 @@ impl PostRepository {
 
         let mut stmt = conn.prepare(&query)?;
 
-        let posts = stmt.query_map([hashtag_name, username, &limit.to_string()], |row| {
+        let mut posts = stmt.query_map([hashtag_name, username, &limit.to_string()], |row| {
             let parent_post_id_str: Option<String> = row.get(7)?;
             let reply_to_user_id_str: Option<String> = row.get(9)?;
             Ok(Post {
@@ -416,10 +1269,541 @@ impl PostRepository {
                 reply_count: row.get(8)?,
                 reply_to_user_id: reply_to_user_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
                 reply_to_username: row.get(10)?,
+                is_deleted: row.get::<_, i32>(11)? != 0,
+                author_badges: Vec::new(),
+                author_status: None,
+                kind: None,
+                is_anonymous: false,
+                reply_policy: ReplyPolicy::Everyone,
+                can_reply: true,
+                is_accepted: false,
+                author_karma: 0,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
+        attach_post_info(&conn, &mut posts)?;
         Ok(posts)
     }
 }
+
+/// A tombstone only holds its thread together, so it no longer says who wrote it
+fn hide_tombstone_authors(posts: &mut [Post]) {
+    for post in posts.iter_mut().filter(|post| post.is_deleted) {
+        post.author_id = Uuid::nil();
+        post.author_username = String::new();
+    }
+}
+
+/// Fill in `author_badges`, `author_status` and `author_karma` with one
+/// lookup each for all the authors, `kind` for the posts that are events,
+/// review requests or jobs, `is_anonymous` and `is_accepted`
+fn attach_post_info(conn: &Connection, posts: &mut [Post]) -> Result<()> {
+    let mut author_ids: Vec<Uuid> = posts.iter().map(|post| post.author_id).collect();
+    author_ids.sort();
+    author_ids.dedup();
+    let badges = badges_for(conn, &author_ids)?;
+    let statuses = statuses_for(conn, &author_ids)?;
+    let karma = karma_for(conn, &author_ids)?;
+    let post_ids: Vec<Uuid> = posts.iter().map(|post| post.id).collect();
+    let mut events = events_for(conn, &post_ids)?;
+    let mut reviews = reviews_for(conn, &post_ids)?;
+    let mut jobs = jobs_for(conn, &post_ids)?;
+    let anonymous = anonymous_among(conn, &post_ids)?;
+    let reply_policies = reply_policies_for(conn, &post_ids)?;
+    let accepted = accepted_among(conn, &post_ids)?;
+    for post in posts {
+        post.author_badges = badges.get(&post.author_id).cloned().unwrap_or_default();
+        post.author_status = statuses.get(&post.author_id).cloned();
+        post.author_karma = karma.get(&post.author_id).copied().unwrap_or(0);
+        post.kind = events
+            .remove(&post.id)
+            .map(PostKind::Event)
+            .or_else(|| reviews.remove(&post.id).map(PostKind::ReviewRequest))
+            .or_else(|| jobs.remove(&post.id).map(PostKind::Job));
+        post.is_anonymous = anonymous.contains(&post.id);
+        post.reply_policy = reply_policies.get(&post.id).copied().unwrap_or_default();
+        post.is_accepted = accepted.contains(&post.id);
+    }
+    Ok(())
+}
+
+/// Reply policies of those of `post_ids` that limit replies
+fn reply_policies_for(conn: &Connection, post_ids: &[Uuid]) -> Result<HashMap<Uuid, ReplyPolicy>> {
+    if post_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let placeholders = vec!["?"; post_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT post_id, policy FROM reply_policies WHERE post_id IN ({})",
+        placeholders
+    ))?;
+    let rows = stmt
+        .query_map(
+            rusqlite::params_from_iter(post_ids.iter().map(|id| id.to_string())),
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows
+        .iter()
+        .filter_map(|(id, policy)| Some((Uuid::parse_str(id).ok()?, ReplyPolicy::parse(policy)?)))
+        .collect())
+}
+
+/// Which of `post_ids` were posted anonymously
+fn anonymous_among(conn: &Connection, post_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+    if post_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let placeholders = vec!["?"; post_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT post_id FROM anonymous_posts WHERE post_id IN ({})",
+        placeholders
+    ))?;
+    let ids = stmt
+        .query_map(
+            rusqlite::params_from_iter(post_ids.iter().map(|id| id.to_string())),
+            |row| row.get::<_, String>(0),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids.iter().filter_map(|id| Uuid::parse_str(id).ok()).collect())
+}
+
+/// Which of `post_ids` are accepted replies
+fn accepted_among(conn: &Connection, post_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+    if post_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let placeholders = vec!["?"; post_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT reply_id FROM accepted_replies WHERE reply_id IN ({})",
+        placeholders
+    ))?;
+    let ids = stmt
+        .query_map(
+            rusqlite::params_from_iter(post_ids.iter().map(|id| id.to_string())),
+            |row| row.get::<_, String>(0),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids.iter().filter_map(|id| Uuid::parse_str(id).ok()).collect())
+}
+
+/// A plain top-level post by `author_id`, for tests to override fields on
+#[cfg(test)]
+pub(crate) fn test_post(author_id: Uuid, content: &str) -> Post {
+    Post {
+        id: Uuid::new_v4(),
+        author_id,
+        author_username: String::new(),
+        content: content.to_string(),
+        created_at: Utc::now(),
+        upvotes: 0,
+        downvotes: 0,
+        hashtags: Vec::new(),
+        user_vote: None,
+        parent_post_id: None,
+        reply_count: 0,
+        reply_to_user_id: None,
+        reply_to_username: None,
+        is_deleted: false,
+        author_badges: Vec::new(),
+        author_status: None,
+        kind: None,
+        is_anonymous: false,
+        reply_policy: ReplyPolicy::Everyone,
+        can_reply: true,
+        is_accepted: false,
+        author_karma: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn make_post(author_id: Uuid, content: &str, minutes_ago: i64, parent: Option<Uuid>) -> Post {
+        Post {
+            created_at: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            parent_post_id: parent,
+            ..test_post(author_id, content)
+        }
+    }
+
+    #[test]
+    fn test_get_by_user_paginates_top_level_posts() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "pager", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = PostRepository::new(db.pool.clone());
+        let oldest = make_post(user_id, "oldest", 30, None);
+        repo.create(&oldest)?;
+        repo.create(&make_post(user_id, "middle", 20, None))?;
+        repo.create(&make_post(user_id, "newest", 10, None))?;
+        // Replies don't belong on the profile list
+        repo.create(&make_post(user_id, "a reply", 5, Some(oldest.id)))?;
+
+        let first_page = repo.get_by_user(&user_id, 2, 0)?;
+        let contents: Vec<&str> = first_page.iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(contents, vec!["newest", "middle"]);
+
+        let second_page = repo.get_by_user(&user_id, 2, 2)?;
+        assert_eq!(second_page.len(), 1);
+        assert_eq!(second_page[0].content, "oldest");
+        assert_eq!(second_page[0].reply_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_anonymous_posts_stay_off_the_authors_lists() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "shy", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = PostRepository::new(db.pool.clone());
+        let signed = make_post(user_id, "signed", 20, None);
+        let anonymous = Post {
+            is_anonymous: true,
+            ..make_post(user_id, "anonymous", 10, None)
+        };
+        repo.create(&signed)?;
+        repo.create(&anonymous)?;
+
+        // The author is kept for moderation, and the post is flagged
+        let stored = repo.get_by_id(&anonymous.id)?.unwrap();
+        assert!(stored.is_anonymous);
+        assert_eq!(stored.author_id, user_id);
+        assert!(!repo.get_by_id(&signed.id)?.unwrap().is_anonymous);
+
+        // but nothing listed by author gives it away
+        let ids = |posts: Vec<Post>| posts.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(ids(repo.get_by_user(&user_id, 10, 0)?), vec![signed.id]);
+        assert_eq!(
+            ids(repo.get_posts_by_username("shy", SortOrder::Newest, 10)?),
+            vec![signed.id]
+        );
+        assert!(repo.get_related(&anonymous.id, 10)?.is_empty());
+        assert!(repo.get_posts(SortOrder::Newest, 10)?.iter().any(|p| p.id == anonymous.id));
+
+        let since = Utc::now() - chrono::Duration::days(1);
+        assert_eq!(repo.count_anonymous_since(&user_id, since)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reply_policy_is_kept_on_the_thread_root() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "quiet", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = PostRepository::new(db.pool.clone());
+        let root = Post {
+            reply_policy: ReplyPolicy::Following,
+            ..make_post(user_id, "root", 20, None)
+        };
+        let reply = make_post(user_id, "reply", 10, Some(root.id));
+        let nested = make_post(user_id, "nested", 5, Some(reply.id));
+        for post in [&root, &reply, &nested] {
+            repo.create(post)?;
+        }
+
+        assert_eq!(repo.get_by_id(&root.id)?.unwrap().reply_policy, ReplyPolicy::Following);
+        assert_eq!(repo.get_by_id(&reply.id)?.unwrap().reply_policy, ReplyPolicy::Everyone);
+
+        // Replies at any depth answer to the root's policy
+        let found = repo.thread_root(&nested.id)?.unwrap();
+        assert_eq!(found.id, root.id);
+        assert_eq!(found.reply_policy, ReplyPolicy::Following);
+        assert_eq!(repo.thread_root(&root.id)?.unwrap().id, root.id);
+        assert!(repo.thread_root(&Uuid::new_v4())?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_replies_and_upvoted_pages_for_a_user() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        for (id, name) in [(alice, "alice"), (bob, "bob")] {
+            db.pool.get()?.execute(
+                "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+                (id.to_string(), name, "2024-01-01T00:00:00Z", 1),
+            )?;
+        }
+
+        let repo = PostRepository::new(db.pool.clone());
+        let first = make_post(bob, "first", 30, None);
+        let second = make_post(bob, "second", 20, None);
+        repo.create(&first)?;
+        repo.create(&second)?;
+        repo.create(&make_post(alice, "top-level", 15, None))?;
+        repo.create(&make_post(alice, "older reply", 10, Some(first.id)))?;
+        repo.create(&make_post(alice, "newer reply", 5, Some(second.id)))?;
+
+        let replies = repo.get_replies_by_user(&alice, 10, 0)?;
+        let contents: Vec<&str> = replies.iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(contents, vec!["newer reply", "older reply"]);
+        assert_eq!(repo.get_replies_by_user(&alice, 1, 1)?[0].content, "older reply");
+
+        let votes = crate::db::repositories::VoteRepository::new(db.pool.clone());
+        votes.upsert_vote(&alice, &first.id, fido_types::VoteDirection::Up)?;
+        votes.upsert_vote(&alice, &second.id, fido_types::VoteDirection::Down)?;
+        let upvoted = repo.get_upvoted_by_user(&alice, 10, 0)?;
+        assert_eq!(upvoted.len(), 1);
+        assert_eq!(upvoted[0].id, first.id);
+        assert!(repo.get_upvoted_by_user(&bob, 10, 0)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_related_posts_rank_shared_hashtags_before_same_author() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let conn = db.pool.get()?;
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        for (id, name) in [(alice, "alice"), (bob, "bob")] {
+            conn.execute(
+                "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+                (id.to_string(), name, "2024-01-01T00:00:00Z", 1),
+            )?;
+        }
+
+        let repo = PostRepository::new(db.pool.clone());
+        let hashtags = crate::db::repositories::HashtagRepository::new(db.pool.clone());
+        let post = |author, content: &str, minutes_ago, tags: &[&str]| -> Result<Post> {
+            let post = make_post(author, content, minutes_ago, None);
+            repo.create(&post)?;
+            let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+            hashtags.store_hashtags(&post.id, &tags)?;
+            Ok(post)
+        };
+        let original = post(alice, "#rust #tui", 60, &["rust", "tui"])?;
+        post(bob, "#rust #tui", 50, &["rust", "tui"])?;
+        post(bob, "#rust", 10, &["rust"])?;
+        post(alice, "older from alice", 40, &[])?;
+        post(alice, "newer from alice", 20, &[])?;
+        post(bob, "unrelated", 5, &["go"])?;
+
+        let related: Vec<String> = repo
+            .get_related(&original.id, 10)?
+            .into_iter()
+            .map(|p| format!("{} {}", p.author_username, p.content))
+            .collect();
+        assert_eq!(
+            related,
+            vec![
+                "bob #rust #tui",
+                "bob #rust",
+                "alice newer from alice",
+                "alice older from alice"
+            ]
+        );
+        assert_eq!(repo.get_related(&original.id, 1)?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_triggers_keep_counters_in_sync_and_repair_fixes_drift() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        let conn = db.pool.get()?;
+        conn.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "counter", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = PostRepository::new(db.pool.clone());
+        let post = make_post(user_id, "root", 10, None);
+        repo.create(&post)?;
+        repo.create(&make_post(user_id, "reply", 5, Some(post.id)))?;
+        conn.execute(
+            "INSERT INTO votes (user_id, post_id, direction, created_at) VALUES (?, ?, 'up', ?)",
+            (user_id.to_string(), post.id.to_string(), "2024-01-01T00:00:00Z"),
+        )?;
+
+        let stored: (i32, i32, i32) = conn.query_row(
+            "SELECT upvotes, downvotes, reply_count FROM posts WHERE id = ?",
+            [post.id.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert_eq!(stored, (1, 0, 1), "Triggers should maintain the counters");
+        assert_eq!(repo.repair_counters()?, 0);
+
+        // Simulate a counter that was bumped outside the triggers
+        conn.execute(
+            "UPDATE posts SET upvotes = 42 WHERE id = ?",
+            [post.id.to_string()],
+        )?;
+        assert_eq!(repo.repair_counters()?, 1);
+        let upvotes: i32 = conn.query_row(
+            "SELECT upvotes FROM posts WHERE id = ?",
+            [post.id.to_string()],
+            |row| row.get(0),
+        )?;
+        assert_eq!(upvotes, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_tombstones_posts_with_replies_and_purges_when_empty() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "deleter", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = PostRepository::new(db.pool.clone());
+        let root = make_post(user_id, "root", 10, None);
+        let reply = make_post(user_id, "reply", 5, Some(root.id));
+        repo.create(&root)?;
+        repo.create(&reply)?;
+
+        // Root still has a reply, so it becomes a placeholder
+        assert_eq!(repo.delete_post(&root.id)?, DeleteOutcome::Tombstoned);
+        let tombstone = repo.get_by_id(&root.id)?.expect("tombstone should remain");
+        assert!(tombstone.is_deleted);
+        assert!(tombstone.content.is_empty());
+        assert_eq!(tombstone.author_id, Uuid::nil());
+        assert!(tombstone.author_username.is_empty());
+        let feed = repo.get_posts(SortOrder::Newest, 10)?;
+        assert!(feed.iter().all(|post| post.author_username.is_empty()));
+        assert_eq!(repo.get_replies(&root.id)?.len(), 1, "Thread should be preserved");
+
+        // Removing the last reply purges the now-empty tombstone too
+        assert_eq!(repo.delete_post(&reply.id)?, DeleteOutcome::Purged);
+        assert!(repo.get_by_id(&reply.id)?.is_none());
+        assert!(repo.get_by_id(&root.id)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_quota_counts_and_retention_prunes_old_posts() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "pruner", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let repo = PostRepository::new(db.pool.clone());
+        let days = 24 * 60;
+        let old_thread = make_post(user_id, "old thread", 40 * days, None);
+        let recent_reply = make_post(user_id, "recent reply", 5, Some(old_thread.id));
+        let old_alone = make_post(user_id, "old alone", 35 * days, None);
+        let recent = make_post(user_id, "recent", 60, None);
+        for post in [&old_thread, &recent_reply, &old_alone, &recent] {
+            repo.create(post)?;
+        }
+
+        let day_ago = Utc::now() - chrono::Duration::days(1);
+        assert_eq!(repo.count_created_since(&user_id, day_ago)?, 2);
+
+        let cutoff = Utc::now() - chrono::Duration::days(30);
+        assert_eq!(repo.prune_older_than(cutoff)?, 2);
+        assert!(repo.get_by_id(&old_alone.id)?.is_none());
+        // The old root keeps its recent reply attached as a placeholder
+        assert!(repo.get_by_id(&old_thread.id)?.is_some_and(|p| p.is_deleted));
+        assert!(repo.get_by_id(&recent.id)?.is_some());
+
+        // Already-pruned placeholders aren't counted again
+        assert_eq!(repo.prune_older_than(cutoff)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_posts_carry_author_badges() -> Result<()> {
+        use crate::db::repositories::UserRepository;
+        use fido_types::Badge;
+
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "badged", "2024-01-01T00:00:00Z", 1),
+        )?;
+
+        let users = UserRepository::new(db.pool.clone());
+        users.grant_badge(&user_id, Badge::Bot)?;
+        users.grant_badge(&user_id, Badge::Admin)?;
+        users.grant_badge(&user_id, Badge::Admin)?;
+
+        let repo = PostRepository::new(db.pool.clone());
+        let post = make_post(user_id, "beep", 1, None);
+        repo.create(&post)?;
+
+        let loaded = repo.get_by_id(&post.id)?.unwrap();
+        assert_eq!(loaded.author_badges, vec![Badge::Admin, Badge::Bot]);
+        assert_eq!(users.get_by_id(&user_id)?.unwrap().badges, loaded.author_badges);
+
+        assert!(users.revoke_badge(&user_id, Badge::Bot)?);
+        assert!(!users.revoke_badge(&user_id, Badge::Bot)?);
+        assert_eq!(repo.get_by_user(&user_id, 10, 0)?[0].author_badges, vec![Badge::Admin]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_statuses_are_hidden() -> Result<()> {
+        use crate::db::repositories::UserRepository;
+        use fido_types::UserStatus;
+
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), "busy", "2024-01-01T00:00:00Z", 1),
+        )?;
+        let repo = PostRepository::new(db.pool.clone());
+        let post = make_post(user_id, "status check", 1, None);
+        repo.create(&post)?;
+
+        let users = UserRepository::new(db.pool.clone());
+        let status = UserStatus {
+            text: "Hacking on fido".to_string(),
+            emoji: Some("🛠".to_string()),
+            expires_at: Some(Utc::now() + chrono::Duration::hours(1)),
+        };
+        users.set_status(&user_id, &status)?;
+        assert_eq!(users.get_status(&user_id)?.as_ref(), Some(&status));
+        assert_eq!(repo.get_by_id(&post.id)?.unwrap().author_status, Some(status));
+
+        users.set_status(
+            &user_id,
+            &UserStatus {
+                text: "Gone".to_string(),
+                emoji: None,
+                expires_at: Some(Utc::now() - chrono::Duration::minutes(1)),
+            },
+        )?;
+        assert_eq!(users.get_status(&user_id)?, None);
+        assert_eq!(repo.get_by_id(&post.id)?.unwrap().author_status, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use fido_types::ReviewRequestDetails;
+
+use crate::db::DbPool;
+
+pub struct ReviewRepository {
+    pool: DbPool,
+}
+
+impl ReviewRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Make an existing post a review request, open from the start
+    pub fn create(&self, post_id: &Uuid, url: &str, language: &str) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO review_requests (post_id, url, language) VALUES (?, ?, ?)",
+            (post_id.to_string(), url, language),
+        )
+        .context("Failed to create review request")?;
+        Ok(())
+    }
+
+    /// Author of the review request, or `None` if the post isn't one
+    pub fn author_of(&self, post_id: &Uuid) -> Result<Option<Uuid>> {
+        let conn = self.pool.get()?;
+        let author: Option<String> = conn
+            .query_row(
+                "SELECT p.author_id FROM review_requests r JOIN posts p ON p.id = r.post_id
+                 WHERE r.post_id = ?",
+                [post_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(author.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    /// Close a review request, or reopen it
+    pub fn set_open(&self, post_id: &Uuid, open: bool) -> Result<()> {
+        let closed_at = (!open).then(|| Utc::now().to_rfc3339());
+        let conn = self.pool.get()?;
+        conn.execute(
+            "UPDATE review_requests SET closed_at = ? WHERE post_id = ?",
+            (closed_at, post_id.to_string()),
+        )
+        .context("Failed to update review request")?;
+        Ok(())
+    }
+}
+
+/// Review details for each of `post_ids` that is a review request
+pub(crate) fn reviews_for(
+    conn: &Connection,
+    post_ids: &[Uuid],
+) -> Result<HashMap<Uuid, ReviewRequestDetails>> {
+    let mut reviews = HashMap::new();
+    if post_ids.is_empty() {
+        return Ok(reviews);
+    }
+
+    let placeholders = vec!["?"; post_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT post_id, url, language, closed_at IS NULL FROM review_requests WHERE post_id IN ({})",
+        placeholders
+    ))?;
+    let rows = stmt.query_map(
+        rusqlite::params_from_iter(post_ids.iter().map(|id| id.to_string())),
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                ReviewRequestDetails {
+                    url: row.get(1)?,
+                    language: row.get(2)?,
+                    is_open: row.get(3)?,
+                },
+            ))
+        },
+    )?;
+    for row in rows {
+        let (post_id, details) = row?;
+        if let Ok(post_id) = Uuid::parse_str(&post_id) {
+            reviews.insert(post_id, details);
+        }
+    }
+    Ok(reviews)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{test_post, HashtagRepository, PostRepository, ReviewScope};
+    use crate::db::Database;
+    use fido_types::{PostKind, SortOrder};
+
+    #[test]
+    fn test_open_review_requests_follow_the_viewers_languages() {
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        db.seed_test_data().unwrap();
+        let reviews = ReviewRepository::new(db.pool.clone());
+        let posts = PostRepository::new(db.pool.clone());
+        let hashtags = HashtagRepository::new(db.pool.clone());
+        let alice = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap();
+        let bob = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440002").unwrap();
+
+        let rust = test_post(alice, "New parser, please look at the error paths");
+        let go = test_post(alice, "Small refactor of the worker pool");
+        for (post, language) in [(&rust, "rust"), (&go, "go")] {
+            posts.create(post).unwrap();
+            reviews
+                .create(&post.id, "https://github.com/fido/fido/pull/1", language)
+                .unwrap();
+        }
+        hashtags.follow_hashtag(&bob, "rust").unwrap();
+
+        let ids = |scope: ReviewScope| -> Vec<Uuid> {
+            posts
+                .get_open_review_requests(&scope, SortOrder::Newest, 10)
+                .unwrap()
+                .iter()
+                .map(|p| p.id)
+                .collect()
+        };
+        assert_eq!(ids(ReviewScope::FollowedBy(bob)), vec![rust.id]);
+        assert_eq!(ids(ReviewScope::Language("go".to_string())), vec![go.id]);
+        assert_eq!(ids(ReviewScope::All).len(), 2);
+
+        let stored = posts.get_by_id(&rust.id).unwrap().unwrap();
+        let Some(PostKind::ReviewRequest(details)) = stored.kind else {
+            panic!("post should carry its review details");
+        };
+        assert_eq!(details.language, "rust");
+        assert!(details.is_open);
+        assert_eq!(reviews.author_of(&rust.id).unwrap(), Some(alice));
+
+        // Closed requests drop out until reopened
+        reviews.set_open(&rust.id, false).unwrap();
+        assert!(ids(ReviewScope::FollowedBy(bob)).is_empty());
+        reviews.set_open(&rust.id, true).unwrap();
+        assert_eq!(ids(ReviewScope::FollowedBy(bob)), vec![rust.id]);
+    }
+}
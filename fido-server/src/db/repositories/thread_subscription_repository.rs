@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use uuid::Uuid;
+
+use fido_types::WatchedThread;
+
+use crate::db::DbPool;
+
+pub struct ThreadSubscriptionRepository {
+    pool: DbPool,
+}
+
+impl ThreadSubscriptionRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Start watching a thread. Replies from before now don't count as new.
+    pub fn watch(&self, user_id: &Uuid, post_id: &Uuid) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT OR IGNORE INTO thread_subscriptions (user_id, post_id, created_at, last_seen_at)
+             VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), post_id.to_string(), &now, &now),
+        ).context("Failed to watch thread")?;
+        Ok(())
+    }
+
+    /// Stop watching a thread; returns whether it was watched
+    pub fn unwatch(&self, user_id: &Uuid, post_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn.execute(
+            "DELETE FROM thread_subscriptions WHERE user_id = ? AND post_id = ?",
+            (user_id.to_string(), post_id.to_string()),
+        ).context("Failed to unwatch thread")?;
+        Ok(removed > 0)
+    }
+
+    /// The thread has been read up to now; returns whether it is watched
+    pub fn mark_seen(&self, user_id: &Uuid, post_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let updated = conn.execute(
+            "UPDATE thread_subscriptions SET last_seen_at = ? WHERE user_id = ? AND post_id = ?",
+            (Utc::now().to_rfc3339(), user_id.to_string(), post_id.to_string()),
+        ).context("Failed to mark thread seen")?;
+        Ok(updated > 0)
+    }
+
+    /// Every thread the user watches, those with unseen replies first. Only
+    /// other people's live replies count, at any depth below the watched post.
+    pub fn watched_threads(&self, user_id: &Uuid) -> Result<Vec<WatchedThread>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "WITH RECURSIVE thread(root_id, id, author_id, created_at, is_deleted) AS (
+                SELECT s.post_id, p.id, p.author_id, p.created_at, p.is_deleted
+                FROM thread_subscriptions s
+                JOIN posts p ON p.parent_post_id = s.post_id
+                WHERE s.user_id = ?1
+
+                UNION ALL
+
+                SELECT t.root_id, p.id, p.author_id, p.created_at, p.is_deleted
+                FROM posts p
+                JOIN thread t ON p.parent_post_id = t.id
+            )
+            SELECT s.post_id, u.username, root.content,
+                   (SELECT COUNT(*) FROM thread t
+                    WHERE t.root_id = s.post_id AND t.author_id != ?1
+                      AND t.is_deleted = 0 AND t.created_at > s.last_seen_at) AS new_replies
+            FROM thread_subscriptions s
+            JOIN posts root ON root.id = s.post_id
+            JOIN users u ON u.id = root.author_id
+            WHERE s.user_id = ?1
+            ORDER BY new_replies DESC, s.created_at DESC",
+        )?;
+        let threads = stmt
+            .query_map([user_id.to_string()], |row| {
+                Ok(WatchedThread {
+                    post_id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+                    author_username: row.get(1)?,
+                    content: row.get(2)?,
+                    new_replies: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list watched threads")?;
+        Ok(threads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{test_post, PostRepository};
+    use crate::db::Database;
+    use fido_types::Post;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(id)
+    }
+
+    fn post(db: &Database, author_id: Uuid, parent_post_id: Option<Uuid>) -> Result<Uuid> {
+        let post = Post {
+            parent_post_id,
+            ..test_post(author_id, "thread post")
+        };
+        PostRepository::new(db.pool.clone()).create(&post)?;
+        Ok(post.id)
+    }
+
+    #[test]
+    fn test_watched_thread_counts_new_replies_from_others() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+        let carol = insert_user(&db, "carol")?;
+
+        let root = post(&db, bob, None)?;
+        let before_watching = post(&db, carol, Some(root))?;
+
+        let repo = ThreadSubscriptionRepository::new(db.pool.clone());
+        repo.watch(&alice, &root)?;
+        repo.watch(&alice, &root)?;
+
+        // A direct reply, a nested one, and alice's own (which doesn't count)
+        post(&db, carol, Some(root))?;
+        post(&db, bob, Some(before_watching))?;
+        post(&db, alice, Some(root))?;
+
+        let threads = repo.watched_threads(&alice)?;
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].post_id, root);
+        assert_eq!(threads[0].author_username, "bob");
+        assert_eq!(threads[0].new_replies, 2);
+        assert!(repo.watched_threads(&carol)?.is_empty(), "Subscriptions are per user");
+
+        assert!(repo.mark_seen(&alice, &root)?);
+        assert_eq!(repo.watched_threads(&alice)?[0].new_replies, 0);
+
+        assert!(repo.unwatch(&alice, &root)?);
+        assert!(!repo.unwatch(&alice, &root)?);
+        assert!(!repo.mark_seen(&alice, &root)?);
+        assert!(repo.watched_threads(&alice)?.is_empty());
+        Ok(())
+    }
+}
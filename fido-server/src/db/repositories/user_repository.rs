@@ -1,12 +1,26 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashMap;
 use uuid::Uuid;
 
-use fido_types::User;
+use fido_types::{Badge, TopContributor, User, UserStatus};
 
 use crate::db::DbPool;
 
+/// Karma for each of a user's replies a thread's author accepted, on top of
+/// one point per upvote and minus one per downvote
+pub const ACCEPTED_REPLY_KARMA: i32 = 10;
+
+/// A user's follower, following and post counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProfileCounts {
+    pub follower_count: usize,
+    pub following_count: usize,
+    /// Posts and replies that aren't deleted
+    pub post_count: usize,
+}
+
 pub struct UserRepository {
     pool: DbPool,
 }
@@ -26,20 +40,44 @@ impl UserRepository {
              ORDER BY username"
         )?;
 
-        let users = stmt.query_map([], |row| {
+        let mut users = stmt.query_map([], |row| {
             Ok(User {
                 id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
                 username: row.get(1)?,
                 bio: row.get(2)?,
                 join_date: row.get::<_, String>(3)?.parse::<DateTime<Utc>>().unwrap(),
                 is_test_user: row.get::<_, i32>(4)? == 1,
+                badges: Vec::new(),
+                karma: 0,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
+        attach_user_info(&conn, &mut users)?;
         Ok(users)
     }
 
+    /// Follower, following and post counts, kept current by triggers on
+    /// `follows` and `posts`
+    pub fn get_counts(&self, user_id: &Uuid) -> Result<Option<ProfileCounts>> {
+        let conn = self.pool.get()?;
+        let counts = conn
+            .query_row(
+                "SELECT follower_count, following_count, post_count FROM users WHERE id = ?",
+                [user_id.to_string()],
+                |row| {
+                    Ok(ProfileCounts {
+                        follower_count: row.get::<_, i64>(0)? as usize,
+                        following_count: row.get::<_, i64>(1)? as usize,
+                        post_count: row.get::<_, i64>(2)? as usize,
+                    })
+                },
+            )
+            .optional()
+            .context("Failed to read user counts")?;
+        Ok(counts)
+    }
+
     /// Get user by ID
     pub fn get_by_id(&self, user_id: &Uuid) -> Result<Option<User>> {
         let conn = self.pool.get()?;
@@ -49,16 +87,21 @@ impl UserRepository {
              WHERE id = ?"
         )?;
 
-        let user = stmt.query_row([user_id.to_string()], |row| {
+        let mut user = stmt.query_row([user_id.to_string()], |row| {
             Ok(User {
                 id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
                 username: row.get(1)?,
                 bio: row.get(2)?,
                 join_date: row.get::<_, String>(3)?.parse::<DateTime<Utc>>().unwrap(),
                 is_test_user: row.get::<_, i32>(4)? == 1,
+                badges: Vec::new(),
+                karma: 0,
             })
         }).optional()?;
 
+        if let Some(user) = user.as_mut() {
+            attach_user_info(&conn, std::slice::from_mut(user))?;
+        }
         Ok(user)
     }
 
@@ -71,16 +114,21 @@ impl UserRepository {
              WHERE username = ?"
         )?;
 
-        let user = stmt.query_row([username], |row| {
+        let mut user = stmt.query_row([username], |row| {
             Ok(User {
                 id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
                 username: row.get(1)?,
                 bio: row.get(2)?,
                 join_date: row.get::<_, String>(3)?.parse::<DateTime<Utc>>().unwrap(),
                 is_test_user: row.get::<_, i32>(4)? == 1,
+                badges: Vec::new(),
+                karma: 0,
             })
         }).optional()?;
 
+        if let Some(user) = user.as_mut() {
+            attach_user_info(&conn, std::slice::from_mut(user))?;
+        }
         Ok(user)
     }
 
@@ -121,17 +169,20 @@ impl UserRepository {
              ORDER BY username"
         )?;
 
-        let users = stmt.query_map([], |row| {
+        let mut users = stmt.query_map([], |row| {
             Ok(User {
                 id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
                 username: row.get(1)?,
                 bio: row.get(2)?,
                 join_date: row.get::<_, String>(3)?.parse::<DateTime<Utc>>().unwrap(),
                 is_test_user: row.get::<_, i32>(4)? == 1,
+                badges: Vec::new(),
+                karma: 0,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
 
+        attach_user_info(&conn, &mut users)?;
         Ok(users)
     }
 
@@ -155,16 +206,21 @@ impl UserRepository {
              WHERE github_id = ?"
         )?;
 
-        let user = stmt.query_row([github_id], |row| {
+        let mut user = stmt.query_row([github_id], |row| {
             Ok(User {
                 id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
                 username: row.get(1)?,
                 bio: row.get(2)?,
                 join_date: row.get::<_, String>(3)?.parse::<DateTime<Utc>>().unwrap(),
                 is_test_user: row.get::<_, i32>(4)? == 1,
+                badges: Vec::new(),
+                karma: 0,
             })
         }).optional()?;
 
+        if let Some(user) = user.as_mut() {
+            attach_user_info(&conn, std::slice::from_mut(user))?;
+        }
         Ok(user)
     }
 
@@ -208,6 +264,424 @@ impl UserRepository {
             bio,
             join_date,
             is_test_user: false,
+            badges: Vec::new(),
+            karma: 0,
         })
     }
+
+    /// A user's karma; see `karma_for`
+    pub fn get_karma(&self, user_id: &Uuid) -> Result<i32> {
+        let conn = self.pool.get()?;
+        Ok(karma_for(&conn, &[*user_id])?.remove(user_id).unwrap_or(0))
+    }
+
+    /// People who earned the most karma between `since` and `until`, most
+    /// first: votes from others cast in that time, and accepted answers
+    /// picked in it. Only those who came out ahead are listed.
+    pub fn top_contributors_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: i32,
+    ) -> Result<Vec<TopContributor>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT p.author_id, u.username, SUM(points.points) AS karma
+             FROM (
+                 SELECT v.post_id, CASE v.direction WHEN 'up' THEN 1 ELSE -1 END AS points
+                 FROM votes v JOIN posts p ON p.id = v.post_id
+                 WHERE v.user_id != p.author_id AND v.created_at >= ?1 AND v.created_at < ?2
+                 UNION ALL
+                 SELECT reply_id, {} FROM accepted_replies
+                 WHERE accepted_at >= ?1 AND accepted_at < ?2
+             ) points
+             JOIN posts p ON p.id = points.post_id
+             JOIN users u ON u.id = p.author_id
+             WHERE p.is_deleted = 0
+               AND p.id NOT IN (SELECT post_id FROM anonymous_posts)
+             GROUP BY p.author_id
+             HAVING karma > 0
+             ORDER BY karma DESC, u.username ASC
+             LIMIT ?3",
+            ACCEPTED_REPLY_KARMA
+        ))?;
+        let rows = stmt
+            .query_map((since.to_rfc3339(), until.to_rfc3339(), limit), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i32>(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to get top contributors")?;
+
+        let mut contributors: Vec<TopContributor> = rows
+            .into_iter()
+            .filter_map(|(id, username, karma)| {
+                Some(TopContributor {
+                    user_id: Uuid::parse_str(&id).ok()?,
+                    username,
+                    badges: Vec::new(),
+                    karma,
+                })
+            })
+            .collect();
+        let ids: Vec<Uuid> = contributors.iter().map(|c| c.user_id).collect();
+        let mut badges = badges_for(&conn, &ids)?;
+        for contributor in &mut contributors {
+            contributor.badges = badges.remove(&contributor.user_id).unwrap_or_default();
+        }
+        Ok(contributors)
+    }
+
+    /// Badges held by a user
+    pub fn get_badges(&self, user_id: &Uuid) -> Result<Vec<Badge>> {
+        let conn = self.pool.get()?;
+        Ok(badges_for(&conn, &[*user_id])?.remove(user_id).unwrap_or_default())
+    }
+
+    /// Give a user a badge; granting one they already hold is a no-op
+    pub fn grant_badge(&self, user_id: &Uuid, badge: Badge) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT OR IGNORE INTO user_badges (user_id, badge, granted_at) VALUES (?, ?, ?)",
+            (user_id.to_string(), badge.as_str(), Utc::now().to_rfc3339()),
+        ).context("Failed to grant badge")?;
+        Ok(())
+    }
+
+    /// Take a badge away, returning whether the user had it
+    pub fn revoke_badge(&self, user_id: &Uuid, badge: Badge) -> Result<bool> {
+        let conn = self.pool.get()?;
+        let removed = conn.execute(
+            "DELETE FROM user_badges WHERE user_id = ? AND badge = ?",
+            (user_id.to_string(), badge.as_str()),
+        ).context("Failed to revoke badge")?;
+        Ok(removed > 0)
+    }
+
+    /// A user's status, unless it has expired
+    pub fn get_status(&self, user_id: &Uuid) -> Result<Option<UserStatus>> {
+        let conn = self.pool.get()?;
+        Ok(statuses_for(&conn, &[*user_id])?.remove(user_id))
+    }
+
+    /// Set or replace a user's status
+    pub fn set_status(&self, user_id: &Uuid, status: &UserStatus) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO user_statuses (user_id, text, emoji, expires_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(user_id) DO UPDATE SET
+                 text = excluded.text,
+                 emoji = excluded.emoji,
+                 expires_at = excluded.expires_at,
+                 updated_at = excluded.updated_at",
+            (
+                user_id.to_string(),
+                &status.text,
+                &status.emoji,
+                status.expires_at.map(|at| at.to_rfc3339()),
+                Utc::now().to_rfc3339(),
+            ),
+        ).context("Failed to set status")?;
+        Ok(())
+    }
+
+    /// Remove a user's status
+    pub fn clear_status(&self, user_id: &Uuid) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "DELETE FROM user_statuses WHERE user_id = ?",
+            [user_id.to_string()],
+        ).context("Failed to clear status")?;
+        Ok(())
+    }
+
+    /// Where a user's account moved to, if they've left this instance
+    pub fn get_moved_to(&self, user_id: &Uuid) -> Result<Option<String>> {
+        let conn = self.pool.get()?;
+        conn.query_row(
+            "SELECT moved_to FROM account_moves WHERE user_id = ?",
+            [user_id.to_string()],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to read account move")
+    }
+
+    /// Mark a user's account as moved, or clear the marker with `None`
+    pub fn set_moved_to(&self, user_id: &Uuid, moved_to: Option<&str>) -> Result<()> {
+        let conn = self.pool.get()?;
+        match moved_to {
+            Some(moved_to) => conn.execute(
+                "INSERT INTO account_moves (user_id, moved_to, moved_at) VALUES (?, ?, ?)
+                 ON CONFLICT(user_id) DO UPDATE SET
+                     moved_to = excluded.moved_to,
+                     moved_at = excluded.moved_at",
+                (user_id.to_string(), moved_to, Utc::now().to_rfc3339()),
+            ),
+            None => conn.execute(
+                "DELETE FROM account_moves WHERE user_id = ?",
+                [user_id.to_string()],
+            ),
+        }
+        .context("Failed to update account move")?;
+        Ok(())
+    }
+}
+
+/// Badges for each of `user_ids` that has any, in display order.
+/// Takes a connection so callers can reuse the one they already hold.
+pub(crate) fn badges_for(conn: &Connection, user_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Badge>>> {
+    let mut badges: HashMap<Uuid, Vec<Badge>> = HashMap::new();
+    if user_ids.is_empty() {
+        return Ok(badges);
+    }
+
+    let placeholders = vec!["?"; user_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT user_id, badge FROM user_badges WHERE user_id IN ({})",
+        placeholders
+    ))?;
+    let rows = stmt.query_map(
+        rusqlite::params_from_iter(user_ids.iter().map(|id| id.to_string())),
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+    )?;
+    for row in rows {
+        let (user_id, badge) = row?;
+        if let (Ok(user_id), Some(badge)) = (Uuid::parse_str(&user_id), Badge::parse(&badge)) {
+            badges.entry(user_id).or_default().push(badge);
+        }
+    }
+    for list in badges.values_mut() {
+        list.sort();
+    }
+    Ok(badges)
+}
+
+/// Unexpired statuses for each of `user_ids` that has one
+pub(crate) fn statuses_for(conn: &Connection, user_ids: &[Uuid]) -> Result<HashMap<Uuid, UserStatus>> {
+    let mut statuses = HashMap::new();
+    if user_ids.is_empty() {
+        return Ok(statuses);
+    }
+
+    let placeholders = vec!["?"; user_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT user_id, text, emoji, expires_at FROM user_statuses
+         WHERE user_id IN ({}) AND (expires_at IS NULL OR expires_at > ?)",
+        placeholders
+    ))?;
+    let params = user_ids
+        .iter()
+        .map(|id| id.to_string())
+        .chain(std::iter::once(Utc::now().to_rfc3339()));
+    let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            UserStatus {
+                text: row.get(1)?,
+                emoji: row.get(2)?,
+                expires_at: row
+                    .get::<_, Option<String>>(3)?
+                    .and_then(|at| at.parse::<DateTime<Utc>>().ok()),
+            },
+        ))
+    })?;
+    for row in rows {
+        let (user_id, status) = row?;
+        if let Ok(user_id) = Uuid::parse_str(&user_id) {
+            statuses.insert(user_id, status);
+        }
+    }
+    Ok(statuses)
+}
+
+/// Karma for each of `user_ids` that has any: votes from others on their
+/// live, signed posts and replies, plus `ACCEPTED_REPLY_KARMA` per accepted
+/// reply. Anonymous posts don't count, so karma can't point back to them.
+pub(crate) fn karma_for(conn: &Connection, user_ids: &[Uuid]) -> Result<HashMap<Uuid, i32>> {
+    if user_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let placeholders = vec!["?"; user_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT p.author_id, SUM(points.points)
+         FROM (
+             SELECT v.post_id, CASE v.direction WHEN 'up' THEN 1 ELSE -1 END AS points
+             FROM votes v JOIN posts p ON p.id = v.post_id
+             WHERE v.user_id != p.author_id
+             UNION ALL
+             SELECT reply_id, {} FROM accepted_replies
+         ) points
+         JOIN posts p ON p.id = points.post_id
+         WHERE p.author_id IN ({}) AND p.is_deleted = 0
+           AND p.id NOT IN (SELECT post_id FROM anonymous_posts)
+         GROUP BY p.author_id",
+        ACCEPTED_REPLY_KARMA, placeholders
+    ))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(user_ids.iter().map(|id| id.to_string())), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, karma)| Some((Uuid::parse_str(&id).ok()?, karma)))
+        .collect())
+}
+
+fn attach_user_info(conn: &Connection, users: &mut [User]) -> Result<()> {
+    let ids: Vec<Uuid> = users.iter().map(|user| user.id).collect();
+    let mut badges = badges_for(conn, &ids)?;
+    let karma = karma_for(conn, &ids)?;
+    for user in users {
+        user.badges = badges.remove(&user.id).unwrap_or_default();
+        user.karma = karma.get(&user.id).copied().unwrap_or(0);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{FriendRepository, PostRepository, VoteRepository};
+    use crate::db::Database;
+    use fido_types::VoteDirection;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(id)
+    }
+
+    #[test]
+    fn test_counts_follow_follows_and_posts() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+        let users = UserRepository::new(db.pool.clone());
+        let friends = FriendRepository::new(db.pool.clone());
+
+        friends.follow_user(&alice, &bob)?;
+        friends.follow_user(&alice, &bob)?;
+        friends.follow_user(&bob, &alice)?;
+        let post = Uuid::new_v4();
+        for id in [post, Uuid::new_v4()] {
+            db.pool.get()?.execute(
+                "INSERT INTO posts (id, author_id, content, created_at) VALUES (?, ?, ?, ?)",
+                (id.to_string(), bob.to_string(), "hi", Utc::now().to_rfc3339()),
+            )?;
+        }
+        assert_eq!(
+            users.get_counts(&bob)?,
+            Some(ProfileCounts {
+                follower_count: 1,
+                following_count: 1,
+                post_count: 2,
+            })
+        );
+
+        friends.unfollow_user(&alice, &bob)?;
+        db.pool
+            .get()?
+            .execute("UPDATE posts SET is_deleted = 1 WHERE id = ?", [post.to_string()])?;
+        let bob_counts = users.get_counts(&bob)?.unwrap();
+        assert_eq!((bob_counts.follower_count, bob_counts.post_count), (0, 1));
+        assert_eq!(users.get_counts(&alice)?.unwrap().following_count, 0);
+        assert_eq!(users.get_counts(&Uuid::new_v4())?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_karma_counts_votes_from_others_and_accepted_replies() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+        let carol = insert_user(&db, "carol")?;
+        let users = UserRepository::new(db.pool.clone());
+        let votes = VoteRepository::new(db.pool.clone());
+
+        let insert_post = |author: Uuid, parent: Option<Uuid>| -> Result<Uuid> {
+            let id = Uuid::new_v4();
+            db.pool.get()?.execute(
+                "INSERT INTO posts (id, author_id, content, created_at, parent_post_id) VALUES (?, ?, ?, ?, ?)",
+                (id.to_string(), author.to_string(), "hi", Utc::now().to_rfc3339(), parent.map(|p| p.to_string())),
+            )?;
+            Ok(id)
+        };
+        let question = insert_post(alice, None)?;
+        let answer = insert_post(bob, Some(question))?;
+        let anonymous = insert_post(bob, None)?;
+        db.pool.get()?.execute(
+            "INSERT INTO anonymous_posts (post_id) VALUES (?)",
+            [anonymous.to_string()],
+        )?;
+
+        votes.upsert_vote(&alice, &answer, VoteDirection::Up)?;
+        votes.upsert_vote(&carol, &answer, VoteDirection::Up)?;
+        votes.upsert_vote(&carol, &question, VoteDirection::Down)?;
+        // Voting on your own post, or on an anonymous one, doesn't count
+        votes.upsert_vote(&bob, &answer, VoteDirection::Up)?;
+        votes.upsert_vote(&carol, &anonymous, VoteDirection::Up)?;
+        assert_eq!(users.get_karma(&bob)?, 2);
+        assert_eq!(users.get_karma(&alice)?, -1);
+        assert_eq!(users.get_karma(&carol)?, 0);
+
+        PostRepository::new(db.pool.clone()).accept_reply(&question, &answer)?;
+        assert_eq!(users.get_karma(&bob)?, 2 + ACCEPTED_REPLY_KARMA);
+        assert_eq!(users.get_by_id(&bob)?.unwrap().karma, 2 + ACCEPTED_REPLY_KARMA);
+
+        // Deleting the reply takes its karma with it
+        db.pool
+            .get()?
+            .execute("UPDATE posts SET is_deleted = 1 WHERE id = ?", [answer.to_string()])?;
+        assert_eq!(users.get_karma(&bob)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_contributors_count_only_the_window() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let alice = insert_user(&db, "alice")?;
+        let bob = insert_user(&db, "bob")?;
+        let carol = insert_user(&db, "carol")?;
+        let users = UserRepository::new(db.pool.clone());
+        let votes = VoteRepository::new(db.pool.clone());
+
+        let insert_post = |author: Uuid| -> Result<Uuid> {
+            let id = Uuid::new_v4();
+            db.pool.get()?.execute(
+                "INSERT INTO posts (id, author_id, content, created_at) VALUES (?, ?, ?, ?)",
+                (id.to_string(), author.to_string(), "hi", Utc::now().to_rfc3339()),
+            )?;
+            Ok(id)
+        };
+        let alices = insert_post(alice)?;
+        let bobs = insert_post(bob)?;
+        let carols = insert_post(carol)?;
+        votes.upsert_vote(&bob, &alices, VoteDirection::Up)?;
+        votes.upsert_vote(&carol, &alices, VoteDirection::Up)?;
+        votes.upsert_vote(&alice, &bobs, VoteDirection::Up)?;
+        votes.upsert_vote(&alice, &carols, VoteDirection::Down)?;
+        // An old vote counts toward karma but not toward this week
+        let old = (Utc::now() - chrono::Duration::days(10)).to_rfc3339();
+        db.pool.get()?.execute(
+            "UPDATE votes SET created_at = ? WHERE user_id = ? AND post_id = ?",
+            (old, carol.to_string(), alices.to_string()),
+        )?;
+
+        let now = Utc::now() + chrono::Duration::seconds(1);
+        let week = users.top_contributors_between(now - chrono::Duration::days(7), now, 10)?;
+        let ranked: Vec<(&str, i32)> = week.iter().map(|c| (c.username.as_str(), c.karma)).collect();
+        // Ties go alphabetically, and nobody with no or negative karma is listed
+        assert_eq!(ranked, vec![("alice", 1), ("bob", 1)]);
+        assert_eq!(users.get_karma(&alice)?, 2);
+        Ok(())
+    }
 }
@@ -3,10 +3,19 @@ use chrono::Utc;
 use rusqlite::OptionalExtension;
 use uuid::Uuid;
 
-use fido_types::{Vote, VoteDirection};
+use fido_types::{PostVoteSummary, VisibleVote, Vote, VoteDirection};
 
 use crate::db::DbPool;
 
+/// Who is asking for a post's votes, which decides how much is revealed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteAudience {
+    /// Anyone other than the author: aggregate counts only
+    Public,
+    /// The post's author: counts plus voters who opted into being visible
+    PostAuthor,
+}
+
 pub struct VoteRepository {
     pool: DbPool,
 }
@@ -72,17 +81,149 @@ impl VoteRepository {
         Ok(())
     }
 
-    /// Calculate karma for a user (sum of upvotes on their posts)
-    pub fn calculate_karma(&self, user_id: &Uuid) -> Result<i32> {
+    /// Summarize the votes on a post for the given audience.
+    ///
+    /// Voter identities never leave this method unless the audience is the
+    /// post's author *and* the voter enabled `votes_visible_to_authors`.
+    pub fn get_vote_summary(&self, post_id: &Uuid, audience: VoteAudience) -> Result<PostVoteSummary> {
+        let conn = self.pool.get()?;
+        let (upvotes, downvotes): (i32, i32) = conn.query_row(
+            "SELECT COALESCE(SUM(direction = 'up'), 0), COALESCE(SUM(direction = 'down'), 0)
+             FROM votes WHERE post_id = ?",
+            [post_id.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).context("Failed to count votes")?;
+
+        let visible_voters = match audience {
+            VoteAudience::Public => Vec::new(),
+            VoteAudience::PostAuthor => {
+                let mut stmt = conn.prepare(
+                    "SELECT u.username, v.direction
+                     FROM votes v
+                     JOIN users u ON v.user_id = u.id
+                     JOIN user_configs c ON c.user_id = v.user_id
+                     WHERE v.post_id = ? AND c.votes_visible_to_authors = 1
+                     ORDER BY v.created_at DESC"
+                )?;
+                let voters = stmt
+                    .query_map([post_id.to_string()], |row| {
+                        let direction: String = row.get(1)?;
+                        Ok(VisibleVote {
+                            username: row.get(0)?,
+                            direction: VoteDirection::parse(&direction).unwrap_or(VoteDirection::Up),
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()
+                    .context("Failed to get visible voters")?;
+                voters
+            }
+        };
+
+        Ok(PostVoteSummary {
+            post_id: *post_id,
+            upvotes,
+            downvotes,
+            visible_voters,
+        })
+    }
+
+    /// Upvotes and downvotes on a user's live, signed posts and replies
+    pub fn votes_received(&self, user_id: &Uuid) -> Result<(u32, u32)> {
         let conn = self.pool.get()?;
-        let karma: i32 = conn.query_row(
-            "SELECT COUNT(*) 
+        let votes = conn.query_row(
+            "SELECT COALESCE(SUM(v.direction = 'up'), 0), COALESCE(SUM(v.direction = 'down'), 0)
              FROM votes v
              JOIN posts p ON v.post_id = p.id
-             WHERE p.author_id = ? AND v.direction = 'up'",
+             WHERE p.author_id = ? AND p.is_deleted = 0
+               AND p.id NOT IN (SELECT post_id FROM anonymous_posts)",
             [user_id.to_string()],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(votes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::ConfigRepository;
+    use crate::db::Database;
+    use fido_types::UserConfig;
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let user_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (user_id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(user_id)
+    }
+
+    #[test]
+    fn test_vote_summary_only_reveals_opted_in_voters_to_author() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let author = insert_user(&db, "author")?;
+        let shy = insert_user(&db, "shy")?;
+        let open = insert_user(&db, "open")?;
+
+        let post_id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO posts (id, author_id, content, created_at) VALUES (?, ?, ?, ?)",
+            (post_id.to_string(), author.to_string(), "hello", "2024-01-01T00:00:00Z"),
         )?;
-        Ok(karma)
+
+        ConfigRepository::new(db.pool.clone()).update(&UserConfig {
+            user_id: open,
+            votes_visible_to_authors: true,
+            ..UserConfig::default()
+        })?;
+
+        let repo = VoteRepository::new(db.pool.clone());
+        repo.upsert_vote(&shy, &post_id, VoteDirection::Down)?;
+        repo.upsert_vote(&open, &post_id, VoteDirection::Up)?;
+
+        let public = repo.get_vote_summary(&post_id, VoteAudience::Public)?;
+        assert_eq!((public.upvotes, public.downvotes), (1, 1));
+        assert!(public.visible_voters.is_empty());
+
+        let for_author = repo.get_vote_summary(&post_id, VoteAudience::PostAuthor)?;
+        assert_eq!((for_author.upvotes, for_author.downvotes), (1, 1));
+        assert_eq!(for_author.visible_voters.len(), 1);
+        assert_eq!(for_author.visible_voters[0].username, "open");
+        assert_eq!(for_author.visible_voters[0].direction, VoteDirection::Up);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_votes_received_skip_anonymous_posts() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let author = insert_user(&db, "author")?;
+        let voter = insert_user(&db, "voter")?;
+        let critic = insert_user(&db, "critic")?;
+
+        let signed = Uuid::new_v4();
+        let anonymous = Uuid::new_v4();
+        for post_id in [signed, anonymous] {
+            db.pool.get()?.execute(
+                "INSERT INTO posts (id, author_id, content, created_at) VALUES (?, ?, ?, ?)",
+                (post_id.to_string(), author.to_string(), "hello", "2024-01-01T00:00:00Z"),
+            )?;
+        }
+        db.pool.get()?.execute(
+            "INSERT INTO anonymous_posts (post_id) VALUES (?)",
+            [anonymous.to_string()],
+        )?;
+
+        let repo = VoteRepository::new(db.pool.clone());
+        repo.upsert_vote(&voter, &signed, VoteDirection::Up)?;
+        repo.upsert_vote(&critic, &signed, VoteDirection::Down)?;
+        repo.upsert_vote(&voter, &anonymous, VoteDirection::Up)?;
+
+        assert_eq!(repo.votes_received(&author)?, (1, 1));
+        assert_eq!(repo.votes_received(&voter)?, (0, 0));
+        Ok(())
     }
 }
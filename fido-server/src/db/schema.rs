@@ -137,6 +137,14 @@ CREATE INDEX IF NOT EXISTS idx_dms_from_user ON direct_messages(from_user_id);
 CREATE INDEX IF NOT EXISTS idx_dms_to_user ON direct_messages(to_user_id);
 CREATE INDEX IF NOT EXISTS idx_dms_created_at ON direct_messages(created_at DESC);
 
+-- Text and code files sent with direct messages, one per message
+CREATE TABLE IF NOT EXISTS dm_attachments (
+    message_id TEXT PRIMARY KEY,
+    filename TEXT NOT NULL,
+    body TEXT NOT NULL,
+    FOREIGN KEY (message_id) REFERENCES direct_messages(id) ON DELETE CASCADE
+);
+
 -- User configurations table
 CREATE TABLE IF NOT EXISTS user_configs (
     user_id TEXT PRIMARY KEY,
@@ -144,9 +152,323 @@ CREATE TABLE IF NOT EXISTS user_configs (
     sort_order TEXT NOT NULL DEFAULT 'Newest',
     max_posts_display INTEGER NOT NULL DEFAULT 25,
     emoji_enabled INTEGER NOT NULL DEFAULT 1,
+    votes_visible_to_authors INTEGER NOT NULL DEFAULT 0,
+    do_not_disturb INTEGER NOT NULL DEFAULT 0,
+    quiet_hours_enabled INTEGER NOT NULL DEFAULT 0,
+    quiet_hours_start INTEGER NOT NULL DEFAULT 22,
+    quiet_hours_end INTEGER NOT NULL DEFAULT 7,
+    show_unread_counts INTEGER NOT NULL DEFAULT 1,
+    accessible_layout INTEGER NOT NULL DEFAULT 0,
+    announcements TEXT NOT NULL DEFAULT 'Off',
+    reduced_motion INTEGER NOT NULL DEFAULT 0,
+    timestamp_style TEXT NOT NULL DEFAULT 'Absolute',
+    digest_frequency TEXT NOT NULL DEFAULT 'Off',
+    feed_density TEXT NOT NULL DEFAULT 'Comfortable',
+    preview_lines INTEGER NOT NULL DEFAULT 0,
+    show_vote_counts INTEGER NOT NULL DEFAULT 1,
+    show_karma INTEGER NOT NULL DEFAULT 0,
+    pinned_hashtags TEXT NOT NULL DEFAULT '',
+    discoverable INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Instance emoji registered by admins (shortcode without colons)
+CREATE TABLE IF NOT EXISTS custom_emoji (
+    shortcode TEXT PRIMARY KEY,
+    replacement TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- Role and recognition badges (admin, moderator, bot, early_adopter)
+CREATE TABLE IF NOT EXISTS user_badges (
+    user_id TEXT NOT NULL,
+    badge TEXT NOT NULL,
+    granted_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, badge),
     FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
 );
 
+-- DM conversations a user pinned to the top of their list
+CREATE TABLE IF NOT EXISTS pinned_conversations (
+    user_id TEXT NOT NULL,
+    pinned_user_id TEXT NOT NULL,
+    pinned_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, pinned_user_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (pinned_user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Short user status lines; expired rows are ignored when read
+CREATE TABLE IF NOT EXISTS user_statuses (
+    user_id TEXT PRIMARY KEY,
+    text TEXT NOT NULL,
+    emoji TEXT,
+    expires_at TEXT,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Channels: named rooms posts can be made in, apart from the global feed
+CREATE TABLE IF NOT EXISTS channels (
+    id TEXT PRIMARY KEY,
+    name TEXT UNIQUE NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- Who joined which channel, and up to when they have read it
+CREATE TABLE IF NOT EXISTS channel_members (
+    channel_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    joined_at TEXT NOT NULL,
+    last_read_at TEXT NOT NULL,
+    PRIMARY KEY (channel_id, user_id),
+    FOREIGN KEY (channel_id) REFERENCES channels(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_channel_members_user ON channel_members(user_id);
+
+-- Top-level posts made in a channel
+CREATE TABLE IF NOT EXISTS channel_posts (
+    post_id TEXT PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (channel_id) REFERENCES channels(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_channel_posts_channel ON channel_posts(channel_id);
+
+-- Event posts: when and where, next to the post's text
+CREATE TABLE IF NOT EXISTS post_events (
+    post_id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    starts_at TEXT NOT NULL,
+    location TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_events_starts_at ON post_events(starts_at);
+
+-- Who is going to or interested in which event
+CREATE TABLE IF NOT EXISTS event_rsvps (
+    post_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('going', 'interested')),
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (post_id, user_id),
+    FOREIGN KEY (post_id) REFERENCES post_events(post_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Code review requests: what to review and in which language; open until
+-- the author closes them
+CREATE TABLE IF NOT EXISTS review_requests (
+    post_id TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    language TEXT NOT NULL,
+    closed_at TEXT,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_requests_language ON review_requests(language);
+
+-- Posts whose author is hidden; posts.author_id still says who wrote them,
+-- for moderation and the author's own use
+CREATE TABLE IF NOT EXISTS anonymous_posts (
+    post_id TEXT PRIMARY KEY,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Who may reply to a post, for posts that limit it: 'following' (people the
+-- author follows) or 'mentioned' (people @mentioned in it)
+CREATE TABLE IF NOT EXISTS reply_policies (
+    post_id TEXT PRIMARY KEY,
+    policy TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- The reply a thread's author accepted as the answer, one per thread;
+-- post_id is the thread's top post
+CREATE TABLE IF NOT EXISTS accepted_replies (
+    post_id TEXT PRIMARY KEY,
+    reply_id TEXT NOT NULL UNIQUE,
+    accepted_at TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (reply_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Job posts: the listing's fields, kept typed so the Jobs feed can filter them
+CREATE TABLE IF NOT EXISTS post_jobs (
+    post_id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    company TEXT NOT NULL,
+    work_mode TEXT NOT NULL CHECK (work_mode IN ('remote', 'onsite')),
+    url TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_jobs_work_mode ON post_jobs(work_mode);
+
+-- Accounts that moved to another instance; the old profile points there
+CREATE TABLE IF NOT EXISTS account_moves (
+    user_id TEXT PRIMARY KEY,
+    moved_to TEXT NOT NULL,
+    moved_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Digests of missed activity; `body` is the serialized Digest
+CREATE TABLE IF NOT EXISTS digests (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    body TEXT NOT NULL,
+    seen INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_digests_user ON digests(user_id, period_end);
+
+-- Weekly changelogs of followed hashtags, shared by all their followers;
+-- `body` is the serialized HashtagChangelog
+CREATE TABLE IF NOT EXISTS hashtag_changelogs (
+    id TEXT PRIMARY KEY,
+    hashtag_id TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    body TEXT NOT NULL,
+    FOREIGN KEY (hashtag_id) REFERENCES hashtags(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_hashtag_changelogs_hashtag ON hashtag_changelogs(hashtag_id, period_end);
+
+-- Followed hashtags a user doesn't want weekly changelogs for
+CREATE TABLE IF NOT EXISTS hashtag_digest_optouts (
+    user_id TEXT NOT NULL,
+    hashtag_id TEXT NOT NULL,
+    PRIMARY KEY (user_id, hashtag_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (hashtag_id) REFERENCES hashtags(id) ON DELETE CASCADE
+);
+
+-- Unposted drafts, kept on the server so they follow the user between machines
+CREATE TABLE IF NOT EXISTS post_drafts (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_drafts_user ON post_drafts(user_id, updated_at);
+
+-- Requests to publish a post quoting a DM; the other participant approves
+CREATE TABLE IF NOT EXISTS dm_crossposts (
+    id TEXT PRIMARY KEY,
+    message_id TEXT NOT NULL,
+    requester_id TEXT NOT NULL,
+    approver_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    post_id TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (message_id) REFERENCES direct_messages(id) ON DELETE CASCADE,
+    FOREIGN KEY (requester_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (approver_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_dm_crossposts_approver ON dm_crossposts(approver_id, status);
+
+-- Threads a user watches for new replies; `last_seen_at` is when they last opened it
+CREATE TABLE IF NOT EXISTS thread_subscriptions (
+    user_id TEXT NOT NULL,
+    post_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, post_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Posts a user bookmarked
+CREATE TABLE IF NOT EXISTS bookmarks (
+    user_id TEXT NOT NULL,
+    post_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, post_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Users whose posts are left out of another user's feed
+CREATE TABLE IF NOT EXISTS user_mutes (
+    user_id TEXT NOT NULL,
+    muted_user_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, muted_user_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (muted_user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Accounts a user follows on GitHub, refreshed at each GitHub login and
+-- matched against `users.github_id`; `dismissed` once the match was offered
+CREATE TABLE IF NOT EXISTS github_following (
+    user_id TEXT NOT NULL,
+    github_id INTEGER NOT NULL,
+    dismissed INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, github_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- A user's GitHub token, kept only when they granted the `gist` scope so
+-- overlong posts can be published as gists on their behalf
+CREATE TABLE IF NOT EXISTS github_tokens (
+    user_id TEXT PRIMARY KEY,
+    access_token TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Posts a content filter flagged or limited, waiting for an admin; `limit`
+-- rows keep the post out of everyone else's view until it's approved
+CREATE TABLE IF NOT EXISTS moderation_queue (
+    post_id TEXT PRIMARY KEY,
+    action TEXT NOT NULL,
+    filter TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    flagged_at TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Every admin action, append-only: the triggers below refuse edits and
+-- deletes. No foreign keys, so entries outlive the accounts they mention;
+-- `actor_username` is kept as it was at the time
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    actor_id TEXT NOT NULL,
+    actor_username TEXT NOT NULL,
+    action TEXT NOT NULL,
+    target TEXT NOT NULL,
+    reason TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_action ON admin_audit_log(action, id);
+
+CREATE TRIGGER IF NOT EXISTS trg_admin_audit_log_no_update BEFORE UPDATE ON admin_audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit_log is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_admin_audit_log_no_delete BEFORE DELETE ON admin_audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit_log is append-only');
+END;
+
 -- Post rate limiting table
 CREATE TABLE IF NOT EXISTS post_rate_limits (
     user_id TEXT PRIMARY KEY,
@@ -158,6 +480,104 @@ CREATE TABLE IF NOT EXISTS post_rate_limits (
 CREATE INDEX IF NOT EXISTS idx_post_rate_limits_user ON post_rate_limits(user_id);
 "#;
 
+/// Triggers that keep the denormalized post and user counters in step with
+/// the rows they count. Each trigger recomputes the aggregate rather than
+/// bumping it, so a missed or repeated write can't leave a counter off by one.
+///
+/// Applied after the ad-hoc column migrations since `reply_count` and the
+/// user counters are added there.
+pub const COUNTER_TRIGGERS: &str = r#"
+CREATE TRIGGER IF NOT EXISTS trg_votes_insert_counts AFTER INSERT ON votes
+BEGIN
+    UPDATE posts
+    SET upvotes = (SELECT COUNT(*) FROM votes WHERE post_id = NEW.post_id AND direction = 'up'),
+        downvotes = (SELECT COUNT(*) FROM votes WHERE post_id = NEW.post_id AND direction = 'down')
+    WHERE id = NEW.post_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_votes_update_counts AFTER UPDATE ON votes
+BEGIN
+    UPDATE posts
+    SET upvotes = (SELECT COUNT(*) FROM votes WHERE post_id = posts.id AND direction = 'up'),
+        downvotes = (SELECT COUNT(*) FROM votes WHERE post_id = posts.id AND direction = 'down')
+    WHERE id IN (OLD.post_id, NEW.post_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_votes_delete_counts AFTER DELETE ON votes
+BEGIN
+    UPDATE posts
+    SET upvotes = (SELECT COUNT(*) FROM votes WHERE post_id = OLD.post_id AND direction = 'up'),
+        downvotes = (SELECT COUNT(*) FROM votes WHERE post_id = OLD.post_id AND direction = 'down')
+    WHERE id = OLD.post_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_posts_insert_reply_count AFTER INSERT ON posts
+WHEN NEW.parent_post_id IS NOT NULL
+BEGIN
+    UPDATE posts
+    SET reply_count = (SELECT COUNT(*) FROM posts WHERE parent_post_id = NEW.parent_post_id)
+    WHERE id = NEW.parent_post_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_posts_delete_reply_count AFTER DELETE ON posts
+WHEN OLD.parent_post_id IS NOT NULL
+BEGIN
+    UPDATE posts
+    SET reply_count = (SELECT COUNT(*) FROM posts WHERE parent_post_id = OLD.parent_post_id)
+    WHERE id = OLD.parent_post_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_follows_insert_counts AFTER INSERT ON follows
+BEGIN
+    UPDATE users
+    SET follower_count = (SELECT COUNT(*) FROM follows WHERE following_id = NEW.following_id)
+    WHERE id = NEW.following_id;
+    UPDATE users
+    SET following_count = (SELECT COUNT(*) FROM follows WHERE follower_id = NEW.follower_id)
+    WHERE id = NEW.follower_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_follows_delete_counts AFTER DELETE ON follows
+BEGIN
+    UPDATE users
+    SET follower_count = (SELECT COUNT(*) FROM follows WHERE following_id = OLD.following_id)
+    WHERE id = OLD.following_id;
+    UPDATE users
+    SET following_count = (SELECT COUNT(*) FROM follows WHERE follower_id = OLD.follower_id)
+    WHERE id = OLD.follower_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_posts_insert_post_count AFTER INSERT ON posts
+BEGIN
+    UPDATE users
+    SET post_count = (SELECT COUNT(*) FROM posts WHERE author_id = NEW.author_id AND is_deleted = 0)
+    WHERE id = NEW.author_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_posts_update_post_count AFTER UPDATE OF is_deleted, author_id ON posts
+BEGIN
+    UPDATE users
+    SET post_count = (SELECT COUNT(*) FROM posts WHERE author_id = users.id AND is_deleted = 0)
+    WHERE id IN (OLD.author_id, NEW.author_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_posts_delete_post_count AFTER DELETE ON posts
+BEGIN
+    UPDATE users
+    SET post_count = (SELECT COUNT(*) FROM posts WHERE author_id = OLD.author_id AND is_deleted = 0)
+    WHERE id = OLD.author_id;
+END;
+"#;
+
+/// Fills in the user counters from scratch, for databases that had follows
+/// and posts before the counters existed
+pub const RECOUNT_USER_COUNTERS: &str = r#"
+UPDATE users
+SET follower_count = (SELECT COUNT(*) FROM follows WHERE following_id = users.id),
+    following_count = (SELECT COUNT(*) FROM follows WHERE follower_id = users.id),
+    post_count = (SELECT COUNT(*) FROM posts WHERE author_id = users.id AND is_deleted = 0);
+"#;
+
 /// Test data for development and testing
 /// Includes comprehensive test data for all features:
 /// - 3 test users (alice, bob, charlie)
@@ -347,6 +767,14 @@ INSERT OR IGNORE INTO posts (id, author_id, content, created_at, upvotes, downvo
 -- ============================================================================
 -- SOCIAL CONNECTIONS (Follows)
 -- ============================================================================
+INSERT OR IGNORE INTO channels (id, name, description, created_by, created_at) VALUES
+    ('750e8400-e29b-41d4-a716-446655440001', 'rust-help', 'Stuck on the borrow checker? Ask here', '550e8400-e29b-41d4-a716-446655440001', '2024-01-05T09:00:00Z'),
+    ('750e8400-e29b-41d4-a716-446655440002', 'show-and-tell', 'Share what you built this week', '550e8400-e29b-41d4-a716-446655440004', '2024-01-05T09:00:00Z');
+
+INSERT OR IGNORE INTO channel_members (channel_id, user_id, joined_at, last_read_at) VALUES
+    ('750e8400-e29b-41d4-a716-446655440001', '550e8400-e29b-41d4-a716-446655440001', '2024-01-05T09:00:00Z', '2024-01-05T09:00:00Z'),
+    ('750e8400-e29b-41d4-a716-446655440002', '550e8400-e29b-41d4-a716-446655440004', '2024-01-05T09:00:00Z', '2024-01-05T09:00:00Z');
+
 INSERT OR IGNORE INTO follows (follower_id, following_id, created_at) VALUES
     -- Alice follows (active networker)
     ('550e8400-e29b-41d4-a716-446655440001', '550e8400-e29b-41d4-a716-446655440002', 1704672000),
@@ -0,0 +1,302 @@
+/// Periodic "while you were away" digests and on-demand catch-ups
+///
+/// Users opt in to digests with `digest_frequency`. An hourly job compiles a
+/// digest for every subscriber whose last period has elapsed; the TUI fetches
+/// the newest unseen one on login. Catch-ups are compiled per request for any
+/// `since` the client asks about.
+///
+/// The same job writes a weekly changelog for every hashtag someone follows
+/// and hasn't turned changelogs off for: its top posts of the week. They're
+/// shared by all of the hashtag's followers and shown in the TUI's Digest
+/// section, away from the feed.
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::db::{
+    repositories::{
+        ConfigRepository, DigestRepository, DirectMessageRepository, FriendRepository,
+        HashtagRepository, PostRepository,
+    },
+    DbPool,
+};
+use crate::api::posts::hide_anonymous_authors;
+use fido_types::{Catchup, Digest, DigestFrequency, HashtagChangelog};
+
+/// How many posts a digest highlights
+const TOP_POSTS: i32 = 5;
+
+/// Hashtag changelogs cover a week each
+const CHANGELOG_PERIOD: Duration = Duration::weeks(1);
+
+/// Length of one digest period, or `None` when digests are off
+pub fn period(frequency: DigestFrequency) -> Option<Duration> {
+    match frequency {
+        DigestFrequency::Off => None,
+        DigestFrequency::Daily => Some(Duration::days(1)),
+        DigestFrequency::Weekly => Some(Duration::weeks(1)),
+    }
+}
+
+/// Gather a user's top followed posts in `[since, until)` and their unread DMs
+pub fn compile(pool: &DbPool, user_id: &Uuid, since: DateTime<Utc>, until: DateTime<Utc>) -> Result<Digest> {
+    let mut top_posts = PostRepository::new(pool.clone()).top_followed_between(user_id, since, until, TOP_POSTS)?;
+    hide_anonymous_authors(&mut top_posts, Some(*user_id));
+    let unread_messages = DirectMessageRepository::new(pool.clone()).unread_by_sender(user_id)?;
+    Ok(Digest {
+        id: Uuid::new_v4(),
+        period_start: since,
+        period_end: until,
+        top_posts,
+        unread_messages,
+    })
+}
+
+/// New followers, top followed-hashtag posts since `since`, and unread DMs
+pub fn catchup(pool: &DbPool, user_id: &Uuid, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<Catchup> {
+    let mut top_posts = PostRepository::new(pool.clone())
+        .top_followed_hashtags_between(user_id, since, now, TOP_POSTS)?;
+    hide_anonymous_authors(&mut top_posts, Some(*user_id));
+    Ok(Catchup {
+        since,
+        new_followers: FriendRepository::new(pool.clone()).get_followers_since(user_id, since.timestamp())?,
+        top_posts,
+        unread_messages: DirectMessageRepository::new(pool.clone()).unread_by_sender(user_id)?,
+    })
+}
+
+/// Compile and store a digest for every subscriber whose period has elapsed.
+/// Returns how many digests were written.
+///
+/// Empty digests are still stored, already seen, so the next period starts
+/// where this one ended.
+pub fn run_due(pool: &DbPool, now: DateTime<Utc>) -> Result<usize> {
+    let digests = DigestRepository::new(pool.clone());
+    let mut written = 0;
+    for (user_id, frequency) in ConfigRepository::new(pool.clone()).digest_subscribers()? {
+        let Some(period) = period(frequency) else { continue };
+        let since = match digests.last_period_end(&user_id)? {
+            Some(last) if now - last < period => continue,
+            Some(last) => last,
+            None => now - period,
+        };
+        digests.store(&user_id, &compile(pool, &user_id, since, now)?)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// The changelog's one-line summary of how busy the week was
+fn changelog_summary(hashtag: &str, posts: i64, authors: i64) -> String {
+    match (posts, authors) {
+        (0, _) => format!("Quiet week in #{}: no new posts", hashtag),
+        (1, _) => format!("#{} this week: 1 post", hashtag),
+        (posts, 1) => format!("#{} this week: {} posts from 1 person", hashtag, posts),
+        (posts, authors) => format!("#{} this week: {} posts from {} people", hashtag, posts, authors),
+    }
+}
+
+/// Gather a hashtag's top posts in `[since, until)`. Anonymous posts are
+/// left out since the changelog is the same for every reader.
+pub fn compile_changelog(
+    pool: &DbPool,
+    hashtag: &str,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<HashtagChangelog> {
+    let posts = PostRepository::new(pool.clone());
+    let (post_count, author_count) = posts.hashtag_activity_between(hashtag, since, until)?;
+    Ok(HashtagChangelog {
+        id: Uuid::new_v4(),
+        hashtag: hashtag.to_string(),
+        period_start: since,
+        period_end: until,
+        summary: changelog_summary(hashtag, post_count, author_count),
+        top_posts: posts.top_with_hashtag_between(hashtag, since, until, TOP_POSTS)?,
+    })
+}
+
+/// Compile and store a changelog for every followed hashtag whose week has
+/// elapsed. Returns how many were written.
+pub fn run_due_changelogs(pool: &DbPool, now: DateTime<Utc>) -> Result<usize> {
+    let digests = DigestRepository::new(pool.clone());
+    let mut written = 0;
+    for hashtag in HashtagRepository::new(pool.clone()).digest_hashtags()? {
+        let since = match digests.last_changelog_end(&hashtag)? {
+            Some(last) if now - last < CHANGELOG_PERIOD => continue,
+            Some(last) => last,
+            None => now - CHANGELOG_PERIOD,
+        };
+        digests.store_changelog(&compile_changelog(pool, &hashtag, since, now)?)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::test_post;
+    use crate::db::Database;
+    use fido_types::{DirectMessage, Post};
+
+    fn insert_user(db: &Database, username: &str) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        ConfigRepository::new(db.pool.clone()).create_default(&id)?;
+        Ok(id)
+    }
+
+    fn subscribe(db: &Database, user_id: &Uuid, frequency: DigestFrequency) -> Result<()> {
+        db.pool.get()?.execute(
+            "UPDATE user_configs SET digest_frequency = ? WHERE user_id = ?",
+            (frequency.as_str(), user_id.to_string()),
+        )?;
+        Ok(())
+    }
+
+    fn post_by(author_id: Uuid, content: &str, upvotes: i32, hours_ago: i64) -> Post {
+        Post {
+            created_at: Utc::now() - Duration::hours(hours_ago),
+            upvotes,
+            ..test_post(author_id, content)
+        }
+    }
+
+    fn send_dm(db: &Database, from_user_id: Uuid, to_user_id: Uuid) -> Result<()> {
+        DirectMessageRepository::new(db.pool.clone()).create(&DirectMessage {
+            id: Uuid::new_v4(),
+            from_user_id,
+            to_user_id,
+            from_username: String::new(),
+            to_username: String::new(),
+            content: "hi".to_string(),
+            created_at: Utc::now(),
+            is_read: false,
+            attachment: None,
+        })
+    }
+
+    #[test]
+    fn test_digest_collects_followed_posts_and_unread_dms() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let reader = insert_user(&db, "reader")?;
+        let followed = insert_user(&db, "followed")?;
+        let stranger = insert_user(&db, "stranger")?;
+        FriendRepository::new(db.pool.clone()).follow_user(&reader, &followed)?;
+
+        let posts = PostRepository::new(db.pool.clone());
+        posts.create(&post_by(followed, "meh", 1, 2))?;
+        posts.create(&post_by(followed, "great", 9, 3))?;
+        posts.create(&post_by(followed, "too old", 50, 48))?;
+        posts.create(&post_by(stranger, "not followed", 99, 1))?;
+        send_dm(&db, stranger, reader)?;
+
+        let now = Utc::now();
+        let digest = compile(&db.pool, &reader, now - Duration::days(1), now)?;
+        let contents: Vec<_> = digest.top_posts.iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(contents, vec!["great", "meh"]);
+        assert_eq!(digest.unread_messages.len(), 1);
+        assert_eq!(digest.unread_messages[0].username, "stranger");
+        assert_eq!(digest.unread_messages[0].count, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_catchup_lists_new_followers_and_hashtag_posts() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let reader = insert_user(&db, "reader")?;
+        let fan = insert_user(&db, "fan")?;
+        let followed = insert_user(&db, "followed")?;
+        let friends = FriendRepository::new(db.pool.clone());
+        friends.follow_user(&fan, &reader)?;
+        friends.follow_user(&reader, &followed)?;
+        let hashtags = HashtagRepository::new(db.pool.clone());
+        hashtags.follow_hashtag(&reader, "rust")?;
+
+        let posts = PostRepository::new(db.pool.clone());
+        let tagged = post_by(fan, "#rust is great", 3, 1);
+        posts.create(&tagged)?;
+        hashtags.store_hashtags(&tagged.id, &["rust".to_string()])?;
+        // From a followed user but without a followed hashtag
+        posts.create(&post_by(followed, "lunch", 10, 1))?;
+
+        let now = Utc::now();
+        let missed = catchup(&db.pool, &reader, now - Duration::hours(13), now)?;
+        assert_eq!(missed.new_followers, vec!["fan".to_string()]);
+        let contents: Vec<_> = missed.top_posts.iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(contents, vec!["#rust is great"]);
+
+        // Followers from before `since` aren't new
+        let later = now + Duration::seconds(5);
+        assert!(catchup(&db.pool, &reader, later, later)?.new_followers.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_due_waits_for_each_period() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let daily = insert_user(&db, "daily")?;
+        let weekly = insert_user(&db, "weekly")?;
+        insert_user(&db, "optedout")?;
+        subscribe(&db, &daily, DigestFrequency::Daily)?;
+        subscribe(&db, &weekly, DigestFrequency::Weekly)?;
+
+        let start = Utc::now();
+        assert_eq!(run_due(&db.pool, start)?, 2);
+        assert_eq!(run_due(&db.pool, start + Duration::hours(1))?, 0);
+        // Nothing happened yet, so there's nothing to show
+        let repo = DigestRepository::new(db.pool.clone());
+        assert!(repo.latest_unseen(&daily)?.is_none());
+
+        send_dm(&db, weekly, daily)?;
+        assert_eq!(run_due(&db.pool, start + Duration::days(1))?, 1);
+        let latest = repo.latest_unseen(&daily)?.expect("daily digest");
+        assert_eq!(latest.period_start, repo.last_period_end(&weekly)?.unwrap());
+        assert!(repo.mark_seen(&daily, &latest.id)?);
+        assert!(repo.latest_unseen(&daily)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_changelogs_are_weekly_per_followed_hashtag() -> Result<()> {
+        let db = Database::in_memory()?;
+        db.initialize()?;
+        let reader = insert_user(&db, "reader")?;
+        let author = insert_user(&db, "author")?;
+        let hashtags = HashtagRepository::new(db.pool.clone());
+        hashtags.follow_hashtag(&reader, "rust")?;
+        hashtags.follow_hashtag(&reader, "go")?;
+        assert!(hashtags.set_digest_enabled(&reader, "go", false)?);
+        assert!(!hashtags.set_digest_enabled(&reader, "zig", false)?);
+
+        let posts = PostRepository::new(db.pool.clone());
+        for (content, upvotes) in [("#rust 2024 edition", 4), ("#rust quiet one", 0)] {
+            let post = post_by(author, content, upvotes, 2);
+            posts.create(&post)?;
+            hashtags.store_hashtags(&post.id, &["rust".to_string()])?;
+        }
+
+        // Only #rust: #go is followed but turned off
+        let start = Utc::now();
+        assert_eq!(run_due_changelogs(&db.pool, start)?, 1);
+        assert_eq!(run_due_changelogs(&db.pool, start + Duration::days(1))?, 0);
+        let changelog = DigestRepository::new(db.pool.clone())
+            .latest_changelog("rust")?
+            .expect("rust changelog");
+        assert_eq!(changelog.summary, "#rust this week: 2 posts from 1 person");
+        let contents: Vec<_> = changelog.top_posts.iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(contents, vec!["#rust 2024 edition", "#rust quiet one"]);
+
+        assert_eq!(run_due_changelogs(&db.pool, start + Duration::weeks(1))?, 1);
+        let quiet = DigestRepository::new(db.pool.clone()).latest_changelog("rust")?.unwrap();
+        assert_eq!(quiet.summary, "Quiet week in #rust: no new posts");
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+/// Conversation exports, for archiving a DM thread outside Fido
+///
+/// The server renders the file so every client exports the same thing; the
+/// TUI only chooses where to save it.
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use fido_types::{DirectMessage, ExportFormat};
+
+/// `fido-dm-<other>-<date>.<ext>`
+pub fn filename(other_username: &str, format: ExportFormat, now: DateTime<Utc>) -> String {
+    format!(
+        "fido-dm-{}-{}.{}",
+        other_username,
+        now.format("%Y-%m-%d"),
+        format.extension()
+    )
+}
+
+/// Write out `messages` (oldest first, usernames filled in) between
+/// `username` and `other_username`
+pub fn render(
+    username: &str,
+    other_username: &str,
+    messages: &[DirectMessage],
+    format: ExportFormat,
+    now: DateTime<Utc>,
+) -> Result<String> {
+    match format {
+        ExportFormat::Markdown => Ok(markdown(username, other_username, messages, now)),
+        ExportFormat::Json => json(username, other_username, messages, now),
+    }
+}
+
+fn markdown(username: &str, other_username: &str, messages: &[DirectMessage], now: DateTime<Utc>) -> String {
+    let mut out = format!(
+        "# Conversation between @{} and @{}\n\nExported {} · {} message{}\n",
+        username,
+        other_username,
+        now.format("%Y-%m-%d %H:%M UTC"),
+        messages.len(),
+        if messages.len() == 1 { "" } else { "s" }
+    );
+    for message in messages {
+        out.push_str(&format!(
+            "\n---\n\n**@{}** · {}\n\n",
+            message.from_username,
+            message.created_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+        if !message.content.is_empty() {
+            out.push_str(&message.content);
+            out.push('\n');
+        }
+        if let Some(attachment) = &message.attachment {
+            // A fence longer than any backtick run inside keeps the file intact
+            let fence = "`".repeat(longest_backtick_run(&attachment.content).max(2) + 1);
+            let language = attachment.filename.rsplit_once('.').map_or("", |(_, ext)| ext);
+            out.push_str(&format!(
+                "\n📎 {}\n\n{}{}\n{}\n{}\n",
+                attachment.filename,
+                fence,
+                language,
+                attachment.content.trim_end_matches('\n'),
+                fence
+            ));
+        }
+    }
+    out
+}
+
+fn json(username: &str, other_username: &str, messages: &[DirectMessage], now: DateTime<Utc>) -> Result<String> {
+    let messages: Vec<_> = messages
+        .iter()
+        .map(|message| {
+            serde_json::json!({
+                "id": message.id,
+                "from": message.from_username,
+                "to": message.to_username,
+                "sent_at": message.created_at,
+                "content": message.content,
+                "attachment": message.attachment,
+            })
+        })
+        .collect();
+    let export = serde_json::json!({
+        "participants": [username, other_username],
+        "exported_at": now,
+        "messages": messages,
+    });
+    Ok(serde_json::to_string_pretty(&export)?)
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fido_types::DmAttachment;
+    use uuid::Uuid;
+
+    fn message(from: &str, to: &str, content: &str, attachment: Option<DmAttachment>) -> DirectMessage {
+        DirectMessage {
+            id: Uuid::new_v4(),
+            from_user_id: Uuid::new_v4(),
+            to_user_id: Uuid::new_v4(),
+            from_username: from.to_string(),
+            to_username: to.to_string(),
+            content: content.to_string(),
+            created_at: "2024-01-31T09:05:00Z".parse().unwrap(),
+            is_read: true,
+            attachment,
+        }
+    }
+
+    #[test]
+    fn test_markdown_export_keeps_order_names_times_and_files() {
+        let now = "2024-02-01T12:00:00Z".parse().unwrap();
+        let messages = vec![
+            message("alice", "bob", "did you see the bug?", None),
+            message(
+                "bob",
+                "alice",
+                "",
+                Some(DmAttachment {
+                    filename: "fix.md".to_string(),
+                    content: "use ```code``` here\n".to_string(),
+                }),
+            ),
+        ];
+
+        let markdown = render("alice", "bob", &messages, ExportFormat::Markdown, now).unwrap();
+        assert!(markdown.starts_with("# Conversation between @alice and @bob\n"));
+        assert!(markdown.contains("2 messages"));
+        let first = markdown.find("**@alice** · 2024-01-31 09:05 UTC\n\ndid you see the bug?").unwrap();
+        let second = markdown.find("**@bob**").unwrap();
+        assert!(first < second);
+        assert!(markdown.contains("📎 fix.md\n\n````md\nuse ```code``` here\n````\n"));
+        assert_eq!(filename("bob", ExportFormat::Markdown, now), "fido-dm-bob-2024-02-01.md");
+    }
+
+    #[test]
+    fn test_json_export_round_trips_messages() {
+        let now = "2024-02-01T12:00:00Z".parse().unwrap();
+        let messages = vec![message("alice", "bob", "hi", None)];
+
+        let json = render("alice", "bob", &messages, ExportFormat::Json, now).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["participants"], serde_json::json!(["alice", "bob"]));
+        assert_eq!(value["messages"][0]["from"], "alice");
+        assert_eq!(value["messages"][0]["content"], "hi");
+        assert_eq!(value["messages"][0]["sent_at"], "2024-01-31T09:05:00Z");
+    }
+}
@@ -0,0 +1,204 @@
+/// Gists: publishing text too long for a post, and reading linked ones
+///
+/// Creating a gist needs the author's own token with the `gist` scope, which
+/// is asked for at GitHub login. Gists are created secret, so they don't
+/// crowd the author's public list but open for anyone with the link. Reading
+/// one goes through the same app-authenticated API calls and cache as
+/// GitHub cards.
+use fido_types::{Gist, GistFile};
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::github_card::{get_json, CardError, ResponseCache};
+
+const GITHUB_API: &str = "https://api.github.com";
+const CREATE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Name of the file a long post is published as
+const GIST_FILENAME: &str = "post.md";
+const MAX_DESCRIPTION_CHARS: usize = 100;
+/// Files of a linked gist shown inline, and characters of each
+const MAX_FILES: usize = 10;
+const MAX_FILE_CHARS: usize = 20_000;
+
+pub type GistCache = ResponseCache<String, Gist>;
+
+/// Why a gist couldn't be created
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GistError {
+    /// Revoked, expired or lacking the `gist` scope
+    #[error("GitHub didn't accept the token")]
+    TokenRejected,
+    #[error("GitHub is unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// The ID of the gist `url` links to, if it links to one
+pub fn parse_gist_url(url: &str) -> Option<String> {
+    let url = Url::parse(url.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https")
+        || url.host_str()?.to_lowercase() != "gist.github.com"
+    {
+        return None;
+    }
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    let id = match segments.as_slice() {
+        [id] | [_, id] | [_, id, "revisions"] => *id,
+        _ => return None,
+    };
+    let is_id = id.len() >= 7 && id.chars().all(|c| c.is_ascii_hexdigit());
+    is_id.then(|| id.to_lowercase())
+}
+
+/// The post for a gist at `url`: the text's opening words, cut at a word so
+/// no hashtag is split, then the link, in `max_chars` all told
+pub fn summarize(content: &str, url: &str, max_chars: usize) -> String {
+    let content = content.trim();
+    // The link, the ellipsis and the blank line before the link
+    let room = max_chars.saturating_sub(url.chars().count() + 3);
+    if content.chars().count() <= room {
+        return format!("{}\n\n{}", content, url);
+    }
+    let cut: String = content.chars().take(room).collect();
+    let opening = match cut.rfind(char::is_whitespace) {
+        Some(end) if end >= cut.len() / 2 => &cut[..end],
+        _ => cut.as_str(),
+    };
+    format!("{}…\n\n{}", opening.trim_end(), url)
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedResponse {
+    html_url: String,
+}
+
+/// Publish `content` as a secret gist with the author's token, returning
+/// its link
+pub async fn create(access_token: &str, content: &str) -> Result<String, GistError> {
+    let description: String = content
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(MAX_DESCRIPTION_CHARS)
+        .collect();
+    let body = serde_json::json!({
+        "description": description,
+        "public": false,
+        "files": { GIST_FILENAME: { "content": content } },
+    });
+
+    let client = reqwest::Client::builder()
+        .timeout(CREATE_TIMEOUT)
+        .build()
+        .map_err(|e| GistError::Unavailable(e.to_string()))?;
+    let response = client
+        .post(format!("{}/gists", GITHUB_API))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("User-Agent", "Fido-Social")
+        .header("Accept", "application/vnd.github.v3+json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| GistError::Unavailable(e.to_string()))?;
+
+    match response.status() {
+        // GitHub answers 404 rather than 403 when the scope is missing
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::NOT_FOUND => {
+            Err(GistError::TokenRejected)
+        }
+        status if status.is_success() => response
+            .json::<CreatedResponse>()
+            .await
+            .map(|created| created.html_url)
+            .map_err(|e| GistError::Unavailable(e.to_string())),
+        status => Err(GistError::Unavailable(format!("status {}", status))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GistOwner {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GistFileResponse {
+    filename: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GistResponse {
+    description: Option<String>,
+    owner: Option<GistOwner>,
+    files: BTreeMap<String, GistFileResponse>,
+}
+
+fn shorten(file: GistFileResponse) -> GistFile {
+    let long = file.content.chars().count() > MAX_FILE_CHARS;
+    GistFile {
+        content: if long {
+            file.content.chars().take(MAX_FILE_CHARS).collect()
+        } else {
+            file.content
+        },
+        truncated: file.truncated || long,
+        filename: file.filename,
+    }
+}
+
+/// Fetch gist `id` with its files cut down for showing inline, `url` left
+/// for the caller to fill in; `None` if GitHub has no such gist
+pub async fn fetch(id: &str, credentials: Option<(&str, &str)>) -> Result<Option<Gist>, CardError> {
+    let gist = get_json::<GistResponse>(&format!("/gists/{}", id), credentials).await?;
+    Ok(gist.map(|gist| Gist {
+        url: String::new(),
+        owner: gist.owner.map(|owner| owner.login),
+        description: gist.description.filter(|d| !d.trim().is_empty()),
+        files: gist.files.into_values().take(MAX_FILES).map(shorten).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://gist.github.com/ferris/0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn test_gist_links_are_recognized() {
+        let id = Some("0123456789abcdef0123456789abcdef".to_string());
+        assert_eq!(parse_gist_url(URL), id);
+        assert_eq!(parse_gist_url("https://gist.github.com/0123456789ABCDEF0123456789abcdef"), id);
+        assert_eq!(parse_gist_url("https://gist.github.com/ferris"), None);
+        assert_eq!(parse_gist_url("https://github.com/ferris/0123456789abcdef"), None);
+        assert_eq!(parse_gist_url("https://gist.github.com/ferris/not-a-gist"), None);
+    }
+
+    #[test]
+    fn test_summaries_fit_in_a_post_and_end_with_the_link() {
+        let content = "Why we moved the #rust build to nextest: ".repeat(20);
+        let summary = summarize(&content, URL, 280);
+        assert!(summary.chars().count() <= 280);
+        assert!(summary.ends_with(&format!("…\n\n{}", URL)));
+        // Cut between words, so the last word before the ellipsis is whole
+        let opening = summary.split('…').next().unwrap();
+        assert!(content.starts_with(opening));
+        assert!(content[opening.len()..].starts_with(' '));
+    }
+
+    #[test]
+    fn test_long_files_are_cut_for_showing_inline() {
+        let file = shorten(GistFileResponse {
+            filename: GIST_FILENAME.to_string(),
+            content: "x".repeat(MAX_FILE_CHARS + 1),
+            truncated: false,
+        });
+        assert_eq!(file.content.chars().count(), MAX_FILE_CHARS);
+        assert!(file.truncated);
+    }
+}
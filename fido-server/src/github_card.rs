@@ -0,0 +1,352 @@
+/// Cards for GitHub repositories, issues and pull requests linked from posts
+///
+/// Metadata comes from the GitHub REST API. Calls are made as the instance's
+/// OAuth app when `GITHUB_CLIENT_SECRET` is set next to `GITHUB_CLIENT_ID`,
+/// which lifts GitHub's limit from 60 requests an hour to 5000. Answers are
+/// cached for a while, links that turn out not to exist included, so a
+/// popular link costs one request however many people see it.
+use fido_types::{GitHubCard, GitHubCardKind, GitHubState};
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const GITHUB_API: &str = "https://api.github.com";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+const ENTRY_TTL: Duration = Duration::from_secs(15 * 60);
+const MAX_ENTRIES: usize = 2048;
+
+/// First path segments on github.com that aren't user or organization names
+const RESERVED_OWNERS: &[&str] = &[
+    "about",
+    "collections",
+    "enterprise",
+    "explore",
+    "features",
+    "login",
+    "marketplace",
+    "notifications",
+    "orgs",
+    "pricing",
+    "settings",
+    "sponsors",
+    "topics",
+    "trending",
+];
+
+/// What a GitHub link points at
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GitHubTarget {
+    Repo,
+    Issue(u64),
+    PullRequest(u64),
+}
+
+/// A GitHub link reduced to what the API needs
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GitHubRef {
+    pub owner: String,
+    pub repo: String,
+    pub target: GitHubTarget,
+}
+
+/// Why a card couldn't be fetched
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CardError {
+    #[error("GitHub is unavailable: {0}")]
+    Unavailable(String),
+}
+
+fn is_name(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// The repository, issue or pull request `url` links to; `None` for
+/// anything else, like a profile, a gist or a page of the site
+pub fn parse_github_url(url: &str) -> Option<GitHubRef> {
+    let url = Url::parse(url.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https")
+        || !matches!(url.host_str()?.to_lowercase().as_str(), "github.com" | "www.github.com")
+    {
+        return None;
+    }
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    let (owner, repo) = match segments.as_slice() {
+        [owner, repo, ..] => (*owner, repo.trim_end_matches(".git")),
+        _ => return None,
+    };
+    if !is_name(owner) || !is_name(repo) || RESERVED_OWNERS.contains(&owner.to_lowercase().as_str()) {
+        return None;
+    }
+    let number = |n: &str| n.parse::<u64>().ok().filter(|n| *n > 0);
+    let target = match &segments[2..] {
+        [] | ["tree" | "blob", ..] => GitHubTarget::Repo,
+        ["issues", n, ..] => GitHubTarget::Issue(number(n)?),
+        ["pull", n, ..] => GitHubTarget::PullRequest(number(n)?),
+        _ => return None,
+    };
+    Some(GitHubRef {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        target,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoResponse {
+    description: Option<String>,
+    stargazers_count: u64,
+    language: Option<String>,
+    #[serde(default)]
+    archived: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    number: u64,
+    title: String,
+    state: String,
+    #[serde(default)]
+    comments: u64,
+    /// Present when the "issue" is a pull request
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullResponse {
+    number: u64,
+    title: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    comments: u64,
+}
+
+fn issue_state(state: &str) -> GitHubState {
+    if state == "open" {
+        GitHubState::Open
+    } else {
+        GitHubState::Closed
+    }
+}
+
+fn pull_state(pull: &PullResponse) -> GitHubState {
+    match (pull.state.as_str(), pull.merged, pull.draft) {
+        (_, true, _) => GitHubState::Merged,
+        ("open", _, true) => GitHubState::Draft,
+        (state, _, _) => issue_state(state),
+    }
+}
+
+/// GET `path` from the API; `None` when GitHub says there's nothing there
+pub(crate) async fn get_json<T: serde::de::DeserializeOwned>(
+    path: &str,
+    credentials: Option<(&str, &str)>,
+) -> Result<Option<T>, CardError> {
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| CardError::Unavailable(e.to_string()))?;
+    let mut request = client
+        .get(format!("{}{}", GITHUB_API, path))
+        .header("User-Agent", "Fido-Social")
+        .header("Accept", "application/vnd.github.v3+json");
+    if let Some((client_id, secret)) = credentials {
+        request = request.basic_auth(client_id, Some(secret));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| CardError::Unavailable(e.to_string()))?;
+
+    match response.status() {
+        // Private repositories look missing too
+        StatusCode::NOT_FOUND | StatusCode::GONE => Ok(None),
+        status if status.is_success() => response
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| CardError::Unavailable(e.to_string())),
+        status => Err(CardError::Unavailable(format!("status {}", status))),
+    }
+}
+
+async fn fetch_pull(
+    base: &str,
+    number: u64,
+    credentials: Option<(&str, &str)>,
+) -> Result<Option<GitHubCardKind>, CardError> {
+    let path = format!("{}/pulls/{}", base, number);
+    Ok(get_json::<PullResponse>(&path, credentials)
+        .await?
+        .map(|pull| GitHubCardKind::PullRequest {
+            number: pull.number,
+            state: pull_state(&pull),
+            title: pull.title,
+            comments: pull.comments,
+        }))
+}
+
+/// Fetch the card for `reference`, with `url` left for the caller to fill
+/// in; `None` if GitHub has no such thing
+pub async fn fetch(
+    reference: &GitHubRef,
+    credentials: Option<(&str, &str)>,
+) -> Result<Option<GitHubCard>, CardError> {
+    let base = format!("/repos/{}/{}", reference.owner, reference.repo);
+    let kind = match reference.target {
+        GitHubTarget::Repo => get_json::<RepoResponse>(&base, credentials)
+            .await?
+            .map(|repo| GitHubCardKind::Repo {
+                description: repo.description.filter(|d| !d.trim().is_empty()),
+                stars: repo.stargazers_count,
+                language: repo.language,
+                archived: repo.archived,
+            }),
+        GitHubTarget::Issue(number) => {
+            let path = format!("{}/issues/{}", base, number);
+            match get_json::<IssueResponse>(&path, credentials).await? {
+                // Linked as an issue but it's a pull request; show it as one
+                Some(issue) if issue.pull_request.is_some() => {
+                    fetch_pull(&base, number, credentials).await?
+                }
+                Some(issue) => Some(GitHubCardKind::Issue {
+                    number: issue.number,
+                    title: issue.title,
+                    state: issue_state(&issue.state),
+                    comments: issue.comments,
+                }),
+                None => None,
+            }
+        }
+        GitHubTarget::PullRequest(number) => fetch_pull(&base, number, credentials).await?,
+    };
+    Ok(kind.map(|kind| GitHubCard {
+        url: String::new(),
+        repo: format!("{}/{}", reference.owner, reference.repo),
+        kind,
+    }))
+}
+
+/// An answer (or a known miss) and when it was fetched
+type CachedAnswer<V> = (Option<V>, Instant);
+
+/// Recent answers from GitHub, shared by every request
+#[derive(Clone)]
+pub struct ResponseCache<K, V> {
+    entries: Arc<Mutex<HashMap<K, CachedAnswer<V>>>>,
+}
+
+pub type GitHubCardCache = ResponseCache<GitHubRef, GitHubCard>;
+
+impl<K, V> Default for ResponseCache<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> ResponseCache<K, V> {
+    /// The cached answer for `reference`: `Some(None)` means GitHub had
+    /// nothing there, `None` that it has to be asked
+    pub fn get(&self, reference: &K) -> Option<Option<V>> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(reference)
+            .filter(|(_, cached_at)| cached_at.elapsed() < ENTRY_TTL)
+            .map(|(card, _)| card.clone())
+    }
+
+    pub fn insert(&self, reference: K, card: Option<V>) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, (_, cached_at)| cached_at.elapsed() < ENTRY_TTL);
+        }
+        if entries.len() >= MAX_ENTRIES {
+            entries.clear();
+        }
+        entries.insert(reference, (card, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(owner: &str, repo: &str, target: GitHubTarget) -> Option<GitHubRef> {
+        Some(GitHubRef {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            target,
+        })
+    }
+
+    #[test]
+    fn test_repo_issue_and_pull_links_are_recognized() {
+        assert_eq!(
+            parse_github_url("https://github.com/rust-lang/rust"),
+            reference("rust-lang", "rust", GitHubTarget::Repo)
+        );
+        assert_eq!(
+            parse_github_url("https://www.github.com/tokio-rs/axum.git"),
+            reference("tokio-rs", "axum", GitHubTarget::Repo)
+        );
+        assert_eq!(
+            parse_github_url("https://github.com/rust-lang/rust/tree/master/src"),
+            reference("rust-lang", "rust", GitHubTarget::Repo)
+        );
+        assert_eq!(
+            parse_github_url("https://github.com/rust-lang/rust/issues/42#issuecomment-1"),
+            reference("rust-lang", "rust", GitHubTarget::Issue(42))
+        );
+        assert_eq!(
+            parse_github_url("https://github.com/rust-lang/rust/pull/7/files"),
+            reference("rust-lang", "rust", GitHubTarget::PullRequest(7))
+        );
+    }
+
+    #[test]
+    fn test_other_links_get_no_card() {
+        assert_eq!(parse_github_url("https://github.com/ferris"), None);
+        assert_eq!(parse_github_url("https://github.com/settings/profile"), None);
+        assert_eq!(parse_github_url("https://github.com/rust-lang/rust/actions"), None);
+        assert_eq!(parse_github_url("https://github.com/rust-lang/rust/issues/new"), None);
+        assert_eq!(parse_github_url("https://gitlab.com/rust-lang/rust"), None);
+        assert_eq!(parse_github_url("https://gist.github.com/ferris/abc"), None);
+    }
+
+    #[test]
+    fn test_pull_request_state() {
+        let pull = |state: &str, merged: bool, draft: bool| PullResponse {
+            number: 1,
+            title: String::new(),
+            state: state.to_string(),
+            draft,
+            merged,
+            comments: 0,
+        };
+        assert_eq!(pull_state(&pull("open", false, false)), GitHubState::Open);
+        assert_eq!(pull_state(&pull("open", false, true)), GitHubState::Draft);
+        assert_eq!(pull_state(&pull("closed", true, false)), GitHubState::Merged);
+        assert_eq!(pull_state(&pull("closed", false, false)), GitHubState::Closed);
+    }
+
+    #[test]
+    fn test_missing_links_are_cached_too() {
+        let cache = GitHubCardCache::default();
+        let gone = parse_github_url("https://github.com/ferris/gone").unwrap();
+        assert_eq!(cache.get(&gone), None);
+        cache.insert(gone.clone(), None);
+        assert_eq!(cache.get(&gone), Some(None));
+    }
+}
@@ -3,9 +3,26 @@
 
 pub mod api;
 pub mod config;
+pub mod content_filter;
+pub mod cookie_auth;
+pub mod cors;
 pub mod db;
+pub mod digest;
+pub mod export;
+pub mod gist;
+pub mod github_card;
 pub mod hashtag;
+pub mod link_preview;
+pub mod listener;
 pub mod mention;
+pub mod migration;
 pub mod oauth;
+pub mod profile_cache;
+pub mod rate_limit;
+pub mod router;
+pub mod sanitize;
 pub mod session;
 pub mod state;
+pub mod stats;
+pub mod tenant;
+pub mod translation;
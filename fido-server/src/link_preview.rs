@@ -0,0 +1,165 @@
+/// Link previews: where a URL posted on this instance really goes
+///
+/// Known shortener links are expanded by following their redirects (without
+/// fetching the final page), and the destination is checked against the
+/// instance blocklist from `[link_previews]` in the settings.
+use fido_types::LinkPreview;
+use reqwest::Url;
+use std::time::Duration;
+
+/// Redirect hops followed while expanding a shortener chain
+const MAX_REDIRECTS: usize = 5;
+const EXPAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+const KNOWN_SHORTENERS: &[&str] = &[
+    "bit.ly",
+    "buff.ly",
+    "cutt.ly",
+    "goo.gl",
+    "is.gd",
+    "ow.ly",
+    "rebrand.ly",
+    "shorturl.at",
+    "t.co",
+    "tinyurl.com",
+];
+
+/// Why a URL can't be previewed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LinkError {
+    #[error("Not a valid URL")]
+    Invalid,
+    #[error("Only http and https links can be opened")]
+    UnsupportedScheme,
+}
+
+/// Lowercased host of `url`, without a leading `www.`
+fn domain_of(url: &Url) -> Option<String> {
+    let host = url.host_str()?.to_lowercase();
+    Some(host.strip_prefix("www.").map(str::to_string).unwrap_or(host))
+}
+
+/// `domain` is `entry` or one of its subdomains
+fn domain_matches(domain: &str, entry: &str) -> bool {
+    domain == entry || domain.ends_with(&format!(".{}", entry))
+}
+
+fn is_shortener(domain: &str) -> bool {
+    KNOWN_SHORTENERS
+        .iter()
+        .any(|shortener| domain_matches(domain, shortener))
+}
+
+/// Blocklist entries as `preview` compares them: trimmed, lowercased and
+/// without a leading `www.`
+pub fn normalize_blocklist(entries: &[String]) -> Vec<String> {
+    entries
+        .iter()
+        .map(|entry| entry.trim().to_lowercase())
+        .map(|entry| entry.strip_prefix("www.").map(str::to_string).unwrap_or(entry))
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Whether `domain` is on the normalized `blocklist`
+fn is_blocked(blocklist: &[String], domain: &str) -> bool {
+    blocklist.iter().any(|entry| domain_matches(domain, entry))
+}
+
+fn parse(url: &str) -> Result<Url, LinkError> {
+    let url = Url::parse(url.trim()).map_err(|_| LinkError::Invalid)?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(LinkError::UnsupportedScheme);
+    }
+    Ok(url)
+}
+
+/// Follow redirects while they stay on known shorteners. Any network
+/// failure just stops the expansion at the last URL reached.
+async fn expand_shortener(url: Url) -> Url {
+    let client = match reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(EXPAND_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(_) => return url,
+    };
+
+    let mut current = url;
+    for _ in 0..MAX_REDIRECTS {
+        if !domain_of(&current).is_some_and(|domain| is_shortener(&domain)) {
+            break;
+        }
+        let response = match client.head(current.clone()).send().await {
+            Ok(response) if response.status().is_redirection() => response,
+            _ => break,
+        };
+        let next = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| current.join(location).ok())
+            .filter(|next| matches!(next.scheme(), "http" | "https"));
+        match next {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Build the preview for `url`, expanding shorteners and applying `blocklist`
+/// (see `normalize_blocklist`)
+pub async fn preview(url: &str, blocklist: &[String]) -> Result<LinkPreview, LinkError> {
+    let original = parse(url)?;
+    let original_domain = domain_of(&original).ok_or(LinkError::Invalid)?;
+
+    let resolved = if is_shortener(&original_domain) {
+        expand_shortener(original.clone()).await
+    } else {
+        original.clone()
+    };
+    let domain = domain_of(&resolved).ok_or(LinkError::Invalid)?;
+
+    let blocked = is_blocked(blocklist, &domain) || is_blocked(blocklist, &original_domain);
+
+    Ok(LinkPreview {
+        url: original.to_string(),
+        expanded: resolved != original,
+        resolved_url: resolved.to_string(),
+        domain,
+        blocked,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocklist_matches_domain_and_subdomains() {
+        let blocklist = normalize_blocklist(&[
+            "Evil.example ".to_string(),
+            "www.phish.test".to_string(),
+            String::new(),
+        ]);
+        assert_eq!(blocklist, vec!["evil.example", "phish.test"]);
+        assert!(is_blocked(&blocklist, "evil.example"));
+        assert!(is_blocked(&blocklist, "cdn.evil.example"));
+        assert!(is_blocked(&blocklist, "phish.test"));
+        assert!(!is_blocked(&blocklist, "notevil.example"));
+        assert!(!is_blocked(&[], "evil.example"));
+    }
+
+    #[test]
+    fn test_only_web_links_are_previewed() {
+        assert_eq!(parse("javascript:alert(1)"), Err(LinkError::UnsupportedScheme));
+        assert_eq!(parse("not a url"), Err(LinkError::Invalid));
+
+        let url = parse("https://www.Example.com/path").unwrap();
+        assert_eq!(domain_of(&url).as_deref(), Some("example.com"));
+        assert!(is_shortener("bit.ly"));
+        assert!(!is_shortener("example.com"));
+    }
+}
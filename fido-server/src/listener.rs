@@ -0,0 +1,227 @@
+//! Where the server accepts connections: `host:port`, a Unix domain socket,
+//! or a socket handed over by systemd socket activation.
+//!
+//! Unix socket peers are local processes (usually nginx), so their requests
+//! carry `127.0.0.1` as the peer address; list it in
+//! `rate_limits.trusted_proxies` to have `X-Forwarded-For` believed.
+
+use axum::Router;
+use std::io;
+use std::net::SocketAddr;
+
+use crate::config::Server;
+
+/// First descriptor systemd passes (`SD_LISTEN_FDS_START`)
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: i32 = 3;
+
+/// A bound socket ready to serve
+pub enum Listener {
+    Tcp(tokio::net::TcpListener),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+}
+
+impl std::fmt::Display for Listener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Listener::Tcp(listener) => match listener.local_addr() {
+                Ok(addr) => write!(f, "{}", addr),
+                Err(_) => write!(f, "TCP socket"),
+            },
+            #[cfg(unix)]
+            Listener::Unix(listener) => match listener
+                .local_addr()
+                .ok()
+                .and_then(|addr| addr.as_pathname().map(|path| path.display().to_string()))
+            {
+                Some(path) => write!(f, "unix:{}", path),
+                None => write!(f, "Unix socket"),
+            },
+        }
+    }
+}
+
+/// Bind according to `[server]`: an inherited systemd socket wins when
+/// `systemd_activation` is on and one was passed, then `unix_socket`, then
+/// `host:port`
+pub async fn bind(server: &Server) -> io::Result<Listener> {
+    #[cfg(unix)]
+    {
+        if server.systemd_activation {
+            if let Some(listener) = from_systemd()? {
+                return Ok(listener);
+            }
+            tracing::warn!("systemd_activation is on but no socket was passed; binding normally");
+        }
+        if let Some(path) = server.unix_socket.as_deref().filter(|path| !path.is_empty()) {
+            return bind_unix(path);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        if server.systemd_activation || server.unix_socket.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Unix sockets and systemd activation need a Unix platform",
+            ));
+        }
+    }
+
+    let addr: SocketAddr = format!("{}:{}", server.host, server.port)
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    Ok(Listener::Tcp(tokio::net::TcpListener::bind(addr).await?))
+}
+
+/// Listen on the socket at `path`, replacing a stale one left by an
+/// earlier run. Anything at `path` that isn't a socket is left alone.
+#[cfg(unix)]
+fn bind_unix(path: &str) -> io::Result<Listener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path),
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+    Ok(Listener::Unix(tokio::net::UnixListener::bind(path)?))
+}
+
+/// The first socket passed by systemd (`sd_listen_fds`), if it's meant for
+/// this process. The variables are cleared so children don't inherit them.
+#[cfg(unix)]
+fn from_systemd() -> io::Result<Option<Listener>> {
+    use std::os::fd::{FromRawFd, IntoRawFd};
+
+    let for_us = std::env::var("LISTEN_PID")
+        .ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+        .is_some_and(|pid| pid == std::process::id());
+    let count = std::env::var("LISTEN_FDS")
+        .ok()
+        .and_then(|count| count.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+    std::env::remove_var("LISTEN_PID");
+    std::env::remove_var("LISTEN_FDS");
+    std::env::remove_var("LISTEN_FDNAMES");
+    if !for_us || count == 0 {
+        return Ok(None);
+    }
+    if count > 1 {
+        tracing::warn!("systemd passed {} sockets; only the first is used", count);
+    }
+
+    // SAFETY: systemd guarantees descriptors from SD_LISTEN_FDS_START on are
+    // open listening sockets owned by this process, and nothing else has
+    // taken ownership of the first one
+    let tcp = unsafe { std::net::TcpListener::from_raw_fd(SD_LISTEN_FDS_START) };
+    if tcp.local_addr().is_ok() {
+        tcp.set_nonblocking(true)?;
+        return Ok(Some(Listener::Tcp(tokio::net::TcpListener::from_std(tcp)?)));
+    }
+    // Not an inet socket, so it's a Unix one
+    // SAFETY: the descriptor came from into_raw_fd and is owned by nobody else
+    let unix = unsafe { std::os::unix::net::UnixListener::from_raw_fd(tcp.into_raw_fd()) };
+    unix.set_nonblocking(true)?;
+    Ok(Some(Listener::Unix(tokio::net::UnixListener::from_std(unix)?)))
+}
+
+/// Serve `app` until the listener fails
+pub async fn serve(listener: Listener, app: Router) -> io::Result<()> {
+    match listener {
+        Listener::Tcp(listener) => {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        }
+        #[cfg(unix)]
+        Listener::Unix(listener) => serve_unix(listener, app).await,
+    }
+}
+
+#[cfg(unix)]
+async fn serve_unix(listener: tokio::net::UnixListener, app: Router) -> io::Result<()> {
+    use axum::extract::ConnectInfo;
+    use hyper::body::Incoming;
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use tower::Service;
+
+    let loopback = SocketAddr::from(([127, 0, 0, 1], 0));
+    let app = app.layer(axum::Extension(ConnectInfo(loopback)));
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                // Running out of descriptors is transient; back off briefly
+                tracing::error!("Failed to accept Unix socket connection: {}", e);
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        let app = app.clone();
+        tokio::spawn(async move {
+            let service = hyper::service::service_fn(move |request: hyper::Request<Incoming>| {
+                app.clone().call(request)
+            });
+            if let Err(e) = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("Unix socket connection ended: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn server(unix_socket: Option<String>) -> Server {
+        Server {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            public_url: None,
+            unix_socket,
+            systemd_activation: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_binds_unix_socket_and_replaces_stale_one() {
+        let path = std::env::temp_dir().join(format!("fido-test-{}.sock", uuid::Uuid::new_v4()));
+        let path_text = path.to_string_lossy().to_string();
+
+        let first = bind(&server(Some(path_text.clone()))).await.unwrap();
+        assert!(matches!(first, Listener::Unix(_)));
+        drop(first);
+        // The socket file outlives the listener; binding again must clean it up
+        let second = bind(&server(Some(path_text.clone()))).await.unwrap();
+        assert_eq!(second.to_string(), format!("unix:{}", path_text));
+        drop(second);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_refuses_to_replace_a_regular_file() {
+        let path = std::env::temp_dir().join(format!("fido-test-{}.sock", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "not a socket").unwrap();
+        let result = bind(&server(Some(path.to_string_lossy().to_string()))).await;
+        assert!(result.is_err());
+        assert!(path.exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_tcp() {
+        let listener = bind(&server(None)).await.unwrap();
+        assert!(matches!(listener, Listener::Tcp(_)));
+    }
+}
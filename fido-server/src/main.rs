@@ -1,23 +1,34 @@
 mod api;
 mod config;
+mod content_filter;
+mod cookie_auth;
+mod cors;
 mod db;
+mod digest;
+mod export;
+mod gist;
+mod github_card;
 mod hashtag;
+mod link_preview;
+mod listener;
 mod mention;
+mod migration;
 mod oauth;
+mod profile_cache;
 mod rate_limit;
+mod router;
+mod sanitize;
 mod session;
 mod state;
+mod stats;
+mod tenant;
+mod translation;
 
-use axum::{
-    middleware,
-    routing::{delete, get, post, put},
-    Router,
-};
+use axum::middleware;
 use tower_http::services::ServeDir;
-use rate_limit::RateLimiter;
+use router::api_router;
 use state::AppState;
-use std::net::SocketAddr;
-use tower_http::cors::{Any, CorsLayer};
+use std::collections::HashMap;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -57,8 +68,102 @@ async fn main() {
         std::process::exit(1);
     }
 
+    let db_options = settings.database.connection_options();
+    let db = open_database(&settings.database.path, db_options);
+
+    // Create application state
+    let state = build_state(db, &settings, settings.server.public_url.clone());
+    spawn_background_tasks(&state, &settings);
+
+    // Configure CORS; SIGHUP reloads it from the settings
+    let cors_policy = cors::SharedCorsPolicy::new(&settings.cors);
+    #[cfg(unix)]
+    {
+        let cors_policy = cors_policy.clone();
+        tokio::spawn(async move {
+            use tokio::signal::unix::{signal, SignalKind};
+            let mut hangups = match signal(SignalKind::hangup()) {
+                Ok(hangups) => hangups,
+                Err(e) => {
+                    tracing::warn!("Settings reload on SIGHUP unavailable: {}", e);
+                    return;
+                }
+            };
+            while hangups.recv().await.is_some() {
+                match config::Settings::new() {
+                    Ok(settings) => {
+                        cors_policy.replace(&settings.cors);
+                        tracing::info!(
+                            "Reloaded CORS policy: origins {:?}",
+                            settings.cors.allowed_origins
+                        );
+                    }
+                    Err(e) => tracing::error!("Keeping the current CORS policy; reload failed: {}", e),
+                }
+            }
+        });
+    }
+
+    // Build router
+    let mut app = api_router(state, &settings);
+
+    // Other communities hosted by this process, each with its own database
+    // and sessions
+    let mut host_tenants = HashMap::new();
+    for tenant_settings in &settings.tenants {
+        tracing::info!("Starting tenant {}...", tenant_settings.name);
+        let db = open_database(&tenant_settings.database, db_options);
+        let mut tenant_state = build_state(db, &settings, tenant_settings.public_url.clone())
+            .with_admins(tenant_settings.admins.clone());
+        if let Some(prefix) = &tenant_settings.path_prefix {
+            tenant_state = tenant_state.with_cookie_path(prefix);
+        }
+        spawn_background_tasks(&tenant_state, &settings);
+        let router = api_router(tenant_state, &settings);
+        for host in &tenant_settings.hosts {
+            host_tenants.insert(host.clone(), router.clone());
+        }
+        if let Some(prefix) = &tenant_settings.path_prefix {
+            app = app.nest(prefix, router);
+        }
+    }
+    let host_tenants = tenant::HostTenants::new(host_tenants);
+    if !host_tenants.is_empty() {
+        app = app.layer(middleware::from_fn_with_state(host_tenants, tenant::host_dispatch));
+    }
+
+    let app = app
+        .layer(middleware::from_fn_with_state(cors_policy, cors::cors_middleware))
+        // Serve static files from web directory (must be last)
+        .fallback_service(ServeDir::new("/web"));
+
+    // Start server
+    let listener = match listener::bind(&settings.server).await {
+        Ok(listener) => {
+            tracing::info!("Successfully bound to {}", listener);
+            listener
+        }
+        Err(e) => {
+            tracing::error!("Failed to bind to {}: {}", settings.bind_address(), e);
+            eprintln!("FATAL: Failed to bind to {}: {}", settings.bind_address(), e);
+            std::process::exit(1);
+        }
+    };
+
+    tracing::info!("Server starting successfully on {}", listener);
+
+    if let Err(e) = listener::serve(listener, app).await {
+        tracing::error!("Server error: {}", e);
+        eprintln!("FATAL: Server error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Open the database at `path`, creating its directory and schema and
+/// seeding test data. Exits the process when any step fails.
+fn open_database(path: &str, options: db::ConnectionOptions) -> db::Database {
     // Check database directory permissions
-    let db_path = std::path::Path::new(&settings.database.path);
+    let db_path = std::path::Path::new(path);
     if let Some(parent) = db_path.parent() {
         tracing::info!("Checking database directory: {}", parent.display());
         match std::fs::metadata(parent) {
@@ -81,7 +186,7 @@ async fn main() {
 
     // Initialize database with detailed error handling
     tracing::info!("Creating database connection...");
-    let db = match db::Database::new(&settings.database.path) {
+    let db = match db::Database::with_options(path, options) {
         Ok(db) => {
             tracing::info!("Successfully created database connection");
             db
@@ -112,9 +217,26 @@ async fn main() {
 
     tracing::info!("Database initialized successfully");
 
-    // Create application state
-    let state = AppState::new(db);
+    db
+}
 
+/// State shared by one community's handlers
+fn build_state(db: db::Database, settings: &config::Settings, public_url: Option<String>) -> AppState {
+    AppState::new(db)
+        .with_blocked_domains(&settings.link_previews.blocked_domains)
+        .with_limits(settings.limits.clone())
+        .with_anonymous_posts(settings.anonymous_posts.clone())
+        .with_leaderboard(settings.leaderboard.clone())
+        .with_session_lifetime_days(settings.sessions.lifetime_days)
+        .with_translation(settings.translation.clone())
+        .with_public_url(public_url)
+        .with_content_filter(content_filter::FilterPipeline::from_config(
+            &settings.content_filter,
+        ))
+}
+
+/// Session cleanup, retention pruning and digests for one community
+fn spawn_background_tasks(state: &AppState, settings: &config::Settings) {
     // Run initial session cleanup on startup
     tracing::info!("Running initial session cleanup...");
     match state.session_manager.cleanup_expired_sessions() {
@@ -132,8 +254,9 @@ async fn main() {
 
     // Start background task for periodic session cleanup
     let cleanup_state = state.clone();
+    let cleanup_every = settings.sessions.cleanup_interval_minutes as u64 * 60;
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600)); // Run every hour
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(cleanup_every));
         loop {
             interval.tick().await;
             tracing::debug!("Running periodic session cleanup...");
@@ -150,106 +273,45 @@ async fn main() {
         }
     });
 
-    // Configure CORS
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    // Create global rate limiter: 100 requests per minute per user
-    let rate_limiter = RateLimiter::new(100, 60);
-
-    // Build router
-    let app = Router::new()
-        // Health check
-        .route("/health", get(health_check))
-        // Authentication routes
-        .route("/users/test", get(api::auth::list_test_users))
-        .route("/auth/login", post(api::auth::login))
-        .route("/auth/logout", post(api::auth::logout))
-        .route("/auth/cleanup-sessions", post(api::auth::cleanup_sessions))
-        // GitHub Device Flow routes
-        .route("/auth/github/device", post(api::auth::github_device_flow))
-        .route("/auth/github/device/poll", post(api::auth::github_device_poll))
-        .route("/auth/validate", get(api::auth::validate_session))
-        // Post routes
-        .route("/posts", get(api::posts::get_posts))
-        .route("/posts", post(api::posts::create_post))
-        .route("/posts/:id/vote", post(api::posts::vote_on_post))
-        .route("/posts/:id/replies", get(api::posts::get_replies))
-        .route("/posts/:id/reply", post(api::posts::create_reply))
-        .route("/posts/:id/thread", get(api::posts::get_thread))
-        .route("/posts/:id", get(api::posts::get_post))
-        .route("/posts/:id", put(api::posts::update_post))
-        .route("/posts/:id", delete(api::posts::delete_post))
-        // Profile routes
-        .route("/users/:id/profile", get(api::profile::get_profile))
-        .route("/users/:id/profile", put(api::profile::update_profile))
-        .route("/users/:id/hashtags", get(api::profile::get_user_hashtags))
-        // DM routes
-        .route("/dms/conversations", get(api::dms::get_conversations))
-        .route("/dms/conversations/:user_id", get(api::dms::get_conversation))
-        .route("/dms/conversations/:user_id", delete(api::dms::delete_conversation))
-        .route("/dms/mark-read/:user_id", post(api::dms::mark_messages_read))
-        .route("/dms", post(api::dms::send_message))
-        // Config routes
-        .route("/config", get(api::config::get_config))
-        .route("/config", put(api::config::update_config))
-        // Hashtag routes
-        .route("/hashtags/followed", get(api::hashtags::get_followed_hashtags))
-        .route("/hashtags/follow", post(api::hashtags::follow_hashtag))
-        .route("/hashtags/follow/:name", delete(api::hashtags::unfollow_hashtag))
-        .route("/hashtags/search", get(api::hashtags::search_hashtags))
-        .route("/hashtags/active", get(api::hashtags::get_active_hashtags))
-        // User routes
-        .route("/users/search", get(api::friends::search_users))
-        .route("/users/:id/profile-view", get(api::friends::get_user_profile))
-        .route("/users/:id/follow", post(api::friends::follow_user).delete(api::friends::unfollow_user))
-        // Social routes
-        .route("/social/following", get(api::friends::get_following_list))
-        .route("/social/followers", get(api::friends::get_followers_list))
-        .route("/social/mutual", get(api::friends::get_mutual_friends_list))
-        .with_state(state)
-        .layer(middleware::from_fn(rate_limit::rate_limit_middleware))
-        .layer(axum::Extension(rate_limiter))
-        .layer(cors)
-        // Serve static files from web directory (must be last)
-        .fallback_service(ServeDir::new("/web"));
-
-    // Start server
-    let addr_str = format!("{}:{}", settings.server.host, settings.server.port);
-    let addr: SocketAddr = match addr_str.parse() {
-        Ok(addr) => addr,
-        Err(e) => {
-            tracing::error!("Failed to parse server address '{}': {}", addr_str, e);
-            eprintln!("FATAL: Failed to parse server address '{}': {}", addr_str, e);
-            std::process::exit(1);
-        }
-    };
-    
-    tracing::info!("Binding to address: {}", addr);
-
-    let listener = match tokio::net::TcpListener::bind(addr).await {
-        Ok(listener) => {
-            tracing::info!("Successfully bound to address: {}", addr);
-            listener
-        }
-        Err(e) => {
-            tracing::error!("Failed to bind to address {}: {}", addr, e);
-            eprintln!("FATAL: Failed to bind to address {}: {}", addr, e);
-            std::process::exit(1);
-        }
-    };
-
-    tracing::info!("Server starting successfully on {}", addr);
-    
-    if let Err(e) = axum::serve(listener, app).await {
-        tracing::error!("Server error: {}", e);
-        eprintln!("FATAL: Server error: {}", e);
-        std::process::exit(1);
+    // Prune posts past the retention period, if the instance sets one
+    let retention_days = settings.limits.post_retention_days;
+    if retention_days > 0 {
+        let prune_state = state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
+                let repo = db::repositories::PostRepository::new(prune_state.db.pool.clone());
+                match repo.prune_older_than(cutoff) {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Retention: pruned {} posts older than {} days", count, retention_days);
+                        prune_state.profile_cache.clear();
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Post retention pruning failed: {}", e),
+                }
+            }
+        });
     }
-}
 
-async fn health_check() -> &'static str {
-    "OK"
+    // Compile daily/weekly digests for users who opted in, and weekly
+    // changelogs of followed hashtags
+    let digest_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            match digest::run_due(&digest_state.db.pool, chrono::Utc::now()) {
+                Ok(count) if count > 0 => tracing::info!("Compiled {} digests", count),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Digest compilation failed: {}", e),
+            }
+            match digest::run_due_changelogs(&digest_state.db.pool, chrono::Utc::now()) {
+                Ok(count) if count > 0 => tracing::info!("Compiled {} hashtag changelogs", count),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Hashtag changelog compilation failed: {}", e),
+            }
+        }
+    });
 }
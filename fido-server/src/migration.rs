@@ -0,0 +1,220 @@
+/// Moving an account between Fido instances
+///
+/// The old server writes out the account's posts, follows and bio as an
+/// `AccountExport`; the new one imports it into an existing account, keeping
+/// original timestamps and self-reply threads. Importing the same export
+/// twice skips what's already there.
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use fido_types::{
+    AccountExport, AccountImportSummary, ExportedPost, Post, ReplyPolicy, User, ACCOUNT_EXPORT_VERSION,
+};
+
+use crate::db::repositories::{Connections, FriendRepository, HashtagRepository, PostRepository, UserRepository};
+use crate::db::DbPool;
+use crate::hashtag::extract_hashtags;
+use crate::sanitize::{sanitize_content, ContentKind};
+
+/// Write out `user`'s account
+pub fn export_account(pool: &DbPool, user: &User) -> Result<AccountExport> {
+    let user_repo = UserRepository::new(pool.clone());
+    let posts = PostRepository::new(pool.clone())
+        .get_all_by_author(&user.id)
+        .context("Failed to read posts")?;
+
+    // Replies to other people's posts travel as top-level posts; the thread
+    // they belonged to doesn't exist on the other side
+    let own: HashSet<Uuid> = posts.iter().map(|post| post.id).collect();
+    let posts = posts
+        .into_iter()
+        .map(|post| ExportedPost {
+            id: post.id,
+            content: post.content,
+            created_at: post.created_at,
+            parent_id: post.parent_post_id.filter(|parent| own.contains(parent)),
+        })
+        .collect();
+
+    let mut follows = Vec::new();
+    for id in FriendRepository::new(pool.clone())
+        .list_connections(&user.id, Connections::Following, None, None, None)
+        .context("Failed to read follows")?
+    {
+        if let Some(followed) = user_repo.get_by_id(&id)? {
+            follows.push(followed.username);
+        }
+    }
+
+    Ok(AccountExport {
+        version: ACCOUNT_EXPORT_VERSION,
+        username: user.username.clone(),
+        bio: user.bio.clone().filter(|bio| !bio.is_empty()),
+        exported_at: Utc::now(),
+        follows,
+        posts,
+    })
+}
+
+/// Bring `export` into `user`'s account on this instance
+pub fn import_account(pool: &DbPool, user: &User, export: &AccountExport) -> Result<AccountImportSummary> {
+    anyhow::ensure!(
+        export.version <= ACCOUNT_EXPORT_VERSION,
+        "Export version {} is newer than this server understands ({})",
+        export.version,
+        ACCOUNT_EXPORT_VERSION
+    );
+
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool.clone());
+    let hashtag_repo = HashtagRepository::new(pool.clone());
+    let mut summary = AccountImportSummary::default();
+
+    // Oldest first, so a reply's parent is always imported before it
+    let mut posts: Vec<&ExportedPost> = export.posts.iter().collect();
+    posts.sort_by_key(|post| post.created_at);
+    let mut ids: HashMap<Uuid, Uuid> = HashMap::new();
+
+    for exported in posts {
+        let Ok(content) = sanitize_content(ContentKind::Post, &exported.content) else {
+            summary.posts_rejected += 1;
+            continue;
+        };
+        if let Some(existing) = post_repo.find_by_author_at(&user.id, exported.created_at, &content)? {
+            ids.insert(exported.id, existing);
+            summary.duplicates_skipped += 1;
+            continue;
+        }
+
+        let parent_post_id = exported.parent_id.and_then(|parent| ids.get(&parent)).copied();
+        let post = Post {
+            id: Uuid::new_v4(),
+            author_id: user.id,
+            author_username: user.username.clone(),
+            content,
+            created_at: exported.created_at,
+            upvotes: 0,
+            downvotes: 0,
+            hashtags: Vec::new(),
+            user_vote: None,
+            parent_post_id,
+            reply_count: 0,
+            reply_to_user_id: parent_post_id.map(|_| user.id),
+            reply_to_username: parent_post_id.map(|_| user.username.clone()),
+            is_deleted: false,
+            author_badges: Vec::new(),
+            author_status: None,
+            kind: None,
+            is_anonymous: false,
+            reply_policy: ReplyPolicy::Everyone,
+            can_reply: true,
+            is_accepted: false,
+            author_karma: 0,
+        };
+        post_repo
+            .create(&post)
+            .with_context(|| format!("Failed to import post from {}", post.created_at))?;
+        hashtag_repo.store_hashtags(&post.id, &extract_hashtags(&post.content))?;
+
+        ids.insert(exported.id, post.id);
+        summary.posts_imported += 1;
+        if parent_post_id.is_some() {
+            summary.replies_threaded += 1;
+        }
+    }
+    if summary.posts_imported > 0 {
+        post_repo.repair_counters().context("Failed to update reply counts")?;
+    }
+
+    let friend_repo = FriendRepository::new(pool.clone());
+    for username in &export.follows {
+        match user_repo.get_by_username(username)? {
+            Some(followed) if followed.id != user.id => {
+                friend_repo.follow_user(&user.id, &followed.id)?;
+                summary.follows_imported += 1;
+            }
+            _ => summary.follows_unmatched += 1,
+        }
+    }
+
+    // Never overwrite a bio the account already has here
+    if user.bio.as_deref().unwrap_or("").is_empty() {
+        if let Some(Ok(bio)) = export.bio.as_deref().map(|bio| sanitize_content(ContentKind::Bio, bio)) {
+            if !bio.is_empty() {
+                user_repo.update_bio(&user.id, &bio)?;
+                summary.bio_imported = true;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::test_post;
+    use crate::db::Database;
+
+    fn insert_user(db: &Database, username: &str) -> Result<User> {
+        let id = Uuid::new_v4();
+        db.pool.get()?.execute(
+            "INSERT INTO users (id, username, join_date, is_test_user) VALUES (?, ?, ?, ?)",
+            (id.to_string(), username, "2024-01-01T00:00:00Z", 1),
+        )?;
+        Ok(UserRepository::new(db.pool.clone()).get_by_id(&id)?.unwrap())
+    }
+
+    #[test]
+    fn test_export_then_import_keeps_threads_and_is_repeatable() -> Result<()> {
+        // Two instances, each with the people involved
+        let old = Database::in_memory()?;
+        old.initialize()?;
+        let alice = insert_user(&old, "alice")?;
+        let bob = insert_user(&old, "bob")?;
+        UserRepository::new(old.pool.clone()).update_bio(&alice.id, "Moving soon")?;
+        FriendRepository::new(old.pool.clone()).follow_user(&alice.id, &bob.id)?;
+
+        let posts = PostRepository::new(old.pool.clone());
+        let root = Post {
+            author_username: "alice".to_string(),
+            created_at: Utc::now() - chrono::Duration::hours(2),
+            ..test_post(alice.id, "hello #rust")
+        };
+        posts.create(&root)?;
+        posts.create(&Post {
+            id: Uuid::new_v4(),
+            content: "follow-up".to_string(),
+            created_at: Utc::now() - chrono::Duration::hours(1),
+            parent_post_id: Some(root.id),
+            ..root.clone()
+        })?;
+
+        let alice = UserRepository::new(old.pool.clone()).get_by_id(&alice.id)?.unwrap();
+        let export = export_account(&old.pool, &alice)?;
+        assert_eq!(export.follows, vec!["bob"]);
+        assert_eq!(export.posts.len(), 2);
+        assert_eq!(export.posts[1].parent_id, Some(export.posts[0].id));
+
+        let new = Database::in_memory()?;
+        new.initialize()?;
+        let moved = insert_user(&new, "alice")?;
+        insert_user(&new, "bob")?;
+        let summary = import_account(&new.pool, &moved, &export)?;
+        assert_eq!(summary.posts_imported, 2);
+        assert_eq!(summary.replies_threaded, 1);
+        assert_eq!(summary.follows_imported, 1);
+        assert!(summary.bio_imported);
+
+        let imported = PostRepository::new(new.pool.clone()).get_all_by_author(&moved.id)?;
+        assert_eq!(imported[1].parent_post_id, Some(imported[0].id));
+        assert_eq!(imported[0].created_at.timestamp(), root.created_at.timestamp());
+
+        let again = import_account(&new.pool, &moved, &export)?;
+        assert_eq!(again.posts_imported, 0);
+        assert_eq!(again.duplicates_skipped, 2);
+        Ok(())
+    }
+}
@@ -6,6 +6,9 @@ use std::env;
 #[derive(Debug, Clone)]
 pub struct GitHubOAuthConfig {
     client_id: String,
+    /// Not needed for the device flow; with it, API calls made for the
+    /// instance rather than a user get the app's higher rate limit
+    client_secret: Option<String>,
 }
 
 /// GitHub user information from API
@@ -19,6 +22,15 @@ pub struct GitHubUser {
     pub avatar_url: Option<String>,
 }
 
+/// Pages of the following list fetched at login (100 accounts each)
+const MAX_FOLLOWING_PAGES: usize = 10;
+
+/// An entry in a GitHub following list; only the ID is needed
+#[derive(Debug, Deserialize)]
+struct GitHubAccount {
+    id: i64,
+}
+
 /// GitHub Device Flow - Device code response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceCodeResponse {
@@ -29,13 +41,30 @@ pub struct DeviceCodeResponse {
     pub interval: i64,
 }
 
+/// Scopes asked for at login: the email for the account, and gists so
+/// posts too long for the feed can be published as one
+const LOGIN_SCOPES: &str = "user:email gist";
+
+/// A user's access token and the scopes they granted, which can be fewer
+/// than were asked for
+#[derive(Debug, Clone)]
+pub struct AccessToken {
+    pub token: String,
+    pub scopes: Vec<String>,
+}
+
+impl AccessToken {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+}
+
 /// GitHub Device Flow - Access token response
 #[derive(Debug, Deserialize)]
 struct DeviceTokenResponse {
     access_token: Option<String>,
     #[allow(dead_code)]
     token_type: Option<String>,
-    #[allow(dead_code)]
     scope: Option<String>,
     error: Option<String>,
     error_description: Option<String>,
@@ -46,19 +75,31 @@ impl GitHubOAuthConfig {
     pub fn from_env() -> Result<Self> {
         let client_id = env::var("GITHUB_CLIENT_ID")
             .context("GITHUB_CLIENT_ID environment variable not set")?;
+        let client_secret = env::var("GITHUB_CLIENT_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty());
         
         Ok(Self {
             client_id,
+            client_secret,
         })
     }
 
+    /// The app's ID and secret, for authenticating API calls as the OAuth
+    /// app; `None` without a secret
+    pub fn app_credentials(&self) -> Option<(&str, &str)> {
+        self.client_secret
+            .as_deref()
+            .map(|secret| (self.client_id.as_str(), secret))
+    }
+
     /// Request a device code from GitHub (Device Flow step 1)
     pub async fn request_device_code(&self) -> Result<DeviceCodeResponse> {
         let client = reqwest::Client::new();
         
         let params = [
             ("client_id", self.client_id.as_str()),
-            ("scope", "user:email"),
+            ("scope", LOGIN_SCOPES),
         ];
         
         let response = client
@@ -89,7 +130,7 @@ impl GitHubOAuthConfig {
 
     /// Poll for access token (Device Flow step 2)
     /// Returns Ok(Some(token)) if authorized, Ok(None) if still pending, Err if failed
-    pub async fn poll_device_token(&self, device_code: &str) -> Result<Option<String>> {
+    pub async fn poll_device_token(&self, device_code: &str) -> Result<Option<AccessToken>> {
         let client = reqwest::Client::new();
         
         let params = [
@@ -138,8 +179,11 @@ impl GitHubOAuthConfig {
             }
         }
         
-        // Success - return the access token
-        Ok(token_response.access_token)
+        // Success - return the access token with the scopes it carries
+        let scopes = parse_scopes(token_response.scope.as_deref().unwrap_or_default());
+        Ok(token_response
+            .access_token
+            .map(|token| AccessToken { token, scopes }))
     }
 
     /// Fetch GitHub user profile using access token
@@ -173,6 +217,52 @@ impl GitHubOAuthConfig {
         Ok(user)
     }
 
+    /// IDs of the accounts the user follows on GitHub, up to
+    /// `MAX_FOLLOWING_PAGES` pages of 100
+    pub async fn get_following_ids(&self, access_token: &str) -> Result<Vec<i64>> {
+        let client = reqwest::Client::new();
+        let mut ids = Vec::new();
+
+        for page in 1..=MAX_FOLLOWING_PAGES {
+            let response = client
+                .get("https://api.github.com/user/following")
+                .query(&[("per_page", "100"), ("page", &page.to_string())])
+                .header("Authorization", format!("Bearer {}", access_token))
+                .header("User-Agent", "Fido-Social")
+                .header("Accept", "application/vnd.github.v3+json")
+                .send()
+                .await
+                .context("Failed to send following list request to GitHub")?;
+
+            if !response.status().is_success() {
+                return Err(anyhow!(
+                    "GitHub following list fetch failed with status {}",
+                    response.status()
+                ));
+            }
+
+            let accounts: Vec<GitHubAccount> = response
+                .json()
+                .await
+                .context("Failed to parse GitHub following list")?;
+            let last_page = accounts.len() < 100;
+            ids.extend(accounts.into_iter().map(|account| account.id));
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(ids)
+    }
+}
+
+/// GitHub lists granted scopes separated by commas
+fn parse_scopes(scope: &str) -> Vec<String> {
+    scope
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 #[cfg(test)]
@@ -182,10 +272,29 @@ mod tests {
     #[test]
     fn test_oauth_config_from_env() {
         std::env::set_var("GITHUB_CLIENT_ID", "test_client_id");
+        std::env::remove_var("GITHUB_CLIENT_SECRET");
         
         let config = GitHubOAuthConfig::from_env().unwrap();
         assert_eq!(config.client_id, "test_client_id");
+        assert_eq!(config.app_credentials(), None);
+
+        std::env::set_var("GITHUB_CLIENT_SECRET", "test_secret");
+        let config = GitHubOAuthConfig::from_env().unwrap();
+        assert_eq!(config.app_credentials(), Some(("test_client_id", "test_secret")));
         
         std::env::remove_var("GITHUB_CLIENT_ID");
+        std::env::remove_var("GITHUB_CLIENT_SECRET");
+    }
+
+    #[test]
+    fn test_granted_scopes_are_read() {
+        let token = AccessToken {
+            token: "t".to_string(),
+            scopes: parse_scopes("gist,user:email"),
+        };
+        assert!(token.has_scope("gist"));
+        assert!(token.has_scope("user:email"));
+        assert!(!token.has_scope("repo"));
+        assert!(parse_scopes("").is_empty());
     }
 }
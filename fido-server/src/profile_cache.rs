@@ -0,0 +1,158 @@
+/// Short-lived cache of each user's follower, following and post counts
+///
+/// Profiles and social lists show these for every user they mention. The
+/// counters themselves live on the user row; this saves the lookup for
+/// users who come up again and again. Follow and post handlers invalidate
+/// the users they touch, and entries expire anyway so nothing stays stale
+/// for long if a write path is missed.
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::db::repositories::ProfileCounts;
+
+const ENTRY_TTL: Duration = Duration::from_secs(60);
+const MAX_ENTRIES: usize = 4096;
+
+/// Hit and miss counts since the server started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ProfileCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+    pub entries: usize,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<Uuid, (ProfileCounts, Instant)>,
+    /// Bumped by every invalidation, so a load that raced one isn't cached
+    generation: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct ProfileCache {
+    inner: Arc<Mutex<Inner>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+    invalidations: Arc<AtomicU64>,
+}
+
+impl ProfileCache {
+    /// Counts for `user_id`, calling `load` when they aren't cached; `None`
+    /// from `load` (no such user) isn't cached
+    pub fn counts(
+        &self,
+        user_id: &Uuid,
+        load: impl FnOnce() -> Result<Option<ProfileCounts>>,
+    ) -> Result<Option<ProfileCounts>> {
+        let generation = {
+            let inner = self.inner.lock().unwrap();
+            if let Some((counts, cached_at)) = inner.entries.get(user_id) {
+                if cached_at.elapsed() < ENTRY_TTL {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(Some(*counts));
+                }
+            }
+            inner.generation
+        };
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let counts = load()?;
+        if let Some(counts) = counts {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.generation == generation {
+                if inner.entries.len() >= MAX_ENTRIES {
+                    inner
+                        .entries
+                        .retain(|_, (_, cached_at)| cached_at.elapsed() < ENTRY_TTL);
+                }
+                if inner.entries.len() >= MAX_ENTRIES {
+                    inner.entries.clear();
+                }
+                inner.entries.insert(*user_id, (counts, Instant::now()));
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Forget the counts of users a follow or post just changed
+    pub fn invalidate(&self, user_ids: &[Uuid]) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.generation += 1;
+        for user_id in user_ids {
+            inner.entries.remove(user_id);
+        }
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Forget everything, after changes too broad to track (retention pruning)
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.generation += 1;
+        inner.entries.clear();
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> ProfileCacheStats {
+        ProfileCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            entries: self.inner.lock().unwrap().entries.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(post_count: usize) -> ProfileCounts {
+        ProfileCounts {
+            post_count,
+            ..ProfileCounts::default()
+        }
+    }
+
+    #[test]
+    fn test_counts_are_cached_until_invalidated() -> Result<()> {
+        let cache = ProfileCache::default();
+        let alice = Uuid::new_v4();
+
+        assert_eq!(
+            cache.counts(&alice, || Ok(Some(counts(1))))?,
+            Some(counts(1))
+        );
+        // Served from the cache, so the loader isn't consulted
+        assert_eq!(
+            cache.counts(&alice, || Ok(Some(counts(2))))?,
+            Some(counts(1))
+        );
+
+        cache.invalidate(&[alice]);
+        assert_eq!(
+            cache.counts(&alice, || Ok(Some(counts(2))))?,
+            Some(counts(2))
+        );
+
+        // Unknown users aren't remembered
+        let nobody = Uuid::new_v4();
+        assert_eq!(cache.counts(&nobody, || Ok(None))?, None);
+
+        assert_eq!(
+            cache.stats(),
+            ProfileCacheStats {
+                hits: 1,
+                misses: 3,
+                invalidations: 1,
+                entries: 1,
+            }
+        );
+        Ok(())
+    }
+}
@@ -1,34 +1,146 @@
 use axum::{
-    extract::Request,
-    http::StatusCode,
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use crate::config::RateLimits;
+use crate::db::repositories::UserRepository;
+use crate::state::AppState;
+
+/// Who a request counts against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitKey {
+    /// A valid session
+    Token(String),
+    /// A client without a session
+    Ip(IpAddr),
+}
+
+impl RateLimitKey {
+    /// Sessions and addresses live in separate buckets
+    fn bucket(&self) -> String {
+        match self {
+            RateLimitKey::Token(token) => format!("token:{}", token),
+            RateLimitKey::Ip(ip) => format!("ip:{}", ip),
+        }
+    }
+}
+
 /// Simple in-memory rate limiter
-/// Tracks requests per session token with a sliding window
+/// Tracks requests per session token, or per client IP without one, with a
+/// fixed one-minute window
 #[derive(Clone)]
 pub struct RateLimiter {
-    // Map of session_token -> (request_count, window_start)
+    // Map of bucket -> (request_count, window_start)
     state: Arc<Mutex<HashMap<String, (u32, Instant)>>>,
-    max_requests: u32,
     window_duration: Duration,
+    authenticated_per_minute: u32,
+    anonymous_per_minute: u32,
+    trust_any_proxy: bool,
+    trusted_proxies: Arc<Vec<IpAddr>>,
+    ip_overrides: Arc<HashMap<IpAddr, u32>>,
+    /// Keyed by lowercase username
+    user_overrides: Arc<HashMap<String, u32>>,
 }
 
 impl RateLimiter {
-    pub fn new(max_requests: u32, window_seconds: u64) -> Self {
+    pub fn new(config: &RateLimits) -> Self {
+        let mut trusted_proxies = Vec::new();
+        let mut trust_any_proxy = false;
+        for proxy in &config.trusted_proxies {
+            match proxy.trim() {
+                "*" => trust_any_proxy = true,
+                proxy => match proxy.parse() {
+                    Ok(ip) => trusted_proxies.push(ip),
+                    Err(_) => {
+                        tracing::warn!("Ignoring trusted proxy {:?}: not an IP address", proxy)
+                    }
+                },
+            }
+        }
+
+        let mut ip_overrides = HashMap::new();
+        let mut user_overrides = HashMap::new();
+        for (identity, limit) in &config.overrides {
+            match identity.trim().parse::<IpAddr>() {
+                Ok(ip) => {
+                    ip_overrides.insert(ip, *limit);
+                }
+                Err(_) => {
+                    user_overrides.insert(identity.trim().to_lowercase(), *limit);
+                }
+            }
+        }
+
         Self {
             state: Arc::new(Mutex::new(HashMap::new())),
-            max_requests,
-            window_duration: Duration::from_secs(window_seconds),
+            window_duration: Duration::from_secs(60),
+            authenticated_per_minute: config.authenticated_per_minute,
+            anonymous_per_minute: config.anonymous_per_minute,
+            trust_any_proxy,
+            trusted_proxies: Arc::new(trusted_proxies),
+            ip_overrides: Arc::new(ip_overrides),
+            user_overrides: Arc::new(user_overrides),
         }
     }
 
-    /// Check if a request should be allowed
-    pub fn check_rate_limit(&self, token: &str) -> Result<(), String> {
+    fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        self.trust_any_proxy || self.trusted_proxies.contains(&ip)
+    }
+
+    /// The address a request really came from. `X-Forwarded-For` is only
+    /// read when the peer is a trusted proxy, and then the nearest hop that
+    /// isn't one of our proxies is the client.
+    pub fn client_ip(&self, peer: IpAddr, forwarded_for: Option<&str>) -> IpAddr {
+        if !self.is_trusted_proxy(peer) {
+            return peer;
+        }
+        let hops: Vec<IpAddr> = forwarded_for
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|hop| hop.trim().parse().ok())
+            .collect();
+        hops.iter()
+            .rev()
+            .find(|hop| !self.is_trusted_proxy(**hop))
+            .or(hops.first())
+            .copied()
+            .unwrap_or(peer)
+    }
+
+    /// Whether any override names a user, so sessions need resolving to a username
+    pub fn has_user_overrides(&self) -> bool {
+        !self.user_overrides.is_empty()
+    }
+
+    /// Requests per minute allowed for `key`; `username` is the session's
+    /// owner when known
+    pub fn limit_for(&self, key: &RateLimitKey, username: Option<&str>) -> u32 {
+        match key {
+            RateLimitKey::Token(_) => username
+                .and_then(|name| self.user_overrides.get(&name.to_lowercase()))
+                .copied()
+                .unwrap_or(self.authenticated_per_minute),
+            RateLimitKey::Ip(ip) => self
+                .ip_overrides
+                .get(ip)
+                .copied()
+                .unwrap_or(self.anonymous_per_minute),
+        }
+    }
+
+    /// Count a request against `key`, allowing `max_requests` per window (0
+    /// for no limit); the error is how long until the window resets
+    pub fn check_rate_limit(&self, key: &RateLimitKey, max_requests: u32) -> Result<(), Duration> {
+        if max_requests == 0 {
+            return Ok(());
+        }
         let mut state = self.state.lock().unwrap();
         let now = Instant::now();
 
@@ -37,16 +149,12 @@ impl RateLimiter {
             state.retain(|_, (_, start)| now.duration_since(*start) < self.window_duration * 2);
         }
 
-        match state.get_mut(token) {
+        match state.get_mut(&key.bucket()) {
             Some((count, window_start)) => {
                 // Check if we're still in the same window
                 if now.duration_since(*window_start) < self.window_duration {
-                    if *count >= self.max_requests {
-                        let remaining = self.window_duration - now.duration_since(*window_start);
-                        return Err(format!(
-                            "Rate limit exceeded. Try again in {} seconds.",
-                            remaining.as_secs()
-                        ));
+                    if *count >= max_requests {
+                        return Err(self.window_duration - now.duration_since(*window_start));
                     }
                     *count += 1;
                 } else {
@@ -56,8 +164,8 @@ impl RateLimiter {
                 }
             }
             None => {
-                // First request from this token
-                state.insert(token.to_string(), (1, now));
+                // First request in this bucket
+                state.insert(key.bucket(), (1, now));
             }
         }
 
@@ -67,26 +175,127 @@ impl RateLimiter {
 
 /// Middleware to apply rate limiting to all requests
 pub async fn rate_limit_middleware(
+    State(app_state): State<AppState>,
     axum::Extension(limiter): axum::Extension<RateLimiter>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Extract session token from headers
-    let token = request
+    // Health checks come from the platform, often many at once from one address
+    if request.uri().path() == "/health" {
+        return Ok(next.run(request).await);
+    }
+
+    // A token only gets its own bucket once it's known to be valid, so
+    // made-up tokens can't dodge the per-IP limit
+    let session = request
         .headers()
         .get("X-Session-Token")
-        .and_then(|v| v.to_str().ok());
-
-    // Only rate limit authenticated requests
-    if let Some(token) = token {
-        if let Err(msg) = limiter.check_rate_limit(token) {
-            return Ok((
-                StatusCode::TOO_MANY_REQUESTS,
-                format!("{{\"error\": \"{}\"}}", msg),
-            )
-                .into_response());
+        .and_then(|v| v.to_str().ok())
+        .and_then(|token| {
+            app_state
+                .get_authenticated_user_id_from_token(token)
+                .map(|user_id| (token.to_string(), user_id))
+        });
+
+    let (key, limit) = match session {
+        Some((token, user_id)) => {
+            let username = if limiter.has_user_overrides() {
+                UserRepository::new(app_state.db.pool.clone())
+                    .get_by_id(&user_id)
+                    .ok()
+                    .flatten()
+                    .map(|user| user.username)
+            } else {
+                None
+            };
+            let key = RateLimitKey::Token(token);
+            let limit = limiter.limit_for(&key, username.as_deref());
+            (key, limit)
+        }
+        None => {
+            let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>()
+            else {
+                return Ok(next.run(request).await);
+            };
+            let forwarded_for = request
+                .headers()
+                .get("X-Forwarded-For")
+                .and_then(|v| v.to_str().ok());
+            let key = RateLimitKey::Ip(limiter.client_ip(peer.ip(), forwarded_for));
+            let limit = limiter.limit_for(&key, None);
+            (key, limit)
         }
+    };
+
+    if let Err(retry_after) = limiter.check_rate_limit(&key, limit) {
+        let seconds = retry_after.as_secs().max(1);
+        return Ok((
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, seconds.to_string())],
+            format!(
+                "{{\"error\": \"Rate limit exceeded. Try again in {} seconds.\"}}",
+                seconds
+            ),
+        )
+            .into_response());
     }
 
     Ok(next.run(request).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(config: RateLimits) -> RateLimiter {
+        RateLimiter::new(&config)
+    }
+
+    #[test]
+    fn test_forwarded_for_is_only_believed_from_trusted_proxies() {
+        let limiter = limiter(RateLimits {
+            trusted_proxies: vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()],
+            ..RateLimits::default()
+        });
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+
+        // Straight from the client: the header is whatever it claims
+        assert_eq!(limiter.client_ip(client, Some("1.2.3.4")), client);
+        // Through our proxies: spoofed hops to the left are skipped
+        assert_eq!(
+            limiter.client_ip(proxy, Some("1.2.3.4, 203.0.113.7, 10.0.0.2")),
+            client
+        );
+        assert_eq!(limiter.client_ip(proxy, None), proxy);
+    }
+
+    #[test]
+    fn test_sessions_and_addresses_have_separate_budgets_and_overrides() {
+        let limiter = limiter(RateLimits {
+            authenticated_per_minute: 2,
+            anonymous_per_minute: 1,
+            trusted_proxies: Vec::new(),
+            overrides: HashMap::from([("Bot".to_string(), 0), ("198.51.100.9".to_string(), 3)]),
+        });
+        let session = RateLimitKey::Token("abc".to_string());
+        let ip = RateLimitKey::Ip("203.0.113.7".parse().unwrap());
+
+        assert_eq!(limiter.limit_for(&session, Some("alice")), 2);
+        assert_eq!(limiter.limit_for(&session, Some("bot")), 0);
+        assert_eq!(limiter.limit_for(&ip, None), 1);
+        assert_eq!(
+            limiter.limit_for(&RateLimitKey::Ip("198.51.100.9".parse().unwrap()), None),
+            3
+        );
+
+        assert!(limiter.check_rate_limit(&ip, 1).is_ok());
+        assert!(limiter.check_rate_limit(&ip, 1).is_err());
+        // The same client with a session starts fresh
+        assert!(limiter.check_rate_limit(&session, 2).is_ok());
+        assert!(limiter.check_rate_limit(&session, 2).is_ok());
+        assert!(limiter.check_rate_limit(&session, 2).is_err());
+        // 0 never limits
+        assert!((0..5).all(|_| limiter.check_rate_limit(&ip, 0).is_ok()));
+    }
+}
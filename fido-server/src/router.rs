@@ -0,0 +1,216 @@
+//! The API's routes and the middleware around them, shared by the server
+//! binary and the integration tests.
+
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, post, put},
+    Router,
+};
+
+use crate::api;
+use crate::config;
+use crate::cookie_auth;
+use crate::rate_limit::{self, RateLimiter};
+use crate::state::AppState;
+
+/// Every API route, bound to one community's state
+pub fn api_router(state: AppState, settings: &config::Settings) -> Router {
+    // Per-minute limits keyed by session token, or client IP without one
+    let rate_limiter = RateLimiter::new(&settings.rate_limits);
+    let rate_limit_state = state.clone();
+
+    Router::new()
+        // Health check
+        .route("/health", get(health_check))
+        .route("/features", get(api::features::get_features))
+        .route("/version", get(api::version::get_version))
+        // Authentication routes
+        .route("/users/test", get(api::auth::list_test_users))
+        .route("/auth/challenge", get(api::auth::pow_challenge))
+        .route("/auth/login", post(api::auth::login))
+        .route("/auth/logout", post(api::auth::logout))
+        .route("/auth/cleanup-sessions", post(api::auth::cleanup_sessions))
+        // GitHub Device Flow routes
+        .route("/auth/github/device", post(api::auth::github_device_flow))
+        .route("/auth/github/device/poll", post(api::auth::github_device_poll))
+        .route("/auth/validate", get(api::auth::validate_session))
+        .route("/auth/heartbeat", post(api::auth::heartbeat))
+        // Cookie auth for the browser web terminal
+        .route("/auth/web/login", post(api::auth::web_login))
+        .route("/auth/web/logout", post(api::auth::web_logout))
+        .route("/auth/csrf", get(api::auth::csrf_token))
+        // Post routes
+        .route("/posts", get(api::posts::get_posts))
+        .route("/posts", post(api::posts::create_post))
+        .route("/posts/:id/vote", post(api::posts::vote_on_post))
+        .route("/posts/:id/votes", get(api::posts::get_post_votes))
+        .route("/posts/:id/replies", get(api::posts::get_replies))
+        .route("/posts/:id/reply", post(api::posts::create_reply))
+        .route("/posts/:id/thread", get(api::posts::get_thread))
+        .route("/posts/:id/translate", get(api::posts::translate_post))
+        .route("/posts/:id/related", get(api::posts::get_related_posts))
+        .route(
+            "/posts/:id/accept",
+            put(api::posts::accept_reply).delete(api::posts::unaccept_reply),
+        )
+        .route(
+            "/posts/:id/watch",
+            put(api::watches::watch_thread).delete(api::watches::unwatch_thread),
+        )
+        .route("/posts/:id/watch/seen", post(api::watches::mark_thread_seen))
+        .route("/watches", get(api::watches::list_watched_threads))
+        // Channel routes
+        .route(
+            "/channels",
+            get(api::channels::list_channels).post(api::channels::create_channel),
+        )
+        .route(
+            "/channels/:name/membership",
+            put(api::channels::join_channel).delete(api::channels::leave_channel),
+        )
+        .route("/channels/:name/read", post(api::channels::mark_channel_read))
+        .route(
+            "/channels/:name/posts",
+            get(api::channels::get_channel_posts).post(api::channels::create_channel_post),
+        )
+        .route(
+            "/events",
+            get(api::events::upcoming_events).post(api::events::create_event),
+        )
+        .route(
+            "/posts/:id/rsvp",
+            put(api::events::set_rsvp).delete(api::events::clear_rsvp),
+        )
+        .route(
+            "/reviews",
+            get(api::reviews::get_review_requests).post(api::reviews::create_review_request),
+        )
+        .route("/posts/:id/review", put(api::reviews::set_review_open))
+        .route("/jobs", get(api::jobs::get_jobs).post(api::jobs::create_job))
+        .route("/leaderboard/posts", get(api::leaderboard::top_posts))
+        .route("/leaderboard/contributors", get(api::leaderboard::top_contributors))
+        .route("/bookmarks", get(api::bookmarks::list_bookmarks))
+        .route("/bookmarks/batch", post(api::bookmarks::bookmark_posts))
+        .route("/bookmarks/:post_id", delete(api::bookmarks::remove_bookmark))
+        .route("/mutes", get(api::mutes::list_mutes))
+        .route("/mutes/batch", post(api::mutes::mute_users))
+        .route("/mutes/:user_id", delete(api::mutes::unmute_user))
+        .route("/posts/:id", get(api::posts::get_post))
+        .route("/posts/:id", put(api::posts::update_post))
+        .route("/posts/:id", delete(api::posts::delete_post))
+        .route("/p/:id", get(api::permalink::post_page))
+        .route("/u", get(api::permalink::directory_page))
+        .route("/u/:username", get(api::permalink::profile_page))
+        .route("/robots.txt", get(api::permalink::robots_txt))
+        .route("/sitemap.xml", get(api::permalink::sitemap))
+        // Profile routes
+        .route("/users/:id/profile", get(api::profile::get_profile))
+        .route("/users/:id/profile", put(api::profile::update_profile))
+        .route("/users/:id/hashtags", get(api::profile::get_user_hashtags))
+        .route("/users/:id/stats", get(api::profile::get_user_stats))
+        .route("/users/:id/posts", get(api::profile::get_user_posts))
+        .route("/users/:id/upvoted", get(api::profile::get_user_upvoted))
+        .route(
+            "/users/:id/status",
+            put(api::profile::set_status).delete(api::profile::clear_status),
+        )
+        // Moving accounts between instances
+        .route("/account/export", get(api::account::export_account))
+        .route(
+            "/account/import",
+            post(api::account::import_account)
+                .layer(DefaultBodyLimit::max(api::account::MAX_IMPORT_BYTES)),
+        )
+        .route("/account/moved", put(api::account::set_moved))
+        // DM routes
+        .route("/dms/conversations", get(api::dms::get_conversations))
+        .route("/dms/conversations/:user_id", get(api::dms::get_conversation))
+        .route("/dms/conversations/:user_id", delete(api::dms::delete_conversation))
+        .route("/dms/conversations/:user_id/export", get(api::dms::export_conversation))
+        .route(
+            "/dms/conversations/:user_id/pin",
+            put(api::dms::pin_conversation).delete(api::dms::unpin_conversation),
+        )
+        .route("/dms/mark-read/:user_id", post(api::dms::mark_messages_read))
+        .route("/dms", post(api::dms::send_message))
+        .route(
+            "/dms/crossposts",
+            get(api::crossposts::list_crossposts).post(api::crossposts::request_crosspost),
+        )
+        .route("/dms/crossposts/:id/approve", post(api::crossposts::approve_crosspost))
+        .route("/dms/crossposts/:id/decline", post(api::crossposts::decline_crosspost))
+        // Config routes
+        .route("/config", get(api::config::get_config))
+        .route("/config", put(api::config::update_config))
+        // Hashtag routes
+        .route("/hashtags/followed", get(api::hashtags::get_followed_hashtags))
+        .route("/hashtags/follow", post(api::hashtags::follow_hashtag))
+        .route("/hashtags/follow/:name", delete(api::hashtags::unfollow_hashtag))
+        .route("/hashtags/search", get(api::hashtags::search_hashtags))
+        .route("/hashtags/active", get(api::hashtags::get_active_hashtags))
+        .route("/emoji", get(api::emoji::list_emoji))
+        .route("/emoji", put(api::emoji::upsert_emoji))
+        .route("/emoji/:shortcode", delete(api::emoji::delete_emoji))
+        .route("/links/preview", get(api::links::preview_link))
+        .route("/links/github", get(api::links::github_card))
+        .route("/links/gist", get(api::links::gist))
+        .route("/gists", post(api::gists::create_gist_post))
+        .route("/digest", get(api::digest::get_digest))
+        .route("/digest/:id/seen", post(api::digest::mark_digest_seen))
+        .route("/digest/hashtags", get(api::digest::get_hashtag_digest))
+        .route("/digest/hashtags/:name", put(api::digest::set_hashtag_digest))
+        .route("/catchup", get(api::digest::get_catchup))
+        .route("/drafts", get(api::drafts::list_drafts).post(api::drafts::create_draft))
+        .route(
+            "/drafts/:id",
+            put(api::drafts::update_draft).delete(api::drafts::delete_draft),
+        )
+        .route("/admin/backup", post(api::admin::create_backup))
+        .route(
+            "/admin/users/:id/badges/:badge",
+            put(api::admin::grant_badge).delete(api::admin::revoke_badge),
+        )
+        .route("/admin/moderation", get(api::admin::moderation_queue))
+        .route(
+            "/admin/moderation/:post_id",
+            delete(api::admin::remove_moderated_post),
+        )
+        .route(
+            "/admin/moderation/:post_id/approve",
+            post(api::admin::approve_moderated_post),
+        )
+        .route("/admin/profile-cache", get(api::admin::profile_cache_stats))
+        .route("/admin/audit", get(api::admin::audit_log))
+        .route("/admin/sessions", get(api::admin::session_stats))
+        .route("/admin/sessions/cleanup", post(api::admin::force_session_cleanup))
+        // User routes
+        .route("/users/search", get(api::friends::search_users))
+        .route("/users/:id/profile-view", get(api::friends::get_user_profile))
+        .route("/users/:id/follow", post(api::friends::follow_user).delete(api::friends::unfollow_user))
+        // Social routes
+        .route("/social/following", get(api::friends::get_following_list))
+        .route("/social/followers", get(api::friends::get_followers_list))
+        .route("/social/mutual", get(api::friends::get_mutual_friends_list))
+        .route("/social/github-suggestions", get(api::friends::get_github_suggestions))
+        .route(
+            "/social/github-suggestions/follow-all",
+            post(api::friends::follow_github_suggestions),
+        )
+        .route(
+            "/social/github-suggestions/dismiss",
+            post(api::friends::dismiss_github_suggestions),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn_with_state(
+            rate_limit_state,
+            rate_limit::rate_limit_middleware,
+        ))
+        .layer(axum::Extension(rate_limiter))
+        // Runs before rate limiting so cookie sessions are limited per token too
+        .layer(middleware::from_fn(cookie_auth::cookie_auth_middleware))
+}
+
+async fn health_check() -> &'static str {
+    "OK"
+}
@@ -0,0 +1,361 @@
+/// Content sanitization and validation for user-written text
+///
+/// Everything users write ends up rendered in other people's terminals, so
+/// before storage we drop control characters and terminal escape sequences
+/// (which could move the cursor, retitle the window or hide text), remove
+/// bidi overrides that can disguise what a post says, normalize to NFC and
+/// then check the length in characters.
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+use unicode_normalization::UnicodeNormalization;
+
+pub const MAX_POST_CHARS: usize = 280;
+/// Text published as a gist because it doesn't fit in a post
+pub const MAX_LONG_POST_CHARS: usize = 20_000;
+pub const MAX_MESSAGE_CHARS: usize = 1000;
+pub const MAX_BIO_CHARS: usize = 160;
+pub const MAX_STATUS_CHARS: usize = 80;
+pub const MAX_STATUS_EMOJI_CHARS: usize = 16;
+/// Drafts keep emoji shortcodes unexpanded, so they get more room than a post
+pub const MAX_DRAFT_CHARS: usize = 1000;
+/// Largest text or code file that can be attached to a DM, in bytes
+pub const MAX_ATTACHMENT_BYTES: usize = 16 * 1024;
+pub const MAX_ATTACHMENT_NAME_CHARS: usize = 100;
+pub const MAX_CHANNEL_DESCRIPTION_CHARS: usize = 140;
+pub const MAX_EVENT_TITLE_CHARS: usize = 100;
+/// Room for a street address or a long meeting URL
+pub const MAX_EVENT_LOCATION_CHARS: usize = 200;
+pub const MAX_REVIEW_URL_CHARS: usize = 300;
+pub const MAX_JOB_TITLE_CHARS: usize = 100;
+pub const MAX_COMPANY_CHARS: usize = 100;
+pub const MAX_JOB_URL_CHARS: usize = 300;
+
+/// Extensions accepted for DM attachments: plain text, config and source
+const ATTACHMENT_EXTENSIONS: &[&str] = &[
+    "txt", "md", "log", "csv", "json", "toml", "yaml", "yml", "xml", "ini", "conf", "diff",
+    "patch", "rs", "py", "js", "ts", "jsx", "tsx", "go", "c", "h", "cpp", "hpp", "cs", "java",
+    "kt", "swift", "rb", "php", "lua", "sh", "bash", "zsh", "fish", "sql", "html", "css",
+    "scss", "ex", "exs", "hs", "ml", "zig", "nix",
+];
+/// Extensionless files that are plain text by convention
+const ATTACHMENT_NAMES: &[&str] = &["Makefile", "Dockerfile", "Justfile", "LICENSE", "README"];
+
+/// What a piece of content is, which decides its limits and error wording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    Post,
+    Reply,
+    LongPost,
+    Message,
+    Bio,
+    Status,
+    StatusEmoji,
+    Draft,
+    Attachment,
+    AttachmentName,
+    ChannelDescription,
+    EventTitle,
+    EventLocation,
+    ReviewUrl,
+    JobTitle,
+    Company,
+    JobUrl,
+}
+
+impl ContentKind {
+    pub fn max_chars(self) -> usize {
+        match self {
+            ContentKind::Post | ContentKind::Reply => MAX_POST_CHARS,
+            ContentKind::LongPost => MAX_LONG_POST_CHARS,
+            ContentKind::Message => MAX_MESSAGE_CHARS,
+            ContentKind::Bio => MAX_BIO_CHARS,
+            ContentKind::Status => MAX_STATUS_CHARS,
+            ContentKind::StatusEmoji => MAX_STATUS_EMOJI_CHARS,
+            ContentKind::Draft => MAX_DRAFT_CHARS,
+            // Attachments are limited by size in bytes, which caps characters too
+            ContentKind::Attachment => MAX_ATTACHMENT_BYTES,
+            ContentKind::AttachmentName => MAX_ATTACHMENT_NAME_CHARS,
+            ContentKind::ChannelDescription => MAX_CHANNEL_DESCRIPTION_CHARS,
+            ContentKind::EventTitle => MAX_EVENT_TITLE_CHARS,
+            ContentKind::EventLocation => MAX_EVENT_LOCATION_CHARS,
+            ContentKind::ReviewUrl => MAX_REVIEW_URL_CHARS,
+            ContentKind::JobTitle => MAX_JOB_TITLE_CHARS,
+            ContentKind::Company => MAX_COMPANY_CHARS,
+            ContentKind::JobUrl => MAX_JOB_URL_CHARS,
+        }
+    }
+
+    /// A bio may be cleared and a channel go undescribed; everything else
+    /// needs some text
+    fn allows_empty(self) -> bool {
+        matches!(self, ContentKind::Bio | ContentKind::ChannelDescription)
+    }
+}
+
+impl fmt::Display for ContentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ContentKind::Post => "Post content",
+            ContentKind::Reply => "Reply content",
+            ContentKind::LongPost => "Long post",
+            ContentKind::Message => "Message content",
+            ContentKind::Bio => "Bio",
+            ContentKind::Status => "Status",
+            ContentKind::StatusEmoji => "Status emoji",
+            ContentKind::Draft => "Draft",
+            ContentKind::Attachment => "Attachment",
+            ContentKind::AttachmentName => "Attachment name",
+            ContentKind::ChannelDescription => "Channel description",
+            ContentKind::EventTitle => "Event title",
+            ContentKind::EventLocation => "Event location",
+            ContentKind::ReviewUrl => "Review URL",
+            ContentKind::JobTitle => "Job title",
+            ContentKind::Company => "Company",
+            ContentKind::JobUrl => "Job link",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Why content was rejected
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ContentError {
+    #[error("{0} cannot be empty")]
+    Empty(ContentKind),
+    #[error("{kind} exceeds {max} character limit (current: {actual})")]
+    TooLong {
+        kind: ContentKind,
+        max: usize,
+        actual: usize,
+    },
+    #[error("Only text and code files can be attached, not '{0}'")]
+    UnsupportedAttachment(String),
+    #[error("Attachment exceeds {max} bytes (current: {actual})")]
+    AttachmentTooLarge { max: usize, actual: usize },
+    #[error("{0} must be an http(s) link")]
+    NotALink(ContentKind),
+}
+
+/// Clean `input` and check it against the limits for `kind`, returning the
+/// text to store
+pub fn sanitize_content(kind: ContentKind, input: &str) -> Result<String, ContentError> {
+    let cleaned: String = strip_control_sequences(input).nfc().collect();
+    let cleaned = cleaned.trim().to_string();
+
+    if cleaned.is_empty() && !kind.allows_empty() {
+        return Err(ContentError::Empty(kind));
+    }
+    let actual = cleaned.chars().count();
+    if actual > kind.max_chars() {
+        return Err(ContentError::TooLong {
+            kind,
+            max: kind.max_chars(),
+            actual,
+        });
+    }
+
+    Ok(cleaned)
+}
+
+/// Clean a link and check that it's a web address, so it's safe to offer
+/// for opening in a browser
+pub fn sanitize_link(kind: ContentKind, input: &str) -> Result<String, ContentError> {
+    let link = sanitize_content(kind, input)?;
+    match reqwest::Url::parse(&link) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host().is_some() => Ok(link),
+        _ => Err(ContentError::NotALink(kind)),
+    }
+}
+
+/// Clean a DM attachment, returning its name and content to store.
+///
+/// The name loses any directories and has to look like a text or code file.
+/// The content gets the same scrubbing as a message, except that tabs expand
+/// to four spaces and leading whitespace stays, so code keeps its indentation.
+pub fn sanitize_attachment(filename: &str, content: &str) -> Result<(String, String), ContentError> {
+    let base = filename.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name = sanitize_content(ContentKind::AttachmentName, base)?;
+    let allowed = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => {
+            ATTACHMENT_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+        }
+        _ => ATTACHMENT_NAMES.contains(&name.as_str()),
+    };
+    if !allowed {
+        return Err(ContentError::UnsupportedAttachment(name));
+    }
+
+    let cleaned: String = strip_control_sequences(&content.replace('\t', "    "))
+        .nfc()
+        .collect();
+    let cleaned = cleaned.trim_end().trim_start_matches('\n').to_string();
+    if cleaned.is_empty() {
+        return Err(ContentError::Empty(ContentKind::Attachment));
+    }
+    if cleaned.len() > MAX_ATTACHMENT_BYTES {
+        return Err(ContentError::AttachmentTooLarge {
+            max: MAX_ATTACHMENT_BYTES,
+            actual: cleaned.len(),
+        });
+    }
+
+    Ok((name, cleaned))
+}
+
+/// Bidi embedding, override and isolate controls
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Remove escape sequences and control characters, keeping newlines.
+/// Tabs become spaces and carriage returns become newlines so nothing can
+/// overwrite an earlier part of the line.
+fn strip_control_sequences(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.next() {
+                Some('[') => skip_csi(&mut chars),
+                Some(']' | 'P' | 'X' | '^' | '_') => skip_string(&mut chars),
+                // Two-character escapes: the second character is dropped too
+                _ => {}
+            },
+            '\u{9b}' => skip_csi(&mut chars),
+            '\u{90}' | '\u{98}' | '\u{9d}' | '\u{9e}' | '\u{9f}' => skip_string(&mut chars),
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    out.push('\n');
+                }
+            }
+            '\n' => out.push('\n'),
+            '\t' => out.push(' '),
+            c if c.is_control() || is_bidi_control(c) => {}
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Skip a control sequence's parameters up to and including its final byte
+fn skip_csi(chars: &mut Peekable<Chars>) {
+    for c in chars.by_ref() {
+        if ('\u{40}'..='\u{7e}').contains(&c) {
+            break;
+        }
+    }
+}
+
+/// Skip an OSC/DCS-style string up to its terminator (BEL, ST or ESC \)
+fn skip_string(chars: &mut Peekable<Chars>) {
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{07}' | '\u{9c}' => break,
+            '\u{1b}' => {
+                if chars.peek() == Some(&'\\') {
+                    chars.next();
+                }
+                break;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_escape_sequences_and_controls() {
+        let input = "\u{1b}[2J\u{1b}[31mred\u{1b}[0m \u{1b}]0;pwned\u{07}title\u{7}\u{0}\u{9b}1;1Hend";
+        assert_eq!(sanitize_content(ContentKind::Post, input).unwrap(), "red titleend");
+
+        let input = "line one\r\nline two\rthree\tfour";
+        assert_eq!(
+            sanitize_content(ContentKind::Post, input).unwrap(),
+            "line one\nline two\nthree four"
+        );
+
+        let input = "abc\u{202E}cba\u{2066}x";
+        assert_eq!(sanitize_content(ContentKind::Post, input).unwrap(), "abccbax");
+    }
+
+    #[test]
+    fn test_normalizes_unicode_and_counts_characters() {
+        // "e" + combining acute becomes a single "é"
+        assert_eq!(sanitize_content(ContentKind::Post, "cafe\u{301}").unwrap(), "café");
+
+        // 280 multi-byte characters fit even though they're over 280 bytes
+        let emoji = "🦀".repeat(MAX_POST_CHARS);
+        assert!(sanitize_content(ContentKind::Post, &emoji).is_ok());
+        assert_eq!(
+            sanitize_content(ContentKind::Reply, &format!("{}!", emoji)),
+            Err(ContentError::TooLong {
+                kind: ContentKind::Reply,
+                max: MAX_POST_CHARS,
+                actual: MAX_POST_CHARS + 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty_after_cleaning_is_rejected() {
+        assert_eq!(
+            sanitize_content(ContentKind::Message, " \u{1b}[1m \n"),
+            Err(ContentError::Empty(ContentKind::Message))
+        );
+        assert_eq!(
+            ContentError::Empty(ContentKind::Post).to_string(),
+            "Post content cannot be empty"
+        );
+        assert_eq!(sanitize_content(ContentKind::Bio, "").unwrap(), "");
+    }
+
+    #[test]
+    fn test_links_must_be_web_addresses() {
+        assert_eq!(
+            sanitize_link(ContentKind::JobUrl, " https://jobs.example.com/42 ").unwrap(),
+            "https://jobs.example.com/42"
+        );
+        assert_eq!(
+            sanitize_link(ContentKind::ReviewUrl, "git@github.com:fido/fido.git"),
+            Err(ContentError::NotALink(ContentKind::ReviewUrl))
+        );
+        assert!(sanitize_link(ContentKind::JobUrl, "javascript:alert(1)").is_err());
+        assert_eq!(
+            ContentError::NotALink(ContentKind::JobUrl).to_string(),
+            "Job link must be an http(s) link"
+        );
+    }
+
+    #[test]
+    fn test_attachment_keeps_indentation_and_checks_name_and_size() {
+        let (name, content) =
+            sanitize_attachment("../src/main.rs", "\nfn main() {\n\tprintln!(\"\u{1b}[2Jhi\");\n}\n\n")
+                .unwrap();
+        assert_eq!(name, "main.rs");
+        assert_eq!(content, "fn main() {\n    println!(\"hi\");\n}");
+        assert_eq!(sanitize_attachment("Makefile", "all:").unwrap().0, "Makefile");
+
+        assert_eq!(
+            sanitize_attachment("photo.png", "\u{89}PNG"),
+            Err(ContentError::UnsupportedAttachment("photo.png".to_string()))
+        );
+        assert!(sanitize_attachment(".env", "SECRET=1").is_err());
+        assert_eq!(
+            sanitize_attachment("notes.txt", " \n\t"),
+            Err(ContentError::Empty(ContentKind::Attachment))
+        );
+        assert_eq!(
+            sanitize_attachment("big.log", &"x".repeat(MAX_ATTACHMENT_BYTES + 1)),
+            Err(ContentError::AttachmentTooLarge {
+                max: MAX_ATTACHMENT_BYTES,
+                actual: MAX_ATTACHMENT_BYTES + 1,
+            })
+        );
+    }
+}
@@ -1,30 +1,82 @@
 use crate::db::Database;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// How long a session lasts after creation or the latest heartbeat, unless
+/// `[sessions] lifetime_days` says otherwise
+pub const SESSION_LIFETIME_DAYS: i64 = 30;
+
+/// Expired-session sweeps since the server started
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CleanupStats {
+    pub runs: u64,
+    pub sessions_removed: u64,
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Sessions the most recent sweep removed
+    pub last_removed: u64,
+}
+
+/// Live sessions held by one user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserSessionCount {
+    pub user_id: Uuid,
+    pub username: String,
+    pub sessions: u64,
+    /// When the longest-lived of them runs out
+    pub latest_expiry: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct CleanupCounters {
+    runs: AtomicU64,
+    sessions_removed: AtomicU64,
+    last_run: Mutex<Option<(DateTime<Utc>, u64)>>,
+}
+
 /// Database-backed session manager for persistent authentication
 /// 
 /// Manages user sessions with token-based authentication, including:
 /// - Session creation with UUID v4 tokens
 /// - Session validation with expiry checking
+/// - Sliding expiry via heartbeats
 /// - Session deletion (logout)
 /// - Automatic cleanup of expired sessions
 #[derive(Clone)]
 pub struct SessionManager {
     db: Database,
+    lifetime: Duration,
+    cleanup: Arc<CleanupCounters>,
 }
 
 impl SessionManager {
     /// Create a new session manager
     pub fn new(db: Database) -> Self {
-        Self { db }
+        Self {
+            db,
+            lifetime: Duration::days(SESSION_LIFETIME_DAYS),
+            cleanup: Arc::default(),
+        }
+    }
+
+    /// Let sessions last `days` after login or their latest heartbeat
+    pub fn with_lifetime_days(mut self, days: u32) -> Self {
+        self.lifetime = Duration::days(days.into());
+        self
+    }
+
+    /// How long a session lasts without a heartbeat
+    pub fn lifetime(&self) -> Duration {
+        self.lifetime
     }
 
     /// Create a new session for a user
     /// 
-    /// Generates a cryptographically secure UUID v4 token and stores it in the database
-    /// with a 30-day expiry period.
+    /// Generates a cryptographically secure UUID v4 token and stores it in the database,
+    /// expiring after the configured lifetime (30 days by default).
     /// 
     /// # Arguments
     /// * `user_id` - The UUID of the user to create a session for
@@ -34,7 +86,7 @@ impl SessionManager {
     pub fn create_session(&self, user_id: Uuid) -> Result<String> {
         let token = Uuid::new_v4().to_string();
         let created_at = Utc::now();
-        let expires_at = created_at + Duration::days(30);
+        let expires_at = created_at + self.lifetime;
         
         let conn = self.db.connection()?;
         conn.execute(
@@ -92,6 +144,31 @@ impl SessionManager {
         Ok(user_id)
     }
 
+    /// Extend a live session so it expires a full lifetime from now
+    /// 
+    /// Called by client heartbeats, so a session only lapses after a full
+    /// lifetime without use rather than a lifetime after login.
+    /// 
+    /// # Arguments
+    /// * `token` - The session token to extend
+    /// 
+    /// # Returns
+    /// * `Result<(Uuid, DateTime<Utc>)>` - The user ID and the new expiry
+    /// * `Err` - If the session is invalid or already expired
+    pub fn extend_session(&self, token: &str) -> Result<(Uuid, DateTime<Utc>)> {
+        let user_id = self.validate_session(token)?;
+        let expires_at = Utc::now() + self.lifetime;
+
+        let conn = self.db.connection()?;
+        conn.execute(
+            "UPDATE sessions SET expires_at = ?1 WHERE token = ?2",
+            rusqlite::params![expires_at.to_rfc3339(), token],
+        )
+        .context("Failed to extend session")?;
+
+        Ok((user_id, expires_at))
+    }
+
     /// Delete a session (logout)
     /// 
     /// Removes the session from the database, effectively logging out the user.
@@ -136,9 +213,60 @@ impl SessionManager {
         if rows_affected > 0 {
             tracing::info!("Cleaned up {} expired sessions", rows_affected);
         }
+
+        let removed = rows_affected as u64;
+        self.cleanup.runs.fetch_add(1, Ordering::Relaxed);
+        self.cleanup.sessions_removed.fetch_add(removed, Ordering::Relaxed);
+        *self.cleanup.last_run.lock().unwrap() = Some((Utc::now(), removed));
         
         Ok(rows_affected)
     }
+
+    /// Counts from `cleanup_expired_sessions` since startup
+    pub fn cleanup_stats(&self) -> CleanupStats {
+        let last_run = *self.cleanup.last_run.lock().unwrap();
+        CleanupStats {
+            runs: self.cleanup.runs.load(Ordering::Relaxed),
+            sessions_removed: self.cleanup.sessions_removed.load(Ordering::Relaxed),
+            last_run_at: last_run.map(|(at, _)| at),
+            last_removed: last_run.map_or(0, |(_, removed)| removed),
+        }
+    }
+
+    /// Unexpired sessions per user, most first
+    pub fn active_sessions_by_user(&self) -> Result<Vec<UserSessionCount>> {
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT s.user_id, COALESCE(u.username, ''), COUNT(*), MAX(s.expires_at)
+             FROM sessions s
+             LEFT JOIN users u ON u.id = s.user_id
+             WHERE s.expires_at >= ?1
+             GROUP BY s.user_id
+             ORDER BY COUNT(*) DESC, u.username",
+        )?;
+        let counts = stmt
+            .query_map([Utc::now().to_rfc3339()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })?
+            .filter_map(|row| row.ok())
+            .filter_map(|(user_id, username, sessions, latest_expiry)| {
+                Some(UserSessionCount {
+                    user_id: Uuid::parse_str(&user_id).ok()?,
+                    username,
+                    sessions: sessions as u64,
+                    latest_expiry: DateTime::parse_from_rfc3339(&latest_expiry)
+                        .ok()?
+                        .with_timezone(&Utc),
+                })
+            })
+            .collect();
+        Ok(counts)
+    }
 }
 
 #[cfg(test)]
@@ -239,6 +367,67 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_configured_lifetime_and_cleanup_stats() {
+        let db = setup_test_db();
+        let manager = SessionManager::new(db.clone()).with_lifetime_days(2);
+        let user_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440099").unwrap();
+        let live = manager.create_session(user_id).expect("Failed to create session");
+        let doomed = manager.create_session(user_id).expect("Failed to create session");
+
+        let (_, expires_at) = manager.extend_session(&live).expect("Failed to extend session");
+        assert!(expires_at < Utc::now() + Duration::days(2) + Duration::minutes(1));
+        assert!(expires_at > Utc::now() + Duration::days(1));
+
+        let counts = manager.active_sessions_by_user().expect("Failed to count sessions");
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].username, "testuser");
+        assert_eq!(counts[0].sessions, 2);
+
+        let conn = db.connection().expect("Failed to get connection");
+        conn.execute(
+            "UPDATE sessions SET expires_at = ?1 WHERE token = ?2",
+            rusqlite::params![(Utc::now() - Duration::hours(1)).to_rfc3339(), doomed],
+        )
+        .expect("Failed to expire session");
+        assert_eq!(manager.active_sessions_by_user().unwrap()[0].sessions, 1);
+
+        assert_eq!(manager.cleanup_stats().runs, 0);
+        manager.cleanup_expired_sessions().expect("Failed to cleanup");
+        manager.cleanup_expired_sessions().expect("Failed to cleanup");
+        let stats = manager.cleanup_stats();
+        assert_eq!(stats.runs, 2);
+        assert_eq!(stats.sessions_removed, 1);
+        assert_eq!(stats.last_removed, 0);
+        assert!(stats.last_run_at.is_some());
+    }
+
+    #[test]
+    fn test_extend_session_slides_expiry() {
+        let db = setup_test_db();
+        let manager = SessionManager::new(db.clone());
+        let user_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440099").unwrap();
+        let token = manager.create_session(user_id).expect("Failed to create session");
+
+        // Pretend the session is about to lapse
+        let conn = db.connection().expect("Failed to get connection");
+        let soon = (Utc::now() + Duration::minutes(5)).to_rfc3339();
+        conn.execute(
+            "UPDATE sessions SET expires_at = ?1 WHERE token = ?2",
+            rusqlite::params![soon, token],
+        )
+        .expect("Failed to shorten session");
+
+        let (extended_user_id, expires_at) =
+            manager.extend_session(&token).expect("Failed to extend session");
+        assert_eq!(extended_user_id, user_id);
+        assert!(expires_at > Utc::now() + Duration::days(SESSION_LIFETIME_DAYS - 1));
+
+        // Dead sessions can't be revived
+        manager.delete_session(&token).expect("Failed to delete session");
+        assert!(manager.extend_session(&token).is_err());
+    }
+
     #[test]
     fn test_session_token_uniqueness() {
         let db = setup_test_db();
@@ -1,10 +1,33 @@
+use crate::config::{AnonymousPosts, Leaderboard, Limits, Translation};
+use crate::content_filter::FilterPipeline;
+use crate::db::repositories::{ProfileCounts, UserRepository};
 use crate::db::Database;
+use crate::gist::GistCache;
+use crate::github_card::GitHubCardCache;
+use crate::profile_cache::ProfileCache;
 use crate::session::SessionManager;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: Database,
     pub session_manager: SessionManager,
+    /// Normalized `[link_previews]` blocklist, checked by `/links/preview`
+    pub blocked_domains: Vec<String>,
+    pub limits: Limits,
+    pub anonymous_posts: AnonymousPosts,
+    pub leaderboard: Leaderboard,
+    pub translation: Translation,
+    pub content_filter: FilterPipeline,
+    pub profile_cache: ProfileCache,
+    pub github_cards: GitHubCardCache,
+    pub gists: GistCache,
+    /// Where the instance is reached from outside, without a trailing slash
+    pub public_url: Option<String>,
+    /// Path cookie sessions are scoped to: `/`, or a tenant's path prefix
+    pub cookie_path: String,
+    /// A tenant's admin usernames; `None` for the main community, whose
+    /// admins come from `FIDO_ADMIN_USERS`
+    pub admins: Option<Vec<String>>,
 }
 
 impl AppState {
@@ -13,9 +36,88 @@ impl AppState {
         Self { 
             db,
             session_manager,
+            blocked_domains: Vec::new(),
+            limits: Limits::default(),
+            anonymous_posts: AnonymousPosts::default(),
+            leaderboard: Leaderboard::default(),
+            translation: Translation::default(),
+            content_filter: FilterPipeline::default(),
+            profile_cache: ProfileCache::default(),
+            github_cards: GitHubCardCache::default(),
+            gists: GistCache::default(),
+            public_url: None,
+            cookie_path: "/".to_string(),
+            admins: None,
         }
     }
+
+    /// Refuse links to `domains` and their subdomains
+    pub fn with_blocked_domains(mut self, domains: &[String]) -> Self {
+        self.blocked_domains = crate::link_preview::normalize_blocklist(domains);
+        self
+    }
+
+    /// Apply the instance's storage limits
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Allow anonymous posts, and how many
+    pub fn with_anonymous_posts(mut self, anonymous_posts: AnonymousPosts) -> Self {
+        self.anonymous_posts = anonymous_posts;
+        self
+    }
+
+    /// Turn the leaderboard, or its ranking of people, on or off
+    pub fn with_leaderboard(mut self, leaderboard: Leaderboard) -> Self {
+        self.leaderboard = leaderboard;
+        self
+    }
+
+    /// Let sessions last `days` after login or their latest heartbeat
+    pub fn with_session_lifetime_days(mut self, days: u32) -> Self {
+        self.session_manager = self.session_manager.with_lifetime_days(days);
+        self
+    }
+
+    /// Use `translation` as the backend for `/posts/:id/translate`
+    pub fn with_translation(mut self, translation: Translation) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Build absolute links (sitemap, robots.txt) on `url`
+    pub fn with_public_url(mut self, url: Option<String>) -> Self {
+        self.public_url = url.map(|url| url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Scope cookie sessions to `path`, where a tenant is mounted
+    pub fn with_cookie_path(mut self, path: &str) -> Self {
+        self.cookie_path = path.to_string();
+        self
+    }
+
+    /// Only let `admins` administer this community, as for a tenant
+    pub fn with_admins(mut self, admins: Vec<String>) -> Self {
+        self.admins = Some(admins);
+        self
+    }
+
+    /// Run new posts, replies and edits through `content_filter`
+    pub fn with_content_filter(mut self, content_filter: FilterPipeline) -> Self {
+        self.content_filter = content_filter;
+        self
+    }
     
+    /// Follower, following and post counts for `user_id`, through the cache
+    pub fn profile_counts(&self, user_id: &uuid::Uuid) -> anyhow::Result<Option<ProfileCounts>> {
+        self.profile_cache.counts(user_id, || {
+            UserRepository::new(self.db.pool.clone()).get_counts(user_id)
+        })
+    }
+
     /// Get authenticated user ID from session token
     pub fn get_authenticated_user_id_from_token(&self, token: &str) -> Option<uuid::Uuid> {
         self.session_manager.validate_session(token).ok()
@@ -0,0 +1,148 @@
+//! Profile writing statistics, worked out from when and what a user posted
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::hashtag::extract_hashtags;
+use fido_types::{HashtagUse, UserStats};
+
+/// Weeks covered by the posts-per-week sparkline
+pub const STATS_WEEKS: usize = 12;
+
+/// Hashtags listed as most used
+pub const TOP_HASHTAGS: usize = 5;
+
+/// Statistics over `history`, the (created at, content) of each post and
+/// reply, with the votes the user received
+pub fn compute_stats(
+    history: &[(DateTime<Utc>, String)],
+    (upvotes, downvotes): (u32, u32),
+    now: DateTime<Utc>,
+) -> UserStats {
+    let days: BTreeSet<NaiveDate> = history.iter().map(|(at, _)| at.date_naive()).collect();
+    let (current_streak, longest_streak) = streaks(&days, now.date_naive());
+
+    UserStats {
+        posts_per_week: posts_per_week(history.iter().map(|(at, _)| *at), now),
+        current_streak,
+        longest_streak,
+        top_hashtags: top_hashtags(history.iter().map(|(_, content)| content.as_str())),
+        upvotes_received: upvotes,
+        downvotes_received: downvotes,
+        total_posts: history.len() as u32,
+        total_words: history
+            .iter()
+            .map(|(_, content)| content.split_whitespace().count() as u32)
+            .sum(),
+        total_characters: history
+            .iter()
+            .map(|(_, content)| content.chars().count() as u32)
+            .sum(),
+    }
+}
+
+/// Counts per rolling 7-day window over the last `STATS_WEEKS` weeks,
+/// oldest first
+fn posts_per_week(times: impl Iterator<Item = DateTime<Utc>>, now: DateTime<Utc>) -> Vec<u32> {
+    let mut weeks = vec![0; STATS_WEEKS];
+    for at in times {
+        let age = now - at;
+        if age < Duration::zero() {
+            continue;
+        }
+        let weeks_ago = (age.num_seconds() / Duration::weeks(1).num_seconds()) as usize;
+        if weeks_ago < STATS_WEEKS {
+            weeks[STATS_WEEKS - 1 - weeks_ago] += 1;
+        }
+    }
+    weeks
+}
+
+/// Current and longest runs of consecutive days in `days`. The current run
+/// still counts if it ended yesterday, since today isn't over yet.
+fn streaks(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> (u32, u32) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        run = match previous {
+            Some(previous) if previous.succ_opt() == Some(*day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+
+    let yesterday = today.pred_opt();
+    let current = match previous {
+        Some(last) if last == today || Some(last) == yesterday => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+/// The most used hashtags, counting each post once, ties alphabetically
+fn top_hashtags<'a>(contents: impl Iterator<Item = &'a str>) -> Vec<HashtagUse> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for content in contents {
+        for tag in extract_hashtags(content) {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+    let mut top: Vec<HashtagUse> = counts
+        .into_iter()
+        .map(|(name, count)| HashtagUse { name, count })
+        .collect();
+    top.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    top.truncate(TOP_HASHTAGS);
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_streaks_run_to_today_or_yesterday() {
+        let days: BTreeSet<NaiveDate> = [1, 2, 3, 7, 8]
+            .iter()
+            .map(|day| at(*day, 12).date_naive())
+            .collect();
+        assert_eq!(streaks(&days, at(8, 0).date_naive()), (2, 3));
+        assert_eq!(streaks(&days, at(9, 0).date_naive()), (2, 3));
+        assert_eq!(streaks(&days, at(10, 0).date_naive()), (0, 3));
+        assert_eq!(streaks(&BTreeSet::new(), at(10, 0).date_naive()), (0, 0));
+    }
+
+    #[test]
+    fn test_stats_count_weeks_words_and_hashtags() {
+        let now = at(29, 12);
+        let history = vec![
+            (at(1, 9), "Old news #rust".to_string()),
+            (at(27, 9), "Shipping the #rust parser today #release".to_string()),
+            (at(28, 9), "#Rust again".to_string()),
+            (at(29, 9), "Thanks all".to_string()),
+        ];
+        let stats = compute_stats(&history, (7, 1), now);
+
+        assert_eq!(stats.posts_per_week.len(), STATS_WEEKS);
+        assert_eq!(stats.posts_per_week[STATS_WEEKS - 1], 3);
+        assert_eq!(stats.posts_per_week[STATS_WEEKS - 5], 1);
+        assert_eq!((stats.current_streak, stats.longest_streak), (3, 3));
+        assert_eq!(
+            stats.top_hashtags,
+            vec![
+                HashtagUse { name: "rust".to_string(), count: 3 },
+                HashtagUse { name: "release".to_string(), count: 1 },
+            ]
+        );
+        assert_eq!((stats.upvotes_received, stats.downvotes_received), (7, 1));
+        assert_eq!(stats.total_posts, 4);
+        assert_eq!(stats.total_words, 3 + 6 + 2 + 2);
+        assert_eq!(stats.total_characters, 75);
+    }
+}
@@ -0,0 +1,109 @@
+//! Several communities in one server process.
+//!
+//! Each tenant from `[[tenants]]` gets its own database, `AppState` and API
+//! router, so posts, users and sessions never cross between them. Tenants
+//! with a `path_prefix` are nested under it; tenants with `hosts` are picked
+//! by the middleware below before the default instance's routes run.
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Routers of the tenants reached by hostname
+#[derive(Clone, Default)]
+pub struct HostTenants(Arc<HashMap<String, Router>>);
+
+impl HostTenants {
+    pub fn new(routers: HashMap<String, Router>) -> Self {
+        Self(Arc::new(
+            routers
+                .into_iter()
+                .map(|(host, router)| (normalize_host(&host), router))
+                .collect(),
+        ))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The router serving `host`, as sent in a `Host` header
+    fn router_for(&self, host: &str) -> Option<&Router> {
+        self.0.get(&normalize_host(host))
+    }
+}
+
+/// Lowercase hostname without a port or trailing dot
+pub fn normalize_host(host: &str) -> String {
+    let host = host.trim();
+    let host = match host.rsplit_once(':') {
+        // Leave IPv6 literals like [::1] alone unless a port follows the bracket
+        Some((name, port)) if port.bytes().all(|b| b.is_ascii_digit()) && !name.ends_with(':') => {
+            name
+        }
+        _ => host,
+    };
+    host.trim_end_matches('.').to_lowercase()
+}
+
+/// Hand requests for a tenant's hostname to that tenant's router
+pub async fn host_dispatch(
+    State(tenants): State<HostTenants>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let router = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .and_then(|host| tenants.router_for(host))
+        .cloned();
+    match router {
+        Some(router) => match router.oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        },
+        None => next.run(request).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get};
+
+    #[test]
+    fn test_normalize_host() {
+        assert_eq!(normalize_host("Rust.Fido.example:8443"), "rust.fido.example");
+        assert_eq!(normalize_host("rust.fido.example."), "rust.fido.example");
+        assert_eq!(normalize_host("[::1]:3000"), "[::1]");
+        assert_eq!(normalize_host("[::1]"), "[::1]");
+    }
+
+    #[tokio::test]
+    async fn test_host_dispatch_picks_the_tenant_router() {
+        let tenant = Router::new().route("/health", get(|| async { "tenant" }));
+        let tenants = HostTenants::new(HashMap::from([("rust.fido.example".to_string(), tenant)]));
+        let app = Router::new()
+            .route("/health", get(|| async { "default" }))
+            .layer(middleware::from_fn_with_state(tenants, host_dispatch));
+
+        for (host, expected) in [("rust.fido.example:3000", "tenant"), ("fido.example", "default")] {
+            let request = Request::builder()
+                .uri("/health")
+                .header(header::HOST, host)
+                .body(Body::empty())
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            let body = axum::body::to_bytes(response.into_body(), 1024).await.unwrap();
+            assert_eq!(body, expected);
+        }
+    }
+}
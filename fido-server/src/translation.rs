@@ -0,0 +1,143 @@
+/// Post translation through the instance's LibreTranslate-compatible service
+///
+/// Off unless `[translation] url` (or `FIDO_TRANSLATE_URL`) is set. Posts are
+/// sent as plain text with the source language left for the service to
+/// detect.
+use crate::config::Translation;
+use reqwest::Url;
+use serde::Deserialize;
+use std::time::Duration;
+
+const TRANSLATE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why a post couldn't be translated
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TranslateError {
+    #[error("Translation is not enabled on this server")]
+    Disabled,
+    #[error("Not a valid language code")]
+    InvalidLanguage,
+    #[error("Translation service error: {0}")]
+    Backend(String),
+}
+
+/// A translated text and what the service detected it was written in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translated {
+    pub text: String,
+    pub source_language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+    #[serde(rename = "detectedLanguage")]
+    detected_language: Option<DetectedLanguage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DetectedLanguage {
+    language: String,
+}
+
+/// Normalize a language code like `pt-BR` or `EN`; `None` if it isn't one
+pub fn normalize_language(code: &str) -> Option<String> {
+    let code = code.trim();
+    let valid = (2..=8).contains(&code.len())
+        && code.chars().all(|c| c.is_ascii_alphabetic() || c == '-')
+        && code.chars().next().is_some_and(|c| c.is_ascii_alphabetic());
+    valid.then(|| code.to_lowercase())
+}
+
+/// Name shown in the attribution: the service's host, or the URL as set
+pub fn provider_name(config: &Translation) -> String {
+    Url::parse(config.url.trim())
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| config.url.trim().to_string())
+}
+
+fn endpoint(config: &Translation) -> Result<Url, TranslateError> {
+    let base = config.url.trim().trim_end_matches('/');
+    Url::parse(&format!("{}/translate", base))
+        .map_err(|_| TranslateError::Backend("translation URL is invalid".to_string()))
+}
+
+/// Translate `text` into `target` with the configured service
+pub async fn translate(
+    config: &Translation,
+    text: &str,
+    target: &str,
+) -> Result<Translated, TranslateError> {
+    if !config.enabled() {
+        return Err(TranslateError::Disabled);
+    }
+    let target = normalize_language(target).ok_or(TranslateError::InvalidLanguage)?;
+
+    let mut body = serde_json::json!({
+        "q": text,
+        "source": "auto",
+        "target": target,
+        "format": "text",
+    });
+    if !config.api_key.is_empty() {
+        body["api_key"] = serde_json::Value::String(config.api_key.clone());
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(TRANSLATE_TIMEOUT)
+        .build()
+        .map_err(|e| TranslateError::Backend(e.to_string()))?;
+    let response = client
+        .post(endpoint(config)?)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| TranslateError::Backend(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(TranslateError::Backend(format!(
+            "service returned {}",
+            response.status()
+        )));
+    }
+    let response: TranslateResponse = response
+        .json()
+        .await
+        .map_err(|e| TranslateError::Backend(e.to_string()))?;
+
+    Ok(Translated {
+        text: response.translated_text,
+        source_language: response.detected_language.map(|detected| detected.language),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_codes_are_normalized_or_refused() {
+        assert_eq!(normalize_language(" EN "), Some("en".to_string()));
+        assert_eq!(normalize_language("pt-BR"), Some("pt-br".to_string()));
+        assert_eq!(normalize_language("e"), None);
+        assert_eq!(normalize_language("en&q=x"), None);
+        assert_eq!(normalize_language("-en"), None);
+    }
+
+    #[test]
+    fn test_provider_is_the_service_host() {
+        let config = Translation {
+            url: "https://translate.example.org/".to_string(),
+            api_key: String::new(),
+        };
+        assert_eq!(provider_name(&config), "translate.example.org");
+        assert_eq!(endpoint(&config).unwrap().as_str(), "https://translate.example.org/translate");
+    }
+
+    #[tokio::test]
+    async fn test_translate_refuses_when_disabled() {
+        let result = translate(&Translation::default(), "hola", "en").await;
+        assert_eq!(result, Err(TranslateError::Disabled));
+    }
+}
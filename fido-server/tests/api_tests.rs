@@ -0,0 +1,199 @@
+// Integration tests for the API: authentication, who may do what, and
+// pagination, through the full router
+
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+use fido_types::{LoginResponse, Post};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_login_issues_a_session_that_logout_ends() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+
+    let login: LoginResponse = app
+        .request(Method::POST, "/auth/login")
+        .json(&json!({ "username": "alice" }))
+        .send()
+        .await
+        .expect_status(StatusCode::OK)
+        .json();
+    assert_eq!(login.user.id, alice.id);
+
+    let validated = app
+        .get("/auth/validate")
+        .token(&login.session_token)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    assert_eq!(validated.body["user"]["username"], "alice");
+
+    app.request(Method::POST, "/auth/logout")
+        .json(&login.session_token)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    app.get("/auth/validate")
+        .token(&login.session_token)
+        .send()
+        .await
+        .expect_status(StatusCode::UNAUTHORIZED);
+
+    // Other sessions of the same user stay signed in
+    app.get("/auth/validate")
+        .signed_in_as(&alice)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_login_rejects_unknown_users() {
+    let app = TestApp::new();
+
+    app.request(Method::POST, "/auth/login")
+        .json(&json!({ "username": "nobody" }))
+        .send()
+        .await
+        .expect_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_writing_needs_a_valid_session() {
+    let app = TestApp::new();
+    let post = json!({ "content": "hello" });
+
+    app.request(Method::POST, "/posts")
+        .json(&post)
+        .send()
+        .await
+        .expect_status(StatusCode::UNAUTHORIZED);
+    app.request(Method::POST, "/posts")
+        .token("not-a-session")
+        .json(&post)
+        .send()
+        .await
+        .expect_status(StatusCode::UNAUTHORIZED);
+
+    // Reading doesn't
+    app.get("/posts").send().await.expect_status(StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_posting_again_too_soon_is_rate_limited() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+    let post = json!({ "content": "hello" });
+
+    for expected in [StatusCode::OK, StatusCode::TOO_MANY_REQUESTS] {
+        app.request(Method::POST, "/posts")
+            .signed_in_as(&alice)
+            .json(&post)
+            .send()
+            .await
+            .expect_status(expected);
+    }
+}
+
+#[tokio::test]
+async fn test_only_the_author_can_edit_or_delete_a_post() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+    let bob = app.user("bob");
+    let post = app.post(&alice, "my first post").await;
+    let path = format!("/posts/{}", post.id);
+
+    app.request(Method::PUT, &path)
+        .signed_in_as(&bob)
+        .json(&json!({ "content": "bob was here" }))
+        .send()
+        .await
+        .expect_status(StatusCode::FORBIDDEN);
+    app.request(Method::DELETE, &path)
+        .signed_in_as(&bob)
+        .send()
+        .await
+        .expect_status(StatusCode::FORBIDDEN);
+    let unchanged: Post = app.get(&path).send().await.expect_status(StatusCode::OK).json();
+    assert_eq!(unchanged.content, "my first post");
+
+    app.request(Method::DELETE, &path)
+        .signed_in_as(&alice)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    app.get(&path).send().await.expect_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_admin_routes_turn_away_other_users() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+
+    app.get("/admin/audit")
+        .send()
+        .await
+        .expect_status(StatusCode::UNAUTHORIZED);
+    app.get("/admin/audit")
+        .signed_in_as(&alice)
+        .send()
+        .await
+        .expect_status(StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_user_posts_come_in_pages_without_overlap() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+    let bob = app.user("bob");
+    let mut published = HashSet::new();
+    for i in 0..5 {
+        published.insert(app.post(&alice, &format!("post number {}", i)).await.id);
+    }
+    app.post(&bob, "not one of alice's").await;
+
+    let mut seen = HashSet::new();
+    for (offset, expected) in [(0, 2), (2, 2), (4, 1), (6, 0)] {
+        let page: Vec<Post> = app
+            .get(&format!("/users/{}/posts?limit=2&offset={}", alice.id, offset))
+            .send()
+            .await
+            .expect_status(StatusCode::OK)
+            .json();
+        assert_eq!(page.len(), expected, "page at offset {}", offset);
+        for post in page {
+            assert!(seen.insert(post.id), "{} was on an earlier page", post.id);
+        }
+    }
+    assert_eq!(seen, published);
+
+    app.get(&format!("/users/{}/posts?limit=0", alice.id))
+        .send()
+        .await
+        .expect_status(StatusCode::BAD_REQUEST);
+    app.get(&format!("/users/{}/posts?offset=-1", alice.id))
+        .send()
+        .await
+        .expect_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_feed_limit_caps_the_posts_returned() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+    for i in 0..4 {
+        app.post(&alice, &format!("post number {}", i)).await;
+    }
+
+    let posts: Vec<Post> = app
+        .get("/posts?limit=3")
+        .send()
+        .await
+        .expect_status(StatusCode::OK)
+        .json();
+    assert_eq!(posts.len(), 3);
+}
@@ -0,0 +1,209 @@
+//! An in-process Fido server for integration tests: the full API router
+//! over a fresh in-memory database, with builders for the users, sessions
+//! and posts a test starts from. Requests go straight to the router, so no
+//! port is bound and tests run in parallel.
+
+// Each test file compiles its own copy and uses only some of it
+#![allow(dead_code)]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Method, Request, StatusCode},
+    Router,
+};
+use chrono::Utc;
+use fido_server::{
+    config::Settings,
+    db::{repositories::UserRepository, Database},
+    router::api_router,
+    state::AppState,
+};
+use fido_types::{Post, User};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// A server with its own empty database
+pub struct TestApp {
+    pub state: AppState,
+    router: Router,
+}
+
+/// A user created by [`TestApp::user`], signed in with a session of its own
+#[derive(Debug, Clone)]
+pub struct TestUser {
+    pub id: Uuid,
+    pub username: String,
+    pub token: String,
+}
+
+impl TestApp {
+    /// A server with the default settings
+    pub fn new() -> Self {
+        Self::with_state(|state| state)
+    }
+
+    /// A server whose state `configure` adjusts first, e.g. to set limits
+    pub fn with_state(configure: impl FnOnce(AppState) -> AppState) -> Self {
+        // Named and shared, so every pooled connection opens the same
+        // database rather than an empty one of its own
+        let path = format!("file:fido-test-{}?mode=memory&cache=shared", Uuid::new_v4());
+        let db = Database::new(path).expect("Failed to create test database");
+        db.initialize().expect("Failed to initialize test database");
+
+        let state = configure(AppState::new(db));
+        let router = api_router(state.clone(), &Settings::default());
+        Self { state, router }
+    }
+
+    /// Create a test user, who may also sign in through `/auth/login`, and
+    /// open a session for them
+    pub fn user(&self, username: &str) -> TestUser {
+        let user = User {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            bio: None,
+            join_date: Utc::now(),
+            is_test_user: true,
+            badges: Vec::new(),
+            karma: 0,
+        };
+        UserRepository::new(self.state.db.pool.clone())
+            .create(&user)
+            .expect("Failed to create test user");
+        TestUser {
+            id: user.id,
+            username: user.username,
+            token: self.session(user.id),
+        }
+    }
+
+    /// Open another session for `user_id`, returning its token
+    pub fn session(&self, user_id: Uuid) -> String {
+        self.state
+            .session_manager
+            .create_session(user_id)
+            .expect("Failed to create test session")
+    }
+
+    /// Publish a post as `author` through `POST /posts`, then lift the
+    /// cooldown between posts so the test can publish the next one
+    pub async fn post(&self, author: &TestUser, content: &str) -> Post {
+        let post = self
+            .request(Method::POST, "/posts")
+            .signed_in_as(author)
+            .json(&serde_json::json!({ "content": content }))
+            .send()
+            .await
+            .expect_status(StatusCode::OK)
+            .json();
+        self.state
+            .db
+            .connection()
+            .expect("Failed to get connection")
+            .execute(
+                "DELETE FROM post_rate_limits WHERE user_id = ?",
+                [author.id.to_string()],
+            )
+            .expect("Failed to lift the post cooldown");
+        post
+    }
+
+    pub fn get(&self, path: &str) -> TestRequest<'_> {
+        self.request(Method::GET, path)
+    }
+
+    pub fn request(&self, method: Method, path: &str) -> TestRequest<'_> {
+        TestRequest {
+            app: self,
+            method,
+            path: path.to_string(),
+            token: None,
+            body: None,
+        }
+    }
+}
+
+/// A request being put together; nothing is sent until [`TestRequest::send`]
+pub struct TestRequest<'a> {
+    app: &'a TestApp,
+    method: Method,
+    path: String,
+    token: Option<String>,
+    body: Option<Value>,
+}
+
+impl TestRequest<'_> {
+    /// Send `user`'s session token
+    pub fn signed_in_as(self, user: &TestUser) -> Self {
+        self.token(&user.token)
+    }
+
+    /// Send `token` as the session token, valid or not
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    pub fn json(mut self, body: &impl Serialize) -> Self {
+        self.body = Some(serde_json::to_value(body).expect("Failed to serialize request body"));
+        self
+    }
+
+    pub async fn send(self) -> TestResponse {
+        let mut request = Request::builder().method(self.method).uri(&self.path);
+        if let Some(token) = &self.token {
+            request = request.header("X-Session-Token", token);
+        }
+        let body = match &self.body {
+            Some(body) => {
+                request = request.header("Content-Type", "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+        let request = request.body(body).expect("Failed to build request");
+
+        let response = self
+            .app
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("The router never fails");
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read response body");
+        TestResponse {
+            status,
+            body: serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned())),
+        }
+    }
+}
+
+/// A response, its body parsed as JSON where it is JSON and kept as a
+/// string otherwise
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+impl TestResponse {
+    /// Fail the test, showing the body, unless the status is `status`
+    #[track_caller]
+    pub fn expect_status(self, status: StatusCode) -> Self {
+        assert_eq!(self.status, status, "unexpected response: {}", self.body);
+        self
+    }
+
+    /// The body as `T`
+    #[track_caller]
+    pub fn json<T: DeserializeOwned>(&self) -> T {
+        serde_json::from_value(self.body.clone())
+            .unwrap_or_else(|e| panic!("Unexpected response body {}: {}", self.body, e))
+    }
+}
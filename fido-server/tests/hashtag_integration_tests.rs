@@ -1,111 +1,179 @@
 // Integration tests for hashtag system
 // These tests verify the full flow of hashtag functionality
 
-#[cfg(test)]
-mod integration_tests {
-
-    // Note: These are placeholder integration tests
-    // Full integration tests would require:
-    // 1. Starting the server
-    // 2. Making HTTP requests
-    // 3. Verifying responses
-    
-    // For now, we document the test scenarios that should be covered:
-    
-    /// Test Scenario: Post creation with hashtag extraction
-    /// 1. Create a post with hashtags in content
-    /// 2. Verify hashtags are extracted and stored
-    /// 3. Verify post-hashtag relationships are created
-    /// 4. Verify user activity is tracked
-    #[test]
-    fn test_post_creation_with_hashtags() {
-        // This would require:
-        // - POST /posts with content containing #rust #programming
-        // - GET /posts/:id to verify hashtags are included
-        // - Verify database has hashtag entries
-        assert!(true, "Integration test placeholder");
-    }
+mod common;
 
-    /// Test Scenario: Follow/unfollow flow
-    /// 1. Follow a hashtag
-    /// 2. Verify it appears in followed list
-    /// 3. Unfollow the hashtag
-    /// 4. Verify it's removed from followed list
-    #[test]
-    fn test_follow_unfollow_flow() {
-        // This would require:
-        // - POST /hashtags/follow with hashtag name
-        // - GET /hashtags/followed to verify
-        // - DELETE /hashtags/follow/:name
-        // - GET /hashtags/followed to verify removal
-        assert!(true, "Integration test placeholder");
-    }
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+use fido_types::Post;
+use serde_json::{json, Value};
 
-    /// Test Scenario: Filtered post retrieval
-    /// 1. Create posts with different hashtags
-    /// 2. Filter by specific hashtag
-    /// 3. Verify only posts with that hashtag are returned
-    /// 4. Test different sort orders (newest, top, hot)
-    #[test]
-    fn test_filtered_post_retrieval() {
-        // This would require:
-        // - POST /posts multiple times with different hashtags
-        // - GET /posts?hashtag=rust&sort=newest
-        // - Verify filtered results
-        // - Test with sort=top and sort=hot
-        assert!(true, "Integration test placeholder");
-    }
+/// Names in a list of hashtag responses
+fn names(hashtags: &Value) -> Vec<&str> {
+    hashtags
+        .as_array()
+        .expect("a list of hashtags")
+        .iter()
+        .map(|hashtag| hashtag["name"].as_str().unwrap())
+        .collect()
+}
 
-    /// Test Scenario: Hashtag search
-    /// 1. Create multiple hashtags
-    /// 2. Search with partial match
-    /// 3. Verify results are sorted by post count
-    /// 4. Verify case-insensitive search
-    #[test]
-    fn test_hashtag_search() {
-        // This would require:
-        // - Create hashtags via posts or follows
-        // - GET /hashtags/search?q=rust
-        // - Verify results include rust, rustlang, etc.
-        // - Verify sorting by post count
-        assert!(true, "Integration test placeholder");
-    }
+/// Test Scenario: Post creation with hashtag extraction
+/// 1. Create a post with hashtags in content
+/// 2. Verify hashtags are extracted and returned with the post
+#[tokio::test]
+async fn test_post_creation_with_hashtags() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
 
-    /// Test Scenario: Activity tracking
-    /// 1. Create post with hashtags (increments activity)
-    /// 2. Vote on post with hashtags (increments activity)
-    /// 3. View filtered posts (updates timestamp)
-    /// 4. Verify activity metrics on profile
-    #[test]
-    fn test_activity_tracking() {
-        // This would require:
-        // - POST /posts with hashtags
-        // - POST /posts/:id/vote
-        // - GET /posts?hashtag=rust
-        // - GET /users/:id/profile
-        // - Verify recent_hashtags includes tracked hashtags
-        assert!(true, "Integration test placeholder");
-    }
+    let post = app.post(&alice, "Learning #rust and #programming today").await;
+    let mut hashtags = post.hashtags.clone();
+    hashtags.sort();
+    assert_eq!(hashtags, vec!["programming", "rust"]);
+
+    let fetched: Post = app
+        .get(&format!("/posts/{}", post.id))
+        .send()
+        .await
+        .expect_status(StatusCode::OK)
+        .json();
+    assert_eq!(fetched.hashtags.len(), 2);
+}
+
+/// Test Scenario: Follow/unfollow flow
+/// 1. Follow a hashtag
+/// 2. Verify it appears in followed list
+/// 3. Unfollow the hashtag
+/// 4. Verify it's removed from followed list
+#[tokio::test]
+async fn test_follow_unfollow_flow() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+
+    app.request(Method::POST, "/hashtags/follow")
+        .signed_in_as(&alice)
+        .json(&json!({ "name": "rust" }))
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    let followed = app
+        .get("/hashtags/followed")
+        .signed_in_as(&alice)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    assert_eq!(names(&followed.body), vec!["rust"]);
+
+    app.request(Method::DELETE, "/hashtags/follow/rust")
+        .signed_in_as(&alice)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    let followed = app
+        .get("/hashtags/followed")
+        .signed_in_as(&alice)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    assert!(names(&followed.body).is_empty());
 
-    /// Test Scenario: Sort preference persistence
-    /// 1. Change sort order
-    /// 2. Verify it's saved in config
-    /// 3. Reload and verify sort order persists
-    #[test]
-    fn test_sort_preference_persistence() {
-        // This would require:
-        // - PUT /config with sort_order
-        // - GET /config to verify
-        // - Restart session and verify persistence
-        assert!(true, "Integration test placeholder");
+    // Following is per user and needs a session
+    app.get("/hashtags/followed")
+        .send()
+        .await
+        .expect_status(StatusCode::UNAUTHORIZED);
+}
+
+/// Test Scenario: Filtered post retrieval
+/// 1. Create posts with different hashtags
+/// 2. Filter by specific hashtag
+/// 3. Verify only posts with that hashtag are returned, in every sort order
+#[tokio::test]
+async fn test_filtered_post_retrieval() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+    let rust = app.post(&alice, "Ownership clicked #rust").await;
+    let both = app.post(&alice, "Async in #rust and #go").await;
+    app.post(&alice, "Goroutines are neat #go").await;
+
+    for sort in ["newest", "top", "hot"] {
+        let posts: Vec<Post> = app
+            .get(&format!("/posts?hashtag=rust&sort={}", sort))
+            .send()
+            .await
+            .expect_status(StatusCode::OK)
+            .json();
+        let mut ids: Vec<_> = posts.iter().map(|post| post.id).collect();
+        ids.sort();
+        let mut expected = vec![rust.id, both.id];
+        expected.sort();
+        assert_eq!(ids, expected, "sort={}", sort);
     }
 }
 
-// To run these tests:
-// cargo test --test hashtag_integration_tests
+/// Test Scenario: Hashtag search
+/// 1. Create multiple hashtags
+/// 2. Search with partial match
+/// 3. Verify results are sorted by post count
+#[tokio::test]
+async fn test_hashtag_search() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+    app.post(&alice, "First #rustlang post").await;
+    app.post(&alice, "Loving #rust").await;
+    app.post(&alice, "Still loving #rust").await;
+    app.post(&alice, "Off topic #python").await;
+
+    let found = app
+        .get("/hashtags/search?q=rust")
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    assert_eq!(names(&found.body), vec!["rust", "rustlang"]);
+    assert_eq!(found.body[0]["post_count"], 2);
+}
+
+/// Test Scenario: Activity tracking
+/// 1. Create posts with hashtags (increments activity)
+/// 2. Verify the most used hashtag leads the user's active hashtags
+#[tokio::test]
+async fn test_activity_tracking() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+    app.post(&alice, "Morning #rust").await;
+    app.post(&alice, "Evening #rust").await;
+    app.post(&alice, "One #python post").await;
 
-// Note: Full integration tests would be implemented using:
-// - reqwest for HTTP requests
-// - A test server instance
-// - Test database cleanup between tests
-// - Proper authentication setup
+    let active = app
+        .get("/hashtags/active")
+        .signed_in_as(&alice)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    assert_eq!(names(&active.body).first(), Some(&"rust"));
+}
+
+/// Test Scenario: Sort preference persistence
+/// 1. Change sort order
+/// 2. Verify it's saved in config, for other sessions too
+#[tokio::test]
+async fn test_sort_preference_persistence() {
+    let app = TestApp::new();
+    let alice = app.user("alice");
+
+    app.request(Method::PUT, "/config")
+        .signed_in_as(&alice)
+        .json(&json!({ "sort_order": "Popular" }))
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+
+    let other_session = app.session(alice.id);
+    let config = app
+        .get("/config")
+        .token(&other_session)
+        .send()
+        .await
+        .expect_status(StatusCode::OK);
+    assert_eq!(config.body["sort_order"], "Popular");
+}
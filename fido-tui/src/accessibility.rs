@@ -0,0 +1,130 @@
+//! Announcing state changes for screen reader users.
+//!
+//! The app queues plain-text announcements (what's focused, new toasts) and
+//! the event loop hands them to the `Announcer` chosen in Settings.
+
+use fido_types::AnnounceOutput;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Announcements longer than this are cut, so one long post doesn't hold up
+/// everything after it
+const MAX_ANNOUNCEMENT_CHARS: usize = 300;
+
+/// Somewhere announcements can be sent
+pub trait Announcer {
+    fn announce(&mut self, message: &str) -> std::io::Result<()>;
+}
+
+/// Desktop notifications through the terminal's OSC 9 escape, written to the
+/// same stream as the UI so it also works over SSH
+pub struct TerminalNotifier<'a, W: Write> {
+    out: &'a mut W,
+}
+
+impl<'a, W: Write> TerminalNotifier<'a, W> {
+    pub fn new(out: &'a mut W) -> Self {
+        Self { out }
+    }
+}
+
+impl<W: Write> Announcer for TerminalNotifier<'_, W> {
+    fn announce(&mut self, message: &str) -> std::io::Result<()> {
+        write!(self.out, "\x1b]9;{}\x07", message)?;
+        self.out.flush()
+    }
+}
+
+/// Speech through speech-dispatcher's `spd-say`, on the machine running the TUI
+pub struct SpeechDispatcher;
+
+impl Announcer for SpeechDispatcher {
+    fn announce(&mut self, message: &str) -> std::io::Result<()> {
+        // Don't wait: spd-say queues the text and exits
+        Command::new("spd-say")
+            .arg("--")
+            .arg(message)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map(|_| ())
+    }
+}
+
+/// Single line of printable text, short enough to announce; `None` if
+/// nothing is left
+pub fn clean(message: &str) -> Option<String> {
+    let line = message
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>();
+    let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.is_empty() {
+        return None;
+    }
+    Some(match line.char_indices().nth(MAX_ANNOUNCEMENT_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line,
+    })
+}
+
+/// Send `messages` to `output`. Speech falls back to terminal notifications
+/// when it isn't available (SSH sessions, or no speech-dispatcher).
+pub fn deliver<W: Write>(
+    output: AnnounceOutput,
+    speech_available: &mut bool,
+    terminal: &mut W,
+    messages: Vec<String>,
+) {
+    for message in messages {
+        let sent = match output {
+            AnnounceOutput::Off => return,
+            AnnounceOutput::Speech if *speech_available => {
+                let sent = SpeechDispatcher.announce(&message);
+                if let Err(e) = &sent {
+                    log::warn!("spd-say failed, announcing in the terminal instead: {}", e);
+                    *speech_available = false;
+                    TerminalNotifier::new(terminal).announce(&message)
+                } else {
+                    sent
+                }
+            }
+            AnnounceOutput::Speech | AnnounceOutput::Terminal => {
+                TerminalNotifier::new(terminal).announce(&message)
+            }
+        };
+        if let Err(e) = sent {
+            log::warn!("Couldn't announce: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announcements_are_single_clean_lines() {
+        assert_eq!(clean("  New\npost\x1b[31m by bob "), Some("New post [31m by bob".to_string()));
+        assert_eq!(clean("\n\t"), None);
+        let long = "a".repeat(MAX_ANNOUNCEMENT_CHARS + 10);
+        assert_eq!(clean(&long).unwrap().chars().count(), MAX_ANNOUNCEMENT_CHARS + 1);
+    }
+
+    #[test]
+    fn test_terminal_notifier_writes_osc_9() {
+        let mut out = Vec::new();
+        deliver(
+            AnnounceOutput::Terminal,
+            &mut true,
+            &mut out,
+            vec!["Posts tab".to_string()],
+        );
+        assert_eq!(out, b"\x1b]9;Posts tab\x07");
+
+        let mut out = Vec::new();
+        deliver(AnnounceOutput::Off, &mut true, &mut out, vec!["ignored".to_string()]);
+        assert!(out.is_empty());
+    }
+}
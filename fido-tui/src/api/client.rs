@@ -1,7 +1,11 @@
 use reqwest::Client;
 use serde::de::DeserializeOwned;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+use super::retry::{CircuitBreaker, RetryPolicy};
 use super::{ApiError, ApiResult};
 use fido_types::*;
 
@@ -36,20 +40,47 @@ impl VoteDirection {
 
 
 
+/// How long to show a rate limit when the server doesn't send `Retry-After`
+const DEFAULT_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Hardest login proof-of-work the client will attempt (about 2^28 hashes)
+const MAX_LOGIN_POW_DIFFICULTY: u32 = 28;
+
+/// Default per-request timeout, overridable with `FIDO_REQUEST_TIMEOUT_SECS`
+fn default_request_timeout() -> Duration {
+    std::env::var("FIDO_REQUEST_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(15))
+}
+
 /// API client for communicating with the Fido server
 #[derive(Clone)]
 pub struct ApiClient {
     client: Client,
     base_url: String,
     session_token: Option<String>,
+    retry_policy: RetryPolicy,
+    circuit: CircuitBreaker,
+    request_timeout: Duration,
+    /// Set on a 429 response; shared by all clones like the circuit breaker
+    rate_limited_until: Arc<Mutex<Option<Instant>>>,
+    /// Session lives in the server's HttpOnly cookie instead of `session_token`
+    cookie_auth: bool,
+    /// Echoed as `X-CSRF-Token` on cookie-authenticated requests
+    csrf_token: Arc<Mutex<Option<String>>>,
+    /// Set when the server answers 401 to a request made with a session;
+    /// shared by all clones so background tasks report it too
+    session_rejected: Arc<AtomicBool>,
 }
 
 impl ApiClient {
     /// Create a new API client
     pub fn new(base_url: impl Into<String>) -> Self {
         let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .connect_timeout(std::time::Duration::from_secs(10))
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
             .build()
             .expect("Failed to create HTTP client");
             
@@ -57,9 +88,74 @@ impl ApiClient {
             client,
             base_url: base_url.into(),
             session_token: None,
+            retry_policy: RetryPolicy::from_env(),
+            circuit: CircuitBreaker::default(),
+            request_timeout: default_request_timeout(),
+            rate_limited_until: Arc::new(Mutex::new(None)),
+            cookie_auth: false,
+            csrf_token: Arc::new(Mutex::new(None)),
+            session_rejected: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Switch to cookie-based auth, as used by the web terminal: logins go
+    /// through `/auth/web/login`, the session token stays in the HTTP
+    /// client's cookie jar and never reaches this process's memory or disk.
+    pub fn with_cookie_auth(mut self) -> Self {
+        self.client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .cookie_store(true)
+            .build()
+            .expect("Failed to create HTTP client");
+        self.cookie_auth = true;
+        self
+    }
+
+    pub fn uses_cookie_auth(&self) -> bool {
+        self.cookie_auth
+    }
+
+    /// True when repeated connection failures have tripped the circuit breaker
+    pub fn is_offline(&self) -> bool {
+        self.circuit.is_open()
+    }
+
+    /// True while the server has recently answered 429 Too Many Requests
+    pub fn is_rate_limited(&self) -> bool {
+        self.rate_limited_until
+            .lock()
+            .unwrap()
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// How long until requests are allowed again after a 429, if still waiting
+    pub fn rate_limit_remaining(&self) -> Option<Duration> {
+        self.rate_limited_until
+            .lock()
+            .unwrap()
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Start waiting out a 429, returning how long the server asked for
+    fn record_rate_limit(&self, response: &reqwest::Response) -> Duration {
+        let backoff = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_RATE_LIMIT_BACKOFF);
+        self.rate_limit_for(backoff);
+        backoff
+    }
+
+    /// Hold off for `backoff`, as if the server had answered 429
+    pub(crate) fn rate_limit_for(&self, backoff: Duration) {
+        *self.rate_limited_until.lock().unwrap() = Some(Instant::now() + backoff);
+    }
+
     /// Helper to build API URLs
     fn build_url(&self, path: &str) -> String {
         format!("{}{}", self.base_url, path)
@@ -83,14 +179,82 @@ impl ApiClient {
     /// Set the session token for authenticated requests
     pub fn set_session_token(&mut self, token: Option<String>) {
         self.session_token = token;
+        self.session_rejected.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether the server rejected the session since the last call
+    pub fn take_session_rejected(&self) -> bool {
+        self.session_rejected.swap(false, Ordering::Relaxed)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn session_token(&self) -> Option<&str> {
+        self.session_token.as_deref()
+    }
+
+    /// Return a copy of this client whose requests use a different timeout.
+    ///
+    /// Handy for interactive calls (search-as-you-type) that should give up
+    /// quickly rather than hold stale work for the full default.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        let mut client = self.clone();
+        client.request_timeout = timeout;
+        client
     }
 
-    /// Helper to add session token to request if available
-    fn add_auth_header(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    /// Helper to apply the per-request timeout, session token and CSRF token
+    /// (if available)
+    fn prepare_request(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let mut req = req.timeout(self.request_timeout);
         if let Some(token) = &self.session_token {
-            req.header("X-Session-Token", token)
-        } else {
-            req
+            req = req.header("X-Session-Token", token);
+        }
+        if let Some(csrf) = self.csrf_token.lock().unwrap().as_deref() {
+            req = req.header("X-CSRF-Token", csrf);
+        }
+        req
+    }
+
+    /// Send an idempotent GET, retrying transient failures with jittered
+    /// exponential backoff. Connection errors and 5xx responses that survive
+    /// all retries count against the circuit breaker.
+    async fn send_get(&self, url: &str) -> ApiResult<reqwest::Response> {
+        if !self.circuit.allow_request() {
+            return Err(ApiError::Offline(
+                "connection to server lost, retrying shortly".to_string(),
+            ));
+        }
+
+        let mut attempt = 0;
+        loop {
+            let result = self.prepare_request(self.client.get(url)).send().await;
+            let (transient, server_down) = match &result {
+                Ok(response) => (
+                    response.status().is_server_error()
+                        || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS,
+                    response.status().is_server_error(),
+                ),
+                Err(e) => {
+                    let transient = e.is_connect() || e.is_timeout() || e.is_request();
+                    (transient, transient)
+                }
+            };
+
+            if transient && attempt < self.retry_policy.max_retries {
+                tokio::time::sleep(self.retry_policy.delay_for(attempt)).await;
+                attempt += 1;
+                continue;
+            }
+
+            if server_down {
+                self.circuit.record_failure();
+            } else if result.is_ok() {
+                self.circuit.record_success();
+            }
+            return result.map_err(ApiError::from);
         }
     }
 
@@ -101,6 +265,15 @@ impl ApiClient {
         if status.is_success() {
             response.json().await.map_err(ApiError::from)
         } else {
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                let retry_after = self.record_rate_limit(&response);
+                return Err(ApiError::RateLimited { retry_after });
+            }
+            if status == reqwest::StatusCode::UNAUTHORIZED
+                && (self.session_token.is_some() || self.cookie_auth)
+            {
+                self.session_rejected.store(true, Ordering::Relaxed);
+            }
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             
             // Clean up HTML error messages (e.g., from nginx 404 pages)
@@ -128,23 +301,77 @@ impl ApiClient {
     /// Get list of test users
     pub async fn get_test_users(&self) -> ApiResult<Vec<User>> {
         let url = format!("{}/users/test", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
     /// Login with username
     pub async fn login(&mut self, username: String) -> ApiResult<LoginResponse> {
         let url = format!("{}/auth/login", self.base_url);
-        let request = LoginRequest { username };
+        let pow = self.solve_login_challenge().await?;
+        let request = LoginRequest { username, pow };
         let response = self.client.post(&url).json(&request).send().await?;
         let login_response: LoginResponse = self.handle_response(response).await?;
         
         // Store session token
-        self.session_token = Some(login_response.session_token.clone());
+        self.set_session_token(Some(login_response.session_token.clone()));
         
         Ok(login_response)
     }
 
+    /// Fetch and solve the server's login proof-of-work, if it asks for one.
+    /// Hashing runs on a blocking thread so the UI keeps drawing.
+    async fn solve_login_challenge(&self) -> ApiResult<Option<PowSolution>> {
+        let Ok(features) = self.get_features().await else {
+            return Ok(None);
+        };
+        if features.login_pow_difficulty.is_none() {
+            return Ok(None);
+        }
+
+        let url = format!("{}/auth/challenge", self.base_url);
+        let response = self.send_get(&url).await?;
+        let challenge: PowChallenge = self.handle_response(response).await?;
+        if challenge.difficulty > MAX_LOGIN_POW_DIFFICULTY {
+            return Err(ApiError::Api(format!(
+                "Server asks for a {}-bit proof-of-work; at most {} is supported",
+                challenge.difficulty, MAX_LOGIN_POW_DIFFICULTY
+            )));
+        }
+        let nonce = tokio::task::spawn_blocking({
+            let (text, difficulty) = (challenge.challenge.clone(), challenge.difficulty);
+            move || pow::solve(&text, difficulty)
+        })
+        .await
+        .map_err(|e| ApiError::Api(format!("Proof-of-work failed: {}", e)))?;
+        Ok(Some(PowSolution {
+            challenge: challenge.challenge,
+            nonce,
+        }))
+    }
+
+    /// Login with a test user using cookie auth (see [`Self::with_cookie_auth`])
+    pub async fn web_login(&self, username: String) -> ApiResult<User> {
+        let url = format!("{}/auth/web/login", self.base_url);
+        let pow = self.solve_login_challenge().await?;
+        let request = LoginRequest { username, pow };
+        let response = self.client.post(&url).json(&request).send().await?;
+        let login_response: WebLoginResponse = self.handle_response(response).await?;
+
+        *self.csrf_token.lock().unwrap() = Some(login_response.csrf_token);
+        self.session_rejected.store(false, Ordering::Relaxed);
+        Ok(login_response.user)
+    }
+
+    /// End a cookie session; the server clears both cookies
+    pub async fn web_logout(&self) -> ApiResult<()> {
+        let url = format!("{}/auth/web/logout", self.base_url);
+        let response = self.prepare_request(self.client.post(&url)).send().await?;
+        response.error_for_status()?;
+        *self.csrf_token.lock().unwrap() = None;
+        Ok(())
+    }
+
     // Post endpoints
 
     /// Get posts with optional limit, sort order, and filters
@@ -167,16 +394,25 @@ impl ApiClient {
         let params_ref: Vec<(&str, &str)> = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
         let url = self.build_url_with_params("/posts", &params_ref);
         
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
     /// Create a new post
     pub async fn create_post(&self, content: String) -> ApiResult<Post> {
+        self.create_post_with(CreatePostRequest {
+            content,
+            anonymous: false,
+            reply_policy: ReplyPolicy::Everyone,
+        })
+        .await
+    }
+
+    /// Create a post with its options: the author hidden, where the
+    /// instance allows it, and who may reply
+    pub async fn create_post_with(&self, request: CreatePostRequest) -> ApiResult<Post> {
         let url = format!("{}/posts", self.base_url);
-        let request = CreatePostRequest { content };
-        let req = self.add_auth_header(self.client.post(&url).json(&request));
+        let req = self.prepare_request(self.client.post(&url).json(&request));
         let response = req.send().await?;
         self.handle_response(response).await
     }
@@ -187,7 +423,7 @@ impl ApiClient {
         let request = VoteRequest { 
             direction: direction.to_string()
         };
-        let req = self.add_auth_header(self.client.post(&url).json(&request));
+        let req = self.prepare_request(self.client.post(&url).json(&request));
         let response = req.send().await?;
         self.handle_response(response).await
     }
@@ -195,16 +431,21 @@ impl ApiClient {
     /// Get a single post by ID
     pub async fn get_post_by_id(&self, post_id: Uuid) -> ApiResult<Post> {
         let url = format!("{}/posts/{}", self.base_url, post_id);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
     /// Get replies for a post
     pub async fn get_replies(&self, post_id: Uuid) -> ApiResult<Vec<Post>> {
         let url = format!("{}/posts/{}/replies", self.base_url, post_id);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get posts related to a post by shared hashtags or author
+    pub async fn get_related_posts(&self, post_id: Uuid) -> ApiResult<Vec<Post>> {
+        let url = format!("{}/posts/{}/related", self.base_url, post_id);
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
@@ -212,7 +453,7 @@ impl ApiClient {
     pub async fn create_reply(&self, post_id: Uuid, content: String) -> ApiResult<Post> {
         let url = format!("{}/posts/{}/reply", self.base_url, post_id);
         let request = CreateReplyRequest { content };
-        let req = self.add_auth_header(self.client.post(&url).json(&request));
+        let req = self.prepare_request(self.client.post(&url).json(&request));
         let response = req.send().await?;
         self.handle_response(response).await
     }
@@ -221,7 +462,7 @@ impl ApiClient {
     pub async fn update_post(&self, post_id: Uuid, content: String) -> ApiResult<Post> {
         let url = format!("{}/posts/{}", self.base_url, post_id);
         let request = UpdatePostRequest { content };
-        let req = self.add_auth_header(self.client.put(&url).json(&request));
+        let req = self.prepare_request(self.client.put(&url).json(&request));
         let response = req.send().await?;
         self.handle_response(response).await
     }
@@ -229,26 +470,291 @@ impl ApiClient {
     /// Delete a post
     pub async fn delete_post(&self, post_id: Uuid) -> ApiResult<serde_json::Value> {
         let url = format!("{}/posts/{}", self.base_url, post_id);
-        let req = self.add_auth_header(self.client.delete(&url));
+        let req = self.prepare_request(self.client.delete(&url));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Watch or stop watching a thread for new replies
+    pub async fn set_thread_watched(&self, post_id: Uuid, watched: bool) -> ApiResult<()> {
+        let url = format!("{}/posts/{}/watch", self.base_url, post_id);
+        let req = if watched {
+            self.client.put(&url)
+        } else {
+            self.client.delete(&url)
+        };
+        let response = self.prepare_request(req).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Record that a watched thread's replies have been read
+    pub async fn mark_thread_seen(&self, post_id: Uuid) -> ApiResult<()> {
+        let url = format!("{}/posts/{}/watch/seen", self.base_url, post_id);
+        let response = self.prepare_request(self.client.post(&url)).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Threads the user watches, with how many replies they haven't seen
+    pub async fn get_watched_threads(&self) -> ApiResult<Vec<WatchedThread>> {
+        let url = format!("{}/watches", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    // Channel endpoints
+
+    /// Every channel, joined ones first, with unread counts
+    pub async fn get_channels(&self) -> ApiResult<Vec<Channel>> {
+        let url = format!("{}/channels", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Create a channel; the creator joins it
+    pub async fn create_channel(&self, name: String, description: String) -> ApiResult<Channel> {
+        let url = format!("{}/channels", self.base_url);
+        let request = CreateChannelRequest { name, description };
+        let req = self.prepare_request(self.client.post(&url).json(&request));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Join or leave a channel
+    pub async fn set_channel_joined(&self, name: &str, joined: bool) -> ApiResult<()> {
+        let url = format!("{}/channels/{}/membership", self.base_url, name);
+        let req = if joined {
+            self.client.put(&url)
+        } else {
+            self.client.delete(&url)
+        };
+        let response = self.prepare_request(req).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Record that a channel has been read up to now
+    pub async fn mark_channel_read(&self, name: &str) -> ApiResult<()> {
+        let url = format!("{}/channels/{}/read", self.base_url, name);
+        let response = self.prepare_request(self.client.post(&url)).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// A channel's feed
+    pub async fn get_channel_posts(&self, name: &str, limit: i32, sort: String) -> ApiResult<Vec<Post>> {
+        let limit = limit.to_string();
+        let url = self.build_url_with_params(
+            &format!("/channels/{}/posts", name),
+            &[("limit", limit.as_str()), ("sort", sort.as_str())],
+        );
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Post in a channel the user has joined
+    pub async fn create_channel_post(
+        &self,
+        name: &str,
+        content: String,
+        reply_policy: ReplyPolicy,
+    ) -> ApiResult<Post> {
+        let url = format!("{}/channels/{}/posts", self.base_url, name);
+        let request = CreatePostRequest {
+            content,
+            anonymous: false,
+            reply_policy,
+        };
+        let req = self.prepare_request(self.client.post(&url).json(&request));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    // Event endpoints
+
+    /// Post an event; `content` is its description
+    pub async fn create_event(&self, request: CreateEventRequest) -> ApiResult<Post> {
+        let url = format!("{}/events", self.base_url);
+        let req = self.prepare_request(self.client.post(&url).json(&request));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Upcoming events from the user and who and what they follow, soonest first
+    pub async fn get_upcoming_events(&self) -> ApiResult<Vec<Post>> {
+        let url = format!("{}/events", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// The past week's best-voted posts
+    pub async fn get_top_posts(&self) -> ApiResult<Vec<Post>> {
+        let url = format!("{}/leaderboard/posts", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// The past week's top contributors; `None` where the instance doesn't
+    /// rank people
+    pub async fn get_top_contributors(&self) -> ApiResult<Option<Vec<TopContributor>>> {
+        let url = format!("{}/leaderboard/contributors", self.base_url);
+        let response = self.send_get(&url).await?;
+        match self.handle_response(response).await {
+            Ok(contributors) => Ok(Some(contributors)),
+            Err(ApiError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Answer an event, or withdraw the answer with `None`
+    pub async fn set_event_rsvp(&self, post_id: Uuid, status: Option<RsvpStatus>) -> ApiResult<()> {
+        let url = format!("{}/posts/{}/rsvp", self.base_url, post_id);
+        let req = match status {
+            Some(status) => self.client.put(&url).json(&RsvpRequest { status }),
+            None => self.client.delete(&url),
+        };
+        let response = self.prepare_request(req).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Publish text too long for a post as a gist on the user's GitHub
+    /// account, posting its opening words and the link
+    pub async fn create_gist_post(&self, content: String) -> ApiResult<Post> {
+        let url = format!("{}/gists", self.base_url);
+        let request = CreateGistPostRequest { content };
+        let req = self.prepare_request(self.client.post(&url).json(&request));
         let response = req.send().await?;
         self.handle_response(response).await
     }
 
+    // Review request endpoints
+
+    /// Ask for a code review; `content` says what to look at
+    pub async fn create_review_request(&self, request: CreateReviewRequest) -> ApiResult<Post> {
+        let url = format!("{}/reviews", self.base_url);
+        let req = self.prepare_request(self.client.post(&url).json(&request));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Open review requests in the languages the user follows
+    pub async fn get_review_requests(&self, limit: i32, sort: String) -> ApiResult<Vec<Post>> {
+        let limit = limit.to_string();
+        let url = self.build_url_with_params(
+            "/reviews",
+            &[("limit", limit.as_str()), ("sort", sort.as_str())],
+        );
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Close one of the user's review requests, or reopen it
+    pub async fn set_review_open(&self, post_id: Uuid, is_open: bool) -> ApiResult<()> {
+        let url = format!("{}/posts/{}/review", self.base_url, post_id);
+        let req = self.client.put(&url).json(&UpdateReviewRequest { is_open });
+        let response = self.prepare_request(req).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Accept a reply as the answer to its thread, or clear the accepted
+    /// answer; thread author only
+    pub async fn set_accepted_reply(&self, reply_id: Uuid, accept: bool) -> ApiResult<()> {
+        let url = format!("{}/posts/{}/accept", self.base_url, reply_id);
+        let req = if accept {
+            self.client.put(&url)
+        } else {
+            self.client.delete(&url)
+        };
+        let response = self.prepare_request(req).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Post a job opening
+    pub async fn create_job(&self, request: CreateJobRequest) -> ApiResult<Post> {
+        let url = format!("{}/jobs", self.base_url);
+        let req = self.prepare_request(self.client.post(&url).json(&request));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Job posts, optionally only remote or only onsite ones
+    pub async fn get_jobs(
+        &self,
+        work_mode: Option<WorkMode>,
+        limit: i32,
+        sort: String,
+    ) -> ApiResult<Vec<Post>> {
+        let limit = limit.to_string();
+        let mut params = vec![("limit", limit.as_str()), ("sort", sort.as_str())];
+        if let Some(work_mode) = work_mode {
+            params.push(("work_mode", work_mode.as_str()));
+        }
+        let url = self.build_url_with_params("/jobs", &params);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
     // Profile endpoints
 
     /// Get user profile (own profile - legacy)
     pub async fn get_profile(&self, user_id: Uuid) -> ApiResult<UserProfile> {
         let url = format!("{}/users/{}/profile", self.base_url, user_id);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Posting statistics for a profile: posts per week, streaks, hashtags
+    /// and votes received
+    pub async fn get_user_stats(&self, user_id: Uuid) -> ApiResult<UserStats> {
+        let url = format!("{}/users/{}/stats", self.base_url, user_id);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get a page of a user's posts, newest first
+    pub async fn get_user_posts(&self, user_id: String, limit: i32, offset: i32) -> ApiResult<Vec<Post>> {
+        let limit = limit.to_string();
+        let offset = offset.to_string();
+        let url = self.build_url_with_params(
+            &format!("/users/{}/posts", user_id),
+            &[("limit", limit.as_str()), ("offset", offset.as_str())],
+        );
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get a page of a user's replies, newest first
+    pub async fn get_user_replies(&self, user_id: String, limit: i32, offset: i32) -> ApiResult<Vec<Post>> {
+        let limit = limit.to_string();
+        let offset = offset.to_string();
+        let url = self.build_url_with_params(
+            &format!("/users/{}/posts", user_id),
+            &[("limit", limit.as_str()), ("offset", offset.as_str()), ("replies", "true")],
+        );
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get a page of the posts the logged-in user upvoted, most recent first
+    pub async fn get_upvoted_posts(&self, user_id: String, limit: i32, offset: i32) -> ApiResult<Vec<Post>> {
+        let limit = limit.to_string();
+        let offset = offset.to_string();
+        let url = self.build_url_with_params(
+            &format!("/users/{}/upvoted", user_id),
+            &[("limit", limit.as_str()), ("offset", offset.as_str())],
+        );
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
     /// Get user profile view (for viewing any user's profile with relationship status)
     pub async fn get_user_profile_view(&self, user_id: String) -> ApiResult<fido_types::UserProfileView> {
         let url = format!("{}/users/{}/profile-view", self.base_url, user_id);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
@@ -256,34 +762,72 @@ impl ApiClient {
     pub async fn update_bio(&self, user_id: Uuid, bio: String) -> ApiResult<serde_json::Value> {
         let url = format!("{}/users/{}/profile", self.base_url, user_id);
         let request = UpdateBioRequest { bio };
-        let req = self.add_auth_header(self.client.put(&url).json(&request));
+        let req = self.prepare_request(self.client.put(&url).json(&request));
         let response = req.send().await?;
         self.handle_response(response).await
     }
 
+    /// Set the current user's status
+    pub async fn set_status(&self, user_id: Uuid, request: SetStatusRequest) -> ApiResult<UserStatus> {
+        let url = format!("{}/users/{}/status", self.base_url, user_id);
+        let req = self.prepare_request(self.client.put(&url).json(&request));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Clear the current user's status
+    pub async fn clear_status(&self, user_id: Uuid) -> ApiResult<()> {
+        let url = format!("{}/users/{}/status", self.base_url, user_id);
+        let req = self.prepare_request(self.client.delete(&url));
+        let response = req.send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
     // Direct message endpoints
 
     /// Get conversations list
     pub async fn get_conversations(&self) -> ApiResult<Vec<serde_json::Value>> {
         let url = format!("{}/dms/conversations", self.base_url);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
     /// Get conversation with specific user
     pub async fn get_conversation(&self, user_id: Uuid) -> ApiResult<Vec<DirectMessage>> {
         let url = format!("{}/dms/conversations/{}", self.base_url, user_id);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// A conversation rendered as a Markdown or JSON file
+    pub async fn export_conversation(
+        &self,
+        user_id: Uuid,
+        format: ExportFormat,
+    ) -> ApiResult<ConversationExport> {
+        let url = self.build_url_with_params(
+            &format!("/dms/conversations/{}/export", user_id),
+            &[("format", format.as_str())],
+        );
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
-    /// Send a direct message
-    pub async fn send_message(&self, to_username: String, content: String) -> ApiResult<DirectMessage> {
+    /// Send a direct message, optionally with a text file attached
+    pub async fn send_message(
+        &self,
+        to_username: String,
+        content: String,
+        attachment: Option<DmAttachment>,
+    ) -> ApiResult<DirectMessage> {
         let url = format!("{}/dms", self.base_url);
-        let request_body = SendMessageRequest { to_username, content };
-        let req = self.add_auth_header(self.client.post(&url).json(&request_body));
+        let request_body = SendMessageRequest {
+            to_username,
+            content,
+            attachment,
+        };
+        let req = self.prepare_request(self.client.post(&url).json(&request_body));
         let response = req.send().await?;
         self.handle_response(response).await
     }
@@ -291,35 +835,240 @@ impl ApiClient {
     /// Mark messages as read for a specific user
     pub async fn mark_messages_read(&self, user_id: Uuid) -> ApiResult<serde_json::Value> {
         let url = format!("{}/dms/mark-read/{}", self.base_url, user_id);
-        let req = self.add_auth_header(self.client.post(&url));
+        let req = self.prepare_request(self.client.post(&url));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Pin or unpin the conversation with a user
+    pub async fn set_conversation_pinned(&self, user_id: Uuid, pinned: bool) -> ApiResult<()> {
+        let url = format!("{}/dms/conversations/{}/pin", self.base_url, user_id);
+        let req = if pinned {
+            self.client.put(&url)
+        } else {
+            self.client.delete(&url)
+        };
+        let response = self.prepare_request(req).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Ask the other participant to approve a post quoting one of their messages
+    pub async fn request_crosspost(&self, message_id: Uuid, content: String) -> ApiResult<()> {
+        let url = format!("{}/dms/crossposts", self.base_url);
+        let request_body = CreateCrosspostRequest { message_id, content };
+        let req = self.prepare_request(self.client.post(&url).json(&request_body));
         let response = req.send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Cross-posts awaiting our approval, plus our own recent requests
+    pub async fn get_crossposts(&self) -> ApiResult<Vec<Crosspost>> {
+        let url = format!("{}/dms/crossposts", self.base_url);
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
+    /// Approve a cross-post, which publishes it
+    pub async fn approve_crosspost(&self, crosspost_id: Uuid) -> ApiResult<Post> {
+        let url = format!("{}/dms/crossposts/{}/approve", self.base_url, crosspost_id);
+        let response = self.prepare_request(self.client.post(&url)).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Decline a cross-post
+    pub async fn decline_crosspost(&self, crosspost_id: Uuid) -> ApiResult<()> {
+        let url = format!("{}/dms/crossposts/{}/decline", self.base_url, crosspost_id);
+        let response = self.prepare_request(self.client.post(&url)).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Bookmark several posts; returns how many weren't bookmarked already
+    pub async fn bookmark_posts(&self, post_ids: Vec<Uuid>) -> ApiResult<usize> {
+        let url = format!("{}/bookmarks/batch", self.base_url);
+        let request_body = BookmarkPostsRequest { post_ids };
+        let req = self.prepare_request(self.client.post(&url).json(&request_body));
+        let response = req.send().await?;
+        let result: BatchResult = self.handle_response(response).await?;
+        Ok(result.applied)
+    }
+
+    /// Mute several users so their posts leave our feed; returns how many
+    /// weren't muted already
+    pub async fn mute_users(&self, user_ids: Vec<Uuid>) -> ApiResult<usize> {
+        let url = format!("{}/mutes/batch", self.base_url);
+        let request_body = MuteUsersRequest { user_ids };
+        let req = self.prepare_request(self.client.post(&url).json(&request_body));
+        let response = req.send().await?;
+        let result: BatchResult = self.handle_response(response).await?;
+        Ok(result.applied)
+    }
+
     // Configuration endpoints
 
     /// Get user configuration
     pub async fn get_config(&self) -> ApiResult<UserConfig> {
         let url = format!("{}/config", self.base_url);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
     /// Update user configuration
     pub async fn update_config(&self, request: UpdateConfigRequest) -> ApiResult<UserConfig> {
         let url = format!("{}/config", self.base_url);
-        let req = self.add_auth_header(self.client.put(&url).json(&request));
+        let req = self.prepare_request(self.client.put(&url).json(&request));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    // Draft endpoints
+
+    /// Post drafts saved on the server, most recently edited first
+    pub async fn list_drafts(&self) -> ApiResult<Vec<PostDraft>> {
+        let url = format!("{}/drafts", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Save a new draft on the server
+    pub async fn create_draft(&self, content: String) -> ApiResult<PostDraft> {
+        let url = format!("{}/drafts", self.base_url);
+        let req = self.prepare_request(self.client.post(&url).json(&SaveDraftRequest { content }));
         let response = req.send().await?;
         self.handle_response(response).await
     }
+
+    /// Replace the content of an existing draft
+    pub async fn update_draft(&self, draft_id: Uuid, content: String) -> ApiResult<PostDraft> {
+        let url = format!("{}/drafts/{}", self.base_url, draft_id);
+        let req = self.prepare_request(self.client.put(&url).json(&SaveDraftRequest { content }));
+        let response = req.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Discard a draft
+    pub async fn delete_draft(&self, draft_id: Uuid) -> ApiResult<()> {
+        let url = format!("{}/drafts/{}", self.base_url, draft_id);
+        let req = self.prepare_request(self.client.delete(&url));
+        let response = req.send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Get the instance's custom emoji table
+    pub async fn get_custom_emoji(&self) -> ApiResult<Vec<CustomEmoji>> {
+        let url = format!("{}/emoji", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Server build; also cheap enough to time as a ping
+    pub async fn get_version(&self) -> ApiResult<ServerVersion> {
+        let url = format!("{}/version", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Instance limits, with the caller's remaining posts for today
+    pub async fn get_features(&self) -> ApiResult<InstanceFeatures> {
+        let url = format!("{}/features", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Newest digest the user hasn't dismissed; `None` when there isn't one
+    pub async fn get_digest(&self) -> ApiResult<Option<Digest>> {
+        let url = format!("{}/digest", self.base_url);
+        let response = self.send_get(&url).await?;
+        match self.handle_response(response).await {
+            Ok(digest) => Ok(Some(digest)),
+            Err(ApiError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// New followers, top followed-hashtag posts and unread DMs since `since`
+    pub async fn get_catchup(&self, since: chrono::DateTime<chrono::Utc>) -> ApiResult<Catchup> {
+        let since = since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let url = self.build_url_with_params("/catchup", &[("since", &since)]);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Dismiss a digest so it isn't shown again
+    pub async fn mark_digest_seen(&self, digest_id: Uuid) -> ApiResult<()> {
+        let url = format!("{}/digest/{}/seen", self.base_url, digest_id);
+        let req = self.prepare_request(self.client.post(&url));
+        let response = req.send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Followed hashtags with their latest weekly changelog
+    pub async fn get_hashtag_digest(&self) -> ApiResult<Vec<HashtagDigestEntry>> {
+        let url = format!("{}/digest/hashtags", self.base_url);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Turn a followed hashtag's weekly changelog on or off
+    pub async fn set_hashtag_digest(&self, hashtag: &str, enabled: bool) -> ApiResult<()> {
+        let url = format!("{}/digest/hashtags/{}", self.base_url, hashtag);
+        let req = self.client.put(&url).json(&UpdateHashtagDigestRequest { enabled });
+        let response = self.prepare_request(req).send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Ask the server where a link really goes and whether it's blocked
+    pub async fn preview_link(&self, link: &str) -> ApiResult<LinkPreview> {
+        let url = self.build_url_with_params("/links/preview", &[("url", link)]);
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Card for a GitHub repository, issue or pull request link; `None` for
+    /// other links, ones GitHub doesn't have, and servers without cards
+    pub async fn get_github_card(&self, link: &str) -> ApiResult<Option<GitHubCard>> {
+        let url = self.build_url_with_params("/links/github", &[("url", link)]);
+        let response = self.send_get(&url).await?;
+        match self.handle_response(response).await {
+            Ok(card) => Ok(Some(card)),
+            Err(ApiError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The gist `link` points at, cut down for reading inline; `None` for
+    /// other links, missing gists, and servers that can't show them
+    pub async fn get_gist(&self, link: &str) -> ApiResult<Option<Gist>> {
+        let url = self.build_url_with_params("/links/gist", &[("url", link)]);
+        let response = self.send_get(&url).await?;
+        match self.handle_response(response).await {
+            Ok(gist) => Ok(Some(gist)),
+            Err(ApiError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Machine-translate a post into `target` (a language code like "es")
+    pub async fn translate_post(&self, post_id: Uuid, target: &str) -> ApiResult<PostTranslation> {
+        let url = self.build_url_with_params(
+            &format!("/posts/{}/translate", post_id),
+            &[("target", target)],
+        );
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
     // Hashtag endpoints
 
     /// Get followed hashtags
     pub async fn get_followed_hashtags(&self) -> ApiResult<Vec<String>> {
         let url = format!("{}/hashtags/followed", self.base_url);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         let hashtags: Vec<serde_json::Value> = self.handle_response(response).await?;
         Ok(hashtags.into_iter().filter_map(|h| h.get("name").and_then(|n| n.as_str()).map(String::from)).collect())
     }
@@ -328,7 +1077,7 @@ impl ApiClient {
     pub async fn follow_hashtag(&self, name: String) -> ApiResult<()> {
         let url = self.build_url("/hashtags/follow");
         let request_body = serde_json::json!({ "name": name });
-        let req = self.add_auth_header(self.client.post(&url).json(&request_body));
+        let req = self.prepare_request(self.client.post(&url).json(&request_body));
         let response = req.send().await?;
         let _: serde_json::Value = self.handle_response(response).await?;
         Ok(())
@@ -337,7 +1086,7 @@ impl ApiClient {
     /// Unfollow a hashtag
     pub async fn unfollow_hashtag(&self, name: String) -> ApiResult<()> {
         let url = self.build_url(&format!("/hashtags/follow/{}", name));
-        let req = self.add_auth_header(self.client.delete(&url));
+        let req = self.prepare_request(self.client.delete(&url));
         let response = req.send().await?;
         let _: serde_json::Value = self.handle_response(response).await?;
         Ok(())
@@ -346,8 +1095,7 @@ impl ApiClient {
     /// Search hashtags
     pub async fn search_hashtags(&self, query: String) -> ApiResult<Vec<String>> {
         let url = format!("{}/hashtags/search?q={}", self.base_url, urlencoding::encode(&query));
-        let req = self.client.get(&url);
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         let hashtags: Vec<serde_json::Value> = self.handle_response(response).await?;
         Ok(hashtags.into_iter().filter_map(|h| h.get("name").and_then(|n| n.as_str()).map(String::from)).collect())
     }
@@ -357,7 +1105,7 @@ impl ApiClient {
     /// Follow a user
     pub async fn follow_user(&self, user_id: String) -> ApiResult<()> {
         let url = format!("{}/users/{}/follow", self.base_url, user_id);
-        let req = self.add_auth_header(self.client.post(&url));
+        let req = self.prepare_request(self.client.post(&url));
         let response = req.send().await?;
         response.error_for_status()?;
         Ok(())
@@ -366,7 +1114,7 @@ impl ApiClient {
     /// Unfollow a user
     pub async fn unfollow_user(&self, user_id: String) -> ApiResult<()> {
         let url = format!("{}/users/{}/follow", self.base_url, user_id);
-        let req = self.add_auth_header(self.client.delete(&url));
+        let req = self.prepare_request(self.client.delete(&url));
         let response = req.send().await?;
         response.error_for_status()?;
         Ok(())
@@ -375,32 +1123,70 @@ impl ApiClient {
     /// Get following list
     pub async fn get_following_list(&self) -> ApiResult<Vec<SocialUserInfo>> {
         let url = format!("{}/social/following", self.base_url);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
-        self.handle_response(response).await
-    }
-
-    /// Get followers list
-    pub async fn get_followers_list(&self) -> ApiResult<Vec<SocialUserInfo>> {
-        let url = format!("{}/social/followers", self.base_url);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
     /// Get mutual friends list
     pub async fn get_mutual_friends_list(&self) -> ApiResult<Vec<SocialUserInfo>> {
         let url = format!("{}/social/mutual", self.base_url);
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// One page of a social list, most recent follow first. `list` is
+    /// `following`, `followers` or `mutual`; `cursor` is the last user id of
+    /// the previous page and `query` searches usernames on the server
+    pub async fn get_social_page(
+        &self,
+        list: &str,
+        query: &str,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> ApiResult<Vec<SocialUserInfo>> {
+        let mut url = format!(
+            "{}/social/{}?limit={}&q={}",
+            self.base_url,
+            list,
+            limit,
+            urlencoding::encode(query)
+        );
+        if let Some(cursor) = cursor {
+            url.push_str(&format!("&cursor={}", urlencoding::encode(cursor)));
+        }
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// People the user follows on GitHub who are on Fido but not followed here
+    pub async fn get_github_suggestions(&self) -> ApiResult<Vec<GitHubSuggestion>> {
+        let url = format!("{}/social/github-suggestions", self.base_url);
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
+    /// Follow all of them; returns how many were followed
+    pub async fn follow_github_suggestions(&self) -> ApiResult<usize> {
+        let url = format!("{}/social/github-suggestions/follow-all", self.base_url);
+        let req = self.prepare_request(self.client.post(&url));
+        let response = req.send().await?;
+        let body: serde_json::Value = self.handle_response(response).await?;
+        Ok(body["followed"].as_u64().unwrap_or(0) as usize)
+    }
+
+    /// Stop suggesting the people currently suggested
+    pub async fn dismiss_github_suggestions(&self) -> ApiResult<()> {
+        let url = format!("{}/social/github-suggestions/dismiss", self.base_url);
+        let req = self.prepare_request(self.client.post(&url));
+        let response = req.send().await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
     /// Search users by username
     pub async fn search_users(&self, query: String) -> ApiResult<Vec<UserSearchResult>> {
         let url = format!("{}/users/search?q={}", self.base_url, urlencoding::encode(&query));
-        let req = self.add_auth_header(self.client.get(&url));
-        let response = req.send().await?;
+        let response = self.send_get(&url).await?;
         self.handle_response(response).await
     }
 
@@ -427,7 +1213,14 @@ impl ApiClient {
     /// Validate session token
     pub async fn validate_session(&self) -> ApiResult<ValidateSessionResponse> {
         let url = format!("{}/auth/validate", self.base_url);
-        let req = self.add_auth_header(self.client.get(&url));
+        let response = self.send_get(&url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Keep the session alive; the server slides its expiry forward
+    pub async fn heartbeat(&self) -> ApiResult<HeartbeatResponse> {
+        let url = format!("{}/auth/heartbeat", self.base_url);
+        let req = self.prepare_request(self.client.post(&url));
         let response = req.send().await?;
         self.handle_response(response).await
     }
@@ -462,6 +1255,12 @@ impl Default for ApiClient {
     }
 }
 
+#[derive(Debug, serde::Deserialize)]
+struct WebLoginResponse {
+    user: User,
+    csrf_token: String,
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct SocialUserInfo {
     pub id: String,
@@ -496,6 +1295,11 @@ pub struct ValidateSessionResponse {
     pub valid: bool,
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct HeartbeatResponse {
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct SessionPollResponse {
     pub session_token: Option<String>,
@@ -1,3 +1,4 @@
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -19,6 +20,26 @@ pub enum ApiError {
 
     #[error("Bad request: {0}")]
     BadRequest(String),
+
+    #[error("Offline: {0}")]
+    Offline(String),
+
+    /// 429 Too Many Requests, with how long the server asked us to wait
+    #[error("Too many requests; retry in {}s", retry_after.as_secs().max(1))]
+    RateLimited { retry_after: Duration },
+}
+
+impl ApiError {
+    /// Whether trying the same request again later could succeed: the
+    /// connection failed, the server had an error (5xx) or it asked us to
+    /// slow down
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ApiError::Network(_) | ApiError::Offline(_) | ApiError::RateLimited { .. } => true,
+            ApiError::Api(message) => message.starts_with("Server error"),
+            _ => false,
+        }
+    }
 }
 
 pub type ApiResult<T> = Result<T, ApiError>;
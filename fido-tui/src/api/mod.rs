@@ -1,5 +1,6 @@
 mod client;
 mod error;
+mod retry;
 
 pub use client::{ApiClient, VoteDirection, SocialUserInfo};
 pub use error::{ApiError, ApiResult};
@@ -0,0 +1,192 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Retry policy for idempotent (GET) requests
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Extra attempts after the first one fails (0 disables retries)
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on each subsequent attempt
+    pub base_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Build a policy from the environment, falling back to defaults.
+    ///
+    /// `FIDO_RETRY_MAX` sets the number of retries and `FIDO_RETRY_BASE_MS`
+    /// the initial backoff in milliseconds.
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        if let Some(max) = std::env::var("FIDO_RETRY_MAX")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            policy.max_retries = max;
+        }
+        if let Some(ms) = std::env::var("FIDO_RETRY_BASE_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            policy.base_delay = Duration::from_millis(ms);
+        }
+        policy
+    }
+
+    /// Delay before retry number `attempt` (0-based), using full jitter:
+    /// a random duration between zero and the exponential backoff cap.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let millis = exponential.as_millis() as u64;
+        if millis == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(jitter_seed() % (millis + 1))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Cheap pseudo-random value for jitter (no need for a real RNG here)
+fn jitter_seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish()
+}
+
+#[derive(Debug)]
+struct CircuitState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Circuit breaker shared by all clones of an `ApiClient`.
+///
+/// After `failure_threshold` consecutive connection-level failures the circuit
+/// opens and requests fail fast (the TUI shows offline mode). Once `cooldown`
+/// has passed a single request is let through; success closes the circuit.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    state: Arc<Mutex<CircuitState>>,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CircuitState {
+                consecutive_failures: 0,
+                opened_at: None,
+            })),
+            failure_threshold,
+            cooldown,
+        }
+    }
+
+    /// Whether a request may be attempted right now
+    pub fn allow_request(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => true,
+            Some(opened_at) if opened_at.elapsed() >= self.cooldown => {
+                // Half-open: let one probe through and restart the cooldown
+                state.opened_at = Some(Instant::now());
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures = 0;
+        state.opened_at = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold {
+            state.opened_at = Some(Instant::now());
+        }
+    }
+
+    /// True while the circuit is open (server considered unreachable)
+    pub fn is_open(&self) -> bool {
+        self.state.lock().unwrap().opened_at.is_some()
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(400),
+        };
+        for attempt in 0..10 {
+            assert!(policy.delay_for(attempt) <= Duration::from_millis(400));
+        }
+    }
+
+    #[test]
+    fn test_circuit_opens_after_threshold_and_recovers() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        assert!(!breaker.allow_request(), "Open circuit should fail fast");
+
+        breaker.record_success();
+        assert!(!breaker.is_open());
+        assert!(breaker.allow_request());
+    }
+
+    #[test]
+    fn test_circuit_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        assert!(breaker.allow_request(), "Probe should be allowed after cooldown");
+    }
+
+    #[test]
+    fn test_clones_share_state() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let clone = breaker.clone();
+        clone.record_failure();
+        assert!(breaker.is_open());
+    }
+}
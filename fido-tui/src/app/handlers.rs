@@ -1,8 +1,7 @@
-use crate::app::state::{App, FilterTab, InputMode, Screen, SettingsField, Tab};
+use crate::app::state::{App, BulkAction, FilterTab, InputMode, JOB_FILTER_CHOICES, Screen, Tab};
 use crate::{log_key_event, log_settings};
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use fido_types::Post;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
     if key.kind != KeyEventKind::Press {
@@ -23,6 +22,121 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         return Ok(());
     }
 
+    // Priority 1.1: Log viewer (Ctrl+L, on any screen)
+    if app.log_viewer.show {
+        return app.handle_log_viewer_keys(key);
+    }
+    if is_log_viewer_key(&key) {
+        app.toggle_log_viewer();
+        return Ok(());
+    }
+
+    // Priority 1.12: Connection diagnostics (Ctrl+Shift+D, on any screen)
+    if app.diagnostics.show {
+        return app.handle_diagnostics_keys(key);
+    }
+    if is_diagnostics_key(&key) {
+        app.toggle_diagnostics();
+        return Ok(());
+    }
+
+    // Priority 1.13: Server and client releases don't work together; all
+    // that's left is quitting or switching to another server
+    if app.version_incompatible() && !app.server_switcher.show_modal {
+        if is_server_switch_key(&key) {
+            app.open_server_switcher();
+        } else if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter) {
+            app.running = false;
+        }
+        return Ok(());
+    }
+
+    // Priority 1.15: Session expired; Enter signs back in, which needs the
+    // server and is handled by the event loop
+    if app.reauth.show {
+        if key.code == KeyCode::Esc {
+            app.abandon_reauth();
+        }
+        return Ok(());
+    }
+
+    // Priority 1.2: Offer to restore a draft left by a crash
+    if app.recovered_draft.is_some() && app.current_screen == Screen::Main {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => app.restore_recovered_draft(),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.discard_recovered_draft(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Priority 1.21: Catch-up screen after a long absence
+    if app.catchup.is_some() && app.current_screen == Screen::Main {
+        if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+            app.dismiss_catchup();
+        }
+        return Ok(());
+    }
+
+    // Priority 1.215: "While you were away" digest
+    if app.digest.is_some() && app.current_screen == Screen::Main {
+        if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+            app.dismiss_digest();
+        }
+        return Ok(());
+    }
+
+    // Priority 1.217: Cross-post request asking for our consent ('y' and
+    // 'n' need the server and are handled by the event loop)
+    if app.crosspost_prompt_open() {
+        if key.code == KeyCode::Esc {
+            app.dismiss_crosspost_prompt();
+        }
+        return Ok(());
+    }
+
+    // Priority 1.218: People to follow from GitHub (Enter follows them all
+    // and is handled by the event loop)
+    if app.github_suggestions_open() {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+            app.dismiss_github_suggestions();
+        }
+        return Ok(());
+    }
+
+    // Priority 1.22: Link confirmation
+    if app.link_preview.show {
+        return app.handle_link_preview_keys(key);
+    }
+
+    // Priority 1.23: Share menu for a post
+    if app.share_menu.show {
+        return app.handle_share_menu_keys(key);
+    }
+
+    // Priority 1.25: Server quick-switch modal (Ctrl+Shift+S, on any screen)
+    if app.server_switcher.show_modal {
+        return app.handle_server_switcher_keys(key);
+    }
+    if is_server_switch_key(&key) && !app.composer_state.is_open() {
+        app.open_server_switcher();
+        return Ok(());
+    }
+
+    // Priority 1.3: Reading history (Ctrl+H; Enter opens the post and is
+    // handled by the event loop)
+    if app.reading_history.show {
+        return app.handle_reading_history_keys(key);
+    }
+    if is_reading_history_key(&key)
+        && app.current_screen == Screen::Main
+        && app.input_mode == InputMode::Navigation
+        && !app.composer_state.is_open()
+    {
+        app.open_reading_history();
+        return Ok(());
+    }
+
     // Priority 1.5: User profile view
     if app.user_profile_view.is_some() {
         return app.handle_user_profile_view_keys(key);
@@ -54,12 +168,40 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         return app.handle_filter_modal_keys(key);
     }
 
+    // Priority 3.9: Drafts list, shown over the new-post composer
+    if app.drafts.show {
+        return app.handle_drafts_keys(key);
+    }
+
+    // Priority 3.95: Upcoming events, opened from the feed
+    if app.events.show {
+        return app.handle_events_keys(key);
+    }
+    if app.highlights.show {
+        return app.handle_highlights_keys(key);
+    }
+    if app.hashtag_digest.show {
+        return app.handle_hashtag_digest_keys(key);
+    }
+
     // Priority 4: Unified composer modal
     if app.composer_state.is_open() {
         if matches!(key.code, KeyCode::Esc) {
             app.close_composer();
             return Ok(());
         }
+        if key.code == KeyCode::Char('o') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            app.open_drafts();
+            return Ok(());
+        }
+        if key.code == KeyCode::Char('t') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            app.toggle_anonymous_post();
+            return Ok(());
+        }
+        if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            app.cycle_reply_policy();
+            return Ok(());
+        }
         // All other keys are handled by TextArea
         app.handle_composer_input(key);
         return Ok(());
@@ -148,6 +290,12 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         }
     }
 
+    // Priority 4.5: Esc lets go of a hashtag, mention or link focused in
+    // the feed, or leaves visual selection mode, rather than quitting
+    if key.code == KeyCode::Esc && (app.clear_token_focus() || app.clear_bulk_selection()) {
+        return Ok(());
+    }
+
     // Priority 5: Global keys (quit/exit)
     match key.code {
         // '?' key for help (Shift+/)
@@ -192,22 +340,69 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Ctrl+Shift+S. Terminals report the letter as either case with SHIFT set.
+pub fn is_server_switch_key(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('s') | KeyCode::Char('S'))
+        && key.modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+}
+
+/// Ctrl+Shift+D
+pub fn is_diagnostics_key(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('d') | KeyCode::Char('D'))
+        && key.modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+}
+
+/// Ctrl+L
+pub fn is_log_viewer_key(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('l') | KeyCode::Char('L'))
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Ctrl+H
+pub fn is_reading_history_key(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('h') | KeyCode::Char('H'))
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
 pub fn handle_main_keys(app: &mut App, key: KeyEvent) -> Result<()> {
     // Debug logging for h/H/l/L keys
     if matches!(key.code, KeyCode::Char('h') | KeyCode::Char('H') | KeyCode::Char('l') | KeyCode::Char('L')) {
         log_key_event!(app.log_config, "handle_main_keys received key: {:?}, current_tab: {:?}", key.code, app.current_tab);
     }
     
+    // Every key goes to the pinned hashtags input while it's open
+    if app.current_tab == Tab::Settings && app.settings_state.pinned_hashtags_input.is_some() {
+        return app.handle_settings_keys(key);
+    }
+    if app.current_tab == Tab::Channels && app.channels_state.new_channel_input.is_some() {
+        return app.handle_channels_keys(key);
+    }
+    if app.current_tab == Tab::DMs && app.dms_state.reminder_input.is_some() {
+        return app.handle_dms_keys(key);
+    }
+
     match key.code {
+        // In the feed, Tab first steps through the selected post's hashtags,
+        // mentions and links before moving on to the next tab
         KeyCode::Tab => {
-            app.next_tab();
+            if !app.focus_next_token() {
+                app.next_tab();
+            }
         }
         KeyCode::BackTab => {
-            app.previous_tab();
+            if !app.focus_previous_token() {
+                app.previous_tab();
+            }
         }
         // Shift+L (logout) is handled in main.rs as an async operation
+        KeyCode::Char('A')
+            if !app.composer_state.is_open() && app.input_mode == InputMode::Navigation =>
+        {
+            app.switch_account();
+        }
         _ => match app.current_tab {
             Tab::Posts => app.handle_posts_keys(key)?,
+            Tab::Channels => app.handle_channels_keys(key)?,
             Tab::Profile => app.handle_profile_keys(key)?,
             Tab::DMs => app.handle_dms_keys(key)?,
             Tab::Settings => {
@@ -228,6 +423,32 @@ pub fn handle_posts_keys(app: &mut App, key: KeyEvent) -> Result<()> {
         return app.handle_post_detail_keys(key);
     }
 
+    if app.posts_state.snooze_input.is_some() {
+        app.handle_snooze_input(key);
+        return Ok(());
+    }
+
+    // Any other key lets go of a token focused with Tab
+    app.focused_token = None;
+
+    // Visual selection mode: Space marks posts, then b/m/o act on all of them
+    if app.posts_state.bulk_selection.is_some() {
+        let action = match key.code {
+            KeyCode::Char(' ') => {
+                app.toggle_post_mark();
+                return Ok(());
+            }
+            KeyCode::Char('b') | KeyCode::Char('B') => Some(BulkAction::Bookmark),
+            KeyCode::Char('m') | KeyCode::Char('M') => Some(BulkAction::MuteAuthors),
+            KeyCode::Char('o') | KeyCode::Char('O') => Some(BulkAction::OpenInBrowser),
+            _ => None,
+        };
+        if let Some(action) = action {
+            app.apply_bulk_action(action);
+            return Ok(());
+        }
+    }
+
     match key.code {
         KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
             app.next_post();
@@ -243,173 +464,72 @@ pub fn handle_posts_keys(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Char('f') | KeyCode::Char('F') => {
             app.open_filter_modal();
         }
-        KeyCode::Char('s') | KeyCode::Char('S') => {
+        KeyCode::Char('s') => {
             app.open_user_search_modal();
         }
-        KeyCode::Char('p') | KeyCode::Char('P') => {}
-        KeyCode::Enter => {}
-        _ => {}
-    }
-    Ok(())
-}
-
-pub fn handle_profile_keys(app: &mut App, key: KeyEvent) -> Result<()> {
-    if app.profile_state.show_edit_bio_modal {
-        return app.handle_edit_bio_modal_keys(key);
-    }
-
-    match key.code {
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => app.next_user_post(),
-        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => app.previous_user_post(),
+        KeyCode::Char('S') => {
+            app.open_share_menu();
+        }
+        KeyCode::Char('V') => {
+            app.toggle_bulk_selection();
+        }
+        KeyCode::Char('o') | KeyCode::Char('O') => {
+            app.open_link_preview();
+        }
+        KeyCode::Char('T') => {
+            app.toggle_exact_time();
+        }
+        KeyCode::Char('v') => {
+            app.toggle_raw_diff();
+        }
         KeyCode::Char('e') | KeyCode::Char('E') => {
-            if let Some(profile) = &app.profile_state.profile {
-                let current_bio = profile.bio.clone().unwrap_or_default();
-                app.open_composer_edit_bio(current_bio);
-            }
+            app.expand_similar_posts();
         }
-        KeyCode::Char('f') | KeyCode::Char('F') => {
-            app.friends_state.show_friends_modal = true;
-            app.friends_state.selected_index = 0;
-            app.friends_state.error = None;
+        KeyCode::Char('c') => {
+            app.open_events();
+        }
+        KeyCode::Char('H') => {
+            app.open_highlights();
+        }
+        KeyCode::Char('W') => {
+            app.open_hashtag_digest();
+        }
+        KeyCode::Char('z') => {
+            app.start_snooze();
         }
+        KeyCode::Char(c @ '1'..='9') => {
+            app.apply_pinned_hashtag(c as usize - '0' as usize);
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => {}
+        KeyCode::Enter => {}
         _ => {}
     }
     Ok(())
 }
 
-pub fn handle_dms_keys(app: &mut App, key: KeyEvent) -> Result<()> {
-    if app.dms_state.show_new_conversation_modal {
-        return app.handle_new_conversation_modal_keys(key);
-    }
-
-    match app.input_mode {
-        InputMode::Navigation => match key.code {
-            KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => match app.dms_state.selected_conversation_index {
-                None => {
-                    // If there's a pending conversation, clear it and move to "New Conversation" button
-                    if app.dms_state.pending_conversation_username.is_some() {
-                        app.dms_state.pending_conversation_username = None;
-                        app.dms_state.message_input.clear();
-                    }
-                    app.dms_state.selected_conversation_index = Some(usize::MAX);
-                }
-                Some(usize::MAX) => {
-                    if !app.dms_state.conversations.is_empty() {
-                        app.dms_state.selected_conversation_index = Some(0);
-                    }
-                }
-                Some(index) => {
-                    if index < app.dms_state.conversations.len().saturating_sub(1) {
-                        app.dms_state.selected_conversation_index = Some(index + 1);
-                    }
-                }
-            },
-            KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => match app.dms_state.selected_conversation_index {
-                None => {
-                    // If there's a pending conversation, clear it and navigate
-                    if app.dms_state.pending_conversation_username.is_some() {
-                        app.dms_state.pending_conversation_username = None;
-                        app.dms_state.message_input.clear();
-                    }
-                    if !app.dms_state.conversations.is_empty() {
-                        app.dms_state.selected_conversation_index =
-                            Some(app.dms_state.conversations.len() - 1);
-                    } else {
-                        app.dms_state.selected_conversation_index = Some(usize::MAX);
-                    }
-                }
-                Some(0) => {
-                    app.dms_state.selected_conversation_index = Some(usize::MAX);
-                }
-                Some(usize::MAX) => {}
-                Some(index) => {
-                    app.dms_state.selected_conversation_index = Some(index - 1);
-                }
-            },
-            KeyCode::Enter => {
-                if app.dms_state.selected_conversation_index == Some(usize::MAX) {
-                    app.dms_state.show_new_conversation_modal = true;
-                    app.dms_state.new_conversation_username.clear();
-                    app.input_mode = InputMode::Typing;
-                }
-            }
-            _ => {
-                app.input_mode = InputMode::Typing;
-                app.handle_dm_input(key);
-                if app.dms_state.messages.is_empty() {
-                    app.dms_state.needs_message_load = true;
-                }
+/// Enter, Space and creating a channel need the server and are handled
+/// in the runner; this covers moving around and typing a new name
+pub fn handle_channels_keys(app: &mut App, key: KeyEvent) -> Result<()> {
+    if let Some(input) = &mut app.channels_state.new_channel_input {
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
             }
-        },
-        InputMode::Typing => match key.code {
             KeyCode::Esc => {
-                app.clear_dm_message();
+                app.channels_state.new_channel_input = None;
+                app.channels_state.error = None;
                 app.input_mode = InputMode::Navigation;
             }
-            KeyCode::Enter => {}
-            _ => {
-                app.handle_dm_input(key);
-                if app.is_dm_message_empty() && key.code == KeyCode::Backspace {
-                    app.input_mode = InputMode::Navigation;
-                }
-            }
-        },
-    }
-    Ok(())
-}
-
-pub fn handle_settings_keys(app: &mut App, key: KeyEvent) -> Result<()> {
-    // Debug logging - log ALL keys
-    log_settings!(app.log_config, "handle_settings_keys START: key={:?}", key.code);
-    
-    if app.settings_state.show_save_confirmation {
-        log_settings!(app.log_config, "In save confirmation mode");
-
-        match key.code {
-            KeyCode::Char('y') | KeyCode::Char('Y') => {}
-            KeyCode::Char('n') | KeyCode::Char('N') => {
-                app.confirm_discard_changes();
-            }
             _ => {}
         }
         return Ok(());
     }
 
     match key.code {
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
-            app.settings_state.selected_field = match app.settings_state.selected_field {
-                SettingsField::ColorScheme => SettingsField::SortOrder,
-                SettingsField::SortOrder => SettingsField::MaxPosts,
-                SettingsField::MaxPosts => SettingsField::MaxPosts,
-            };
-        }
-        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
-            app.settings_state.selected_field = match app.settings_state.selected_field {
-                SettingsField::ColorScheme => SettingsField::ColorScheme,
-                SettingsField::SortOrder => SettingsField::ColorScheme,
-                SettingsField::MaxPosts => SettingsField::SortOrder,
-            };
-        }
-        KeyCode::Char('h') | KeyCode::Char('H') | KeyCode::Left => {
-            log_settings!(app.log_config, "Matched h/H/Left pattern, field: {:?}", app.settings_state.selected_field);
-            match app.settings_state.selected_field {
-                SettingsField::ColorScheme => app.cycle_color_scheme_backward(),
-                SettingsField::SortOrder => app.cycle_sort_order_backward(),
-                SettingsField::MaxPosts => app.decrement_max_posts(),
-            }
-        },
-        KeyCode::Char('l') | KeyCode::Char('L') | KeyCode::Right | KeyCode::Enter => match app.settings_state.selected_field {
-            SettingsField::ColorScheme => app.cycle_color_scheme(),
-            SettingsField::SortOrder => app.cycle_sort_order(),
-            SettingsField::MaxPosts => app.increment_max_posts(),
-        },
-        KeyCode::Backspace if app.settings_state.selected_field == SettingsField::MaxPosts => {
-            app.remove_digit_from_max_posts();
-        }
-        KeyCode::Char(c) if app.settings_state.selected_field == SettingsField::MaxPosts => {
-            app.add_digit_to_max_posts(c);
-        }
-        KeyCode::Char('s') => {}
+        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => app.next_channel(),
+        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => app.previous_channel(),
+        KeyCode::Char('n') | KeyCode::Char('N') => app.open_new_channel_prompt(),
         _ => {}
     }
     Ok(())
@@ -421,18 +541,14 @@ pub fn handle_filter_modal_keys(app: &mut App, key: KeyEvent) -> Result<()> {
 
     if in_add_hashtag_input {
         match key.code {
-            KeyCode::Char(c) => {
-                app.posts_state.filter_modal_state.add_hashtag_input.push(c);
-            }
-            KeyCode::Backspace => {
-                app.posts_state.filter_modal_state.add_hashtag_input.pop();
-            }
             KeyCode::Esc => {
                 app.posts_state.filter_modal_state.show_add_hashtag_input = false;
                 app.posts_state.filter_modal_state.add_hashtag_input.clear();
             }
             KeyCode::Enter => {}
-            _ => {}
+            _ => {
+                app.posts_state.filter_modal_state.add_hashtag_input.handle_key(key);
+            }
         }
         return Ok(());
     }
@@ -446,14 +562,20 @@ pub fn handle_filter_modal_keys(app: &mut App, key: KeyEvent) -> Result<()> {
                 match app.posts_state.filter_modal_state.selected_tab {
                     FilterTab::All => FilterTab::Hashtags,
                     FilterTab::Hashtags => FilterTab::Users,
-                    FilterTab::Users => FilterTab::All,
+                    FilterTab::Users => FilterTab::Reviews,
+                    FilterTab::Reviews => FilterTab::Jobs,
+                    FilterTab::Jobs => FilterTab::Later,
+                    FilterTab::Later => FilterTab::All,
                 };
             app.posts_state.filter_modal_state.selected_index = 0;
         }
         KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => {
             app.posts_state.filter_modal_state.selected_tab =
                 match app.posts_state.filter_modal_state.selected_tab {
-                    FilterTab::All => FilterTab::Users,
+                    FilterTab::All => FilterTab::Later,
+                    FilterTab::Later => FilterTab::Jobs,
+                    FilterTab::Jobs => FilterTab::Reviews,
+                    FilterTab::Reviews => FilterTab::Users,
                     FilterTab::Users => FilterTab::Hashtags,
                     FilterTab::Hashtags => FilterTab::All,
                 };
@@ -461,7 +583,8 @@ pub fn handle_filter_modal_keys(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
             let max_index = match app.posts_state.filter_modal_state.selected_tab {
-                FilterTab::All => 0,
+                FilterTab::All | FilterTab::Reviews | FilterTab::Later => 0,
+                FilterTab::Jobs => JOB_FILTER_CHOICES.len() - 1,
                 FilterTab::Hashtags => app.posts_state.filter_modal_state.hashtag_list.len(),
                 FilterTab::Users => app
                     .posts_state
@@ -496,194 +619,3 @@ pub fn handle_filter_modal_keys(app: &mut App, key: KeyEvent) -> Result<()> {
     }
     Ok(())
 }
-
-pub fn handle_post_detail_keys(app: &mut App, key: KeyEvent) -> Result<()> {
-    if let Some(detail_state) = &app.post_detail_state {
-        if detail_state.show_full_post_modal {
-            return handle_full_post_modal_keys(app, key);
-        }
-        if detail_state.show_reply_composer {
-            return handle_reply_composer_keys(app, key);
-        }
-        if detail_state.show_delete_confirmation {
-            return app.handle_delete_confirmation_keys(key);
-        }
-    }
-
-    match key.code {
-        KeyCode::Enter => {
-            app.open_full_post_modal();
-        }
-        KeyCode::Esc => {
-            app.close_post_detail();
-        }
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
-            if let Some(detail_state) = &mut app.post_detail_state {
-                let direct_reply_count = detail_state
-                    .replies
-                    .iter()
-                    .filter(|reply| {
-                        if let Some(parent_id) = reply.parent_post_id {
-                            !detail_state.replies.iter().any(|r| r.id == parent_id)
-                        } else {
-                            false
-                        }
-                    })
-                    .count();
-
-                if direct_reply_count > 0 {
-                    let next_index = match detail_state.reply_list_state.selected() {
-                        None => 0,
-                        Some(i) if i < direct_reply_count - 1 => i + 1,
-                        Some(i) => i,
-                    };
-                    detail_state.reply_list_state.select(Some(next_index));
-                }
-            }
-        }
-        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
-            if let Some(detail_state) = &mut app.post_detail_state {
-                let direct_reply_count = detail_state
-                    .replies
-                    .iter()
-                    .filter(|reply| {
-                        if let Some(parent_id) = reply.parent_post_id {
-                            !detail_state.replies.iter().any(|r| r.id == parent_id)
-                        } else {
-                            false
-                        }
-                    })
-                    .count();
-
-                if direct_reply_count > 0 {
-                    match detail_state.reply_list_state.selected() {
-                        None => {}
-                        Some(0) => {
-                            detail_state.reply_list_state.select(None);
-                        }
-                        Some(i) => {
-                            detail_state.reply_list_state.select(Some(i - 1));
-                        }
-                    }
-                }
-            }
-        }
-        KeyCode::Char('r') | KeyCode::Char('R') => {
-            if let Some(detail_state) = &app.post_detail_state {
-                if let Some(post) = &detail_state.post {
-                    app.open_composer_reply(
-                        post.id,
-                        post.author_username.clone(),
-                        post.content.clone(),
-                    );
-                }
-            }
-        }
-        KeyCode::Char('c') | KeyCode::Char('C') => {
-            if let Some(detail_state) = &app.post_detail_state {
-                if let Some(selected_reply_index) = detail_state.reply_list_state.selected() {
-                    let direct_replies: Vec<&Post> = detail_state
-                        .replies
-                        .iter()
-                        .filter(|reply| {
-                            if let Some(parent_id) = reply.parent_post_id {
-                                !detail_state.replies.iter().any(|r| r.id == parent_id)
-                            } else {
-                                false
-                            }
-                        })
-                        .collect();
-
-                    if let Some(reply) = direct_replies.get(selected_reply_index) {
-                        app.open_composer_reply(
-                            reply.id,
-                            reply.author_username.clone(),
-                            reply.content.clone(),
-                        );
-                    }
-                }
-            }
-        }
-        KeyCode::Char('x') | KeyCode::Char('X') => {
-            app.show_delete_confirmation();
-        }
-        KeyCode::Char('u') | KeyCode::Char('U') => {}
-        KeyCode::Char('d') | KeyCode::Char('D') => {}
-        KeyCode::Char('p') | KeyCode::Char('P') => {}
-        _ => {}
-    }
-    Ok(())
-}
-
-pub fn handle_full_post_modal_keys(app: &mut App, key: KeyEvent) -> Result<()> {
-    match key.code {
-        KeyCode::Esc => {
-            app.close_full_post_modal();
-        }
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
-            app.modal_next_reply();
-        }
-        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
-            app.modal_previous_reply();
-        }
-        KeyCode::Char(' ') | KeyCode::Enter => {
-            app.modal_toggle_expansion();
-        }
-        KeyCode::Char('r') | KeyCode::Char('R') => {
-            if let Some(detail_state) = &app.post_detail_state {
-                if let Some(modal_post_id) = detail_state.full_post_modal_id {
-                    let post_to_reply = if let Some(post) = &detail_state.post {
-                        if post.id == modal_post_id {
-                            Some(post.clone())
-                        } else {
-                            detail_state
-                                .replies
-                                .iter()
-                                .find(|r| r.id == modal_post_id)
-                                .cloned()
-                        }
-                    } else {
-                        None
-                    };
-
-                    if let Some(post) = post_to_reply {
-                        // Don't close the modal - keep it open so we return to it after submitting
-                        app.open_composer_reply(
-                            post.id,
-                            post.author_username.clone(),
-                            post.content.clone(),
-                        );
-                    }
-                }
-            }
-        }
-        KeyCode::Char('c') | KeyCode::Char('C') => {}
-        KeyCode::Char('u') | KeyCode::Char('U') => {}
-        KeyCode::Char('d') | KeyCode::Char('D') => {}
-        KeyCode::Char('x') | KeyCode::Char('X') => {
-            app.show_delete_confirmation();
-        }
-        _ => {}
-    }
-    Ok(())
-}
-
-pub fn handle_reply_composer_keys(app: &mut App, key: KeyEvent) -> Result<()> {
-    match key.code {
-        KeyCode::Char(c) => {
-            app.add_char_to_reply(c);
-        }
-        KeyCode::Backspace => {
-            app.remove_char_from_reply();
-        }
-        KeyCode::Enter
-            if key
-                .modifiers
-                .contains(crossterm::event::KeyModifiers::CONTROL) => {}
-        KeyCode::Esc => {
-            app.close_reply_composer();
-        }
-        _ => {}
-    }
-    Ok(())
-}
@@ -1,17 +1,68 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
-use fido_types::Post;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use fido_types::{Post, ReplyPolicy, User};
 use ratatui::style::Style;
 use ratatui::widgets::ListState;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tui_textarea::TextArea;
 use uuid::Uuid;
 
 use crate::api::ApiClient;
+use crate::post_tokens::{extract_tokens, PostToken};
+use crate::thread_tree;
+use crate::ui::text_input::TextInput;
 
 pub mod state;
 pub use state::*;
 pub mod handlers;
+pub mod tasks;
+pub use tasks::{TaskKind, TaskResult, TaskRunner};
+pub mod toasts;
+pub use toasts::{ToastLevel, ToastQueue};
+
+/// Per-request timeout for search-as-you-type lookups
+const USER_SEARCH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Quiet period after the last keystroke before a user search is sent
+const USER_SEARCH_DEBOUNCE: Duration = Duration::from_millis(250);
+/// Number of recent user search queries kept in memory
+const USER_SEARCH_CACHE_SIZE: usize = 32;
+/// Page size when loading a user's posts
+const PROFILE_POSTS_PAGE_SIZE: i32 = 25;
+/// Users fetched per page in the social modal
+const SOCIAL_PAGE_SIZE: usize = 50;
+/// Most marked posts opened in the browser at once
+const MAX_BULK_OPEN: usize = 10;
+/// Posts shown in the user profile view
+const PROFILE_VIEW_RECENT_POSTS: i32 = 5;
+/// Status text (80 on the server) plus room for an emoji and "for 12h"
+const STATUS_COMPOSER_MAX_CHARS: usize = 100;
+/// How long someone has to be away before login shows the catch-up screen
+const CATCHUP_AFTER_HOURS: i64 = 12;
+/// Automatic retries for a DM that failed because of the connection
+const DM_AUTO_RETRIES: u32 = 3;
+/// GitHub links asked about at once; the rest wait for the next load
+const MAX_GITHUB_CARDS_PER_LOAD: usize = 20;
+/// Longest text the composer takes for publishing as a gist (the server's
+/// limit for long posts)
+pub const GIST_POST_MAX_CHARS: usize = 20_000;
+/// Posts kept in the reading history; the oldest fall off
+const MAX_READING_HISTORY: usize = 200;
+/// Tag of the countdown toast shown while the server rate limits us
+const RATE_LIMIT_TOAST: &str = "rate-limit";
+/// This client's release, compared with the server's at startup
+pub const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Oldest server release this client works with
+const MIN_SERVER_VERSION: &str = "0.1.0";
+/// Wait before the first automatic DM retry; doubles with each one after
+const DM_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Start of the catch-up window, if the user was last seen long enough ago
+fn catchup_since(
+    last_seen: Option<chrono::DateTime<chrono::Utc>>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    last_seen.filter(|last| now - *last > chrono::Duration::hours(CATCHUP_AFTER_HOURS))
+}
 
 impl App {
     pub fn new() -> Self {
@@ -39,6 +90,10 @@ impl App {
                 github_verification_uri: None,
                 github_poll_interval: None,
                 github_auth_start_time: None,
+                restoring_session: false,
+                saved_accounts: Vec::new(),
+                switching_account: None,
+                remember_accounts: true,
             },
             current_tab: Tab::Posts,
             posts_state: PostsState {
@@ -46,7 +101,6 @@ impl App {
                 list_state: ListState::default(),
                 loading: false,
                 error: None,
-                message: None,
                 show_new_post_modal: false,
                 new_post_content: String::new(),
                 pending_load: false,
@@ -63,20 +117,31 @@ impl App {
                     checked_hashtags: Vec::new(),
                     checked_users: Vec::new(),
                     show_add_hashtag_input: false,
-                    add_hashtag_input: String::new(),
+                    add_hashtag_input: hashtag_input(),
                 },
                 sort_order: "Newest".to_string(),
                 at_end_of_feed: false,
+                awaiting_settings: false,
+                reduced_motion: false,
+                similar: std::collections::HashMap::new(),
+                bulk_selection: None,
+                positions: std::collections::HashMap::new(),
+                pending_restore: None,
+                read_later: Vec::new(),
+                snooze_input: None,
             },
+            channels_state: ChannelsState::default(),
             profile_state: ProfileState {
                 profile: None,
+                stats: None,
                 user_posts: Vec::new(),
+                posts_tab: ProfilePostsTab::default(),
+                has_more_posts: false,
                 list_state: ListState::default(),
                 loading: false,
                 error: None,
                 show_edit_bio_modal: false,
-                edit_bio_content: String::new(),
-                edit_bio_cursor_position: 0,
+                edit_bio: bio_input(),
             },
             dms_state: DMsState {
                 conversations: Vec::new(),
@@ -95,18 +160,26 @@ impl App {
                 },
                 messages_scroll_offset: 0,
                 show_new_conversation_modal: false,
-                new_conversation_username: String::new(),
+                new_conversation_username: username_input(),
                 pending_conversation_username: None,
                 unread_counts: std::collections::HashMap::new(),
                 current_conversation_user: None,
+                selected_message: None,
+                pending_attachment: None,
+                expanded_attachments: std::collections::HashSet::new(),
+                outbox: Vec::new(),
+                drafts: std::collections::HashMap::new(),
+                last_conversation: None,
+                follow_ups: crate::config::DmFollowUps::default(),
+                reminder_input: None,
                 needs_message_load: false,
                 show_dm_error_modal: false,
                 dm_error_message: String::new(),
                 failed_username: None,
+                share_prefill: None,
                 available_mutual_friends: Vec::new(),
                 new_conversation_selected_index: 0,
                 new_conversation_search_mode: false,
-                new_conversation_search_query: String::new(),
             },
             settings_state: SettingsState {
                 config: None,
@@ -115,10 +188,11 @@ impl App {
                 loading: false,
                 error: None,
                 selected_field: SettingsField::ColorScheme,
-                max_posts_input: String::new(),
+                max_posts_input: max_posts_input(),
                 has_unsaved_changes: false,
                 show_save_confirmation: false,
                 pending_tab: None,
+                pinned_hashtags_input: None,
             },
             post_detail_state: None,
             viewing_post_detail: false,
@@ -139,12 +213,13 @@ impl App {
                 error: None,
                 loading: false,
                 return_to_modal_after_profile: false,
+                pages: std::collections::HashMap::new(),
             },
             hashtags_state: HashtagsState {
                 hashtags: Vec::new(),
                 show_hashtags_modal: false,
                 show_add_hashtag_input: false,
-                add_hashtag_name: String::new(),
+                add_hashtag_name: hashtag_input(),
                 selected_hashtag: 0,
                 error: None,
                 loading: false,
@@ -158,160 +233,53 @@ impl App {
                 selected_index: 0,
                 loading: false,
                 error: None,
+                search_due: None,
+                cache: SearchCache::new(USER_SEARCH_CACHE_SIZE),
             },
             user_profile_view: None,
             log_config: crate::logging::LogConfig::default(),
+            task_runner: TaskRunner::new(),
+            server_config: crate::server_config::ServerConfigManager::new()
+                .expect("Failed to initialize server profiles"),
+            active_profile: None,
+            server_switcher: ServerSwitcherState::default(),
+            toasts: ToastQueue::default(),
+            quiet_state: QuietState::default(),
+            accessibility: AccessibilityState::default(),
+            log_viewer: LogViewerState::default(),
+            recovered_draft: None,
+            digest: None,
+            catchup: None,
+            github_suggestions: None,
+            link_preview: LinkPreviewState::default(),
+            github_cards: std::collections::HashMap::new(),
+            gists: std::collections::HashMap::new(),
+            share_menu: ShareMenuState::default(),
+            pending_clipboard: None,
+            drafts: DraftsState::default(),
+            events: EventsState::default(),
+            highlights: HighlightsState::default(),
+            reading_history: ReadingHistoryState::default(),
+            hashtag_digest: HashtagDigestState::default(),
+            translations: TranslationState::default(),
+            exact_time_post: None,
+            raw_diff_posts: std::collections::HashSet::new(),
+            focused_token: None,
+            instance_features: None,
+            watched_threads: std::collections::HashMap::new(),
+            crossposts: CrosspostState::default(),
+            reauth: ReauthState::default(),
+            diagnostics: DiagnosticsState::default(),
+            version_check: None,
+            rate_limit_waiting: false,
         }
     }
 
     /// Create a new App with a custom server URL
     pub fn with_server_url(server_url: String) -> Self {
-        let config_manager =
-            crate::config::ConfigManager::new().expect("Failed to initialize config manager");
-        let instance_id = crate::config::ConfigManager::generate_instance_id();
-
-        // Clean up old sessions on startup
-        let _ = config_manager.cleanup_old_sessions();
-
         Self {
-            running: true,
-            current_screen: Screen::Auth,
             api_client: ApiClient::new(server_url),
-            auth_state: AuthState {
-                test_users: Vec::new(),
-                selected_index: 0,
-                loading: false,
-                error: None,
-                current_user: None,
-                show_github_option: true,
-                github_auth_in_progress: false,
-                github_device_code: None,
-                github_user_code: None,
-                github_verification_uri: None,
-                github_poll_interval: None,
-                github_auth_start_time: None,
-            },
-            current_tab: Tab::Posts,
-            posts_state: PostsState {
-                posts: Vec::new(),
-                list_state: ListState::default(),
-                loading: false,
-                error: None,
-                message: None,
-                show_new_post_modal: false,
-                new_post_content: String::new(),
-                pending_load: false,
-                current_filter: PostFilter::All,
-                show_filter_modal: false,
-                filter_modal_state: FilterModalState {
-                    selected_tab: FilterTab::All,
-                    hashtag_list: Vec::new(),
-                    user_list: Vec::new(),
-                    selected_index: 0,
-                    search_input: String::new(),
-                    search_mode: false,
-                    search_results: Vec::new(),
-                    checked_hashtags: Vec::new(),
-                    checked_users: Vec::new(),
-                    show_add_hashtag_input: false,
-                    add_hashtag_input: String::new(),
-                },
-                sort_order: "Newest".to_string(),
-                at_end_of_feed: false,
-            },
-            profile_state: ProfileState {
-                profile: None,
-                user_posts: Vec::new(),
-                list_state: ListState::default(),
-                loading: false,
-                error: None,
-                show_edit_bio_modal: false,
-                edit_bio_content: String::new(),
-                edit_bio_cursor_position: 0,
-            },
-            dms_state: DMsState {
-                conversations: Vec::new(),
-                selected_conversation_index: None, // No conversation selected by default
-                messages: Vec::new(),
-                loading: false,
-                error: None,
-                message_input: String::new(),
-                message_textarea: {
-                    let mut textarea = TextArea::default();
-                    textarea.set_cursor_line_style(Style::default());
-                    textarea.set_style(Style::default());
-                    // Enable hard tab indent for better wrapping behavior
-                    textarea.set_hard_tab_indent(true);
-                    textarea
-                },
-                messages_scroll_offset: 0,
-                show_new_conversation_modal: false,
-                new_conversation_username: String::new(),
-                pending_conversation_username: None,
-                unread_counts: std::collections::HashMap::new(),
-                current_conversation_user: None,
-                needs_message_load: false,
-                show_dm_error_modal: false,
-                dm_error_message: String::new(),
-                failed_username: None,
-                available_mutual_friends: Vec::new(),
-                new_conversation_selected_index: 0,
-                new_conversation_search_mode: false,
-                new_conversation_search_query: String::new(),
-            },
-            settings_state: SettingsState {
-                config: None,
-                original_config: None,
-                original_max_posts_input: String::new(),
-                loading: false,
-                error: None,
-                selected_field: SettingsField::ColorScheme,
-                max_posts_input: String::new(),
-                has_unsaved_changes: false,
-                show_save_confirmation: false,
-                pending_tab: None,
-            },
-            post_detail_state: None,
-            viewing_post_detail: false,
-            config_manager,
-            instance_id,
-            show_help: false,
-            input_mode: InputMode::Navigation,
-            composer_state: ComposerState::new(),
-            friends_state: FriendsState {
-                show_friends_modal: false,
-                selected_tab: SocialTab::Following,
-                following: Vec::new(),
-                followers: Vec::new(),
-                mutual_friends: Vec::new(),
-                selected_index: 0,
-                search_query: String::new(),
-                search_mode: false,
-                error: None,
-                loading: false,
-                return_to_modal_after_profile: false,
-            },
-            hashtags_state: HashtagsState {
-                hashtags: Vec::new(),
-                show_hashtags_modal: false,
-                show_add_hashtag_input: false,
-                add_hashtag_name: String::new(),
-                selected_hashtag: 0,
-                error: None,
-                loading: false,
-                show_unfollow_confirmation: false,
-                hashtag_to_unfollow: None,
-            },
-            user_search_state: UserSearchState {
-                show_modal: false,
-                search_query: String::new(),
-                search_results: Vec::new(),
-                selected_index: 0,
-                loading: false,
-                error: None,
-            },
-            user_profile_view: None,
-            log_config: crate::logging::LogConfig::default(),
+            ..Self::new()
         }
     }
 
@@ -320,119 +288,1074 @@ impl App {
         self.show_help = !self.show_help;
     }
 
-    /// Switch to next tab
-    pub fn next_tab(&mut self) {
-        let next = self.current_tab.next();
-        self.try_switch_tab(next);
+    /// Reopen the composer with the draft recovered from a crash
+    pub fn restore_recovered_draft(&mut self) {
+        let Some(draft) = self.recovered_draft.take() else {
+            return;
+        };
+        crate::crash::discard_draft(&self.config_manager.crash_dir());
+
+        match draft.mode {
+            ComposerMode::NewPost => self.open_composer_new_post(),
+            ComposerMode::Reply {
+                parent_post_id,
+                parent_author,
+                parent_content,
+            } => self.open_composer_reply(parent_post_id, parent_author, parent_content),
+            ComposerMode::EditPost { post_id } => {
+                self.open_composer_edit_post(post_id, draft.content);
+                return;
+            }
+            ComposerMode::EditBio => {
+                self.open_composer_edit_bio(draft.content);
+                return;
+            }
+            ComposerMode::SetStatus => {
+                self.open_composer_set_status(draft.content);
+                return;
+            }
+            ComposerMode::CrossPost {
+                message_id,
+                other_username,
+            } => {
+                self.open_composer_crosspost(message_id, other_username, draft.content);
+                return;
+            }
+            ComposerMode::NewJob(form) => self.start_job_step(form),
+        }
+        self.composer_state.textarea.insert_str(draft.content);
     }
 
-    /// Switch to previous tab
-    pub fn previous_tab(&mut self) {
-        let prev = self.current_tab.previous();
-        self.try_switch_tab(prev);
+    pub fn discard_recovered_draft(&mut self) {
+        if self.recovered_draft.take().is_some() {
+            crate::crash::discard_draft(&self.config_manager.crash_dir());
+        }
     }
 
-    /// Try to switch tab, checking for unsaved changes
-    fn try_switch_tab(&mut self, new_tab: Tab) {
-        // If we're leaving settings with unsaved changes, show confirmation
-        if self.current_tab == Tab::Settings && self.settings_state.has_unsaved_changes {
-            self.settings_state.show_save_confirmation = true;
-            self.settings_state.pending_tab = Some(new_tab);
-        } else {
-            self.current_tab = new_tab;
-        }
+    /// Open or close the log viewer, starting at the newest lines
+    pub fn toggle_log_viewer(&mut self) {
+        self.log_viewer.show = !self.log_viewer.show;
+        self.log_viewer.searching = false;
+        self.log_viewer.scroll = 0;
     }
 
-    /// Confirm tab switch or logout without saving
-    pub fn confirm_discard_changes(&mut self) {
-        self.settings_state.has_unsaved_changes = false;
-        self.settings_state.show_save_confirmation = false;
+    /// Ask the server which release it runs, so an incompatible pairing is
+    /// explained up front instead of as failed requests
+    pub fn spawn_version_check(&mut self) {
+        self.version_check = None;
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::VersionCheck, async move {
+            let result = match client.get_version().await {
+                Ok(version) => Ok(Some(version)),
+                Err(crate::api::ApiError::NotFound(_)) => Ok(None),
+                Err(e) => Err(e.to_string()),
+            };
+            TaskResult::VersionChecked(result)
+        });
+    }
 
-        if let Some(pending_tab) = self.settings_state.pending_tab.take() {
-            // Switch to pending tab
-            self.current_tab = pending_tab;
-        } else {
-            // No pending tab means logout/exit was requested
-            // Clear session file
-            if let Err(e) = self.config_manager.delete_session(&self.instance_id) {
-                eprintln!("Warning: Failed to delete session: {}", e);
+    fn apply_version_check(&mut self, result: Result<Option<fido_types::ServerVersion>, String>) {
+        let server = match result {
+            Ok(Some(server)) => server,
+            Ok(None) => {
+                log::info!("Server predates GET /version; skipping the version check");
+                return;
+            }
+            // Connection trouble shows up in the loads that follow
+            Err(e) => {
+                log::debug!("Version check failed: {}", e);
+                return;
             }
+        };
 
-            // Reset app state
-            self.auth_state.current_user = None;
-            self.current_screen = Screen::Auth;
-            self.posts_state.posts.clear();
-            self.profile_state.profile = None;
-            self.dms_state.conversations.clear();
-            self.dms_state.messages.clear();
+        let compatibility = fido_types::version::check(CLIENT_VERSION, &server, MIN_SERVER_VERSION);
+        match &compatibility {
+            fido_types::version::Compatibility::UpgradeAvailable { latest } => {
+                self.toasts.info(format!(
+                    "fido {} is available (you have {}): cargo install fido",
+                    latest, CLIENT_VERSION
+                ));
+            }
+            incompatible if incompatible.is_incompatible() => {
+                log::warn!(
+                    "Server {} and client {} are incompatible: {:?}",
+                    server.version,
+                    CLIENT_VERSION,
+                    incompatible
+                );
+                // Whatever is still loading would only fail to parse
+                self.task_runner.cancel_all();
+                self.auth_state.restoring_session = false;
+            }
+            _ => {}
         }
+        self.diagnostics.server_version = Some(server.version);
+        self.version_check = Some(compatibility);
     }
 
-    /// Cancel tab switch
-    pub fn cancel_tab_switch(&mut self) {
-        self.settings_state.show_save_confirmation = false;
-        self.settings_state.pending_tab = None;
+    /// The server and this client can't work together
+    pub fn version_incompatible(&self) -> bool {
+        self.version_check
+            .as_ref()
+            .is_some_and(|compatibility| compatibility.is_incompatible())
+    }
+
+    /// While the server is rate limiting us, count down in a toast. Returns
+    /// true once the wait is over so the caller can retry what's on screen.
+    pub fn tick_rate_limit(&mut self) -> bool {
+        match self.api_client.rate_limit_remaining() {
+            Some(remaining) => {
+                let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                self.toasts.upsert(
+                    RATE_LIMIT_TOAST,
+                    ToastLevel::Info,
+                    format!("Too many requests – retrying in {}s", secs),
+                    remaining + Duration::from_secs(1),
+                );
+                self.rate_limit_waiting = true;
+                false
+            }
+            None if self.rate_limit_waiting => {
+                self.rate_limit_waiting = false;
+                self.toasts.dismiss(RATE_LIMIT_TOAST);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Clear expired messages (auto-clear after 3 seconds)
-    pub fn clear_expired_messages(&mut self) {
-        let now = std::time::Instant::now();
-        let duration = std::time::Duration::from_secs(3);
-
-        // Clear posts state message if expired
-        if let Some((_, timestamp)) = &self.posts_state.message {
-            if now.duration_since(*timestamp) > duration {
-                self.posts_state.message = None;
-            }
+    /// Open or close the diagnostics screen; opening pings right away
+    pub fn toggle_diagnostics(&mut self) {
+        self.diagnostics.show = !self.diagnostics.show;
+        if self.diagnostics.show {
+            self.spawn_diagnostics_ping();
+        } else {
+            self.task_runner.cancel(TaskKind::DiagnosticsPing);
         }
+    }
 
-        // Clear post detail state message if expired
-        if let Some(detail_state) = &mut self.post_detail_state {
-            if let Some((_, timestamp)) = &detail_state.message {
-                if now.duration_since(*timestamp) > duration {
-                    detail_state.message = None;
-                }
-            }
+    /// Keys while diagnostics are open: `r` pings now, Esc/q close
+    pub fn handle_diagnostics_keys(&mut self, key: KeyEvent) -> Result<()> {
+        if handlers::is_diagnostics_key(&key) {
+            self.toggle_diagnostics();
+            return Ok(());
         }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.toggle_diagnostics(),
+            KeyCode::Char('r') => self.spawn_diagnostics_ping(),
+            _ => {}
+        }
+        Ok(())
     }
 
-    /// Check if we need to load data when switching tabs
-    pub fn needs_tab_data_load(&self) -> bool {
-        matches!(self.current_tab, Tab::Profile | Tab::DMs | Tab::Settings)
+    /// Time a `GET /version` round trip for the diagnostics screen
+    pub fn spawn_diagnostics_ping(&mut self) {
+        self.diagnostics.last_ping = Some(Instant::now());
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::DiagnosticsPing, async move {
+            let started = Instant::now();
+            let result = client
+                .get_version()
+                .await
+                .map(|version| (version.version, started.elapsed()))
+                .map_err(|e| e.to_string());
+            TaskResult::DiagnosticsPinged(result)
+        });
     }
 
-    /// Logout and clear session
-    pub async fn logout(&mut self) -> Result<()> {
-        // Check for unsaved changes in Settings
-        if self.current_tab == Tab::Settings && self.settings_state.has_unsaved_changes {
-            self.settings_state.show_save_confirmation = true;
-            self.settings_state.pending_tab = None; // None indicates logout
+    /// Keys while the log viewer is open: `/` searches, `f` cycles the level
+    /// filter, j/k and PageUp/PageDown scroll, g/G jump to oldest/newest
+    pub fn handle_log_viewer_keys(&mut self, key: KeyEvent) -> Result<()> {
+        if handlers::is_log_viewer_key(&key) {
+            self.toggle_log_viewer();
             return Ok(());
         }
 
-        // Call server logout endpoint to invalidate session (best effort)
-        // We don't fail if this errors since we'll clear local session anyway
-        if let Ok(session_store) = crate::session::SessionStore::new() {
-            if let Ok(Some(token)) = session_store.load() {
-                let _ = self.api_client.logout(token).await;
-            }
-            
-            // Delete local session file
-            if let Err(e) = session_store.delete() {
-                log::warn!("Failed to delete session file: {}", e);
+        let viewer = &mut self.log_viewer;
+        if viewer.searching {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter => viewer.searching = false,
+                KeyCode::Backspace => {
+                    viewer.search.pop();
+                    viewer.scroll = 0;
+                }
+                KeyCode::Char(c) => {
+                    viewer.search.push(c);
+                    viewer.scroll = 0;
+                }
+                _ => {}
             }
+            return Ok(());
         }
 
-        // Also clear old config_manager session for backwards compatibility
-        if let Err(e) = self.config_manager.delete_session(&self.instance_id) {
-            log::warn!("Failed to delete config_manager session: {}", e);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.toggle_log_viewer(),
+            KeyCode::Char('/') => viewer.searching = true,
+            KeyCode::Char('f') => viewer.cycle_level(),
+            KeyCode::Char('k') | KeyCode::Up => viewer.scroll += 1,
+            KeyCode::Char('j') | KeyCode::Down => viewer.scroll = viewer.scroll.saturating_sub(1),
+            KeyCode::PageUp => viewer.scroll += 10,
+            KeyCode::PageDown => viewer.scroll = viewer.scroll.saturating_sub(10),
+            KeyCode::Char('g') => viewer.scroll = usize::MAX,
+            KeyCode::Char('G') => viewer.scroll = 0,
+            _ => {}
         }
 
-        // Reset app state
-        self.auth_state.current_user = None;
-        self.current_screen = Screen::Auth;
-        self.posts_state.posts.clear();
-        self.profile_state.profile = None;
+        let matching = crate::logging::recent_lines()
+            .iter()
+            .filter(|line| self.log_viewer.matches(line))
+            .count();
+        self.log_viewer.scroll = self.log_viewer.scroll.min(matching.saturating_sub(1));
+        Ok(())
+    }
+
+    /// Show the link confirmation for the selected post (feed or thread)
+    pub fn open_link_preview(&mut self) {
+        let post = if self.viewing_post_detail {
+            self.get_selected_post_in_modal()
+        } else {
+            self.posts_state
+                .list_state
+                .selected()
+                .and_then(|index| self.posts_state.posts.get(index).cloned())
+        };
+        let Some(post) = post else {
+            return;
+        };
+
+        let mut urls = crate::links::extract_urls(&post.content);
+        // What a review request or job post links to comes first
+        let kind_url = match &post.kind {
+            Some(fido_types::PostKind::ReviewRequest(review)) => Some(&review.url),
+            Some(fido_types::PostKind::Job(job)) => Some(&job.url),
+            _ => None,
+        };
+        if let Some(kind_url) = kind_url {
+            urls.retain(|url| url != kind_url);
+            urls.insert(0, kind_url.clone());
+        }
+        if urls.is_empty() {
+            self.toasts.info("No links in this post");
+            return;
+        }
+        self.link_preview = LinkPreviewState {
+            show: true,
+            urls,
+            ..LinkPreviewState::default()
+        };
+        self.spawn_link_preview();
+    }
+
+    /// Show the share menu for the selected post (feed or thread)
+    pub fn open_share_menu(&mut self) {
+        let post = if self.viewing_post_detail {
+            self.get_selected_post_in_modal()
+        } else {
+            self.posts_state
+                .list_state
+                .selected()
+                .and_then(|index| self.posts_state.list_index_to_post_index(index))
+                .and_then(|index| self.posts_state.posts.get(index).cloned())
+        };
+        let Some(post) = post else {
+            return;
+        };
+        if post.is_deleted {
+            self.toasts.info("Deleted posts can't be shared");
+            return;
+        }
+        self.share_menu = ShareMenuState {
+            show: true,
+            post: Some(post),
+            selected: 0,
+        };
+    }
+
+    pub fn close_share_menu(&mut self) {
+        self.share_menu = ShareMenuState::default();
+    }
+
+    /// Web address of a post on the current server
+    pub fn post_permalink(&self, post_id: Uuid) -> String {
+        format!(
+            "{}/p/{}",
+            self.api_client.base_url().trim_end_matches('/'),
+            post_id
+        )
+    }
+
+    /// Keys while the share menu is open: j/k pick an action, Enter does it
+    pub fn handle_share_menu_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let count = ShareAction::ALL.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.close_share_menu(),
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => {
+                self.share_menu.selected = (self.share_menu.selected + 1) % count;
+            }
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => {
+                self.share_menu.selected = (self.share_menu.selected + count - 1) % count;
+            }
+            KeyCode::Enter => {
+                if let Some(action) = ShareAction::ALL.get(self.share_menu.selected) {
+                    self.share_post(*action);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Do `action` with the post in the share menu and close it
+    pub fn share_post(&mut self, action: ShareAction) {
+        let Some(post) = self.share_menu.post.take() else {
+            return;
+        };
+        self.close_share_menu();
+        let permalink = self.post_permalink(post.id);
+
+        match action {
+            ShareAction::CopyPermalink => {
+                self.pending_clipboard = Some(permalink);
+                self.toasts.success("Copied link to post");
+            }
+            ShareAction::CopyQuote => {
+                self.pending_clipboard = Some(markdown_quote(&post, &permalink));
+                self.toasts.success("Copied post as a quote");
+            }
+            ShareAction::OpenInBrowser => match webbrowser::open(&permalink) {
+                Ok(()) => self.toasts.success("Opened post in browser"),
+                Err(e) => self.toasts.error(format!("Couldn't open browser: {}", e)),
+            },
+            ShareAction::SendAsDm => {
+                // Pick a mutual friend in the DMs tab's new conversation
+                // modal; the link lands in the message input after that
+                if self.viewing_post_detail {
+                    self.close_post_detail();
+                }
+                self.current_tab = Tab::DMs;
+                self.dms_state.share_prefill =
+                    Some(format!("@{}'s post: {}", post.author_username, permalink));
+                self.dms_state.show_new_conversation_modal = true;
+                self.dms_state.new_conversation_username.clear();
+                self.input_mode = InputMode::Typing;
+            }
+        }
+    }
+
+    pub fn close_link_preview(&mut self) {
+        self.task_runner.cancel(TaskKind::LinkPreview);
+        self.link_preview = LinkPreviewState::default();
+    }
+
+    /// Resolve the selected link on the server
+    fn spawn_link_preview(&mut self) {
+        let Some(url) = self.link_preview.urls.get(self.link_preview.selected).cloned() else {
+            return;
+        };
+        self.link_preview.preview = None;
+        self.link_preview.loading = true;
+        self.link_preview.unchecked = false;
+        self.link_preview.confirm_unchecked = false;
+
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::LinkPreview, async move {
+            let result = client.preview_link(&url).await.map_err(|e| e.to_string());
+            TaskResult::LinkPreviewLoaded { url, result }
+        });
+    }
+
+    /// Fetch cards for GitHub links in the feed and the open thread that
+    /// haven't been asked about yet
+    pub fn load_github_cards(&mut self) {
+        let thread = self.post_detail_state.iter().flat_map(|detail| {
+            detail.post.iter().chain(detail.replies.iter())
+        });
+        let mut urls: Vec<String> = Vec::new();
+        for post in self.posts_state.posts.iter().chain(thread) {
+            if post.is_deleted {
+                continue;
+            }
+            for url in crate::links::extract_urls(&post.content) {
+                if crate::links::is_github_link(&url)
+                    && !self.github_cards.contains_key(&url)
+                    && !urls.contains(&url)
+                {
+                    urls.push(url);
+                }
+            }
+        }
+        if urls.is_empty() {
+            return;
+        }
+        urls.truncate(MAX_GITHUB_CARDS_PER_LOAD);
+
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::GitHubCards, async move {
+            let mut cards = Vec::new();
+            for url in urls {
+                let result = client.get_github_card(&url).await.map_err(|e| e.to_string());
+                cards.push((url, result));
+            }
+            TaskResult::GitHubCardsLoaded(cards)
+        });
+    }
+
+    /// Fetch the gist linked from the open thread's post, to read inline
+    pub fn load_thread_gist(&mut self) {
+        let Some(post) = self
+            .post_detail_state
+            .as_ref()
+            .and_then(|detail| detail.post.as_ref())
+            .filter(|post| !post.is_deleted)
+        else {
+            return;
+        };
+        let Some(url) = crate::links::extract_urls(&post.content)
+            .into_iter()
+            .find(|url| crate::links::is_gist_link(url))
+        else {
+            return;
+        };
+        if self.gists.contains_key(&url) {
+            return;
+        }
+
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Gist, async move {
+            let result = client.get_gist(&url).await.map_err(|e| e.to_string());
+            TaskResult::GistLoaded { url, result }
+        });
+    }
+
+    fn apply_link_preview(&mut self, url: String, result: Result<fido_types::LinkPreview, String>) {
+        let state = &mut self.link_preview;
+        if !state.show || state.urls.get(state.selected) != Some(&url) {
+            return;
+        }
+        state.loading = false;
+        match result {
+            Ok(preview) => state.preview = Some(preview),
+            Err(e) => {
+                log::warn!("Link preview failed for {}: {}", url, e);
+                state.preview = crate::links::local_preview(&url);
+                state.unchecked = true;
+            }
+        }
+    }
+
+    /// Show the selected post in the thread modal translated into the
+    /// terminal's language, or back in the original if it already is
+    /// Show or hide the exact time of the selected post, in the feed or the
+    /// thread modal
+    pub fn toggle_exact_time(&mut self) {
+        let Some(post_id) = self.post_under_cursor() else {
+            return;
+        };
+        self.exact_time_post = if self.exact_time_post == Some(post_id) {
+            None
+        } else {
+            Some(post_id)
+        };
+    }
+
+    /// Switch the selected post's diff between colored and as typed, in the
+    /// feed or the thread modal
+    pub fn toggle_raw_diff(&mut self) {
+        let Some(post_id) = self.post_under_cursor() else {
+            return;
+        };
+        if !self.raw_diff_posts.remove(&post_id) {
+            self.raw_diff_posts.insert(post_id);
+        }
+    }
+
+    /// Whether `post_id`'s diff, if it has one, is shown colored
+    pub fn shows_diff(&self, post_id: Uuid) -> bool {
+        !self.raw_diff_posts.contains(&post_id)
+    }
+
+    /// The post selected in the thread modal when it's open, or else in the
+    /// feed
+    fn post_under_cursor(&self) -> Option<Uuid> {
+        if self.viewing_post_detail {
+            self.get_selected_post_in_modal().map(|post| post.id)
+        } else {
+            self.posts_state
+                .list_state
+                .selected()
+                .and_then(|index| self.posts_state.list_index_to_post_index(index))
+                .and_then(|index| self.posts_state.posts.get(index))
+                .map(|post| post.id)
+        }
+    }
+
+    /// The post selected in the feed, when the feed itself has the keyboard
+    fn selected_feed_post(&self) -> Option<&Post> {
+        if self.current_tab != Tab::Posts
+            || self.viewing_post_detail
+            || self.posts_state.show_new_post_modal
+        {
+            return None;
+        }
+        self.posts_state
+            .list_state
+            .selected()
+            .and_then(|index| self.posts_state.list_index_to_post_index(index))
+            .and_then(|index| self.posts_state.posts.get(index))
+    }
+
+    /// Position of the focused hashtag, mention or link in `post_id`, if
+    /// that post is still the selected one
+    pub fn focused_token_in(&self, post_id: Uuid) -> Option<usize> {
+        let (focused_post, index) = self.focused_token?;
+        (focused_post == post_id && self.selected_feed_post()?.id == post_id).then_some(index)
+    }
+
+    /// The focused token itself
+    pub fn focused_post_token(&self) -> Option<PostToken> {
+        let post = self.selected_feed_post()?;
+        let index = self.focused_token_in(post.id)?;
+        extract_tokens(&post.content).into_iter().nth(index)
+    }
+
+    /// Tab inside the selected post: focus its next hashtag, mention or
+    /// link. False once past the last one (or with none), so Tab can fall
+    /// through to switching tabs.
+    pub fn focus_next_token(&mut self) -> bool {
+        let Some(post) = self.selected_feed_post() else {
+            return false;
+        };
+        let (post_id, count) = (post.id, extract_tokens(&post.content).len());
+        let next = self.focused_token_in(post_id).map_or(0, |index| index + 1);
+        self.focused_token = (next < count).then_some((post_id, next));
+        self.focused_token.is_some()
+    }
+
+    /// Shift+Tab inside the selected post; false when nothing was focused
+    pub fn focus_previous_token(&mut self) -> bool {
+        let Some(post_id) = self.selected_feed_post().map(|post| post.id) else {
+            return false;
+        };
+        let Some(index) = self.focused_token_in(post_id) else {
+            return false;
+        };
+        self.focused_token = index.checked_sub(1).map(|previous| (post_id, previous));
+        true
+    }
+
+    /// Drop the token focus; false when there wasn't one
+    pub fn clear_token_focus(&mut self) -> bool {
+        let had_focus = self.focused_post_token().is_some();
+        self.focused_token = None;
+        had_focus
+    }
+
+    /// Enter on the focused token: a hashtag filters the feed, a mention
+    /// opens that user's profile, a link goes through the usual link
+    /// confirmation
+    pub async fn open_focused_token(&mut self) -> Result<()> {
+        let Some(token) = self.focused_post_token() else {
+            return Ok(());
+        };
+        self.focused_token = None;
+        match token {
+            PostToken::Hashtag(tag) => self.apply_filter(PostFilter::Hashtag(tag)).await?,
+            PostToken::Mention(username) => {
+                match self.api_client.search_users(username.clone()).await {
+                    Ok(results) => {
+                        match results
+                            .into_iter()
+                            .find(|user| user.username.eq_ignore_ascii_case(&username))
+                        {
+                            Some(user) => self.load_user_profile_view(user.id).await?,
+                            None => self.toasts.info(format!("No user named @{}", username)),
+                        }
+                    }
+                    Err(e) => self.toasts.error(format!("Couldn't look up @{}: {}", username, e)),
+                }
+            }
+            PostToken::Url(url) => {
+                self.link_preview = LinkPreviewState {
+                    show: true,
+                    urls: vec![url],
+                    ..LinkPreviewState::default()
+                };
+                self.spawn_link_preview();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn toggle_translation(&mut self) {
+        let Some(post) = self.get_selected_post_in_modal() else {
+            return;
+        };
+        if self.translations.shown.remove(&post.id).is_some() {
+            return;
+        }
+        if post.is_deleted || self.translations.pending == Some(post.id) {
+            return;
+        }
+        if self
+            .instance_features
+            .as_ref()
+            .is_some_and(|features| !features.translation_enabled)
+        {
+            self.toasts.info("This server doesn't offer translation");
+            return;
+        }
+
+        self.translations.pending = Some(post.id);
+        let client = self.api_client.clone();
+        let target = preferred_language();
+        self.task_runner.spawn(TaskKind::Translation, async move {
+            let result = client
+                .translate_post(post.id, &target)
+                .await
+                .map_err(|e| e.to_string());
+            TaskResult::PostTranslated { post_id: post.id, result }
+        });
+    }
+
+    fn apply_translation(&mut self, post_id: Uuid, result: Result<fido_types::PostTranslation, String>) {
+        if self.translations.pending == Some(post_id) {
+            self.translations.pending = None;
+        }
+        match result {
+            Ok(translation) => {
+                self.translations.shown.insert(post_id, translation);
+            }
+            Err(e) => self.toasts.error(format!("Couldn't translate: {}", e)),
+        }
+    }
+
+    /// Keys while the link confirmation is open: Tab/j/k pick a link, Enter
+    /// opens it in the browser (twice when the server couldn't check it),
+    /// Esc cancels
+    pub fn handle_link_preview_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let count = self.link_preview.urls.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.close_link_preview()
+            }
+            KeyCode::Tab | KeyCode::Down | KeyCode::Char('j') if count > 1 => {
+                self.link_preview.selected = (self.link_preview.selected + 1) % count;
+                self.spawn_link_preview();
+            }
+            KeyCode::BackTab | KeyCode::Up | KeyCode::Char('k') if count > 1 => {
+                self.link_preview.selected = (self.link_preview.selected + count - 1) % count;
+                self.spawn_link_preview();
+            }
+            KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                let Some(preview) = self.link_preview.preview.clone() else {
+                    return Ok(());
+                };
+                if preview.blocked {
+                    self.toasts.error(format!(
+                        "{} is blocked on this server",
+                        preview.domain
+                    ));
+                    return Ok(());
+                }
+                if self.link_preview.unchecked && !self.link_preview.confirm_unchecked {
+                    self.link_preview.confirm_unchecked = true;
+                    return Ok(());
+                }
+                match webbrowser::open(&preview.resolved_url) {
+                    Ok(()) => self.toasts.success(format!("Opened {}", preview.domain)),
+                    Err(e) => self.toasts.error(format!("Couldn't open browser: {}", e)),
+                }
+                self.close_link_preview();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Switch to next tab
+    pub fn next_tab(&mut self) {
+        let next = self.current_tab.next();
+        self.try_switch_tab(next);
+    }
+
+    /// Switch to previous tab
+    pub fn previous_tab(&mut self) {
+        let prev = self.current_tab.previous();
+        self.try_switch_tab(prev);
+    }
+
+    /// Try to switch tab, checking for unsaved changes
+    fn try_switch_tab(&mut self, new_tab: Tab) {
+        // If we're leaving settings with unsaved changes, show confirmation
+        if self.current_tab == Tab::Settings && self.settings_state.has_unsaved_changes {
+            self.settings_state.show_save_confirmation = true;
+            self.settings_state.pending_tab = Some(new_tab);
+        } else {
+            self.current_tab = new_tab;
+        }
+    }
+
+    /// Confirm tab switch or logout without saving
+    pub fn confirm_discard_changes(&mut self) {
+        self.settings_state.has_unsaved_changes = false;
+        self.settings_state.show_save_confirmation = false;
+
+        if let Some(pending_tab) = self.settings_state.pending_tab.take() {
+            // Switch to pending tab
+            self.current_tab = pending_tab;
+        } else {
+            // No pending tab means logout/exit was requested
+            // Clear session file
+            if let Err(e) = self.config_manager.delete_session(&self.instance_id) {
+                eprintln!("Warning: Failed to delete session: {}", e);
+            }
+
+            // Reset app state
+            self.auth_state.current_user = None;
+            self.current_screen = Screen::Auth;
+            self.posts_state.posts.clear();
+        self.posts_state.similar.clear();
+            self.profile_state.profile = None;
+            self.profile_state.stats = None;
+            self.dms_state.conversations.clear();
+            self.dms_state.messages.clear();
+        }
+    }
+
+    /// Cancel tab switch
+    pub fn cancel_tab_switch(&mut self) {
+        self.settings_state.show_save_confirmation = false;
+        self.settings_state.pending_tab = None;
+    }
+
+    /// Drop toasts that have been up for their full duration
+    pub fn clear_expired_messages(&mut self) {
+        self.toasts.expire(Instant::now());
+    }
+
+    /// Server reachability for the status bar
+    pub fn connection_state(&self) -> ConnectionState {
+        if !self.api_client.is_offline() {
+            ConnectionState::Online
+        } else if self.task_runner.is_running(TaskKind::Posts) {
+            // The event loop probes with a feed load while offline
+            ConnectionState::Reconnecting
+        } else {
+            ConnectionState::Offline
+        }
+    }
+
+    /// Unread direct messages across all conversations
+    pub fn unread_dm_count(&self) -> usize {
+        self.dms_state.unread_counts.values().sum()
+    }
+
+    /// Count shown after a tab's name in the tab bar, 0 for none. Tabs
+    /// without unread items (and everything, if counts are hidden) get 0.
+    pub fn tab_unread_count(&self, tab: Tab) -> usize {
+        let hidden = self
+            .settings_state
+            .original_config
+            .as_ref()
+            .is_some_and(|config| !config.show_unread_counts);
+        match tab {
+            _ if hidden => 0,
+            Tab::DMs => self.visible_unread_dm_count(),
+            Tab::Channels => self
+                .channels_state
+                .channels
+                .iter()
+                .filter(|channel| channel.joined)
+                .map(|channel| channel.unread_count.max(0) as usize)
+                .sum(),
+            Tab::Posts | Tab::Profile | Tab::Settings => 0,
+        }
+    }
+
+    /// Unread count for the DM badges, which stay put while notifications
+    /// are muted (counts going down still show, since that's the user reading)
+    pub fn visible_unread_dm_count(&self) -> usize {
+        let unread = self.unread_dm_count();
+        match self.quiet_state.unread_at_start {
+            Some(at_start) => unread.min(at_start),
+            None => unread,
+        }
+    }
+
+    /// Why notifications are muted right now, going by the saved settings
+    pub fn quiet_reason(&self) -> Option<QuietReason> {
+        self.quiet_reason_at(chrono::Timelike::hour(&chrono::Local::now()) as u8)
+    }
+
+    fn quiet_reason_at(&self, hour: u8) -> Option<QuietReason> {
+        let config = self.settings_state.original_config.as_ref()?;
+        if config.do_not_disturb {
+            Some(QuietReason::DoNotDisturb)
+        } else if config.in_quiet_hours(hour) {
+            Some(QuietReason::QuietHours)
+        } else {
+            None
+        }
+    }
+
+    /// Show an unsolicited notification, or just count it while muted
+    pub fn notify(&mut self, message: impl Into<String>) {
+        if self.quiet_state.unread_at_start.is_some() {
+            self.quiet_state.missed_notifications += 1;
+        } else {
+            self.toasts.info(message);
+        }
+    }
+
+    /// Plain single-column layouts are on (saved setting)
+    pub fn accessible_layout(&self) -> bool {
+        self.settings_state
+            .original_config
+            .as_ref()
+            .is_some_and(|config| config.accessible_layout)
+    }
+
+    /// Loading animations are off and frames are drawn only on change (saved setting)
+    pub fn reduced_motion(&self) -> bool {
+        self.settings_state
+            .original_config
+            .as_ref()
+            .is_some_and(|config| config.reduced_motion)
+    }
+
+    /// Authors' karma shows next to their usernames (saved setting)
+    pub fn show_karma(&self) -> bool {
+        self.settings_state
+            .original_config
+            .as_ref()
+            .is_some_and(|config| config.show_karma)
+    }
+
+    /// Relative or absolute post times (saved setting)
+    pub fn timestamp_style(&self) -> fido_types::TimestampStyle {
+        self.settings_state
+            .original_config
+            .as_ref()
+            .map(|config| config.timestamp_style)
+            .unwrap_or_default()
+    }
+
+    /// Feed density, preview line limit (0 for none) and whether vote
+    /// counts show in the feed (saved settings)
+    pub fn feed_layout(&self) -> (fido_types::FeedDensity, u8, bool) {
+        match &self.settings_state.original_config {
+            Some(config) => (
+                config.feed_density,
+                config.preview_lines,
+                config.show_vote_counts,
+            ),
+            None => (fido_types::FeedDensity::default(), 0, true),
+        }
+    }
+
+    /// Where announcements go (saved setting)
+    pub fn announce_output(&self) -> fido_types::AnnounceOutput {
+        self.settings_state
+            .original_config
+            .as_ref()
+            .map(|config| config.announcements)
+            .unwrap_or_default()
+    }
+
+    /// Queue what changed since the last frame for announcing: new toasts,
+    /// then the focus if it moved. Called every frame.
+    pub fn queue_announcements(&mut self) {
+        let fresh_toasts = self.toasts.take_fresh();
+        if self.announce_output() == fido_types::AnnounceOutput::Off {
+            self.accessibility.last_focus = None;
+            return;
+        }
+
+        let focus = self.focus_description();
+        let state = &mut self.accessibility;
+        state
+            .pending
+            .extend(fresh_toasts.iter().filter_map(|toast| crate::accessibility::clean(toast)));
+        if state.last_focus.as_ref() != Some(&focus) {
+            state.pending.extend(crate::accessibility::clean(&focus));
+            state.last_focus = Some(focus);
+        }
+    }
+
+    /// Announcements for the event loop to deliver
+    pub fn take_announcements(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.accessibility.pending)
+    }
+
+    /// One sentence saying where the user is and what's selected
+    pub fn focus_description(&self) -> String {
+        if self.current_screen == Screen::Auth {
+            return match self.auth_state.test_users.get(self.auth_state.selected_index) {
+                Some(user) => format!("Log in as {}", user.username),
+                None => "Log in".to_string(),
+            };
+        }
+        if self.show_help {
+            return "Help, every key for this screen. Escape closes".to_string();
+        }
+        if let Some(mode) = &self.composer_state.mode {
+            let what = match mode {
+                ComposerMode::NewPost => "new post".to_string(),
+                ComposerMode::Reply { parent_author, .. } => format!("reply to {}", parent_author),
+                ComposerMode::EditPost { .. } => "post edit".to_string(),
+                ComposerMode::EditBio => "bio".to_string(),
+                ComposerMode::SetStatus => "status".to_string(),
+                ComposerMode::CrossPost { other_username, .. } => {
+                    format!("cross-post of a message with {}", other_username)
+                }
+                ComposerMode::NewJob(form) => format!("job post, {}", form.step.prompt()),
+            };
+            return format!("Writing {}", what);
+        }
+        if self.viewing_post_detail {
+            return match self.get_selected_post_in_modal() {
+                Some(post) => format!("Thread. {}", describe_post(&post)),
+                None => "Thread".to_string(),
+            };
+        }
+
+        match self.current_tab {
+            Tab::Posts => {
+                let posts = &self.posts_state.posts;
+                let selected = self
+                    .posts_state
+                    .list_state
+                    .selected()
+                    .and_then(|list_index| self.posts_state.list_index_to_post_index(list_index))
+                    .and_then(|index| posts.get(index).map(|post| (index, post)));
+                match selected {
+                    Some((index, post)) => format!(
+                        "Post {} of {}. {}",
+                        index + 1,
+                        posts.len(),
+                        describe_post(post)
+                    ),
+                    None => "Posts tab, no posts".to_string(),
+                }
+            }
+            Tab::DMs => {
+                let selected = self
+                    .dms_state
+                    .selected_conversation_index
+                    .and_then(|index| self.dms_state.conversations.get(index));
+                match selected {
+                    Some(convo) if convo.unread_count > 0 => format!(
+                        "Conversation with {}, {} unread",
+                        convo.other_username, convo.unread_count
+                    ),
+                    Some(convo) => format!("Conversation with {}", convo.other_username),
+                    None => "Direct messages, no conversation selected".to_string(),
+                }
+            }
+            Tab::Channels => match self.channels_state.channels.get(self.channels_state.selected) {
+                Some(channel) if channel.unread_count > 0 => format!(
+                    "Channel #{}, {} unread",
+                    channel.name, channel.unread_count
+                ),
+                Some(channel) if channel.joined => format!("Channel #{}, joined", channel.name),
+                Some(channel) => format!("Channel #{}", channel.name),
+                None => "Channels tab, no channels".to_string(),
+            },
+            Tab::Profile => "Profile tab".to_string(),
+            Tab::Settings => format!("Settings, {}", self.settings_state.selected_field.label()),
+        }
+    }
+
+    /// Start or end muting as do-not-disturb and quiet hours come and go.
+    /// Called every frame; when muting ends, what piled up is summarised.
+    pub fn refresh_quiet_mode(&mut self) {
+        let muted = self.quiet_reason().is_some();
+        self.apply_quiet_mode(muted);
+    }
+
+    fn apply_quiet_mode(&mut self, muted: bool) {
+        match (muted, self.quiet_state.unread_at_start) {
+            (true, None) => {
+                self.quiet_state.unread_at_start = Some(self.unread_dm_count());
+            }
+            (false, Some(at_start)) => {
+                let new_messages = self.unread_dm_count().saturating_sub(at_start);
+                let missed = std::mem::take(&mut self.quiet_state).missed_notifications;
+                if new_messages > 0 {
+                    self.toasts.info(format!(
+                        "✉ {} new message{} while notifications were muted",
+                        new_messages,
+                        if new_messages == 1 { "" } else { "s" }
+                    ));
+                } else if missed > 0 {
+                    self.toasts.info(format!(
+                        "{} notification{} while notifications were muted",
+                        missed,
+                        if missed == 1 { "" } else { "s" }
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check if we need to load data when switching tabs
+    pub fn needs_tab_data_load(&self) -> bool {
+        matches!(
+            self.current_tab,
+            Tab::Channels | Tab::Profile | Tab::DMs | Tab::Settings
+        )
+    }
+
+    /// Logout and clear session
+    pub async fn logout(&mut self) -> Result<()> {
+        // Check for unsaved changes in Settings
+        if self.current_tab == Tab::Settings && self.settings_state.has_unsaved_changes {
+            self.settings_state.show_save_confirmation = true;
+            self.settings_state.pending_tab = None; // None indicates logout
+            return Ok(());
+        }
+
+        // Call server logout endpoint to invalidate session (best effort)
+        // We don't fail if this errors since we'll clear local session anyway
+        if self.api_client.uses_cookie_auth() {
+            let _ = self.api_client.web_logout().await;
+        } else if let Ok(session_store) = self.session_store() {
+            if let Ok(Some(token)) = session_store.load() {
+                let _ = self.api_client.logout(token).await;
+            }
+            
+            // Delete local session file
+            if let Err(e) = session_store.delete() {
+                log::warn!("Failed to delete session file: {}", e);
+            }
+        }
+
+        // Also clear old config_manager session for backwards compatibility
+        if let Err(e) = self.config_manager.delete_session(&self.instance_id) {
+            log::warn!("Failed to delete config_manager session: {}", e);
+        }
+
+        // Forget this account only; other saved accounts stay switchable
+        if let Some(user) = &self.auth_state.current_user {
+            let account_store = self
+                .server_config
+                .account_store(self.active_profile.as_deref(), &user.username);
+            if let Err(e) = account_store.and_then(|store| store.delete()) {
+                log::warn!("Failed to delete saved account: {}", e);
+            }
+        }
+        self.refresh_saved_accounts();
+        self.record_last_seen();
+
+        // Reset app state
+        self.task_runner.cancel(TaskKind::Heartbeat);
+        self.auth_state.current_user = None;
+        self.current_screen = Screen::Auth;
+        self.posts_state.posts.clear();
+        self.posts_state.similar.clear();
+        self.profile_state.profile = None;
+        self.profile_state.stats = None;
         self.dms_state.conversations.clear();
         self.dms_state.messages.clear();
         
@@ -448,12 +1371,19 @@ impl App {
         Ok(())
     }
 
-    /// Load test users from API
-    pub async fn load_test_users(&mut self) -> Result<()> {
+    /// Load test users from API on a background task
+    pub fn spawn_load_test_users(&mut self) {
         self.auth_state.loading = true;
         self.auth_state.error = None;
 
-        match self.api_client.get_test_users().await {
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::TestUsers, async move {
+            TaskResult::TestUsersLoaded(client.get_test_users().await.map_err(|e| e.to_string()))
+        });
+    }
+
+    fn apply_test_users_result(&mut self, result: Result<Vec<User>, String>) {
+        match result {
             Ok(users) => {
                 if users.is_empty() {
                     self.auth_state.error = Some(
@@ -472,10 +1402,703 @@ impl App {
                 self.auth_state.loading = false;
             }
         }
+    }
+
+    /// Validate the stored session in the background.
+    ///
+    /// The main screen is shown right away with skeleton placeholders; if the
+    /// session turns out to be invalid we fall back to the auth screen.
+    pub fn spawn_restore_session(&mut self) {
+        let session_store = self.session_store();
+        self.spawn_restore_session_from(session_store);
+    }
+
+    fn spawn_restore_session_from(&mut self, session_store: Result<crate::session::SessionStore>) {
+        self.auth_state.restoring_session = true;
+        self.current_screen = Screen::Main;
+        self.posts_state.loading = true;
+
+        // Start from a clean client so a previous account's token isn't reused
+        let mut client = self.api_client.clone();
+        client.set_session_token(None);
+        self.task_runner.spawn(TaskKind::Session, async move {
+            let restored = match session_store {
+                Ok(session_store) => {
+                    let mut auth_flow = crate::auth::AuthFlow::new(client, session_store);
+                    match auth_flow.check_existing_session().await {
+                        Ok(Some(user)) => Some((user, auth_flow.api_client().clone())),
+                        _ => None,
+                    }
+                }
+                Err(_) => None,
+            };
+            TaskResult::SessionRestored(restored)
+        });
+    }
+
+    /// Session store for the active server profile
+    pub fn session_store(&self) -> Result<crate::session::SessionStore> {
+        self.server_config.session_store(self.active_profile.as_deref())
+    }
+
+    /// Point the client at a server profile without touching loaded state
+    pub fn use_profile(&mut self, profile: &crate::server_config::ServerProfile) {
+        self.api_client = ApiClient::new(profile.url.clone());
+        self.active_profile = Some(profile.name.clone());
+    }
+
+    /// Open the server quick-switch modal on the active profile
+    pub fn open_server_switcher(&mut self) {
+        let profiles = self.server_config.profiles();
+        self.server_switcher.selected_index = self
+            .active_profile
+            .as_deref()
+            .and_then(|name| profiles.iter().position(|p| p.name == name))
+            .unwrap_or(0);
+        self.server_switcher.error = if profiles.is_empty() {
+            Some("No server profiles yet. Start with --profile <name> --server <url>.".to_string())
+        } else {
+            None
+        };
+        self.server_switcher.show_modal = true;
+    }
+
+    pub fn close_server_switcher(&mut self) {
+        self.server_switcher.show_modal = false;
+        self.server_switcher.error = None;
+    }
+
+    /// Handle keys for the server quick-switch modal
+    pub fn handle_server_switcher_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let count = self.server_config.profiles().len();
+        match key.code {
+            KeyCode::Esc => self.close_server_switcher(),
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') if count > 0 => {
+                self.server_switcher.selected_index =
+                    (self.server_switcher.selected_index + 1).min(count - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
+                self.server_switcher.selected_index =
+                    self.server_switcher.selected_index.saturating_sub(1);
+            }
+            KeyCode::Enter if count > 0 => {
+                let name = self.server_config.profiles()[self.server_switcher.selected_index]
+                    .name
+                    .clone();
+                if let Err(e) = self.switch_server_profile(&name) {
+                    self.server_switcher.error = Some(e.to_string());
+                    return Ok(());
+                }
+                self.close_server_switcher();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Swap to another server profile: new ApiClient, cleared state, and a
+    /// restore of that profile's stored session (or the auth screen)
+    pub fn switch_server_profile(&mut self, name: &str) -> Result<()> {
+        let profile = self
+            .server_config
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Unknown server profile '{}'", name))?;
+        if self.active_profile.as_deref() == Some(name) {
+            return Ok(());
+        }
+
+        self.server_config.set_active(name)?;
+        if let Err(e) = self.server_config.save() {
+            log::warn!("Failed to save active server profile: {}", e);
+        }
+
+        // Nothing from the old server may land after the switch
+        self.task_runner.cancel_all();
+        self.use_profile(&profile);
+        self.server_switcher.switched = true;
+
+        self.auth_state.test_users.clear();
+        self.auth_state.selected_index = 0;
+        self.clear_session_state();
+        self.refresh_saved_accounts();
+
+        let has_session = self
+            .session_store()
+            .ok()
+            .and_then(|store| store.load().ok().flatten())
+            .is_some();
+        if has_session {
+            self.spawn_restore_session();
+        } else {
+            self.current_screen = Screen::Auth;
+            self.posts_state.loading = false;
+            self.spawn_load_test_users();
+        }
+
+        self.spawn_version_check();
+
+        log::info!("Switched to server profile '{}' ({})", profile.name, profile.url);
+        self.toasts.info(format!("Switched to {}", profile.name));
+        Ok(())
+    }
+
+    /// Drop everything loaded for the signed-in account
+    fn clear_session_state(&mut self) {
+        // A late heartbeat must not judge whichever session comes next
+        self.task_runner.cancel(TaskKind::Heartbeat);
+        self.remember_feed_position();
+        self.posts_state.positions.clear();
+        self.posts_state.pending_restore = None;
+        self.posts_state.read_later.clear();
+        self.posts_state.snooze_input = None;
+        self.dms_state.last_conversation = None;
+        self.auth_state.current_user = None;
+        self.auth_state.github_auth_in_progress = false;
+        self.auth_state.github_device_code = None;
+        self.auth_state.error = None;
+        self.current_tab = Tab::Posts;
+        self.posts_state.posts.clear();
+        self.posts_state.similar.clear();
+        self.posts_state.list_state.select(None);
+        self.posts_state.current_filter = PostFilter::All;
+        self.profile_state.profile = None;
+        self.profile_state.stats = None;
+        self.profile_state.user_posts.clear();
+        self.profile_state.posts_tab = ProfilePostsTab::default();
+        self.dms_state.conversations.clear();
+        self.dms_state.messages.clear();
+        self.dms_state.selected_message = None;
+        self.dms_state.pending_attachment = None;
+        self.dms_state.expanded_attachments.clear();
+        self.task_runner.cancel(TaskKind::DmDelivery);
+        self.dms_state.outbox.clear();
+        self.dms_state.unread_counts.clear();
+        self.dms_state.drafts.clear();
+        self.dms_state.follow_ups = crate::config::DmFollowUps::default();
+        self.dms_state.reminder_input = None;
+        self.clear_dm_message();
+        self.settings_state.config = None;
+        self.settings_state.original_config = None;
+        self.posts_state.reduced_motion = false;
+        self.post_detail_state = None;
+        self.viewing_post_detail = false;
+        self.user_profile_view = None;
+        self.posts_state.loading = false;
+        self.instance_features = None;
+        self.task_runner.cancel(TaskKind::Digest);
+        self.digest = None;
+        self.task_runner.cancel(TaskKind::Catchup);
+        self.catchup = None;
+        self.task_runner.cancel(TaskKind::GitHubSuggestions);
+        self.github_suggestions = None;
+        self.close_drafts();
+        self.drafts.drafts.clear();
+        self.close_events();
+        self.events.events.clear();
+        self.close_highlights();
+        self.highlights = HighlightsState::default();
+        self.close_hashtag_digest();
+        self.hashtag_digest = HashtagDigestState::default();
+        self.reading_history = ReadingHistoryState::default();
+        self.task_runner.cancel(TaskKind::WatchedThreads);
+        self.watched_threads.clear();
+        self.task_runner.cancel(TaskKind::Crossposts);
+        self.crossposts = CrosspostState::default();
+        self.task_runner.cancel(TaskKind::Translation);
+        self.translations = TranslationState::default();
+        crate::emoji::set_custom_emoji(Vec::new());
+    }
+
+    fn apply_session_restored(&mut self, restored: Option<(User, ApiClient)>) {
+        self.auth_state.restoring_session = false;
+
+        let switching_account = self.auth_state.switching_account.take();
+        match restored {
+            Some((user, client)) => {
+                log::info!("Restored session for user: {}", user.username);
+                if let Some(token) = client.session_token().map(str::to_string) {
+                    if switching_account.is_some() {
+                        // The switched-to account is the one resumed on next launch
+                        if let Err(e) = self.session_store().and_then(|store| store.save(&token)) {
+                            log::warn!("Failed to save session: {}", e);
+                        }
+                    }
+                    self.remember_account(&user.username, &token);
+                }
+                if switching_account.is_some() {
+                    self.toasts.success(format!("✓ Signed in as @{}", user.username));
+                }
+                self.auth_state.current_user = Some(user);
+                self.api_client = client;
+                self.start_session_data_load();
+            }
+            None => {
+                log::info!("No valid session found, showing authentication screen");
+                self.current_screen = Screen::Auth;
+                self.posts_state.loading = false;
+                self.refresh_saved_accounts();
+                // Reloading the account list clears any error, so report
+                // the expired session after it
+                self.spawn_load_test_users();
+                if let Some(username) = switching_account {
+                    self.auth_state.error = Some(format!(
+                        "Session for @{} has expired. Please log in again.",
+                        username
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Re-read which accounts have a saved session on the current server
+    pub fn refresh_saved_accounts(&mut self) {
+        self.auth_state.saved_accounts = if self.auth_state.remember_accounts {
+            self.server_config
+                .saved_accounts(self.active_profile.as_deref())
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Keep a login's token so the account can be switched back to later
+    pub fn remember_account(&mut self, username: &str, token: &str) {
+        if !self.auth_state.remember_accounts {
+            return;
+        }
+        let saved = self
+            .server_config
+            .account_store(self.active_profile.as_deref(), username)
+            .and_then(|store| store.save(token));
+        if let Err(e) = saved {
+            log::warn!("Failed to save account @{}: {}", username, e);
+        }
+        self.refresh_saved_accounts();
+    }
+
+    /// Resume a saved account from the auth screen without logging in again
+    pub fn switch_to_saved_account(&mut self, index: usize) {
+        let Some(username) = self.auth_state.saved_accounts.get(index).cloned() else {
+            return;
+        };
+        let store = self
+            .server_config
+            .account_store(self.active_profile.as_deref(), &username);
+        self.auth_state.error = None;
+        self.auth_state.switching_account = Some(username);
+        self.spawn_restore_session_from(store);
+    }
+
+    /// Leave the current account signed in and go back to the auth screen to
+    /// pick another one
+    pub fn switch_account(&mut self) {
+        self.task_runner.cancel_all();
+        self.clear_session_state();
+        self.current_screen = Screen::Auth;
+        self.refresh_saved_accounts();
+        if self.auth_state.test_users.is_empty() {
+            self.spawn_load_test_users();
+        }
+    }
+
+    /// Load settings, filter preference, custom emoji and the feed after
+    /// authenticating.
+    ///
+    /// Settings and posts stream in on background tasks; the feed waits for
+    /// settings so it uses the right sort order and page size.
+    pub fn start_session_data_load(&mut self) {
+        self.load_filter_preference();
+        self.load_list_positions();
+        self.load_read_later();
+        self.load_reading_history();
+        self.load_dm_drafts();
+        self.load_dm_follow_ups();
+        self.posts_state.loading = true;
+        self.posts_state.awaiting_settings = true;
+        self.spawn_load_settings();
+        self.spawn_load_custom_emoji();
+        self.spawn_load_features();
+        self.spawn_poll_unread_counts();
+        self.spawn_poll_watched_threads();
+        self.spawn_poll_crossposts();
+        self.spawn_load_digest();
+        self.spawn_catchup_if_away();
+        self.spawn_load_github_suggestions();
+    }
+
+    /// After a long absence, fetch what happened since the user was last
+    /// seen; either way, the clock restarts now
+    fn spawn_catchup_if_away(&mut self) {
+        let Some(user) = &self.auth_state.current_user else {
+            return;
+        };
+        let last_seen = self
+            .config_manager
+            .load_last_seen(&user.id.to_string())
+            .unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable last-seen time: {}", e);
+                None
+            });
+        self.record_last_seen();
+
+        let Some(since) = catchup_since(last_seen, chrono::Utc::now()) else {
+            return;
+        };
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Catchup, async move {
+            TaskResult::CatchupLoaded(client.get_catchup(since).await.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Note that the user is active now
+    fn record_last_seen(&self) {
+        if let Some(user) = &self.auth_state.current_user {
+            if let Err(e) = self
+                .config_manager
+                .save_last_seen(&user.id.to_string(), chrono::Utc::now())
+            {
+                log::warn!("Failed to record last-seen time: {}", e);
+            }
+        }
+    }
+
+    /// Close the catch-up screen and land on the feed
+    pub fn dismiss_catchup(&mut self) {
+        if self.catchup.take().is_some() {
+            self.current_tab = Tab::Posts;
+        }
+    }
+
+    /// Fetch the digest the server compiled while the user was away
+    fn spawn_load_digest(&mut self) {
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Digest, async move {
+            TaskResult::DigestLoaded(client.get_digest().await.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Close the digest and tell the server not to show it again
+    pub fn dismiss_digest(&mut self) {
+        let Some(digest) = self.digest.take() else {
+            return;
+        };
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Digest, async move {
+            TaskResult::DigestDismissed(
+                client.mark_digest_seen(digest.id).await.map_err(|e| e.to_string()),
+            )
+        });
+    }
+
+    /// Look for people followed on GitHub who are on Fido; the server
+    /// refreshes the list at each GitHub login
+    fn spawn_load_github_suggestions(&mut self) {
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::GitHubSuggestions, async move {
+            TaskResult::GitHubSuggestionsLoaded(
+                client.get_github_suggestions().await.map_err(|e| e.to_string()),
+            )
+        });
+    }
+
+    /// Whether the GitHub follow suggestions are being offered. They wait
+    /// for the digest, catch-up and draft recovery prompts.
+    pub fn github_suggestions_open(&self) -> bool {
+        self.github_suggestions.is_some()
+            && self.current_screen == Screen::Main
+            && self.digest.is_none()
+            && self.catchup.is_none()
+            && self.recovered_draft.is_none()
+            && !self.composer_state.is_open()
+    }
+
+    /// Follow everyone suggested (Enter)
+    pub async fn follow_github_suggestions(&mut self) -> Result<()> {
+        if self.github_suggestions.take().is_none() {
+            return Ok(());
+        }
+        match self.api_client.follow_github_suggestions().await {
+            Ok(followed) => {
+                self.toasts.success(format!(
+                    "Following {} {} from GitHub",
+                    followed,
+                    if followed == 1 { "person" } else { "people" }
+                ));
+                self.spawn_load_posts();
+            }
+            Err(e) => self.toasts.error(format!("Couldn't follow: {}", e)),
+        }
+        Ok(())
+    }
+
+    /// Close the suggestions and tell the server not to offer these people
+    /// again (Esc)
+    pub fn dismiss_github_suggestions(&mut self) {
+        if self.github_suggestions.take().is_none() {
+            return;
+        }
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::GitHubSuggestions, async move {
+            TaskResult::GitHubSuggestionsDismissed(
+                client.dismiss_github_suggestions().await.map_err(|e| e.to_string()),
+            )
+        });
+    }
+
+    /// Fetch the instance's posting limits for the composer
+    fn spawn_load_features(&mut self) {
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Features, async move {
+            TaskResult::FeaturesLoaded(client.get_features().await.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Count a successful post or reply against today's remaining quota
+    fn note_post_created(&mut self) {
+        if let Some(remaining) = self
+            .instance_features
+            .as_mut()
+            .and_then(|features| features.posts_remaining_today.as_mut())
+        {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    /// Fetch the instance's custom emoji so shortcodes resolve when posting
+    fn spawn_load_custom_emoji(&mut self) {
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::CustomEmoji, async move {
+            TaskResult::CustomEmojiLoaded(
+                client.get_custom_emoji().await.map_err(|e| e.to_string()),
+            )
+        });
+    }
+
+    /// Ping the server so the session's expiry slides forward while fido is open
+    pub fn spawn_heartbeat(&mut self) {
+        if self.api_client.session_token().is_none() {
+            return;
+        }
+        self.record_last_seen();
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Heartbeat, async move {
+            let expired = match client.heartbeat().await {
+                Ok(response) => {
+                    log::debug!("Session extended until {}", response.expires_at);
+                    false
+                }
+                Err(crate::api::ApiError::Unauthorized(e)) => {
+                    log::warn!("Heartbeat rejected: {}", e);
+                    true
+                }
+                // Network trouble says nothing about the session; try next time
+                Err(e) => {
+                    log::debug!("Heartbeat failed: {}", e);
+                    false
+                }
+            };
+            TaskResult::HeartbeatCompleted { expired }
+        });
+    }
+
+    /// The server no longer knows this session: forget it and ask for a new login
+    fn apply_session_expired(&mut self) {
+        if let Err(e) = self.session_store().and_then(|store| store.delete()) {
+            log::warn!("Failed to delete session file: {}", e);
+        }
+        if let Some(user) = &self.auth_state.current_user {
+            let account_store = self
+                .server_config
+                .account_store(self.active_profile.as_deref(), &user.username);
+            if let Err(e) = account_store.and_then(|store| store.delete()) {
+                log::warn!("Failed to delete saved account: {}", e);
+            }
+        }
+
+        self.task_runner.cancel_all();
+        self.api_client.set_session_token(None);
+        self.clear_session_state();
+        self.refresh_saved_accounts();
+        self.current_screen = Screen::Auth;
+        // After the reload, which clears any error
+        self.spawn_load_test_users();
+        self.auth_state.error = Some("Your session has expired. Please log in again.".to_string());
+    }
+
+    /// The server rejected the session while logged in: ask to sign in
+    /// again over the current screen instead of dropping back to login
+    pub fn open_reauth(&mut self) {
+        if self.reauth.show
+            || self.current_screen != Screen::Main
+            || self.auth_state.current_user.is_none()
+            || self.auth_state.restoring_session
+        {
+            return;
+        }
+        self.task_runner.cancel(TaskKind::Heartbeat);
+        self.reauth = ReauthState {
+            show: true,
+            ..ReauthState::default()
+        };
+    }
+
+    /// Enter in the re-auth prompt: test accounts log straight back in,
+    /// GitHub accounts start the device flow (polled by `poll_reauth`)
+    pub async fn start_reauth(&mut self) -> Result<()> {
+        let Some(user) = self.auth_state.current_user.clone() else {
+            return Ok(());
+        };
+        if self.reauth.device_code.is_some() {
+            return Ok(());
+        }
+        self.reauth.error = None;
+
+        if user.is_test_user {
+            let result = if self.api_client.uses_cookie_auth() {
+                self.api_client.web_login(user.username.clone()).await.map(|_| None)
+            } else {
+                self.api_client
+                    .login(user.username.clone())
+                    .await
+                    .map(|response| Some(response.session_token))
+            };
+            match result {
+                Ok(token) => self.finish_reauth(token).await?,
+                Err(e) => self.reauth.error = Some(format!("Sign-in failed: {}", e)),
+            }
+            return Ok(());
+        }
+
+        match self.api_client.github_device_flow().await {
+            Ok(flow) => {
+                if let Err(e) = webbrowser::open(&flow.verification_uri) {
+                    log::warn!("Failed to open browser: {}", e);
+                }
+                self.reauth.device_code = Some(flow.device_code);
+                self.reauth.user_code = Some(flow.user_code);
+                self.reauth.verification_uri = Some(flow.verification_uri);
+                self.reauth.poll_interval = flow.interval.max(1) as u64;
+                self.reauth.started = Some(Instant::now());
+                self.reauth.last_poll = Some(Instant::now());
+            }
+            Err(e) => {
+                self.reauth.error = Some(format!("Couldn't start GitHub sign-in: {}", e))
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether the device code has been authorized yet
+    pub async fn poll_reauth(&mut self) -> Result<()> {
+        let Some(device_code) = self.reauth.device_code.clone() else {
+            return Ok(());
+        };
+        if self
+            .reauth
+            .started
+            .is_some_and(|started| started.elapsed() > ReauthState::DEVICE_FLOW_TIMEOUT)
+        {
+            self.reset_reauth_device_flow();
+            self.reauth.error = Some("The code expired. Press Enter for a new one.".to_string());
+            return Ok(());
+        }
+        self.reauth.last_poll = Some(Instant::now());
+
+        match self.api_client.github_device_poll(&device_code).await {
+            Ok(response) => {
+                let expected = self.auth_state.current_user.as_ref().map(|user| user.id);
+                if expected != Some(response.user.id) {
+                    // Carrying on as someone else would mix two accounts' state
+                    self.reset_reauth_device_flow();
+                    self.reauth.error = Some(format!(
+                        "That signed in @{}; sign in as @{} to carry on, or Esc to log out",
+                        response.user.username,
+                        self.auth_state
+                            .current_user
+                            .as_ref()
+                            .map(|user| user.username.as_str())
+                            .unwrap_or_default()
+                    ));
+                    return Ok(());
+                }
+                self.finish_reauth(Some(response.session_token)).await?;
+            }
+            Err(crate::api::ApiError::BadRequest(message))
+                if message.contains("authorization_pending") => {}
+            Err(e) => {
+                self.reset_reauth_device_flow();
+                self.reauth.error = Some(format!("GitHub sign-in failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    fn reset_reauth_device_flow(&mut self) {
+        self.reauth.device_code = None;
+        self.reauth.user_code = None;
+        self.reauth.verification_uri = None;
+        self.reauth.started = None;
+        self.reauth.last_poll = None;
+    }
+
+    /// Signed back in: keep the new session (`None` with cookie auth, where
+    /// it stays in the cookie jar) and retry what the expired one couldn't
+    async fn finish_reauth(&mut self, token: Option<String>) -> Result<()> {
+        if let Some(token) = token {
+            self.api_client.set_session_token(Some(token.clone()));
+            if let Err(e) = self.session_store().and_then(|store| store.save(&token)) {
+                log::warn!("Failed to save session: {}", e);
+            }
+            if let Some(username) = self.auth_state.current_user.as_ref().map(|u| u.username.clone()) {
+                self.remember_account(&username, &token);
+            }
+        }
+        self.reauth = ReauthState::default();
+        self.toasts.success("✓ Signed back in");
+        self.spawn_heartbeat();
+        self.reload_current_screen().await
+    }
 
+    /// Run again the requests behind what's on screen, after they failed
+    /// on an expired session or a rate limit. Unsaved settings are saved
+    /// rather than reloaded over.
+    pub async fn reload_current_screen(&mut self) -> Result<()> {
+        if self.current_screen == Screen::Auth {
+            if self.auth_state.test_users.is_empty() {
+                self.spawn_load_test_users();
+            }
+            return Ok(());
+        }
+        match self.current_tab {
+            Tab::Posts => {
+                let open_post = self
+                    .post_detail_state
+                    .as_ref()
+                    .filter(|_| self.viewing_post_detail)
+                    .and_then(|detail| detail.post.as_ref().map(|post| post.id));
+                if let Some(post_id) = open_post {
+                    self.load_post_detail(post_id).await?;
+                }
+                self.spawn_load_posts();
+            }
+            Tab::DMs => {
+                self.spawn_load_conversations();
+                self.dms_state.needs_message_load = true;
+            }
+            Tab::Channels => self.load_channels().await?,
+            Tab::Profile => self.load_profile().await?,
+            Tab::Settings if self.settings_state.has_unsaved_changes => self.save_settings().await?,
+            Tab::Settings => self.spawn_load_settings(),
+        }
         Ok(())
     }
 
+    /// Esc in the re-auth prompt: give up and go back to the login screen
+    pub fn abandon_reauth(&mut self) {
+        self.reauth = ReauthState::default();
+        self.apply_session_expired();
+    }
+
     /// Login with selected user
     pub async fn login_selected_user(&mut self) -> Result<()> {
         if self.auth_state.test_users.is_empty() {
@@ -486,6 +2109,23 @@ impl App {
         self.auth_state.loading = true;
         self.auth_state.error = None;
 
+        // Web terminal: the session stays in the cookie jar, nothing is saved
+        if self.api_client.uses_cookie_auth() {
+            match self.api_client.web_login(selected_user.username.clone()).await {
+                Ok(user) => {
+                    self.auth_state.current_user = Some(user);
+                    self.auth_state.loading = false;
+                    self.current_screen = Screen::Main;
+                    self.start_session_data_load();
+                }
+                Err(e) => {
+                    self.auth_state.error = Some(format!("Login failed: {}", e));
+                    self.auth_state.loading = false;
+                }
+            }
+            return Ok(());
+        }
+
         match self.api_client.login(selected_user.username.clone()).await {
             Ok(response) => {
                 self.auth_state.current_user = Some(response.user.clone());
@@ -505,15 +2145,10 @@ impl App {
                 {
                     eprintln!("Warning: Failed to save session: {}", e);
                 }
+                self.remember_account(&response.user.username, &response.session_token);
 
-                // Load user settings first (so posts use correct preferences)
-                let _ = self.load_settings().await;
-
-                // Load filter preference
-                self.load_filter_preference();
-
-                // Load posts after successful login (will use loaded settings and filter)
-                let _ = self.load_posts().await;
+                // Settings, filter preference and posts load in the background
+                self.start_session_data_load();
             }
             Err(e) => {
                 self.auth_state.error = Some(format!("Login failed: {}", e));
@@ -529,12 +2164,26 @@ impl App {
         self.posts_state.loading = true;
         self.posts_state.error = None;
 
-        // Yield to allow UI to render the loading state
-        tokio::task::yield_now().await;
-
-        // Add 200ms delay to ensure loading spinner is visible
-        tokio::time::sleep(Duration::from_millis(200)).await;
+        let (client, filter, sort_order, max_posts) = self.posts_request();
+        let result = fetch_posts(client, filter, sort_order, max_posts).await;
+        self.apply_posts_result(result);
+
+        Ok(())
+    }
+
+    /// Load posts on a background task; the result is applied by `process_task_results`
+    pub fn spawn_load_posts(&mut self) {
+        self.posts_state.loading = true;
+        self.posts_state.error = None;
+
+        let (client, filter, sort_order, max_posts) = self.posts_request();
+        self.task_runner.spawn(TaskKind::Posts, async move {
+            TaskResult::PostsLoaded(fetch_posts(client, filter, sort_order, max_posts).await)
+        });
+    }
 
+    /// Snapshot everything a feed request needs so it can run without borrowing the app
+    fn posts_request(&self) -> (ApiClient, PostFilter, String, i32) {
         // Get sort order and max posts from config
         let sort_order = self
             .settings_state
@@ -550,100 +2199,224 @@ impl App {
             .map(|c| c.max_posts_display)
             .unwrap_or(25);
 
-        // Apply current filter
-        let result = match &self.posts_state.current_filter {
-            PostFilter::All => {
-                self.api_client
-                    .get_posts(Some(max_posts), Some(sort_order.clone()), None, None)
-                    .await
-            }
-            PostFilter::Hashtag(tag) => {
-                self.api_client
-                    .get_posts(
-                        Some(max_posts),
-                        Some(sort_order.clone()),
-                        Some(tag.clone()),
-                        None,
-                    )
-                    .await
-            }
-            PostFilter::User(user) => {
-                self.api_client
-                    .get_posts(
-                        Some(max_posts),
-                        Some(sort_order.clone()),
-                        None,
-                        Some(user.clone()),
-                    )
-                    .await
-            }
-            PostFilter::Multi { hashtags, users } => {
-                // Fetch posts for each filter and combine them
-                let mut all_posts = Vec::new();
-
-                // Fetch for each hashtag
-                for hashtag in hashtags {
-                    if let Ok(posts) = self
-                        .api_client
-                        .get_posts(
-                            Some(max_posts),
-                            Some(sort_order.clone()),
-                            Some(hashtag.clone()),
-                            None,
-                        )
-                        .await
-                    {
-                        all_posts.extend(posts);
-                    }
-                }
-
-                // Fetch for each user
-                for username in users {
-                    if let Ok(posts) = self
-                        .api_client
-                        .get_posts(
-                            Some(max_posts),
-                            Some(sort_order.clone()),
-                            None,
-                            Some(username.clone()),
-                        )
-                        .await
-                    {
-                        all_posts.extend(posts);
-                    }
-                }
-
-                // Remove duplicates by post ID
-                all_posts.sort_by(|a, b| b.created_at.cmp(&a.created_at)); // Sort by newest first
-                all_posts.dedup_by(|a, b| a.id == b.id);
-
-                // Limit to max_posts
-                all_posts.truncate(max_posts as usize);
-
-                Ok(all_posts)
-            }
-        };
+        (
+            self.api_client.clone(),
+            self.posts_state.current_filter.clone(),
+            sort_order,
+            max_posts,
+        )
+    }
 
+    /// Apply a finished feed load to the posts state
+    fn apply_posts_result(&mut self, result: Result<Vec<Post>, String>) {
         match result {
             Ok(posts) => {
+                let posts = self.with_read_later(posts, chrono::Utc::now());
                 let has_posts = !posts.is_empty();
+                let (posts, similar) = collapse_similar_posts(posts);
                 self.posts_state.posts = posts;
+                self.posts_state.similar = similar;
+                self.posts_state.loading = false;
                 // Server now includes user_vote in each post
                 if has_posts {
-                    self.posts_state.list_state.select(Some(0));
+                    self.restore_feed_position();
+                    self.load_github_cards();
                 } else {
+                    self.posts_state.pending_restore = None;
                     self.posts_state.list_state.select(None);
                 }
-                self.posts_state.loading = false;
             }
             Err(e) => {
-                let error_msg = categorize_error(&e.to_string());
+                let error_msg = categorize_error(&e);
                 self.posts_state.error = Some(error_msg);
                 self.posts_state.loading = false;
             }
         }
+    }
 
-        Ok(())
+    /// Apply results from completed background tasks (called once per frame).
+    /// Returns whether anything was applied.
+    pub fn process_task_results(&mut self) -> bool {
+        let mut applied = false;
+        while let Some(result) = self.task_runner.try_recv() {
+            applied = true;
+            match result {
+                TaskResult::PostsLoaded(result) => self.apply_posts_result(result),
+                TaskResult::ConversationsLoaded(result) => {
+                    self.apply_conversations_result(result)
+                }
+                TaskResult::UnreadCountsPolled(result) => self.apply_unread_poll(result),
+                TaskResult::WatchedThreadsPolled(result) => match result {
+                    Ok(threads) => self.update_watched_threads(&threads),
+                    Err(e) => log::debug!("Watched threads poll failed: {}", e),
+                },
+                TaskResult::CrosspostsPolled(result) => match result {
+                    Ok(crossposts) => self.update_crossposts(crossposts),
+                    Err(e) => log::debug!("Cross-post poll failed: {}", e),
+                },
+                TaskResult::DmDelivered {
+                    local_id,
+                    result,
+                    transient,
+                } => self.apply_dm_delivery(local_id, result, transient),
+                TaskResult::SettingsLoaded(result) => self.apply_settings_result(result),
+                TaskResult::TestUsersLoaded(result) => self.apply_test_users_result(result),
+                TaskResult::SessionRestored(restored) => self.apply_session_restored(restored),
+                TaskResult::FilterModalDataLoaded { hashtags, users } => {
+                    self.apply_filter_modal_data(hashtags, users)
+                }
+                TaskResult::UserSearchCompleted { query, result } => {
+                    self.apply_user_search_result(query, result)
+                }
+                TaskResult::ProfilePostsLoaded(result) => self.apply_profile_posts_page(result),
+                TaskResult::ProfileStatsLoaded(result) => match result {
+                    Ok(stats) => self.profile_state.stats = Some(stats),
+                    // The panel is extra; the profile itself loaded fine
+                    Err(e) => log::warn!("Failed to load profile stats: {}", e),
+                },
+                TaskResult::CustomEmojiLoaded(result) => match result {
+                    Ok(emoji) => crate::emoji::set_custom_emoji(emoji),
+                    // Built-in shortcodes still work; instance emoji just stay literal
+                    Err(e) => log::warn!("Failed to load custom emoji: {}", e),
+                },
+                TaskResult::HeartbeatCompleted { expired } => {
+                    if expired {
+                        self.open_reauth();
+                    }
+                }
+                TaskResult::DiagnosticsPinged(result) => self.diagnostics.record_ping(result),
+                TaskResult::VersionChecked(result) => self.apply_version_check(result),
+                TaskResult::FeaturesLoaded(result) => match result {
+                    Ok(features) => self.instance_features = Some(features),
+                    // Older servers have no /features; the composer just shows no limits
+                    Err(e) => log::debug!("Instance features unavailable: {}", e),
+                },
+                TaskResult::LinkPreviewLoaded { url, result } => {
+                    self.apply_link_preview(url, result)
+                }
+                TaskResult::PostTranslated { post_id, result } => {
+                    self.apply_translation(post_id, result)
+                }
+                TaskResult::GitHubCardsLoaded(cards) => {
+                    for (url, result) in cards {
+                        match result {
+                            Ok(card) => {
+                                self.github_cards.insert(url, card);
+                            }
+                            // Asked again on the next load
+                            Err(e) => log::debug!("GitHub card for {} failed: {}", url, e),
+                        }
+                    }
+                }
+                TaskResult::GistLoaded { url, result } => match result {
+                    Ok(gist) => {
+                        self.gists.insert(url, gist);
+                    }
+                    // Asked again when the thread is next opened
+                    Err(e) => log::debug!("Gist {} failed: {}", url, e),
+                },
+                TaskResult::DigestLoaded(result) => match result {
+                    Ok(digest) => self.digest = digest,
+                    // Older servers have no /digest
+                    Err(e) => log::debug!("Digest unavailable: {}", e),
+                },
+                // Shown again next login, which is harmless
+                TaskResult::DigestDismissed(Err(e)) => {
+                    log::warn!("Failed to mark digest seen: {}", e)
+                }
+                TaskResult::DigestDismissed(Ok(())) => {}
+                TaskResult::GitHubSuggestionsLoaded(result) => match result {
+                    Ok(suggestions) if !suggestions.is_empty() => {
+                        self.github_suggestions = Some(suggestions)
+                    }
+                    Ok(_) => {}
+                    // Older servers have no suggestions
+                    Err(e) => log::debug!("GitHub suggestions unavailable: {}", e),
+                },
+                // Offered again next login, which is harmless
+                TaskResult::GitHubSuggestionsDismissed(Err(e)) => {
+                    log::warn!("Failed to dismiss GitHub suggestions: {}", e)
+                }
+                TaskResult::GitHubSuggestionsDismissed(Ok(())) => {}
+                TaskResult::BulkActionDone { action, result } => match (action, result) {
+                    (BulkAction::Bookmark, Ok(count)) => {
+                        self.toasts.success(format!("Bookmarked {}", plural_posts(count)))
+                    }
+                    (BulkAction::MuteAuthors, Ok(count)) => {
+                        self.toasts.success(format!(
+                            "Muted {} author{}",
+                            count,
+                            if count == 1 { "" } else { "s" }
+                        ));
+                        self.posts_state.pending_load = true;
+                    }
+                    // Opened on the spot, never spawned
+                    (BulkAction::OpenInBrowser, Ok(_)) => {}
+                    (_, Err(e)) => self.toasts.error(categorize_error(&e)),
+                },
+                TaskResult::SocialPageLoaded {
+                    tab,
+                    query,
+                    append,
+                    result,
+                } => match result {
+                    Ok(users) => {
+                        self.apply_social_page(tab, query, append, users);
+                        if !append && tab == self.friends_state.selected_tab {
+                            self.friends_state.selected_index = 0;
+                        }
+                    }
+                    Err(e) => self.friends_state.error = Some(categorize_error(&e)),
+                },
+                TaskResult::DraftsLoaded(result) => {
+                    self.drafts.loading = false;
+                    match result {
+                        Ok(drafts) => self.drafts.drafts = drafts,
+                        Err(e) => self.drafts.error = Some(categorize_error(&e)),
+                    }
+                }
+                TaskResult::EventsLoaded(result) => {
+                    self.events.loading = false;
+                    match result {
+                        Ok(events) => self.events.events = events,
+                        Err(e) => self.events.error = Some(categorize_error(&e)),
+                    }
+                }
+                TaskResult::HighlightsLoaded(result) => {
+                    self.highlights.loading = false;
+                    match result {
+                        Ok((top_posts, top_contributors)) => {
+                            self.highlights.top_posts = top_posts;
+                            self.highlights.top_contributors = top_contributors;
+                        }
+                        Err(e) => self.highlights.error = Some(categorize_error(&e)),
+                    }
+                }
+                TaskResult::HashtagDigestLoaded(result) => {
+                    self.hashtag_digest.loading = false;
+                    match result {
+                        Ok(entries) => {
+                            self.hashtag_digest.entries = entries;
+                            let rows = self.hashtag_digest.rows().len();
+                            self.hashtag_digest.selected =
+                                self.hashtag_digest.selected.min(rows.saturating_sub(1));
+                        }
+                        Err(e) => self.hashtag_digest.error = Some(categorize_error(&e)),
+                    }
+                }
+                TaskResult::CatchupLoaded(result) => match result {
+                    // Nothing happened; go straight to the feed
+                    Ok(catchup)
+                        if catchup.new_followers.is_empty()
+                            && catchup.top_posts.is_empty()
+                            && catchup.unread_messages.is_empty() => {}
+                    Ok(catchup) => self.catchup = Some(catchup),
+                    // Older servers have no /catchup
+                    Err(e) => log::debug!("Catch-up unavailable: {}", e),
+                },
+            }
+        }
+        applied
     }
 
     /// Vote on the currently selected post
@@ -655,6 +2428,11 @@ impl App {
             let selected_post = &mut self.posts_state.posts[selected_index];
             let post_id = selected_post.id;
 
+            // Nothing to vote on once a post is deleted
+            if selected_post.is_deleted {
+                return Ok(());
+            }
+
             // Check if user has already voted on this post
             let previous_vote = selected_post.user_vote.clone();
 
@@ -758,36 +2536,37 @@ impl App {
         // Lists will be loaded async in main loop
     }
 
-    /// Load filter modal data (hashtags and following users)
-    pub async fn load_filter_modal_data(&mut self) -> Result<()> {
-        // Load followed hashtags
-        match self.api_client.get_followed_hashtags().await {
-            Ok(hashtags) => {
-                self.posts_state.filter_modal_state.hashtag_list = hashtags;
-            }
-            Err(_) => {
-                // Silently fail, just show empty list
-                self.posts_state.filter_modal_state.hashtag_list.clear();
-            }
-        }
-
-        // Load following users (people you follow)
-        match self.api_client.get_following_list().await {
-            Ok(following) => {
-                self.posts_state.filter_modal_state.user_list =
-                    following.into_iter().map(|user| user.username).collect();
-            }
-            Err(_) => {
-                // Silently fail, just show empty list
-                self.posts_state.filter_modal_state.user_list.clear();
-            }
-        }
+    /// Load filter modal data (hashtags and following users) on a background task
+    pub fn spawn_load_filter_modal_data(&mut self) {
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::FilterModalData, async move {
+            let hashtags = client
+                .get_followed_hashtags()
+                .await
+                .map_err(|e| e.to_string());
+            let users = client
+                .get_following_list()
+                .await
+                .map(|following| following.into_iter().map(|user| user.username).collect())
+                .map_err(|e| e.to_string());
+            TaskResult::FilterModalDataLoaded { hashtags, users }
+        });
+    }
 
-        Ok(())
+    fn apply_filter_modal_data(
+        &mut self,
+        hashtags: Result<Vec<String>, String>,
+        users: Result<Vec<String>, String>,
+    ) {
+        // Silently fail, just show empty lists
+        self.posts_state.filter_modal_state.hashtag_list = hashtags.unwrap_or_default();
+        self.posts_state.filter_modal_state.user_list = users.unwrap_or_default();
     }
 
     /// Close filter modal (keeps checked items for next time)
     pub fn close_filter_modal(&mut self) {
+        // A list load still in flight is no longer wanted
+        self.task_runner.cancel(TaskKind::FilterModalData);
         self.posts_state.show_filter_modal = false;
         self.posts_state.filter_modal_state.search_mode = false;
         self.posts_state.filter_modal_state.search_input.clear();
@@ -865,8 +2644,8 @@ impl App {
                     }
                 }
             }
-            FilterTab::All => {
-                // No toggle for "All" tab
+            FilterTab::All | FilterTab::Reviews | FilterTab::Jobs | FilterTab::Later => {
+                // Nothing to toggle in the "All", "Reviews", "Jobs" and "Later" tabs
             }
         }
     }
@@ -1058,7 +2837,7 @@ impl App {
 
     /// Apply filter and reload posts
     pub async fn apply_filter(&mut self, filter: PostFilter) -> Result<()> {
-        self.posts_state.current_filter = filter.clone();
+        self.switch_feed_filter(filter);
         self.close_filter_modal();
 
         // Save filter preference
@@ -1069,6 +2848,28 @@ impl App {
         Ok(())
     }
 
+    /// New posts made while a channel's feed is open go into that channel.
+    /// Anonymity and who may reply come from the composer.
+    async fn create_post_in_feed(&self, content: String) -> crate::api::ApiResult<Post> {
+        let reply_policy = self.composer_state.reply_policy;
+        match &self.posts_state.current_filter {
+            PostFilter::Channel(name) => {
+                self.api_client
+                    .create_channel_post(name, content, reply_policy)
+                    .await
+            }
+            _ => {
+                self.api_client
+                    .create_post_with(fido_types::CreatePostRequest {
+                        content,
+                        anonymous: self.composer_state.anonymous,
+                        reply_policy,
+                    })
+                    .await
+            }
+        }
+    }
+
     /// Save current filter preference to disk
     fn save_filter_preference(&self) {
         if let Some(user) = &self.auth_state.current_user {
@@ -1088,6 +2889,265 @@ impl App {
         }
     }
 
+    /// Switch the feed to `filter`, remembering where we were in the old
+    /// one; the new one opens where we left it once it loads
+    fn switch_feed_filter(&mut self, filter: PostFilter) {
+        self.remember_feed_position();
+        self.posts_state.pending_restore = self
+            .posts_state
+            .positions
+            .get(&filter.position_key())
+            .copied();
+        self.posts_state.current_filter = filter;
+    }
+
+    /// Note the selected post and scroll offset of the feed on screen, and
+    /// save them with the other positions
+    pub fn remember_feed_position(&mut self) {
+        // Until a load lands, the posts shown belong to another filter
+        if self.posts_state.loading || self.posts_state.pending_load {
+            return;
+        }
+        let selected = self
+            .posts_state
+            .list_state
+            .selected()
+            .and_then(|index| self.posts_state.list_index_to_post_index(index))
+            .and_then(|index| self.posts_state.posts.get(index))
+            .map(|post| post.id);
+        if let Some(selected) = selected {
+            let position = crate::config::ListPosition {
+                selected,
+                offset: self.posts_state.list_state.offset(),
+            };
+            self.posts_state
+                .positions
+                .insert(self.posts_state.current_filter.position_key(), position);
+            self.save_list_positions();
+        }
+    }
+
+    /// Select the post a restored position points at, or the first post if
+    /// there's none or it's gone from the feed
+    fn restore_feed_position(&mut self) {
+        let restored = self.posts_state.pending_restore.take().and_then(|position| {
+            let index = self
+                .posts_state
+                .posts
+                .iter()
+                .position(|post| post.id == position.selected)?;
+            Some((self.posts_state.post_index_to_list_index(index), position.offset))
+        });
+        match restored {
+            Some((list_index, offset)) => {
+                self.posts_state.list_state.select(Some(list_index));
+                *self.posts_state.list_state.offset_mut() = offset.min(list_index);
+            }
+            None => self.posts_state.list_state.select(Some(0)),
+        }
+    }
+
+    /// Write feed and DM positions to disk
+    fn save_list_positions(&self) {
+        if let Some(user) = &self.auth_state.current_user {
+            let positions = crate::config::ListPositions {
+                feeds: self.posts_state.positions.clone(),
+                dm_conversation: self.dms_state.last_conversation,
+            };
+            if let Err(e) = self
+                .config_manager
+                .save_list_positions(&user.id.to_string(), &positions)
+            {
+                log::warn!("Failed to save list positions: {}", e);
+            }
+        }
+    }
+
+    /// Fit the read-later queue into a feed load. The Later filter lists
+    /// the queue itself, soonest back first. Elsewhere posts still snoozed
+    /// are left out, and in the global feed the ones that are back go first.
+    fn with_read_later(&self, posts: Vec<Post>, now: chrono::DateTime<chrono::Utc>) -> Vec<Post> {
+        let queue = &self.posts_state.read_later;
+        let filter = &self.posts_state.current_filter;
+        if *filter == PostFilter::Later {
+            let mut snoozed: Vec<_> = queue.iter().collect();
+            snoozed.sort_by_key(|snoozed| snoozed.until);
+            return snoozed.into_iter().map(|snoozed| snoozed.post.clone()).collect();
+        }
+
+        let global = *filter == PostFilter::All;
+        let hidden: std::collections::HashSet<Uuid> = queue
+            .iter()
+            .filter(|snoozed| global || snoozed.until > now)
+            .map(|snoozed| snoozed.post.id)
+            .collect();
+        let mut posts: Vec<Post> = posts.into_iter().filter(|post| !hidden.contains(&post.id)).collect();
+        if global {
+            let back = queue.iter().filter(|snoozed| snoozed.until <= now);
+            posts.splice(0..0, back.map(|snoozed| snoozed.post.clone()));
+        }
+        posts
+    }
+
+    /// Snooze the selected post (z): ask how long for. In the Later feed,
+    /// z puts the post back in the feed instead.
+    pub fn start_snooze(&mut self) {
+        let Some(post) = self.selected_feed_post() else {
+            return;
+        };
+        if self.posts_state.current_filter == PostFilter::Later {
+            let post_id = post.id;
+            self.finish_read_later(post_id);
+            self.drop_from_feed(post_id);
+            self.toasts.info("Unsnoozed, back in the feed");
+            return;
+        }
+        self.posts_state.snooze_input = Some(String::new());
+        self.input_mode = InputMode::Typing;
+    }
+
+    /// Typing how long to snooze for; Enter snoozes, Esc cancels
+    pub fn handle_snooze_input(&mut self, key: KeyEvent) {
+        let Some(input) = &mut self.posts_state.snooze_input else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => {
+                self.posts_state.snooze_input = None;
+                self.input_mode = InputMode::Navigation;
+            }
+            KeyCode::Enter => self.snooze_selected_post(chrono::Utc::now()),
+            _ => {}
+        }
+    }
+
+    /// Hide the selected post until the delay typed (`30m`, `3h`, `1d`, `1w`)
+    /// has passed, then bring it back at the top of the feed
+    fn snooze_selected_post(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        let Some(input) = self.posts_state.snooze_input.clone() else {
+            return;
+        };
+        let Some(minutes) = crate::status::parse_duration(input.trim()) else {
+            self.toasts.error("Snooze for how long? Try 30m, 3h, 1d or 1w");
+            return;
+        };
+        self.posts_state.snooze_input = None;
+        self.input_mode = InputMode::Navigation;
+        let Some(post) = self.selected_feed_post().cloned() else {
+            return;
+        };
+
+        let until = now + chrono::Duration::minutes(minutes as i64);
+        self.posts_state.read_later.retain(|snoozed| snoozed.post.id != post.id);
+        self.posts_state.read_later.push(crate::config::SnoozedPost {
+            post: post.clone(),
+            until,
+            resurfaced: false,
+        });
+        self.save_read_later();
+
+        self.drop_from_feed(post.id);
+        self.toasts.success(format!(
+            "Snoozed until {}; it's under Later in the filters",
+            crate::status::format_expiry(until)
+        ));
+    }
+
+    /// Take a post out of the feed on screen; the next one takes its place
+    fn drop_from_feed(&mut self, post_id: Uuid) {
+        self.posts_state.posts.retain(|post| post.id != post_id);
+        let last = self.posts_state.posts.len().checked_sub(1);
+        let selected = self
+            .posts_state
+            .list_state
+            .selected()
+            .zip(last)
+            .map(|(index, last)| index.min(last));
+        self.posts_state.list_state.select(selected);
+    }
+
+    /// Bring back snoozed posts whose time has come: each is announced once
+    /// and, in the global feed, put at the top. Called from the event loop.
+    pub fn resurface_snoozed_posts(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        let mut back = Vec::new();
+        for snoozed in &mut self.posts_state.read_later {
+            if !snoozed.resurfaced && snoozed.until <= now {
+                snoozed.resurfaced = true;
+                back.push(snoozed.post.clone());
+            }
+        }
+        if back.is_empty() {
+            return;
+        }
+
+        for post in back {
+            self.notify(format!("⏰ Snoozed post from @{} is back", post.author_username));
+            if self.posts_state.current_filter == PostFilter::All
+                && !self.posts_state.posts.iter().any(|p| p.id == post.id)
+            {
+                self.posts_state.posts.insert(0, post);
+                // Keep the same post selected
+                if let Some(selected) = self.posts_state.list_state.selected() {
+                    self.posts_state.list_state.select(Some(selected + 1));
+                }
+            }
+        }
+        self.save_read_later();
+    }
+
+    /// A post from the read-later queue has been read (opened) or unsnoozed
+    fn finish_read_later(&mut self, post_id: Uuid) {
+        let before = self.posts_state.read_later.len();
+        self.posts_state.read_later.retain(|snoozed| snoozed.post.id != post_id);
+        if self.posts_state.read_later.len() != before {
+            self.save_read_later();
+        }
+    }
+
+    /// Write the read-later queue to disk
+    fn save_read_later(&self) {
+        if let Some(user) = &self.auth_state.current_user {
+            if let Err(e) = self
+                .config_manager
+                .save_read_later(&user.id.to_string(), &self.posts_state.read_later)
+            {
+                log::warn!("Failed to save read-later queue: {}", e);
+            }
+        }
+    }
+
+    /// Load posts snoozed in an earlier session
+    fn load_read_later(&mut self) {
+        if let Some(user) = &self.auth_state.current_user {
+            match self.config_manager.load_read_later(&user.id.to_string()) {
+                Ok(queue) => self.posts_state.read_later = queue,
+                Err(e) => log::warn!("Ignoring unreadable read-later queue: {}", e),
+            }
+        }
+    }
+
+    /// Load positions saved by an earlier session; the feed opens where it
+    /// was left
+    fn load_list_positions(&mut self) {
+        if let Some(user) = &self.auth_state.current_user {
+            match self.config_manager.load_list_positions(&user.id.to_string()) {
+                Ok(positions) => {
+                    self.posts_state.pending_restore = positions
+                        .feeds
+                        .get(&self.posts_state.current_filter.position_key())
+                        .copied();
+                    self.posts_state.positions = positions.feeds;
+                    self.dms_state.last_conversation = positions.dm_conversation;
+                }
+                Err(e) => log::warn!("Ignoring unreadable list positions: {}", e),
+            }
+        }
+    }
+
     /// Add character to new post content
     pub fn add_char_to_post(&mut self, c: char) {
         if self.posts_state.new_post_content.len() < 280 {
@@ -1128,11 +3188,11 @@ impl App {
         // Parse emoji shortcodes before sending
         let content = crate::emoji::parse_emoji_shortcodes(&self.posts_state.new_post_content);
 
-        match self.api_client.create_post(content).await {
+        match self.create_post_in_feed(content).await {
             Ok(_) => {
                 // Close modal and refresh posts (also switches to navigation mode)
                 self.close_new_post_modal();
-                self.load_posts().await?;
+                self.spawn_load_posts();
             }
             Err(e) => {
                 // Categorize errors for better user feedback
@@ -1145,6 +3205,119 @@ impl App {
     }
 
     /// Load profile data
+    /// Load the channel list for the Channels tab
+    pub async fn load_channels(&mut self) -> Result<()> {
+        self.channels_state.loading = true;
+        self.channels_state.error = None;
+        match self.api_client.get_channels().await {
+            Ok(channels) => {
+                self.channels_state.selected = self
+                    .channels_state
+                    .selected
+                    .min(channels.len().saturating_sub(1));
+                self.channels_state.channels = channels;
+            }
+            Err(e) => {
+                let error_msg = categorize_error(&e.to_string());
+                self.channels_state.error = Some(format!("{} (Press r to retry)", error_msg));
+            }
+        }
+        self.channels_state.loading = false;
+        Ok(())
+    }
+
+    pub fn next_channel(&mut self) {
+        if self.channels_state.selected + 1 < self.channels_state.channels.len() {
+            self.channels_state.selected += 1;
+        }
+    }
+
+    pub fn previous_channel(&mut self) {
+        self.channels_state.selected = self.channels_state.selected.saturating_sub(1);
+    }
+
+    /// Show the selected channel's feed in the Posts tab and mark it read
+    pub async fn open_selected_channel(&mut self) -> Result<()> {
+        let Some(channel) = self
+            .channels_state
+            .channels
+            .get_mut(self.channels_state.selected)
+        else {
+            return Ok(());
+        };
+        let name = channel.name.clone();
+        if channel.joined && channel.unread_count > 0 {
+            channel.unread_count = 0;
+            if let Err(e) = self.api_client.mark_channel_read(&name).await {
+                log::debug!("Failed to mark #{} read: {}", name, e);
+            }
+        }
+        self.current_tab = Tab::Posts;
+        self.apply_filter(PostFilter::Channel(name)).await
+    }
+
+    /// Join the selected channel, or leave it if already a member
+    pub async fn toggle_selected_channel_membership(&mut self) -> Result<()> {
+        let Some(channel) = self.channels_state.channels.get(self.channels_state.selected) else {
+            return Ok(());
+        };
+        let (name, join) = (channel.name.clone(), !channel.joined);
+        match self.api_client.set_channel_joined(&name, join).await {
+            Ok(()) => {
+                if join {
+                    self.toasts.success(format!("Joined #{}", name));
+                } else {
+                    self.toasts.info(format!("Left #{}", name));
+                }
+                self.load_channels().await?;
+                // Keep the same channel selected after the list reorders
+                if let Some(index) = self.channels_state.channels.iter().position(|c| c.name == name) {
+                    self.channels_state.selected = index;
+                }
+            }
+            Err(e) => self.toasts.error(format!("Couldn't update #{}: {}", name, e)),
+        }
+        Ok(())
+    }
+
+    /// Open the prompt for a new channel's name
+    pub fn open_new_channel_prompt(&mut self) {
+        self.channels_state.new_channel_input = Some(String::new());
+        self.channels_state.error = None;
+        self.input_mode = InputMode::Typing;
+    }
+
+    /// Create the channel named in the prompt; the server validates the name
+    pub async fn create_channel(&mut self) -> Result<()> {
+        let Some(name) = self.channels_state.new_channel_input.clone() else {
+            return Ok(());
+        };
+        let name = name.trim().trim_start_matches('#').to_string();
+        if name.is_empty() {
+            self.channels_state.new_channel_input = None;
+            self.input_mode = InputMode::Navigation;
+            return Ok(());
+        }
+        match self.api_client.create_channel(name, String::new()).await {
+            Ok(channel) => {
+                self.channels_state.new_channel_input = None;
+                self.input_mode = InputMode::Navigation;
+                self.toasts.success(format!("Created #{}", channel.name));
+                self.load_channels().await?;
+                if let Some(index) = self
+                    .channels_state
+                    .channels
+                    .iter()
+                    .position(|c| c.name == channel.name)
+                {
+                    self.channels_state.selected = index;
+                }
+            }
+            Err(e) => self.channels_state.error = Some(categorize_error(&e.to_string())),
+        }
+        Ok(())
+    }
+
     pub async fn load_profile(&mut self) -> Result<()> {
         if let Some(user) = &self.auth_state.current_user {
             self.profile_state.loading = true;
@@ -1154,6 +3327,14 @@ impl App {
             match self.api_client.get_profile(user.id).await {
                 Ok(profile) => {
                     self.profile_state.profile = Some(profile);
+                    // Stats come in on their own; the profile shows without them
+                    let client = self.api_client.clone();
+                    let user_id = user.id;
+                    self.task_runner.spawn(TaskKind::ProfileStats, async move {
+                        TaskResult::ProfileStatsLoaded(
+                            client.get_user_stats(user_id).await.map_err(|e| e.to_string()),
+                        )
+                    });
                 }
                 Err(e) => {
                     let error_msg = categorize_error(&e.to_string());
@@ -1162,20 +3343,22 @@ impl App {
                     self.profile_state.loading = false;
                     return Ok(());
                 }
-            }
-
-            // Load user's posts
-            match self
-                .api_client
-                .get_posts(Some(100), Some("newest".to_string()), None, None)
-                .await
+            }
+
+            // Load the first page of the open list; later pages load on scroll
+            self.task_runner.cancel(TaskKind::ProfilePosts);
+            match fetch_profile_posts(
+                self.api_client.clone(),
+                user.id.to_string(),
+                self.profile_state.posts_tab,
+                0,
+            )
+            .await
             {
                 Ok(posts) => {
-                    // Filter to only show current user's posts
-                    self.profile_state.user_posts = posts
-                        .into_iter()
-                        .filter(|p| p.author_id == user.id)
-                        .collect();
+                    self.profile_state.has_more_posts =
+                        posts.len() as i32 == PROFILE_POSTS_PAGE_SIZE;
+                    self.profile_state.user_posts = posts;
                     if !self.profile_state.user_posts.is_empty() {
                         self.profile_state.list_state.select(Some(0));
                     } else {
@@ -1195,14 +3378,77 @@ impl App {
         Ok(())
     }
 
+    /// Fetch the next page of the Profile tab's posts on a background task
+    fn spawn_load_more_profile_posts(&mut self) {
+        let Some(user) = &self.auth_state.current_user else {
+            return;
+        };
+        if !self.profile_state.has_more_posts
+            || self.task_runner.is_running(TaskKind::ProfilePosts)
+        {
+            return;
+        }
+
+        let client = self.api_client.clone();
+        let user_id = user.id.to_string();
+        let tab = self.profile_state.posts_tab;
+        let offset = self.profile_state.user_posts.len() as i32;
+        self.task_runner.spawn(TaskKind::ProfilePosts, async move {
+            TaskResult::ProfilePostsLoaded(
+                fetch_profile_posts(client, user_id, tab, offset)
+                    .await
+                    .map_err(|e| e.to_string()),
+            )
+        });
+    }
+
+    /// Switch the Profile tab between posts, replies and upvoted posts
+    /// (←/→), loading the newly picked list from the start
+    pub fn switch_profile_posts_tab(&mut self, forward: bool) {
+        let tabs = ProfilePostsTab::ALL;
+        let current = tabs
+            .iter()
+            .position(|tab| *tab == self.profile_state.posts_tab)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % tabs.len()
+        } else {
+            (current + tabs.len() - 1) % tabs.len()
+        };
+        self.profile_state.posts_tab = tabs[next];
+        self.profile_state.user_posts.clear();
+        self.profile_state.list_state.select(None);
+        self.profile_state.has_more_posts = true;
+        // Replaces any page still loading for the previous list
+        self.task_runner.cancel(TaskKind::ProfilePosts);
+        self.spawn_load_more_profile_posts();
+    }
+
+    fn apply_profile_posts_page(&mut self, result: Result<Vec<Post>, String>) {
+        match result {
+            Ok(posts) => {
+                self.profile_state.has_more_posts = posts.len() as i32 == PROFILE_POSTS_PAGE_SIZE;
+                self.profile_state.user_posts.extend(posts);
+                if self.profile_state.list_state.selected().is_none()
+                    && !self.profile_state.user_posts.is_empty()
+                {
+                    self.profile_state.list_state.select(Some(0));
+                }
+            }
+            Err(e) => {
+                // Keep what's already shown; scrolling to the end again retries
+                log::warn!("Failed to load more profile posts: {}", e);
+            }
+        }
+    }
+
     /// Open edit bio modal
     pub fn open_edit_bio_modal(&mut self) {
         if let Some(profile) = &self.profile_state.profile {
             self.profile_state.show_edit_bio_modal = true;
-            self.profile_state.edit_bio_content = profile.bio.clone().unwrap_or_default();
-            // Set cursor to end (character count, not byte length)
-            self.profile_state.edit_bio_cursor_position =
-                self.profile_state.edit_bio_content.chars().count();
+            self.profile_state
+                .edit_bio
+                .set_value(profile.bio.as_deref().unwrap_or_default());
             self.input_mode = InputMode::Typing;
         }
     }
@@ -1210,52 +3456,16 @@ impl App {
     /// Close edit bio modal
     pub fn close_edit_bio_modal(&mut self) {
         self.profile_state.show_edit_bio_modal = false;
-        self.profile_state.edit_bio_content.clear();
-        self.profile_state.edit_bio_cursor_position = 0;
+        self.profile_state.edit_bio.clear();
         self.input_mode = InputMode::Navigation;
     }
 
-    /// Add character to bio
-    pub fn add_char_to_bio(&mut self, c: char) {
-        // Check character count, not byte length
-        let char_count = self.profile_state.edit_bio_content.chars().count();
-        if char_count < 160 {
-            // Find the byte position for the cursor
-            let byte_pos = self
-                .profile_state
-                .edit_bio_content
-                .char_indices()
-                .nth(self.profile_state.edit_bio_cursor_position)
-                .map(|(pos, _)| pos)
-                .unwrap_or(self.profile_state.edit_bio_content.len());
-
-            self.profile_state.edit_bio_content.insert(byte_pos, c);
-            self.profile_state.edit_bio_cursor_position += 1;
-        }
-    }
-
-    /// Remove character before cursor (backspace)
-    pub fn remove_char_from_bio(&mut self) {
-        if self.profile_state.edit_bio_cursor_position > 0 {
-            // Find the byte position of the character to remove
-            if let Some((byte_pos, _)) = self
-                .profile_state
-                .edit_bio_content
-                .char_indices()
-                .nth(self.profile_state.edit_bio_cursor_position - 1)
-            {
-                self.profile_state.edit_bio_content.remove(byte_pos);
-                self.profile_state.edit_bio_cursor_position -= 1;
-            }
-        }
-    }
-
     /// Submit bio update
     pub async fn submit_bio_update(&mut self) -> Result<()> {
         if let Some(user) = &self.auth_state.current_user {
             self.profile_state.error = None;
 
-            let bio = self.profile_state.edit_bio_content.clone();
+            let bio = self.profile_state.edit_bio.value().to_string();
 
             match self.api_client.update_bio(user.id, bio).await {
                 Ok(_) => {
@@ -1288,75 +3498,128 @@ impl App {
     // FRIENDS METHODS
     // ============================================================================
 
-    /// Load social connections (following, followers, mutual friends)
+    /// Load the first page of each social list (following, followers, mutual friends)
     pub async fn load_social_connections(&mut self) -> Result<()> {
         self.friends_state.loading = true;
         self.friends_state.error = None;
+        self.task_runner.cancel(TaskKind::SocialList);
 
-        // Load all three lists
-        let following_result = self.api_client.get_following_list().await;
-        let followers_result = self.api_client.get_followers_list().await;
-        let mutual_result = self.api_client.get_mutual_friends_list().await;
-
-        // Check for errors and provide detailed messages
-        if let Err(e) = &following_result {
-            let error_msg = format!("Failed to load following: {}", e);
-            self.friends_state.error = Some(error_msg.clone());
-            self.friends_state.loading = false;
-            return Err(anyhow::anyhow!(error_msg));
-        }
-        if let Err(e) = &followers_result {
-            let error_msg = format!("Failed to load followers: {}", e);
-            self.friends_state.error = Some(error_msg.clone());
-            self.friends_state.loading = false;
-            return Err(anyhow::anyhow!(error_msg));
-        }
-        if let Err(e) = &mutual_result {
-            let error_msg = format!("Failed to load mutual friends: {}", e);
-            self.friends_state.error = Some(error_msg.clone());
-            self.friends_state.loading = false;
-            return Err(anyhow::anyhow!(error_msg));
-        }
-
-        // All succeeded, unwrap safely
-        let following = following_result.unwrap();
-        let followers = followers_result.unwrap();
-        let mutual = mutual_result.unwrap();
-
-        self.friends_state.following = following
-            .into_iter()
-            .map(|u| UserInfo {
-                id: u.id,
-                username: u.username,
-                follower_count: u.follower_count,
-                following_count: u.following_count,
-            })
-            .collect();
-
-        self.friends_state.followers = followers
-            .into_iter()
-            .map(|u| UserInfo {
-                id: u.id,
-                username: u.username,
-                follower_count: u.follower_count,
-                following_count: u.following_count,
-            })
-            .collect();
-
-        self.friends_state.mutual_friends = mutual
-            .into_iter()
-            .map(|u| UserInfo {
-                id: u.id,
-                username: u.username,
-                follower_count: u.follower_count,
-                following_count: u.following_count,
-            })
-            .collect();
+        let query = self.friends_state.search_query.trim().to_string();
+        for tab in [SocialTab::Following, SocialTab::Followers, SocialTab::MutualFriends] {
+            let users = match self
+                .api_client
+                .get_social_page(tab.api_list(), &query, None, SOCIAL_PAGE_SIZE)
+                .await
+            {
+                Ok(users) => users,
+                Err(e) => {
+                    let list = match tab {
+                        SocialTab::Following => "following",
+                        SocialTab::Followers => "followers",
+                        SocialTab::MutualFriends => "mutual friends",
+                    };
+                    let error_msg = format!("Failed to load {}: {}", list, e);
+                    self.friends_state.error = Some(error_msg.clone());
+                    self.friends_state.loading = false;
+                    return Err(anyhow::anyhow!(error_msg));
+                }
+            };
+            self.apply_social_page(tab, query.clone(), false, users);
+        }
 
         self.friends_state.loading = false;
         Ok(())
     }
 
+    fn social_list_mut(&mut self, tab: SocialTab) -> &mut Vec<UserInfo> {
+        match tab {
+            SocialTab::Following => &mut self.friends_state.following,
+            SocialTab::Followers => &mut self.friends_state.followers,
+            SocialTab::MutualFriends => &mut self.friends_state.mutual_friends,
+        }
+    }
+
+    /// Store a fetched page, replacing the tab's list unless `append`
+    fn apply_social_page(
+        &mut self,
+        tab: SocialTab,
+        query: String,
+        append: bool,
+        users: Vec<crate::api::SocialUserInfo>,
+    ) {
+        let has_more = users.len() == SOCIAL_PAGE_SIZE;
+        let users = users.into_iter().map(|u| UserInfo {
+            id: u.id,
+            username: u.username,
+            follower_count: u.follower_count,
+            following_count: u.following_count,
+        });
+        let list = self.social_list_mut(tab);
+        if !append {
+            list.clear();
+        }
+        list.extend(users);
+        self.friends_state
+            .pages
+            .insert(tab, SocialListPage { query, has_more });
+    }
+
+    /// Fetch the next page of the selected tab when the user scrolls past
+    /// the last loaded user
+    fn spawn_load_more_social(&mut self) {
+        let tab = self.friends_state.selected_tab;
+        let Some(page) = self.friends_state.pages.get(&tab) else {
+            return;
+        };
+        if !page.has_more
+            || page.query != self.friends_state.search_query.trim()
+            || self.task_runner.is_running(TaskKind::SocialList)
+        {
+            return;
+        }
+        let cursor = match tab {
+            SocialTab::Following => self.friends_state.following.last(),
+            SocialTab::Followers => self.friends_state.followers.last(),
+            SocialTab::MutualFriends => self.friends_state.mutual_friends.last(),
+        }
+        .map(|user| user.id.clone());
+        self.spawn_social_page(tab, cursor);
+    }
+
+    /// Refetch the selected tab from the start if it was loaded for a
+    /// different search
+    fn refresh_social_tab_for_search(&mut self) {
+        let tab = self.friends_state.selected_tab;
+        let stale = self
+            .friends_state
+            .pages
+            .get(&tab)
+            .is_some_and(|page| page.query != self.friends_state.search_query.trim());
+        if stale {
+            self.spawn_social_page(tab, None);
+        }
+    }
+
+    /// Fetch a page of `tab` for the current search; without a cursor it
+    /// replaces the tab's list
+    fn spawn_social_page(&mut self, tab: SocialTab, cursor: Option<String>) {
+        let query = self.friends_state.search_query.trim().to_string();
+        let append = cursor.is_some();
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::SocialList, async move {
+            let result = client
+                .get_social_page(tab.api_list(), &query, cursor.as_deref(), SOCIAL_PAGE_SIZE)
+                .await
+                .map_err(|e| e.to_string());
+            TaskResult::SocialPageLoaded {
+                tab,
+                query,
+                append,
+                result,
+            }
+        });
+    }
+
     /// Close social connections modal
     pub fn close_friends_modal(&mut self) {
         self.friends_state.show_friends_modal = false;
@@ -1401,8 +3664,11 @@ impl App {
                     self.friends_state.search_mode = false;
                     self.friends_state.search_query.clear();
                 }
-                _ => {}
+                _ => return Ok(()),
             }
+            // Loaded users are filtered straight away; the server search
+            // finds the ones not loaded yet
+            self.refresh_social_tab_for_search();
             return Ok(());
         }
 
@@ -1416,6 +3682,9 @@ impl App {
                 let max_index = self.get_filtered_social_list().len().saturating_sub(1);
                 if self.friends_state.selected_index < max_index {
                     self.friends_state.selected_index += 1;
+                } else {
+                    // At the last loaded user: fetch more if there are any
+                    self.spawn_load_more_social();
                 }
             }
             KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('L') => {
@@ -1426,6 +3695,7 @@ impl App {
                     SocialTab::MutualFriends => SocialTab::Following,
                 };
                 self.friends_state.selected_index = 0;
+                self.refresh_social_tab_for_search();
             }
             KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => {
                 // Cycle through tabs backwards
@@ -1435,6 +3705,7 @@ impl App {
                     SocialTab::MutualFriends => SocialTab::Followers,
                 };
                 self.friends_state.selected_index = 0;
+                self.refresh_social_tab_for_search();
             }
             KeyCode::Char('/') => {
                 // Enter search mode
@@ -1542,7 +3813,7 @@ impl App {
                 // Reload hashtags list
                 self.load_hashtags().await?;
                 // Also reload filter modal data to update the filter list
-                self.load_filter_modal_data().await?;
+                self.spawn_load_filter_modal_data();
                 self.hashtags_state.add_hashtag_name.clear();
                 self.hashtags_state.show_add_hashtag_input = false;
                 Ok(())
@@ -1563,7 +3834,7 @@ impl App {
                 // Reload hashtags list
                 self.load_hashtags().await?;
                 // Also reload filter modal data to update the filter list
-                self.load_filter_modal_data().await?;
+                self.spawn_load_filter_modal_data();
                 Ok(())
             }
             Err(e) => {
@@ -1641,13 +3912,9 @@ impl App {
                 self.hashtags_state.add_hashtag_name.clear();
                 self.hashtags_state.error = None;
             }
-            KeyCode::Char(c) => {
-                self.hashtags_state.add_hashtag_name.push(c);
-            }
-            KeyCode::Backspace => {
-                self.hashtags_state.add_hashtag_name.pop();
+            _ => {
+                self.hashtags_state.add_hashtag_name.handle_key(key);
             }
-            _ => {}
         }
         Ok(())
     }
@@ -1681,6 +3948,9 @@ impl App {
         self.apply_composer_styling(&mut textarea);
         self.composer_state.textarea = textarea;
         self.composer_state.max_chars = 280;
+        self.composer_state.server_draft = None;
+        self.composer_state.anonymous = false;
+        self.composer_state.reply_policy = ReplyPolicy::Everyone;
         self.input_mode = InputMode::Typing;
     }
 
@@ -1691,6 +3961,14 @@ impl App {
         parent_author: String,
         parent_content: String,
     ) {
+        // The thread's top post decides who may reply anywhere in it
+        if let Some(root) = self.post_detail_state.as_ref().and_then(|s| s.post.as_ref()) {
+            if !root.can_reply {
+                let message = root.reply_policy.explain(&root.author_username);
+                self.toasts.info(message);
+                return;
+            }
+        }
         self.composer_state.mode = Some(ComposerMode::Reply {
             parent_post_id,
             parent_author,
@@ -1732,14 +4010,467 @@ impl App {
         self.input_mode = InputMode::Typing;
     }
 
-    /// Close composer
-    pub fn close_composer(&mut self) {
-        self.composer_state.mode = None;
-        let mut textarea = TextArea::default();
-        textarea.set_hard_tab_indent(true);
-        self.apply_composer_styling(&mut textarea);
-        self.composer_state.textarea = textarea;
-        self.input_mode = InputMode::Navigation;
+    /// Open composer for setting the status line; submitting it empty clears it
+    pub fn open_composer_set_status(&mut self, current_status: String) {
+        self.composer_state.mode = Some(ComposerMode::SetStatus);
+        let mut textarea = TextArea::from(current_status.lines());
+        textarea.set_hard_tab_indent(true);
+        self.apply_composer_styling(&mut textarea);
+        self.composer_state.textarea = textarea;
+        self.composer_state.max_chars = STATUS_COMPOSER_MAX_CHARS;
+        self.input_mode = InputMode::Typing;
+    }
+
+    /// Open composer for cross-posting a DM, starting from `content`
+    pub fn open_composer_crosspost(
+        &mut self,
+        message_id: Uuid,
+        other_username: String,
+        content: String,
+    ) {
+        self.composer_state.mode = Some(ComposerMode::CrossPost {
+            message_id,
+            other_username,
+        });
+        let mut textarea = TextArea::default();
+        textarea.set_hard_tab_indent(true);
+        self.apply_composer_styling(&mut textarea);
+        // Inserted rather than loaded so the cursor lands after the quote
+        textarea.insert_str(content);
+        self.composer_state.textarea = textarea;
+        self.composer_state.max_chars = 280;
+        self.input_mode = InputMode::Typing;
+    }
+
+    /// Quote the selected DM (or the newest one) in a new post for the
+    /// other participant to approve (Ctrl+X)
+    pub fn open_crosspost_for_selected_message(&mut self) {
+        let Some(other_username) = self
+            .dms_state
+            .selected_conversation_index
+            .and_then(|index| self.dms_state.conversations.get(index))
+            .map(|convo| convo.other_username.clone())
+        else {
+            return;
+        };
+        let message = match self.dms_state.selected_message {
+            Some(index) => self.dms_state.messages.get(index),
+            None => self.dms_state.messages.last(),
+        };
+        let Some(message) = message else {
+            self.toasts.info("No message to cross-post");
+            return;
+        };
+
+        let mut quote = format!("@{} wrote:\n", message.from_username);
+        for line in message.content.lines() {
+            quote.push_str("> ");
+            quote.push_str(line);
+            quote.push('\n');
+        }
+        quote.push('\n');
+        let message_id = message.id;
+        self.open_composer_crosspost(message_id, other_username, quote);
+    }
+
+    /// Close composer
+    pub fn close_composer(&mut self) {
+        self.composer_state.mode = None;
+        let mut textarea = TextArea::default();
+        textarea.set_hard_tab_indent(true);
+        self.apply_composer_styling(&mut textarea);
+        self.composer_state.textarea = textarea;
+        self.composer_state.server_draft = None;
+        self.composer_state.anonymous = false;
+        self.composer_state.reply_policy = ReplyPolicy::Everyone;
+        self.input_mode = InputMode::Navigation;
+    }
+
+    /// Cycle who may reply to the new post being written (Ctrl+R): everyone,
+    /// people the author follows, or people mentioned in it
+    pub fn cycle_reply_policy(&mut self) {
+        if self.composer_state.mode != Some(ComposerMode::NewPost) {
+            return;
+        }
+        self.composer_state.reply_policy = self.composer_state.reply_policy.next();
+    }
+
+    /// Switch the new post being written between signed and anonymous
+    /// (Ctrl+T), where the instance allows anonymous posts
+    pub fn toggle_anonymous_post(&mut self) {
+        if self.composer_state.mode != Some(ComposerMode::NewPost) {
+            return;
+        }
+        if matches!(self.posts_state.current_filter, PostFilter::Channel(_)) {
+            self.toasts.info("Channel posts can't be anonymous");
+            return;
+        }
+        let allowed = self
+            .instance_features
+            .as_ref()
+            .is_some_and(|features| features.anonymous_posting);
+        if !allowed {
+            self.toasts.info("This instance doesn't allow anonymous posts");
+            return;
+        }
+        self.composer_state.anonymous = !self.composer_state.anonymous;
+    }
+
+    /// Save the new post being written as a server-side draft (Ctrl+D),
+    /// updating the draft it came from if there is one
+    pub async fn save_composer_draft(&mut self) -> Result<()> {
+        if self.composer_state.mode != Some(ComposerMode::NewPost) {
+            self.toasts.info("Only new posts can be saved as drafts");
+            return Ok(());
+        }
+        let content = self.composer_state.get_content();
+        if content.trim().is_empty() {
+            self.toasts.info("Nothing to save yet");
+            return Ok(());
+        }
+
+        let result = match self.composer_state.server_draft {
+            Some(draft_id) => self.api_client.update_draft(draft_id, content).await,
+            None => self.api_client.create_draft(content).await,
+        };
+        match result {
+            Ok(draft) => {
+                self.composer_state.server_draft = Some(draft.id);
+                self.drafts.drafts.retain(|d| d.id != draft.id);
+                self.drafts.drafts.insert(0, draft);
+                self.toasts.success("✓ Draft saved");
+            }
+            // Deleted from another machine; the next save starts a new draft
+            Err(crate::api::ApiError::NotFound(_)) => {
+                self.composer_state.server_draft = None;
+                self.toasts.error("That draft was deleted elsewhere; press Ctrl+D to save it again");
+            }
+            Err(e) => self.toasts.error(format!("Couldn't save draft: {}", e)),
+        }
+        Ok(())
+    }
+
+    /// List server-side drafts over the new-post composer (Ctrl+O)
+    pub fn open_drafts(&mut self) {
+        if self.composer_state.mode != Some(ComposerMode::NewPost) {
+            return;
+        }
+        self.drafts.show = true;
+        self.drafts.selected = 0;
+        self.drafts.loading = true;
+        self.drafts.error = None;
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Drafts, async move {
+            TaskResult::DraftsLoaded(client.list_drafts().await.map_err(|e| e.to_string()))
+        });
+    }
+
+    pub fn close_drafts(&mut self) {
+        self.task_runner.cancel(TaskKind::Drafts);
+        self.drafts.show = false;
+        self.drafts.loading = false;
+    }
+
+    /// Keys while the drafts list is open: j/k pick, Enter loads the draft
+    /// into the composer, Esc goes back to it unchanged. Deleting ('x') is
+    /// async and handled by the runner.
+    pub fn handle_drafts_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let count = self.drafts.drafts.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.close_drafts(),
+            KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                self.drafts.selected = (self.drafts.selected + 1).min(count - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.drafts.selected = self.drafts.selected.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                if let Some(draft) = self.drafts.drafts.get(self.drafts.selected).cloned() {
+                    self.open_composer_new_post();
+                    self.composer_state.textarea.insert_str(draft.content);
+                    self.composer_state.server_draft = Some(draft.id);
+                    self.close_drafts();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Delete the selected draft in the drafts list ('x')
+    pub async fn delete_selected_draft(&mut self) -> Result<()> {
+        let Some(draft_id) = self.drafts.drafts.get(self.drafts.selected).map(|d| d.id) else {
+            return Ok(());
+        };
+        match self.api_client.delete_draft(draft_id).await {
+            // Already deleted elsewhere is as good as deleted here
+            Ok(()) | Err(crate::api::ApiError::NotFound(_)) => {
+                self.drafts.drafts.retain(|d| d.id != draft_id);
+                self.drafts.selected = self
+                    .drafts
+                    .selected
+                    .min(self.drafts.drafts.len().saturating_sub(1));
+                if self.composer_state.server_draft == Some(draft_id) {
+                    self.composer_state.server_draft = None;
+                }
+                self.toasts.info("Draft deleted");
+            }
+            Err(e) => self.toasts.error(format!("Couldn't delete draft: {}", e)),
+        }
+        Ok(())
+    }
+
+    /// List upcoming events from the feed ('c')
+    pub fn open_events(&mut self) {
+        self.events.show = true;
+        self.events.selected = 0;
+        self.events.loading = true;
+        self.events.error = None;
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Events, async move {
+            TaskResult::EventsLoaded(client.get_upcoming_events().await.map_err(|e| e.to_string()))
+        });
+    }
+
+    pub fn close_events(&mut self) {
+        self.task_runner.cancel(TaskKind::Events);
+        self.events.show = false;
+        self.events.loading = false;
+    }
+
+    /// Keys while the events list is open: j/k pick, Esc closes. RSVPs and
+    /// opening an event are async and handled by the runner.
+    pub fn handle_events_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let count = self.events.events.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.close_events(),
+            KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                self.events.selected = (self.events.selected + 1).min(count - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.events.selected = self.events.selected.saturating_sub(1);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Show the past week's top posts and contributors ('H')
+    pub fn open_highlights(&mut self) {
+        self.highlights.show = true;
+        self.highlights.selected = 0;
+        self.highlights.loading = true;
+        self.highlights.error = None;
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Highlights, async move {
+            let result = async {
+                let top_posts = client.get_top_posts().await?;
+                let top_contributors = client.get_top_contributors().await?;
+                Ok::<_, crate::api::ApiError>((top_posts, top_contributors))
+            }
+            .await;
+            TaskResult::HighlightsLoaded(result.map_err(|e| e.to_string()))
+        });
+    }
+
+    pub fn close_highlights(&mut self) {
+        self.task_runner.cancel(TaskKind::Highlights);
+        self.highlights.show = false;
+        self.highlights.loading = false;
+    }
+
+    /// Show the weekly changelogs of followed hashtags ('W')
+    pub fn open_hashtag_digest(&mut self) {
+        self.hashtag_digest.show = true;
+        self.hashtag_digest.selected = 0;
+        self.load_hashtag_digest();
+    }
+
+    fn load_hashtag_digest(&mut self) {
+        self.hashtag_digest.loading = true;
+        self.hashtag_digest.error = None;
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::HashtagDigest, async move {
+            let result = client.get_hashtag_digest().await.map_err(|e| e.to_string());
+            TaskResult::HashtagDigestLoaded(result)
+        });
+    }
+
+    pub fn close_hashtag_digest(&mut self) {
+        self.task_runner.cancel(TaskKind::HashtagDigest);
+        self.hashtag_digest.show = false;
+        self.hashtag_digest.loading = false;
+    }
+
+    /// Show the posts opened in earlier threads (Ctrl+H)
+    pub fn open_reading_history(&mut self) {
+        self.reading_history.show = true;
+        self.reading_history.search.clear();
+        self.reading_history.selected = 0;
+        self.input_mode = InputMode::Typing;
+    }
+
+    pub fn close_reading_history(&mut self) {
+        self.reading_history.show = false;
+        self.input_mode = InputMode::Navigation;
+    }
+
+    /// Keys while the reading history is open: typing searches, arrows
+    /// move, Esc or Ctrl+H closes. Opening a post is async and handled by the runner.
+    pub fn handle_reading_history_keys(&mut self, key: KeyEvent) -> Result<()> {
+        if key.code == KeyCode::Esc || handlers::is_reading_history_key(&key) {
+            self.close_reading_history();
+            return Ok(());
+        }
+        let history = &mut self.reading_history;
+        match key.code {
+            KeyCode::Down => {
+                let count = history.matches().len();
+                history.selected = (history.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up => history.selected = history.selected.saturating_sub(1),
+            KeyCode::Backspace => {
+                history.search.pop();
+                history.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                history.search.push(c);
+                history.selected = 0;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Put a post opened in the thread view at the top of the reading history
+    fn record_reading_history(&mut self, post: Post, now: chrono::DateTime<chrono::Utc>) {
+        let entries = &mut self.reading_history.entries;
+        entries.retain(|entry| entry.post.id != post.id);
+        entries.insert(0, crate::config::HistoryEntry { post, opened_at: now });
+        entries.truncate(MAX_READING_HISTORY);
+        self.save_reading_history();
+    }
+
+    /// Write the reading history to disk
+    fn save_reading_history(&self) {
+        if let Some(user) = &self.auth_state.current_user {
+            if let Err(e) = self
+                .config_manager
+                .save_history(&user.id.to_string(), &self.reading_history.entries)
+            {
+                log::warn!("Failed to save reading history: {}", e);
+            }
+        }
+    }
+
+    /// Load the posts read in earlier sessions
+    fn load_reading_history(&mut self) {
+        if let Some(user) = &self.auth_state.current_user {
+            match self.config_manager.load_history(&user.id.to_string()) {
+                Ok(entries) => self.reading_history.entries = entries,
+                Err(e) => log::warn!("Ignoring unreadable reading history: {}", e),
+            }
+        }
+    }
+
+    /// Keys while the Digest section is open: j/k move between hashtags
+    /// and their posts, Esc closes. Toggling a hashtag and opening a post
+    /// are async and handled by the runner.
+    pub fn handle_hashtag_digest_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let count = self.hashtag_digest.rows().len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.close_hashtag_digest(),
+            KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                self.hashtag_digest.selected = (self.hashtag_digest.selected + 1).min(count - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.hashtag_digest.selected = self.hashtag_digest.selected.saturating_sub(1);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Turn the selected hashtag's weekly changelog off, or back on (Space)
+    pub async fn toggle_selected_hashtag_digest(&mut self) -> Result<()> {
+        let index = match self.hashtag_digest.selected_row() {
+            Some(HashtagDigestRow::Hashtag(index)) | Some(HashtagDigestRow::Post(index, _)) => index,
+            None => return Ok(()),
+        };
+        let entry = &self.hashtag_digest.entries[index];
+        let (hashtag, enabled) = (entry.hashtag.clone(), !entry.enabled);
+        if let Err(e) = self.api_client.set_hashtag_digest(&hashtag, enabled).await {
+            self.hashtag_digest.error = Some(categorize_error(&e.to_string()));
+            return Ok(());
+        }
+
+        let entry = &mut self.hashtag_digest.entries[index];
+        entry.enabled = enabled;
+        if enabled {
+            // Its latest changelog comes with the refreshed list
+            self.load_hashtag_digest();
+        } else {
+            entry.changelog = None;
+        }
+        // Stay on the hashtag, whose posts may have just gone
+        self.hashtag_digest.selected = self
+            .hashtag_digest
+            .rows()
+            .iter()
+            .position(|row| *row == HashtagDigestRow::Hashtag(index))
+            .unwrap_or(0);
+        Ok(())
+    }
+
+    /// Keys while the highlights are open: j/k pick a post, Esc closes.
+    /// Opening a post is async and handled by the runner.
+    pub fn handle_highlights_keys(&mut self, key: KeyEvent) -> Result<()> {
+        let count = self.highlights.top_posts.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.close_highlights(),
+            KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                self.highlights.selected = (self.highlights.selected + 1).min(count - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.highlights.selected = self.highlights.selected.saturating_sub(1);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Answer the selected event with `status` ('g' going, 'i' interested);
+    /// giving the same answer again withdraws it
+    pub async fn toggle_selected_event_rsvp(&mut self, status: fido_types::RsvpStatus) -> Result<()> {
+        let Some(post) = self.events.events.get(self.events.selected) else {
+            return Ok(());
+        };
+        let Some(fido_types::PostKind::Event(event)) = &post.kind else {
+            return Ok(());
+        };
+        let post_id = post.id;
+        let previous = event.my_rsvp;
+        let next = if previous == Some(status) { None } else { Some(status) };
+
+        if let Err(e) = self.api_client.set_event_rsvp(post_id, next).await {
+            self.toasts.error(format!("Couldn't update RSVP: {}", e));
+            return Ok(());
+        }
+        if let Some(fido_types::PostKind::Event(event)) = self
+            .events
+            .events
+            .iter_mut()
+            .find(|p| p.id == post_id)
+            .and_then(|p| p.kind.as_mut())
+        {
+            apply_rsvp(event, next);
+        }
+        match next {
+            Some(fido_types::RsvpStatus::Going) => self.toasts.success("You're going"),
+            Some(fido_types::RsvpStatus::Interested) => self.toasts.info("Marked as interested"),
+            None => self.toasts.info("RSVP withdrawn"),
+        }
+        Ok(())
     }
 
     /// Apply consistent styling to composer TextArea
@@ -1769,7 +4500,7 @@ impl App {
             let current_count = self.composer_state.char_count();
 
             // Only allow input if under the limit
-            if current_count >= self.composer_state.max_chars {
+            if current_count >= self.composer_input_limit() {
                 // Don't process this character - limit reached
                 return;
             }
@@ -1779,25 +4510,88 @@ impl App {
         use tui_textarea::Input;
         let input = Input::from(crossterm::event::Event::Key(key));
         self.composer_state.textarea.input(input);
+    }
 
-        // After input, check if we need to wrap the current line
-        // This ensures text stays visible within the modal
-        self.wrap_composer_text_if_needed();
+    /// Whether the composer's text could be published as a gist when it's
+    /// too long for a post: new posts under the user's name, outside channels
+    pub fn can_post_as_gist(&self) -> bool {
+        self.composer_state.mode == Some(ComposerMode::NewPost)
+            && !self.composer_state.anonymous
+            && !matches!(self.posts_state.current_filter, PostFilter::Channel(_))
     }
 
-    /// Wrap text in composer if current line exceeds reasonable width
-    fn wrap_composer_text_if_needed(&mut self) {
-        crate::text_wrapper::wrap_textarea_if_needed(
-            &mut self.composer_state.textarea,
-            crate::text_wrapper::WrapConfig::COMPOSER,
-        );
+    /// How many characters the composer takes: past the post limit when the
+    /// overflow can go to a gist
+    fn composer_input_limit(&self) -> usize {
+        if self.can_post_as_gist() {
+            GIST_POST_MAX_CHARS
+        } else {
+            self.composer_state.max_chars
+        }
+    }
+
+    /// Publish a new post too long for the feed as a gist, posting its
+    /// opening words and the link (Ctrl+G)
+    pub async fn submit_composer_as_gist(&mut self) -> Result<()> {
+        if self.composer_state.mode != Some(ComposerMode::NewPost) {
+            return Ok(());
+        }
+        if !self.can_post_as_gist() {
+            self.posts_state.error = Some(
+                "Validation Error: Only posts under your name outside channels can be gists"
+                    .to_string(),
+            );
+            return Ok(());
+        }
+        if self.composer_state.char_count() <= self.composer_state.max_chars {
+            self.posts_state.error = Some(
+                "Validation Error: This fits in a post; gists are for longer text".to_string(),
+            );
+            return Ok(());
+        }
+
+        self.posts_state.error = None;
+        let content = crate::emoji::parse_emoji_shortcodes(&self.composer_state.get_content());
+        let result = self.api_client.create_gist_post(content).await;
+        self.finish_new_post(result).await;
+        Ok(())
+    }
+
+    /// Close the composer once a new post went through, or show why not
+    async fn finish_new_post(&mut self, result: crate::api::ApiResult<Post>) {
+        match result {
+            Ok(_) => {
+                self.note_post_created();
+                // Once posted, the draft it came from is done with
+                if let Some(draft_id) = self.composer_state.server_draft {
+                    if let Err(e) = self.api_client.delete_draft(draft_id).await {
+                        log::warn!("Failed to delete posted draft: {}", e);
+                    }
+                    self.drafts.drafts.retain(|d| d.id != draft_id);
+                }
+                self.close_composer();
+                self.spawn_load_posts();
+            }
+            Err(e) => {
+                self.posts_state.error = Some(categorize_error(&e.to_string()));
+            }
+        }
     }
 
     /// Submit composer content based on mode
     pub async fn submit_composer(&mut self) -> Result<()> {
+        if let Some(ComposerMode::NewJob(form)) = &self.composer_state.mode {
+            let form = form.clone();
+            return self.advance_job_composer(form).await;
+        }
         let content = self.composer_state.get_content();
         let trimmed = content.trim();
 
+        // An empty status clears it
+        if trimmed.is_empty() && self.composer_state.mode == Some(ComposerMode::SetStatus) {
+            return self.clear_status().await;
+        }
+
         // Validate empty input
         if trimmed.is_empty() {
             match &self.composer_state.mode {
@@ -1821,7 +4615,12 @@ impl App {
                     self.profile_state.error =
                         Some("Validation Error: Bio cannot be empty.".to_string());
                 }
-                None => {}
+                Some(ComposerMode::SetStatus) => {}
+                Some(ComposerMode::CrossPost { .. }) => {
+                    self.dms_state.error =
+                        Some("Validation Error: Cannot post empty content.".to_string());
+                }
+                Some(ComposerMode::NewJob(_)) | None => {}
             }
             return Ok(());
         }
@@ -1834,6 +4633,10 @@ impl App {
                 self.composer_state.max_chars, char_count
             );
             match &self.composer_state.mode {
+                Some(ComposerMode::NewPost) if self.can_post_as_gist() => {
+                    self.posts_state.error =
+                        Some(format!("{}. Ctrl+G posts it as a gist.", error_msg));
+                }
                 Some(ComposerMode::NewPost) => {
                     self.posts_state.error = Some(error_msg);
                 }
@@ -1842,10 +4645,13 @@ impl App {
                         detail_state.error = Some(error_msg);
                     }
                 }
-                Some(ComposerMode::EditBio) => {
+                Some(ComposerMode::EditBio) | Some(ComposerMode::SetStatus) => {
                     self.profile_state.error = Some(error_msg);
                 }
-                None => {}
+                Some(ComposerMode::CrossPost { .. }) => {
+                    self.dms_state.error = Some(error_msg);
+                }
+                Some(ComposerMode::NewJob(_)) | None => {}
             }
             return Ok(());
         }
@@ -1857,15 +4663,32 @@ impl App {
         match &self.composer_state.mode {
             Some(ComposerMode::NewPost) => {
                 self.posts_state.error = None;
-                match self.api_client.create_post(parsed_content).await {
-                    Ok(_) => {
-                        self.close_composer();
-                        self.load_posts().await?;
+                // `/job` on its own switches to the guided job composer
+                if parsed_content.trim() == "/job" {
+                    self.composer_state.server_draft = None;
+                    self.composer_state.anonymous = false;
+                    self.start_job_step(JobForm::new());
+                    return Ok(());
+                }
+                let result = match parse_post_command(&parsed_content) {
+                    Some(Err(e)) => {
+                        self.posts_state.error = Some(format!("Validation Error: {}", e));
+                        return Ok(());
                     }
-                    Err(e) => {
-                        self.posts_state.error = Some(categorize_error(&e.to_string()));
+                    Some(Ok(_)) if self.composer_state.anonymous => {
+                        self.posts_state.error = Some(
+                            "Validation Error: Events and review requests can't be anonymous"
+                                .to_string(),
+                        );
+                        return Ok(());
                     }
-                }
+                    Some(Ok(PostCommand::Event(event))) => self.api_client.create_event(event).await,
+                    Some(Ok(PostCommand::Review(review))) => {
+                        self.api_client.create_review_request(review).await
+                    }
+                    None => self.create_post_in_feed(parsed_content).await,
+                };
+                self.finish_new_post(result).await;
             }
             Some(ComposerMode::Reply { parent_post_id, .. }) => {
                 let post_id = *parent_post_id;
@@ -1897,6 +4720,7 @@ impl App {
                 match self.api_client.create_reply(post_id, parsed_content).await {
                     Ok(new_reply) => {
                         let new_reply_id = new_reply.id;
+                        self.note_post_created();
                         
                         if let Some(ref mut f) = log {
                             let _ = writeln!(f, "Reply created successfully, new_reply_id={}", new_reply_id);
@@ -2004,93 +4828,690 @@ impl App {
                     }
                 }
             }
-            None => {}
+            Some(ComposerMode::SetStatus) => {
+                if let Some(user) = &self.auth_state.current_user {
+                    self.profile_state.error = None;
+                    let request = crate::status::parse_status_input(&parsed_content);
+                    match self.api_client.set_status(user.id, request).await {
+                        Ok(_) => {
+                            self.close_composer();
+                            self.toasts.success("Status updated");
+                            self.load_profile().await?;
+                        }
+                        Err(e) => {
+                            self.profile_state.error = Some(categorize_error(&e.to_string()));
+                        }
+                    }
+                }
+            }
+            Some(ComposerMode::CrossPost {
+                message_id,
+                other_username,
+            }) => {
+                let (message_id, other_username) = (*message_id, other_username.clone());
+                self.dms_state.error = None;
+                match self.api_client.request_crosspost(message_id, parsed_content).await {
+                    Ok(()) => {
+                        self.close_composer();
+                        self.toasts
+                            .success(format!("Sent to @{} for approval", other_username));
+                    }
+                    Err(e) => {
+                        self.dms_state.error = Some(categorize_error(&e.to_string()));
+                    }
+                }
+            }
+            Some(ComposerMode::NewJob(_)) | None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Show the guided job composer at `form`'s step, with an empty input
+    fn start_job_step(&mut self, form: JobForm) {
+        let mut textarea = TextArea::default();
+        textarea.set_hard_tab_indent(true);
+        self.apply_composer_styling(&mut textarea);
+        self.composer_state.textarea = textarea;
+        self.composer_state.max_chars = form.step.max_chars();
+        self.composer_state.mode = Some(ComposerMode::NewJob(form));
+        self.input_mode = InputMode::Typing;
+    }
+
+    /// Take the answer to the guided job composer's current step, moving on
+    /// to the next step or posting the job after the last
+    async fn advance_job_composer(&mut self, mut form: JobForm) -> Result<()> {
+        let answer = crate::emoji::parse_emoji_shortcodes(&self.composer_state.get_content());
+        if self.composer_state.char_count() > form.step.max_chars() {
+            self.toasts.error(format!(
+                "{} is limited to {} characters",
+                form.step.prompt(),
+                form.step.max_chars()
+            ));
+            return Ok(());
+        }
+        match form.answer(&answer) {
+            Err(e) => self.toasts.error(e),
+            Ok(None) => self.start_job_step(form),
+            Ok(Some(job)) => match self.api_client.create_job(job).await {
+                Ok(_) => {
+                    self.note_post_created();
+                    self.close_composer();
+                    self.spawn_load_posts();
+                    self.toasts.success("✓ Job posted");
+                }
+                Err(e) => self.toasts.error(categorize_error(&e.to_string())),
+            },
+        }
+        Ok(())
+    }
+
+    /// Clear the current user's status from the status composer
+    async fn clear_status(&mut self) -> Result<()> {
+        let Some(user) = &self.auth_state.current_user else {
+            return Ok(());
+        };
+        match self.api_client.clear_status(user.id).await {
+            Ok(()) => {
+                self.close_composer();
+                self.toasts.info("Status cleared");
+                self.load_profile().await?;
+            }
+            Err(e) => {
+                self.profile_state.error = Some(categorize_error(&e.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Load DM conversations
+    pub async fn load_conversations(&mut self) -> Result<()> {
+        self.dms_state.loading = true;
+        self.dms_state.error = None;
+
+        let result = fetch_conversations(self.api_client.clone()).await;
+        self.apply_conversations_result(result);
+
+        Ok(())
+    }
+
+    /// Load DM conversations on a background task
+    pub fn spawn_load_conversations(&mut self) {
+        self.dms_state.loading = true;
+        self.dms_state.error = None;
+
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Conversations, async move {
+            TaskResult::ConversationsLoaded(fetch_conversations(client).await)
+        });
+    }
+
+    /// Refresh unread counts in the background, for the tab bar badges
+    pub fn spawn_poll_unread_counts(&mut self) {
+        if self.api_client.session_token().is_none()
+            || self.task_runner.is_running(TaskKind::Conversations)
+        {
+            return;
+        }
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::UnreadCounts, async move {
+            TaskResult::UnreadCountsPolled(fetch_conversations(client).await)
+        });
+    }
+
+    /// Apply a background unread poll without disturbing the DMs tab's
+    /// list or selection
+    fn apply_unread_poll(&mut self, result: Result<Vec<Conversation>, String>) {
+        match result {
+            Ok(conversations) => {
+                self.update_unread_counts(&conversations);
+                for convo in &mut self.dms_state.conversations {
+                    if let Some(count) = self.dms_state.unread_counts.get(&convo.other_user_id) {
+                        convo.unread_count = *count as i32;
+                    }
+                }
+            }
+            Err(e) => log::debug!("Unread poll failed: {}", e),
+        }
+    }
+
+    /// Replace the unread counts with the server's, announcing messages that
+    /// arrived since the last update. The conversation open on the DMs tab
+    /// stays at zero since it's being read.
+    fn update_unread_counts(&mut self, conversations: &[Conversation]) {
+        // Counts from an earlier load, to spot messages that came in since
+        let previous_unread =
+            (!self.dms_state.unread_counts.is_empty()).then(|| self.unread_dm_count());
+        let reading = self
+            .dms_state
+            .current_conversation_user
+            .filter(|_| self.current_tab == Tab::DMs);
+
+        self.dms_state.unread_counts.clear();
+        for convo in conversations {
+            let count = if reading == Some(convo.other_user_id) {
+                0
+            } else {
+                convo.unread_count as usize
+            };
+            self.dms_state.unread_counts.insert(convo.other_user_id, count);
+        }
+        self.apply_marked_unread();
+
+        if let Some(previous) = previous_unread {
+            let new_messages = self.unread_dm_count().saturating_sub(previous);
+            if new_messages > 0 {
+                self.notify(format!(
+                    "✉ {} new direct message{}",
+                    new_messages,
+                    if new_messages == 1 { "" } else { "s" }
+                ));
+            }
+        }
+    }
+
+    /// Abandon an in-flight conversations load (the DMs tab was left)
+    pub fn cancel_load_conversations(&mut self) {
+        if self.task_runner.is_running(TaskKind::Conversations) {
+            self.task_runner.cancel(TaskKind::Conversations);
+            self.dms_state.loading = false;
+        }
+    }
+
+    /// Apply a finished conversations load to the DMs state
+    fn apply_conversations_result(&mut self, result: Result<Vec<Conversation>, String>) {
+        match result {
+            Ok(conversations) => {
+                self.update_unread_counts(&conversations);
+                self.dms_state.conversations = conversations;
+                self.apply_marked_unread();
+
+                // Go back to the conversation open last time, or the first
+                if !self.dms_state.conversations.is_empty() {
+                    let last = self.dms_state.last_conversation.and_then(|user_id| {
+                        self.dms_state
+                            .conversations
+                            .iter()
+                            .position(|c| c.other_user_id == user_id)
+                    });
+                    self.dms_state.selected_conversation_index = Some(last.unwrap_or(0));
+                    self.dms_state.needs_message_load = true;
+                }
+
+                self.dms_state.loading = false;
+            }
+            Err(e) => {
+                let error_msg = categorize_error(&e);
+                self.dms_state.error = Some(error_msg);
+                self.dms_state.loading = false;
+            }
+        }
+    }
+
+    /// Load messages for selected conversation
+    pub async fn load_conversation_messages(&mut self) -> Result<()> {
+        if self.dms_state.conversations.is_empty() {
+            return Ok(());
+        }
+
+        // Check if a conversation is selected (not the "New Conversation" button)
+        let selected_index = match self.dms_state.selected_conversation_index {
+            Some(usize::MAX) => return Ok(()), // "New Conversation" button selected, nothing to load
+            Some(index) => index,
+            None => return Ok(()), // No conversation selected, nothing to load
+        };
+
+        let conversation = &self.dms_state.conversations[selected_index];
+        let other_user_id = conversation.other_user_id;
+
+        match self.api_client.get_conversation(other_user_id).await {
+            Ok(messages) => {
+                self.dms_state.messages = messages;
+                self.dms_state.selected_message = None;
+                self.dms_state.pending_attachment = None;
+                if self.dms_state.last_conversation != Some(other_user_id) {
+                    self.dms_state.last_conversation = Some(other_user_id);
+                    self.save_list_positions();
+                }
+
+                // Mark conversation as read when opening it
+                self.mark_conversation_as_read(other_user_id).await?;
+            }
+            Err(e) => {
+                let error_msg = categorize_error(&e.to_string());
+                self.dms_state.error = Some(error_msg);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pin or unpin the selected conversation (Shift+P)
+    pub async fn toggle_selected_conversation_pin(&mut self) -> Result<()> {
+        let Some(convo) = self
+            .dms_state
+            .selected_conversation_index
+            .and_then(|index| self.dms_state.conversations.get(index))
+        else {
+            return Ok(());
+        };
+        let (user_id, username, pinned) =
+            (convo.other_user_id, convo.other_username.clone(), !convo.pinned);
+
+        match self.api_client.set_conversation_pinned(user_id, pinned).await {
+            Ok(()) => {
+                if let Some(convo) = self
+                    .dms_state
+                    .conversations
+                    .iter_mut()
+                    .find(|c| c.other_user_id == user_id)
+                {
+                    convo.pinned = pinned;
+                }
+                self.sort_conversations();
+                if pinned {
+                    self.toasts.success(format!("📌 Pinned @{}", username));
+                } else {
+                    self.toasts.info(format!("Unpinned @{}", username));
+                }
+            }
+            Err(e) => self.toasts.error(format!("Couldn't update pin: {}", e)),
+        }
+        Ok(())
+    }
+
+    /// Check watched threads for new replies in the background
+    pub fn spawn_poll_watched_threads(&mut self) {
+        if self.api_client.session_token().is_none() {
+            return;
+        }
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::WatchedThreads, async move {
+            TaskResult::WatchedThreadsPolled(
+                client.get_watched_threads().await.map_err(|e| e.to_string()),
+            )
+        });
+    }
+
+    /// Replace the watched threads with the server's, notifying about
+    /// replies that arrived since the last poll
+    fn update_watched_threads(&mut self, threads: &[fido_types::WatchedThread]) {
+        for thread in threads {
+            let known = self.watched_threads.get(&thread.post_id).copied().unwrap_or(0);
+            let new_replies = thread.new_replies - known;
+            if new_replies > 0 {
+                let first_line = thread.content.lines().next().unwrap_or_default();
+                let mut excerpt = crate::text_wrapper::take_width(first_line, 30).to_string();
+                if excerpt.len() < first_line.len() {
+                    excerpt.push('…');
+                }
+                self.notify(format!(
+                    "💬 {} new repl{} in @{}'s thread \"{}\"",
+                    new_replies,
+                    if new_replies == 1 { "y" } else { "ies" },
+                    thread.author_username,
+                    excerpt
+                ));
+            }
+        }
+        self.watched_threads = threads
+            .iter()
+            .map(|thread| (thread.post_id, thread.new_replies))
+            .collect();
+    }
+
+    /// Watch or stop watching the thread open in the detail view ('w')
+    pub async fn toggle_thread_watch(&mut self) -> Result<()> {
+        let Some(post_id) = self
+            .post_detail_state
+            .as_ref()
+            .and_then(|state| state.full_post_modal_id)
+        else {
+            return Ok(());
+        };
+        let watch = !self.watched_threads.contains_key(&post_id);
+
+        match self.api_client.set_thread_watched(post_id, watch).await {
+            Ok(()) => {
+                if watch {
+                    self.watched_threads.insert(post_id, 0);
+                    self.toasts.success("👁 Watching this thread for new replies");
+                } else {
+                    self.watched_threads.remove(&post_id);
+                    self.toasts.info("Stopped watching this thread");
+                }
+            }
+            Err(e) => self.toasts.error(format!("Couldn't update watch: {}", e)),
+        }
+        Ok(())
+    }
+
+    /// Close the open review request in the detail view, or reopen it ('m');
+    /// only its author can
+    pub async fn toggle_review_open(&mut self) -> Result<()> {
+        let me = self.auth_state.current_user.as_ref().map(|user| user.id);
+        let Some((post_id, is_open)) = self
+            .post_detail_state
+            .as_ref()
+            .and_then(|state| state.post.as_ref())
+            .and_then(|post| match &post.kind {
+                Some(fido_types::PostKind::ReviewRequest(review)) if me == Some(post.author_id) => {
+                    Some((post.id, review.is_open))
+                }
+                _ => None,
+            })
+        else {
+            return Ok(());
+        };
+
+        match self.api_client.set_review_open(post_id, !is_open).await {
+            Ok(()) => {
+                let detail_post = self.post_detail_state.as_mut().and_then(|state| state.post.as_mut());
+                let feed_posts = self.posts_state.posts.iter_mut().filter(|p| p.id == post_id);
+                for post in detail_post.into_iter().chain(feed_posts) {
+                    if let Some(fido_types::PostKind::ReviewRequest(review)) = &mut post.kind {
+                        review.is_open = !is_open;
+                    }
+                }
+                if is_open {
+                    self.toasts.success("✓ Review request closed");
+                } else {
+                    self.toasts.info("Review request reopened");
+                }
+            }
+            Err(e) => self.toasts.error(format!("Couldn't update review request: {}", e)),
         }
-
         Ok(())
     }
 
-    /// Load DM conversations
-    pub async fn load_conversations(&mut self) -> Result<()> {
-        self.dms_state.loading = true;
-        self.dms_state.error = None;
-
-        match self.api_client.get_conversations().await {
-            Ok(convos) => {
-                // Parse conversations from JSON
-                self.dms_state.conversations = convos
-                    .iter()
-                    .filter_map(|c| {
-                        Some(Conversation {
-                            other_user_id: c.get("other_user_id")?.as_str()?.parse().ok()?,
-                            other_username: c.get("other_username")?.as_str()?.to_string(),
-                            last_message: c.get("last_message")?.as_str()?.to_string(),
-                            last_message_time: c
-                                .get("last_message_time")?
-                                .as_str()?
-                                .parse()
-                                .ok()?,
-                            unread_count: c.get("unread_count")?.as_i64()? as i32,
-                        })
-                    })
-                    .collect();
+    /// Accept the reply selected in the thread as its answer, or clear it
+    /// if it already is ('a'); only the thread's author can, and not on
+    /// their own replies
+    pub async fn toggle_accepted_reply(&mut self) -> Result<()> {
+        let me = self.auth_state.current_user.as_ref().map(|user| user.id);
+        let root_author = self
+            .post_detail_state
+            .as_ref()
+            .and_then(|state| state.post.as_ref())
+            .map(|post| post.author_id);
+        if me.is_none() || me != root_author {
+            return Ok(());
+        }
+        let Some(reply) = self.get_selected_post_in_modal() else {
+            return Ok(());
+        };
+        if reply.parent_post_id.is_none() || reply.is_deleted || Some(reply.author_id) == me {
+            return Ok(());
+        }
 
-                // Update unread_counts HashMap from conversations
-                self.dms_state.unread_counts.clear();
-                for convo in &self.dms_state.conversations {
-                    self.dms_state
-                        .unread_counts
-                        .insert(convo.other_user_id, convo.unread_count as usize);
+        let accept = !reply.is_accepted;
+        match self.api_client.set_accepted_reply(reply.id, accept).await {
+            Ok(()) => {
+                if let Some(state) = self.post_detail_state.as_mut() {
+                    // A thread has one accepted answer at most
+                    for post in &mut state.replies {
+                        post.is_accepted = accept && post.id == reply.id;
+                    }
                 }
-
-                // Select first conversation if available
-                if !self.dms_state.conversations.is_empty() {
-                    self.dms_state.selected_conversation_index = Some(0);
-                    self.dms_state.needs_message_load = true;
+                if accept {
+                    self.toasts.success("✓ Marked as the accepted answer");
+                } else {
+                    self.toasts.info("Accepted answer cleared");
                 }
-
-                self.dms_state.loading = false;
-            }
-            Err(e) => {
-                let error_msg = categorize_error(&e.to_string());
-                self.dms_state.error = Some(error_msg);
-                self.dms_state.loading = false;
             }
+            Err(e) => self.toasts.error(format!("Couldn't update accepted answer: {}", e)),
         }
-
         Ok(())
     }
 
-    /// Load messages for selected conversation
-    pub async fn load_conversation_messages(&mut self) -> Result<()> {
-        if self.dms_state.conversations.is_empty() {
-            return Ok(());
+    /// Check for cross-post requests awaiting our consent, and for decisions
+    /// on our own, in the background
+    pub fn spawn_poll_crossposts(&mut self) {
+        if self.api_client.session_token().is_none() {
+            return;
         }
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Crossposts, async move {
+            TaskResult::CrosspostsPolled(client.get_crossposts().await.map_err(|e| e.to_string()))
+        });
+    }
 
-        // Check if a conversation is selected (not the "New Conversation" button)
-        let selected_index = match self.dms_state.selected_conversation_index {
-            Some(usize::MAX) => return Ok(()), // "New Conversation" button selected, nothing to load
-            Some(index) => index,
-            None => return Ok(()), // No conversation selected, nothing to load
+    /// Notify about our requests that were decided since the last poll, and
+    /// ask about the oldest request waiting on us if nothing is being asked
+    fn update_crossposts(&mut self, crossposts: Vec<fido_types::Crosspost>) {
+        let me = self
+            .auth_state
+            .current_user
+            .as_ref()
+            .map(|user| user.username.clone())
+            .unwrap_or_default();
+
+        let mut outgoing = std::collections::HashMap::new();
+        for crosspost in crossposts.iter().filter(|c| c.requester_username == me) {
+            let was_pending = self.crossposts.outgoing.get(&crosspost.id)
+                == Some(&fido_types::CrosspostStatus::Pending);
+            if was_pending {
+                match crosspost.status {
+                    fido_types::CrosspostStatus::Approved => self.notify(format!(
+                        "📣 @{} approved your cross-post; it's in the feed",
+                        crosspost.approver_username
+                    )),
+                    fido_types::CrosspostStatus::Declined => self.notify(format!(
+                        "@{} declined your cross-post",
+                        crosspost.approver_username
+                    )),
+                    fido_types::CrosspostStatus::Pending => {}
+                }
+            }
+            outgoing.insert(crosspost.id, crosspost.status);
+        }
+        self.crossposts.outgoing = outgoing;
+
+        if self.crossposts.prompt.is_none() {
+            let dismissed = &self.crossposts.dismissed;
+            let next = crossposts.into_iter().rev().find(|c| {
+                c.approver_username == me
+                    && c.status == fido_types::CrosspostStatus::Pending
+                    && !dismissed.contains(&c.id)
+            });
+            self.crossposts.prompt = next;
+        }
+    }
+
+    /// Whether a cross-post request is asking for our consent
+    pub fn crosspost_prompt_open(&self) -> bool {
+        self.crossposts.prompt.is_some()
+            && self.current_screen == Screen::Main
+            && !self.composer_state.is_open()
+    }
+
+    /// Publish the cross-post being asked about ('y')
+    pub async fn approve_crosspost(&mut self) -> Result<()> {
+        let Some(request) = self.crossposts.prompt.take() else {
+            return Ok(());
         };
+        match self.api_client.approve_crosspost(request.id).await {
+            Ok(_) => {
+                self.toasts.success(format!(
+                    "📣 Published @{}'s cross-post",
+                    request.requester_username
+                ));
+                self.spawn_load_posts();
+            }
+            Err(e) => self.toasts.error(format!("Couldn't approve cross-post: {}", e)),
+        }
+        Ok(())
+    }
 
-        let conversation = &self.dms_state.conversations[selected_index];
-        let other_user_id = conversation.other_user_id;
+    /// Refuse the cross-post being asked about ('n')
+    pub async fn decline_crosspost(&mut self) -> Result<()> {
+        let Some(request) = self.crossposts.prompt.take() else {
+            return Ok(());
+        };
+        match self.api_client.decline_crosspost(request.id).await {
+            Ok(()) => self.toasts.info(format!(
+                "Declined @{}'s cross-post",
+                request.requester_username
+            )),
+            Err(e) => self.toasts.error(format!("Couldn't decline cross-post: {}", e)),
+        }
+        Ok(())
+    }
 
-        match self.api_client.get_conversation(other_user_id).await {
-            Ok(messages) => {
-                self.dms_state.messages = messages;
+    /// Put off the cross-post being asked about until next session (Esc)
+    pub fn dismiss_crosspost_prompt(&mut self) {
+        if let Some(request) = self.crossposts.prompt.take() {
+            self.crossposts.dismissed.insert(request.id);
+        }
+    }
 
-                // Mark conversation as read when opening it
-                self.mark_conversation_as_read(other_user_id).await?;
+    /// Attach the file at `path` to the next message, or drop the attached
+    /// file if `path` is empty
+    fn attach_dm_file(&mut self, path: &str) {
+        if path.is_empty() {
+            if self.dms_state.pending_attachment.take().is_some() {
+                self.toasts.info("Attachment removed");
             }
-            Err(e) => {
-                let error_msg = categorize_error(&e.to_string());
-                self.dms_state.error = Some(error_msg);
+            self.clear_dm_message();
+            return;
+        }
+        match crate::attachments::load(path) {
+            Ok(attachment) => {
+                self.toasts.success(format!(
+                    "📎 Attached {}; Enter sends it",
+                    attachment.filename
+                ));
+                self.dms_state.pending_attachment = Some(attachment);
+                self.dms_state.error = None;
+                self.clear_dm_message();
             }
+            Err(e) => self.dms_state.error = Some(format!("Attachment Error: {}", e)),
         }
+    }
 
-        Ok(())
+    /// Save the selected conversation to the Downloads folder, as Markdown
+    /// unless `format` asks for JSON
+    async fn export_dm_conversation(&mut self, format: &str) {
+        let format = if format.is_empty() {
+            fido_types::ExportFormat::Markdown
+        } else if let Some(format) = fido_types::ExportFormat::parse(format) {
+            format
+        } else {
+            self.dms_state.error = Some(format!(
+                "Export Error: unknown format '{}'; use markdown or json",
+                format
+            ));
+            return;
+        };
+        let user_id = match self.selected_dm_user() {
+            Some(user_id) if self.dms_state.pending_conversation_username.is_none() => user_id,
+            _ => {
+                self.dms_state.error = Some("Export Error: nothing to export yet".to_string());
+                return;
+            }
+        };
+        let Some(dir) = crate::attachments::download_dir() else {
+            self.dms_state.error =
+                Some("Export Error: couldn't find a folder to save to".to_string());
+            return;
+        };
+
+        match self.api_client.export_conversation(user_id, format).await {
+            Ok(export) => match crate::attachments::save(&export.filename, &export.content, &dir) {
+                Ok(path) => {
+                    self.dms_state.error = None;
+                    self.clear_dm_message();
+                    self.input_mode = InputMode::Navigation;
+                    self.toasts
+                        .success(format!("Exported conversation to {}", path.display()));
+                }
+                Err(e) => self.dms_state.error = Some(format!("Export Error: {}", e)),
+            },
+            Err(e) => self.dms_state.error = Some(categorize_error(&e.to_string())),
+        }
+    }
+
+    /// The message Ctrl+E and Ctrl+S act on: the picked one, or else the
+    /// newest with an attachment
+    fn dm_attachment_target(&self) -> Option<usize> {
+        match self.dms_state.selected_message {
+            Some(index) => self
+                .dms_state
+                .messages
+                .get(index)
+                .filter(|message| message.attachment.is_some())
+                .map(|_| index),
+            None => self
+                .dms_state
+                .messages
+                .iter()
+                .rposition(|message| message.attachment.is_some()),
+        }
+    }
+
+    /// Expand or collapse an attachment in the conversation (Ctrl+E)
+    pub fn toggle_dm_attachment(&mut self) {
+        let Some(index) = self.dm_attachment_target() else {
+            self.toasts.info("No attachment to show");
+            return;
+        };
+        let id = self.dms_state.messages[index].id;
+        if !self.dms_state.expanded_attachments.remove(&id) {
+            self.dms_state.expanded_attachments.insert(id);
+        }
+    }
+
+    /// Save an attachment from the conversation to the Downloads folder (Ctrl+S)
+    pub fn save_dm_attachment(&mut self) {
+        let Some(attachment) = self
+            .dm_attachment_target()
+            .and_then(|index| self.dms_state.messages[index].attachment.clone())
+        else {
+            self.toasts.info("No attachment to save");
+            return;
+        };
+        let Some(dir) = crate::attachments::download_dir() else {
+            self.toasts.error("Couldn't find a folder to save to");
+            return;
+        };
+        match crate::attachments::save(&attachment.filename, &attachment.content, &dir) {
+            Ok(path) => self.toasts.success(format!("Saved {}", path.display())),
+            Err(e) => self.toasts.error(format!("Couldn't save attachment: {}", e)),
+        }
+    }
+
+    /// Step the message picked for cross-posting back or forward ([ / ]);
+    /// moving past the newest goes back to following the latest message
+    pub fn select_dm_message(&mut self, older: bool) {
+        let count = self.dms_state.messages.len();
+        if count == 0 {
+            return;
+        }
+        self.dms_state.selected_message = match (self.dms_state.selected_message, older) {
+            (None, true) => Some(count - 1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index + 1 < count => Some(index + 1),
+            (Some(_), false) => None,
+        };
+    }
+
+    /// Pinned conversations first, then most recent activity first, keeping
+    /// the same conversation selected
+    fn sort_conversations(&mut self) {
+        let selected_user = self.selected_dm_user();
+        self.dms_state.conversations.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then(b.last_message_time.cmp(&a.last_message_time))
+        });
+        if let Some(user_id) = selected_user {
+            self.dms_state.selected_conversation_index = self
+                .dms_state
+                .conversations
+                .iter()
+                .position(|c| c.other_user_id == user_id);
+        }
     }
 
     /// Mark conversation as read
@@ -2098,6 +5519,11 @@ impl App {
         // Set current conversation user
         self.dms_state.current_conversation_user = Some(user_id);
 
+        // Opening a conversation undoes marking it unread
+        if self.dms_state.follow_ups.marked_unread.remove(&user_id) {
+            self.save_dm_follow_ups();
+        }
+
         // Call API to mark messages as read
         match self.api_client.mark_messages_read(user_id).await {
             Ok(_) => {
@@ -2125,42 +5551,326 @@ impl App {
             }
         }
 
-        Ok(())
-    }
-
-    /// Handle keyboard input for DM message textarea
-    pub fn handle_dm_input(&mut self, key: KeyEvent) {
-        // DMs don't have a strict character limit, but we can add one if needed
-        // For now, just pass through to TextArea which handles wrapping
-        use tui_textarea::Input;
-        let input = Input::from(crossterm::event::Event::Key(key));
-        self.dms_state.message_textarea.input(input);
-
-        // After input, check if we need to wrap the current line
-        self.wrap_dm_text_if_needed();
-    }
-
-    /// Wrap text in DM input if current line exceeds reasonable width
-    fn wrap_dm_text_if_needed(&mut self) {
-        crate::text_wrapper::wrap_textarea_if_needed(
-            &mut self.dms_state.message_textarea,
-            crate::text_wrapper::WrapConfig::DM_PANEL,
+        Ok(())
+    }
+
+    /// Handle keyboard input for DM message textarea
+    pub fn handle_dm_input(&mut self, key: KeyEvent) {
+        // DMs don't have a strict character limit, but we can add one if needed
+        // For now, just pass through to TextArea which handles wrapping
+        use tui_textarea::Input;
+        let input = Input::from(crossterm::event::Event::Key(key));
+        self.dms_state.message_textarea.input(input);
+
+        // After input, check if we need to wrap the current line
+        self.wrap_dm_text_if_needed();
+    }
+
+    /// Wrap text in DM input if current line exceeds reasonable width
+    fn wrap_dm_text_if_needed(&mut self) {
+        crate::text_wrapper::wrap_textarea_if_needed(
+            &mut self.dms_state.message_textarea,
+            crate::text_wrapper::WrapConfig::DM_PANEL,
+        );
+    }
+
+    /// Insert a bracketed paste or an input method commit into whichever
+    /// input is taking text, as one edit rather than a key press per
+    /// character. False when no input is open to take it.
+    pub fn insert_text(&mut self, text: &str) -> bool {
+        if self.show_help || self.log_viewer.show || self.diagnostics.show || self.reauth.show {
+            return false;
+        }
+
+        if self.composer_state.is_open() {
+            let room = self
+                .composer_input_limit()
+                .saturating_sub(self.composer_state.char_count());
+            let text: String = if text.chars().count() > room {
+                self.toasts.info("Text cut short at the character limit");
+                text.chars().take(room).collect()
+            } else {
+                text.to_string()
+            };
+            crate::text_wrapper::paste_into_textarea(
+                &mut self.composer_state.textarea,
+                &text,
+                None,
+            );
+            return true;
+        }
+
+        if self.profile_state.show_edit_bio_modal {
+            self.profile_state.edit_bio.insert_str(text);
+        } else if self.dms_state.show_new_conversation_modal
+            && self.dms_state.new_conversation_search_mode
+        {
+            if self.dms_state.new_conversation_username.insert_str(text) {
+                self.dms_state.new_conversation_selected_index = 0;
+            }
+        } else if self.hashtags_state.show_hashtags_modal
+            && self.hashtags_state.show_add_hashtag_input
+        {
+            self.hashtags_state.add_hashtag_name.insert_str(text);
+        } else if self.posts_state.show_filter_modal
+            && self.posts_state.filter_modal_state.show_add_hashtag_input
+        {
+            self.posts_state.filter_modal_state.add_hashtag_input.insert_str(text);
+        } else if let Some(input) = &mut self.settings_state.pinned_hashtags_input {
+            input.insert_str(text);
+        } else if self.current_screen == Screen::Main
+            && self.current_tab == Tab::DMs
+            && !self.viewing_post_detail
+            && !self.friends_state.show_friends_modal
+            && !self.hashtags_state.show_hashtags_modal
+            && !self.posts_state.show_filter_modal
+            && !self.dms_state.show_new_conversation_modal
+            && !self.dms_state.show_dm_error_modal
+            && self.dms_state.reminder_input.is_none()
+        {
+            self.input_mode = InputMode::Typing;
+            crate::text_wrapper::paste_into_textarea(
+                &mut self.dms_state.message_textarea,
+                text,
+                Some(crate::text_wrapper::WrapConfig::DM_PANEL),
+            );
+            if self.dms_state.messages.is_empty() {
+                self.dms_state.needs_message_load = true;
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Get DM message content from textarea
+    pub fn get_dm_message_content(&self) -> String {
+        self.dms_state.message_textarea.lines().join("\n")
+    }
+
+    /// Clear DM message textarea
+    pub fn clear_dm_message(&mut self) {
+        let mut textarea = TextArea::default();
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_style(Style::default());
+        // Enable hard tab indent for better wrapping behavior
+        textarea.set_hard_tab_indent(true);
+        self.dms_state.message_textarea = textarea;
+    }
+
+    /// The other user in the selected conversation, if one is selected
+    fn selected_dm_user(&self) -> Option<uuid::Uuid> {
+        self.dms_state
+            .selected_conversation_index
+            .and_then(|index| self.dms_state.conversations.get(index))
+            .map(|convo| convo.other_user_id)
+    }
+
+    /// Put the DM being typed aside as the selected conversation's draft and
+    /// clear the input. Clearing the input this way drops any older draft.
+    pub fn stash_dm_draft(&mut self) {
+        if let Some(user_id) = self.selected_dm_user() {
+            let content = self.get_dm_message_content();
+            if content.trim().is_empty() {
+                self.dms_state.drafts.remove(&user_id);
+            } else {
+                self.dms_state.drafts.insert(user_id, content);
+            }
+            self.save_dm_drafts();
+        }
+        self.clear_dm_message();
+    }
+
+    /// Bring back the selected conversation's draft, unless something is
+    /// already being typed
+    pub fn restore_dm_draft(&mut self) {
+        if !self.is_dm_message_empty() {
+            return;
+        }
+        let draft = self
+            .selected_dm_user()
+            .and_then(|user_id| self.dms_state.drafts.get(&user_id))
+            .cloned();
+        if let Some(draft) = draft {
+            self.dms_state.message_textarea.insert_str(draft);
+        }
+    }
+
+    /// Write DM drafts to disk
+    fn save_dm_drafts(&self) {
+        if let Some(user) = &self.auth_state.current_user {
+            if let Err(e) = self
+                .config_manager
+                .save_dm_drafts(&user.id.to_string(), &self.dms_state.drafts)
+            {
+                log::warn!("Failed to save DM drafts: {}", e);
+            }
+        }
+    }
+
+    /// Load DM drafts saved by an earlier session
+    fn load_dm_drafts(&mut self) {
+        if let Some(user) = &self.auth_state.current_user {
+            match self.config_manager.load_dm_drafts(&user.id.to_string()) {
+                Ok(drafts) => self.dms_state.drafts = drafts,
+                Err(e) => log::warn!("Ignoring unreadable DM drafts: {}", e),
+            }
+        }
+    }
+
+    /// Write DM follow-ups to disk
+    fn save_dm_follow_ups(&self) {
+        if let Some(user) = &self.auth_state.current_user {
+            if let Err(e) = self
+                .config_manager
+                .save_dm_follow_ups(&user.id.to_string(), &self.dms_state.follow_ups)
+            {
+                log::warn!("Failed to save DM follow-ups: {}", e);
+            }
+        }
+    }
+
+    /// Load conversations marked unread and reminders from an earlier session
+    fn load_dm_follow_ups(&mut self) {
+        if let Some(user) = &self.auth_state.current_user {
+            match self.config_manager.load_dm_follow_ups(&user.id.to_string()) {
+                Ok(follow_ups) => self.dms_state.follow_ups = follow_ups,
+                Err(e) => log::warn!("Ignoring unreadable DM follow-ups: {}", e),
+            }
+        }
+    }
+
+    /// Count each conversation marked unread as at least one unread message,
+    /// on top of the server's counts
+    fn apply_marked_unread(&mut self) {
+        let marked = &self.dms_state.follow_ups.marked_unread;
+        for user_id in marked {
+            let count = self.dms_state.unread_counts.entry(*user_id).or_insert(0);
+            *count = (*count).max(1);
+        }
+        for convo in &mut self.dms_state.conversations {
+            if marked.contains(&convo.other_user_id) {
+                convo.unread_count = convo.unread_count.max(1);
+            }
+        }
+    }
+
+    /// Mark the selected conversation unread (U), so it keeps its badge
+    /// until it's opened again
+    pub fn mark_selected_conversation_unread(&mut self) {
+        let Some(convo) = self
+            .dms_state
+            .selected_conversation_index
+            .and_then(|index| self.dms_state.conversations.get(index))
+        else {
+            return;
+        };
+        let (user_id, username) = (convo.other_user_id, convo.other_username.clone());
+        self.dms_state.follow_ups.marked_unread.insert(user_id);
+        self.apply_marked_unread();
+        self.save_dm_follow_ups();
+        self.toasts.info(format!("Marked @{} unread", username));
+    }
+
+    /// Ask for a delay to be reminded of the selected conversation after (R)
+    pub fn start_dm_reminder_input(&mut self) {
+        if self.selected_dm_user().is_some() {
+            self.dms_state.reminder_input = Some(String::new());
+            self.input_mode = InputMode::Typing;
+        }
+    }
+
+    /// Typing the reminder delay; Enter sets it, Esc cancels
+    pub fn handle_dm_reminder_input(&mut self, key: KeyEvent) {
+        let Some(input) = &mut self.dms_state.reminder_input else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => {
+                self.dms_state.reminder_input = None;
+                self.input_mode = InputMode::Navigation;
+            }
+            KeyCode::Enter => self.set_dm_reminder(chrono::Utc::now()),
+            _ => {}
+        }
+    }
+
+    /// Remind about the selected conversation after the delay typed, like
+    /// `3h`, `30m` or `1d`. Leaving the delay empty clears its reminder.
+    fn set_dm_reminder(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        let Some(input) = self.dms_state.reminder_input.clone() else {
+            return;
+        };
+        let Some(convo) = self
+            .dms_state
+            .selected_conversation_index
+            .and_then(|index| self.dms_state.conversations.get(index))
+        else {
+            self.dms_state.reminder_input = None;
+            self.input_mode = InputMode::Navigation;
+            return;
+        };
+        let (user_id, username) = (convo.other_user_id, convo.other_username.clone());
+
+        if input.trim().is_empty() {
+            self.dms_state.reminder_input = None;
+            self.input_mode = InputMode::Navigation;
+            if self.dms_state.follow_ups.reminders.remove(&user_id).is_some() {
+                self.save_dm_follow_ups();
+                self.toasts.info(format!("Reminder for @{} cleared", username));
+            }
+            return;
+        }
+        let Some(minutes) = crate::status::parse_duration(input.trim()) else {
+            self.toasts.error("Remind me in how long? Try 30m, 3h or 1d");
+            return;
+        };
+
+        let due = now + chrono::Duration::minutes(minutes as i64);
+        self.dms_state.reminder_input = None;
+        self.input_mode = InputMode::Navigation;
+        self.dms_state.follow_ups.reminders.insert(
+            user_id,
+            crate::config::DmReminder {
+                username: username.clone(),
+                due,
+            },
         );
-    }
-
-    /// Get DM message content from textarea
-    pub fn get_dm_message_content(&self) -> String {
-        self.dms_state.message_textarea.lines().join("\n")
-    }
+        self.save_dm_follow_ups();
+        self.toasts.success(format!(
+            "Reminder set for @{} at {}",
+            username,
+            crate::status::format_expiry(due)
+        ));
+    }
+
+    /// Raise reminders that have come due: their conversations are marked
+    /// unread and a toast names each one. Called from the event loop.
+    pub fn fire_due_dm_reminders(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        let due: Vec<uuid::Uuid> = self
+            .dms_state
+            .follow_ups
+            .reminders
+            .iter()
+            .filter(|(_, reminder)| reminder.due <= now)
+            .map(|(user_id, _)| *user_id)
+            .collect();
+        if due.is_empty() {
+            return;
+        }
 
-    /// Clear DM message textarea
-    pub fn clear_dm_message(&mut self) {
-        let mut textarea = TextArea::default();
-        textarea.set_cursor_line_style(Style::default());
-        textarea.set_style(Style::default());
-        // Enable hard tab indent for better wrapping behavior
-        textarea.set_hard_tab_indent(true);
-        self.dms_state.message_textarea = textarea;
+        for user_id in due {
+            if let Some(reminder) = self.dms_state.follow_ups.reminders.remove(&user_id) {
+                self.dms_state.follow_ups.marked_unread.insert(user_id);
+                self.notify(format!("⏰ Reminder: follow up with @{}", reminder.username));
+            }
+        }
+        self.apply_marked_unread();
+        self.save_dm_follow_ups();
     }
 
     /// Check if DM message is empty
@@ -2173,8 +5883,28 @@ impl App {
         let content = self.get_dm_message_content();
         let trimmed = content.trim();
 
-        // Validate empty input
-        if trimmed.is_empty() {
+        // `/attach <path>` picks a file for the next message instead of sending
+        if let Some(path) = trimmed
+            .strip_prefix("/attach")
+            .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+        {
+            let path = path.trim().to_string();
+            self.attach_dm_file(&path);
+            return Ok(());
+        }
+
+        // `/export [json]` saves the conversation to a file instead
+        if let Some(format) = trimmed
+            .strip_prefix("/export")
+            .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+        {
+            let format = format.trim().to_string();
+            self.export_dm_conversation(&format).await;
+            return Ok(());
+        }
+
+        // Validate empty input (a file can go on its own)
+        if trimmed.is_empty() && self.dms_state.pending_attachment.is_none() {
             self.dms_state.error = Some(
                 "Validation Error: Cannot send empty message. Type something first!".to_string(),
             );
@@ -2217,35 +5947,42 @@ impl App {
         // Parse emoji shortcodes before sending
         let parsed_content = crate::emoji::parse_emoji_shortcodes(&content);
 
+        // Existing conversations go through the outbox, which shows the
+        // message straight away and retries it if the connection drops. A new
+        // conversation is sent directly so a mistyped username can be caught.
+        if self.dms_state.pending_conversation_username.is_none() {
+            if let Some(to_user_id) = self.selected_dm_user() {
+                self.queue_dm(to_user_id, to_username, parsed_content);
+            }
+            return Ok(());
+        }
+
         match self
             .api_client
-            .send_message(to_username.clone(), parsed_content)
+            .send_message(
+                to_username.clone(),
+                parsed_content,
+                self.dms_state.pending_attachment.clone(),
+            )
             .await
         {
             Ok(_) => {
                 self.clear_dm_message();
+                self.dms_state.pending_attachment = None;
+                if let Some(user_id) = self.selected_dm_user() {
+                    if self.dms_state.drafts.remove(&user_id).is_some() {
+                        self.save_dm_drafts();
+                    }
+                }
                 self.input_mode = InputMode::Navigation;
 
-                // If this was a pending conversation, clear it and reload conversations
-                if self.dms_state.pending_conversation_username.is_some() {
-                    self.dms_state.pending_conversation_username = None;
-                    self.load_conversations().await?;
-                    // Select the new conversation (will be first)
-                    if !self.dms_state.conversations.is_empty() {
-                        self.dms_state.selected_conversation_index = Some(0);
-                        self.dms_state.needs_message_load = true;
-                    }
-                } else {
-                    // Regular message in existing conversation
-                    self.load_conversation_messages().await?;
-
-                    // Keep unread count at 0 for current conversation
-                    if let Some(index) = self.dms_state.selected_conversation_index {
-                        if index < self.dms_state.conversations.len() {
-                            let other_user_id = self.dms_state.conversations[index].other_user_id;
-                            self.dms_state.unread_counts.insert(other_user_id, 0);
-                        }
-                    }
+                // The conversation exists now; reload and select it
+                self.dms_state.pending_conversation_username = None;
+                self.load_conversations().await?;
+                // Select the new conversation (will be first)
+                if !self.dms_state.conversations.is_empty() {
+                    self.dms_state.selected_conversation_index = Some(0);
+                    self.dms_state.needs_message_load = true;
                 }
             }
             Err(e) => {
@@ -2274,6 +6011,164 @@ impl App {
         Ok(())
     }
 
+    /// Queue a message to an existing conversation and clear the input
+    fn queue_dm(&mut self, to_user_id: Uuid, to_username: String, content: String) {
+        self.dms_state.outbox.push(OutgoingMessage {
+            local_id: Uuid::new_v4(),
+            to_user_id,
+            to_username,
+            content,
+            attachment: self.dms_state.pending_attachment.take(),
+            queued_at: chrono::Utc::now(),
+            state: DeliveryState::Pending,
+            attempts: 0,
+            retry_at: None,
+            error: None,
+        });
+        self.clear_dm_message();
+        if self.dms_state.drafts.remove(&to_user_id).is_some() {
+            self.save_dm_drafts();
+        }
+        self.input_mode = InputMode::Navigation;
+        // Bring the end of the conversation, where the message shows, into view
+        self.dms_state.selected_message = None;
+        self.pump_dm_outbox();
+    }
+
+    /// Send the oldest pending message unless one is already on its way.
+    /// Messages queued behind a failed one to the same person wait for it,
+    /// so a conversation never arrives out of order.
+    fn pump_dm_outbox(&mut self) {
+        if self.task_runner.is_running(TaskKind::DmDelivery) {
+            return;
+        }
+        let mut blocked = std::collections::HashSet::new();
+        let mut next = None;
+        for message in &self.dms_state.outbox {
+            match message.state {
+                DeliveryState::Failed => {
+                    blocked.insert(message.to_user_id);
+                }
+                DeliveryState::Pending if !blocked.contains(&message.to_user_id) => {
+                    next = Some(message.clone());
+                    break;
+                }
+                DeliveryState::Pending => {}
+            }
+        }
+        let Some(message) = next else {
+            return;
+        };
+
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::DmDelivery, async move {
+            let result = client
+                .send_message(message.to_username, message.content, message.attachment)
+                .await;
+            let transient = result.as_ref().is_err_and(|e| e.is_transient());
+            TaskResult::DmDelivered {
+                local_id: message.local_id,
+                result: result.map_err(|e| e.to_string()),
+                transient,
+            }
+        });
+    }
+
+    /// Settle a send: a delivered message joins its conversation, a failed one
+    /// waits to be retried. Either way the next queued message goes out.
+    fn apply_dm_delivery(
+        &mut self,
+        local_id: Uuid,
+        result: Result<fido_types::DirectMessage, String>,
+        transient: bool,
+    ) {
+        let Some(index) = self
+            .dms_state
+            .outbox
+            .iter()
+            .position(|m| m.local_id == local_id)
+        else {
+            // Dropped on logout
+            return;
+        };
+
+        match result {
+            Ok(message) => {
+                let queued = self.dms_state.outbox.remove(index);
+                if self.selected_dm_user() == Some(queued.to_user_id) {
+                    self.dms_state.messages.push(message.clone());
+                }
+                if let Some(convo) = self
+                    .dms_state
+                    .conversations
+                    .iter_mut()
+                    .find(|c| c.other_user_id == queued.to_user_id)
+                {
+                    convo.last_message = message.content.clone();
+                    convo.last_message_time = message.created_at;
+                }
+                self.sort_conversations();
+            }
+            Err(error) => {
+                log::warn!("Failed to send DM: {}", error);
+                let queued = &mut self.dms_state.outbox[index];
+                queued.attempts += 1;
+                queued.state = DeliveryState::Failed;
+                queued.retry_at = (transient && queued.attempts <= DM_AUTO_RETRIES)
+                    .then(|| Instant::now() + DM_RETRY_DELAY * 2u32.pow(queued.attempts - 1));
+                queued.error = Some(categorize_error(&error));
+            }
+        }
+        self.pump_dm_outbox();
+    }
+
+    /// Requeue failed DMs whose automatic retry is due; called every tick.
+    /// Nothing is retried while the client is offline.
+    pub fn retry_due_dm_deliveries(&mut self) {
+        if self.dms_state.outbox.is_empty() || self.api_client.is_offline() {
+            return;
+        }
+        let now = Instant::now();
+        for message in &mut self.dms_state.outbox {
+            if message.state == DeliveryState::Failed
+                && message.retry_at.is_some_and(|at| at <= now)
+            {
+                message.state = DeliveryState::Pending;
+                message.retry_at = None;
+            }
+        }
+        self.pump_dm_outbox();
+    }
+
+    /// Whether the open conversation has a message that failed to send
+    pub fn has_failed_dm(&self) -> bool {
+        let user_id = self.selected_dm_user();
+        self.dms_state
+            .outbox
+            .iter()
+            .any(|m| Some(m.to_user_id) == user_id && m.state == DeliveryState::Failed)
+    }
+
+    /// Try the open conversation's failed messages again now ('r'), with a
+    /// fresh set of automatic retries
+    pub fn retry_failed_dms(&mut self) {
+        let Some(user_id) = self.selected_dm_user() else {
+            return;
+        };
+        for message in self
+            .dms_state
+            .outbox
+            .iter_mut()
+            .filter(|m| m.to_user_id == user_id && m.state == DeliveryState::Failed)
+        {
+            message.state = DeliveryState::Pending;
+            message.attempts = 0;
+            message.retry_at = None;
+            message.error = None;
+        }
+        self.pump_dm_outbox();
+    }
+
     /// Load mutual friends for DM availability
     pub async fn load_mutual_friends_for_dms(&mut self) -> Result<()> {
         match self.api_client.get_mutual_friends_list().await {
@@ -2300,16 +6195,16 @@ impl App {
     /// Close new conversation modal
     pub fn close_new_conversation_modal(&mut self) {
         self.dms_state.show_new_conversation_modal = false;
+        self.dms_state.share_prefill = None;
         self.dms_state.new_conversation_username.clear();
         self.dms_state.new_conversation_selected_index = 0;
         self.dms_state.new_conversation_search_mode = false;
-        self.dms_state.new_conversation_search_query.clear();
         self.input_mode = InputMode::Navigation;
     }
 
     /// Get filtered mutual friends list for new conversation modal
     pub fn get_filtered_mutual_friends(&self) -> Vec<&UserInfo> {
-        let query = self.dms_state.new_conversation_search_query.to_lowercase();
+        let query = self.dms_state.new_conversation_username.value().to_lowercase();
         
         if query.is_empty() {
             self.dms_state.available_mutual_friends.iter().collect()
@@ -2336,18 +6231,6 @@ impl App {
             .any(|u| u.username == username)
     }
 
-    /// Add character to new conversation username
-    pub fn add_char_to_new_conversation(&mut self, c: char) {
-        if self.dms_state.new_conversation_username.len() < 50 {
-            self.dms_state.new_conversation_username.push(c);
-        }
-    }
-
-    /// Remove character from new conversation username
-    pub fn remove_char_from_new_conversation(&mut self) {
-        self.dms_state.new_conversation_username.pop();
-    }
-
     /// Start new conversation (just prepare, don't send anything yet)
     pub async fn start_new_conversation(&mut self) -> Result<()> {
         // Get the selected user from the filtered list
@@ -2360,6 +6243,7 @@ impl App {
         let to_username = filtered[selected_index].username.clone();
 
         self.dms_state.error = None;
+        let shared = self.dms_state.share_prefill.take();
 
         // Store the username for the pending conversation
         self.dms_state.pending_conversation_username = Some(to_username.clone());
@@ -2375,6 +6259,12 @@ impl App {
         // Switch to typing mode so user can immediately start composing
         self.input_mode = InputMode::Typing;
 
+        // A post shared from the share menu is ready to send
+        if let Some(text) = shared {
+            self.clear_dm_message();
+            self.dms_state.message_textarea.insert_str(text);
+        }
+
         Ok(())
     }
 
@@ -2436,23 +6326,48 @@ impl App {
         self.settings_state.loading = true;
         self.settings_state.error = None;
 
-        match self.api_client.get_config().await {
+        let result = self.api_client.get_config().await.map_err(|e| e.to_string());
+        self.apply_settings_result(result);
+
+        Ok(())
+    }
+
+    /// Load settings on a background task
+    pub fn spawn_load_settings(&mut self) {
+        self.settings_state.loading = true;
+        self.settings_state.error = None;
+
+        let client = self.api_client.clone();
+        self.task_runner.spawn(TaskKind::Settings, async move {
+            TaskResult::SettingsLoaded(client.get_config().await.map_err(|e| e.to_string()))
+        });
+    }
+
+    fn apply_settings_result(&mut self, result: Result<fido_types::UserConfig, String>) {
+        match result {
             Ok(config) => {
-                self.settings_state.max_posts_input = config.max_posts_display.to_string();
+                self.settings_state
+                    .max_posts_input
+                    .set_value(&config.max_posts_display.to_string());
                 self.settings_state.original_max_posts_input = config.max_posts_display.to_string();
+                self.posts_state.reduced_motion = config.reduced_motion;
                 self.settings_state.config = Some(config.clone());
                 self.settings_state.original_config = Some(config);
                 self.settings_state.loading = false;
                 self.settings_state.has_unsaved_changes = false;
             }
             Err(e) => {
-                let error_msg = categorize_error(&e.to_string());
+                let error_msg = categorize_error(&e);
                 self.settings_state.error = Some(error_msg);
                 self.settings_state.loading = false;
             }
         }
 
-        Ok(())
+        // Feed was waiting on sort order / page size; load it with whatever we have now
+        if self.posts_state.awaiting_settings {
+            self.posts_state.awaiting_settings = false;
+            self.spawn_load_posts();
+        }
     }
 
     /// Cycle color scheme
@@ -2462,90 +6377,354 @@ impl App {
                 fido_types::ColorScheme::Default => fido_types::ColorScheme::Dark,
                 fido_types::ColorScheme::Dark => fido_types::ColorScheme::Light,
                 fido_types::ColorScheme::Light => fido_types::ColorScheme::Solarized,
-                fido_types::ColorScheme::Solarized => fido_types::ColorScheme::Default,
+                fido_types::ColorScheme::Solarized => fido_types::ColorScheme::HighContrast,
+                fido_types::ColorScheme::HighContrast => fido_types::ColorScheme::Deuteranopia,
+                fido_types::ColorScheme::Deuteranopia => fido_types::ColorScheme::Protanopia,
+                fido_types::ColorScheme::Protanopia => fido_types::ColorScheme::Default,
+            };
+            self.check_settings_changes();
+        }
+    }
+
+    /// Cycle color scheme backward
+    pub fn cycle_color_scheme_backward(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.color_scheme = match config.color_scheme {
+                fido_types::ColorScheme::Default => fido_types::ColorScheme::Protanopia,
+                fido_types::ColorScheme::Dark => fido_types::ColorScheme::Default,
+                fido_types::ColorScheme::Light => fido_types::ColorScheme::Dark,
+                fido_types::ColorScheme::Solarized => fido_types::ColorScheme::Light,
+                fido_types::ColorScheme::HighContrast => fido_types::ColorScheme::Solarized,
+                fido_types::ColorScheme::Deuteranopia => fido_types::ColorScheme::HighContrast,
+                fido_types::ColorScheme::Protanopia => fido_types::ColorScheme::Deuteranopia,
+            };
+            self.check_settings_changes();
+        }
+    }
+
+    /// Cycle sort order
+    pub fn cycle_sort_order(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.sort_order = match config.sort_order {
+                fido_types::SortOrder::Newest => fido_types::SortOrder::Popular,
+                fido_types::SortOrder::Popular => fido_types::SortOrder::Controversial,
+                fido_types::SortOrder::Controversial => fido_types::SortOrder::Newest,
+            };
+            self.check_settings_changes();
+        }
+    }
+
+    /// Cycle sort order backward
+    pub fn cycle_sort_order_backward(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.sort_order = match config.sort_order {
+                fido_types::SortOrder::Newest => fido_types::SortOrder::Controversial,
+                fido_types::SortOrder::Popular => fido_types::SortOrder::Newest,
+                fido_types::SortOrder::Controversial => fido_types::SortOrder::Popular,
+            };
+            self.check_settings_changes();
+        }
+    }
+
+    /// Type into the max posts input
+    pub fn edit_max_posts(&mut self, key: KeyEvent) {
+        if self.settings_state.max_posts_input.handle_key(key) {
+            self.check_settings_changes();
+        }
+    }
+
+    /// Increment max posts display
+    pub fn increment_max_posts(&mut self) {
+        if let Ok(current) = self.settings_state.max_posts_input.value().parse::<i32>() {
+            let new_value = (current + 1).min(420); // Increment by 1, max 100
+            self.settings_state.max_posts_input.set_value(&new_value.to_string());
+            self.check_settings_changes();
+        }
+    }
+
+    /// Decrement max posts display
+    pub fn decrement_max_posts(&mut self) {
+        if let Ok(current) = self.settings_state.max_posts_input.value().parse::<i32>() {
+            let new_value = (current - 1).max(1); // Decrement by 1, min 1
+            self.settings_state.max_posts_input.set_value(&new_value.to_string());
+            self.check_settings_changes();
+        }
+    }
+
+    /// Toggle whether post authors can see this user's votes
+    pub fn toggle_vote_visibility(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.votes_visible_to_authors = !config.votes_visible_to_authors;
+            self.check_settings_changes();
+        }
+    }
+
+    /// Toggle do-not-disturb
+    pub fn toggle_do_not_disturb(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.do_not_disturb = !config.do_not_disturb;
+            self.check_settings_changes();
+        }
+    }
+
+    /// Toggle daily quiet hours
+    pub fn toggle_quiet_hours(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.quiet_hours_enabled = !config.quiet_hours_enabled;
+            self.check_settings_changes();
+        }
+    }
+
+    /// Move the start of quiet hours by `delta` hours, wrapping around midnight
+    pub fn shift_quiet_hours_start(&mut self, delta: i8) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.quiet_hours_start = shift_hour(config.quiet_hours_start, delta);
+            self.check_settings_changes();
+        }
+    }
+
+    /// Move the end of quiet hours by `delta` hours, wrapping around midnight
+    pub fn shift_quiet_hours_end(&mut self, delta: i8) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.quiet_hours_end = shift_hour(config.quiet_hours_end, delta);
+            self.check_settings_changes();
+        }
+    }
+
+    /// Show or hide unread counts next to tab names
+    pub fn toggle_unread_counts(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.show_unread_counts = !config.show_unread_counts;
+            self.check_settings_changes();
+        }
+    }
+
+    /// Switch between the normal and the plain single-column layout
+    pub fn toggle_accessible_layout(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.accessible_layout = !config.accessible_layout;
+            self.check_settings_changes();
+        }
+    }
+
+    /// Toggle reduced motion
+    pub fn toggle_reduced_motion(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.reduced_motion = !config.reduced_motion;
+            self.check_settings_changes();
+        }
+    }
+
+    /// Cycle how often the server compiles a digest: Off, Daily, Weekly
+    pub fn cycle_digest_frequency(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.digest_frequency = match config.digest_frequency {
+                fido_types::DigestFrequency::Off => fido_types::DigestFrequency::Daily,
+                fido_types::DigestFrequency::Daily => fido_types::DigestFrequency::Weekly,
+                fido_types::DigestFrequency::Weekly => fido_types::DigestFrequency::Off,
+            };
+            self.check_settings_changes();
+        }
+    }
+
+    /// Cycle digest frequency backward
+    pub fn cycle_digest_frequency_backward(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.digest_frequency = match config.digest_frequency {
+                fido_types::DigestFrequency::Off => fido_types::DigestFrequency::Weekly,
+                fido_types::DigestFrequency::Daily => fido_types::DigestFrequency::Off,
+                fido_types::DigestFrequency::Weekly => fido_types::DigestFrequency::Daily,
             };
             self.check_settings_changes();
         }
     }
 
-    /// Cycle color scheme backward
-    pub fn cycle_color_scheme_backward(&mut self) {
+    /// Switch between relative and absolute post times
+    pub fn toggle_timestamp_style(&mut self) {
         if let Some(config) = &mut self.settings_state.config {
-            config.color_scheme = match config.color_scheme {
-                fido_types::ColorScheme::Default => fido_types::ColorScheme::Solarized,
-                fido_types::ColorScheme::Dark => fido_types::ColorScheme::Default,
-                fido_types::ColorScheme::Light => fido_types::ColorScheme::Dark,
-                fido_types::ColorScheme::Solarized => fido_types::ColorScheme::Light,
+            config.timestamp_style = match config.timestamp_style {
+                fido_types::TimestampStyle::Absolute => fido_types::TimestampStyle::Relative,
+                fido_types::TimestampStyle::Relative => fido_types::TimestampStyle::Absolute,
             };
             self.check_settings_changes();
         }
     }
 
-    /// Cycle sort order
-    pub fn cycle_sort_order(&mut self) {
+    /// Switch the feed between comfortable and compact posts
+    pub fn toggle_feed_density(&mut self) {
         if let Some(config) = &mut self.settings_state.config {
-            config.sort_order = match config.sort_order {
-                fido_types::SortOrder::Newest => fido_types::SortOrder::Popular,
-                fido_types::SortOrder::Popular => fido_types::SortOrder::Controversial,
-                fido_types::SortOrder::Controversial => fido_types::SortOrder::Newest,
+            config.feed_density = match config.feed_density {
+                fido_types::FeedDensity::Comfortable => fido_types::FeedDensity::Compact,
+                fido_types::FeedDensity::Compact => fido_types::FeedDensity::Comfortable,
             };
             self.check_settings_changes();
         }
     }
 
-    /// Cycle sort order backward
-    pub fn cycle_sort_order_backward(&mut self) {
+    /// Change the preview line limit by `delta`, wrapping between "All" (0)
+    /// and the largest limit
+    pub fn shift_preview_lines(&mut self, delta: i8) {
         if let Some(config) = &mut self.settings_state.config {
-            config.sort_order = match config.sort_order {
-                fido_types::SortOrder::Newest => fido_types::SortOrder::Controversial,
-                fido_types::SortOrder::Popular => fido_types::SortOrder::Newest,
-                fido_types::SortOrder::Controversial => fido_types::SortOrder::Popular,
-            };
+            let steps = i16::from(fido_types::UserConfig::MAX_PREVIEW_LINES) + 1;
+            config.preview_lines =
+                (i16::from(config.preview_lines) + i16::from(delta)).rem_euclid(steps) as u8;
             self.check_settings_changes();
         }
     }
 
-    /// Add digit to max posts input
-    pub fn add_digit_to_max_posts(&mut self, c: char) {
-        if c.is_ascii_digit() && self.settings_state.max_posts_input.len() < 3 {
-            self.settings_state.max_posts_input.push(c);
+    /// Show or hide vote counts in the feed
+    pub fn toggle_vote_counts(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.show_vote_counts = !config.show_vote_counts;
             self.check_settings_changes();
         }
     }
 
-    /// Remove digit from max posts input
-    pub fn remove_digit_from_max_posts(&mut self) {
-        if !self.settings_state.max_posts_input.is_empty() {
-            self.settings_state.max_posts_input.pop();
+    /// Show or hide authors' karma next to their usernames
+    pub fn toggle_karma_display(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.show_karma = !config.show_karma;
             self.check_settings_changes();
         }
     }
 
-    /// Increment max posts display
-    pub fn increment_max_posts(&mut self) {
-        if let Ok(current) = self.settings_state.max_posts_input.parse::<i32>() {
-            let new_value = (current + 1).min(420); // Increment by 1, max 100
-            self.settings_state.max_posts_input = new_value.to_string();
+    /// Let search engines index (or not) this account's public pages
+    pub fn toggle_discoverable(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.discoverable = !config.discoverable;
             self.check_settings_changes();
         }
     }
 
-    /// Decrement max posts display
-    pub fn decrement_max_posts(&mut self) {
-        if let Ok(current) = self.settings_state.max_posts_input.parse::<i32>() {
-            let new_value = (current - 1).max(1); // Decrement by 1, min 1
-            self.settings_state.max_posts_input = new_value.to_string();
+    /// Start typing the pinned hashtags, seeded with the current ones
+    pub fn start_editing_pinned_hashtags(&mut self) {
+        if let Some(config) = &self.settings_state.config {
+            let current: Vec<String> = config
+                .pinned_hashtags
+                .iter()
+                .map(|tag| format!("#{}", tag))
+                .collect();
+            let mut input = TextInput::new();
+            input.set_value(&current.join(" "));
+            self.settings_state.pinned_hashtags_input = Some(input);
+            self.input_mode = InputMode::Typing;
+        }
+    }
+
+    /// Keys while typing pinned hashtags: Enter keeps them, Esc drops the edit
+    pub fn handle_pinned_hashtags_input(&mut self, key: KeyEvent) {
+        let Some(input) = &mut self.settings_state.pinned_hashtags_input else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => self.commit_pinned_hashtags(),
+            KeyCode::Esc => {
+                self.settings_state.pinned_hashtags_input = None;
+                self.settings_state.error = None;
+                self.input_mode = InputMode::Navigation;
+            }
+            _ => {
+                input.handle_key(key);
+            }
+        }
+    }
+
+    /// Take the typed hashtags (separated by spaces or commas) into the
+    /// unsaved settings; invalid input stays open with an error
+    fn commit_pinned_hashtags(&mut self) {
+        let Some(input) = &self.settings_state.pinned_hashtags_input else {
+            return;
+        };
+        let tags: Vec<&str> = input
+            .value()
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        match fido_types::UserConfig::normalize_pinned_hashtags(&tags) {
+            Ok(pinned) => {
+                if let Some(config) = &mut self.settings_state.config {
+                    config.pinned_hashtags = pinned;
+                }
+                self.settings_state.pinned_hashtags_input = None;
+                self.settings_state.error = None;
+                self.input_mode = InputMode::Navigation;
+                self.check_settings_changes();
+            }
+            Err(e) => {
+                self.settings_state.error = Some(format!("Validation Error: {}", e));
+            }
+        }
+    }
+
+    /// Number key `key` in the feed: switch to the hashtag pinned to it, or
+    /// back to the global feed if that hashtag is already the filter
+    pub fn apply_pinned_hashtag(&mut self, key: usize) {
+        let tag = self
+            .settings_state
+            .original_config
+            .as_ref()
+            .and_then(|config| config.pinned_hashtag(key))
+            .map(str::to_string);
+        let Some(tag) = tag else {
+            self.toasts
+                .info(format!("Nothing pinned to {} (pin hashtags in Settings)", key));
+            return;
+        };
+        let filter = PostFilter::Hashtag(tag);
+        let filter = if self.posts_state.current_filter == filter {
+            PostFilter::All
+        } else {
+            filter
+        };
+        self.switch_feed_filter(filter);
+        self.save_filter_preference();
+        self.posts_state.pending_load = true;
+    }
+
+    /// Cycle where announcements go: Off, Terminal, Speech
+    pub fn cycle_announcements(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.announcements = match config.announcements {
+                fido_types::AnnounceOutput::Off => fido_types::AnnounceOutput::Terminal,
+                fido_types::AnnounceOutput::Terminal => fido_types::AnnounceOutput::Speech,
+                fido_types::AnnounceOutput::Speech => fido_types::AnnounceOutput::Off,
+            };
+            self.check_settings_changes();
+        }
+    }
+
+    /// Cycle announcements backward
+    pub fn cycle_announcements_backward(&mut self) {
+        if let Some(config) = &mut self.settings_state.config {
+            config.announcements = match config.announcements {
+                fido_types::AnnounceOutput::Off => fido_types::AnnounceOutput::Speech,
+                fido_types::AnnounceOutput::Terminal => fido_types::AnnounceOutput::Off,
+                fido_types::AnnounceOutput::Speech => fido_types::AnnounceOutput::Terminal,
+            };
             self.check_settings_changes();
         }
     }
 
+    /// Encrypt stored sessions with the OS keychain, or go back to plain files
+    pub fn toggle_session_encryption(&mut self) {
+        let enabled = !self.server_config.encrypt_sessions();
+        match self.server_config.set_encrypt_sessions(enabled) {
+            Ok(()) if enabled => self.toasts.success("✓ Saved sessions are now encrypted"),
+            Ok(()) => self.toasts.info("Saved sessions are no longer encrypted"),
+            Err(e) => {
+                log::warn!("Failed to change session encryption: {:#}", e);
+                self.toasts.error(format!("Couldn't change session encryption: {}", e));
+            }
+        }
+    }
+
     /// Save settings
     pub async fn save_settings(&mut self) -> Result<()> {
         if let Some(config) = &self.settings_state.config {
             self.settings_state.error = None;
 
             // Validate max posts
-            let max_posts = match self.settings_state.max_posts_input.parse::<i32>() {
+            let max_posts = match self.settings_state.max_posts_input.value().parse::<i32>() {
                 Ok(n) if n > 0 => n,
                 Ok(n) => {
                     self.settings_state.error = Some(format!(
@@ -2562,7 +6741,7 @@ impl App {
                 Err(_) => {
                     self.settings_state.error = Some(format!(
                         "Validation Error: '{}' is not a valid number",
-                        self.settings_state.max_posts_input
+                        self.settings_state.max_posts_input.value()
                     ));
                     return Ok(());
                 }
@@ -2573,21 +6752,40 @@ impl App {
                 sort_order: Some(config.sort_order.as_str().to_string()),
                 max_posts_display: Some(max_posts),
                 emoji_enabled: Some(config.emoji_enabled),
+                votes_visible_to_authors: Some(config.votes_visible_to_authors),
+                do_not_disturb: Some(config.do_not_disturb),
+                quiet_hours_enabled: Some(config.quiet_hours_enabled),
+                quiet_hours_start: Some(config.quiet_hours_start),
+                quiet_hours_end: Some(config.quiet_hours_end),
+                show_unread_counts: Some(config.show_unread_counts),
+                accessible_layout: Some(config.accessible_layout),
+                announcements: Some(config.announcements.as_str().to_string()),
+                reduced_motion: Some(config.reduced_motion),
+                timestamp_style: Some(config.timestamp_style.as_str().to_string()),
+                feed_density: Some(config.feed_density.as_str().to_string()),
+                preview_lines: Some(config.preview_lines),
+                show_vote_counts: Some(config.show_vote_counts),
+                show_karma: Some(config.show_karma),
+                pinned_hashtags: Some(config.pinned_hashtags.clone()),
+                digest_frequency: Some(config.digest_frequency.as_str().to_string()),
+                discoverable: Some(config.discoverable),
             };
 
             match self.api_client.update_config(request).await {
                 Ok(updated_config) => {
-                    self.settings_state.max_posts_input =
-                        updated_config.max_posts_display.to_string();
+                    self.settings_state
+                        .max_posts_input
+                        .set_value(&updated_config.max_posts_display.to_string());
                     self.settings_state.original_max_posts_input =
                         updated_config.max_posts_display.to_string();
+                    self.posts_state.reduced_motion = updated_config.reduced_motion;
                     self.settings_state.config = Some(updated_config.clone());
                     self.settings_state.original_config = Some(updated_config);
                     self.settings_state.has_unsaved_changes = false;
-                    self.settings_state.error = Some("✓ Settings saved successfully!".to_string());
+                    self.toasts.success("✓ Settings saved");
 
                     // Reload posts with new settings (max_posts_display and sort_order)
-                    let _ = self.load_posts().await;
+                    self.spawn_load_posts();
                 }
                 Err(e) => {
                     self.settings_state.error = Some(format!(
@@ -2608,9 +6806,26 @@ impl App {
             &self.settings_state.original_config,
         ) {
             let config_changed = current.color_scheme != original.color_scheme
-                || current.sort_order != original.sort_order;
-            let max_posts_changed =
-                self.settings_state.max_posts_input != self.settings_state.original_max_posts_input;
+                || current.sort_order != original.sort_order
+                || current.votes_visible_to_authors != original.votes_visible_to_authors
+                || current.do_not_disturb != original.do_not_disturb
+                || current.quiet_hours_enabled != original.quiet_hours_enabled
+                || current.quiet_hours_start != original.quiet_hours_start
+                || current.quiet_hours_end != original.quiet_hours_end
+                || current.show_unread_counts != original.show_unread_counts
+                || current.accessible_layout != original.accessible_layout
+                || current.reduced_motion != original.reduced_motion
+                || current.timestamp_style != original.timestamp_style
+                || current.feed_density != original.feed_density
+                || current.preview_lines != original.preview_lines
+                || current.show_vote_counts != original.show_vote_counts
+                || current.show_karma != original.show_karma
+                || current.pinned_hashtags != original.pinned_hashtags
+                || current.digest_frequency != original.digest_frequency
+                || current.discoverable != original.discoverable
+                || current.announcements != original.announcements;
+            let max_posts_changed = self.settings_state.max_posts_input.value()
+                != self.settings_state.original_max_posts_input;
 
             self.settings_state.has_unsaved_changes = config_changed || max_posts_changed;
         }
@@ -2637,12 +6852,24 @@ impl App {
         match key.code {
             KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => self.next_user_post(),
             KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => self.previous_user_post(),
+            KeyCode::Left | KeyCode::Char('h') => self.switch_profile_posts_tab(false),
+            KeyCode::Right | KeyCode::Char('l') => self.switch_profile_posts_tab(true),
             KeyCode::Char('e') | KeyCode::Char('E') => {
                 if let Some(profile) = &self.profile_state.profile {
                     let current_bio = profile.bio.clone().unwrap_or_default();
                     self.open_composer_edit_bio(current_bio);
                 }
             }
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                if let Some(profile) = &self.profile_state.profile {
+                    let current_status = profile
+                        .status
+                        .as_ref()
+                        .map(crate::status::status_input)
+                        .unwrap_or_default();
+                    self.open_composer_set_status(current_status);
+                }
+            }
             KeyCode::Char('f') | KeyCode::Char('F') => {
                 // Open social connections modal
                 self.friends_state.show_friends_modal = true;
@@ -2657,36 +6884,12 @@ impl App {
     /// Handle keys for edit bio modal
     pub fn handle_edit_bio_modal_keys(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
-            KeyCode::Char(c) => {
-                self.add_char_to_bio(c);
-            }
-            KeyCode::Backspace => {
-                self.remove_char_from_bio();
-            }
             KeyCode::Enter => {
                 // Enter saves bio (will be handled async in main loop)
             }
-            KeyCode::Left => {
-                if self.profile_state.edit_bio_cursor_position > 0 {
-                    self.profile_state.edit_bio_cursor_position -= 1;
-                }
-            }
-            KeyCode::Right => {
-                let char_count = self.profile_state.edit_bio_content.chars().count();
-                if self.profile_state.edit_bio_cursor_position < char_count {
-                    self.profile_state.edit_bio_cursor_position += 1;
-                }
-            }
-            KeyCode::Home => {
-                // Move to start of text
-                self.profile_state.edit_bio_cursor_position = 0;
-            }
-            KeyCode::End => {
-                // Move to end of text (character count, not byte length)
-                self.profile_state.edit_bio_cursor_position =
-                    self.profile_state.edit_bio_content.chars().count();
+            _ => {
+                self.profile_state.edit_bio.handle_key(key);
             }
-            _ => {}
         }
         Ok(())
     }
@@ -2697,6 +6900,10 @@ impl App {
         if self.dms_state.show_new_conversation_modal {
             return self.handle_new_conversation_modal_keys(key);
         }
+        if self.dms_state.reminder_input.is_some() {
+            self.handle_dm_reminder_input(key);
+            return Ok(());
+        }
 
         // Check input mode
         match self.input_mode {
@@ -2757,6 +6964,44 @@ impl App {
                             self.input_mode = InputMode::Typing;
                         }
                     }
+                    // Pick a message in the open conversation
+                    KeyCode::Char('[') => self.select_dm_message(true),
+                    KeyCode::Char(']') => self.select_dm_message(false),
+                    // Cross-post the picked message to the feed. Behind Ctrl
+                    // because a plain letter starts typing a message.
+                    KeyCode::Char('x') | KeyCode::Char('X')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        self.open_crosspost_for_selected_message()
+                    }
+                    // Show or save the picked (or newest) attachment
+                    KeyCode::Char('e') | KeyCode::Char('E')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        self.toggle_dm_attachment()
+                    }
+                    KeyCode::Char('s') | KeyCode::Char('S')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        self.save_dm_attachment()
+                    }
+                    // Mark the selected conversation unread, or set a
+                    // reminder to come back to it
+                    KeyCode::Char('u') | KeyCode::Char('U')
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && self.selected_dm_user().is_some() =>
+                    {
+                        self.mark_selected_conversation_unread()
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R')
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && self.selected_dm_user().is_some() =>
+                    {
+                        self.start_dm_reminder_input()
+                    }
+                    // Resend messages that didn't go through; otherwise 'r'
+                    // starts a message like any other letter
+                    KeyCode::Char('r') if self.has_failed_dm() => self.retry_failed_dms(),
 
                     _ => {
                         // Any other key starts typing mode
@@ -2772,8 +7017,8 @@ impl App {
             InputMode::Typing => {
                 match key.code {
                     KeyCode::Esc => {
-                        // Clear input and return to navigation mode
-                        self.clear_dm_message();
+                        // Keep the input as this conversation's draft and return to navigation mode
+                        self.stash_dm_draft();
                         self.input_mode = InputMode::Navigation;
                     }
                     KeyCode::Enter => {
@@ -2799,18 +7044,14 @@ impl App {
         // If in search mode, handle search input
         if self.dms_state.new_conversation_search_mode {
             match key.code {
-                KeyCode::Char(c) => {
-                    self.dms_state.new_conversation_search_query.push(c);
-                    self.dms_state.new_conversation_selected_index = 0;
-                }
-                KeyCode::Backspace => {
-                    self.dms_state.new_conversation_search_query.pop();
-                    self.dms_state.new_conversation_selected_index = 0;
-                }
                 KeyCode::Esc => {
                     self.dms_state.new_conversation_search_mode = false;
                 }
-                _ => {}
+                _ => {
+                    if self.dms_state.new_conversation_username.handle_key(key) {
+                        self.dms_state.new_conversation_selected_index = 0;
+                    }
+                }
             }
             return Ok(());
         }
@@ -2855,12 +7096,35 @@ impl App {
             return Ok(());
         }
 
+        if self.settings_state.pinned_hashtags_input.is_some() {
+            self.handle_pinned_hashtags_input(key);
+            return Ok(());
+        }
+
         match key.code {
             KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
                 self.settings_state.selected_field = match self.settings_state.selected_field {
                     SettingsField::ColorScheme => SettingsField::SortOrder,
                     SettingsField::SortOrder => SettingsField::MaxPosts,
-                    SettingsField::MaxPosts => SettingsField::MaxPosts, // Stop at last field
+                    SettingsField::MaxPosts => SettingsField::VotePrivacy,
+                    SettingsField::VotePrivacy => SettingsField::DoNotDisturb,
+                    SettingsField::DoNotDisturb => SettingsField::QuietHours,
+                    SettingsField::QuietHours => SettingsField::QuietHoursStart,
+                    SettingsField::QuietHoursStart => SettingsField::QuietHoursEnd,
+                    SettingsField::QuietHoursEnd => SettingsField::TabUnreadCounts,
+                    SettingsField::TabUnreadCounts => SettingsField::Digest,
+                    SettingsField::Digest => SettingsField::AccessibleLayout,
+                    SettingsField::AccessibleLayout => SettingsField::Announcements,
+                    SettingsField::Announcements => SettingsField::ReducedMotion,
+                    SettingsField::ReducedMotion => SettingsField::Timestamps,
+                    SettingsField::Timestamps => SettingsField::FeedDensity,
+                    SettingsField::FeedDensity => SettingsField::PreviewLines,
+                    SettingsField::PreviewLines => SettingsField::VoteCounts,
+                    SettingsField::VoteCounts => SettingsField::Karma,
+                    SettingsField::Karma => SettingsField::PinnedHashtags,
+                    SettingsField::PinnedHashtags => SettingsField::Discoverable,
+                    SettingsField::Discoverable => SettingsField::SessionEncryption,
+                    SettingsField::SessionEncryption => SettingsField::SessionEncryption, // Stop at last field
                 };
             }
             KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
@@ -2868,23 +7132,76 @@ impl App {
                     SettingsField::ColorScheme => SettingsField::ColorScheme, // Stop at first field
                     SettingsField::SortOrder => SettingsField::ColorScheme,
                     SettingsField::MaxPosts => SettingsField::SortOrder,
+                    SettingsField::VotePrivacy => SettingsField::MaxPosts,
+                    SettingsField::DoNotDisturb => SettingsField::VotePrivacy,
+                    SettingsField::QuietHours => SettingsField::DoNotDisturb,
+                    SettingsField::QuietHoursStart => SettingsField::QuietHours,
+                    SettingsField::QuietHoursEnd => SettingsField::QuietHoursStart,
+                    SettingsField::TabUnreadCounts => SettingsField::QuietHoursEnd,
+                    SettingsField::AccessibleLayout => SettingsField::Digest,
+                    SettingsField::Digest => SettingsField::TabUnreadCounts,
+                    SettingsField::Announcements => SettingsField::AccessibleLayout,
+                    SettingsField::ReducedMotion => SettingsField::Announcements,
+                    SettingsField::Timestamps => SettingsField::ReducedMotion,
+                    SettingsField::FeedDensity => SettingsField::Timestamps,
+                    SettingsField::PreviewLines => SettingsField::FeedDensity,
+                    SettingsField::VoteCounts => SettingsField::PreviewLines,
+                    SettingsField::PinnedHashtags => SettingsField::Karma,
+                    SettingsField::Karma => SettingsField::VoteCounts,
+                    SettingsField::SessionEncryption => SettingsField::Discoverable,
+                    SettingsField::Discoverable => SettingsField::PinnedHashtags,
                 };
             }
             KeyCode::Char('h') | KeyCode::Char('H') | KeyCode::Left => match self.settings_state.selected_field {
                 SettingsField::ColorScheme => self.cycle_color_scheme_backward(),
                 SettingsField::SortOrder => self.cycle_sort_order_backward(),
                 SettingsField::MaxPosts => self.decrement_max_posts(),
+                SettingsField::VotePrivacy => self.toggle_vote_visibility(),
+                SettingsField::DoNotDisturb => self.toggle_do_not_disturb(),
+                SettingsField::QuietHours => self.toggle_quiet_hours(),
+                SettingsField::QuietHoursStart => self.shift_quiet_hours_start(-1),
+                SettingsField::QuietHoursEnd => self.shift_quiet_hours_end(-1),
+                SettingsField::TabUnreadCounts => self.toggle_unread_counts(),
+                SettingsField::Digest => self.cycle_digest_frequency_backward(),
+                SettingsField::AccessibleLayout => self.toggle_accessible_layout(),
+                SettingsField::Announcements => self.cycle_announcements_backward(),
+                SettingsField::ReducedMotion => self.toggle_reduced_motion(),
+                SettingsField::Timestamps => self.toggle_timestamp_style(),
+                SettingsField::FeedDensity => self.toggle_feed_density(),
+                SettingsField::PreviewLines => self.shift_preview_lines(-1),
+                SettingsField::VoteCounts => self.toggle_vote_counts(),
+                SettingsField::Karma => self.toggle_karma_display(),
+                SettingsField::PinnedHashtags => {}
+                SettingsField::Discoverable => self.toggle_discoverable(),
+                SettingsField::SessionEncryption => self.toggle_session_encryption(),
             },
             KeyCode::Char('l') | KeyCode::Char('L') | KeyCode::Right | KeyCode::Enter => match self.settings_state.selected_field {
                 SettingsField::ColorScheme => self.cycle_color_scheme(),
                 SettingsField::SortOrder => self.cycle_sort_order(),
                 SettingsField::MaxPosts => self.increment_max_posts(),
+                SettingsField::VotePrivacy => self.toggle_vote_visibility(),
+                SettingsField::DoNotDisturb => self.toggle_do_not_disturb(),
+                SettingsField::QuietHours => self.toggle_quiet_hours(),
+                SettingsField::QuietHoursStart => self.shift_quiet_hours_start(1),
+                SettingsField::QuietHoursEnd => self.shift_quiet_hours_end(1),
+                SettingsField::TabUnreadCounts => self.toggle_unread_counts(),
+                SettingsField::Digest => self.cycle_digest_frequency(),
+                SettingsField::AccessibleLayout => self.toggle_accessible_layout(),
+                SettingsField::Announcements => self.cycle_announcements(),
+                SettingsField::ReducedMotion => self.toggle_reduced_motion(),
+                SettingsField::Timestamps => self.toggle_timestamp_style(),
+                SettingsField::FeedDensity => self.toggle_feed_density(),
+                SettingsField::PreviewLines => self.shift_preview_lines(1),
+                SettingsField::VoteCounts => self.toggle_vote_counts(),
+                SettingsField::Karma => self.toggle_karma_display(),
+                SettingsField::PinnedHashtags => self.start_editing_pinned_hashtags(),
+                SettingsField::Discoverable => self.toggle_discoverable(),
+                SettingsField::SessionEncryption => self.toggle_session_encryption(),
             },
-            KeyCode::Backspace if self.settings_state.selected_field == SettingsField::MaxPosts => {
-                self.remove_digit_from_max_posts();
-            }
-            KeyCode::Char(c) if self.settings_state.selected_field == SettingsField::MaxPosts => {
-                self.add_digit_to_max_posts(c);
+            KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(_)
+                if self.settings_state.selected_field == SettingsField::MaxPosts =>
+            {
+                self.edit_max_posts(key);
             }
             KeyCode::Char('s') => {
                 // Save settings (will be handled async in main loop)
@@ -2899,6 +7216,11 @@ impl App {
         handlers::handle_posts_keys(self, key)
     }
 
+    /// Handle keys for Channels tab
+    pub fn handle_channels_keys(&mut self, key: KeyEvent) -> Result<()> {
+        handlers::handle_channels_keys(self, key)
+    }
+
     /// Handle keys for filter modal
     pub fn handle_filter_modal_keys(&mut self, key: KeyEvent) -> Result<()> {
         handlers::handle_filter_modal_keys(self, key)
@@ -2923,10 +7245,32 @@ impl App {
             KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
                 self.modal_previous_reply();
             }
-            KeyCode::Char(' ') | KeyCode::Enter => {
+            // Enter on a focused related post opens it (handled async in main loop)
+            KeyCode::Char(' ') | KeyCode::Enter if self.selected_related_post_id().is_none() => {
                 // Toggle expansion in modal
                 self.modal_toggle_expansion();
             }
+            KeyCode::Tab => {
+                self.cycle_related_focus(true);
+            }
+            KeyCode::BackTab => {
+                self.cycle_related_focus(false);
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                self.open_link_preview();
+            }
+            KeyCode::Char('t') => {
+                self.toggle_translation();
+            }
+            KeyCode::Char('T') => {
+                self.toggle_exact_time();
+            }
+            KeyCode::Char('v') => {
+                self.toggle_raw_diff();
+            }
+            KeyCode::Char('S') => {
+                self.open_share_menu();
+            }
             KeyCode::Char('r') | KeyCode::Char('R') => {
                 // Reply to the currently selected post/comment in modal
                 if let Some(detail_state) = &self.post_detail_state {
@@ -2977,6 +7321,9 @@ impl App {
             KeyCode::Char('d') | KeyCode::Char('D') => {
                 // Downvote from modal (will be handled async in main loop)
             }
+            KeyCode::Char('w') | KeyCode::Char('W') => {
+                // Watch/unwatch the thread (will be handled async in main loop)
+            }
             KeyCode::Char('x') | KeyCode::Char('X') => {
                 // Delete selected post (only if user owns it)
                 self.show_delete_confirmation();
@@ -3013,6 +7360,136 @@ impl App {
         Ok(())
     }
 
+    /// Show the near-duplicates folded into the selected feed entry, right after it
+    pub fn expand_similar_posts(&mut self) {
+        let Some(index) = self
+            .posts_state
+            .list_state
+            .selected()
+            .and_then(|list_idx| self.posts_state.list_index_to_post_index(list_idx))
+        else {
+            return;
+        };
+        let Some(post_id) = self.posts_state.posts.get(index).map(|post| post.id) else {
+            return;
+        };
+        if let Some(hidden) = self.posts_state.similar.remove(&post_id) {
+            let at = index + 1;
+            self.posts_state.posts.splice(at..at, hidden);
+        }
+    }
+
+    /// Enter or leave visual selection mode ('V'); entering marks the
+    /// selected post
+    pub fn toggle_bulk_selection(&mut self) {
+        if self.posts_state.bulk_selection.take().is_some() {
+            return;
+        }
+        let marked = self.selected_feed_post().map(|post| post.id).into_iter().collect();
+        self.posts_state.bulk_selection = Some(marked);
+    }
+
+    /// Leave visual selection mode; false when it wasn't on
+    pub fn clear_bulk_selection(&mut self) -> bool {
+        self.posts_state.bulk_selection.take().is_some()
+    }
+
+    /// Mark or unmark the selected post (Space in visual selection mode)
+    pub fn toggle_post_mark(&mut self) {
+        let Some(post_id) = self.selected_feed_post().map(|post| post.id) else {
+            return;
+        };
+        if let Some(marked) = &mut self.posts_state.bulk_selection {
+            if !marked.remove(&post_id) {
+                marked.insert(post_id);
+            }
+        }
+    }
+
+    /// Marked posts still in the feed, in feed order
+    pub fn marked_posts(&self) -> Vec<&Post> {
+        let Some(marked) = &self.posts_state.bulk_selection else {
+            return Vec::new();
+        };
+        self.posts_state
+            .posts
+            .iter()
+            .filter(|post| marked.contains(&post.id))
+            .collect()
+    }
+
+    /// Apply `action` to every marked post and leave visual selection mode.
+    /// Bookmarking and muting go to the server in one batch request each.
+    pub fn apply_bulk_action(&mut self, action: BulkAction) {
+        let posts: Vec<(Uuid, Uuid)> = self
+            .marked_posts()
+            .into_iter()
+            .map(|post| (post.id, post.author_id))
+            .collect();
+        if posts.is_empty() {
+            self.toasts.info("No posts marked; Space marks the selected one");
+            return;
+        }
+        self.posts_state.bulk_selection = None;
+
+        let client = self.api_client.clone();
+        match action {
+            BulkAction::Bookmark => {
+                let post_ids = posts.iter().map(|(post_id, _)| *post_id).collect();
+                self.task_runner.spawn(TaskKind::BulkAction, async move {
+                    TaskResult::BulkActionDone {
+                        action,
+                        result: client.bookmark_posts(post_ids).await.map_err(|e| e.to_string()),
+                    }
+                });
+            }
+            BulkAction::MuteAuthors => {
+                let own_id = self.auth_state.current_user.as_ref().map(|user| user.id);
+                let mut author_ids: Vec<Uuid> = Vec::new();
+                for (_, author_id) in &posts {
+                    // Anonymous authors come through as the nil ID
+                    if Some(*author_id) != own_id
+                        && !author_id.is_nil()
+                        && !author_ids.contains(author_id)
+                    {
+                        author_ids.push(*author_id);
+                    }
+                }
+                if author_ids.is_empty() {
+                    self.toasts.info("Those are all your own posts");
+                    return;
+                }
+                self.task_runner.spawn(TaskKind::BulkAction, async move {
+                    TaskResult::BulkActionDone {
+                        action,
+                        result: client.mute_users(author_ids).await.map_err(|e| e.to_string()),
+                    }
+                });
+            }
+            BulkAction::OpenInBrowser => {
+                let mut opened = 0;
+                for (post_id, _) in posts.iter().take(MAX_BULK_OPEN) {
+                    match webbrowser::open(&self.post_permalink(*post_id)) {
+                        Ok(()) => opened += 1,
+                        Err(e) => {
+                            self.toasts.error(format!("Couldn't open browser: {}", e));
+                            return;
+                        }
+                    }
+                }
+                if posts.len() > MAX_BULK_OPEN {
+                    self.toasts.info(format!(
+                        "Opened the first {} of {} posts",
+                        opened,
+                        posts.len()
+                    ));
+                } else {
+                    self.toasts.success(format!("Opened {} in browser", plural_posts(opened)));
+                }
+            }
+        }
+    }
+
     pub fn next_post(&mut self) {
         if self.posts_state.posts.is_empty() {
             return;
@@ -3076,6 +7553,8 @@ impl App {
         let i = match self.profile_state.list_state.selected() {
             Some(i) => {
                 if i >= self.profile_state.user_posts.len() - 1 {
+                    // At the last loaded post: fetch older ones if there are any
+                    self.spawn_load_more_profile_posts();
                     i // Stop at last post, don't wrap
                 } else {
                     i + 1
@@ -3136,6 +7615,10 @@ impl App {
                     self.auth_state.selected_index += 1;
                 }
             }
+            KeyCode::Char(c @ '1'..='9') if !self.auth_state.github_auth_in_progress => {
+                let index = c as usize - '1' as usize;
+                self.switch_to_saved_account(index);
+            }
             _ => {}
         }
         Ok(())
@@ -3155,6 +7638,7 @@ impl App {
     /// Open post detail view
     pub async fn open_post_detail(&mut self, post_id: Uuid) -> Result<()> {
         let previous_position = self.posts_state.list_state.selected();
+        self.finish_read_later(post_id);
         
         // Initialize modal list state with root post selected (index 0)
         let mut modal_list_state = ListState::default();
@@ -3170,7 +7654,6 @@ impl App {
             reply_list_state: ListState::default(),
             loading: true,
             error: None,
-            message: None,
             show_reply_composer: false,
             reply_content: String::new(),
             show_delete_confirmation: false,
@@ -3180,9 +7663,14 @@ impl App {
             full_post_modal_id: Some(post_id), // Set the post ID for modal
             modal_list_state,
             modal_expanded_posts, // Root post pre-expanded
+            related: Vec::new(),
+            related_selected: None,
         });
         self.viewing_post_detail = true;
         self.load_post_detail(post_id).await?;
+        if let Some(post) = self.post_detail_state.as_ref().and_then(|d| d.post.clone()) {
+            self.record_reading_history(post, chrono::Utc::now());
+        }
         Ok(())
     }
 
@@ -3201,7 +7689,6 @@ impl App {
                 reply_list_state: ListState::default(),
                 loading: true,
                 error: None,
-                message: None,
                 show_reply_composer: false,
                 reply_content: String::new(),
                 show_delete_confirmation: false,
@@ -3211,6 +7698,8 @@ impl App {
                 full_post_modal_id: Some(post_id),
                 modal_list_state,
                 modal_expanded_posts,
+                related: Vec::new(),
+                related_selected: None,
             });
         }
         
@@ -3260,7 +7749,32 @@ impl App {
                 Err(e) => {
                     detail_state.error = Some(categorize_error(&e.to_string()));
                     detail_state.loading = false;
+                    return Ok(());
+                }
+            }
+
+            // Related posts are a discovery aid, so the thread still opens without them
+            match self.api_client.get_related_posts(post_id).await {
+                Ok(related) => {
+                    if detail_state.related_selected.is_some_and(|i| i >= related.len()) {
+                        detail_state.related_selected = None;
+                    }
+                    detail_state.related = related;
                 }
+                Err(e) => log::debug!("Failed to load related posts: {}", e),
+            }
+        }
+
+        self.load_github_cards();
+        self.load_thread_gist();
+
+        // Every reply has just been loaded, so a watched thread is caught up
+        if self.watched_threads.contains_key(&post_id) {
+            match self.api_client.mark_thread_seen(post_id).await {
+                Ok(()) => {
+                    self.watched_threads.insert(post_id, 0);
+                }
+                Err(e) => log::debug!("Failed to mark thread seen: {}", e),
             }
         }
         Ok(())
@@ -3418,107 +7932,13 @@ impl App {
             None => return Ok(()),
         };
 
-        // Get the modal root post ID
-        let modal_root_id = match detail_state.full_post_modal_id {
-            Some(id) => id,
-            None => return Ok(()),
-        };
-
-        // Get selected index (0 = root, 1+ = replies)
+        // Map the selection (0 = root, 1+ = visible replies) to the post voted on
         let selected_idx = detail_state.modal_list_state.selected().unwrap_or(0);
-
-        // Find the post ID to vote on
-        let post_id = if selected_idx == 0 {
-            // Voting on root post
-            modal_root_id
-        } else {
-            // Voting on a reply - need to map index to post ID
-            // Find the modal root post
-            let modal_root = if let Some(post) = &detail_state.post {
-                if post.id == modal_root_id {
-                    Some(post.clone())
-                } else {
-                    detail_state
-                        .replies
-                        .iter()
-                        .find(|r| r.id == modal_root_id)
-                        .cloned()
-                }
-            } else {
-                None
-            };
-
-            if let Some(root) = modal_root {
-                // Filter replies to descendants (excluding the root itself)
-                let modal_replies: Vec<Post> = detail_state
-                    .replies
-                    .iter()
-                    .filter(|reply| {
-                        if reply.id == root.id {
-                            return false;
-                        }
-                        let mut current_parent = reply.parent_post_id;
-                        while let Some(parent_id) = current_parent {
-                            if parent_id == root.id {
-                                return true;
-                            }
-                            current_parent = detail_state
-                                .replies
-                                .iter()
-                                .find(|r| r.id == parent_id)
-                                .and_then(|r| r.parent_post_id);
-                        }
-                        false
-                    })
-                    .cloned()
-                    .collect();
-
-                // Build tree and flatten to map index to post
-                use std::collections::HashMap;
-                let mut children_map: HashMap<Uuid, Vec<&Post>> = HashMap::new();
-                for reply in &modal_replies {
-                    if let Some(parent_id) = reply.parent_post_id {
-                        children_map
-                            .entry(parent_id)
-                            .or_default()
-                            .push(reply);
-                    }
-                }
-
-                // Flatten the tree respecting current expansion state
-                let mut flattened_posts = vec![];
-                fn collect_visible(
-                    post_id: &Uuid,
-                    children_map: &HashMap<Uuid, Vec<&Post>>,
-                    expanded_posts: &HashMap<Uuid, bool>,
-                    result: &mut Vec<Uuid>,
-                ) {
-                    if let Some(children) = children_map.get(post_id) {
-                        for child in children {
-                            result.push(child.id);
-                            if expanded_posts.get(&child.id).copied().unwrap_or(false) {
-                                collect_visible(&child.id, children_map, expanded_posts, result);
-                            }
-                        }
-                    }
-                }
-
-                collect_visible(
-                    &root.id,
-                    &children_map,
-                    &detail_state.modal_expanded_posts,
-                    &mut flattened_posts,
-                );
-
-                // Map selected_idx to post_id (accounting for root at index 0)
-                if selected_idx > 0 && selected_idx <= flattened_posts.len() {
-                    flattened_posts[selected_idx - 1]
-                } else {
-                    return Ok(()); // Invalid index
-                }
-            } else {
-                return Ok(()); // Root not found
-            }
+        let post_id = match detail_state.modal_thread().and_then(|thread| {
+            thread.post_at(selected_idx, &detail_state.modal_expanded_posts)
+        }) {
+            Some(post) => post.id,
+            None => return Ok(()),
         };
 
         // Find the post to get its current vote state
@@ -3827,90 +8247,63 @@ impl App {
         }
     }
 
-    /// Close full post modal
-    pub fn close_full_post_modal(&mut self) {
-        // Since we're using modal-first approach, closing the modal means
-        // closing the entire post detail view and returning to feed
-        self.close_post_detail();
-    }
-    
+    /// Close full post modal
+    pub fn close_full_post_modal(&mut self) {
+        // Since we're using modal-first approach, closing the modal means
+        // closing the entire post detail view and returning to feed
+        self.close_post_detail();
+    }
+    
+    /// Move Tab focus through the related footer; past either end it returns to the thread
+    pub fn cycle_related_focus(&mut self, forward: bool) {
+        let Some(detail_state) = &mut self.post_detail_state else {
+            return;
+        };
+        let count = detail_state.related.len();
+        if count == 0 {
+            return;
+        }
+        detail_state.related_selected = match (detail_state.related_selected, forward) {
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(i), true) if i + 1 < count => Some(i + 1),
+            (Some(i), false) if i > 0 => Some(i - 1),
+            _ => None,
+        };
+    }
+
+    /// The related post currently focused in the full post modal, if any
+    pub fn selected_related_post_id(&self) -> Option<Uuid> {
+        let detail_state = self.post_detail_state.as_ref()?;
+        detail_state
+            .related
+            .get(detail_state.related_selected?)
+            .map(|post| post.id)
+    }
+
+    /// Replace the open thread with the focused related post
+    pub async fn open_related_post(&mut self) -> Result<()> {
+        let Some(post_id) = self.selected_related_post_id() else {
+            return Ok(());
+        };
+        // Keep the feed position from the thread the user first opened
+        let previous_position = self
+            .post_detail_state
+            .as_ref()
+            .and_then(|state| state.previous_feed_position);
+        self.open_post_detail(post_id).await?;
+        if let Some(detail_state) = &mut self.post_detail_state {
+            detail_state.previous_feed_position = previous_position;
+        }
+        Ok(())
+    }
+
     /// Get the currently selected post in the modal based on flattened tree
     fn get_selected_post_in_modal(&self) -> Option<Post> {
-        let detail_state = self.post_detail_state.as_ref()?;
-        let selected_idx = detail_state.modal_list_state.selected()?;
-        
-        if selected_idx == 0 {
-            // Root post
-            let root_id = detail_state.full_post_modal_id?;
-            if let Some(post) = &detail_state.post {
-                if post.id == root_id {
-                    return Some(post.clone());
-                }
-            }
-            return detail_state.replies.iter().find(|r| r.id == root_id).cloned();
-        }
-        
-        // Find in flattened tree (selected_idx - 1 because root is index 0)
-        let root_id = detail_state.full_post_modal_id?;
-        let root_post = if let Some(post) = &detail_state.post {
-            if post.id == root_id {
-                post.clone()
-            } else {
-                detail_state.replies.iter().find(|r| r.id == root_id)?.clone()
-            }
-        } else {
-            return None;
-        };
-        
-        // Filter replies to descendants
-        let modal_replies: Vec<Post> = detail_state.replies.iter()
-            .filter(|reply| {
-                reply.id != root_post.id && is_descendant_of_post(reply, &root_post.id, &detail_state.replies)
-            })
+        self.post_detail_state
+            .as_ref()?
+            .selected_modal_post()
             .cloned()
-            .collect();
-        
-        // Build and flatten tree
-        let flattened = self.flatten_modal_tree(&root_post, &modal_replies, &detail_state.modal_expanded_posts);
-        
-        // Get the post at selected_idx - 1
-        flattened.get(selected_idx - 1).cloned()
-    }
-    
-    /// Flatten modal tree for selection
-    fn flatten_modal_tree(&self, root: &Post, replies: &[Post], expanded: &std::collections::HashMap<Uuid, bool>) -> Vec<Post> {
-        use std::collections::HashMap;
-        
-        let mut children_map: HashMap<Uuid, Vec<&Post>> = HashMap::new();
-        for reply in replies {
-            if let Some(parent_id) = reply.parent_post_id {
-                children_map.entry(parent_id).or_default().push(reply);
-            }
-        }
-        
-        let mut result = Vec::new();
-        
-        fn collect_visible(
-            post_id: &Uuid,
-            children_map: &HashMap<Uuid, Vec<&Post>>,
-            expanded: &HashMap<Uuid, bool>,
-            result: &mut Vec<Post>,
-        ) {
-            if let Some(children) = children_map.get(post_id) {
-                for child in children {
-                    result.push((*child).clone());
-                    if expanded.get(&child.id).copied().unwrap_or(false) {
-                        collect_visible(&child.id, children_map, expanded, result);
-                    }
-                }
-            }
-        }
-        
-        if expanded.get(&root.id).copied().unwrap_or(false) {
-            collect_visible(&root.id, &children_map, expanded, &mut result);
-        }
-        
-        result
     }
 
     /// Navigate down in modal
@@ -3945,101 +8338,9 @@ impl App {
 
     /// Calculate the maximum index for modal navigation
     fn calculate_modal_max_index_for_state(detail_state: &PostDetailState) -> usize {
-        if let Some(modal_post_id) = detail_state.full_post_modal_id {
-            // Find the modal root post
-            let modal_root = if let Some(post) = &detail_state.post {
-                if post.id == modal_post_id {
-                    Some(post.clone())
-                } else {
-                    detail_state
-                        .replies
-                        .iter()
-                        .find(|r| r.id == modal_post_id)
-                        .cloned()
-                }
-            } else {
-                None
-            };
-
-            if let Some(root) = modal_root {
-                // Filter replies to descendants
-                let modal_replies: Vec<Post> = detail_state
-                    .replies
-                    .iter()
-                    .filter(|reply| {
-                        if reply.id == root.id {
-                            return false;
-                        }
-                        let mut current_parent = reply.parent_post_id;
-                        while let Some(parent_id) = current_parent {
-                            if parent_id == root.id {
-                                return true;
-                            }
-                            current_parent = detail_state
-                                .replies
-                                .iter()
-                                .find(|r| r.id == parent_id)
-                                .and_then(|r| r.parent_post_id);
-                        }
-                        false
-                    })
-                    .cloned()
-                    .collect();
-
-                // Check if root is expanded
-                let root_is_expanded = detail_state
-                    .modal_expanded_posts
-                    .get(&root.id)
-                    .copied()
-                    .unwrap_or(false);
-
-                if !root_is_expanded || modal_replies.is_empty() {
-                    // Only root post is visible (index 0)
-                    return 0;
-                }
-
-                // Build children map and count visible items
-                use std::collections::HashMap;
-                let mut children_map: HashMap<Uuid, Vec<&Post>> = HashMap::new();
-                for reply in &modal_replies {
-                    if let Some(parent_id) = reply.parent_post_id {
-                        children_map
-                            .entry(parent_id)
-                            .or_default()
-                            .push(reply);
-                    }
-                }
-
-                // Count visible flattened items
-                let mut visible_count = 0;
-                fn count_visible(
-                    post_id: &Uuid,
-                    children_map: &HashMap<Uuid, Vec<&Post>>,
-                    expanded_posts: &std::collections::HashMap<Uuid, bool>,
-                    count: &mut usize,
-                ) {
-                    if let Some(children) = children_map.get(post_id) {
-                        for child in children {
-                            *count += 1;
-                            if expanded_posts.get(&child.id).copied().unwrap_or(false) {
-                                count_visible(&child.id, children_map, expanded_posts, count);
-                            }
-                        }
-                    }
-                }
-
-                count_visible(
-                    &root.id,
-                    &children_map,
-                    &detail_state.modal_expanded_posts,
-                    &mut visible_count,
-                );
-
-                // Max index is visible_count (root is 0, replies are 1..=visible_count)
-                return visible_count;
-            }
-        }
-        0
+        detail_state
+            .modal_thread()
+            .map_or(0, |thread| thread.max_index(&detail_state.modal_expanded_posts))
     }
 
     /// Toggle expansion in modal
@@ -4048,118 +8349,14 @@ impl App {
             if detail_state.show_full_post_modal {
                 let selected_idx = detail_state.modal_list_state.selected().unwrap_or(0);
 
-                // Index 0 is the root post - toggle it to show/hide direct children
-                if selected_idx == 0 {
-                    if let Some(modal_post_id) = detail_state.full_post_modal_id {
-                        let is_expanded = detail_state
-                            .modal_expanded_posts
-                            .get(&modal_post_id)
-                            .copied()
-                            .unwrap_or(false);
-                        detail_state
-                            .modal_expanded_posts
-                            .insert(modal_post_id, !is_expanded);
-                    }
-                    return;
-                }
-
-                // Find the modal root post and build the flattened tree
-                if let Some(modal_post_id) = detail_state.full_post_modal_id {
-                    let modal_root = if let Some(post) = &detail_state.post {
-                        if post.id == modal_post_id {
-                            Some(post.clone())
-                        } else {
-                            detail_state
-                                .replies
-                                .iter()
-                                .find(|r| r.id == modal_post_id)
-                                .cloned()
-                        }
-                    } else {
-                        None
-                    };
-
-                    if let Some(root) = modal_root {
-                        // Filter replies to descendants (excluding the root itself)
-                        let modal_replies: Vec<Post> = detail_state
-                            .replies
-                            .iter()
-                            .filter(|reply| {
-                                if reply.id == root.id {
-                                    return false; // Exclude the root itself
-                                }
-                                let mut current_parent = reply.parent_post_id;
-                                while let Some(parent_id) = current_parent {
-                                    if parent_id == root.id {
-                                        return true;
-                                    }
-                                    current_parent = detail_state
-                                        .replies
-                                        .iter()
-                                        .find(|r| r.id == parent_id)
-                                        .and_then(|r| r.parent_post_id);
-                                }
-                                false
-                            })
-                            .cloned()
-                            .collect();
-
-                        // Build tree and flatten to map index to post
-                        use std::collections::HashMap;
-                        let mut children_map: HashMap<Uuid, Vec<&Post>> = HashMap::new();
-                        for reply in &modal_replies {
-                            if let Some(parent_id) = reply.parent_post_id {
-                                children_map
-                                    .entry(parent_id)
-                                    .or_default()
-                                    .push(reply);
-                            }
-                        }
-
-                        // Flatten the tree respecting current expansion state
-                        let mut flattened_posts = vec![];
-                        fn collect_visible(
-                            post_id: &Uuid,
-                            children_map: &HashMap<Uuid, Vec<&Post>>,
-                            expanded_posts: &HashMap<Uuid, bool>,
-                            result: &mut Vec<Uuid>,
-                        ) {
-                            if let Some(children) = children_map.get(post_id) {
-                                for child in children {
-                                    result.push(child.id);
-                                    // Only recurse if this post is expanded
-                                    if expanded_posts.get(&child.id).copied().unwrap_or(false) {
-                                        collect_visible(
-                                            &child.id,
-                                            children_map,
-                                            expanded_posts,
-                                            result,
-                                        );
-                                    }
-                                }
-                            }
-                        }
-
-                        collect_visible(
-                            &root.id,
-                            &children_map,
-                            &detail_state.modal_expanded_posts,
-                            &mut flattened_posts,
-                        );
-
-                        // Find the post at selected_idx (accounting for root at index 0)
-                        if selected_idx > 0 && selected_idx <= flattened_posts.len() {
-                            let post_id = flattened_posts[selected_idx - 1];
-                            let is_expanded = detail_state
-                                .modal_expanded_posts
-                                .get(&post_id)
-                                .copied()
-                                .unwrap_or(false);
-                            detail_state
-                                .modal_expanded_posts
-                                .insert(post_id, !is_expanded);
-                        }
-                    }
+                // Index 0 is the root post - toggling it shows/hides its direct children
+                let post_id = detail_state.modal_thread().and_then(|thread| {
+                    thread
+                        .post_at(selected_idx, &detail_state.modal_expanded_posts)
+                        .map(|post| post.id)
+                });
+                if let Some(post_id) = post_id {
+                    thread_tree::toggle(&mut detail_state.modal_expanded_posts, post_id);
                 }
             }
         }
@@ -4172,123 +8369,15 @@ impl App {
                 return;
             }
 
-            // Get the modal root post
-            let modal_root_id = match detail_state.full_post_modal_id {
-                Some(id) => id,
-                None => return,
-            };
-
-            // Check if the reply_id is the root itself
-            if reply_id == modal_root_id {
-                detail_state.modal_list_state.select(Some(0));
-                return;
-            }
-
-            // Find the modal root post
-            let modal_root = if let Some(post) = &detail_state.post {
-                if post.id == modal_root_id {
-                    Some(post.clone())
-                } else {
-                    detail_state
-                        .replies
-                        .iter()
-                        .find(|r| r.id == modal_root_id)
-                        .cloned()
-                }
-            } else {
-                None
-            };
-
-            let root = match modal_root {
-                Some(r) => r,
-                None => return,
-            };
-
-            // Filter replies to descendants of modal root
-            let modal_replies: Vec<Post> = detail_state
-                .replies
-                .iter()
-                .filter(|reply| {
-                    if reply.id == root.id {
-                        return false;
-                    }
-                    let mut current_parent = reply.parent_post_id;
-                    while let Some(parent_id) = current_parent {
-                        if parent_id == root.id {
-                            return true;
-                        }
-                        current_parent = detail_state
-                            .replies
-                            .iter()
-                            .find(|r| r.id == parent_id)
-                            .and_then(|r| r.parent_post_id);
-                    }
-                    false
-                })
-                .cloned()
-                .collect();
-
-            // Build flattened tree to find the index
-            use std::collections::HashMap;
-            let mut children_map: HashMap<Uuid, Vec<&Post>> = HashMap::new();
-            for reply in &modal_replies {
-                if let Some(parent_id) = reply.parent_post_id {
-                    children_map
-                        .entry(parent_id)
-                        .or_default()
-                        .push(reply);
-                }
-            }
-
-            // Expand all ancestors of the target reply so it's visible
-            let mut ancestors = vec![];
-            let mut current_parent = modal_replies
-                .iter()
-                .find(|r| r.id == reply_id)
-                .and_then(|r| r.parent_post_id);
-            
-            while let Some(parent_id) = current_parent {
-                ancestors.push(parent_id);
-                current_parent = modal_replies
-                    .iter()
-                    .find(|r| r.id == parent_id)
-                    .and_then(|r| r.parent_post_id);
-            }
-            
-            // Expand root and all ancestors
-            detail_state.modal_expanded_posts.insert(root.id, true);
-            for ancestor_id in ancestors {
-                detail_state.modal_expanded_posts.insert(ancestor_id, true);
-            }
-
-            // Flatten the tree with current expansion state
-            let mut flattened_posts = vec![];
-            fn collect_visible(
-                post_id: &Uuid,
-                children_map: &HashMap<Uuid, Vec<&Post>>,
-                expanded_posts: &HashMap<Uuid, bool>,
-                result: &mut Vec<Uuid>,
-            ) {
-                if let Some(children) = children_map.get(post_id) {
-                    for child in children {
-                        result.push(child.id);
-                        if expanded_posts.get(&child.id).copied().unwrap_or(false) {
-                            collect_visible(&child.id, children_map, expanded_posts, result);
-                        }
-                    }
-                }
-            }
-
-            collect_visible(
-                &root.id,
-                &children_map,
-                &detail_state.modal_expanded_posts,
-                &mut flattened_posts,
-            );
+            // Expand the root and every ancestor of the reply so it's visible
+            let mut expanded = std::mem::take(&mut detail_state.modal_expanded_posts);
+            let index = detail_state
+                .modal_thread()
+                .and_then(|thread| thread.reveal(reply_id, &mut expanded));
+            detail_state.modal_expanded_posts = expanded;
 
-            // Find the index of the target reply (add 1 because root is at index 0)
-            if let Some(pos) = flattened_posts.iter().position(|&id| id == reply_id) {
-                detail_state.modal_list_state.select(Some(pos + 1));
+            if let Some(index) = index {
+                detail_state.modal_list_state.select(Some(index));
             }
         }
     }
@@ -4331,6 +8420,13 @@ impl App {
     pub async fn load_user_profile_view(&mut self, user_id: String) -> Result<()> {
         match self.api_client.get_user_profile_view(user_id.clone()).await {
             Ok(profile_data) => {
+                // Recent posts are a nice-to-have; show the profile even if they fail
+                let recent_posts = self
+                    .api_client
+                    .get_user_posts(user_id.clone(), PROFILE_VIEW_RECENT_POSTS, 0)
+                    .await
+                    .unwrap_or_default();
+
                 self.user_profile_view = Some(UserProfileViewState {
                     user_id: profile_data.id,
                     username: profile_data.username,
@@ -4339,6 +8435,7 @@ impl App {
                     follower_count: profile_data.follower_count,
                     following_count: profile_data.following_count,
                     post_count: profile_data.post_count,
+                    recent_posts,
                     relationship: match profile_data.relationship {
                         fido_types::RelationshipStatus::Self_ => RelationshipStatus::Self_,
                         fido_types::RelationshipStatus::MutualFriends => {
@@ -4350,6 +8447,10 @@ impl App {
                         }
                         fido_types::RelationshipStatus::None => RelationshipStatus::None,
                     },
+                    badges: profile_data.badges,
+                    status: profile_data.status,
+                    moved_to: profile_data.moved_to,
+                    karma: profile_data.karma,
                     loading: false,
                     error: None,
                 });
@@ -4406,6 +8507,9 @@ impl App {
 
     /// Close user search modal
     pub fn close_user_search_modal(&mut self) {
+        self.task_runner.cancel(TaskKind::UserSearch);
+        self.user_search_state.search_due = None;
+        self.user_search_state.loading = false;
         self.user_search_state.show_modal = false;
         self.user_search_state.search_query.clear();
         self.user_search_state.search_results.clear();
@@ -4413,38 +8517,100 @@ impl App {
         self.input_mode = InputMode::Navigation;
     }
 
-    /// Search users by query
-    pub async fn search_users(&mut self) -> Result<()> {
+    /// React to a change in the search query.
+    ///
+    /// Cached queries are answered immediately; otherwise the request is
+    /// debounced so fast typing doesn't send one search per keystroke.
+    pub fn queue_user_search(&mut self) {
         let query = self.user_search_state.search_query.clone();
 
+        // Whatever was pending or in flight is for an older query
+        self.task_runner.cancel(TaskKind::UserSearch);
+        self.user_search_state.search_due = None;
+
         // Require at least 2 characters
         if query.len() < 2 {
             self.user_search_state.search_results.clear();
-            return Ok(());
+            self.user_search_state.loading = false;
+            return;
+        }
+
+        if let Some(results) = self.user_search_state.cache.get(&query) {
+            self.user_search_state.search_results = results;
+            self.user_search_state.selected_index = 0;
+            self.user_search_state.loading = false;
+            self.user_search_state.error = None;
+            return;
+        }
+
+        // Keep showing the previous results until the new ones arrive to avoid flicker
+        self.user_search_state.loading = true;
+        self.user_search_state.search_due = Some(Instant::now() + USER_SEARCH_DEBOUNCE);
+    }
+
+    /// Send the debounced user search once its quiet period has elapsed (called once per frame)
+    pub fn tick_user_search(&mut self) {
+        match self.user_search_state.search_due {
+            Some(due) if Instant::now() >= due => {
+                self.user_search_state.search_due = None;
+                self.spawn_search_users();
+            }
+            _ => {}
         }
+    }
 
+    /// Search users by the current query on a background task
+    fn spawn_search_users(&mut self) {
+        let query = self.user_search_state.search_query.clone();
         self.user_search_state.loading = true;
         self.user_search_state.error = None;
 
-        match self.api_client.search_users(query).await {
+        // Search-as-you-type should give up quickly rather than hang on a slow server
+        let client = self.api_client.with_timeout(USER_SEARCH_TIMEOUT);
+        self.task_runner.spawn(TaskKind::UserSearch, async move {
+            let result = client
+                .search_users(query.clone())
+                .await
+                .map(|results| {
+                    results
+                        .into_iter()
+                        .map(|r| UserSearchResult {
+                            id: r.id,
+                            username: r.username,
+                        })
+                        .collect()
+                })
+                .map_err(|e| e.to_string());
+            TaskResult::UserSearchCompleted { query, result }
+        });
+    }
+
+    fn apply_user_search_result(
+        &mut self,
+        query: String,
+        result: Result<Vec<UserSearchResult>, String>,
+    ) {
+        if let Ok(results) = &result {
+            self.user_search_state
+                .cache
+                .insert(query.clone(), results.clone());
+        }
+
+        // Results for a query the user has already typed past are stale
+        if !self.user_search_state.show_modal || query != self.user_search_state.search_query {
+            return;
+        }
+
+        match result {
             Ok(results) => {
-                self.user_search_state.search_results = results
-                    .into_iter()
-                    .map(|r| UserSearchResult {
-                        id: r.id,
-                        username: r.username,
-                    })
-                    .collect();
+                self.user_search_state.search_results = results;
                 self.user_search_state.selected_index = 0;
-                self.user_search_state.loading = false;
             }
             Err(e) => {
                 self.user_search_state.error = Some(format!("Search failed: {}", e));
-                self.user_search_state.loading = false;
             }
         }
-
-        Ok(())
+        self.user_search_state.loading = false;
     }
 
     /// Navigate user search results
@@ -4489,8 +8655,8 @@ impl App {
 
             // Use existing helper method to get the post that would be deleted
             if let Some(deletable_post) = detail_state.get_deletable_post() {
-                // Only show confirmation if user owns the post
-                if current_user_id == Some(deletable_post.author_id) {
+                // Only show confirmation if user owns the post (tombstones are already deleted)
+                if current_user_id == Some(deletable_post.author_id) && !deletable_post.is_deleted {
                     detail_state.show_delete_confirmation = true;
                 }
             }
@@ -4530,7 +8696,12 @@ impl App {
         }
 
         match self.api_client.delete_post(post_id).await {
-            Ok(_) => {
+            Ok(response) => {
+                // Posts with replies are kept as "[deleted]" placeholders server-side
+                let tombstoned = response
+                    .get("tombstoned")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
                 if is_reply {
                     // Deleted a reply - reload the post detail to refresh replies
                     if let Some(main_id) = main_post_id {
@@ -4538,15 +8709,22 @@ impl App {
                             detail_state.show_delete_confirmation = false;
                         }
                         self.load_post_detail(main_id).await?;
-                        if let Some(detail_state) = &mut self.post_detail_state {
-                            detail_state.message = Some(("✓ Reply deleted successfully".to_string(), std::time::Instant::now()));
-                        }
+                        self.toasts.success("✓ Reply deleted");
                     }
                 } else {
                     // Deleted the main post - close detail view and remove from feed
                     self.close_post_detail();
-                    if let Some(index) = self.posts_state.posts.iter().position(|p| p.id == post_id)
-                    {
+                    let position = self.posts_state.posts.iter().position(|p| p.id == post_id);
+                    if let (true, Some(index)) = (tombstoned, position) {
+                        // Keep the thread reachable through its placeholder
+                        let post = &mut self.posts_state.posts[index];
+                        post.is_deleted = true;
+                        post.content.clear();
+                        post.hashtags.clear();
+                        post.upvotes = 0;
+                        post.downvotes = 0;
+                        post.user_vote = None;
+                    } else if let Some(index) = position {
                         self.posts_state.posts.remove(index);
                         if self.posts_state.posts.is_empty() {
                             self.posts_state.list_state.select(None);
@@ -4556,7 +8734,7 @@ impl App {
                                 .select(Some(self.posts_state.posts.len() - 1));
                         }
                     }
-                    self.posts_state.message = Some(("✓ Post deleted successfully".to_string(), std::time::Instant::now()));
+                    self.toasts.success("✓ Post deleted");
                 }
             }
             Err(e) => {
@@ -4589,26 +8767,321 @@ impl Default for App {
     }
 }
 
-/// Check if a reply is a descendant of a given post
-fn is_descendant_of_post(reply: &Post, ancestor_id: &Uuid, all_replies: &[Post]) -> bool {
-    let mut current_parent = reply.parent_post_id;
-    
-    while let Some(parent_id) = current_parent {
-        if parent_id == *ancestor_id {
-            return true;
+/// Fold runs of consecutive posts with the same text (reposts, copy-paste
+/// duplicates) into their first post, returning the remaining feed and the
+/// hidden posts keyed by the entry that stands in for them
+fn collapse_similar_posts(
+    posts: Vec<Post>,
+) -> (Vec<Post>, std::collections::HashMap<Uuid, Vec<Post>>) {
+    let mut kept: Vec<Post> = Vec::with_capacity(posts.len());
+    let mut similar: std::collections::HashMap<Uuid, Vec<Post>> =
+        std::collections::HashMap::new();
+    let mut last_key: Option<String> = None;
+    for post in posts {
+        let key = similarity_key(&post.content);
+        let is_repeat = !post.is_deleted && !key.is_empty() && last_key.as_ref() == Some(&key);
+        if is_repeat {
+            if let Some(entry) = kept.last() {
+                similar.entry(entry.id).or_default().push(post);
+                continue;
+            }
+        }
+        last_key = (!post.is_deleted).then_some(key);
+        kept.push(post);
+    }
+    (kept, similar)
+}
+
+/// Post text reduced to lowercase words, so case, punctuation and spacing
+/// differences still count as the same post
+fn similarity_key(content: &str) -> String {
+    content
+        .split(|c: char| !c.is_alphanumeric() && c != '#' && c != '@')
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Fetch the feed for the given filter (runs without access to the app)
+async fn fetch_posts(
+    client: ApiClient,
+    filter: PostFilter,
+    sort_order: String,
+    max_posts: i32,
+) -> std::result::Result<Vec<Post>, String> {
+    let result = match filter {
+        PostFilter::All => {
+            client
+                .get_posts(Some(max_posts), Some(sort_order), None, None)
+                .await
+        }
+        PostFilter::Hashtag(tag) => {
+            client
+                .get_posts(Some(max_posts), Some(sort_order), Some(tag), None)
+                .await
+        }
+        PostFilter::User(user) => {
+            client
+                .get_posts(Some(max_posts), Some(sort_order), None, Some(user))
+                .await
+        }
+        PostFilter::Multi { hashtags, users } => {
+            // Fetch posts for each filter and combine them
+            let mut all_posts = Vec::new();
+
+            // Fetch for each hashtag
+            for hashtag in hashtags {
+                if let Ok(posts) = client
+                    .get_posts(Some(max_posts), Some(sort_order.clone()), Some(hashtag), None)
+                    .await
+                {
+                    all_posts.extend(posts);
+                }
+            }
+
+            // Fetch for each user
+            for username in users {
+                if let Ok(posts) = client
+                    .get_posts(Some(max_posts), Some(sort_order.clone()), None, Some(username))
+                    .await
+                {
+                    all_posts.extend(posts);
+                }
+            }
+
+            // Remove duplicates by post ID
+            all_posts.sort_by(|a, b| b.created_at.cmp(&a.created_at)); // Sort by newest first
+            all_posts.dedup_by(|a, b| a.id == b.id);
+
+            // Limit to max_posts
+            all_posts.truncate(max_posts as usize);
+
+            Ok(all_posts)
+        }
+        PostFilter::Channel(name) => client.get_channel_posts(&name, max_posts, sort_order).await,
+        PostFilter::Reviews => client.get_review_requests(max_posts, sort_order).await,
+        PostFilter::Jobs(work_mode) => client.get_jobs(work_mode, max_posts, sort_order).await,
+        // Filled in from the local queue when the result is applied
+        PostFilter::Later => Ok(Vec::new()),
+    };
+
+    result.map_err(|e| e.to_string())
+}
+
+/// Fetch a page of the Profile tab's list picked by `tab`
+async fn fetch_profile_posts(
+    client: ApiClient,
+    user_id: String,
+    tab: ProfilePostsTab,
+    offset: i32,
+) -> crate::api::ApiResult<Vec<Post>> {
+    match tab {
+        ProfilePostsTab::Posts => {
+            client
+                .get_user_posts(user_id, PROFILE_POSTS_PAGE_SIZE, offset)
+                .await
+        }
+        ProfilePostsTab::Replies => {
+            client
+                .get_user_replies(user_id, PROFILE_POSTS_PAGE_SIZE, offset)
+                .await
+        }
+        ProfilePostsTab::Upvoted => {
+            client
+                .get_upvoted_posts(user_id, PROFILE_POSTS_PAGE_SIZE, offset)
+                .await
         }
-        current_parent = all_replies.iter()
-            .find(|r| r.id == parent_id)
-            .and_then(|r| r.parent_post_id);
     }
-    
-    false
+}
+
+/// Fetch and parse the DM conversation list
+async fn fetch_conversations(client: ApiClient) -> std::result::Result<Vec<Conversation>, String> {
+    let convos = client
+        .get_conversations()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Parse conversations from JSON
+    Ok(convos
+        .iter()
+        .filter_map(|c| {
+            Some(Conversation {
+                other_user_id: c.get("other_user_id")?.as_str()?.parse().ok()?,
+                other_username: c.get("other_username")?.as_str()?.to_string(),
+                last_message: c.get("last_message")?.as_str()?.to_string(),
+                last_message_time: c.get("last_message_time")?.as_str()?.parse().ok()?,
+                unread_count: c.get("unread_count")?.as_i64()? as i32,
+                pinned: c.get("pinned").and_then(|v| v.as_bool()).unwrap_or(false),
+            })
+        })
+        .collect())
+}
+
+/// A post as one sentence for announcing
+fn describe_post(post: &Post) -> String {
+    if post.is_deleted {
+        return "Deleted post".to_string();
+    }
+    format!(
+        "{}: {}. {} up, {} down, {} {}",
+        post.author_username,
+        post.content,
+        post.upvotes,
+        post.downvotes,
+        post.reply_count,
+        if post.reply_count == 1 { "reply" } else { "replies" }
+    )
+}
+
+/// A post as a Markdown blockquote, attributed and linked
+fn markdown_quote(post: &Post, permalink: &str) -> String {
+    let mut quote: String = post
+        .content
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">\n".to_string()
+            } else {
+                format!("> {}\n", line)
+            }
+        })
+        .collect();
+    quote.push_str(&format!(
+        ">\n> — [@{}]({}), {}\n",
+        post.author_username,
+        permalink,
+        post.created_at.format("%Y-%m-%d")
+    ));
+    quote
+}
+
+/// "1 post", "3 posts"
+fn plural_posts(count: usize) -> String {
+    format!("{} post{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// `hour` moved by `delta` hours on a 24-hour clock
+fn shift_hour(hour: u8, delta: i8) -> u8 {
+    (i16::from(hour) + i16::from(delta)).rem_euclid(24) as u8
+}
+
+/// Language code from a POSIX locale such as `pt_BR.UTF-8`; `None` for the
+/// C/POSIX locale or anything unparseable
+fn language_from_locale(locale: &str) -> Option<String> {
+    let language = locale.split(['_', '.', '@']).next()?.to_lowercase();
+    let valid = (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_alphabetic());
+    valid.then_some(language)
+}
+
+/// Language posts are translated into: the terminal's locale, else English
+fn preferred_language() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|locale| language_from_locale(&locale))
+        .unwrap_or_else(|| "en".to_string())
+}
+
+/// Move the user's answer on an event to `rsvp`, keeping the counts in step
+fn apply_rsvp(event: &mut fido_types::EventDetails, rsvp: Option<fido_types::RsvpStatus>) {
+    use fido_types::RsvpStatus;
+    for (status, delta) in [(event.my_rsvp, -1), (rsvp, 1)] {
+        match status {
+            Some(RsvpStatus::Going) => event.going_count = (event.going_count + delta).max(0),
+            Some(RsvpStatus::Interested) => {
+                event.interested_count = (event.interested_count + delta).max(0)
+            }
+            None => {}
+        }
+    }
+    event.my_rsvp = rsvp;
+}
+
+/// A new post that is really one of the special post types, written as a
+/// command on its first line
+#[derive(Debug)]
+enum PostCommand {
+    Event(fido_types::CreateEventRequest),
+    Review(fido_types::CreateReviewRequest),
+}
+
+fn parse_post_command(content: &str) -> Option<Result<PostCommand, String>> {
+    if let Some(event) = parse_event_command(content) {
+        return Some(event.map(PostCommand::Event));
+    }
+    parse_review_command(content).map(|review| review.map(PostCommand::Review))
+}
+
+/// The `|`-separated fields after `/name` on the first line, and the lines
+/// after it; `None` when the post doesn't start with that command
+fn command_fields<'a>(content: &'a str, name: &str) -> Option<(Vec<&'a str>, &'a str)> {
+    let (first_line, rest) = content.split_once('\n').unwrap_or((content, ""));
+    let spec = first_line.trim().strip_prefix('/')?.strip_prefix(name)?;
+    if !spec.is_empty() && !spec.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((spec.split('|').map(str::trim).collect(), rest.trim()))
+}
+
+/// A new post starting with `/event Title | YYYY-MM-DD HH:MM | Location`
+/// becomes an event, with the lines after it as the description. The time
+/// is local; with no description the title doubles as the post's text.
+/// `None` when the post isn't an event.
+fn parse_event_command(content: &str) -> Option<Result<fido_types::CreateEventRequest, String>> {
+    let (fields, description) = command_fields(content, "event")?;
+    let &[title, when, location] = fields.as_slice() else {
+        return Some(Err("Use /event Title | YYYY-MM-DD HH:MM | Location".to_string()));
+    };
+    if title.is_empty() || location.is_empty() {
+        return Some(Err("Events need a title and a location or link".to_string()));
+    }
+    let Ok(naive) = chrono::NaiveDateTime::parse_from_str(when, "%Y-%m-%d %H:%M") else {
+        return Some(Err(format!("'{}' isn't a YYYY-MM-DD HH:MM time", when)));
+    };
+    let Some(starts_at) = naive.and_local_timezone(chrono::Local).earliest() else {
+        return Some(Err(format!("{} doesn't exist in your timezone", when)));
+    };
+    Some(Ok(fido_types::CreateEventRequest {
+        title: title.to_string(),
+        starts_at: starts_at.with_timezone(&chrono::Utc),
+        location: location.to_string(),
+        content: if description.is_empty() { title } else { description }.to_string(),
+    }))
+}
+
+/// `/review URL | language` asks for a code review, with the lines after it
+/// saying what to look at. The server adds the #reviewrequested and
+/// language hashtags.
+fn parse_review_command(content: &str) -> Option<Result<fido_types::CreateReviewRequest, String>> {
+    let (fields, description) = command_fields(content, "review")?;
+    let &[url, language] = fields.as_slice() else {
+        return Some(Err("Use /review URL | language".to_string()));
+    };
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Some(Err("Review requests link to a repository or pull request".to_string()));
+    }
+    let language = language.trim_start_matches('#');
+    if language.is_empty() {
+        return Some(Err("Say which language to review, like rust or python".to_string()));
+    }
+    Some(Ok(fido_types::CreateReviewRequest {
+        url: url.to_string(),
+        language: language.to_lowercase(),
+        content: if description.is_empty() { url } else { description }.to_string(),
+    }))
 }
 
 /// Categorize error messages for better user feedback
 fn categorize_error(error_str: &str) -> String {
     let error_lower = error_str.to_lowercase();
 
+    // Rate limiting: the countdown toast retries on its own
+    if error_lower.contains("too many requests") {
+        return "Rate Limited: Too many requests. Retrying automatically once the server allows it".to_string();
+    }
+
     // Network errors
     if error_lower.contains("connection")
         || error_lower.contains("timeout")
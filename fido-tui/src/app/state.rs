@@ -1,4 +1,4 @@
-use fido_types::{Post, User, UserProfile};
+use fido_types::{Post, ReplyPolicy, User, UserProfile, WorkMode};
 
 use ratatui::widgets::ListState;
 use std::time::Instant;
@@ -6,6 +6,8 @@ use tui_textarea::TextArea;
 use uuid::Uuid;
 
 use crate::api::ApiClient;
+use crate::thread_tree::ThreadTree;
+use crate::ui::text_input::TextInput;
 
 /// Get platform-appropriate modifier key name for display
 /// Returns "Cmd" on macOS, "Ctrl" on other platforms
@@ -19,6 +21,28 @@ pub fn get_modifier_key_name() -> &'static str {
     "Ctrl"
 }
 
+/// Input for the bio, which holds up to 160 characters
+pub fn bio_input() -> TextInput {
+    TextInput::new().with_max_chars(160)
+}
+
+/// Input for a hashtag name, without the `#`
+pub fn hashtag_input() -> TextInput {
+    TextInput::new().with_filter(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Input for a username
+pub fn username_input() -> TextInput {
+    TextInput::new().with_max_chars(50)
+}
+
+/// Input for the number of posts shown, 1 to 3 digits
+pub fn max_posts_input() -> TextInput {
+    TextInput::new()
+        .with_max_chars(3)
+        .with_filter(|c| c.is_ascii_digit())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputMode {
     Navigation, // Browsing content, shortcuts active
@@ -30,10 +54,58 @@ pub enum SettingsField {
     ColorScheme,
     SortOrder,
     MaxPosts,
+    VotePrivacy,
+    DoNotDisturb,
+    QuietHours,
+    QuietHoursStart,
+    QuietHoursEnd,
+    TabUnreadCounts,
+    Digest,
+    AccessibleLayout,
+    Announcements,
+    ReducedMotion,
+    Timestamps,
+    FeedDensity,
+    PreviewLines,
+    VoteCounts,
+    Karma,
+    PinnedHashtags,
+    Discoverable,
+    /// Local to this machine, applied immediately rather than saved to the server
+    SessionEncryption,
+}
+
+impl SettingsField {
+    /// Name read out when the field is focused
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsField::ColorScheme => "Color scheme",
+            SettingsField::SortOrder => "Sort order",
+            SettingsField::MaxPosts => "Max posts",
+            SettingsField::VotePrivacy => "Vote privacy",
+            SettingsField::DoNotDisturb => "Do not disturb",
+            SettingsField::QuietHours => "Quiet hours",
+            SettingsField::QuietHoursStart => "Quiet hours from",
+            SettingsField::QuietHoursEnd => "Quiet hours until",
+            SettingsField::TabUnreadCounts => "Tab unread counts",
+            SettingsField::Digest => "Digest",
+            SettingsField::AccessibleLayout => "Accessible layout",
+            SettingsField::Announcements => "Announcements",
+            SettingsField::ReducedMotion => "Reduced motion",
+            SettingsField::Timestamps => "Timestamps",
+            SettingsField::FeedDensity => "Feed density",
+            SettingsField::PreviewLines => "Preview lines",
+            SettingsField::VoteCounts => "Vote counts",
+            SettingsField::Karma => "Karma",
+            SettingsField::PinnedHashtags => "Pinned hashtags",
+            SettingsField::Discoverable => "Search engines",
+            SettingsField::SessionEncryption => "Saved sessions",
+        }
+    }
 }
 
 /// Composer mode - determines what type of content is being composed
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ComposerMode {
     NewPost,
     Reply {
@@ -45,6 +117,127 @@ pub enum ComposerMode {
         post_id: Uuid,
     },
     EditBio,
+    /// Status line: optional leading emoji, optional trailing "for 2h"
+    SetStatus,
+    /// Post quoting a DM; published only once the other participant approves
+    CrossPost {
+        message_id: Uuid,
+        other_username: String,
+    },
+    /// Job post, asked for one field at a time
+    NewJob(JobForm),
+}
+
+/// Steps of the guided job composer, in the order they're asked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum JobStep {
+    Title,
+    Company,
+    WorkMode,
+    Link,
+    Description,
+}
+
+impl JobStep {
+    /// What the step asks for, shown above the input
+    pub fn prompt(self) -> &'static str {
+        match self {
+            JobStep::Title => "Job title",
+            JobStep::Company => "Company",
+            JobStep::WorkMode => "Remote or onsite? (r/o)",
+            JobStep::Link => "Link to apply (http/https)",
+            JobStep::Description => "About the role (optional)",
+        }
+    }
+
+    /// Longest answer the server accepts for the step
+    pub fn max_chars(self) -> usize {
+        match self {
+            JobStep::Title | JobStep::Company => 100,
+            JobStep::WorkMode => 10,
+            JobStep::Link => 300,
+            JobStep::Description => 280,
+        }
+    }
+}
+
+/// Answers given so far in the guided job composer
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JobForm {
+    pub step: JobStep,
+    pub title: String,
+    pub company: String,
+    pub work_mode: Option<WorkMode>,
+    pub url: String,
+}
+
+impl JobForm {
+    pub fn new() -> Self {
+        Self {
+            step: JobStep::Title,
+            title: String::new(),
+            company: String::new(),
+            work_mode: None,
+            url: String::new(),
+        }
+    }
+
+    /// Take the answer to the current step and move to the next one. After
+    /// the last step, returns the job to post; with no description the title
+    /// doubles as the post's text.
+    pub fn answer(&mut self, input: &str) -> Result<Option<fido_types::CreateJobRequest>, String> {
+        let input = input.trim();
+        let one_line = input.split_whitespace().collect::<Vec<_>>().join(" ");
+        match self.step {
+            JobStep::Title | JobStep::Company if one_line.is_empty() => {
+                return Err(format!("{} can't be empty", self.step.prompt()));
+            }
+            JobStep::Title => {
+                self.title = one_line;
+                self.step = JobStep::Company;
+            }
+            JobStep::Company => {
+                self.company = one_line;
+                self.step = JobStep::WorkMode;
+            }
+            JobStep::WorkMode => {
+                let work_mode = match one_line.to_lowercase().as_str() {
+                    "r" => Some(WorkMode::Remote),
+                    "o" => Some(WorkMode::Onsite),
+                    other => WorkMode::parse(other),
+                };
+                self.work_mode = Some(work_mode.ok_or("Answer r for remote or o for onsite")?);
+                self.step = JobStep::Link;
+            }
+            JobStep::Link => {
+                if !(one_line.starts_with("https://") || one_line.starts_with("http://")) {
+                    return Err("The link has to start with http:// or https://".to_string());
+                }
+                self.url = one_line;
+                self.step = JobStep::Description;
+            }
+            JobStep::Description => {
+                let Some(work_mode) = self.work_mode else {
+                    return Err("Answer r for remote or o for onsite".to_string());
+                };
+                let content = if input.is_empty() { self.title.clone() } else { input.to_string() };
+                return Ok(Some(fido_types::CreateJobRequest {
+                    title: self.title.clone(),
+                    company: self.company.clone(),
+                    work_mode,
+                    url: self.url.clone(),
+                    content,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Default for JobForm {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Unified composer state using tui-textarea
@@ -52,6 +245,12 @@ pub struct ComposerState {
     pub mode: Option<ComposerMode>,
     pub textarea: TextArea<'static>,
     pub max_chars: usize,
+    /// Server-side draft this new post was saved as or loaded from
+    pub server_draft: Option<Uuid>,
+    /// Post this new post without the author's name (Ctrl+T)
+    pub anonymous: bool,
+    /// Who may reply to this new post (Ctrl+R)
+    pub reply_policy: ReplyPolicy,
 }
 
 impl ComposerState {
@@ -63,6 +262,9 @@ impl ComposerState {
             mode: None,
             textarea,
             max_chars: 280,
+            server_draft: None,
+            anonymous: false,
+            reply_policy: ReplyPolicy::Everyone,
         }
     }
 
@@ -74,8 +276,9 @@ impl ComposerState {
         self.textarea.lines().join("\n")
     }
 
+    /// Length of the content once emoji shortcodes are expanded
     pub fn char_count(&self) -> usize {
-        crate::emoji::count_characters(&self.get_content())
+        crate::emoji::count_submitted_characters(&self.get_content())
     }
 }
 
@@ -92,15 +295,37 @@ pub struct FriendsState {
     pub error: Option<String>,
     pub loading: bool,
     pub return_to_modal_after_profile: bool, // Flag to reopen modal after viewing profile
+    /// Paging for each tab's loaded list
+    pub pages: std::collections::HashMap<SocialTab, SocialListPage>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SocialTab {
     Following,
     Followers,
     MutualFriends,
 }
 
+impl SocialTab {
+    /// Path segment under `/social`
+    pub fn api_list(&self) -> &'static str {
+        match self {
+            SocialTab::Following => "following",
+            SocialTab::Followers => "followers",
+            SocialTab::MutualFriends => "mutual",
+        }
+    }
+}
+
+/// How much of a social list has been fetched
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SocialListPage {
+    /// Search the loaded users were fetched with
+    pub query: String,
+    /// Whether the server may have more beyond the loaded users
+    pub has_more: bool,
+}
+
 /// User information for social lists
 #[derive(Debug, Clone)]
 pub struct UserInfo {
@@ -115,7 +340,7 @@ pub struct HashtagsState {
     pub hashtags: Vec<String>,
     pub show_hashtags_modal: bool,
     pub show_add_hashtag_input: bool,
-    pub add_hashtag_name: String,
+    pub add_hashtag_name: TextInput,
     pub selected_hashtag: usize,
     pub error: Option<String>,
     pub loading: bool,
@@ -131,6 +356,9 @@ pub struct UserSearchState {
     pub selected_index: usize,
     pub loading: bool,
     pub error: Option<String>,
+    /// When the debounced search for the current query should be sent
+    pub search_due: Option<Instant>,
+    pub cache: SearchCache,
 }
 
 #[derive(Debug, Clone)]
@@ -139,6 +367,43 @@ pub struct UserSearchResult {
     pub username: String,
 }
 
+/// Small LRU cache of recent user search results, keyed by query
+pub struct SearchCache {
+    capacity: usize,
+    // Most recently used entry is at the back
+    entries: Vec<(String, Vec<UserSearchResult>)>,
+}
+
+impl SearchCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Look up a query, marking it as most recently used
+    pub fn get(&mut self, query: &str) -> Option<Vec<UserSearchResult>> {
+        let index = self.entries.iter().position(|(q, _)| q == query)?;
+        let entry = self.entries.remove(index);
+        let results = entry.1.clone();
+        self.entries.push(entry);
+        Some(results)
+    }
+
+    /// Store results for a query, evicting the least recently used entry when full
+    pub fn insert(&mut self, query: String, results: Vec<UserSearchResult>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.retain(|(q, _)| *q != query);
+        if self.entries.len() >= self.capacity {
+            self.entries.remove(0);
+        }
+        self.entries.push((query, results));
+    }
+}
+
 /// Main application state
 pub struct App {
     pub running: bool,
@@ -147,6 +412,7 @@ pub struct App {
     pub auth_state: AuthState,
     pub current_tab: Tab,
     pub posts_state: PostsState,
+    pub channels_state: ChannelsState,
     pub profile_state: ProfileState,
     pub dms_state: DMsState,
     pub settings_state: SettingsState,
@@ -162,6 +428,436 @@ pub struct App {
     pub user_search_state: UserSearchState,
     pub user_profile_view: Option<UserProfileViewState>,
     pub log_config: crate::logging::LogConfig,
+    /// Background tasks whose results are applied by the event loop
+    pub task_runner: super::TaskRunner,
+    /// Named server profiles from `~/.fido/servers.json`
+    pub server_config: crate::server_config::ServerConfigManager,
+    /// Profile the client is connected through; `None` uses the default session
+    pub active_profile: Option<String>,
+    pub server_switcher: ServerSwitcherState,
+    /// Transient notifications shown in the bottom-right corner
+    pub toasts: super::ToastQueue,
+    /// Do-not-disturb / quiet hours bookkeeping
+    pub quiet_state: QuietState,
+    pub accessibility: AccessibilityState,
+    pub log_viewer: LogViewerState,
+    /// Composer draft left behind by a crash, offered back on the main screen
+    pub recovered_draft: Option<crate::crash::Draft>,
+    /// "While you were away" digest shown on the main screen until dismissed
+    pub digest: Option<fido_types::Digest>,
+    /// Catch-up summary shown after logging in from a long absence
+    pub catchup: Option<fido_types::Catchup>,
+    /// People followed on GitHub who are on Fido, offered once after login
+    pub github_suggestions: Option<Vec<fido_types::GitHubSuggestion>>,
+    pub link_preview: LinkPreviewState,
+    /// Cards for GitHub links seen in posts; `None` where a link has none
+    pub github_cards: std::collections::HashMap<String, Option<fido_types::GitHubCard>>,
+    /// Gists linked from opened threads, by link; `None` where a link has none
+    pub gists: std::collections::HashMap<String, Option<fido_types::Gist>>,
+    pub share_menu: ShareMenuState,
+    /// Text to copy, written to the terminal by the event loop
+    pub pending_clipboard: Option<String>,
+    pub drafts: DraftsState,
+    pub events: EventsState,
+    pub highlights: HighlightsState,
+    pub reading_history: ReadingHistoryState,
+    pub hashtag_digest: HashtagDigestState,
+    pub translations: TranslationState,
+    /// Post showing its exact time in place of the usual timestamp ('T')
+    pub exact_time_post: Option<Uuid>,
+    /// Posts whose diff is shown as typed rather than colored ('v')
+    pub raw_diff_posts: std::collections::HashSet<Uuid>,
+    /// Hashtag, mention or link picked with Tab inside the selected feed
+    /// post, as (post, position among the post's tokens)
+    pub focused_token: Option<(Uuid, usize)>,
+    /// Posting limits from the server's `/features`; `None` until loaded
+    pub instance_features: Option<fido_types::InstanceFeatures>,
+    /// Watched threads ('w' in a thread) and their unseen reply counts as of
+    /// the last poll
+    pub watched_threads: std::collections::HashMap<Uuid, i32>,
+    pub crossposts: CrosspostState,
+    pub reauth: ReauthState,
+    pub diagnostics: DiagnosticsState,
+    /// Outcome of the startup version handshake; `None` until it answers
+    /// or when the server predates it
+    pub version_check: Option<fido_types::version::Compatibility>,
+    /// Counting down a 429; the screen reloads when the wait is over
+    pub rate_limit_waiting: bool,
+}
+
+/// Signing back in after the server rejected the session, shown over
+/// whatever was open so nothing is lost; Esc gives up and logs out
+#[derive(Default)]
+pub struct ReauthState {
+    pub show: bool,
+    /// GitHub device flow started with Enter, polled by the event loop
+    pub device_code: Option<String>,
+    pub user_code: Option<String>,
+    pub verification_uri: Option<String>,
+    pub poll_interval: u64,
+    pub started: Option<Instant>,
+    pub last_poll: Option<Instant>,
+    pub error: Option<String>,
+}
+
+impl ReauthState {
+    /// Give up on a device code after this long, like the login screen
+    pub const DEVICE_FLOW_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(900);
+
+    /// Whether the device flow should be polled again now
+    pub fn poll_due(&self) -> bool {
+        self.device_code.is_some()
+            && self.last_poll.is_none_or(|last| {
+                last.elapsed() >= std::time::Duration::from_secs(self.poll_interval.max(1))
+            })
+    }
+}
+
+/// Connection diagnostics (Ctrl+Shift+D): what the server is, how fast it
+/// answers and whether the client is backing off
+#[derive(Default)]
+pub struct DiagnosticsState {
+    pub show: bool,
+    /// From `GET /version`; `None` until the first ping answers
+    pub server_version: Option<String>,
+    /// Round trips of the most recent pings, oldest first
+    pub latencies: std::collections::VecDeque<std::time::Duration>,
+    pub last_ping: Option<Instant>,
+    /// Why the last ping failed, cleared by the next one that succeeds
+    pub error: Option<String>,
+}
+
+impl DiagnosticsState {
+    /// Ping this often while the screen is open
+    pub const PING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+    /// Round trips kept for the average
+    pub const MAX_SAMPLES: usize = 10;
+
+    pub fn ping_due(&self) -> bool {
+        self.show
+            && self
+                .last_ping
+                .is_none_or(|last| last.elapsed() >= Self::PING_INTERVAL)
+    }
+
+    pub fn record_ping(&mut self, result: Result<(String, std::time::Duration), String>) {
+        match result {
+            Ok((version, latency)) => {
+                self.server_version = Some(version);
+                if self.latencies.len() == Self::MAX_SAMPLES {
+                    self.latencies.pop_front();
+                }
+                self.latencies.push_back(latency);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn average_latency(&self) -> Option<std::time::Duration> {
+        let count = self.latencies.len() as u32;
+        (count > 0).then(|| self.latencies.iter().sum::<std::time::Duration>() / count)
+    }
+}
+
+/// Requests to cross-post DM messages into the feed (Ctrl+X in DMs)
+#[derive(Default)]
+pub struct CrosspostState {
+    /// Request from the other participant currently asking for our consent
+    pub prompt: Option<fido_types::Crosspost>,
+    /// Requests put off with Esc; asked about again next session
+    pub dismissed: std::collections::HashSet<Uuid>,
+    /// Status of our own requests as of the last poll
+    pub outgoing: std::collections::HashMap<Uuid, fido_types::CrosspostStatus>,
+}
+
+/// Server-side post drafts, listed from the new-post composer (Ctrl+O)
+#[derive(Default)]
+pub struct DraftsState {
+    pub show: bool,
+    /// Most recently edited first
+    pub drafts: Vec<fido_types::PostDraft>,
+    pub selected: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+/// Upcoming events from the people and hashtags the user follows ('c')
+#[derive(Default)]
+pub struct EventsState {
+    pub show: bool,
+    /// Soonest first
+    pub events: Vec<Post>,
+    pub selected: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+/// The past week's top posts and contributors ('H' in the feed)
+#[derive(Default)]
+pub struct HighlightsState {
+    pub show: bool,
+    /// Best-voted first
+    pub top_posts: Vec<Post>,
+    /// Most karma first; `None` where the instance doesn't rank people
+    pub top_contributors: Option<Vec<fido_types::TopContributor>>,
+    /// Index into `top_posts`
+    pub selected: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+/// Posts opened in the thread view, searchable by fuzzy match (Ctrl+H)
+#[derive(Default)]
+pub struct ReadingHistoryState {
+    pub show: bool,
+    /// Most recently opened first
+    pub entries: Vec<crate::config::HistoryEntry>,
+    pub search: String,
+    /// Index into `matches()`
+    pub selected: usize,
+}
+
+impl ReadingHistoryState {
+    /// Entries matching the search, best match first and most recent first
+    /// among equals; all of them, by time, while the search is empty
+    pub fn matches(&self) -> Vec<&crate::config::HistoryEntry> {
+        let mut scored: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let text = format!("{} {}", entry.post.author_username, entry.post.content);
+                crate::fuzzy::score(&self.search, &text).map(|score| (score, entry))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    pub fn selected_post_id(&self) -> Option<Uuid> {
+        self.matches().get(self.selected).map(|entry| entry.post.id)
+    }
+}
+
+/// A line of the Digest section that can be selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashtagDigestRow {
+    /// A followed hashtag, by index into `entries`
+    Hashtag(usize),
+    /// One of its top posts: hashtag index, then post index
+    Post(usize, usize),
+}
+
+/// Weekly changelogs of followed hashtags ('W' in the feed)
+#[derive(Default)]
+pub struct HashtagDigestState {
+    pub show: bool,
+    pub entries: Vec<fido_types::HashtagDigestEntry>,
+    /// Index into `rows()`
+    pub selected: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl HashtagDigestState {
+    /// Each hashtag followed by its top posts, in display order
+    pub fn rows(&self) -> Vec<HashtagDigestRow> {
+        let mut rows = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            rows.push(HashtagDigestRow::Hashtag(index));
+            if let Some(changelog) = &entry.changelog {
+                rows.extend((0..changelog.top_posts.len()).map(|post| HashtagDigestRow::Post(index, post)));
+            }
+        }
+        rows
+    }
+
+    pub fn selected_row(&self) -> Option<HashtagDigestRow> {
+        self.rows().get(self.selected).copied()
+    }
+
+    /// The post under the cursor, when it's on one
+    pub fn selected_post(&self) -> Option<&Post> {
+        match self.selected_row()? {
+            HashtagDigestRow::Post(entry, post) => self.entries[entry].changelog.as_ref()?.top_posts.get(post),
+            HashtagDigestRow::Hashtag(_) => None,
+        }
+    }
+}
+
+/// Confirmation shown before opening a link from a post ('o')
+#[derive(Default)]
+pub struct LinkPreviewState {
+    pub show: bool,
+    /// Links found in the post, in order
+    pub urls: Vec<String>,
+    pub selected: usize,
+    pub preview: Option<fido_types::LinkPreview>,
+    pub loading: bool,
+    /// The server couldn't check the link, so shorteners and the blocklist
+    /// weren't applied
+    pub unchecked: bool,
+    /// Enter was pressed once on an unchecked link; the next one opens it
+    pub confirm_unchecked: bool,
+}
+
+/// Actions applied to every post marked in visual selection mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    Bookmark,
+    MuteAuthors,
+    OpenInBrowser,
+}
+
+/// What the share menu ('S') can do with a post
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareAction {
+    CopyPermalink,
+    CopyQuote,
+    OpenInBrowser,
+    SendAsDm,
+}
+
+impl ShareAction {
+    /// Menu order
+    pub const ALL: [ShareAction; 4] = [
+        ShareAction::CopyPermalink,
+        ShareAction::CopyQuote,
+        ShareAction::OpenInBrowser,
+        ShareAction::SendAsDm,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ShareAction::CopyPermalink => "Copy permalink",
+            ShareAction::CopyQuote => "Copy as Markdown quote",
+            ShareAction::OpenInBrowser => "Open in browser",
+            ShareAction::SendAsDm => "Send as DM to a friend",
+        }
+    }
+}
+
+/// Share menu for the selected post ('S')
+#[derive(Default)]
+pub struct ShareMenuState {
+    pub show: bool,
+    pub post: Option<Post>,
+    /// Index into `ShareAction::ALL`
+    pub selected: usize,
+}
+
+/// Translations shown in place of posts in the thread modal ('t')
+#[derive(Default)]
+pub struct TranslationState {
+    pub shown: std::collections::HashMap<Uuid, fido_types::PostTranslation>,
+    /// Post whose translation is being fetched
+    pub pending: Option<Uuid>,
+}
+
+/// Hidden log viewer (Ctrl+L) over the logging ring buffer
+pub struct LogViewerState {
+    pub show: bool,
+    /// Most verbose level shown
+    pub level: log::LevelFilter,
+    pub search: String,
+    /// Keystrokes go to the search box
+    pub searching: bool,
+    /// Lines scrolled up from the newest; 0 follows the tail
+    pub scroll: usize,
+}
+
+impl Default for LogViewerState {
+    fn default() -> Self {
+        Self {
+            show: false,
+            level: log::LevelFilter::Trace,
+            search: String::new(),
+            searching: false,
+            scroll: 0,
+        }
+    }
+}
+
+impl LogViewerState {
+    /// Whether `line` passes the level filter and the search (case-insensitive)
+    pub fn matches(&self, line: &crate::logging::LogLine) -> bool {
+        line.level <= self.level
+            && (self.search.is_empty() || {
+                let search = self.search.to_lowercase();
+                line.message.to_lowercase().contains(&search)
+                    || line.target.to_lowercase().contains(&search)
+            })
+    }
+
+    /// Step the level filter from everything down to errors only, then wrap
+    pub fn cycle_level(&mut self) {
+        use log::LevelFilter;
+        self.level = match self.level {
+            LevelFilter::Trace => LevelFilter::Debug,
+            LevelFilter::Debug => LevelFilter::Info,
+            LevelFilter::Info => LevelFilter::Warn,
+            LevelFilter::Warn => LevelFilter::Error,
+            LevelFilter::Error | LevelFilter::Off => LevelFilter::Trace,
+        };
+        self.scroll = 0;
+    }
+}
+
+/// Server reachability, as shown in the status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Online,
+    /// The circuit breaker is open and requests fail fast
+    Offline,
+    /// Offline, with a probe request in flight
+    Reconnecting,
+}
+
+/// Quick-switch modal for server profiles
+#[derive(Default)]
+pub struct ServerSwitcherState {
+    pub show_modal: bool,
+    pub selected_index: usize,
+    pub error: Option<String>,
+    /// Set after a switch so the event loop can rebuild anything holding
+    /// the previous ApiClient
+    pub switched: bool,
+}
+
+/// Why notifications are currently muted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuietReason {
+    DoNotDisturb,
+    QuietHours,
+}
+
+/// Notifications held back while do-not-disturb or quiet hours are on
+#[derive(Debug, Default)]
+pub struct QuietState {
+    /// Unread DM count when muting began; the badges show this until unmuted
+    pub unread_at_start: Option<usize>,
+    /// Notification toasts swallowed while muted
+    pub missed_notifications: usize,
+}
+
+/// Announcements for screen reader users, waiting for the event loop
+#[derive(Debug)]
+pub struct AccessibilityState {
+    pub pending: Vec<String>,
+    /// Focus last announced, so only changes are repeated
+    pub last_focus: Option<String>,
+    /// `spd-say` may be used: false for SSH sessions and once it has failed
+    pub speech_available: bool,
+}
+
+impl Default for AccessibilityState {
+    fn default() -> Self {
+        Self {
+            pending: Vec::new(),
+            last_focus: None,
+            speech_available: true,
+        }
+    }
 }
 
 /// Settings tab state
@@ -172,10 +868,12 @@ pub struct SettingsState {
     pub loading: bool,
     pub error: Option<String>,
     pub selected_field: SettingsField,
-    pub max_posts_input: String,
+    pub max_posts_input: TextInput,
     pub has_unsaved_changes: bool,
     pub show_save_confirmation: bool,
     pub pending_tab: Option<Tab>,
+    /// Pinned hashtags being typed (Enter on the field); `None` otherwise
+    pub pinned_hashtags_input: Option<TextInput>,
 }
 
 /// DMs tab state
@@ -189,10 +887,31 @@ pub struct DMsState {
     pub message_textarea: TextArea<'static>, // TextArea for message input
     pub messages_scroll_offset: usize, // Scroll offset for message history
     pub show_new_conversation_modal: bool,
-    pub new_conversation_username: String,
+    /// Username typed to find a mutual friend in the new conversation modal
+    pub new_conversation_username: TextInput,
     pub pending_conversation_username: Option<String>, // Username for new conversation not yet created
     pub unread_counts: std::collections::HashMap<uuid::Uuid, usize>, // user_id -> unread count
     pub current_conversation_user: Option<uuid::Uuid>, // Track open conversation
+    /// Message picked with [ / ] for cross-posting; `None` means the newest
+    pub selected_message: Option<usize>,
+    /// File to send with the next message (`/attach <path>` in the input)
+    pub pending_attachment: Option<fido_types::DmAttachment>,
+    /// Messages whose attachment is shown in full (Ctrl+E)
+    pub expanded_attachments: std::collections::HashSet<uuid::Uuid>,
+    /// Messages the server hasn't accepted yet, oldest first; sent one at a
+    /// time and shown at the end of their conversation until delivered
+    pub outbox: Vec<OutgoingMessage>,
+    /// Unsent message text per conversation (other user's ID), kept when
+    /// leaving a conversation and saved to `~/.fido/dm_drafts_<user>.json`
+    pub drafts: std::collections::HashMap<uuid::Uuid, String>,
+    /// Conversation last opened (other user's ID), selected again when the
+    /// list loads
+    pub last_conversation: Option<uuid::Uuid>,
+    /// Conversations marked unread (U) and follow-up reminders (R), saved
+    /// to `~/.fido/dm_follow_ups_<user>.json`
+    pub follow_ups: crate::config::DmFollowUps,
+    /// Delay being typed for a reminder on the selected conversation
+    pub reminder_input: Option<String>,
     pub needs_message_load: bool,                      // Flag to trigger message loading
     /// Show DM error modal with friend suggestions
     pub show_dm_error_modal: bool,
@@ -200,14 +919,43 @@ pub struct DMsState {
     pub dm_error_message: String,
     /// Username that failed when attempting to start a conversation
     pub failed_username: Option<String>,
+    /// Post link from the share menu, put in the message input once a
+    /// friend is picked in the new conversation modal
+    pub share_prefill: Option<String>,
     /// Mutual friends available for DMs (full user info with stats)
     pub available_mutual_friends: Vec<UserInfo>,
     /// Selected index in new conversation modal
     pub new_conversation_selected_index: usize,
     /// Search mode for new conversation modal
     pub new_conversation_search_mode: bool,
-    /// Search query for new conversation modal
-    pub new_conversation_search_query: String,
+}
+
+/// Where a queued message stands. Delivered messages leave the outbox and
+/// show as ordinary (sent) messages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeliveryState {
+    /// Waiting its turn or on its way to the server
+    Pending,
+    /// The last attempt failed; see `retry_at`
+    Failed,
+}
+
+/// A direct message queued for sending
+#[derive(Debug, Clone)]
+pub struct OutgoingMessage {
+    pub local_id: uuid::Uuid,
+    pub to_user_id: uuid::Uuid,
+    pub to_username: String,
+    pub content: String,
+    pub attachment: Option<fido_types::DmAttachment>,
+    pub queued_at: chrono::DateTime<chrono::Utc>,
+    pub state: DeliveryState,
+    /// Failed attempts since it was queued or last retried by hand
+    pub attempts: u32,
+    /// When a failed message is tried again on its own; `None` once
+    /// automatic retries are used up or the failure isn't worth retrying
+    pub retry_at: Option<Instant>,
+    pub error: Option<String>,
 }
 
 /// Conversation summary
@@ -218,18 +966,62 @@ pub struct Conversation {
     pub last_message: String,
     pub last_message_time: chrono::DateTime<chrono::Utc>,
     pub unread_count: i32,
+    /// Pinned to the top of the list (stored server-side)
+    pub pinned: bool,
+}
+
+/// Which of the user's posts the Profile tab lists (←/→ to switch)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProfilePostsTab {
+    #[default]
+    Posts,
+    Replies,
+    Upvoted,
+}
+
+impl ProfilePostsTab {
+    pub const ALL: [ProfilePostsTab; 3] = [
+        ProfilePostsTab::Posts,
+        ProfilePostsTab::Replies,
+        ProfilePostsTab::Upvoted,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProfilePostsTab::Posts => "Posts",
+            ProfilePostsTab::Replies => "Replies",
+            ProfilePostsTab::Upvoted => "Upvoted",
+        }
+    }
+}
+
+/// Channels tab state
+#[derive(Default)]
+pub struct ChannelsState {
+    /// Joined channels first, as the server orders them
+    pub channels: Vec<fido_types::Channel>,
+    pub selected: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Name being typed for a new channel, while the prompt is open
+    pub new_channel_input: Option<String>,
 }
 
 /// Profile tab state (for viewing own profile)
 pub struct ProfileState {
     pub profile: Option<UserProfile>,
+    /// Posting statistics, loaded after the profile; `None` until then
+    pub stats: Option<fido_types::UserStats>,
+    /// Posts, replies or upvoted posts, as picked by `posts_tab`
     pub user_posts: Vec<Post>,
+    pub posts_tab: ProfilePostsTab,
+    /// Whether the server may have older posts beyond those loaded
+    pub has_more_posts: bool,
     pub list_state: ListState,
     pub loading: bool,
     pub error: Option<String>,
     pub show_edit_bio_modal: bool,
-    pub edit_bio_content: String,
-    pub edit_bio_cursor_position: usize,
+    pub edit_bio: TextInput,
 }
 
 /// User profile view state (for viewing other users' profiles)
@@ -241,7 +1033,13 @@ pub struct UserProfileViewState {
     pub follower_count: usize,
     pub following_count: usize,
     pub post_count: usize,
+    pub recent_posts: Vec<Post>,
     pub relationship: RelationshipStatus,
+    pub badges: Vec<fido_types::Badge>,
+    pub status: Option<fido_types::UserStatus>,
+    /// Where the account has moved to, if it left this instance
+    pub moved_to: Option<String>,
+    pub karma: i32,
     pub loading: bool,
     pub error: Option<String>,
 }
@@ -265,9 +1063,55 @@ pub enum PostFilter {
         hashtags: Vec<String>,
         users: Vec<String>,
     },
+    /// A channel's own feed, by channel name
+    Channel(String),
+    /// Open review requests in the languages the user follows
+    Reviews,
+    /// Job posts, all of them or only remote or onsite ones
+    Jobs(Option<WorkMode>),
+    /// The local read-later queue: posts snoozed with `z`
+    Later,
 }
 
 impl PostFilter {
+    /// Short description for the feed title and status bar
+    pub fn label(&self) -> String {
+        match self {
+            PostFilter::All => "Global Feed".to_string(),
+            PostFilter::Hashtag(tag) => format!("#{}", tag),
+            PostFilter::User(username) => format!("@{}", username),
+            PostFilter::Multi { hashtags, users } => {
+                format!("Filtered ({} items)", hashtags.len() + users.len())
+            }
+            PostFilter::Channel(name) => format!("Channel #{}", name),
+            PostFilter::Reviews => "Review Requests".to_string(),
+            PostFilter::Jobs(None) => "Jobs".to_string(),
+            PostFilter::Jobs(Some(work_mode)) => format!("Jobs ({})", work_mode.label()),
+            PostFilter::Later => "Later".to_string(),
+        }
+    }
+
+    /// Key the feed's position is remembered under, stable across runs
+    pub fn position_key(&self) -> String {
+        match self {
+            PostFilter::All => "all".to_string(),
+            PostFilter::Hashtag(tag) => format!("hashtag:{}", tag.to_lowercase()),
+            PostFilter::User(username) => format!("user:{}", username),
+            PostFilter::Multi { hashtags, users } => {
+                let mut hashtags = hashtags.clone();
+                let mut users = users.clone();
+                hashtags.sort();
+                users.sort();
+                format!("multi:{}|{}", hashtags.join(","), users.join(","))
+            }
+            PostFilter::Channel(name) => format!("channel:{}", name),
+            PostFilter::Reviews => "reviews".to_string(),
+            PostFilter::Jobs(None) => "jobs".to_string(),
+            PostFilter::Jobs(Some(work_mode)) => format!("jobs:{}", work_mode.as_str()),
+            PostFilter::Later => "later".to_string(),
+        }
+    }
+
     /// Convert to UserPreferences format for saving
     pub fn to_preferences(&self) -> crate::config::UserPreferences {
         match self {
@@ -277,6 +1121,7 @@ impl PostFilter {
                 filter_user: None,
                 filter_hashtags: Vec::new(),
                 filter_users: Vec::new(),
+                filter_channel: None,
             },
             PostFilter::Hashtag(tag) => crate::config::UserPreferences {
                 filter_type: "hashtag".to_string(),
@@ -284,6 +1129,7 @@ impl PostFilter {
                 filter_user: None,
                 filter_hashtags: Vec::new(),
                 filter_users: Vec::new(),
+                filter_channel: None,
             },
             PostFilter::User(user) => crate::config::UserPreferences {
                 filter_type: "user".to_string(),
@@ -291,6 +1137,7 @@ impl PostFilter {
                 filter_user: Some(user.clone()),
                 filter_hashtags: Vec::new(),
                 filter_users: Vec::new(),
+                filter_channel: None,
             },
             PostFilter::Multi { hashtags, users } => crate::config::UserPreferences {
                 filter_type: "multi".to_string(),
@@ -298,6 +1145,42 @@ impl PostFilter {
                 filter_user: None,
                 filter_hashtags: hashtags.clone(),
                 filter_users: users.clone(),
+                filter_channel: None,
+            },
+            PostFilter::Channel(name) => crate::config::UserPreferences {
+                filter_type: "channel".to_string(),
+                filter_hashtag: None,
+                filter_user: None,
+                filter_hashtags: Vec::new(),
+                filter_users: Vec::new(),
+                filter_channel: Some(name.clone()),
+            },
+            PostFilter::Reviews => crate::config::UserPreferences {
+                filter_type: "reviews".to_string(),
+                filter_hashtag: None,
+                filter_user: None,
+                filter_hashtags: Vec::new(),
+                filter_users: Vec::new(),
+                filter_channel: None,
+            },
+            PostFilter::Jobs(work_mode) => crate::config::UserPreferences {
+                filter_type: match work_mode {
+                    Some(work_mode) => format!("jobs:{}", work_mode.as_str()),
+                    None => "jobs".to_string(),
+                },
+                filter_hashtag: None,
+                filter_user: None,
+                filter_hashtags: Vec::new(),
+                filter_users: Vec::new(),
+                filter_channel: None,
+            },
+            PostFilter::Later => crate::config::UserPreferences {
+                filter_type: "later".to_string(),
+                filter_hashtag: None,
+                filter_user: None,
+                filter_hashtags: Vec::new(),
+                filter_users: Vec::new(),
+                filter_channel: None,
             },
         }
     }
@@ -323,7 +1206,17 @@ impl PostFilter {
                 hashtags: prefs.filter_hashtags.clone(),
                 users: prefs.filter_users.clone(),
             },
-            _ => PostFilter::All,
+            "channel" => match &prefs.filter_channel {
+                Some(name) => PostFilter::Channel(name.clone()),
+                None => PostFilter::All,
+            },
+            "reviews" => PostFilter::Reviews,
+            "jobs" => PostFilter::Jobs(None),
+            "later" => PostFilter::Later,
+            other => match other.strip_prefix("jobs:").and_then(WorkMode::parse) {
+                Some(work_mode) => PostFilter::Jobs(Some(work_mode)),
+                None => PostFilter::All,
+            },
         }
     }
 }
@@ -334,7 +1227,6 @@ pub struct PostsState {
     pub list_state: ListState,
     pub loading: bool,
     pub error: Option<String>,
-    pub message: Option<(String, Instant)>, // (message, timestamp) - auto-clears after 3 seconds
     pub show_new_post_modal: bool,
     pub new_post_content: String,
     /// Flag to trigger actual load after UI renders loading state
@@ -349,6 +1241,23 @@ pub struct PostsState {
     pub sort_order: String,
     /// Track if at end of feed (for "End of Feed" indicator)
     pub at_end_of_feed: bool,
+    /// Load the feed once settings arrive (sort order and page size come from them)
+    pub awaiting_settings: bool,
+    /// Reduced motion is saved: refreshes don't insert a loading row above the posts
+    pub reduced_motion: bool,
+    /// Near-duplicates folded into the feed entry just before them (entry id -> hidden posts)
+    pub similar: std::collections::HashMap<Uuid, Vec<Post>>,
+    /// Posts marked in visual selection mode ('V'); `None` outside it
+    pub bulk_selection: Option<std::collections::HashSet<Uuid>>,
+    /// Where the user left each feed, by `PostFilter::position_key`; saved
+    /// to `~/.fido/positions_<user>.json`
+    pub positions: std::collections::HashMap<String, crate::config::ListPosition>,
+    /// Position to go back to once the feed being loaded arrives
+    pub pending_restore: Option<crate::config::ListPosition>,
+    /// Posts snoozed with `z`, saved to `~/.fido/read_later_<user>.json`
+    pub read_later: Vec<crate::config::SnoozedPost>,
+    /// How long to snooze the selected post for, while it's being typed
+    pub snooze_input: Option<String>,
 }
 
 impl PostsState {
@@ -358,7 +1267,7 @@ impl PostsState {
         let mut count = 0;
 
         // Loading spinner
-        if self.loading && !self.posts.is_empty() {
+        if self.loading && !self.posts.is_empty() && !self.reduced_motion {
             count += 1;
         }
 
@@ -397,7 +1306,7 @@ pub struct FilterModalState {
     /// Show add hashtag input in hashtags tab
     pub show_add_hashtag_input: bool,
     /// Input for adding new hashtag
-    pub add_hashtag_input: String,
+    pub add_hashtag_input: TextInput,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -405,8 +1314,14 @@ pub enum FilterTab {
     All,
     Hashtags,
     Users,
+    Reviews,
+    Jobs,
+    Later,
 }
 
+/// What each row of the filter modal's Jobs tab shows
+pub const JOB_FILTER_CHOICES: [Option<WorkMode>; 3] = [None, Some(WorkMode::Remote), Some(WorkMode::Onsite)];
+
 /// Post detail view state
 pub struct PostDetailState {
     pub post: Option<Post>,
@@ -414,7 +1329,6 @@ pub struct PostDetailState {
     pub reply_list_state: ListState,
     pub loading: bool,
     pub error: Option<String>,
-    pub message: Option<(String, Instant)>, // (message, timestamp) - auto-clears after 3 seconds
     pub show_reply_composer: bool,
     pub reply_content: String,
     pub show_delete_confirmation: bool,
@@ -429,6 +1343,10 @@ pub struct PostDetailState {
     pub modal_list_state: ListState,
     /// Track expansion state within modal (separate from main view)
     pub modal_expanded_posts: std::collections::HashMap<Uuid, bool>,
+    /// Posts sharing hashtags or the author with the modal's post
+    pub related: Vec<Post>,
+    /// Related post focused with Tab (None while the thread has focus)
+    pub related_selected: Option<usize>,
 }
 
 impl PostDetailState {
@@ -461,30 +1379,7 @@ impl PostDetailState {
     pub fn get_deletable_post(&self) -> Option<&Post> {
         // If in full post modal, get the selected post from modal state
         if self.show_full_post_modal {
-            if let Some(selected_idx) = self.modal_list_state.selected() {
-                if selected_idx == 0 {
-                    // First item is always the root post in modal
-                    return self.full_post_modal_id.and_then(|id| {
-                        if self.post.as_ref().map(|p| p.id) == Some(id) {
-                            self.post.as_ref()
-                        } else {
-                            self.replies.iter().find(|r| r.id == id)
-                        }
-                    });
-                } else {
-                    // Get the flattened visible posts to find the selected one
-                    if let Some(root_id) = self.full_post_modal_id {
-                        let mut flattened_posts = Vec::new();
-                        self.collect_visible_posts_for_modal(root_id, &mut flattened_posts);
-
-                        if selected_idx > 0 && selected_idx <= flattened_posts.len() {
-                            let post_id = flattened_posts[selected_idx - 1];
-                            return self.replies.iter().find(|r| r.id == post_id);
-                        }
-                    }
-                }
-            }
-            return None;
+            return self.selected_modal_post();
         }
 
         // Main detail view logic (existing)
@@ -502,37 +1397,21 @@ impl PostDetailState {
         self.post.as_ref()
     }
 
-    /// Helper to collect visible posts in modal (for deletion)
-    fn collect_visible_posts_for_modal(&self, root_id: Uuid, result: &mut Vec<Uuid>) {
-        use std::collections::HashMap;
-
-        let mut children_map: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
-        for reply in &self.replies {
-            if let Some(parent_id) = reply.parent_post_id {
-                children_map
-                    .entry(parent_id)
-                    .or_default()
-                    .push(reply.id);
-            }
-        }
-
-        fn collect(
-            post_id: &Uuid,
-            children_map: &HashMap<Uuid, Vec<Uuid>>,
-            expanded: &std::collections::HashMap<Uuid, bool>,
-            result: &mut Vec<Uuid>,
-        ) {
-            if let Some(children) = children_map.get(post_id) {
-                for child_id in children {
-                    result.push(*child_id);
-                    if expanded.get(child_id).copied().unwrap_or(false) {
-                        collect(child_id, children_map, expanded, result);
-                    }
-                }
-            }
-        }
+    /// The thread shown in the full post modal, rooted at its post
+    pub fn modal_thread(&self) -> Option<ThreadTree<'_>> {
+        let root_id = self.full_post_modal_id?;
+        let root = self
+            .post
+            .as_ref()
+            .filter(|post| post.id == root_id)
+            .or_else(|| self.replies.iter().find(|reply| reply.id == root_id))?;
+        Some(ThreadTree::new(root, &self.replies))
+    }
 
-        collect(&root_id, &children_map, &self.modal_expanded_posts, result);
+    /// The post selected in the full post modal (index 0 is its root)
+    pub fn selected_modal_post(&self) -> Option<&Post> {
+        let selected_idx = self.modal_list_state.selected()?;
+        self.modal_thread()?.post_at(selected_idx, &self.modal_expanded_posts)
     }
 }
 
@@ -550,6 +1429,15 @@ pub struct AuthState {
     pub github_verification_uri: Option<String>,
     pub github_poll_interval: Option<i64>,
     pub github_auth_start_time: Option<std::time::Instant>,
+    /// Stored session is being validated in the background (main screen shows skeletons)
+    pub restoring_session: bool,
+    /// Accounts with a saved session on the current server, listed on the auth screen
+    pub saved_accounts: Vec<String>,
+    /// Saved account currently being switched to
+    pub switching_account: Option<String>,
+    /// Whether logins are saved for switching later (off in web mode, where
+    /// the host's `~/.fido` is shared between visitors)
+    pub remember_accounts: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -561,15 +1449,30 @@ pub enum Screen {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Tab {
     Posts,
+    Channels,
     DMs,
     Profile,
     Settings,
 }
 
 impl Tab {
+    /// Tabs in the order they appear in the tab bar
+    pub const ALL: [Tab; 5] = [Tab::Posts, Tab::Channels, Tab::DMs, Tab::Profile, Tab::Settings];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Tab::Posts => "Posts",
+            Tab::Channels => "Channels",
+            Tab::DMs => "DMs",
+            Tab::Profile => "Profile",
+            Tab::Settings => "Settings",
+        }
+    }
+
     pub fn next(&self) -> Self {
         match self {
-            Tab::Posts => Tab::DMs,
+            Tab::Posts => Tab::Channels,
+            Tab::Channels => Tab::DMs,
             Tab::DMs => Tab::Profile,
             Tab::Profile => Tab::Settings,
             Tab::Settings => Tab::Posts,
@@ -579,7 +1482,8 @@ impl Tab {
     pub fn previous(&self) -> Self {
         match self {
             Tab::Posts => Tab::Settings,
-            Tab::DMs => Tab::Posts,
+            Tab::Channels => Tab::Posts,
+            Tab::DMs => Tab::Channels,
             Tab::Profile => Tab::DMs,
             Tab::Settings => Tab::Profile,
         }
@@ -0,0 +1,227 @@
+use fido_types::{
+    Catchup, Crosspost, CustomEmoji, Digest, DirectMessage, Gist, GitHubCard, GitHubSuggestion, HashtagDigestEntry, InstanceFeatures, LinkPreview, Post, PostDraft, PostTranslation,
+    TopContributor, User, UserConfig, UserStats, WatchedThread,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::{BulkAction, Conversation, SocialTab, UserSearchResult};
+use crate::api::{ApiClient, SocialUserInfo};
+
+/// Result of an async operation that ran on a background tokio task.
+///
+/// Errors are carried as strings so results can be categorized for display
+/// by the same helpers used for inline loads.
+pub enum TaskResult {
+    PostsLoaded(Result<Vec<Post>, String>),
+    ConversationsLoaded(Result<Vec<Conversation>, String>),
+    /// Background refresh of the conversation list, used only for unread counts
+    UnreadCountsPolled(Result<Vec<Conversation>, String>),
+    SettingsLoaded(Result<UserConfig, String>),
+    TestUsersLoaded(Result<Vec<User>, String>),
+    /// Stored session was validated; carries the authenticated client when valid
+    SessionRestored(Option<(User, ApiClient)>),
+    /// Followed hashtags and followed usernames for the filter modal
+    FilterModalDataLoaded {
+        hashtags: Result<Vec<String>, String>,
+        users: Result<Vec<String>, String>,
+    },
+    UserSearchCompleted {
+        query: String,
+        result: Result<Vec<UserSearchResult>, String>,
+    },
+    /// A further page of the Profile tab's posts
+    ProfilePostsLoaded(Result<Vec<Post>, String>),
+    ProfileStatsLoaded(Result<UserStats, String>),
+    /// Instance emoji table, fetched once per login
+    CustomEmojiLoaded(Result<Vec<CustomEmoji>, String>),
+    /// Keep-alive ping; `expired` only when the server rejected the session
+    HeartbeatCompleted { expired: bool },
+    /// Instance limits shown in the composer
+    FeaturesLoaded(Result<InstanceFeatures, String>),
+    /// Timed `GET /version` for the diagnostics screen: version and round trip
+    DiagnosticsPinged(Result<(String, std::time::Duration), String>),
+    /// Startup handshake; `None` when the server predates `GET /version`
+    VersionChecked(Result<Option<fido_types::ServerVersion>, String>),
+    LinkPreviewLoaded {
+        url: String,
+        result: Result<LinkPreview, String>,
+    },
+    PostTranslated {
+        post_id: Uuid,
+        result: Result<PostTranslation, String>,
+    },
+    /// Cards for GitHub links in the feed and the open thread, by link
+    GitHubCardsLoaded(Vec<(String, Result<Option<GitHubCard>, String>)>),
+    /// The gist linked from the open thread's post
+    GistLoaded {
+        url: String,
+        result: Result<Option<Gist>, String>,
+    },
+    /// Newest unseen digest, if the server compiled one
+    DigestLoaded(Result<Option<Digest>, String>),
+    DigestDismissed(Result<(), String>),
+    CatchupLoaded(Result<Catchup, String>),
+    DraftsLoaded(Result<Vec<PostDraft>, String>),
+    EventsLoaded(Result<Vec<Post>, String>),
+    /// Top posts, and top contributors where the instance ranks people
+    HighlightsLoaded(Result<(Vec<Post>, Option<Vec<TopContributor>>), String>),
+    HashtagDigestLoaded(Result<Vec<HashtagDigestEntry>, String>),
+    WatchedThreadsPolled(Result<Vec<WatchedThread>, String>),
+    CrosspostsPolled(Result<Vec<Crosspost>, String>),
+    GitHubSuggestionsLoaded(Result<Vec<GitHubSuggestion>, String>),
+    GitHubSuggestionsDismissed(Result<(), String>),
+    /// A page of one of the social modal's lists; `append` continues what's
+    /// loaded, otherwise it replaces it (the search changed)
+    SocialPageLoaded {
+        tab: SocialTab,
+        query: String,
+        append: bool,
+        result: Result<Vec<SocialUserInfo>, String>,
+    },
+    /// Bookmarking or muting the posts marked in the feed; the count is
+    /// how many were newly applied
+    BulkActionDone {
+        action: BulkAction,
+        result: Result<usize, String>,
+    },
+    /// A queued DM reached the server, or didn't; `transient` failures are
+    /// retried on their own
+    DmDelivered {
+        local_id: Uuid,
+        result: Result<DirectMessage, String>,
+        transient: bool,
+    },
+}
+
+/// Kinds of background work. Only the newest task of each kind is live:
+/// spawning again (or cancelling) aborts the previous one and discards any
+/// result it may already have sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskKind {
+    Posts,
+    Conversations,
+    Settings,
+    TestUsers,
+    Session,
+    FilterModalData,
+    UserSearch,
+    ProfilePosts,
+    ProfileStats,
+    CustomEmoji,
+    Heartbeat,
+    LinkPreview,
+    GitHubCards,
+    Gist,
+    Features,
+    DiagnosticsPing,
+    VersionCheck,
+    UnreadCounts,
+    Translation,
+    Digest,
+    Catchup,
+    Drafts,
+    Events,
+    Highlights,
+    HashtagDigest,
+    WatchedThreads,
+    Crossposts,
+    DmDelivery,
+    GitHubSuggestions,
+    SocialList,
+    BulkAction,
+}
+
+struct Envelope {
+    kind: TaskKind,
+    id: u64,
+    result: TaskResult,
+}
+
+/// Spawns async work off the event loop and collects the results.
+///
+/// Tasks send their result over an unbounded channel; the main loop drains it
+/// once per frame via `App::process_task_results`, so network latency never
+/// blocks rendering.
+pub struct TaskRunner {
+    sender: mpsc::UnboundedSender<Envelope>,
+    receiver: mpsc::UnboundedReceiver<Envelope>,
+    live: HashMap<TaskKind, (u64, AbortHandle)>,
+    next_id: u64,
+}
+
+impl TaskRunner {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            sender,
+            receiver,
+            live: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Run a future on the tokio runtime and deliver its result to the event
+    /// loop, superseding any in-flight task of the same kind
+    pub fn spawn<F>(&mut self, kind: TaskKind, task: F)
+    where
+        F: Future<Output = TaskResult> + Send + 'static,
+    {
+        self.cancel(kind);
+
+        self.next_id += 1;
+        let id = self.next_id;
+        let sender = self.sender.clone();
+        let handle = tokio::spawn(async move {
+            let result = task.await;
+            // Receiver only goes away on shutdown, nothing to report to then
+            let _ = sender.send(Envelope { kind, id, result });
+        });
+        self.live.insert(kind, (id, handle.abort_handle()));
+    }
+
+    /// Abort the in-flight task of this kind, if any (e.g. the user navigated away)
+    pub fn cancel(&mut self, kind: TaskKind) {
+        if let Some((_, handle)) = self.live.remove(&kind) {
+            handle.abort();
+        }
+    }
+
+    /// Abort every in-flight task, e.g. when switching servers
+    pub fn cancel_all(&mut self) {
+        for (_, (_, handle)) in self.live.drain() {
+            handle.abort();
+        }
+    }
+
+    /// Whether a task of this kind is still running
+    pub fn is_running(&self, kind: TaskKind) -> bool {
+        self.live.contains_key(&kind)
+    }
+
+    /// Take the next completed, non-stale result without blocking
+    pub fn try_recv(&mut self) -> Option<TaskResult> {
+        while let Ok(envelope) = self.receiver.try_recv() {
+            // A result from a superseded or cancelled task must not overwrite newer state
+            if self.live.get(&envelope.kind).map(|(id, _)| *id) == Some(envelope.id) {
+                self.live.remove(&envelope.kind);
+                return Some(envelope.result);
+            }
+        }
+        None
+    }
+
+    /// Number of live tasks whose results haven't been drained yet
+    pub fn in_flight(&self) -> usize {
+        self.live.len()
+    }
+}
+
+impl Default for TaskRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
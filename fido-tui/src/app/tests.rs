@@ -1,5 +1,6 @@
 use super::*;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use fido_types::ReplyPolicy;
 
 /// Helper to create a KeyEvent
 fn key_event(code: KeyCode) -> KeyEvent {
@@ -8,6 +9,34 @@ fn key_event(code: KeyCode) -> KeyEvent {
     event
 }
 
+/// A plain top-level post by `author`, to override fields on as needed
+fn test_post(author: &str, content: &str) -> Post {
+    Post {
+        id: uuid::Uuid::new_v4(),
+        author_id: uuid::Uuid::new_v4(),
+        author_username: author.to_string(),
+        content: content.to_string(),
+        created_at: chrono::Utc::now(),
+        upvotes: 0,
+        downvotes: 0,
+        hashtags: Vec::new(),
+        user_vote: None,
+        parent_post_id: None,
+        reply_count: 0,
+        reply_to_user_id: None,
+        reply_to_username: None,
+        is_deleted: false,
+        author_badges: Vec::new(),
+        author_status: None,
+        kind: None,
+        is_anonymous: false,
+        reply_policy: ReplyPolicy::Everyone,
+        can_reply: true,
+        is_accepted: false,
+        author_karma: 0,
+    }
+}
+
 #[test]
 fn test_escape_closes_help_modal_first() {
     let mut app = App::new();
@@ -101,7 +130,7 @@ fn test_escape_closes_new_conversation_modal() {
     let mut app = App::new();
     app.current_screen = Screen::Main;
     app.dms_state.show_new_conversation_modal = true;
-    app.dms_state.new_conversation_username = "testuser".to_string();
+    app.dms_state.new_conversation_username.set_value("testuser");
     app.running = true;
 
     // Escape should close new conversation modal, not exit app
@@ -112,7 +141,8 @@ fn test_escape_closes_new_conversation_modal() {
         "New conversation modal should be closed"
     );
     assert_eq!(
-        app.dms_state.new_conversation_username, "",
+        app.dms_state.new_conversation_username.value(),
+        "",
         "Username should be cleared"
     );
     assert!(app.running, "App should still be running");
@@ -248,6 +278,17 @@ fn test_settings_cycling() {
     let final_color_scheme = app.settings_state.config.as_ref().unwrap().color_scheme;
     assert_eq!(initial_color_scheme, final_color_scheme);
 
+    // A full forward cycle passes through the accessible palettes
+    let mut seen = Vec::new();
+    for _ in 0..7 {
+        app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+        seen.push(app.settings_state.config.as_ref().unwrap().color_scheme);
+    }
+    assert!(seen.contains(&fido_types::ColorScheme::HighContrast));
+    assert!(seen.contains(&fido_types::ColorScheme::Deuteranopia));
+    assert!(seen.contains(&fido_types::ColorScheme::Protanopia));
+    assert_eq!(seen.last(), Some(&initial_color_scheme));
+
     // Test Sort Order cycling
     app.settings_state.selected_field = SettingsField::SortOrder;
     let initial_sort_order = app.settings_state.config.as_ref().unwrap().sort_order;
@@ -263,6 +304,908 @@ fn test_settings_cycling() {
     assert_eq!(initial_sort_order, final_sort_order);
 }
 
+#[test]
+fn test_vote_visibility_toggle_marks_unsaved_changes() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Settings;
+    app.settings_state.config = Some(fido_types::UserConfig::default());
+    app.settings_state.original_config = Some(fido_types::UserConfig::default());
+    app.settings_state.selected_field = SettingsField::MaxPosts;
+
+    // Vote visibility sits below max posts; the notification, display,
+    // feed layout and pinned hashtag fields follow and the local-only
+    // session encryption field ends the list
+    app.handle_key_event(key_event(KeyCode::Down)).unwrap();
+    assert_eq!(app.settings_state.selected_field, SettingsField::VotePrivacy);
+    for _ in 0..17 {
+        app.handle_key_event(key_event(KeyCode::Down)).unwrap();
+    }
+    assert_eq!(app.settings_state.selected_field, SettingsField::SessionEncryption);
+    app.handle_key_event(key_event(KeyCode::Up)).unwrap();
+    assert_eq!(app.settings_state.selected_field, SettingsField::Discoverable);
+    app.handle_key_event(key_event(KeyCode::Up)).unwrap();
+    assert_eq!(app.settings_state.selected_field, SettingsField::PinnedHashtags);
+    for _ in 0..15 {
+        app.handle_key_event(key_event(KeyCode::Up)).unwrap();
+    }
+    assert_eq!(app.settings_state.selected_field, SettingsField::VotePrivacy);
+
+    // Hidden by default; toggling it is an unsaved change
+    assert!(!app.settings_state.config.as_ref().unwrap().votes_visible_to_authors);
+    app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    assert!(app.settings_state.config.as_ref().unwrap().votes_visible_to_authors);
+    assert!(app.settings_state.has_unsaved_changes);
+
+    // Toggling back restores the saved state
+    app.handle_key_event(key_event(KeyCode::Left)).unwrap();
+    assert!(!app.settings_state.has_unsaved_changes);
+}
+
+#[test]
+fn test_preview_lines_wrap_between_all_and_the_limit() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Settings;
+    app.settings_state.config = Some(fido_types::UserConfig::default());
+    app.settings_state.original_config = Some(fido_types::UserConfig::default());
+    app.settings_state.selected_field = SettingsField::PreviewLines;
+
+    // "All" steps back to the largest limit and forward to 1
+    app.handle_key_event(key_event(KeyCode::Left)).unwrap();
+    assert_eq!(
+        app.settings_state.config.as_ref().unwrap().preview_lines,
+        fido_types::UserConfig::MAX_PREVIEW_LINES
+    );
+    app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    assert_eq!(app.settings_state.config.as_ref().unwrap().preview_lines, 1);
+    assert!(app.settings_state.has_unsaved_changes);
+
+    // Feed layout applies once saved, not while editing
+    app.settings_state.selected_field = SettingsField::FeedDensity;
+    app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    assert_eq!(
+        app.feed_layout(),
+        (fido_types::FeedDensity::Comfortable, 0, true)
+    );
+}
+
+#[test]
+fn test_max_posts_input_takes_up_to_three_digits() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Settings;
+    app.settings_state.config = Some(fido_types::UserConfig::default());
+    app.settings_state.original_config = Some(fido_types::UserConfig::default());
+    app.settings_state.max_posts_input.set_value("25");
+    app.settings_state.original_max_posts_input = "25".to_string();
+    app.settings_state.selected_field = SettingsField::MaxPosts;
+
+    for c in "x0!12".chars() {
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+    }
+    assert_eq!(app.settings_state.max_posts_input.value(), "250");
+    assert!(app.settings_state.has_unsaved_changes);
+
+    app.handle_key_event(key_event(KeyCode::Backspace)).unwrap();
+    assert_eq!(app.settings_state.max_posts_input.value(), "25");
+    assert!(!app.settings_state.has_unsaved_changes);
+}
+
+#[test]
+fn test_bio_editing_moves_by_characters() {
+    let mut app = App::new();
+    app.profile_state.show_edit_bio_modal = true;
+    app.profile_state.edit_bio.set_value("café ☕");
+
+    app.handle_edit_bio_modal_keys(key_event(KeyCode::Left)).unwrap();
+    app.handle_edit_bio_modal_keys(key_event(KeyCode::Left)).unwrap();
+    app.handle_edit_bio_modal_keys(key_event(KeyCode::Backspace)).unwrap();
+    app.handle_edit_bio_modal_keys(key_event(KeyCode::Char('!'))).unwrap();
+    assert_eq!(app.profile_state.edit_bio.value(), "caf! ☕");
+
+    // The bio stops at 160 characters
+    app.profile_state.edit_bio.set_value(&"é".repeat(200));
+    assert_eq!(app.profile_state.edit_bio.value().chars().count(), 160);
+}
+
+#[test]
+fn test_pinned_hashtags_edited_in_settings_switch_the_feed() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Settings;
+    app.settings_state.config = Some(fido_types::UserConfig::default());
+    app.settings_state.original_config = Some(fido_types::UserConfig::default());
+    app.settings_state.selected_field = SettingsField::PinnedHashtags;
+
+    // Typing takes every key, including j/k and q
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert_eq!(app.input_mode, InputMode::Typing);
+    for c in "#Rust, go jobs rust".chars() {
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+    }
+    assert!(app.running);
+    assert_eq!(app.settings_state.selected_field, SettingsField::PinnedHashtags);
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert_eq!(app.input_mode, InputMode::Navigation);
+    assert_eq!(
+        app.settings_state.config.as_ref().unwrap().pinned_hashtags,
+        vec!["rust", "go", "jobs"]
+    );
+    assert!(app.settings_state.has_unsaved_changes);
+
+    // Something that isn't a hashtag keeps the input open
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('!'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert!(app.settings_state.pinned_hashtags_input.is_some());
+    assert!(app.settings_state.error.is_some());
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(app.settings_state.pinned_hashtags_input.is_none());
+
+    // Once saved, number keys in the feed switch to them
+    app.settings_state.original_config = app.settings_state.config.clone();
+    app.current_tab = Tab::Posts;
+    app.handle_key_event(key_event(KeyCode::Char('2'))).unwrap();
+    assert_eq!(
+        app.posts_state.current_filter,
+        PostFilter::Hashtag("go".to_string())
+    );
+    assert!(app.posts_state.pending_load);
+
+    // The same key again goes back to the global feed; an empty slot does nothing
+    app.handle_key_event(key_event(KeyCode::Char('2'))).unwrap();
+    assert_eq!(app.posts_state.current_filter, PostFilter::All);
+    app.handle_key_event(key_event(KeyCode::Char('9'))).unwrap();
+    assert_eq!(app.posts_state.current_filter, PostFilter::All);
+}
+
+#[test]
+fn test_quiet_hours_settings_wrap_around_midnight() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Settings;
+    app.settings_state.config = Some(fido_types::UserConfig::default());
+    app.settings_state.original_config = Some(fido_types::UserConfig::default());
+    app.settings_state.selected_field = SettingsField::QuietHoursEnd;
+
+    // Default window is 22:00-07:00
+    for _ in 0..8 {
+        app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    }
+    assert_eq!(app.settings_state.config.as_ref().unwrap().quiet_hours_end, 15);
+    app.settings_state.selected_field = SettingsField::QuietHoursStart;
+    for _ in 0..3 {
+        app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    }
+    assert_eq!(app.settings_state.config.as_ref().unwrap().quiet_hours_start, 1);
+    assert!(app.settings_state.has_unsaved_changes);
+
+    let config = app.settings_state.config.as_ref().unwrap();
+    assert!(!config.in_quiet_hours(3), "Quiet hours are off until enabled");
+    let config = fido_types::UserConfig {
+        quiet_hours_enabled: true,
+        ..fido_types::UserConfig::default()
+    };
+    assert!(config.in_quiet_hours(23));
+    assert!(config.in_quiet_hours(6));
+    assert!(!config.in_quiet_hours(7));
+    assert!(!config.in_quiet_hours(12));
+}
+
+#[test]
+fn test_unread_poll_updates_tab_counts_without_moving_selection() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    let alice = uuid::Uuid::new_v4();
+    let bob = uuid::Uuid::new_v4();
+    let convo = |id, username: &str, unread_count| Conversation {
+        other_user_id: id,
+        other_username: username.to_string(),
+        last_message: "hi".to_string(),
+        last_message_time: chrono::Utc::now(),
+        unread_count,
+        pinned: false,
+    };
+    app.dms_state.conversations = vec![convo(alice, "alice", 0), convo(bob, "bob", 0)];
+    app.dms_state.selected_conversation_index = Some(1);
+    app.dms_state.unread_counts.insert(alice, 0);
+
+    app.apply_unread_poll(Ok(vec![convo(bob, "bob", 1), convo(alice, "alice", 2)]));
+    assert_eq!(app.tab_unread_count(Tab::DMs), 3);
+    assert_eq!(app.tab_unread_count(Tab::Posts), 0);
+    assert_eq!(app.dms_state.selected_conversation_index, Some(1));
+    assert_eq!(app.dms_state.conversations[0].unread_count, 2);
+    assert!(!app.toasts.is_empty(), "New messages are announced");
+
+    // Turning the setting off hides the count without losing it
+    app.settings_state.original_config = Some(fido_types::UserConfig {
+        show_unread_counts: false,
+        ..fido_types::UserConfig::default()
+    });
+    assert_eq!(app.tab_unread_count(Tab::DMs), 0);
+    assert_eq!(app.unread_dm_count(), 3);
+}
+
+#[test]
+fn test_focus_changes_and_toasts_are_announced_once() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let post = |author: &str, content: &str| Post {
+        upvotes: 1,
+        reply_count: 1,
+        ..test_post(author, content)
+    };
+    app.posts_state.posts = vec![post("alice", "First"), post("bob", "Second\nline")];
+    app.posts_state.list_state.select(Some(0));
+
+    // Nothing is queued while announcements are off
+    app.toasts.info("Saved");
+    app.queue_announcements();
+    assert!(app.take_announcements().is_empty());
+
+    app.settings_state.original_config = Some(fido_types::UserConfig {
+        announcements: fido_types::AnnounceOutput::Terminal,
+        ..fido_types::UserConfig::default()
+    });
+    app.queue_announcements();
+    assert_eq!(
+        app.take_announcements(),
+        vec!["Post 1 of 2. alice: First. 1 up, 0 down, 1 reply".to_string()]
+    );
+    app.queue_announcements();
+    assert!(app.take_announcements().is_empty(), "An unchanged focus isn't repeated");
+
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.toasts.error("Couldn't vote");
+    app.queue_announcements();
+    assert_eq!(
+        app.take_announcements(),
+        vec![
+            "Couldn't vote".to_string(),
+            "Post 2 of 2. bob: Second line. 1 up, 0 down, 1 reply".to_string(),
+        ]
+    );
+
+    app.current_tab = Tab::Settings;
+    app.settings_state.selected_field = SettingsField::Announcements;
+    assert_eq!(app.focus_description(), "Settings, Announcements");
+}
+
+#[test]
+fn test_reduced_motion_refresh_keeps_list_indices() {
+    let mut app = App::new();
+    app.posts_state.posts = vec![test_post("bob", "Hello")];
+    app.posts_state.loading = true;
+    assert_eq!(app.posts_state.post_index_to_list_index(0), 1, "Refresh row sits above the posts");
+
+    app.apply_settings_result(Ok(fido_types::UserConfig {
+        reduced_motion: true,
+        ..fido_types::UserConfig::default()
+    }));
+    assert!(app.reduced_motion());
+    assert_eq!(app.posts_state.post_index_to_list_index(0), 0);
+
+    // Toggling it off is an unsaved change until saved
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Settings;
+    app.settings_state.selected_field = SettingsField::ReducedMotion;
+    app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    assert!(app.settings_state.has_unsaved_changes);
+    assert!(app.reduced_motion(), "Applies once saved");
+}
+
+#[test]
+fn test_shift_t_reveals_exact_time_of_selected_post() {
+    let post = |content: &str| test_post("bob", content);
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    app.posts_state.posts = vec![post("first"), post("second")];
+    app.posts_state.list_state.select(Some(1));
+
+    app.handle_key_event(key_event(KeyCode::Char('T'))).unwrap();
+    assert_eq!(app.exact_time_post, Some(app.posts_state.posts[1].id));
+
+    // Pressing it again hides the exact time
+    app.handle_key_event(key_event(KeyCode::Char('T'))).unwrap();
+    assert_eq!(app.exact_time_post, None);
+
+    // 'v' shows the selected post's diff as typed, and colored again
+    let id = app.posts_state.posts[1].id;
+    app.handle_key_event(key_event(KeyCode::Char('v'))).unwrap();
+    assert!(!app.shows_diff(id));
+    assert!(app.shows_diff(app.posts_state.posts[0].id));
+    app.handle_key_event(key_event(KeyCode::Char('v'))).unwrap();
+    assert!(app.shows_diff(id));
+}
+
+#[tokio::test]
+async fn test_share_menu_copies_links_and_prefills_a_dm() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    app.posts_state.posts = vec![test_post("bob", "line one\nline two")];
+    app.posts_state.list_state.select(Some(0));
+    let permalink = app.post_permalink(app.posts_state.posts[0].id);
+
+    app.handle_key_event(key_event(KeyCode::Char('S'))).unwrap();
+    assert!(app.share_menu.show);
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert!(!app.share_menu.show);
+    assert_eq!(app.pending_clipboard.take(), Some(permalink.clone()));
+
+    // Markdown quote keeps every line and links back to the post
+    app.handle_key_event(key_event(KeyCode::Char('S'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    let quote = app.pending_clipboard.take().unwrap();
+    assert!(quote.starts_with("> line one\n> line two\n"));
+    assert!(quote.contains(&format!("[@bob]({})", permalink)));
+
+    // Sending as a DM picks a mutual friend, then fills in the message
+    app.open_share_menu();
+    app.share_post(ShareAction::SendAsDm);
+    assert_eq!(app.current_tab, Tab::DMs);
+    assert!(app.dms_state.show_new_conversation_modal);
+    app.dms_state.available_mutual_friends = vec![UserInfo {
+        id: uuid::Uuid::new_v4().to_string(),
+        username: "carol".to_string(),
+        follower_count: 0,
+        following_count: 0,
+    }];
+    app.start_new_conversation().await.unwrap();
+    assert_eq!(
+        app.dms_state.pending_conversation_username.as_deref(),
+        Some("carol")
+    );
+    assert_eq!(
+        app.get_dm_message_content(),
+        format!("@bob's post: {}", permalink)
+    );
+}
+
+#[tokio::test]
+async fn test_tab_steps_through_post_tokens_then_switches_tab() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    app.posts_state.posts = vec![test_post("bob", "Thanks @alice for #rust tips https://example.com")];
+    app.posts_state.list_state.select(Some(0));
+    let post_id = app.posts_state.posts[0].id;
+
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(
+        app.focused_post_token(),
+        Some(PostToken::Mention("alice".to_string()))
+    );
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.focused_token_in(post_id), Some(2));
+    app.handle_key_event(key_event(KeyCode::BackTab)).unwrap();
+    assert_eq!(
+        app.focused_post_token(),
+        Some(PostToken::Hashtag("rust".to_string()))
+    );
+
+    // Enter on a hashtag filters the feed by it
+    app.open_focused_token().await.unwrap();
+    assert_eq!(
+        app.posts_state.current_filter,
+        PostFilter::Hashtag("rust".to_string())
+    );
+    assert_eq!(app.focused_token, None);
+
+    // Esc drops the focus without quitting
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(app.running);
+    assert_eq!(app.focused_token, None);
+
+    // Past the last token, Tab moves on to the next tab
+    for _ in 0..4 {
+        app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    }
+    assert_ne!(app.current_tab, Tab::Posts);
+}
+
+#[test]
+fn test_near_duplicate_posts_fold_until_e_is_pressed() {
+    let post = |content: &str| test_post("bob", content);
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    let feed = vec![
+        post("Free stickers! #fido"),
+        post("free stickers  #fido"),
+        post("FREE STICKERS!!! #fido"),
+        post("Something else"),
+        post("Free stickers! #fido"),
+    ];
+    app.apply_posts_result(Ok(feed.clone()));
+
+    // Only consecutive repeats fold; the last one is separated by another post
+    let ids: Vec<_> = app.posts_state.posts.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![feed[0].id, feed[3].id, feed[4].id]);
+    assert_eq!(app.posts_state.similar[&feed[0].id].len(), 2);
+
+    app.handle_key_event(key_event(KeyCode::Char('e'))).unwrap();
+    let ids: Vec<_> = app.posts_state.posts.iter().map(|p| p.id).collect();
+    assert_eq!(ids, feed.iter().map(|p| p.id).collect::<Vec<_>>());
+    assert!(app.posts_state.similar.is_empty());
+}
+
+#[tokio::test]
+async fn test_digest_swallows_keys_until_dismissed() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    app.task_runner.spawn(TaskKind::Digest, async move {
+        TaskResult::DigestLoaded(Ok(Some(fido_types::Digest {
+            id: uuid::Uuid::new_v4(),
+            period_start: chrono::Utc::now() - chrono::Duration::days(1),
+            period_end: chrono::Utc::now(),
+            top_posts: Vec::new(),
+            unread_messages: vec![fido_types::UnreadFrom {
+                username: "alice".to_string(),
+                count: 2,
+            }],
+        })))
+    });
+    for _ in 0..100 {
+        if app.task_runner.in_flight() == 0 {
+            break;
+        }
+        tokio::task::yield_now().await;
+        app.process_task_results();
+    }
+    assert!(app.digest.is_some());
+
+    // Tab switching is held back while the digest is open
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.current_tab, Tab::Posts);
+
+    // Esc dismisses the digest rather than quitting
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(app.digest.is_none());
+    assert!(app.running);
+}
+
+#[test]
+fn test_catchup_shown_only_after_a_long_absence() {
+    let now = chrono::Utc::now();
+    assert_eq!(catchup_since(None, now), None, "First login has nothing to catch up on");
+    assert_eq!(catchup_since(Some(now - chrono::Duration::hours(2)), now), None);
+    let long_ago = now - chrono::Duration::hours(13);
+    assert_eq!(catchup_since(Some(long_ago), now), Some(long_ago));
+
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::DMs;
+    app.catchup = Some(fido_types::Catchup {
+        since: long_ago,
+        new_followers: vec!["alice".to_string()],
+        top_posts: Vec::new(),
+        unread_messages: Vec::new(),
+    });
+
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    assert!(app.catchup.is_some());
+
+    // Enter lands on the feed
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert!(app.catchup.is_none());
+    assert_eq!(app.current_tab, Tab::Posts);
+}
+
+#[test]
+fn test_picking_a_server_draft_loads_it_into_the_composer() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.open_composer_new_post();
+    app.composer_state.textarea.insert_str("scratch");
+
+    let draft = |content: &str| fido_types::PostDraft {
+        id: uuid::Uuid::new_v4(),
+        content: content.to_string(),
+        updated_at: chrono::Utc::now(),
+    };
+    let older = draft("line one\nline two");
+    app.drafts.show = true;
+    app.drafts.drafts = vec![draft("newest"), older.clone()];
+
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+
+    assert!(!app.drafts.show);
+    assert!(app.composer_state.is_open());
+    assert_eq!(app.composer_state.get_content(), older.content);
+    assert_eq!(app.composer_state.server_draft, Some(older.id));
+
+    // Closing the composer forgets which draft it came from
+    app.close_composer();
+    assert_eq!(app.composer_state.server_draft, None);
+}
+
+#[tokio::test]
+async fn test_new_posts_can_run_past_the_limit_to_become_gists() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.open_composer_new_post();
+    app.composer_state.textarea.insert_str("a".repeat(280));
+    app.handle_composer_input(key_event(KeyCode::Char('b')));
+    assert_eq!(app.composer_state.char_count(), 281);
+
+    // Enter offers the gist instead of posting
+    app.submit_composer().await.unwrap();
+    assert!(app.composer_state.is_open());
+    assert!(app.posts_state.error.as_deref().is_some_and(|e| e.contains("Ctrl+G")));
+
+    // Anonymous posts can't be gists, so they stop at the limit
+    app.composer_state.anonymous = true;
+    app.handle_composer_input(key_event(KeyCode::Char('c')));
+    assert_eq!(app.composer_state.char_count(), 281);
+    app.submit_composer_as_gist().await.unwrap();
+    assert!(app.composer_state.is_open());
+
+    // Ctrl+G is only for text that doesn't fit
+    app.composer_state.anonymous = false;
+    app.composer_state.textarea = tui_textarea::TextArea::default();
+    app.composer_state.textarea.insert_str("short");
+    app.submit_composer_as_gist().await.unwrap();
+    assert!(app.posts_state.error.as_deref().is_some_and(|e| e.contains("fits in a post")));
+}
+
+#[test]
+fn test_composer_wraps_long_lines_for_display_only() {
+    let lines = vec!["hello wide world".to_string()];
+    let (wrapped, cursor) = crate::text_wrapper::wrap_for_display(&lines, (0, 11), 10);
+    assert_eq!(wrapped, vec!["hello wide", "world"]);
+    assert_eq!(cursor, (1, 0));
+
+    // A cursor in the spaces at a break stays on the line above
+    let (wrapped, cursor) =
+        crate::text_wrapper::wrap_for_display(&["hello   world".to_string()], (0, 6), 8);
+    assert_eq!(wrapped, vec!["hello", "world"]);
+    assert_eq!(cursor, (0, 5));
+
+    // Wide characters take two columns each
+    let (wrapped, cursor) =
+        crate::text_wrapper::wrap_for_display(&["日本語です".to_string()], (0, 5), 4);
+    assert_eq!(wrapped, vec!["日本", "語で", "す"]);
+    assert_eq!(cursor, (2, 1));
+
+    // Words wider than the box are split
+    let (wrapped, cursor) =
+        crate::text_wrapper::wrap_for_display(&["abcdefg".to_string()], (0, 7), 3);
+    assert_eq!(wrapped, vec!["abc", "def", "g"]);
+    assert_eq!(cursor, (2, 1));
+}
+
+#[test]
+fn test_event_command_parses_and_rsvps_move_the_counts() {
+    let event = parse_event_command("/event Rust meetup | 2030-05-04 18:30 | Room 4\nBring a laptop")
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.title, "Rust meetup");
+    assert_eq!(event.location, "Room 4");
+    assert_eq!(event.content, "Bring a laptop");
+    let local = event.starts_at.with_timezone(&chrono::Local);
+    assert_eq!(local.format("%Y-%m-%d %H:%M").to_string(), "2030-05-04 18:30");
+
+    // Without a description the title is the post's text
+    let bare = parse_event_command("/event Standup | 2030-05-05 09:00 | https://meet.example")
+        .unwrap()
+        .unwrap();
+    assert_eq!(bare.content, "Standup");
+
+    assert!(parse_event_command("just a post").is_none());
+    assert!(parse_event_command("/eventful day").is_none());
+    assert!(parse_event_command("/event Rust meetup | tomorrow | Room 4").unwrap().is_err());
+    assert!(parse_event_command("/event Rust meetup | 2030-05-04 18:30").unwrap().is_err());
+
+    let mut details = fido_types::EventDetails {
+        title: event.title,
+        starts_at: event.starts_at,
+        location: event.location,
+        going_count: 2,
+        interested_count: 0,
+        my_rsvp: None,
+    };
+    apply_rsvp(&mut details, Some(fido_types::RsvpStatus::Going));
+    assert_eq!((details.going_count, details.interested_count), (3, 0));
+    apply_rsvp(&mut details, Some(fido_types::RsvpStatus::Interested));
+    assert_eq!((details.going_count, details.interested_count), (2, 1));
+    apply_rsvp(&mut details, None);
+    assert_eq!((details.going_count, details.interested_count), (2, 0));
+    assert_eq!(details.my_rsvp, None);
+}
+
+#[test]
+fn test_muting_holds_badges_and_toasts_until_unmuted() {
+    let mut app = App::new();
+    let alice = uuid::Uuid::new_v4();
+    app.settings_state.original_config = Some(fido_types::UserConfig {
+        do_not_disturb: true,
+        ..fido_types::UserConfig::default()
+    });
+    assert_eq!(app.quiet_reason_at(12), Some(QuietReason::DoNotDisturb));
+    app.dms_state.unread_counts.insert(alice, 1);
+
+    app.apply_quiet_mode(true);
+    app.dms_state.unread_counts.insert(alice, 4);
+    app.notify("✉ 3 new direct messages");
+    assert_eq!(app.unread_dm_count(), 4, "Counts keep accumulating");
+    assert_eq!(app.visible_unread_dm_count(), 1, "Badges don't move while muted");
+    assert!(app.toasts.is_empty(), "Notification toasts are held back");
+
+    app.apply_quiet_mode(false);
+    assert_eq!(app.visible_unread_dm_count(), 4);
+    let summary: Vec<_> = app.toasts.iter().map(|t| t.message.clone()).collect();
+    assert_eq!(summary, vec!["✉ 3 new messages while notifications were muted"]);
+    assert_eq!(app.quiet_state.missed_notifications, 0);
+}
+
+#[test]
+fn test_watched_thread_poll_notifies_only_new_replies() {
+    let mut app = App::new();
+    let thread = |new_replies| fido_types::WatchedThread {
+        post_id: uuid::Uuid::nil(),
+        author_username: "bob".to_string(),
+        content: "Which terminal emulator do you all use?".to_string(),
+        new_replies,
+    };
+
+    app.update_watched_threads(&[thread(0)]);
+    assert!(app.toasts.is_empty());
+
+    app.update_watched_threads(&[thread(2)]);
+    let messages: Vec<_> = app.toasts.iter().map(|t| t.message.clone()).collect();
+    assert_eq!(
+        messages,
+        vec!["💬 2 new replies in @bob's thread \"Which terminal emulator do you…\""]
+    );
+
+    // The same unseen replies on the next poll aren't announced again
+    app.update_watched_threads(&[thread(2)]);
+    assert_eq!(app.toasts.iter().count(), 1);
+    assert_eq!(app.watched_threads.get(&uuid::Uuid::nil()), Some(&2));
+}
+
+#[test]
+fn test_crosspost_quotes_the_picked_message() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::DMs;
+    app.dms_state.conversations = vec![Conversation {
+        other_user_id: uuid::Uuid::new_v4(),
+        other_username: "bob".to_string(),
+        last_message: String::new(),
+        last_message_time: chrono::Utc::now(),
+        unread_count: 0,
+        pinned: false,
+    }];
+    app.dms_state.selected_conversation_index = Some(0);
+    let message = |from: &str, content: &str| fido_types::DirectMessage {
+        id: uuid::Uuid::new_v4(),
+        from_user_id: uuid::Uuid::new_v4(),
+        to_user_id: uuid::Uuid::new_v4(),
+        from_username: from.to_string(),
+        to_username: String::new(),
+        content: content.to_string(),
+        created_at: chrono::Utc::now(),
+        is_read: true,
+        attachment: None,
+    };
+    app.dms_state.messages = vec![
+        message("bob", "ship it\nthe build is green"),
+        message("alice", "nice"),
+    ];
+    let picked = app.dms_state.messages[0].id;
+
+    // '[' twice steps back from the newest message to the first one
+    app.handle_key_event(key_event(KeyCode::Char('['))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('['))).unwrap();
+    assert_eq!(app.dms_state.selected_message, Some(0));
+    app.handle_key_event(key_event_with_modifiers(KeyCode::Char('x'), KeyModifiers::CONTROL))
+        .unwrap();
+
+    assert_eq!(
+        app.composer_state.mode,
+        Some(ComposerMode::CrossPost {
+            message_id: picked,
+            other_username: "bob".to_string(),
+        })
+    );
+    assert_eq!(
+        app.composer_state.get_content(),
+        "@bob wrote:\n> ship it\n> the build is green\n\n"
+    );
+    assert_eq!(app.input_mode, InputMode::Typing);
+}
+
+#[test]
+fn test_dm_attachment_is_picked_up_and_expands_in_place() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::DMs;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("fix.rs");
+    std::fs::write(&path, "fn fix() {}\n").unwrap();
+    app.attach_dm_file(path.to_str().unwrap());
+    assert_eq!(
+        app.dms_state.pending_attachment.as_ref().map(|a| a.filename.as_str()),
+        Some("fix.rs")
+    );
+    app.attach_dm_file("");
+    assert!(app.dms_state.pending_attachment.is_none());
+
+    let message = |attachment| fido_types::DirectMessage {
+        id: uuid::Uuid::new_v4(),
+        from_user_id: uuid::Uuid::new_v4(),
+        to_user_id: uuid::Uuid::new_v4(),
+        from_username: "bob".to_string(),
+        to_username: String::new(),
+        content: String::new(),
+        created_at: chrono::Utc::now(),
+        is_read: true,
+        attachment,
+    };
+    let snippet = fido_types::DmAttachment {
+        filename: "fix.rs".to_string(),
+        content: "fn fix() {}".to_string(),
+    };
+    app.dms_state.messages = vec![message(Some(snippet)), message(None)];
+    let with_file = app.dms_state.messages[0].id;
+
+    let ctrl_e = key_event_with_modifiers(KeyCode::Char('e'), KeyModifiers::CONTROL);
+
+    // With nothing picked, Ctrl+E opens the newest attachment
+    app.handle_key_event(ctrl_e).unwrap();
+    assert!(app.dms_state.expanded_attachments.contains(&with_file));
+    app.handle_key_event(ctrl_e).unwrap();
+    assert!(app.dms_state.expanded_attachments.is_empty());
+
+    // A picked message without one has nothing to expand
+    app.handle_key_event(key_event(KeyCode::Char('['))).unwrap();
+    app.handle_key_event(ctrl_e).unwrap();
+    assert!(app.dms_state.expanded_attachments.is_empty());
+}
+
+#[test]
+fn test_crosspost_poll_asks_for_consent_and_reports_decisions() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.auth_state.current_user = Some(User {
+        id: uuid::Uuid::new_v4(),
+        username: "alice".to_string(),
+        bio: None,
+        join_date: chrono::Utc::now(),
+        is_test_user: true,
+        badges: Vec::new(),
+        karma: 0,
+    });
+    let crosspost = |requester: &str, approver: &str, status| fido_types::Crosspost {
+        id: uuid::Uuid::from_u128(if requester == "alice" { 1 } else { 2 }),
+        message_id: uuid::Uuid::new_v4(),
+        requester_username: requester.to_string(),
+        approver_username: approver.to_string(),
+        content: "> the build is green".to_string(),
+        status,
+        created_at: chrono::Utc::now(),
+        post_id: None,
+    };
+
+    app.update_crossposts(vec![
+        crosspost("alice", "bob", fido_types::CrosspostStatus::Pending),
+        crosspost("carol", "alice", fido_types::CrosspostStatus::Pending),
+    ]);
+    assert!(app.crosspost_prompt_open());
+    assert_eq!(app.crossposts.prompt.as_ref().unwrap().requester_username, "carol");
+    assert!(app.toasts.is_empty());
+
+    // Put off until next session, so the next poll doesn't ask again
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    app.update_crossposts(vec![
+        crosspost("alice", "bob", fido_types::CrosspostStatus::Approved),
+        crosspost("carol", "alice", fido_types::CrosspostStatus::Pending),
+    ]);
+    assert!(!app.crosspost_prompt_open());
+    let messages: Vec<_> = app.toasts.iter().map(|t| t.message.clone()).collect();
+    assert_eq!(messages, vec!["📣 @bob approved your cross-post; it's in the feed"]);
+}
+
+#[tokio::test]
+async fn test_failed_dm_is_retried_and_joins_the_conversation_once_sent() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::DMs;
+    let bob = uuid::Uuid::new_v4();
+    app.dms_state.conversations = vec![Conversation {
+        other_user_id: bob,
+        other_username: "bob".to_string(),
+        last_message: "hi".to_string(),
+        last_message_time: chrono::Utc::now() - chrono::Duration::hours(1),
+        unread_count: 0,
+        pinned: false,
+    }];
+    app.dms_state.selected_conversation_index = Some(0);
+    let local_id = uuid::Uuid::new_v4();
+    app.dms_state.outbox.push(OutgoingMessage {
+        local_id,
+        to_user_id: bob,
+        to_username: "bob".to_string(),
+        content: "on my way".to_string(),
+        attachment: None,
+        queued_at: chrono::Utc::now(),
+        state: DeliveryState::Pending,
+        attempts: 0,
+        retry_at: None,
+        error: None,
+    });
+
+    // A dropped connection is retried on its own, until the retries run out
+    app.apply_dm_delivery(local_id, Err("Network error: connection reset".to_string()), true);
+    let queued = &app.dms_state.outbox[0];
+    assert_eq!((queued.state, queued.attempts), (DeliveryState::Failed, 1));
+    assert!(queued.retry_at.is_some() && queued.error.is_some());
+    app.dms_state.outbox[0].attempts = DM_AUTO_RETRIES;
+    app.apply_dm_delivery(local_id, Err("Network error: connection reset".to_string()), true);
+    assert!(app.dms_state.outbox[0].retry_at.is_none());
+    assert!(app.has_failed_dm());
+
+    // 'r' tries again by hand
+    app.handle_key_event(key_event(KeyCode::Char('r'))).unwrap();
+    let queued = &app.dms_state.outbox[0];
+    assert_eq!((queued.state, queued.attempts), (DeliveryState::Pending, 0));
+    assert_eq!(app.input_mode, InputMode::Navigation, "'r' retried instead of typing");
+
+    let sent = fido_types::DirectMessage {
+        id: uuid::Uuid::new_v4(),
+        from_user_id: uuid::Uuid::new_v4(),
+        to_user_id: bob,
+        from_username: "alice".to_string(),
+        to_username: "bob".to_string(),
+        content: "on my way".to_string(),
+        created_at: chrono::Utc::now(),
+        is_read: false,
+        attachment: None,
+    };
+    app.apply_dm_delivery(local_id, Ok(sent), false);
+    assert!(app.dms_state.outbox.is_empty());
+    assert_eq!(app.dms_state.messages.len(), 1);
+    assert_eq!(app.dms_state.conversations[0].last_message, "on my way");
+}
+
+#[tokio::test]
+async fn test_github_suggestions_wait_for_the_catchup_and_close_with_esc() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.github_suggestions = Some(vec![fido_types::GitHubSuggestion {
+        user_id: uuid::Uuid::new_v4(),
+        username: "bob".to_string(),
+        github_login: "bobby".to_string(),
+    }]);
+    app.catchup = Some(fido_types::Catchup {
+        since: chrono::Utc::now(),
+        new_followers: Vec::new(),
+        top_posts: Vec::new(),
+        unread_messages: Vec::new(),
+    });
+    assert!(!app.github_suggestions_open(), "The catch-up goes first");
+
+    app.catchup = None;
+    assert!(app.github_suggestions_open());
+    // Other keys don't reach the feed underneath
+    app.handle_key_event(key_event(KeyCode::Char('n'))).unwrap();
+    assert!(!app.composer_state.is_open());
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(app.github_suggestions.is_none());
+}
+
 // ===== Task 13: Test New DM and Input Mode Features =====
 
 /// Helper to create a KeyEvent with modifiers
@@ -465,21 +1408,7 @@ fn test_typing_mode_prevents_navigation_shortcuts() {
     app.input_mode = InputMode::Typing;
 
     // Add some posts
-    app.posts_state.posts = vec![Post {
-        id: uuid::Uuid::new_v4(),
-        author_id: uuid::Uuid::new_v4(),
-        author_username: "user1".to_string(),
-        content: "Post 1".to_string(),
-        created_at: chrono::Utc::now(),
-        upvotes: 0,
-        downvotes: 0,
-        hashtags: Vec::new(),
-        user_vote: None,
-        parent_post_id: None,
-        reply_count: 0,
-        reply_to_user_id: None,
-        reply_to_username: None,
-    }];
+    app.posts_state.posts = vec![test_post("user1", "Post 1")];
     app.posts_state.list_state.select(Some(0));
 
     let initial_upvotes = app.posts_state.posts[0].upvotes;
@@ -508,19 +1437,9 @@ fn test_navigation_mode_allows_shortcuts() {
 
     // Add some posts
     app.posts_state.posts = vec![Post {
-        id: uuid::Uuid::new_v4(),
-        author_id: uuid::Uuid::new_v4(),
-        author_username: "user1".to_string(),
-        content: "Post 1".to_string(),
-        created_at: chrono::Utc::now(),
         upvotes: 5,
         downvotes: 2,
-        hashtags: Vec::new(),
-        user_vote: None,
-        parent_post_id: None,
-        reply_count: 0,
-        reply_to_user_id: None,
-        reply_to_username: None,
+        ..test_post("user1", "Post 1")
     }];
     app.posts_state.list_state.select(Some(0));
 
@@ -623,3 +1542,1499 @@ fn test_current_conversation_user_tracks_open_conversation() {
         "Current conversation user should be tracked"
     );
 }
+
+#[test]
+fn test_dm_drafts_follow_their_conversation() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::DMs;
+    let conversation = |name: &str| Conversation {
+        other_user_id: uuid::Uuid::new_v4(),
+        other_username: name.to_string(),
+        last_message: String::new(),
+        last_message_time: chrono::Utc::now(),
+        unread_count: 0,
+        pinned: false,
+    };
+    app.dms_state.conversations = vec![conversation("alice"), conversation("bob")];
+    let alice = app.dms_state.conversations[0].other_user_id;
+    app.dms_state.selected_conversation_index = Some(0);
+
+    // Half a message to alice, then Esc to go look at bob's conversation
+    app.input_mode = InputMode::Typing;
+    app.dms_state.message_textarea.insert_str("running late, be there at");
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(app.is_dm_message_empty());
+    assert_eq!(app.dms_state.drafts[&alice], "running late, be there at");
+
+    app.handle_key_event(key_event(KeyCode::Down)).unwrap();
+    app.restore_dm_draft();
+    assert!(app.is_dm_message_empty(), "Bob's conversation has no draft");
+
+    app.handle_key_event(key_event(KeyCode::Up)).unwrap();
+    app.restore_dm_draft();
+    assert_eq!(app.get_dm_message_content(), "running late, be there at");
+
+    // Clearing the text and leaving again drops the draft
+    app.clear_dm_message();
+    app.stash_dm_draft();
+    assert!(app.dms_state.drafts.is_empty());
+}
+
+#[test]
+fn test_conversations_marked_unread_or_reminded_keep_their_badge() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::DMs;
+    let conversation = |id, name: &str| Conversation {
+        other_user_id: id,
+        other_username: name.to_string(),
+        last_message: String::new(),
+        last_message_time: chrono::Utc::now(),
+        unread_count: 0,
+        pinned: false,
+    };
+    let alice = uuid::Uuid::new_v4();
+    let bob = uuid::Uuid::new_v4();
+    app.dms_state.conversations = vec![conversation(alice, "alice"), conversation(bob, "bob")];
+    app.dms_state.selected_conversation_index = Some(0);
+
+    // Ctrl+U marks alice unread, and a poll with nothing new doesn't undo it
+    app.handle_key_event(key_event_with_modifiers(KeyCode::Char('u'), KeyModifiers::CONTROL))
+        .unwrap();
+    assert_eq!(app.input_mode, InputMode::Navigation, "u isn't typed into the message");
+    app.apply_unread_poll(Ok(vec![conversation(alice, "alice"), conversation(bob, "bob")]));
+    assert_eq!(app.unread_dm_count(), 1);
+    assert_eq!(app.dms_state.conversations[0].unread_count, 1);
+
+    // Ctrl+R then a delay sets a reminder on bob
+    app.handle_key_event(key_event(KeyCode::Down)).unwrap();
+    app.handle_key_event(key_event_with_modifiers(KeyCode::Char('r'), KeyModifiers::CONTROL))
+        .unwrap();
+    for c in "3h".chars() {
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+    }
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert!(app.dms_state.reminder_input.is_none());
+    assert_eq!(app.input_mode, InputMode::Navigation);
+    let due = app.dms_state.follow_ups.reminders[&bob].due;
+    assert!(due > chrono::Utc::now() + chrono::Duration::minutes(179));
+
+    // Nothing happens before it's due, then bob's badge comes back with a toast
+    app.toasts.take_fresh();
+    app.fire_due_dm_reminders(due - chrono::Duration::minutes(1));
+    assert_eq!(app.unread_dm_count(), 1);
+    app.fire_due_dm_reminders(due);
+    assert!(app.dms_state.follow_ups.reminders.is_empty());
+    assert_eq!(app.unread_dm_count(), 2);
+    assert_eq!(app.dms_state.conversations[1].unread_count, 1);
+    assert_eq!(app.toasts.take_fresh(), vec!["⏰ Reminder: follow up with @bob".to_string()]);
+}
+
+#[test]
+fn test_pinned_conversations_sort_first_and_keep_selection() {
+    let mut app = App::new();
+    let conversation = |name: &str, minutes_ago: i64, pinned: bool| Conversation {
+        other_user_id: uuid::Uuid::new_v4(),
+        other_username: name.to_string(),
+        last_message: String::new(),
+        last_message_time: chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+        unread_count: 0,
+        pinned,
+    };
+    app.dms_state.conversations = vec![
+        conversation("recent", 1, false),
+        conversation("older", 30, false),
+        conversation("oldest", 90, false),
+    ];
+    app.dms_state.selected_conversation_index = Some(2);
+
+    app.dms_state.conversations[2].pinned = true;
+    app.sort_conversations();
+    let order: Vec<_> = app
+        .dms_state
+        .conversations
+        .iter()
+        .map(|c| c.other_username.as_str())
+        .collect();
+    assert_eq!(order, vec!["oldest", "recent", "older"]);
+    assert_eq!(
+        app.dms_state.selected_conversation_index,
+        Some(0),
+        "Selection follows the pinned conversation"
+    );
+}
+
+#[tokio::test]
+async fn test_background_task_results_are_applied_when_drained() {
+    let mut app = App::new();
+    app.posts_state.loading = true;
+    app.task_runner.spawn(TaskKind::Posts, async {
+        TaskResult::PostsLoaded(Err("connection refused".to_string()))
+    });
+
+    // Results only land when the event loop drains the channel
+    for _ in 0..100 {
+        if app.task_runner.in_flight() == 0 {
+            break;
+        }
+        tokio::task::yield_now().await;
+        app.process_task_results();
+    }
+
+    assert_eq!(app.task_runner.in_flight(), 0);
+    assert!(!app.posts_state.loading, "Loading flag should be cleared");
+    assert!(
+        app.posts_state
+            .error
+            .as_deref()
+            .is_some_and(|e| e.starts_with("Network Error")),
+        "Error should be categorized like an inline load"
+    );
+}
+
+#[tokio::test]
+async fn test_feed_load_waits_for_settings_on_startup() {
+    let mut app = App::new();
+    app.posts_state.awaiting_settings = true;
+    app.task_runner.spawn(TaskKind::Settings, async {
+        TaskResult::SettingsLoaded(Ok(fido_types::UserConfig::default()))
+    });
+
+    for _ in 0..100 {
+        if !app.posts_state.awaiting_settings {
+            break;
+        }
+        tokio::task::yield_now().await;
+        app.process_task_results();
+    }
+
+    assert!(app.settings_state.config.is_some(), "Settings should be applied");
+    assert!(!app.posts_state.awaiting_settings);
+    assert!(
+        app.posts_state.loading,
+        "Feed load should start (showing skeletons) once settings arrive"
+    );
+}
+
+#[tokio::test]
+async fn test_superseded_task_result_is_discarded() {
+    let mut app = App::new();
+    app.posts_state.loading = true;
+    app.task_runner.spawn(TaskKind::Posts, async {
+        TaskResult::PostsLoaded(Err("connection refused".to_string()))
+    });
+    // A newer load of the same kind replaces the first one
+    app.task_runner
+        .spawn(TaskKind::Posts, async { TaskResult::PostsLoaded(Ok(Vec::new())) });
+
+    for _ in 0..100 {
+        if app.task_runner.in_flight() == 0 {
+            break;
+        }
+        tokio::task::yield_now().await;
+        app.process_task_results();
+    }
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+        app.process_task_results();
+    }
+
+    assert!(!app.posts_state.loading);
+    assert!(
+        app.posts_state.error.is_none(),
+        "Stale error from the superseded load should not be applied"
+    );
+}
+
+#[tokio::test]
+async fn test_closing_user_search_cancels_in_flight_search() {
+    let mut app = App::new();
+    app.open_user_search_modal();
+    app.user_search_state.search_query = "al".to_string();
+    app.task_runner.spawn(TaskKind::UserSearch, async {
+        TaskResult::UserSearchCompleted {
+            query: "al".to_string(),
+            result: Ok(vec![UserSearchResult {
+                id: "550e8400-e29b-41d4-a716-446655440001".to_string(),
+                username: "alice".to_string(),
+            }]),
+        }
+    });
+
+    app.close_user_search_modal();
+    assert!(!app.task_runner.is_running(TaskKind::UserSearch));
+
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+        app.process_task_results();
+    }
+
+    assert!(
+        app.user_search_state.search_results.is_empty(),
+        "Results from a cancelled search should not be applied"
+    );
+}
+
+#[test]
+fn test_search_cache_evicts_least_recently_used() {
+    let result = |name: &str| {
+        vec![UserSearchResult {
+            id: name.to_string(),
+            username: name.to_string(),
+        }]
+    };
+    let mut cache = SearchCache::new(2);
+    cache.insert("al".to_string(), result("alice"));
+    cache.insert("bo".to_string(), result("bob"));
+
+    // Touch "al" so "bo" becomes the eviction candidate
+    assert!(cache.get("al").is_some());
+    cache.insert("ch".to_string(), result("charlie"));
+
+    assert!(cache.get("bo").is_none());
+    assert_eq!(cache.get("al").unwrap()[0].username, "alice");
+    assert_eq!(cache.get("ch").unwrap()[0].username, "charlie");
+}
+
+#[tokio::test]
+async fn test_user_search_is_debounced_and_served_from_cache() {
+    let mut app = App::new();
+    app.open_user_search_modal();
+
+    app.user_search_state.search_query = "ali".to_string();
+    app.queue_user_search();
+    app.tick_user_search();
+    assert!(
+        !app.task_runner.is_running(TaskKind::UserSearch),
+        "Search should wait for the debounce period"
+    );
+    assert!(app.user_search_state.search_due.is_some());
+
+    app.user_search_state.cache.insert(
+        "alice".to_string(),
+        vec![UserSearchResult {
+            id: "550e8400-e29b-41d4-a716-446655440001".to_string(),
+            username: "alice".to_string(),
+        }],
+    );
+    app.user_search_state.search_query = "alice".to_string();
+    app.queue_user_search();
+
+    assert!(app.user_search_state.search_due.is_none());
+    assert!(!app.user_search_state.loading);
+    assert_eq!(app.user_search_state.search_results.len(), 1);
+}
+
+#[tokio::test]
+async fn test_profile_posts_page_is_appended() {
+    let post = |content: &str| test_post("alice", content);
+
+    let mut app = App::new();
+    app.profile_state.user_posts = vec![post("first page")];
+    app.profile_state.has_more_posts = true;
+    app.task_runner.spawn(TaskKind::ProfilePosts, async move {
+        TaskResult::ProfilePostsLoaded(Ok(vec![post("second page")]))
+    });
+
+    for _ in 0..100 {
+        if app.task_runner.in_flight() == 0 {
+            break;
+        }
+        tokio::task::yield_now().await;
+        app.process_task_results();
+    }
+
+    let contents: Vec<&str> = app
+        .profile_state
+        .user_posts
+        .iter()
+        .map(|p| p.content.as_str())
+        .collect();
+    assert_eq!(contents, vec!["first page", "second page"]);
+    assert!(
+        !app.profile_state.has_more_posts,
+        "A short page means there is nothing older to fetch"
+    );
+}
+
+#[test]
+fn test_composer_limit_counts_expanded_shortcodes() {
+    let mut app = App::new();
+    app.open_composer_new_post();
+    // Over 280 characters as typed, but only 43 once the shortcodes expand
+    let line = ":smile:".repeat(10);
+    app.composer_state.textarea = TextArea::from([line.as_str(); 4]);
+    app.composer_state
+        .textarea
+        .move_cursor(tui_textarea::CursorMove::Bottom);
+    app.composer_state
+        .textarea
+        .move_cursor(tui_textarea::CursorMove::End);
+    assert_eq!(app.composer_state.char_count(), 43);
+
+    app.handle_composer_input(key_event(KeyCode::Char('x')));
+    assert!(app.composer_state.get_content().ends_with('x'));
+    assert_eq!(app.composer_state.char_count(), 44);
+}
+
+#[test]
+fn test_pasting_keeps_line_breaks() {
+    let mut app = App::new();
+    app.open_composer_new_post();
+    let long_line = "word ".repeat(30);
+    assert!(app.insert_text(&format!("first\r\nsecond\r{}", long_line.trim_end())));
+
+    // The composer wraps long lines when drawing them, not in the text
+    let lines = app.composer_state.textarea.lines().to_vec();
+    assert_eq!(lines, ["first", "second", long_line.trim_end()]);
+    assert_eq!(app.composer_state.textarea.cursor().0, 2);
+
+    // Outside the composer, a paste on the DMs tab starts a message
+    app.close_composer();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    assert!(!app.insert_text("日本語"), "Nothing on the feed takes text");
+    app.current_tab = Tab::DMs;
+    assert!(app.insert_text("hello\nthere"));
+    assert_eq!(app.input_mode, InputMode::Typing);
+    assert_eq!(app.get_dm_message_content(), "hello\nthere");
+}
+
+#[tokio::test]
+async fn test_switching_server_profile_swaps_client_and_clears_state() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut app = App::new();
+    app.server_config =
+        crate::server_config::ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+    app.server_config.upsert("work", "http://work.invalid").unwrap();
+    app.server_config.upsert("public", "http://public.invalid").unwrap();
+    let work = app.server_config.get("work").cloned().unwrap();
+    app.use_profile(&work);
+
+    app.current_screen = Screen::Main;
+    app.posts_state.posts = vec![test_post("worker", "from work")];
+    app.task_runner
+        .spawn(TaskKind::Posts, async { TaskResult::PostsLoaded(Ok(Vec::new())) });
+
+    app.handle_key_event(key_event_with_modifiers(
+        KeyCode::Char('S'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    ))
+    .unwrap();
+    assert!(app.server_switcher.show_modal);
+    assert_eq!(app.server_switcher.selected_index, 0);
+
+    app.handle_key_event(key_event(KeyCode::Down)).unwrap();
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+
+    assert!(!app.server_switcher.show_modal);
+    assert!(app.server_switcher.switched);
+    assert_eq!(app.active_profile.as_deref(), Some("public"));
+    assert!(app.posts_state.posts.is_empty());
+    // No stored session for the new profile, so it's back to the auth screen
+    assert_eq!(app.current_screen, Screen::Auth);
+    assert!(!app.task_runner.is_running(TaskKind::Posts));
+    assert_eq!(
+        crate::server_config::ServerConfigManager::with_dir(temp_dir.path().to_path_buf())
+            .active()
+            .map(|p| p.name.clone()),
+        Some("public".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_switch_account_lists_saved_accounts_and_resumes_one() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut app = App::new();
+    app.server_config =
+        crate::server_config::ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+    app.server_config.upsert("work", "http://work.invalid").unwrap();
+    let work = app.server_config.get("work").cloned().unwrap();
+    app.use_profile(&work);
+
+    app.remember_account("bob", "bob-token-123");
+    app.remember_account("alice", "alice-token-123");
+    assert_eq!(app.auth_state.saved_accounts, vec!["alice", "bob"]);
+
+    app.current_screen = Screen::Main;
+    app.auth_state.current_user = Some(User {
+        id: uuid::Uuid::new_v4(),
+        username: "alice".to_string(),
+        bio: None,
+        join_date: chrono::Utc::now(),
+        is_test_user: true,
+        badges: Vec::new(),
+        karma: 0,
+    });
+
+    app.handle_key_event(key_event(KeyCode::Char('A'))).unwrap();
+    assert_eq!(app.current_screen, Screen::Auth);
+    assert!(app.auth_state.current_user.is_none());
+    // Switching away doesn't forget the account
+    assert_eq!(app.auth_state.saved_accounts, vec!["alice", "bob"]);
+
+    app.handle_key_event(key_event(KeyCode::Char('2'))).unwrap();
+    assert_eq!(app.auth_state.switching_account.as_deref(), Some("bob"));
+    assert!(app.auth_state.restoring_session);
+    assert!(app.task_runner.is_running(TaskKind::Session));
+
+    // An expired token drops back to the auth screen with an explanation
+    app.task_runner.cancel_all();
+    app.apply_session_restored(None);
+    assert_eq!(app.current_screen, Screen::Auth);
+    assert!(app.auth_state.switching_account.is_none());
+    assert!(app
+        .auth_state
+        .error
+        .as_deref()
+        .is_some_and(|e| e.contains("@bob")));
+}
+
+#[test]
+fn test_log_viewer_opens_on_ctrl_l_and_filters_lines() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+
+    app.handle_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL))
+        .unwrap();
+    assert!(app.log_viewer.show);
+
+    for c in "/Posts".chars() {
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+    }
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert_eq!(app.log_viewer.search, "Posts");
+    assert!(!app.log_viewer.searching);
+
+    let line = |level, message: &str| crate::logging::LogLine {
+        time: chrono::Local::now(),
+        level,
+        target: "fido::app".to_string(),
+        message: message.to_string(),
+    };
+    assert!(app.log_viewer.matches(&line(log::Level::Debug, "Loaded 25 posts")));
+    assert!(!app.log_viewer.matches(&line(log::Level::Debug, "Settings saved")));
+
+    // Trace → Debug → Info
+    app.handle_key_event(key_event(KeyCode::Char('f'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('f'))).unwrap();
+    assert!(!app.log_viewer.matches(&line(log::Level::Debug, "Loaded 25 posts")));
+    assert!(app.log_viewer.matches(&line(log::Level::Warn, "Retrying posts")));
+
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(!app.log_viewer.show);
+}
+
+#[tokio::test]
+async fn test_diagnostics_open_on_ctrl_shift_d_and_average_pings() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+
+    app.handle_key_event(KeyEvent::new(
+        KeyCode::Char('D'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    ))
+    .unwrap();
+    assert!(app.diagnostics.show);
+    // Opening pings straight away
+    assert!(app.diagnostics.last_ping.is_some());
+    assert!(!app.diagnostics.ping_due());
+
+    // The screen keeps the keys from the feed underneath
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.current_tab, Tab::Posts);
+
+    app.diagnostics.record_ping(Ok(("0.1.0".to_string(), std::time::Duration::from_millis(40))));
+    app.diagnostics.record_ping(Ok(("0.1.0".to_string(), std::time::Duration::from_millis(60))));
+    assert_eq!(app.diagnostics.server_version.as_deref(), Some("0.1.0"));
+    assert_eq!(app.diagnostics.average_latency(), Some(std::time::Duration::from_millis(50)));
+
+    app.diagnostics.record_ping(Err("Network error: connection refused".to_string()));
+    assert!(app.diagnostics.error.is_some());
+    assert_eq!(app.diagnostics.latencies.len(), 2);
+
+    // Only the newest round trips count
+    for _ in 0..DiagnosticsState::MAX_SAMPLES {
+        app.diagnostics.record_ping(Ok(("0.1.0".to_string(), std::time::Duration::from_millis(10))));
+    }
+    assert!(app.diagnostics.error.is_none());
+    assert_eq!(app.diagnostics.average_latency(), Some(std::time::Duration::from_millis(10)));
+
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(!app.diagnostics.show);
+    assert!(!app.diagnostics.ping_due());
+}
+
+#[test]
+fn test_version_handshake_offers_upgrades_and_refuses_incompatible_servers() {
+    let mut app = App::new();
+    let newer = |version: &str, min_client_version: Option<&str>| {
+        Ok(Some(fido_types::ServerVersion {
+            version: version.to_string(),
+            min_client_version: min_client_version.map(str::to_string),
+        }))
+    };
+
+    // Servers from before the handshake are left alone
+    app.apply_version_check(Ok(None));
+    assert!(app.version_check.is_none());
+
+    app.apply_version_check(newer("99.0.0", Some(CLIENT_VERSION)));
+    assert!(matches!(
+        app.version_check,
+        Some(fido_types::version::Compatibility::UpgradeAvailable { .. })
+    ));
+    assert!(!app.version_incompatible());
+    assert!(app.toasts.iter().any(|t| t.message.contains("fido 99.0.0 is available")));
+
+    app.apply_version_check(newer("99.0.0", Some("99.0.0")));
+    assert!(app.version_incompatible());
+
+    // Nothing but quitting gets past the explanation
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.current_screen, Screen::Auth);
+    assert!(app.running);
+    app.handle_key_event(key_event(KeyCode::Char('q'))).unwrap();
+    assert!(!app.running);
+}
+
+#[test]
+fn test_recovered_draft_reopens_the_composer() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let parent_post_id = Uuid::new_v4();
+    app.recovered_draft = Some(crate::crash::Draft {
+        mode: ComposerMode::Reply {
+            parent_post_id,
+            parent_author: "bob".to_string(),
+            parent_content: "Shipping today".to_string(),
+        },
+        content: "Nice!\nCan't wait".to_string(),
+    });
+
+    // Other shortcuts wait until the prompt is answered
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.current_tab, Tab::Posts);
+
+    app.handle_key_event(key_event(KeyCode::Char('y'))).unwrap();
+    assert!(app.recovered_draft.is_none());
+    assert!(matches!(
+        app.composer_state.mode,
+        Some(ComposerMode::Reply { parent_post_id: id, .. }) if id == parent_post_id
+    ));
+    assert_eq!(app.composer_state.get_content(), "Nice!\nCan't wait");
+}
+
+#[tokio::test]
+async fn test_rejected_heartbeat_asks_for_a_new_login() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut app = App::new();
+    app.server_config =
+        crate::server_config::ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+    app.server_config.upsert("work", "http://work.invalid").unwrap();
+    let work = app.server_config.get("work").cloned().unwrap();
+    app.use_profile(&work);
+
+    // Nothing to keep alive before logging in
+    app.spawn_heartbeat();
+    assert!(!app.task_runner.is_running(TaskKind::Heartbeat));
+
+    app.remember_account("alice", "alice-token-123");
+    app.api_client.set_session_token(Some("alice-token-123".to_string()));
+    app.current_screen = Screen::Main;
+    app.auth_state.current_user = Some(User {
+        id: uuid::Uuid::new_v4(),
+        username: "alice".to_string(),
+        bio: None,
+        join_date: chrono::Utc::now(),
+        is_test_user: true,
+        badges: Vec::new(),
+        karma: 0,
+    });
+    app.spawn_heartbeat();
+    assert!(app.task_runner.is_running(TaskKind::Heartbeat));
+
+    app.apply_session_expired();
+    assert_eq!(app.current_screen, Screen::Auth);
+    assert!(app.api_client.session_token().is_none());
+    assert!(app.auth_state.saved_accounts.is_empty());
+    assert!(!app.task_runner.is_running(TaskKind::Heartbeat));
+    assert!(app
+        .auth_state
+        .error
+        .as_deref()
+        .is_some_and(|e| e.contains("expired")));
+}
+
+#[tokio::test]
+async fn test_expired_session_prompts_to_sign_in_over_the_current_screen() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut app = App::new();
+    app.server_config =
+        crate::server_config::ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+    app.api_client.set_session_token(Some("alice-token-123".to_string()));
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Settings;
+    app.settings_state.config = Some(fido_types::UserConfig::default());
+    app.settings_state.has_unsaved_changes = true;
+
+    // Nobody to sign back in as before logging in
+    app.open_reauth();
+    assert!(!app.reauth.show);
+
+    app.auth_state.current_user = Some(User {
+        id: uuid::Uuid::new_v4(),
+        username: "alice".to_string(),
+        bio: None,
+        join_date: chrono::Utc::now(),
+        is_test_user: true,
+        badges: Vec::new(),
+        karma: 0,
+    });
+    app.open_reauth();
+    assert!(app.reauth.show);
+
+    // The prompt takes the keys and leaves everything underneath alone
+    app.handle_key_event(key_event(KeyCode::Char('q'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert!(app.running);
+    assert_eq!(app.current_screen, Screen::Main);
+    assert_eq!(app.current_tab, Tab::Settings);
+    assert!(app.settings_state.has_unsaved_changes);
+    assert_eq!(app.api_client.session_token(), Some("alice-token-123"));
+
+    // Esc gives up and goes back to the login screen
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(!app.reauth.show);
+    assert_eq!(app.current_screen, Screen::Auth);
+    assert!(app.api_client.session_token().is_none());
+}
+
+#[tokio::test]
+async fn test_link_preview_refuses_blocked_domains() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.posts_state.posts = vec![test_post("bob", "Free stuff at https://bit.ly/free and https://example.com/docs")];
+    app.posts_state.list_state.select(Some(0));
+
+    app.handle_key_event(key_event(KeyCode::Char('o'))).unwrap();
+    assert!(app.link_preview.show);
+    assert_eq!(app.link_preview.urls.len(), 2);
+    assert!(app.task_runner.is_running(TaskKind::LinkPreview));
+
+    app.apply_link_preview(
+        "https://bit.ly/free".to_string(),
+        Ok(fido_types::LinkPreview {
+            url: "https://bit.ly/free".to_string(),
+            resolved_url: "https://evil.example/claim".to_string(),
+            domain: "evil.example".to_string(),
+            expanded: true,
+            blocked: true,
+        }),
+    );
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert!(app.link_preview.show, "blocked links stay unopened");
+    assert!(app.toasts.iter().any(|t| t.message.contains("evil.example is blocked")));
+
+    // Other keys stay with the confirmation until it's dismissed
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.link_preview.selected, 1);
+    assert_eq!(app.current_tab, Tab::Posts);
+
+    // Without the server, the preview falls back to the raw link
+    app.apply_link_preview("https://example.com/docs".to_string(), Err("offline".to_string()));
+    let preview = app.link_preview.preview.as_ref().unwrap();
+    assert_eq!(preview.domain, "example.com");
+    assert!(app.link_preview.unchecked);
+
+    // An unchecked link needs a second Enter before it opens
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert!(app.link_preview.show);
+    assert!(app.link_preview.confirm_unchecked);
+
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(!app.link_preview.show);
+}
+
+#[tokio::test]
+async fn test_t_in_thread_toggles_translation() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let post = test_post("ana", "Hola a todos");
+    let mut modal_list_state = ListState::default();
+    modal_list_state.select(Some(0));
+    app.post_detail_state = Some(PostDetailState {
+        post: Some(post.clone()),
+        replies: Vec::new(),
+        reply_list_state: ListState::default(),
+        loading: false,
+        error: None,
+        show_reply_composer: false,
+        reply_content: String::new(),
+        show_delete_confirmation: false,
+        previous_feed_position: None,
+        expanded_posts: Default::default(),
+        show_full_post_modal: true,
+        full_post_modal_id: Some(post.id),
+        modal_list_state,
+        modal_expanded_posts: Default::default(),
+        related: Vec::new(),
+        related_selected: None,
+    });
+    app.viewing_post_detail = true;
+
+    app.handle_key_event(key_event(KeyCode::Char('t'))).unwrap();
+    assert_eq!(app.translations.pending, Some(post.id));
+    assert!(app.task_runner.is_running(TaskKind::Translation));
+
+    app.apply_translation(
+        post.id,
+        Ok(fido_types::PostTranslation {
+            post_id: post.id,
+            translated_content: "Hello everyone".to_string(),
+            source_language: Some("es".to_string()),
+            target_language: "en".to_string(),
+            provider: "translate.example".to_string(),
+        }),
+    );
+    assert_eq!(app.translations.pending, None);
+    assert!(app.translations.shown.contains_key(&post.id));
+
+    // Pressing it again goes back to the original
+    app.handle_key_event(key_event(KeyCode::Char('t'))).unwrap();
+    assert!(app.translations.shown.is_empty());
+    assert!(app.viewing_post_detail);
+
+    // Servers without a translation service say so instead of failing later
+    app.instance_features = Some(fido_types::InstanceFeatures::default());
+    app.handle_key_event(key_event(KeyCode::Char('t'))).unwrap();
+    assert_eq!(app.translations.pending, None);
+    assert!(app.toasts.iter().any(|t| t.message.contains("doesn't offer translation")));
+
+    assert_eq!(language_from_locale("pt_BR.UTF-8").as_deref(), Some("pt"));
+    assert_eq!(language_from_locale("de"), Some("de".to_string()));
+    assert_eq!(language_from_locale("C.UTF-8"), None);
+}
+
+#[test]
+fn test_tab_in_full_post_modal_cycles_related_posts() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let make_post = |content: &str| Post {
+        hashtags: vec!["rust".to_string()],
+        ..test_post("ana", content)
+    };
+    let post = make_post("Parsers in #rust");
+    let related = vec![make_post("Lexers in #rust"), make_post("Macros in #rust")];
+    let mut modal_list_state = ListState::default();
+    modal_list_state.select(Some(0));
+    app.post_detail_state = Some(PostDetailState {
+        post: Some(post.clone()),
+        replies: Vec::new(),
+        reply_list_state: ListState::default(),
+        loading: false,
+        error: None,
+        show_reply_composer: false,
+        reply_content: String::new(),
+        show_delete_confirmation: false,
+        previous_feed_position: None,
+        expanded_posts: Default::default(),
+        show_full_post_modal: true,
+        full_post_modal_id: Some(post.id),
+        modal_list_state,
+        modal_expanded_posts: Default::default(),
+        related: related.clone(),
+        related_selected: None,
+    });
+    app.viewing_post_detail = true;
+
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.selected_related_post_id(), Some(related[0].id));
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.selected_related_post_id(), Some(related[1].id));
+
+    // Space doesn't touch the thread while a related post has focus
+    app.handle_key_event(key_event(KeyCode::Char(' '))).unwrap();
+    assert!(app.post_detail_state.as_ref().unwrap().modal_expanded_posts.is_empty());
+
+    // Past the last one, focus goes back to the thread
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.selected_related_post_id(), None);
+    app.handle_key_event(key_event(KeyCode::BackTab)).unwrap();
+    assert_eq!(app.selected_related_post_id(), Some(related[1].id));
+    assert!(app.viewing_post_detail);
+}
+
+#[test]
+fn test_s_on_profile_opens_status_composer_with_current_status() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Profile;
+    app.input_mode = InputMode::Navigation;
+    app.profile_state.profile = Some(fido_types::UserProfile {
+        user_id: uuid::Uuid::new_v4(),
+        username: "alice".to_string(),
+        bio: None,
+        karma: 0,
+        post_count: 0,
+        join_date: chrono::Utc::now(),
+        recent_hashtags: vec![],
+        badges: vec![],
+        status: Some(fido_types::UserStatus {
+            text: "Heads down".to_string(),
+            emoji: Some("🎧".to_string()),
+            expires_at: None,
+        }),
+        moved_to: None,
+    });
+
+    app.handle_key_event(key_event(KeyCode::Char('s'))).unwrap();
+
+    assert_eq!(app.composer_state.mode, Some(ComposerMode::SetStatus));
+    assert_eq!(app.composer_state.get_content(), "🎧 Heads down");
+}
+
+#[test]
+fn test_capital_letters_start_a_dm_instead_of_a_shortcut() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::DMs;
+
+    for c in ['X', 'E', 'S'] {
+        app.clear_dm_message();
+        app.input_mode = InputMode::Navigation;
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+
+        assert_eq!(app.input_mode, InputMode::Typing);
+        assert_eq!(app.get_dm_message_content(), c.to_string());
+    }
+    assert!(app.composer_state.mode.is_none());
+}
+
+#[tokio::test]
+async fn test_social_modal_loads_more_at_the_end_and_refetches_on_search() {
+    let mut app = App::new();
+    app.friends_state.show_friends_modal = true;
+    app.friends_state.following = ["bob", "carol"]
+        .iter()
+        .map(|name| UserInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            username: name.to_string(),
+            follower_count: 0,
+            following_count: 0,
+        })
+        .collect();
+    app.friends_state.pages.insert(
+        SocialTab::Following,
+        SocialListPage {
+            query: String::new(),
+            has_more: true,
+        },
+    );
+
+    app.handle_friends_modal_keys(key_event(KeyCode::Down)).unwrap();
+    assert!(!app.task_runner.is_running(TaskKind::SocialList));
+    // Past the last loaded user
+    app.handle_friends_modal_keys(key_event(KeyCode::Down)).unwrap();
+    assert!(app.task_runner.is_running(TaskKind::SocialList));
+    assert_eq!(app.friends_state.selected_index, 1);
+    app.task_runner.cancel_all();
+
+    // Nothing more to fetch
+    app.friends_state.pages.get_mut(&SocialTab::Following).unwrap().has_more = false;
+    app.handle_friends_modal_keys(key_event(KeyCode::Down)).unwrap();
+    assert!(!app.task_runner.is_running(TaskKind::SocialList));
+
+    // Searching filters what's loaded and asks the server for the rest
+    app.handle_friends_modal_keys(key_event(KeyCode::Char('/'))).unwrap();
+    app.handle_friends_modal_keys(key_event(KeyCode::Char('c'))).unwrap();
+    assert_eq!(app.get_filtered_social_list().len(), 1);
+    assert!(app.task_runner.is_running(TaskKind::SocialList));
+    app.task_runner.cancel_all();
+}
+
+#[tokio::test]
+async fn test_visual_mode_marks_posts_until_escape() {
+    let mut app = App::new();
+    app.running = true;
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    app.posts_state.posts = ["bob", "carol", "dave"]
+        .iter()
+        .map(|name| test_post(name, &format!("hello from {}", name)))
+        .collect();
+    app.posts_state.list_state.select(Some(0));
+
+    // V marks the selected post; Space marks others as you move
+    app.handle_key_event(key_event(KeyCode::Char('V'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char(' '))).unwrap();
+    let marked: Vec<&str> = app
+        .marked_posts()
+        .iter()
+        .map(|post| post.author_username.as_str())
+        .collect();
+    assert_eq!(marked, vec!["bob", "dave"]);
+
+    // Space again unmarks
+    app.handle_key_event(key_event(KeyCode::Char(' '))).unwrap();
+    assert_eq!(app.marked_posts().len(), 1);
+
+    // Esc leaves the mode instead of quitting
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(app.posts_state.bulk_selection.is_none());
+    assert!(app.running);
+
+    // With nothing marked, a bulk action only explains itself
+    app.posts_state.bulk_selection = Some(Default::default());
+    app.apply_bulk_action(BulkAction::Bookmark);
+    assert!(app.posts_state.bulk_selection.is_some());
+    assert!(!app.task_runner.is_running(TaskKind::BulkAction));
+}
+
+#[tokio::test]
+async fn test_profile_lists_switch_with_left_and_right() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Profile;
+    app.auth_state.current_user = Some(fido_types::User {
+        id: uuid::Uuid::new_v4(),
+        username: "alice".to_string(),
+        bio: None,
+        join_date: chrono::Utc::now(),
+        is_test_user: true,
+        badges: Vec::new(),
+        karma: 0,
+    });
+    app.profile_state.user_posts = vec![test_post("alice", "hello")];
+    app.profile_state.list_state.select(Some(0));
+
+    // Switching clears the old list and fetches the new one from the start
+    app.handle_key_event(key_event(KeyCode::Right)).unwrap();
+    assert_eq!(app.profile_state.posts_tab, ProfilePostsTab::Replies);
+    assert!(app.profile_state.user_posts.is_empty());
+    assert_eq!(app.profile_state.list_state.selected(), None);
+    assert!(app.task_runner.is_running(TaskKind::ProfilePosts));
+
+    // Left from Posts wraps round to Upvoted
+    app.handle_key_event(key_event(KeyCode::Left)).unwrap();
+    app.handle_key_event(key_event(KeyCode::Left)).unwrap();
+    assert_eq!(app.profile_state.posts_tab, ProfilePostsTab::Upvoted);
+    app.task_runner.cancel_all();
+}
+
+#[test]
+fn test_rate_limit_counts_down_in_a_toast_then_retries() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    assert!(!app.tick_rate_limit());
+    assert!(app.toasts.is_empty());
+
+    app.api_client.rate_limit_for(std::time::Duration::from_millis(1500));
+    assert!(!app.tick_rate_limit());
+    assert!(app.rate_limit_waiting);
+    let countdown: Vec<_> = app.toasts.iter().map(|t| t.message.clone()).collect();
+    assert_eq!(countdown, vec!["Too many requests – retrying in 2s".to_string()]);
+
+    // Ticking again updates the same toast rather than stacking another
+    assert!(!app.tick_rate_limit());
+    assert_eq!(app.toasts.iter().count(), 1);
+
+    app.api_client.rate_limit_for(std::time::Duration::ZERO);
+    assert!(app.tick_rate_limit(), "Retries once the wait is over");
+    assert!(!app.rate_limit_waiting);
+    assert!(app.toasts.is_empty());
+    assert!(!app.tick_rate_limit());
+
+    assert!(categorize_error("Too many requests; retry in 12s").starts_with("Rate Limited"));
+}
+
+#[test]
+fn test_channels_tab_navigation_prompt_and_unread_badge() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Channels;
+    let channel = |name: &str, joined: bool, unread_count: i32| fido_types::Channel {
+        name: name.to_string(),
+        description: String::new(),
+        created_at: chrono::Utc::now(),
+        member_count: 1,
+        joined,
+        unread_count,
+    };
+    app.channels_state.channels = vec![
+        channel("rust-help", true, 2),
+        channel("show-and-tell", true, 1),
+        channel("go", false, 5),
+    ];
+
+    // Only joined channels count toward the tab badge
+    assert_eq!(app.tab_unread_count(Tab::Channels), 3);
+    assert_eq!(Tab::Posts.next(), Tab::Channels);
+    assert_eq!(Tab::DMs.previous(), Tab::Channels);
+
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    assert_eq!(app.channels_state.selected, 2);
+    app.handle_key_event(key_event(KeyCode::Char('k'))).unwrap();
+    assert_eq!(app.channels_state.selected, 1);
+
+    // n opens the name prompt; q is typed rather than quitting
+    app.handle_key_event(key_event(KeyCode::Char('n'))).unwrap();
+    assert_eq!(app.input_mode, InputMode::Typing);
+    for c in "qa".chars() {
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+    }
+    assert!(app.running);
+    assert_eq!(app.channels_state.new_channel_input.as_deref(), Some("qa"));
+    app.handle_key_event(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.current_tab, Tab::Channels);
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(app.channels_state.new_channel_input.is_none());
+    assert_eq!(app.input_mode, InputMode::Navigation);
+
+    // A channel feed survives a restart through the saved preferences
+    let filter = PostFilter::Channel("rust-help".to_string());
+    assert_eq!(PostFilter::from_preferences(&filter.to_preferences()), filter);
+    assert_eq!(filter.label(), "Channel #rust-help");
+}
+
+#[test]
+fn test_review_command_and_filter_tab() {
+    let Some(Ok(PostCommand::Review(review))) =
+        parse_post_command("/review https://github.com/fido/fido/pull/7 | #Rust\nError paths in the parser")
+    else {
+        panic!("expected a review request");
+    };
+    assert_eq!(review.url, "https://github.com/fido/fido/pull/7");
+    assert_eq!(review.language, "rust");
+    assert_eq!(review.content, "Error paths in the parser");
+
+    assert!(matches!(
+        parse_post_command("/event Standup | 2030-05-05 09:00 | Room 1"),
+        Some(Ok(PostCommand::Event(_)))
+    ));
+    assert!(parse_post_command("/reviewed it, looks good").is_none());
+    assert!(parse_post_command("/review github.com/fido/fido | rust").unwrap().is_err());
+    assert!(parse_post_command("/review https://github.com/fido/fido").unwrap().is_err());
+
+    // The filter modal has a tab for open review requests
+    let mut app = App::new();
+    app.posts_state.show_filter_modal = true;
+    for _ in 0..3 {
+        app.handle_filter_modal_keys(key_event(KeyCode::Tab)).unwrap();
+    }
+    assert_eq!(app.posts_state.filter_modal_state.selected_tab, FilterTab::Reviews);
+
+    let filter = PostFilter::Reviews;
+    assert_eq!(PostFilter::from_preferences(&filter.to_preferences()), filter);
+}
+
+#[test]
+fn test_job_composer_asks_one_field_at_a_time() {
+    let mut form = JobForm::new();
+    assert!(form.answer("  ").is_err());
+    assert_eq!(form.answer("Rust\nEngineer"), Ok(None));
+    assert_eq!(form.title, "Rust Engineer");
+    assert_eq!(form.answer("Fido Labs"), Ok(None));
+    assert!(form.answer("hybrid").is_err());
+    assert_eq!(form.step, JobStep::WorkMode);
+    assert_eq!(form.answer("r"), Ok(None));
+    assert!(form.answer("jobs.example.com").is_err());
+    assert_eq!(form.answer("https://jobs.example.com/rust"), Ok(None));
+
+    // With no description the title is the post's text
+    let job = form.answer("").unwrap().unwrap();
+    assert_eq!(job.company, "Fido Labs");
+    assert_eq!(job.work_mode, fido_types::WorkMode::Remote);
+    assert_eq!(job.url, "https://jobs.example.com/rust");
+    assert_eq!(job.content, "Rust Engineer");
+}
+
+#[test]
+fn test_jobs_filter_tab_picks_work_mode() {
+    let mut app = App::new();
+    app.posts_state.show_filter_modal = true;
+    for _ in 0..4 {
+        app.handle_filter_modal_keys(key_event(KeyCode::Tab)).unwrap();
+    }
+    assert_eq!(app.posts_state.filter_modal_state.selected_tab, FilterTab::Jobs);
+    for _ in 0..5 {
+        app.handle_filter_modal_keys(key_event(KeyCode::Down)).unwrap();
+    }
+    let selected = app.posts_state.filter_modal_state.selected_index;
+    assert_eq!(JOB_FILTER_CHOICES[selected], Some(fido_types::WorkMode::Onsite));
+    app.handle_filter_modal_keys(key_event(KeyCode::Tab)).unwrap();
+    assert_eq!(app.posts_state.filter_modal_state.selected_tab, FilterTab::Later);
+
+    for filter in [PostFilter::Jobs(None), PostFilter::Jobs(Some(fido_types::WorkMode::Remote))] {
+        assert_eq!(PostFilter::from_preferences(&filter.to_preferences()), filter);
+    }
+    assert_eq!(PostFilter::Jobs(Some(fido_types::WorkMode::Onsite)).label(), "Jobs (On-site)");
+}
+
+#[test]
+fn test_ctrl_t_posts_anonymously_only_where_allowed() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let ctrl_t = || key_event_with_modifiers(KeyCode::Char('t'), KeyModifiers::CONTROL);
+
+    // Servers that don't say they allow it keep posts signed
+    app.open_composer_new_post();
+    app.handle_key_event(ctrl_t()).unwrap();
+    assert!(!app.composer_state.anonymous);
+    assert!(app.toasts.iter().any(|t| t.message.contains("doesn't allow anonymous")));
+
+    app.instance_features = Some(fido_types::InstanceFeatures {
+        anonymous_posting: true,
+        ..Default::default()
+    });
+    app.handle_key_event(ctrl_t()).unwrap();
+    assert!(app.composer_state.anonymous);
+    assert_eq!(app.composer_state.get_content(), "");
+    app.handle_key_event(ctrl_t()).unwrap();
+    assert!(!app.composer_state.anonymous);
+
+    // A fresh composer starts signed again
+    app.handle_key_event(ctrl_t()).unwrap();
+    app.close_composer();
+    app.open_composer_new_post();
+    assert!(!app.composer_state.anonymous);
+
+    // Channel posts always carry their author
+    app.posts_state.current_filter = PostFilter::Channel("rust".to_string());
+    app.handle_key_event(ctrl_t()).unwrap();
+    assert!(!app.composer_state.anonymous);
+}
+
+#[test]
+fn test_limited_replies_explain_instead_of_opening_composer() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let post = Post {
+        reply_policy: ReplyPolicy::Following,
+        can_reply: false,
+        ..test_post("ana", "Friends only today")
+    };
+    app.post_detail_state = Some(PostDetailState {
+        post: Some(post.clone()),
+        replies: Vec::new(),
+        reply_list_state: ListState::default(),
+        loading: false,
+        error: None,
+        show_reply_composer: false,
+        reply_content: String::new(),
+        show_delete_confirmation: false,
+        previous_feed_position: None,
+        expanded_posts: Default::default(),
+        show_full_post_modal: false,
+        full_post_modal_id: None,
+        modal_list_state: ListState::default(),
+        modal_expanded_posts: Default::default(),
+        related: Vec::new(),
+        related_selected: None,
+    });
+    app.viewing_post_detail = true;
+
+    app.open_composer_reply(post.id, post.author_username.clone(), post.content.clone());
+    assert!(!app.composer_state.is_open());
+    assert!(app
+        .toasts
+        .iter()
+        .any(|t| t.message == "@ana only takes replies from people they follow"));
+
+    // Someone the author follows gets the composer as usual
+    app.post_detail_state.as_mut().unwrap().post.as_mut().unwrap().can_reply = true;
+    app.open_composer_reply(post.id, post.author_username.clone(), post.content.clone());
+    assert!(matches!(app.composer_state.mode, Some(ComposerMode::Reply { .. })));
+}
+
+#[test]
+fn test_ctrl_r_cycles_who_can_reply_to_a_new_post() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let ctrl_r = || key_event_with_modifiers(KeyCode::Char('r'), KeyModifiers::CONTROL);
+
+    app.open_composer_new_post();
+    assert_eq!(app.composer_state.reply_policy, ReplyPolicy::Everyone);
+    app.handle_key_event(ctrl_r()).unwrap();
+    assert_eq!(app.composer_state.reply_policy, ReplyPolicy::Following);
+    app.handle_key_event(ctrl_r()).unwrap();
+    assert_eq!(app.composer_state.reply_policy, ReplyPolicy::Mentioned);
+    assert_eq!(app.composer_state.get_content(), "");
+    app.handle_key_event(ctrl_r()).unwrap();
+    assert_eq!(app.composer_state.reply_policy, ReplyPolicy::Everyone);
+
+    // Each new post starts open to everyone
+    app.handle_key_event(ctrl_r()).unwrap();
+    app.close_composer();
+    app.open_composer_new_post();
+    assert_eq!(app.composer_state.reply_policy, ReplyPolicy::Everyone);
+}
+
+#[tokio::test]
+async fn test_highlights_open_from_the_feed_and_pick_posts() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+
+    app.handle_key_event(key_event(KeyCode::Char('H'))).unwrap();
+    assert!(app.highlights.show);
+    assert!(app.highlights.loading);
+    app.highlights.loading = false;
+
+    let post = |content: &str| Post {
+        upvotes: 3,
+        ..test_post("alice", content)
+    };
+    app.highlights.top_posts = vec![post("first"), post("second")];
+
+    // j stops at the last post, k at the first
+    for _ in 0..3 {
+        app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    }
+    assert_eq!(app.highlights.selected, 1);
+    app.handle_key_event(key_event(KeyCode::Char('k'))).unwrap();
+    assert_eq!(app.highlights.selected, 0);
+
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(!app.highlights.show);
+}
+
+#[tokio::test]
+async fn test_hashtag_digest_moves_over_hashtags_and_their_posts() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+
+    app.handle_key_event(key_event(KeyCode::Char('W'))).unwrap();
+    assert!(app.hashtag_digest.show);
+    assert!(app.hashtag_digest.loading);
+    app.hashtag_digest.loading = false;
+
+    let post = |content: &str| Post {
+        upvotes: 3,
+        ..test_post("alice", content)
+    };
+    let now = chrono::Utc::now();
+    let rust_post = post("#rust 2024 edition");
+    app.hashtag_digest.entries = vec![
+        fido_types::HashtagDigestEntry {
+            hashtag: "rust".to_string(),
+            enabled: true,
+            changelog: Some(fido_types::HashtagChangelog {
+                id: uuid::Uuid::new_v4(),
+                hashtag: "rust".to_string(),
+                period_start: now - chrono::Duration::weeks(1),
+                period_end: now,
+                summary: "#rust this week: 1 post".to_string(),
+                top_posts: vec![rust_post.clone()],
+            }),
+        },
+        fido_types::HashtagDigestEntry {
+            hashtag: "go".to_string(),
+            enabled: false,
+            changelog: None,
+        },
+    ];
+
+    // Hashtag, its post, then the next hashtag
+    assert_eq!(
+        app.hashtag_digest.rows(),
+        vec![
+            HashtagDigestRow::Hashtag(0),
+            HashtagDigestRow::Post(0, 0),
+            HashtagDigestRow::Hashtag(1),
+        ]
+    );
+    assert!(app.hashtag_digest.selected_post().is_none());
+    app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    assert_eq!(app.hashtag_digest.selected_post().map(|p| p.id), Some(rust_post.id));
+    for _ in 0..3 {
+        app.handle_key_event(key_event(KeyCode::Char('j'))).unwrap();
+    }
+    assert_eq!(app.hashtag_digest.selected, 2);
+
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(!app.hashtag_digest.show);
+}
+
+#[test]
+fn test_snoozed_posts_leave_the_feed_and_come_back_on_top() {
+    let post = |content: &str| test_post("alice", content);
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    let feed = vec![post("one"), post("two"), post("three")];
+    app.apply_posts_result(Ok(feed.clone()));
+    app.posts_state.list_state.select(Some(1));
+
+    // z, then how long for
+    app.handle_key_event(key_event(KeyCode::Char('z'))).unwrap();
+    assert!(app.posts_state.snooze_input.is_some());
+    for c in "1d".chars() {
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+    }
+    app.handle_key_event(key_event(KeyCode::Enter)).unwrap();
+    assert!(app.posts_state.snooze_input.is_none());
+    assert_eq!(app.input_mode, InputMode::Navigation);
+    assert_eq!(app.posts_state.read_later.len(), 1);
+    assert_eq!(app.posts_state.read_later[0].post.id, feed[1].id);
+    assert_eq!(app.selected_feed_post().map(|p| p.id), Some(feed[2].id));
+
+    // Reloads leave it out until it's due
+    app.apply_posts_result(Ok(feed.clone()));
+    assert!(app.posts_state.posts.iter().all(|p| p.id != feed[1].id));
+    let until = app.posts_state.read_later[0].until;
+    app.toasts.take_fresh();
+    app.resurface_snoozed_posts(until - chrono::Duration::minutes(1));
+    assert!(app.toasts.take_fresh().is_empty());
+
+    // Due: announced once and put first, with the same post still selected
+    app.resurface_snoozed_posts(until);
+    app.resurface_snoozed_posts(until);
+    assert_eq!(app.toasts.take_fresh().len(), 1);
+    assert_eq!(app.posts_state.posts[0].id, feed[1].id);
+    assert_eq!(app.selected_feed_post().map(|p| p.id), Some(feed[0].id));
+
+    // The Later feed lists the queue, and z there unsnoozes
+    app.switch_feed_filter(PostFilter::Later);
+    app.apply_posts_result(Ok(Vec::new()));
+    assert_eq!(app.posts_state.posts.len(), 1);
+    app.handle_key_event(key_event(KeyCode::Char('z'))).unwrap();
+    assert!(app.posts_state.read_later.is_empty());
+    assert!(app.posts_state.posts.is_empty());
+}
+
+#[test]
+fn test_feed_reopens_where_each_filter_was_left() {
+    let post = |content: &str| test_post("alice", content);
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.current_tab = Tab::Posts;
+    let all = vec![post("one"), post("two"), post("three")];
+    app.apply_posts_result(Ok(all.clone()));
+    app.posts_state.list_state.select(Some(2));
+
+    app.switch_feed_filter(PostFilter::Hashtag("Rust".to_string()));
+    app.apply_posts_result(Ok(vec![post("#rust"), post("#rust again")]));
+    assert_eq!(app.posts_state.list_state.selected(), Some(0));
+    app.posts_state.list_state.select(Some(1));
+
+    // Back on All, the third post is selected again even after a new post
+    app.switch_feed_filter(PostFilter::All);
+    let mut refreshed = vec![post("newest")];
+    refreshed.extend(all.iter().cloned());
+    app.apply_posts_result(Ok(refreshed));
+    assert_eq!(app.posts_state.list_state.selected(), Some(3));
+
+    // Tags are remembered regardless of case
+    let rust = &app.posts_state.positions["hashtag:rust"];
+    assert_eq!(PostFilter::Hashtag("rust".to_string()).position_key(), "hashtag:rust");
+    assert_eq!(rust.offset, 0);
+    assert!(app.posts_state.pending_restore.is_none());
+}
+
+#[test]
+fn test_reading_history_keeps_opened_posts_and_searches_them() {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    let tokio = test_post("alice", "Moving the server to tokio today");
+    let scattered = test_post("bob", "Time to kick it off");
+    let other = test_post("carol", "Lunch plans");
+    let now = chrono::Utc::now();
+    app.record_reading_history(tokio.clone(), now - chrono::Duration::days(1));
+    app.record_reading_history(scattered.clone(), now - chrono::Duration::hours(2));
+    app.record_reading_history(other.clone(), now - chrono::Duration::hours(1));
+    // Opening a post again moves it back to the top
+    app.record_reading_history(scattered.clone(), now);
+    let order: Vec<_> = app.reading_history.entries.iter().map(|e| e.post.id).collect();
+    assert_eq!(order, vec![scattered.id, other.id, tokio.id]);
+
+    app.handle_key_event(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::CONTROL))
+        .unwrap();
+    assert!(app.reading_history.show);
+    assert_eq!(app.input_mode, InputMode::Typing);
+    assert_eq!(app.reading_history.selected_post_id(), Some(scattered.id));
+
+    // Letters in order match; the whole word ranks first
+    for c in "tokio".chars() {
+        app.handle_key_event(key_event(KeyCode::Char(c))).unwrap();
+    }
+    let found: Vec<_> = app.reading_history.matches().iter().map(|e| e.post.id).collect();
+    assert_eq!(found, vec![tokio.id, scattered.id]);
+    assert_eq!(app.reading_history.selected_post_id(), Some(tokio.id));
+    app.handle_key_event(key_event(KeyCode::Down)).unwrap();
+    assert_eq!(app.reading_history.selected_post_id(), Some(scattered.id));
+
+    app.handle_key_event(key_event(KeyCode::Esc)).unwrap();
+    assert!(!app.reading_history.show);
+    assert!(app.running);
+    assert_eq!(app.input_mode, InputMode::Navigation);
+}
+
+#[test]
+fn test_modal_navigation_toggling_and_deleting_agree_on_the_selected_reply() {
+    let mut app = App::new();
+    let make_post = |parent_post_id: Option<uuid::Uuid>| Post {
+        parent_post_id,
+        ..test_post("ana", "reply")
+    };
+    let root = make_post(None);
+    let first = make_post(Some(root.id));
+    let nested = make_post(Some(first.id));
+    let second = make_post(Some(root.id));
+    let mut modal_list_state = ListState::default();
+    modal_list_state.select(Some(0));
+    app.post_detail_state = Some(PostDetailState {
+        post: Some(root.clone()),
+        replies: vec![first.clone(), nested.clone(), second.clone()],
+        reply_list_state: ListState::default(),
+        loading: false,
+        error: None,
+        show_reply_composer: false,
+        reply_content: String::new(),
+        show_delete_confirmation: false,
+        previous_feed_position: None,
+        expanded_posts: Default::default(),
+        show_full_post_modal: true,
+        full_post_modal_id: Some(root.id),
+        modal_list_state,
+        modal_expanded_posts: [(root.id, true)].into_iter().collect(),
+        related: Vec::new(),
+        related_selected: None,
+    });
+    let deletable = |app: &App| {
+        app.post_detail_state
+            .as_ref()
+            .unwrap()
+            .get_deletable_post()
+            .map(|post| post.id)
+    };
+
+    // The nested reply stays hidden until its parent is expanded
+    app.modal_next_reply();
+    app.modal_next_reply();
+    app.modal_next_reply();
+    assert_eq!(deletable(&app), Some(second.id));
+
+    app.modal_previous_reply();
+    app.modal_toggle_expansion();
+    app.modal_next_reply();
+    assert_eq!(deletable(&app), Some(nested.id));
+
+    // Collapsing the root leaves only the root selectable
+    app.select_reply_in_modal(root.id);
+    app.modal_toggle_expansion();
+    app.modal_next_reply();
+    assert_eq!(deletable(&app), Some(root.id));
+
+    // Jumping to a reply opens the way to it again
+    app.select_reply_in_modal(nested.id);
+    assert_eq!(deletable(&app), Some(nested.id));
+}
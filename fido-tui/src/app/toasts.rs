@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Toasts kept at once; older ones are dropped first
+const MAX_TOASTS: usize = 4;
+
+/// How long a toast stays up, overridable with `FIDO_TOAST_SECS`
+fn default_toast_duration() -> Duration {
+    std::env::var("FIDO_TOAST_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(3))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub message: String,
+    shown_at: Instant,
+    duration: Duration,
+    /// Set for toasts that are updated in place, like a countdown
+    tag: Option<&'static str>,
+}
+
+impl Toast {
+    fn expired(&self, now: Instant) -> bool {
+        now.duration_since(self.shown_at) >= self.duration
+    }
+}
+
+/// Short-lived notifications any part of the app can push. The event loop
+/// expires them each frame and the UI stacks them in the bottom-right corner.
+pub struct ToastQueue {
+    toasts: VecDeque<Toast>,
+    default_duration: Duration,
+    /// Messages pushed since the last `take_fresh`, for announcing
+    fresh: Vec<String>,
+}
+
+impl Default for ToastQueue {
+    fn default() -> Self {
+        Self::new(default_toast_duration())
+    }
+}
+
+impl ToastQueue {
+    pub fn new(default_duration: Duration) -> Self {
+        Self {
+            toasts: VecDeque::new(),
+            default_duration,
+            fresh: Vec::new(),
+        }
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Info, message);
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Success, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Error, message);
+    }
+
+    pub fn push(&mut self, level: ToastLevel, message: impl Into<String>) {
+        self.push_for(level, message, self.default_duration);
+    }
+
+    /// Push a toast that stays up for `duration` instead of the default
+    pub fn push_for(&mut self, level: ToastLevel, message: impl Into<String>, duration: Duration) {
+        let message = message.into();
+        self.fresh.push(message.clone());
+        // A repeated message moves to the bottom with a fresh timer
+        self.toasts
+            .retain(|toast| !(toast.level == level && toast.message == message));
+        self.toasts.push_back(Toast {
+            level,
+            message,
+            shown_at: Instant::now(),
+            duration,
+            tag: None,
+        });
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Show the toast tagged `tag`, replacing its text if it's already up.
+    /// Only the first message is announced, so a countdown isn't read out
+    /// every second.
+    pub fn upsert(
+        &mut self,
+        tag: &'static str,
+        level: ToastLevel,
+        message: impl Into<String>,
+        duration: Duration,
+    ) {
+        let message = message.into();
+        if let Some(toast) = self.toasts.iter_mut().find(|toast| toast.tag == Some(tag)) {
+            toast.level = level;
+            toast.message = message;
+            toast.shown_at = Instant::now();
+            toast.duration = duration;
+            return;
+        }
+        self.push_for(level, message, duration);
+        if let Some(toast) = self.toasts.back_mut() {
+            toast.tag = Some(tag);
+        }
+    }
+
+    /// Take down the toast tagged `tag`, if it's up
+    pub fn dismiss(&mut self, tag: &'static str) {
+        self.toasts.retain(|toast| toast.tag != Some(tag));
+    }
+
+    /// Drop toasts whose time is up
+    pub fn expire(&mut self, now: Instant) {
+        self.toasts.retain(|toast| !toast.expired(now));
+    }
+
+    /// Messages pushed since the last call, oldest first
+    pub fn take_fresh(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.fresh)
+    }
+
+    /// Toasts to show, oldest first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_stack_dedupe_and_expire() {
+        let mut toasts = ToastQueue::new(Duration::from_secs(3));
+        toasts.success("Saved");
+        toasts.error("Network down");
+        toasts.push_for(ToastLevel::Info, "Synced", Duration::from_secs(10));
+        toasts.success("Saved");
+
+        let messages: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["Network down", "Synced", "Saved"]);
+
+        toasts.expire(Instant::now() + Duration::from_secs(5));
+        let messages: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["Synced"]);
+
+        for i in 0..10 {
+            toasts.info(format!("toast {}", i));
+        }
+        assert_eq!(toasts.iter().count(), MAX_TOASTS);
+        assert_eq!(toasts.iter().last().unwrap().message, "toast 9");
+    }
+
+    #[test]
+    fn test_tagged_toasts_update_in_place() {
+        let mut toasts = ToastQueue::new(Duration::from_secs(3));
+        toasts.upsert("wait", ToastLevel::Info, "Retrying in 3s", Duration::from_secs(4));
+        toasts.info("Saved");
+        toasts.upsert("wait", ToastLevel::Info, "Retrying in 2s", Duration::from_secs(3));
+
+        let messages: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["Retrying in 2s", "Saved"]);
+        assert_eq!(toasts.take_fresh(), vec!["Retrying in 3s", "Saved"]);
+
+        toasts.dismiss("wait");
+        let messages: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["Saved"]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Text and code files sent with direct messages.
+//!
+//! The server decides which files are allowed and cleans their content;
+//! this module reads a file to attach (`/attach <path>` in the DM input)
+//! and writes a received one, or a conversation export, back to disk
+//! without clobbering anything.
+
+use anyhow::{bail, Context, Result};
+use fido_types::DmAttachment;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Matches the server's limit, checked here so big files aren't uploaded
+pub const MAX_ATTACHMENT_BYTES: u64 = 16 * 1024;
+
+/// Read the file at `path` (`~/` allowed) to send with a message
+pub fn load(path: &str) -> Result<DmAttachment> {
+    let path = expand_home(path.trim());
+    let size = fs::metadata(&path)
+        .with_context(|| format!("Can't read {}", path.display()))?
+        .len();
+    if size > MAX_ATTACHMENT_BYTES {
+        bail!(
+            "{} is {} KB; attachments can be at most {} KB",
+            path.display(),
+            size.div_ceil(1024),
+            MAX_ATTACHMENT_BYTES / 1024
+        );
+    }
+    let bytes = fs::read(&path).with_context(|| format!("Can't read {}", path.display()))?;
+    let content = String::from_utf8(bytes)
+        .map_err(|_| anyhow::anyhow!("Only text files can be attached"))?;
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .context("Attach a file, not a directory")?;
+
+    Ok(DmAttachment { filename, content })
+}
+
+/// Write `content` into `dir` as `filename`, numbering the name if a file
+/// of that name is already there. Returns where it was saved.
+pub fn save(filename: &str, content: &str, dir: &Path) -> Result<PathBuf> {
+    // The name came from someone else; never let it pick the directory
+    let name = Path::new(filename)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| !name.starts_with('.'))
+        .unwrap_or_else(|| "attachment.txt".to_string());
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) => (stem.to_string(), format!(".{}", extension)),
+        None => (name.clone(), String::new()),
+    };
+
+    fs::create_dir_all(dir).with_context(|| format!("Can't create {}", dir.display()))?;
+    let mut path = dir.join(&name);
+    let mut copy = 1;
+    while path.exists() {
+        path = dir.join(format!("{}-{}{}", stem, copy, extension));
+        copy += 1;
+    }
+    fs::write(&path, content)
+        .with_context(|| format!("Can't write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Where saved attachments go: the Downloads folder, else the home directory
+pub fn download_dir() -> Option<PathBuf> {
+    dirs::download_dir().or_else(dirs::home_dir)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_rejects_large_and_binary_files() {
+        let dir = TempDir::new().unwrap();
+        let code = dir.path().join("fix.rs");
+        fs::write(&code, "fn fix() {}\n").unwrap();
+        assert_eq!(
+            load(code.to_str().unwrap()).unwrap(),
+            DmAttachment {
+                filename: "fix.rs".to_string(),
+                content: "fn fix() {}\n".to_string(),
+            }
+        );
+
+        let big = dir.path().join("big.log");
+        fs::write(&big, "x".repeat(MAX_ATTACHMENT_BYTES as usize + 1)).unwrap();
+        assert!(load(big.to_str().unwrap()).is_err());
+
+        let binary = dir.path().join("image.txt");
+        fs::write(&binary, [0xff, 0xfe, 0x00]).unwrap();
+        assert!(load(binary.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_save_keeps_existing_files_and_ignores_directories_in_the_name() {
+        let dir = TempDir::new().unwrap();
+        let first = save("../../notes.txt", "hello", dir.path()).unwrap();
+        let second = save("../../notes.txt", "hello", dir.path()).unwrap();
+        assert_eq!(first, dir.path().join("notes.txt"));
+        assert_eq!(second, dir.path().join("notes-1.txt"));
+        assert_eq!(fs::read_to_string(second).unwrap(), "hello");
+    }
+}
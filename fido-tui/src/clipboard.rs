@@ -0,0 +1,26 @@
+//! Copying text to the user's clipboard.
+//!
+//! Uses the terminal's OSC 52 escape, written to the same stream as the UI,
+//! so it reaches the clipboard of whoever is at the keyboard, including
+//! over SSH. Terminals that don't support it ignore the sequence.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::io::Write;
+
+/// Ask the terminal to put `text` on the clipboard
+pub fn copy<W: Write>(out: &mut W, text: &str) -> std::io::Result<()> {
+    write!(out, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_writes_osc52() {
+        let mut out = Vec::new();
+        copy(&mut out, "hi").unwrap();
+        assert_eq!(out, b"\x1b]52;c;aGk=\x07");
+    }
+}
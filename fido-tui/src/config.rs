@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use uuid::Uuid;
 
 /// Session data stored locally
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,11 +17,79 @@ pub struct SessionData {
 /// User preferences stored locally
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserPreferences {
-    pub filter_type: String, // "all", "hashtag", "user", "multi"
+    pub filter_type: String, // "all", "hashtag", "user", "multi", "channel"
     pub filter_hashtag: Option<String>,
     pub filter_user: Option<String>,
     pub filter_hashtags: Vec<String>,
     pub filter_users: Vec<String>,
+    #[serde(default)]
+    pub filter_channel: Option<String>,
+}
+
+/// Where the user left a list, so coming back to it lands there again
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ListPosition {
+    /// The selected post
+    pub selected: Uuid,
+    /// First row scrolled into view
+    pub offset: usize,
+}
+
+/// Saved list positions: each feed filter's, and the DM conversation that
+/// was open
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ListPositions {
+    /// By `PostFilter::position_key`
+    #[serde(default)]
+    pub feeds: HashMap<String, ListPosition>,
+    /// The other user's ID
+    #[serde(default)]
+    pub dm_conversation: Option<Uuid>,
+}
+
+/// A follow-up reminder set on a DM conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DmReminder {
+    /// The other user's username, for the toast
+    pub username: String,
+    pub due: DateTime<Utc>,
+}
+
+/// DM conversations to come back to, kept on this machine only: ones marked
+/// unread by hand, and reminders that mark them unread when due. Both are
+/// keyed by the other user's ID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DmFollowUps {
+    #[serde(default)]
+    pub marked_unread: HashSet<Uuid>,
+    #[serde(default)]
+    pub reminders: HashMap<Uuid, DmReminder>,
+}
+
+impl DmFollowUps {
+    pub fn is_empty(&self) -> bool {
+        self.marked_unread.is_empty() && self.reminders.is_empty()
+    }
+}
+
+/// A post snoozed from the feed. The post is kept whole so it can come back
+/// to the top of the feed even once the feed has moved past it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnoozedPost {
+    pub post: fido_types::Post,
+    /// When it comes back
+    pub until: DateTime<Utc>,
+    /// It has come back and been announced, and now waits at the top of
+    /// the feed until it's opened
+    #[serde(default)]
+    pub resurfaced: bool,
+}
+
+/// A post opened in the thread view, kept for the reading history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub post: fido_types::Post,
+    pub opened_at: DateTime<Utc>,
 }
 
 /// Configuration manager for .fido directory
@@ -39,6 +110,11 @@ impl ConfigManager {
         
         Ok(Self { config_dir })
     }
+
+    /// Keep files in `config_dir` instead of `~/.fido`, e.g. for `--demo`
+    pub fn with_dir(config_dir: PathBuf) -> Self {
+        Self { config_dir }
+    }
     
     /// Get the .fido configuration directory path
     fn get_config_dir() -> Result<PathBuf> {
@@ -47,6 +123,11 @@ impl ConfigManager {
         Ok(home_dir.join(".fido"))
     }
     
+    /// Directory for crash reports and drafts saved by the panic hook
+    pub fn crash_dir(&self) -> PathBuf {
+        self.config_dir.join("crashes")
+    }
+
     /// Get the session file path for a specific instance
     fn get_session_file(&self, instance_id: &str) -> PathBuf {
         self.config_dir.join(format!("session_{}.json", instance_id))
@@ -57,6 +138,36 @@ impl ConfigManager {
         self.config_dir.join(format!("prefs_{}.json", user_id))
     }
     
+    /// Get the unsent DM drafts file path for a specific user
+    fn get_dm_drafts_file(&self, user_id: &str) -> PathBuf {
+        self.config_dir.join(format!("dm_drafts_{}.json", user_id))
+    }
+    
+    /// Get the DM follow-ups file path for a specific user
+    fn get_dm_follow_ups_file(&self, user_id: &str) -> PathBuf {
+        self.config_dir.join(format!("dm_follow_ups_{}.json", user_id))
+    }
+    
+    /// Get the read-later queue file path for a specific user
+    fn get_read_later_file(&self, user_id: &str) -> PathBuf {
+        self.config_dir.join(format!("read_later_{}.json", user_id))
+    }
+    
+    /// Get the reading history file path for a specific user
+    fn get_history_file(&self, user_id: &str) -> PathBuf {
+        self.config_dir.join(format!("history_{}.json", user_id))
+    }
+    
+    /// Get the saved list positions file path for a specific user
+    fn get_positions_file(&self, user_id: &str) -> PathBuf {
+        self.config_dir.join(format!("positions_{}.json", user_id))
+    }
+    
+    /// Get the file recording when a user was last active in fido
+    fn get_last_seen_file(&self, user_id: &str) -> PathBuf {
+        self.config_dir.join(format!("last_seen_{}", user_id))
+    }
+    
     /// Save session data
     pub fn save_session(&self, instance_id: &str, session: &SessionData) -> Result<()> {
         let session_file = self.get_session_file(instance_id);
@@ -122,6 +233,195 @@ impl ConfigManager {
         Ok(Some(prefs))
     }
     
+    /// Save unsent DM drafts, keyed by the other user's ID. An empty map
+    /// removes the file.
+    pub fn save_dm_drafts(&self, user_id: &str, drafts: &HashMap<Uuid, String>) -> Result<()> {
+        let drafts_file = self.get_dm_drafts_file(user_id);
+        
+        if drafts.is_empty() {
+            if drafts_file.exists() {
+                fs::remove_file(&drafts_file)
+                    .context("Failed to delete DM drafts file")?;
+            }
+            return Ok(());
+        }
+        
+        let json = serde_json::to_string_pretty(drafts)
+            .context("Failed to serialize DM drafts")?;
+        
+        fs::write(&drafts_file, json)
+            .context("Failed to write DM drafts file")?;
+        
+        Ok(())
+    }
+    
+    /// Load unsent DM drafts
+    pub fn load_dm_drafts(&self, user_id: &str) -> Result<HashMap<Uuid, String>> {
+        let drafts_file = self.get_dm_drafts_file(user_id);
+        
+        if !drafts_file.exists() {
+            return Ok(HashMap::new());
+        }
+        
+        let json = fs::read_to_string(&drafts_file)
+            .context("Failed to read DM drafts file")?;
+        
+        serde_json::from_str(&json).context("Failed to parse DM drafts")
+    }
+    
+    /// Save conversations marked unread and pending reminders. Having none
+    /// removes the file.
+    pub fn save_dm_follow_ups(&self, user_id: &str, follow_ups: &DmFollowUps) -> Result<()> {
+        let follow_ups_file = self.get_dm_follow_ups_file(user_id);
+        
+        if follow_ups.is_empty() {
+            if follow_ups_file.exists() {
+                fs::remove_file(&follow_ups_file)
+                    .context("Failed to delete DM follow-ups file")?;
+            }
+            return Ok(());
+        }
+        
+        let json = serde_json::to_string_pretty(follow_ups)
+            .context("Failed to serialize DM follow-ups")?;
+        
+        fs::write(&follow_ups_file, json)
+            .context("Failed to write DM follow-ups file")?;
+        
+        Ok(())
+    }
+    
+    /// Load DM follow-ups; empty on first use
+    pub fn load_dm_follow_ups(&self, user_id: &str) -> Result<DmFollowUps> {
+        let follow_ups_file = self.get_dm_follow_ups_file(user_id);
+        
+        if !follow_ups_file.exists() {
+            return Ok(DmFollowUps::default());
+        }
+        
+        let json = fs::read_to_string(&follow_ups_file)
+            .context("Failed to read DM follow-ups file")?;
+        
+        serde_json::from_str(&json).context("Failed to parse DM follow-ups")
+    }
+    
+    /// Save the read-later queue. An empty queue removes the file.
+    pub fn save_read_later(&self, user_id: &str, queue: &[SnoozedPost]) -> Result<()> {
+        let read_later_file = self.get_read_later_file(user_id);
+        
+        if queue.is_empty() {
+            if read_later_file.exists() {
+                fs::remove_file(&read_later_file)
+                    .context("Failed to delete read-later file")?;
+            }
+            return Ok(());
+        }
+        
+        let json = serde_json::to_string_pretty(queue)
+            .context("Failed to serialize read-later queue")?;
+        
+        fs::write(&read_later_file, json)
+            .context("Failed to write read-later file")?;
+        
+        Ok(())
+    }
+    
+    /// Load the read-later queue; empty on first use
+    pub fn load_read_later(&self, user_id: &str) -> Result<Vec<SnoozedPost>> {
+        let read_later_file = self.get_read_later_file(user_id);
+        
+        if !read_later_file.exists() {
+            return Ok(Vec::new());
+        }
+        
+        let json = fs::read_to_string(&read_later_file)
+            .context("Failed to read read-later file")?;
+        
+        serde_json::from_str(&json).context("Failed to parse read-later queue")
+    }
+    
+    /// Save the reading history, newest first
+    pub fn save_history(&self, user_id: &str, history: &[HistoryEntry]) -> Result<()> {
+        let history_file = self.get_history_file(user_id);
+        
+        if history.is_empty() {
+            if history_file.exists() {
+                fs::remove_file(&history_file)
+                    .context("Failed to delete reading history file")?;
+            }
+            return Ok(());
+        }
+        
+        let json = serde_json::to_string_pretty(history)
+            .context("Failed to serialize reading history")?;
+        
+        fs::write(&history_file, json)
+            .context("Failed to write reading history file")?;
+        
+        Ok(())
+    }
+    
+    /// Load the reading history; empty on first use
+    pub fn load_history(&self, user_id: &str) -> Result<Vec<HistoryEntry>> {
+        let history_file = self.get_history_file(user_id);
+        
+        if !history_file.exists() {
+            return Ok(Vec::new());
+        }
+        
+        let json = fs::read_to_string(&history_file)
+            .context("Failed to read reading history file")?;
+        
+        serde_json::from_str(&json).context("Failed to parse reading history")
+    }
+    
+    /// Save where the user left their feeds and DMs
+    pub fn save_list_positions(&self, user_id: &str, positions: &ListPositions) -> Result<()> {
+        let json = serde_json::to_string_pretty(positions)
+            .context("Failed to serialize list positions")?;
+        
+        fs::write(self.get_positions_file(user_id), json)
+            .context("Failed to write list positions file")?;
+        
+        Ok(())
+    }
+    
+    /// Load saved list positions; empty on first use
+    pub fn load_list_positions(&self, user_id: &str) -> Result<ListPositions> {
+        let positions_file = self.get_positions_file(user_id);
+        
+        if !positions_file.exists() {
+            return Ok(ListPositions::default());
+        }
+        
+        let json = fs::read_to_string(&positions_file)
+            .context("Failed to read list positions file")?;
+        
+        serde_json::from_str(&json).context("Failed to parse list positions")
+    }
+    
+    /// Record that the user is active now, for the catch-up screen next login
+    pub fn save_last_seen(&self, user_id: &str, at: DateTime<Utc>) -> Result<()> {
+        fs::write(self.get_last_seen_file(user_id), at.to_rfc3339())
+            .context("Failed to write last-seen file")
+    }
+    
+    /// When the user was last active here; `None` on first use
+    pub fn load_last_seen(&self, user_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let last_seen_file = self.get_last_seen_file(user_id);
+        
+        if !last_seen_file.exists() {
+            return Ok(None);
+        }
+        
+        let text = fs::read_to_string(&last_seen_file)
+            .context("Failed to read last-seen file")?;
+        
+        let at = DateTime::parse_from_rfc3339(text.trim())
+            .context("Failed to parse last-seen time")?;
+        Ok(Some(at.with_timezone(&Utc)))
+    }
+    
     /// Clean up old session files (older than 30 days)
     pub fn cleanup_old_sessions(&self) -> Result<()> {
         use std::time::{Duration, SystemTime};
@@ -164,6 +464,49 @@ impl Default for ConfigManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dm_drafts_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = ConfigManager {
+            config_dir: dir.path().to_path_buf(),
+        };
+        let user_id = Uuid::new_v4().to_string();
+        assert!(manager.load_dm_drafts(&user_id).unwrap().is_empty());
+
+        let mut drafts = HashMap::new();
+        drafts.insert(Uuid::new_v4(), "see you at 5?".to_string());
+        manager.save_dm_drafts(&user_id, &drafts).unwrap();
+        assert_eq!(manager.load_dm_drafts(&user_id).unwrap(), drafts);
+
+        manager.save_dm_drafts(&user_id, &HashMap::new()).unwrap();
+        assert!(!manager.get_dm_drafts_file(&user_id).exists());
+    }
+
+    #[test]
+    fn test_dm_follow_ups_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = ConfigManager {
+            config_dir: dir.path().to_path_buf(),
+        };
+        let user_id = Uuid::new_v4().to_string();
+        assert!(manager.load_dm_follow_ups(&user_id).unwrap().is_empty());
+
+        let mut follow_ups = DmFollowUps::default();
+        follow_ups.marked_unread.insert(Uuid::new_v4());
+        follow_ups.reminders.insert(
+            Uuid::new_v4(),
+            DmReminder {
+                username: "bob".to_string(),
+                due: Utc::now(),
+            },
+        );
+        manager.save_dm_follow_ups(&user_id, &follow_ups).unwrap();
+        assert_eq!(manager.load_dm_follow_ups(&user_id).unwrap(), follow_ups);
+
+        manager.save_dm_follow_ups(&user_id, &DmFollowUps::default()).unwrap();
+        assert!(!manager.get_dm_follow_ups_file(&user_id).exists());
+    }
+
     #[test]
     fn test_generate_instance_id() {
         let id1 = ConfigManager::generate_instance_id();
@@ -173,4 +516,28 @@ mod tests {
         assert!(!id2.is_empty());
         // IDs should be different (unless generated in same millisecond)
     }
+
+    #[test]
+    fn test_list_positions_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = ConfigManager {
+            config_dir: dir.path().to_path_buf(),
+        };
+        let user_id = Uuid::new_v4().to_string();
+        assert_eq!(manager.load_list_positions(&user_id).unwrap(), ListPositions::default());
+
+        let mut positions = ListPositions {
+            dm_conversation: Some(Uuid::new_v4()),
+            ..ListPositions::default()
+        };
+        positions.feeds.insert(
+            "hashtag:rust".to_string(),
+            ListPosition {
+                selected: Uuid::new_v4(),
+                offset: 4,
+            },
+        );
+        manager.save_list_positions(&user_id, &positions).unwrap();
+        assert_eq!(manager.load_list_positions(&user_id).unwrap(), positions);
+    }
 }
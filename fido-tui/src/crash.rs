@@ -0,0 +1,168 @@
+//! Panic handling for the TUI: put the terminal back, write a crash report
+//! to `~/.fido/crashes/`, and keep any unsent composer draft so the next
+//! launch can offer it back.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::app::{App, ComposerMode};
+
+/// Log lines included at the end of a crash report
+const REPORT_LOG_LINES: usize = 200;
+const DRAFT_FILE: &str = "draft.json";
+
+/// Unsent composer text and what it was for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Draft {
+    pub mode: ComposerMode,
+    pub content: String,
+}
+
+/// What the panic hook knows about the app, refreshed every frame since the
+/// hook can't reach the `App` itself
+struct CrashContext {
+    summary: String,
+    draft: Option<Draft>,
+}
+
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext {
+    summary: String::new(),
+    draft: None,
+});
+
+/// Restore the terminal and write a crash report before the default hook
+/// prints the panic
+pub fn install_panic_hook(crash_dir: PathBuf) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = crate::terminal::restore();
+        match write_report(&crash_dir, &info.to_string()) {
+            Ok(path) => eprintln!("fido crashed. Crash report: {}", path.display()),
+            Err(e) => eprintln!("fido crashed and the crash report couldn't be written: {}", e),
+        }
+        previous(info);
+    }));
+}
+
+/// Snapshot what a crash report should describe
+pub fn record_state(app: &App) {
+    let draft = app
+        .composer_state
+        .mode
+        .clone()
+        .map(|mode| Draft {
+            mode,
+            content: app.composer_state.get_content(),
+        })
+        .filter(|draft| !draft.content.trim().is_empty());
+    let summary = format!(
+        "screen: {:?}\ntab: {:?}\nuser: {}\nserver: {}{}\nconnection: {:?}\nthread open: {}\ncomposer: {}\nbackground tasks: {}",
+        app.current_screen,
+        app.current_tab,
+        app.auth_state
+            .current_user
+            .as_ref()
+            .map_or("(none)", |user| user.username.as_str()),
+        app.api_client.base_url(),
+        app.active_profile
+            .as_ref()
+            .map(|profile| format!(" (profile {})", profile))
+            .unwrap_or_default(),
+        app.connection_state(),
+        app.viewing_post_detail,
+        app.composer_state
+            .mode
+            .as_ref()
+            .map_or("closed".to_string(), |mode| format!("{:?}", mode)),
+        app.task_runner.in_flight(),
+    );
+
+    let mut context = CONTEXT.lock().unwrap_or_else(|e| e.into_inner());
+    context.summary = summary;
+    context.draft = draft;
+}
+
+fn write_report(crash_dir: &Path, panic: &str) -> Result<PathBuf> {
+    fs::create_dir_all(crash_dir).context("Failed to create crash directory")?;
+    let context = CONTEXT.lock().unwrap_or_else(|e| e.into_inner());
+
+    let now = chrono::Local::now();
+    let mut report = format!(
+        "fido {} crashed at {}\n\n{}\n\nBacktrace:\n{}\n\nState:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339(),
+        panic,
+        std::backtrace::Backtrace::force_capture(),
+        context.summary,
+    );
+
+    if let Some(draft) = &context.draft {
+        let draft_path = crash_dir.join(DRAFT_FILE);
+        fs::write(&draft_path, serde_json::to_string_pretty(draft)?)?;
+        report.push_str(&format!("\nUnsent draft saved to {}\n", draft_path.display()));
+    }
+
+    let lines = crate::logging::recent_lines();
+    report.push_str(&format!("\nLast {} log lines:\n", REPORT_LOG_LINES));
+    for line in lines.iter().skip(lines.len().saturating_sub(REPORT_LOG_LINES)) {
+        report.push_str(&format!(
+            "{} {:<5} {}: {}\n",
+            line.time.format("%H:%M:%S%.3f"),
+            line.level,
+            line.target,
+            line.message
+        ));
+    }
+
+    let path = crash_dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// The draft left behind by the last crash, if any
+pub fn load_draft(crash_dir: &Path) -> Option<Draft> {
+    let contents = fs::read_to_string(crash_dir.join(DRAFT_FILE)).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| log::warn!("Ignoring unreadable crash draft: {}", e))
+        .ok()
+}
+
+/// Forget the crash draft once it has been restored or declined
+pub fn discard_draft(crash_dir: &Path) {
+    let path = crash_dir.join(DRAFT_FILE);
+    if path.exists() {
+        if let Err(e) = fs::remove_file(&path) {
+            log::warn!("Failed to remove crash draft: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_crash_report_keeps_unsent_draft() {
+        let dir = TempDir::new().unwrap();
+        let mut app = App::new();
+        app.open_composer_new_post();
+        app.composer_state.textarea.insert_str("half-written post");
+        record_state(&app);
+
+        let report_path = write_report(dir.path(), "panicked at src/app/mod.rs:1:1:\nboom").unwrap();
+        let report = fs::read_to_string(report_path).unwrap();
+        assert!(report.contains("boom"));
+        assert!(report.contains("composer: NewPost"));
+
+        let draft = load_draft(dir.path()).unwrap();
+        assert_eq!(draft.mode, ComposerMode::NewPost);
+        assert_eq!(draft.content, "half-written post");
+
+        discard_draft(dir.path());
+        assert!(load_draft(dir.path()).is_none());
+    }
+}
@@ -0,0 +1,667 @@
+//! The demo server's data and the answers it gives: a handful of seeded
+//! users and posts, changed by what the demo user does, and forgotten when
+//! the TUI exits.
+
+use chrono::{DateTime, Duration, Utc};
+use fido_types::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// GETs for features the demo leaves empty: each answers an empty list
+const EMPTY_LISTS: &[&str] = &[
+    "/channels",
+    "/digest/hashtags",
+    "/dms/conversations",
+    "/dms/crossposts",
+    "/drafts",
+    "/emoji",
+    "/events",
+    "/hashtags/active",
+    "/leaderboard/posts",
+    "/reviews",
+    "/social/followers",
+    "/social/following",
+    "/social/github-suggestions",
+    "/social/mutual",
+    "/users/search",
+    "/watches",
+];
+
+/// Seeded users: username and bio
+const USERS: &[(&str, &str)] = &[
+    ("alice", "Rust and terminals. Maintains too many TUIs."),
+    ("bob", "Backend engineer. Boring technology enthusiast."),
+    ("carol", "Dotfiles curator, shell golfer."),
+    ("dave", "Learning Rust in public."),
+    ("eve", "Security engineer. Please rotate your keys."),
+];
+
+/// Seeded top-level posts: author, hours ago, content, upvotes, downvotes
+const POSTS: &[(&str, i64, &str, i32, i32)] = &[
+    ("alice", 1, "Just shipped a ratatui dashboard for our CI runners. Redraws only on change, idles at 0% CPU. #rust #tui", 12, 0),
+    ("bob", 3, "Hot take: most services don't need a message queue, they need a cron job and a table. #backend", 9, 4),
+    ("carol", 5, "Dotfiles trick of the week: bind Ctrl+F to an fzf file picker and never type a path again. #shell #productivity", 7, 0),
+    ("dave", 8, "Week 3 of learning Rust. The borrow checker and I have reached an understanding. Mostly. #rust #learning", 15, 1),
+    ("eve", 12, "Reminder: rotate the tokens you pasted into that screenshot. Yes, that one. #security", 20, 0),
+    ("alice", 26, "What's your go-to crate for config files? figment, config-rs, or hand-rolled serde? #rust", 6, 0),
+    ("bob", 30, "Incremental backups landed in Postgres 17. Restores finally take minutes, not hours. #databases", 5, 0),
+    ("carol", 50, "Terminal font of the month: Iosevka Term. Narrow, readable, ligatures off. #tui", 4, 2),
+];
+
+/// Seeded replies: index of the post replied to, author, hours ago, content
+const REPLIES: &[(usize, &str, i64, &str)] = &[
+    (1, "eve", 2, "Until the cron job runs twice. Ask me how I know."),
+    (3, "alice", 7, "It gets friendlier. Then a lifetime shows up in a trait object and you're back to week 1 :)"),
+    (5, "bob", 25, "config-rs for layering env over files, plain serde for everything else."),
+    (5, "dave", 24, "figment! Its errors tell you which file the bad value came from."),
+];
+
+/// A request as the demo server read it off the socket
+#[derive(Debug, Clone, Default)]
+pub struct DemoRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub token: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl DemoRequest {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, DemoResponse> {
+        serde_json::from_slice(&self.body)
+            .map_err(|e| DemoResponse::error(400, "Bad Request", &e.to_string()))
+    }
+}
+
+/// Status and JSON body of an answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct DemoResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl DemoResponse {
+    fn ok(body: impl serde::Serialize) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_value(body).unwrap_or(Value::Null),
+        }
+    }
+
+    /// The same `{ error, details }` body the real server sends
+    fn error(status: u16, error: &str, details: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": error, "details": details }),
+        }
+    }
+
+    fn not_found(details: &str) -> Self {
+        Self::error(404, "Not Found", details)
+    }
+}
+
+/// Everything the demo server knows
+pub struct DemoApi {
+    users: Vec<User>,
+    posts: Vec<Post>,
+    /// The demo user's votes, by post
+    votes: HashMap<(Uuid, Uuid), String>,
+    sessions: HashMap<String, Uuid>,
+    configs: HashMap<Uuid, UserConfig>,
+    followed: HashMap<Uuid, Vec<String>>,
+}
+
+impl DemoApi {
+    /// The seeded users and posts, dated relative to `now`
+    pub fn seeded(now: DateTime<Utc>) -> Self {
+        let users: Vec<User> = USERS
+            .iter()
+            .enumerate()
+            .map(|(i, (username, bio))| User {
+                id: Uuid::new_v4(),
+                username: username.to_string(),
+                bio: Some(bio.to_string()),
+                join_date: now - Duration::days(90 - i as i64 * 10),
+                is_test_user: true,
+                badges: Vec::new(),
+                karma: 0,
+            })
+            .collect();
+
+        let mut api = Self {
+            users,
+            posts: Vec::new(),
+            votes: HashMap::new(),
+            sessions: HashMap::new(),
+            configs: HashMap::new(),
+            followed: HashMap::new(),
+        };
+        for (author, hours, content, upvotes, downvotes) in POSTS {
+            let author = api.user_named(author).cloned().expect("seeded author");
+            let mut post = new_post(&author, content, now - Duration::hours(*hours));
+            post.upvotes = *upvotes;
+            post.downvotes = *downvotes;
+            api.posts.push(post);
+        }
+        for (parent, author, hours, content) in REPLIES {
+            let author = api.user_named(author).cloned().expect("seeded author");
+            let parent = &api.posts[*parent];
+            let mut reply = new_post(&author, content, now - Duration::hours(*hours));
+            reply.parent_post_id = Some(parent.id);
+            reply.reply_to_user_id = Some(parent.author_id);
+            reply.reply_to_username = Some(parent.author_username.clone());
+            reply.upvotes = 2;
+            api.posts.push(reply);
+        }
+        api
+    }
+
+    /// Answer one request
+    pub fn handle(&mut self, request: &DemoRequest) -> DemoResponse {
+        let viewer = request
+            .token
+            .as_ref()
+            .and_then(|token| self.sessions.get(token))
+            .copied();
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+        let result = match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["version"]) => Ok(DemoResponse::ok(ServerVersion {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                min_client_version: None,
+            })),
+            ("GET", ["features"]) => Ok(DemoResponse::ok(InstanceFeatures::default())),
+            ("GET", ["users", "test"]) => Ok(DemoResponse::ok(&self.users)),
+            ("POST", ["auth", "login"]) => self.login(request),
+            ("POST", ["auth", "logout"]) => {
+                let token: String = request.json().unwrap_or_default();
+                self.sessions.remove(&token);
+                Ok(DemoResponse::ok(json!({ "message": "Logged out successfully" })))
+            }
+            ("GET", ["auth", "validate"]) => self
+                .signed_in(viewer)
+                .map(|user| DemoResponse::ok(json!({ "user": user, "valid": true }))),
+            ("POST", ["auth", "heartbeat"]) => self.signed_in(viewer).map(|_| {
+                DemoResponse::ok(json!({ "expires_at": Utc::now() + Duration::days(30) }))
+            }),
+            ("GET", ["config"]) => self
+                .signed_in(viewer)
+                .map(|user| DemoResponse::ok(self.config(user.id))),
+            ("PUT", ["config"]) => self.update_config(viewer, request),
+            ("GET", ["posts"]) => Ok(self.feed(viewer, request)),
+            ("POST", ["posts"]) => self.create_post(viewer, request, None),
+            ("GET", ["posts", id]) => self
+                .find_post(id)
+                .map(|post| DemoResponse::ok(self.view(post, viewer))),
+            ("PUT", ["posts", id]) => self.update_post(viewer, id, request),
+            ("DELETE", ["posts", id]) => self.delete_post(viewer, id),
+            ("POST", ["posts", id, "vote"]) => self.vote(viewer, id, request),
+            ("GET", ["posts", id, "replies"]) => self.find_post(id).map(|post| {
+                let parent = post.id;
+                DemoResponse::ok(self.list(viewer, |p| p.parent_post_id == Some(parent), false))
+            }),
+            ("POST", ["posts", id, "reply"]) => match self.find_post(id) {
+                Ok(parent) => {
+                    let parent = parent.clone();
+                    self.create_post(viewer, request, Some(&parent))
+                }
+                Err(e) => Err(e),
+            },
+            ("GET", ["posts", id, "related"]) => self.find_post(id).map(|post| {
+                let (id, hashtags) = (post.id, post.hashtags.clone());
+                DemoResponse::ok(self.list(
+                    viewer,
+                    |p| p.id != id && p.hashtags.iter().any(|tag| hashtags.contains(tag)),
+                    true,
+                ))
+            }),
+            ("GET", ["users", id, "profile"]) => self.profile(id),
+            ("GET", ["users", id, "posts"]) => Ok(self.user_posts(viewer, id, request)),
+            ("GET", ["hashtags", "followed"]) => self.signed_in(viewer).map(|user| {
+                let names = self.followed.get(&user.id).cloned().unwrap_or_default();
+                DemoResponse::ok(self.hashtag_counts(names))
+            }),
+            ("POST", ["hashtags", "follow"]) => self.follow(viewer, request),
+            ("DELETE", ["hashtags", "follow", name]) => match self.signed_in(viewer) {
+                Ok(user) => {
+                    let id = user.id;
+                    self.followed.entry(id).or_default().retain(|tag| tag != name);
+                    Ok(DemoResponse::ok(json!({})))
+                }
+                Err(e) => Err(e),
+            },
+            ("GET", ["hashtags", "search"]) => {
+                let query = request.param("q").unwrap_or_default().to_lowercase();
+                let mut names: Vec<String> = self
+                    .posts
+                    .iter()
+                    .flat_map(|post| post.hashtags.iter().cloned())
+                    .filter(|tag| tag.contains(&query))
+                    .collect();
+                names.sort();
+                names.dedup();
+                Ok(DemoResponse::ok(self.hashtag_counts(names)))
+            }
+            ("GET", ["digest"]) => Err(DemoResponse::not_found("No digest yet")),
+            ("GET", ["catchup"]) => Ok(DemoResponse::ok(Catchup {
+                since: Utc::now(),
+                new_followers: Vec::new(),
+                top_posts: Vec::new(),
+                unread_messages: Vec::new(),
+            })),
+            ("GET", ["dms", "conversations", _]) => Ok(DemoResponse::ok(Vec::<Value>::new())),
+            ("GET", _) if EMPTY_LISTS.contains(&request.path.as_str()) => {
+                Ok(DemoResponse::ok(Vec::<Value>::new()))
+            }
+            (method, _) => Err(DemoResponse::not_found(&format!(
+                "{} {} isn't available in demo mode",
+                method, request.path
+            ))),
+        };
+        result.unwrap_or_else(|error| error)
+    }
+
+    fn user_named(&self, username: &str) -> Option<&User> {
+        self.users.iter().find(|user| user.username == username)
+    }
+
+    fn signed_in(&self, viewer: Option<Uuid>) -> Result<&User, DemoResponse> {
+        viewer
+            .and_then(|id| self.users.iter().find(|user| user.id == id))
+            .ok_or_else(|| DemoResponse::error(401, "Unauthorized", "Missing session token"))
+    }
+
+    fn find_post(&self, id: &str) -> Result<&Post, DemoResponse> {
+        let id = Uuid::parse_str(id)
+            .map_err(|_| DemoResponse::error(400, "Bad Request", "Invalid post ID"))?;
+        self.posts
+            .iter()
+            .find(|post| post.id == id)
+            .ok_or_else(|| DemoResponse::not_found("Post not found"))
+    }
+
+    fn login(&mut self, request: &DemoRequest) -> Result<DemoResponse, DemoResponse> {
+        let login: LoginRequest = request.json()?;
+        let user = self
+            .user_named(&login.username)
+            .cloned()
+            .ok_or_else(|| DemoResponse::not_found(&format!("User '{}' not found", login.username)))?;
+        let session_token = Uuid::new_v4().to_string();
+        self.sessions.insert(session_token.clone(), user.id);
+        Ok(DemoResponse::ok(LoginResponse {
+            user,
+            session_token,
+        }))
+    }
+
+    fn config(&self, user_id: Uuid) -> UserConfig {
+        self.configs.get(&user_id).cloned().unwrap_or(UserConfig {
+            user_id,
+            ..UserConfig::default()
+        })
+    }
+
+    /// Apply each field of the request that fits the config, skipping the
+    /// rest, rather than translating every setting's format
+    fn update_config(
+        &mut self,
+        viewer: Option<Uuid>,
+        request: &DemoRequest,
+    ) -> Result<DemoResponse, DemoResponse> {
+        let user_id = self.signed_in(viewer)?.id;
+        let changes: serde_json::Map<String, Value> = request.json()?;
+        let mut config = self.config(user_id);
+        for (key, value) in changes.into_iter().filter(|(_, value)| !value.is_null()) {
+            let mut updated = serde_json::to_value(&config).unwrap_or(Value::Null);
+            updated[key.as_str()] = value;
+            if let Ok(updated) = serde_json::from_value(updated) {
+                config = updated;
+            }
+        }
+        self.configs.insert(user_id, config.clone());
+        Ok(DemoResponse::ok(config))
+    }
+
+    /// `post` as `viewer` sees it: their vote, and the replies it has now
+    fn view(&self, post: &Post, viewer: Option<Uuid>) -> Post {
+        let mut post = post.clone();
+        post.reply_count = self
+            .posts
+            .iter()
+            .filter(|reply| reply.parent_post_id == Some(post.id))
+            .count() as i32;
+        post.user_vote = viewer.and_then(|viewer| self.votes.get(&(viewer, post.id)).cloned());
+        post
+    }
+
+    /// Posts matching `filter`, newest first, or oldest first for a thread
+    fn list(&self, viewer: Option<Uuid>, filter: impl Fn(&Post) -> bool, newest_first: bool) -> Vec<Post> {
+        let mut posts: Vec<Post> = self
+            .posts
+            .iter()
+            .filter(|post| filter(post))
+            .map(|post| self.view(post, viewer))
+            .collect();
+        posts.sort_by_key(|post| post.created_at);
+        if newest_first {
+            posts.reverse();
+        }
+        posts
+    }
+
+    fn feed(&self, viewer: Option<Uuid>, request: &DemoRequest) -> DemoResponse {
+        let hashtag = request.param("hashtag").map(str::to_lowercase);
+        let username = request.param("username");
+        let mut posts = self.list(
+            viewer,
+            |post| {
+                post.parent_post_id.is_none()
+                    && !post.is_deleted
+                    && hashtag.as_ref().is_none_or(|tag| post.hashtags.contains(tag))
+                    && username.is_none_or(|name| post.author_username == name)
+            },
+            true,
+        );
+        match request.param("sort").map(str::to_lowercase).as_deref() {
+            Some("popular") => posts.sort_by_key(|post| -(post.upvotes - post.downvotes)),
+            Some("controversial") => {
+                posts.sort_by_key(|post| -(post.upvotes.min(post.downvotes)))
+            }
+            _ => {}
+        }
+        let limit = request.param("limit").and_then(|limit| limit.parse().ok()).unwrap_or(25);
+        posts.truncate(limit);
+        DemoResponse::ok(posts)
+    }
+
+    fn user_posts(&self, viewer: Option<Uuid>, id: &str, request: &DemoRequest) -> DemoResponse {
+        let replies = request.param("replies") == Some("true");
+        let mut posts = self.list(
+            viewer,
+            |post| post.author_id.to_string() == id && post.parent_post_id.is_some() == replies,
+            true,
+        );
+        let offset = request.param("offset").and_then(|o| o.parse().ok()).unwrap_or(0);
+        let limit = request.param("limit").and_then(|l| l.parse().ok()).unwrap_or(25);
+        posts = posts.into_iter().skip(offset).take(limit).collect();
+        DemoResponse::ok(posts)
+    }
+
+    fn create_post(
+        &mut self,
+        viewer: Option<Uuid>,
+        request: &DemoRequest,
+        parent: Option<&Post>,
+    ) -> Result<DemoResponse, DemoResponse> {
+        let author = self.signed_in(viewer)?.clone();
+        let content: Value = request.json()?;
+        let content = content["content"].as_str().unwrap_or_default().trim().to_string();
+        if content.is_empty() {
+            return Err(DemoResponse::error(400, "Bad Request", "Post content cannot be empty"));
+        }
+        let mut post = new_post(&author, &content, Utc::now());
+        if let Some(parent) = parent {
+            post.parent_post_id = Some(parent.id);
+            post.reply_to_user_id = Some(parent.author_id);
+            post.reply_to_username = Some(parent.author_username.clone());
+        }
+        self.posts.push(post.clone());
+        Ok(DemoResponse::ok(post))
+    }
+
+    /// Find `id` and check `viewer` wrote it, returning its index
+    fn own_post(&self, viewer: Option<Uuid>, id: &str) -> Result<usize, DemoResponse> {
+        let user_id = self.signed_in(viewer)?.id;
+        let post_id = self.find_post(id)?.id;
+        let index = self.posts.iter().position(|post| post.id == post_id).unwrap_or_default();
+        if self.posts[index].author_id != user_id {
+            return Err(DemoResponse::error(
+                403,
+                "Forbidden",
+                "You don't have permission to modify this post",
+            ));
+        }
+        Ok(index)
+    }
+
+    fn update_post(
+        &mut self,
+        viewer: Option<Uuid>,
+        id: &str,
+        request: &DemoRequest,
+    ) -> Result<DemoResponse, DemoResponse> {
+        let index = self.own_post(viewer, id)?;
+        let update: UpdatePostRequest = request.json()?;
+        let post = &mut self.posts[index];
+        post.hashtags = extract_hashtags(&update.content);
+        post.content = update.content;
+        Ok(DemoResponse::ok(post.clone()))
+    }
+
+    /// Posts with replies become "[deleted]" placeholders, as on the server
+    fn delete_post(&mut self, viewer: Option<Uuid>, id: &str) -> Result<DemoResponse, DemoResponse> {
+        let index = self.own_post(viewer, id)?;
+        let post_id = self.posts[index].id;
+        let tombstoned = self.posts.iter().any(|post| post.parent_post_id == Some(post_id));
+        if tombstoned {
+            let post = &mut self.posts[index];
+            post.is_deleted = true;
+            post.content = "[deleted]".to_string();
+        } else {
+            self.posts.remove(index);
+        }
+        Ok(DemoResponse::ok(json!({
+            "success": true,
+            "message": "Post deleted successfully",
+            "post_id": post_id,
+            "tombstoned": tombstoned,
+        })))
+    }
+
+    /// Cast or change the viewer's vote
+    fn vote(&mut self, viewer: Option<Uuid>, id: &str, request: &DemoRequest) -> Result<DemoResponse, DemoResponse> {
+        let user_id = self.signed_in(viewer)?.id;
+        let post_id = self.find_post(id)?.id;
+        let vote: VoteRequest = request.json()?;
+        if vote.direction != "up" && vote.direction != "down" {
+            return Err(DemoResponse::error(400, "Bad Request", "Direction must be up or down"));
+        }
+        let previous = self.votes.insert((user_id, post_id), vote.direction.clone());
+        if let Some(post) = self.posts.iter_mut().find(|post| post.id == post_id) {
+            for (direction, change) in [(previous.as_deref(), -1), (Some(vote.direction.as_str()), 1)] {
+                match direction {
+                    Some("up") => post.upvotes += change,
+                    Some("down") => post.downvotes += change,
+                    _ => {}
+                }
+            }
+        }
+        Ok(DemoResponse::ok(json!({ "message": "Vote recorded" })))
+    }
+
+    fn profile(&self, id: &str) -> Result<DemoResponse, DemoResponse> {
+        let user = self
+            .users
+            .iter()
+            .find(|user| user.id.to_string() == id)
+            .ok_or_else(|| DemoResponse::not_found("User not found"))?;
+        let posts: Vec<&Post> = self.posts.iter().filter(|post| post.author_id == user.id).collect();
+        let mut recent_hashtags: Vec<String> = Vec::new();
+        for post in posts.iter().rev() {
+            for tag in &post.hashtags {
+                if !recent_hashtags.contains(tag) {
+                    recent_hashtags.push(tag.clone());
+                }
+            }
+        }
+        Ok(DemoResponse::ok(UserProfile {
+            user_id: user.id,
+            username: user.username.clone(),
+            bio: user.bio.clone(),
+            karma: posts.iter().map(|post| post.upvotes - post.downvotes).sum(),
+            post_count: posts.len() as i32,
+            join_date: user.join_date,
+            recent_hashtags,
+            badges: Vec::new(),
+            status: None,
+            moved_to: None,
+        }))
+    }
+
+    fn follow(&mut self, viewer: Option<Uuid>, request: &DemoRequest) -> Result<DemoResponse, DemoResponse> {
+        let user_id = self.signed_in(viewer)?.id;
+        let name: Value = request.json()?;
+        let name = name["name"].as_str().unwrap_or_default().trim_start_matches('#').to_lowercase();
+        let followed = self.followed.entry(user_id).or_default();
+        if !name.is_empty() && !followed.contains(&name) {
+            followed.push(name);
+        }
+        Ok(DemoResponse::ok(json!({})))
+    }
+
+    /// `{ name, post_count }` for each hashtag, the most used first
+    fn hashtag_counts(&self, names: Vec<String>) -> Vec<Value> {
+        let mut counted: Vec<(String, usize)> = names
+            .into_iter()
+            .map(|name| {
+                let count = self.posts.iter().filter(|post| post.hashtags.contains(&name)).count();
+                (name, count)
+            })
+            .collect();
+        counted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counted
+            .into_iter()
+            .map(|(name, post_count)| json!({ "name": name, "post_count": post_count }))
+            .collect()
+    }
+}
+
+fn new_post(author: &User, content: &str, created_at: DateTime<Utc>) -> Post {
+    Post {
+        id: Uuid::new_v4(),
+        author_id: author.id,
+        author_username: author.username.clone(),
+        content: content.to_string(),
+        created_at,
+        upvotes: 0,
+        downvotes: 0,
+        hashtags: extract_hashtags(content),
+        user_vote: None,
+        parent_post_id: None,
+        reply_count: 0,
+        reply_to_user_id: None,
+        reply_to_username: None,
+        is_deleted: false,
+        author_badges: Vec::new(),
+        author_status: None,
+        kind: None,
+        is_anonymous: false,
+        reply_policy: ReplyPolicy::default(),
+        can_reply: true,
+        is_accepted: false,
+        author_karma: 0,
+    }
+}
+
+/// Lowercase `#hashtags` in `content`, each once
+fn extract_hashtags(content: &str) -> Vec<String> {
+    let mut hashtags: Vec<String> = Vec::new();
+    for word in content.split_whitespace() {
+        let Some(tag) = word.strip_prefix('#') else {
+            continue;
+        };
+        let tag: String = tag
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect::<String>()
+            .to_lowercase();
+        if !tag.is_empty() && !hashtags.contains(&tag) {
+            hashtags.push(tag);
+        }
+    }
+    hashtags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, token: Option<&str>, body: Value) -> DemoRequest {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        DemoRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            token: token.map(str::to_string),
+            body: serde_json::to_vec(&body).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_seeded_feed_lists_top_level_posts_newest_first() {
+        let mut api = DemoApi::seeded(Utc::now());
+        let response = api.handle(&request("GET", "/posts?limit=3", None, Value::Null));
+        assert_eq!(response.status, 200);
+        let posts: Vec<Post> = serde_json::from_value(response.body).unwrap();
+        assert_eq!(posts.len(), 3);
+        assert_eq!(posts[0].author_username, "alice");
+        assert!(posts.iter().all(|post| post.parent_post_id.is_none()));
+
+        let response = api.handle(&request("GET", "/posts?hashtag=rust", None, Value::Null));
+        let posts: Vec<Post> = serde_json::from_value(response.body).unwrap();
+        assert!(posts.iter().all(|post| post.hashtags.contains(&"rust".to_string())));
+        assert_eq!(posts.len(), 3);
+    }
+
+    #[test]
+    fn test_demo_user_can_log_in_post_and_only_edit_their_own() {
+        let mut api = DemoApi::seeded(Utc::now());
+        let login = api.handle(&request("POST", "/auth/login", None, json!({ "username": "dave" })));
+        let token = login.body["session_token"].as_str().unwrap().to_string();
+
+        let created = api.handle(&request("POST", "/posts", Some(&token), json!({ "content": "Hello #demo" })));
+        assert_eq!(created.status, 200);
+        assert_eq!(created.body["hashtags"], json!(["demo"]));
+
+        let alices = api.handle(&request("GET", "/posts?username=alice&limit=1", None, Value::Null));
+        let path = format!("/posts/{}", alices.body[0]["id"].as_str().unwrap());
+        let denied = api.handle(&request("DELETE", &path, Some(&token), Value::Null));
+        assert_eq!(denied.status, 403);
+
+        let anonymous = api.handle(&request("POST", "/posts", None, json!({ "content": "hi" })));
+        assert_eq!(anonymous.status, 401);
+        let unknown = api.handle(&request("GET", "/leaderboard/contributors", None, Value::Null));
+        assert_eq!(unknown.status, 404);
+    }
+
+    #[test]
+    fn test_config_updates_keep_fields_that_fit() {
+        let mut api = DemoApi::seeded(Utc::now());
+        let login = api.handle(&request("POST", "/auth/login", None, json!({ "username": "eve" })));
+        let token = login.body["session_token"].as_str().unwrap().to_string();
+
+        let updated = api.handle(&request(
+            "PUT",
+            "/config",
+            Some(&token),
+            json!({ "sort_order": "Popular", "max_posts_display": "lots", "emoji_enabled": null }),
+        ));
+        assert_eq!(updated.status, 200);
+        assert_eq!(updated.body["sort_order"], "Popular");
+        assert_eq!(updated.body["max_posts_display"], 25);
+        assert_eq!(updated.body["emoji_enabled"], true);
+    }
+}
@@ -0,0 +1,198 @@
+//! `fido --demo`: the TUI against a mock API running inside the same
+//! process, with seeded users and posts, so trying fido, developing the UI
+//! or recording a screencast needs no fido-server.
+//!
+//! The mock listens on a loopback port and speaks just enough HTTP/1.1 for
+//! `ApiClient`, which then works unchanged. Settings, sessions and history
+//! go to a scratch directory instead of `~/.fido`, removed on exit.
+
+mod data;
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use data::{DemoApi, DemoRequest, DemoResponse};
+
+/// Largest request body the mock reads; anything the TUI sends is far smaller
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// The running mock API; stops and removes its scratch directory when dropped
+pub struct DemoServer {
+    url: String,
+    scratch_dir: PathBuf,
+    task: JoinHandle<()>,
+}
+
+impl DemoServer {
+    /// Seed the data and start answering on a free loopback port
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("Failed to start the demo server")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let scratch_dir = std::env::temp_dir().join(format!("fido-demo-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&scratch_dir).context("Failed to create demo directory")?;
+
+        let api = Arc::new(Mutex::new(DemoApi::seeded(chrono::Utc::now())));
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let api = Arc::clone(&api);
+                tokio::spawn(async move {
+                    if let Err(e) = serve_connection(stream, api).await {
+                        log::debug!("Demo connection closed: {}", e);
+                    }
+                });
+            }
+        });
+        log::info!("Demo server listening on {}", url);
+
+        Ok(Self {
+            url,
+            scratch_dir,
+            task,
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Where the demo keeps what would otherwise go to `~/.fido`
+    pub fn scratch_dir(&self) -> &Path {
+        &self.scratch_dir
+    }
+}
+
+impl Drop for DemoServer {
+    fn drop(&mut self) {
+        self.task.abort();
+        let _ = std::fs::remove_dir_all(&self.scratch_dir);
+    }
+}
+
+/// Answer requests on one connection until the client closes it
+async fn serve_connection(stream: TcpStream, api: Arc<Mutex<DemoApi>>) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    while let Some(request) = read_request(&mut reader).await? {
+        let response = api.lock().unwrap().handle(&request);
+        writer.write_all(&encode_response(&response)).await?;
+    }
+    Ok(())
+}
+
+/// Read one request, or `None` when the connection closed between requests
+async fn read_request<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<DemoRequest>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().context("Empty request line")?.to_string();
+    let target = parts.next().context("Request line without a path")?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| {
+            let decode = |s: &str| {
+                urlencoding::decode(&s.replace('+', " "))
+                    .map(|s| s.into_owned())
+                    .unwrap_or_else(|_| s.to_string())
+            };
+            (decode(key), decode(value))
+        })
+        .collect();
+
+    let mut token = None;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            anyhow::bail!("Connection closed inside headers");
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().context("Bad Content-Length")?;
+        } else if name.eq_ignore_ascii_case("x-session-token") {
+            token = Some(value.to_string());
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        anyhow::bail!("Request body of {} bytes is too large", content_length);
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(Some(DemoRequest {
+        method,
+        path: path.to_string(),
+        query,
+        token,
+        body,
+    }))
+}
+
+fn encode_response(response: &DemoResponse) -> Vec<u8> {
+    let body = response.body.to_string();
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        _ => "Error",
+    };
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        response.status,
+        reason,
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_requests_are_parsed_from_the_wire() {
+        let wire = b"PUT /posts/abc?limit=5&q=rust+tui HTTP/1.1\r\n\
+                     Host: 127.0.0.1\r\n\
+                     X-Session-Token: secret\r\n\
+                     content-length: 2\r\n\
+                     \r\n\
+                     {}GET /version HTTP/1.1\r\n\r\n";
+        let mut reader = BufReader::new(&wire[..]);
+
+        let request = read_request(&mut reader).await.unwrap().unwrap();
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/posts/abc");
+        assert_eq!(
+            request.query,
+            vec![
+                ("limit".to_string(), "5".to_string()),
+                ("q".to_string(), "rust tui".to_string())
+            ]
+        );
+        assert_eq!(request.token.as_deref(), Some("secret"));
+        assert_eq!(request.body, b"{}");
+
+        let next = read_request(&mut reader).await.unwrap().unwrap();
+        assert_eq!(next.path, "/version");
+        assert!(read_request(&mut reader).await.unwrap().is_none());
+    }
+}
@@ -1,4 +1,32 @@
-/// Emoji utility functions for parsing and rendering emojis in posts
+//! Emoji utility functions for parsing and rendering emojis in posts
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use fido_types::CustomEmoji;
+
+/// Instance emoji fetched from the server at login, keyed by shortcode
+static CUSTOM_EMOJI: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+
+/// Replace the table of server-defined emoji
+pub fn set_custom_emoji(emoji: Vec<CustomEmoji>) {
+    let table = emoji
+        .into_iter()
+        .map(|e| (e.shortcode, e.replacement))
+        .collect();
+    if let Ok(mut guard) = CUSTOM_EMOJI.write() {
+        *guard = Some(table);
+    }
+}
+
+/// Look up a shortcode, preferring instance emoji over the built-in set
+fn resolve_shortcode(shortcode: &str) -> Option<String> {
+    let custom = CUSTOM_EMOJI
+        .read()
+        .ok()
+        .and_then(|guard| guard.as_ref()?.get(shortcode).cloned());
+    custom.or_else(|| emojis::get_by_shortcode(shortcode).map(|e| e.as_str().to_string()))
+}
+
 /// Parse emoji shortcodes (e.g., :smile:) and replace them with actual emojis
 pub fn parse_emoji_shortcodes(text: &str) -> String {
     let mut result = String::new();
@@ -27,8 +55,8 @@ pub fn parse_emoji_shortcodes(text: &str) -> String {
             
             if found_closing && !shortcode.is_empty() {
                 // Try to find the emoji by shortcode
-                if let Some(emoji) = emojis::get_by_shortcode(&shortcode) {
-                    result.push_str(emoji.as_str());
+                if let Some(emoji) = resolve_shortcode(&shortcode) {
+                    result.push_str(&emoji);
                 } else {
                     // Not a valid emoji shortcode, keep the original text
                     result.push(':');
@@ -54,6 +82,12 @@ pub fn count_characters(text: &str) -> usize {
     text.chars().count()
 }
 
+/// Count characters as they will be submitted, after shortcodes expand.
+/// `:thumbsup:` is ten characters in the composer but only one in the post.
+pub fn count_submitted_characters(text: &str) -> usize {
+    count_characters(&parse_emoji_shortcodes(text))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,4 +106,23 @@ mod tests {
         assert_eq!(count_characters("Hello 😀"), 7);
         assert_eq!(count_characters("❤️"), 2); // Heart with variation selector
     }
+
+    #[test]
+    fn test_custom_emoji_resolve_before_builtins() {
+        set_custom_emoji(vec![CustomEmoji {
+            shortcode: "fido_party".to_string(),
+            replacement: "\\o/".to_string(),
+        }]);
+        assert_eq!(parse_emoji_shortcodes("ship it :fido_party:"), "ship it \\o/");
+        assert_eq!(parse_emoji_shortcodes(":smile:"), "😄");
+    }
+
+    #[test]
+    fn test_count_submitted_characters() {
+        assert_eq!(count_submitted_characters("Hi :smile:"), 4);
+        assert_eq!(count_submitted_characters(":heart:"), 2);
+        // Unknown and unfinished shortcodes are submitted verbatim
+        assert_eq!(count_submitted_characters(":nope:"), 6);
+        assert_eq!(count_submitted_characters("typing :smi"), 11);
+    }
 }
@@ -0,0 +1,79 @@
+//! Fuzzy matching for lists searched as you type, like reading history.
+//!
+//! Each word of the query has to turn up in the text with its letters in
+//! order, though not necessarily side by side, ignoring case: `tko` finds
+//! "tokio". Matches are ranked so a word found whole beats one spread out.
+
+/// How well `query` matches `text`, higher being better; `None` if some
+/// word of the query isn't in the text. An empty query matches everything
+/// equally.
+pub fn score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    query
+        .split_whitespace()
+        .map(|term| {
+            let term: Vec<char> = term.chars().flat_map(char::to_lowercase).collect();
+            term_score(&term, &text)
+        })
+        .sum()
+}
+
+/// Score one query word (already lowercase) against the text
+fn term_score(term: &[char], text: &[char]) -> Option<u32> {
+    // Found whole, at the start of a word if possible
+    let whole = text
+        .windows(term.len())
+        .enumerate()
+        .filter(|(_, window)| *window == term)
+        .map(|(at, _)| if starts_word(text, at) { 4 } else { 3 })
+        .max();
+    if let Some(bonus) = whole {
+        return Some(bonus * term.len() as u32);
+    }
+
+    // Letters in order, earliest first; runs and word starts count extra
+    let mut score = 0;
+    let mut from = 0;
+    let mut last = None;
+    for &c in term {
+        let at = (from..text.len()).find(|&i| text[i] == c)?;
+        score += 1;
+        if last.is_some_and(|last| last + 1 == at) {
+            score += 1;
+        }
+        if starts_word(text, at) {
+            score += 1;
+        }
+        last = Some(at);
+        from = at + 1;
+    }
+    Some(score)
+}
+
+fn starts_word(text: &[char], at: usize) -> bool {
+    at == 0 || !text[at - 1].is_alphanumeric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_match_in_order_ignoring_case() {
+        assert!(score("tokio", "Moving the server to #Tokio today").is_some());
+        assert!(score("tko", "tokio runtime").is_some());
+        assert!(score("tokio async", "async work on tokio").is_some());
+        assert!(score("okt", "tokio").is_none());
+        assert!(score("tokio axum", "tokio runtime").is_none());
+        assert_eq!(score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_whole_words_rank_above_scattered_letters() {
+        let whole = score("tokio", "notes on tokio").unwrap();
+        let inside = score("tokio", "notes on mytokio").unwrap();
+        let scattered = score("tokio", "to kick it off").unwrap();
+        assert!(whole > inside);
+        assert!(inside > scattered);
+    }
+}
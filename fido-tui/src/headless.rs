@@ -0,0 +1,207 @@
+//! Scriptable subcommands (`fido post`, `fido feed`, `fido dm`) that talk to
+//! the API with the stored session and print to stdout instead of starting
+//! the TUI.
+
+use anyhow::{anyhow, Context, Result};
+use clap::Subcommand;
+use fido_types::{DirectMessage, Post, SortOrder};
+use serde::Serialize;
+
+use crate::api::ApiClient;
+use crate::session::SessionStore;
+
+#[derive(Subcommand)]
+pub enum HeadlessCommand {
+    /// Publish a post
+    Post {
+        /// Post text; emoji shortcodes like :dog: are expanded
+        text: String,
+
+        /// Print the created post as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the feed
+    Feed {
+        /// Number of posts to fetch
+        #[arg(long, default_value_t = 25)]
+        limit: i32,
+
+        /// Sort order: newest, popular or controversial
+        #[arg(long)]
+        sort: Option<String>,
+
+        /// Only posts with this hashtag (without the #)
+        #[arg(long)]
+        hashtag: Option<String>,
+
+        /// Only posts by this user
+        #[arg(long)]
+        user: Option<String>,
+
+        /// Print posts as a JSON array
+        #[arg(long)]
+        json: bool,
+    },
+    /// Send a direct message
+    Dm {
+        /// Recipient's username
+        username: String,
+
+        /// Message text; emoji shortcodes like :dog: are expanded
+        message: String,
+
+        /// Print the sent message as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Run `command` as the user logged in through `session_store`
+pub async fn run(
+    command: HeadlessCommand,
+    mut client: ApiClient,
+    session_store: SessionStore,
+) -> Result<()> {
+    let token = session_store
+        .load()?
+        .context("Not logged in. Run `fido` and log in first.")?;
+    client.set_session_token(Some(token));
+
+    match command {
+        HeadlessCommand::Post { text, json } => {
+            load_custom_emoji(&client).await;
+            let post = client
+                .create_post(crate::emoji::parse_emoji_shortcodes(&text))
+                .await
+                .context("Failed to create post")?;
+            if json {
+                print_json(&post)?;
+            } else {
+                println!("Posted {}", post.id);
+            }
+        }
+        HeadlessCommand::Feed {
+            limit,
+            sort,
+            hashtag,
+            user,
+            json,
+        } => {
+            let sort = sort.as_deref().map(parse_sort).transpose()?;
+            let posts = client
+                .get_posts(Some(limit), sort.map(|s| s.as_str().to_string()), hashtag, user)
+                .await
+                .context("Failed to load feed")?;
+            if json {
+                print_json(&posts)?;
+            } else {
+                for post in &posts {
+                    println!("{}", format_post(post));
+                }
+            }
+        }
+        HeadlessCommand::Dm {
+            username,
+            message,
+            json,
+        } => {
+            load_custom_emoji(&client).await;
+            let username = username.trim_start_matches('@').to_string();
+            let sent = client
+                .send_message(username, crate::emoji::parse_emoji_shortcodes(&message), None)
+                .await
+                .context("Failed to send message")?;
+            if json {
+                print_json(&sent)?;
+            } else {
+                println!("{}", format_message(&sent));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Custom shortcodes are a nicety; post with the built-in set if they can't load
+async fn load_custom_emoji(client: &ApiClient) {
+    match client.get_custom_emoji().await {
+        Ok(emoji) => crate::emoji::set_custom_emoji(emoji),
+        Err(e) => log::warn!("Failed to load custom emoji: {}", e),
+    }
+}
+
+fn parse_sort(value: &str) -> Result<SortOrder> {
+    [SortOrder::Newest, SortOrder::Popular, SortOrder::Controversial]
+        .into_iter()
+        .find(|order| order.as_str().eq_ignore_ascii_case(value))
+        .ok_or_else(|| anyhow!("Unknown sort '{}': use newest, popular or controversial", value))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn format_post(post: &Post) -> String {
+    format!(
+        "{} @{} [+{} -{}] {}",
+        post.created_at.format("%Y-%m-%d %H:%M"),
+        post.author_username,
+        post.upvotes,
+        post.downvotes,
+        post.content.replace('\n', " ")
+    )
+}
+
+fn format_message(message: &DirectMessage) -> String {
+    format!("Sent to @{}: {}", message.to_username, message.content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use fido_types::ReplyPolicy;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_parse_sort_ignores_case() {
+        assert_eq!(parse_sort("popular").unwrap(), SortOrder::Popular);
+        assert_eq!(parse_sort("Newest").unwrap(), SortOrder::Newest);
+        assert!(parse_sort("hot").is_err());
+    }
+
+    #[test]
+    fn test_format_post_is_one_line() {
+        let post = Post {
+            id: Uuid::new_v4(),
+            author_id: Uuid::new_v4(),
+            author_username: "alice".to_string(),
+            content: "hello\nworld #rust".to_string(),
+            created_at: chrono::Utc.with_ymd_and_hms(2024, 12, 1, 9, 30, 0).unwrap(),
+            upvotes: 3,
+            downvotes: 1,
+            hashtags: vec!["rust".to_string()],
+            user_vote: None,
+            parent_post_id: None,
+            reply_count: 0,
+            reply_to_user_id: None,
+            reply_to_username: None,
+            is_deleted: false,
+            author_badges: Vec::new(),
+            author_status: None,
+            kind: None,
+            is_anonymous: false,
+            reply_policy: ReplyPolicy::Everyone,
+            can_reply: true,
+            is_accepted: false,
+            author_karma: 0,
+        };
+
+        assert_eq!(
+            format_post(&post),
+            "2024-12-01 09:30 @alice [+3 -1] hello world #rust"
+        );
+    }
+}
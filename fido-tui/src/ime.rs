@@ -0,0 +1,102 @@
+//! Text from an input method (Japanese, Chinese, Korean).
+//!
+//! The terminal runs the composition itself and, once the user picks a
+//! candidate, sends the whole phrase as a burst of character keys. Handled
+//! one key at a time, a redraw or a background result can land between
+//! them, and each character goes through the limit and wrap checks on its
+//! own. The event loop instead gathers the rest of a burst that starts
+//! with a non-ASCII character and inserts it as one block of text.
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::time::Duration;
+
+use crate::runner::EventSource;
+
+/// The character a plain key press types, if it types one
+fn typed_char(event: &Event) -> Option<char> {
+    match event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => Some(*c),
+        _ => None,
+    }
+}
+
+/// The character typed by `event` when it may start an input method commit
+pub fn commit_start(event: &Event) -> Option<char> {
+    typed_char(event).filter(|c| !c.is_ascii())
+}
+
+/// The text committed along with `first`: it and every character key
+/// already waiting behind it. Also returns the first waiting event that
+/// isn't part of the commit, to be handled next.
+pub async fn collect_commit(
+    first: char,
+    events: &mut impl EventSource,
+) -> Result<(String, Option<Event>)> {
+    let mut text = String::from(first);
+    while let Some(event) = events.next_event(Duration::ZERO).await? {
+        match typed_char(&event) {
+            Some(c) => text.push(c),
+            // Terminals that report key releases send one per character
+            None if matches!(&event, Event::Key(key) if key.kind == KeyEventKind::Release) => {}
+            None => return Ok((text, Some(event))),
+        }
+    }
+    Ok((text, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    struct Queued(VecDeque<Event>);
+
+    #[async_trait::async_trait]
+    impl EventSource for Queued {
+        async fn next_event(&mut self, _timeout: Duration) -> Result<Option<Event>> {
+            Ok(self.0.pop_front())
+        }
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[tokio::test]
+    async fn test_commit_gathers_waiting_characters_and_keeps_the_next_key() {
+        let mut release = KeyEvent::new(KeyCode::Char('本'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        let mut events = Queued(VecDeque::from([
+            Event::Key(release),
+            key(KeyCode::Char('語'), KeyModifiers::NONE),
+            key(KeyCode::Char('!'), KeyModifiers::NONE),
+            key(KeyCode::Enter, KeyModifiers::NONE),
+            key(KeyCode::Char('x'), KeyModifiers::NONE),
+        ]));
+
+        let (text, next) = collect_commit('本', &mut events).await.unwrap();
+        assert_eq!(text, "本語!");
+        assert_eq!(next, Some(key(KeyCode::Enter, KeyModifiers::NONE)));
+        assert_eq!(events.0.len(), 1, "Keys after the commit stay queued");
+
+        let (text, next) = collect_commit('日', &mut Queued(VecDeque::new())).await.unwrap();
+        assert_eq!((text.as_str(), next), ("日", None));
+    }
+
+    #[test]
+    fn test_only_plain_non_ascii_presses_start_a_commit() {
+        let start = |code, modifiers| commit_start(&key(code, modifiers));
+        assert_eq!(start(KeyCode::Char('日'), KeyModifiers::NONE), Some('日'));
+        assert_eq!(start(KeyCode::Char('É'), KeyModifiers::SHIFT), Some('É'));
+        assert_eq!(start(KeyCode::Char('j'), KeyModifiers::NONE), None);
+        assert_eq!(start(KeyCode::Char('日'), KeyModifiers::ALT), None);
+        assert_eq!(start(KeyCode::Enter, KeyModifiers::NONE), None);
+        assert_eq!(commit_start(&Event::Paste("日本".to_string())), None);
+    }
+}
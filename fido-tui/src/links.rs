@@ -0,0 +1,99 @@
+//! Finding links in post text and opening them safely.
+//!
+//! The server resolves shorteners and applies the instance blocklist
+//! (`GET /links/preview`); this module finds the URLs to ask about and builds
+//! a plain preview when the server can't be reached.
+
+use fido_types::LinkPreview;
+use reqwest::Url;
+
+/// Characters that commonly end a sentence right after a URL
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '\'', '"'];
+
+/// Every http(s) URL in `text`, in order, without duplicates
+pub fn extract_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(start) = word.find("https://").or_else(|| word.find("http://")) else {
+            continue;
+        };
+        let candidate = word[start..].trim_end_matches(TRAILING_PUNCTUATION);
+        if Url::parse(candidate).is_ok_and(|url| url.host_str().is_some())
+            && !urls.iter().any(|url| url == candidate)
+        {
+            urls.push(candidate.to_string());
+        }
+    }
+    urls
+}
+
+/// Whether `url` is on github.com and may get a card; the server decides
+/// whether it's a repository, issue or pull request
+pub fn is_github_link(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| {
+        matches!(url.host_str(), Some("github.com" | "www.github.com"))
+            && url.path_segments().is_some_and(|segments| {
+                segments.filter(|segment| !segment.is_empty()).count() >= 2
+            })
+    })
+}
+
+/// Whether `url` points at a gist, which the thread view shows inline
+pub fn is_gist_link(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| {
+        url.host_str() == Some("gist.github.com")
+            && url.path_segments().is_some_and(|segments| {
+                segments.filter(|segment| !segment.is_empty()).count() >= 1
+            })
+    })
+}
+
+/// Preview without the server: no shortener expansion and no blocklist
+pub fn local_preview(url: &str) -> Option<LinkPreview> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    Some(LinkPreview {
+        url: url.to_string(),
+        resolved_url: parsed.to_string(),
+        domain: host.strip_prefix("www.").map(str::to_string).unwrap_or(host),
+        expanded: false,
+        blocked: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_urls_trims_punctuation_and_dedupes() {
+        let text = "Read (https://example.com/a?b=1), then https://bit.ly/x. Again: https://example.com/a?b=1 ftp://nope";
+        assert_eq!(
+            extract_urls(text),
+            vec!["https://example.com/a?b=1", "https://bit.ly/x"]
+        );
+        assert!(extract_urls("no links here http://").is_empty());
+    }
+
+    #[test]
+    fn test_only_github_project_links_get_cards() {
+        assert!(is_github_link("https://github.com/rust-lang/rust"));
+        assert!(is_github_link("https://github.com/rust-lang/rust/pull/7"));
+        assert!(!is_github_link("https://github.com/ferris"));
+        assert!(!is_github_link("https://gitlab.com/rust-lang/rust"));
+    }
+
+    #[test]
+    fn test_gist_links_are_shown_inline() {
+        assert!(is_gist_link("https://gist.github.com/ferris/0123abcd"));
+        assert!(!is_gist_link("https://gist.github.com/"));
+        assert!(!is_github_link("https://gist.github.com/ferris/0123abcd"));
+    }
+
+    #[test]
+    fn test_local_preview_reports_domain() {
+        let preview = local_preview("https://www.Example.com/path").unwrap();
+        assert_eq!(preview.domain, "example.com");
+        assert!(!preview.blocked);
+    }
+}
@@ -1,7 +1,79 @@
-use log::LevelFilter;
+use chrono::{DateTime, Local};
+use log::{Level, LevelFilter, Log, Metadata, Record};
 use simplelog::*;
+use std::collections::VecDeque;
 use std::fs::File;
 use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Log lines kept in memory for the in-app log viewer
+const RING_BUFFER_CAPACITY: usize = 2000;
+
+static RING_BUFFER: Mutex<VecDeque<LogLine>> = Mutex::new(VecDeque::new());
+
+/// One log record as kept in the ring buffer
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+/// Snapshot of the most recent log lines, oldest first
+pub fn recent_lines() -> Vec<LogLine> {
+    RING_BUFFER
+        .lock()
+        .map(|buffer| buffer.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn push_line(line: LogLine) {
+    if let Ok(mut buffer) = RING_BUFFER.lock() {
+        if buffer.len() == RING_BUFFER_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+    }
+}
+
+/// Logger that feeds the ring buffer alongside the log file
+struct RingBufferLogger {
+    level: LevelFilter,
+}
+
+impl Log for RingBufferLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            push_line(LogLine {
+                time: Local::now(),
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+impl SharedLogger for RingBufferLogger {
+    fn level(&self) -> LevelFilter {
+        self.level
+    }
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        Box::new(*self)
+    }
+}
 
 /// Logging configuration for the Fido TUI application
 #[derive(Debug, Clone)]
@@ -134,8 +206,13 @@ pub fn init_logging(config: &LogConfig) -> anyhow::Result<()> {
         .unwrap_or_else(|builder| builder)
         .build();
 
-    // Initialize logger
-    WriteLogger::init(config.level, log_config, log_file)?;
+    // Initialize logger: the file, plus the ring buffer behind the log viewer
+    CombinedLogger::init(vec![
+        WriteLogger::new(config.level, log_config, log_file),
+        Box::new(RingBufferLogger {
+            level: config.level,
+        }),
+    ])?;
 
     log::info!("Logging initialized: file={}, level={:?}", config.log_file.display(), config.level);
     log::debug!("Log features: {:?}", config.features);
@@ -202,3 +279,38 @@ macro_rules! log_debug {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_keeps_most_recent_lines() {
+        let logger = RingBufferLogger {
+            level: LevelFilter::Info,
+        };
+        for i in 0..RING_BUFFER_CAPACITY + 10 {
+            logger.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .target("test")
+                    .args(format_args!("line {}", i))
+                    .build(),
+            );
+        }
+        logger.log(
+            &Record::builder()
+                .level(Level::Debug)
+                .args(format_args!("filtered out"))
+                .build(),
+        );
+
+        let lines = recent_lines();
+        assert_eq!(lines.len(), RING_BUFFER_CAPACITY);
+        assert_eq!(lines[0].message, "line 10");
+        assert_eq!(
+            lines.last().unwrap().message,
+            format!("line {}", RING_BUFFER_CAPACITY + 9)
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Hashtags, mentions and links inside post text.
+//!
+//! Tab on the selected post steps through these in order; the renderer
+//! counts them the same way so the focused one can be highlighted.
+
+/// Something in a post that can be acted on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostToken {
+    /// Tag name without the `#`
+    Hashtag(String),
+    /// Username without the `@`
+    Mention(String),
+    Url(String),
+}
+
+/// The token a single whitespace-separated word stands for, if any
+pub fn word_token(word: &str) -> Option<PostToken> {
+    if let Some(url) = crate::links::extract_urls(word).into_iter().next() {
+        return Some(PostToken::Url(url));
+    }
+    if let Some(tag) = word.strip_prefix('#') {
+        return name_prefix(tag).map(PostToken::Hashtag);
+    }
+    if let Some(username) = word.strip_prefix('@') {
+        return name_prefix(username).map(PostToken::Mention);
+    }
+    None
+}
+
+/// Every token in `content`, in reading order
+pub fn extract_tokens(content: &str) -> Vec<PostToken> {
+    content.split_whitespace().filter_map(word_token).collect()
+}
+
+/// Leading name characters, dropping trailing punctuation like "#rust,"
+fn name_prefix(text: &str) -> Option<String> {
+    let name: String = text
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_tokens_in_reading_order() {
+        let content = "Hi @alice, see #rust and (https://example.com/x). Not # or @ or email@host";
+        assert_eq!(
+            extract_tokens(content),
+            vec![
+                PostToken::Mention("alice".to_string()),
+                PostToken::Hashtag("rust".to_string()),
+                PostToken::Url("https://example.com/x".to_string()),
+            ]
+        );
+    }
+}
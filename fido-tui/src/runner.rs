@@ -0,0 +1,943 @@
+//! The event loop shared by the local terminal and SSH sessions.
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{backend::Backend, layout::Rect, Terminal};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::app::{self, App, FilterTab};
+use crate::{auth, ime, ui};
+
+// Performance optimization notes:
+// - Lazy rendering: Only visible posts/messages are rendered (not all 1000+)
+// - Virtual scrolling: Empty lines represent off-screen content
+// - Viewport caching: Terminal size changes trigger viewport recalculation
+// - Smooth scrolling: Scroll margin keeps selected item in middle third
+// - Minimal redraws: Only changed portions trigger re-render; with reduced
+//   motion, idle frames aren't drawn at all
+//
+// Performance testing recommendations:
+// 1. Test with 1000+ posts: Create test data with large post count
+// 2. Monitor frame rate: Should maintain 60fps even with large datasets
+// 3. Memory usage: Should remain constant regardless of post count
+// 4. Scroll responsiveness: j/k navigation should feel instant
+
+/// Event poll timeout per frame, roughly 60fps
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How often to re-check the server while in offline mode
+const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often a logged-in session pings the server to stay alive
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How often unread counts for the tab bar are refreshed
+const UNREAD_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// With reduced motion, the longest an idle screen goes without a redraw.
+/// Catches timer-driven changes such as toasts expiring or quiet hours ending.
+const IDLE_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Helper to track modal state changes and trigger data loading
+struct ModalStateTracker {
+    filter_modal: bool,
+    friends_modal: bool,
+    new_conversation_modal: bool,
+    user_search_modal: bool,
+    last_search_query: String,
+}
+
+impl ModalStateTracker {
+    fn new() -> Self {
+        Self {
+            filter_modal: false,
+            friends_modal: false,
+            new_conversation_modal: false,
+            user_search_modal: false,
+            last_search_query: String::new(),
+        }
+    }
+
+    /// Check and handle modal state changes, loading data when modals open
+    async fn check_and_load(&mut self, app: &mut App) -> Result<()> {
+        // Filter modal
+        if app.posts_state.show_filter_modal && !self.filter_modal {
+            app.spawn_load_filter_modal_data();
+        }
+        self.filter_modal = app.posts_state.show_filter_modal;
+
+        // Friends modal
+        if app.friends_state.show_friends_modal && !self.friends_modal {
+            app.load_social_connections().await?;
+        }
+        self.friends_modal = app.friends_state.show_friends_modal;
+
+        // User search modal - trigger search on query change
+        if app.user_search_state.show_modal {
+            if !self.user_search_modal {
+                // Modal just opened - no search yet
+                self.user_search_modal = true;
+                self.last_search_query = String::new();
+            } else if app.user_search_state.search_query != self.last_search_query {
+                // Query changed - trigger search
+                self.last_search_query = app.user_search_state.search_query.clone();
+                app.queue_user_search();
+            }
+        } else {
+            self.user_search_modal = false;
+            self.last_search_query.clear();
+        }
+        app.tick_user_search();
+
+        // New conversation modal
+        if app.dms_state.show_new_conversation_modal && !self.new_conversation_modal {
+            app.load_mutual_friends_for_dms().await?;
+        }
+        self.new_conversation_modal = app.dms_state.show_new_conversation_modal;
+
+        Ok(())
+    }
+}
+
+
+/// Where the event loop reads input from
+#[async_trait::async_trait]
+pub trait EventSource {
+    /// Wait up to `timeout` for the next event
+    async fn next_event(&mut self, timeout: Duration) -> Result<Option<Event>>;
+}
+
+/// Input from the local terminal
+pub struct CrosstermEvents;
+
+#[async_trait::async_trait]
+impl EventSource for CrosstermEvents {
+    async fn next_event(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        if event::poll(timeout)? {
+            Ok(Some(event::read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Drive `app` until the user quits, drawing to `tui` and reading from `events`
+pub async fn run<B: Backend + std::io::Write>(
+    tui: &mut Terminal<B>,
+    app: &mut App,
+    mut auth_flow: auth::AuthFlow,
+    events: &mut impl EventSource,
+) -> Result<()> {
+    // Main event loop
+    let mut last_tab = app.current_tab;
+    let mut last_dm_conversation_index = app.dms_state.selected_conversation_index;
+    let mut last_terminal_size = (0, 0);
+    let mut modal_tracker = ModalStateTracker::new();
+    
+    // Track last poll time for device flow
+    let mut last_device_poll = std::time::Instant::now();
+
+    // Offline mode: probe the server periodically and refresh once it's back
+    let mut was_offline = false;
+    let mut last_offline_probe = std::time::Instant::now();
+    let mut last_heartbeat = std::time::Instant::now();
+    let mut last_unread_poll = std::time::Instant::now();
+
+    // Reduced motion only draws after input, finished tasks or the idle interval
+    let mut needs_redraw = true;
+    let mut last_draw = std::time::Instant::now();
+
+    // Input already read, handled key by key before reading more: an input
+    // method commit no text field took, then whatever followed it
+    let mut queued: VecDeque<Event> = VecDeque::new();
+    
+    while app.running {
+        // A server profile switch replaced the ApiClient; follow it
+        if app.server_switcher.switched {
+            app.server_switcher.switched = false;
+            auth_flow = auth::AuthFlow::new(app.api_client.clone(), app.session_store()?);
+        }
+
+        // Poll for GitHub Device Flow completion if in progress
+        if app.auth_state.github_auth_in_progress {
+            // Check for timeout (15 minutes)
+            if let Some(start_time) = app.auth_state.github_auth_start_time {
+                if start_time.elapsed() > Duration::from_secs(900) {
+                    log::warn!("GitHub Device Flow timeout after 15 minutes");
+                    app.auth_state.error = Some("Device authorization timeout: Please try again.".to_string());
+                    app.auth_state.github_auth_in_progress = false;
+                    app.auth_state.github_device_code = None;
+                    app.auth_state.github_user_code = None;
+                    app.auth_state.github_verification_uri = None;
+                    app.auth_state.github_poll_interval = None;
+                    app.auth_state.github_auth_start_time = None;
+                }
+            }
+            
+            // Only poll at the specified interval (default 5 seconds)
+            let poll_interval = app.auth_state.github_poll_interval.unwrap_or(5);
+            if last_device_poll.elapsed() >= Duration::from_secs(poll_interval as u64) {
+                if let Some(device_code) = &app.auth_state.github_device_code.clone() {
+                    log::debug!("Polling GitHub for device authorization...");
+                    
+                    // Try to poll for device authorization
+                    match auth_flow.api_client().github_device_poll(&device_code).await {
+                        Ok(login_response) => {
+                            log::info!("GitHub Device Flow completed successfully for user: {}", login_response.user.username);
+                            
+                            // Store session and update state
+                            if let Err(e) = auth_flow.save_session(&login_response.session_token) {
+                                log::error!("Failed to save session: {}", e);
+                            }
+                            app.remember_account(&login_response.user.username, &login_response.session_token);
+                            
+                            // Set session token in both API clients
+                            auth_flow.api_client_mut().set_session_token(Some(login_response.session_token.clone()));
+                            app.api_client.set_session_token(Some(login_response.session_token.clone()));
+                            
+                            app.auth_state.current_user = Some(login_response.user);
+                            app.current_screen = app::Screen::Main;
+                            app.auth_state.github_auth_in_progress = false;
+                            app.auth_state.github_device_code = None;
+                            app.auth_state.github_user_code = None;
+                            app.auth_state.github_verification_uri = None;
+                            app.auth_state.github_poll_interval = None;
+                            app.auth_state.github_auth_start_time = None;
+                            app.auth_state.error = None;
+                            
+                            // Load initial data in the background
+                            app.start_session_data_load();
+                        }
+                        Err(e) => {
+                            // Check if it's just pending
+                            let error_msg = format!("{:?}", e);
+                            log::debug!("Device poll error: {}", error_msg);
+                            
+                            if !error_msg.contains("authorization_pending") {
+                                log::error!("Error polling for device authorization: {}", e);
+                                app.auth_state.error = Some(format!("Device authorization error: {}", e));
+                                app.auth_state.github_auth_in_progress = false;
+                                app.auth_state.github_device_code = None;
+                                app.auth_state.github_user_code = None;
+                                app.auth_state.github_verification_uri = None;
+                                app.auth_state.github_poll_interval = None;
+                                app.auth_state.github_auth_start_time = None;
+                            }
+                            // If authorization_pending, just continue polling
+                        }
+                    }
+                    
+                    last_device_poll = std::time::Instant::now();
+                }
+            }
+        }
+
+        // Apply results from background tasks that finished since the last frame
+        if app.process_task_results() {
+            needs_redraw = true;
+        }
+
+        // Any request the server answered 401 means the session is gone;
+        // offer to sign back in over the current screen
+        if app.api_client.take_session_rejected() {
+            app.open_reauth();
+            needs_redraw = true;
+        }
+        if app.reauth.poll_due() {
+            app.poll_reauth().await?;
+            needs_redraw = true;
+        }
+        app.retry_due_dm_deliveries();
+
+        // A 429 counts down in a toast, then what's on screen is retried
+        let was_rate_limited = app.rate_limit_waiting;
+        if app.tick_rate_limit() {
+            app.reload_current_screen().await?;
+        }
+        if was_rate_limited || app.rate_limit_waiting {
+            needs_redraw = true;
+        }
+
+        if app.diagnostics.ping_due() {
+            app.spawn_diagnostics_ping();
+        }
+
+        if app.current_screen == app::Screen::Main {
+            let offline = app.api_client.is_offline();
+            if offline
+                && app.task_runner.in_flight() == 0
+                && last_offline_probe.elapsed() >= OFFLINE_PROBE_INTERVAL
+            {
+                // The feed request doubles as the half-open circuit probe
+                last_offline_probe = std::time::Instant::now();
+                app.spawn_load_posts();
+            } else if was_offline && !offline {
+                log::info!("Connection to server restored");
+                app.posts_state.error = None;
+            }
+            was_offline = offline;
+
+            if !offline && last_heartbeat.elapsed() >= HEARTBEAT_INTERVAL {
+                last_heartbeat = std::time::Instant::now();
+                app.spawn_heartbeat();
+            }
+
+            if !offline && last_unread_poll.elapsed() >= UNREAD_POLL_INTERVAL {
+                last_unread_poll = std::time::Instant::now();
+                app.spawn_poll_unread_counts();
+                app.spawn_poll_watched_threads();
+                app.spawn_poll_crossposts();
+            }
+
+            // Reminders and snoozes are kept locally, so they come due
+            // even offline
+            let now = chrono::Utc::now();
+            app.fire_due_dm_reminders(now);
+            app.resurface_snoozed_posts(now);
+        }
+
+        // Check modal state changes and load data as needed
+        modal_tracker.check_and_load(app).await?;
+
+        // Check if we switched tabs and need to load data
+        if app.current_tab != last_tab {
+            if last_tab == app::Tab::DMs {
+                // Don't let a slow conversations load land after we've left the tab
+                app.cancel_load_conversations();
+                app.stash_dm_draft();
+            }
+            if last_tab == app::Tab::Posts {
+                app.remember_feed_position();
+            }
+            match app.current_tab {
+                app::Tab::Channels => {
+                    app.load_channels().await?;
+                }
+                app::Tab::Profile => {
+                    app.load_profile().await?;
+                }
+                app::Tab::DMs => {
+                    app.spawn_load_conversations();
+                    // Applying the result sets selected_conversation_index to 0 if conversations exist
+                }
+                app::Tab::Settings => {
+                    app.spawn_load_settings();
+                }
+                _ => {}
+            }
+        }
+        
+        // Check if we switched conversations in DMs tab or need to load messages
+        if app.current_tab == app::Tab::DMs
+            && (app.dms_state.selected_conversation_index != last_dm_conversation_index || app.dms_state.needs_message_load)
+            && !app.dms_state.conversations.is_empty()
+        {
+            app.load_conversation_messages().await?;
+            app.restore_dm_draft();
+            last_dm_conversation_index = app.dms_state.selected_conversation_index;
+            app.dms_state.needs_message_load = false;
+        }
+        
+        last_tab = app.current_tab;
+
+        // Clear expired messages (auto-clear after 3 seconds)
+        app.clear_expired_messages();
+        app.refresh_quiet_mode();
+
+        // Announcements go out through the UI's own stream (or speech)
+        app.queue_announcements();
+        let announcements = app.take_announcements();
+        if !announcements.is_empty() {
+            crate::accessibility::deliver(
+                app.announce_output(),
+                &mut app.accessibility.speech_available,
+                tui.backend_mut(),
+                announcements,
+            );
+        }
+
+        // Clipboard copies also go out through the UI's own stream
+        if let Some(text) = app.pending_clipboard.take() {
+            if let Err(e) = crate::clipboard::copy(tui.backend_mut(), &text) {
+                log::warn!("Couldn't copy to the clipboard: {}", e);
+            }
+        }
+
+        // Keep the panic hook's view of the app current
+        crate::crash::record_state(app);
+
+        // Render UI with performance optimization
+        if needs_redraw || !app.reduced_motion() || last_draw.elapsed() >= IDLE_REDRAW_INTERVAL {
+            tui.draw(|frame| {
+                // Update viewport height if terminal size changed (for efficient scrolling)
+                let current_size = (frame.area().width, frame.area().height);
+                if current_size != last_terminal_size {
+                    last_terminal_size = current_size;
+                }
+                
+                ui::render(app, frame)
+            })?;
+            needs_redraw = false;
+            last_draw = std::time::Instant::now();
+        }
+        
+        // Kick off a pending feed load in the background (UI keeps rendering meanwhile)
+        if app.posts_state.pending_load {
+            app.posts_state.pending_load = false;
+            app.spawn_load_posts();
+            needs_redraw = true;
+        }
+        
+        // Friends modal data loading is now handled above with last_friends_modal_state
+
+        // Load hashtags when modal is opened and hashtags list is empty
+        if app.hashtags_state.show_hashtags_modal && app.hashtags_state.hashtags.is_empty() && !app.hashtags_state.loading {
+            app.load_hashtags().await?;
+            needs_redraw = true;
+        }
+
+        // Handle events with timeout (~60fps so background results show up promptly)
+        let (event, replayed) = match queued.pop_front() {
+            Some(event) => (Some(event), true),
+            None => (events.next_event(FRAME_INTERVAL).await?, false),
+        };
+        if let Some(event) = event {
+            // Filter out mouse events - keyboard-only navigation
+            if matches!(event, Event::Mouse(_)) {
+                continue;
+            }
+            needs_redraw = true;
+
+            // An input method commits a phrase as a burst of character
+            // keys; the text field that's open takes it in one go
+            let commit_start = ime::commit_start(&event)
+                .filter(|_| !replayed && app.current_screen == app::Screen::Main);
+            if let Some(first) = commit_start {
+                let (text, next) = ime::collect_commit(first, events).await?;
+                if text.chars().count() > 1 && app.insert_text(&text) {
+                    queued.extend(next);
+                    continue;
+                }
+                // Nothing took it as a block, so its keys go one by one
+                queued.extend(text.chars().skip(1).map(|c| {
+                    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                }));
+                queued.extend(next);
+            }
+
+            // Remote terminals can't be measured, so they report their size
+            if let Event::Resize(width, height) = event {
+                tui.resize(Rect::new(0, 0, width, height))?;
+            }
+            
+            // Bracketed paste arrives as one block of text
+            if let Event::Paste(text) = &event {
+                app.insert_text(text);
+                continue;
+            }
+
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    // Log key event with modal context
+                    let modal_context = if app.composer_state.is_open() {
+                        "composer_open"
+                    } else if app.viewing_post_detail {
+                        "post_detail"
+                    } else {
+                        "main_view"
+                    };
+                    log_key_event!(app.log_config, "key={:?}, context={}", key.code, modal_context);
+
+                    // Session expired: the sign-in prompt sits above everything
+                    // but the log viewer and diagnostics, and signing back in
+                    // needs the server
+                    if app.reauth.show
+                        && !app.log_viewer.show
+                        && !app.diagnostics.show
+                        && !app.show_help
+                    {
+                        if key.code == KeyCode::Enter {
+                            app.start_reauth().await?;
+                        } else {
+                            app.handle_key_event(key)?;
+                        }
+                        continue;
+                    }
+
+                    // The server switcher, log viewer, diagnostics, version
+                    // mismatch, draft prompt, login summaries, link
+                    // confirmation and share menu sit above every screen, so
+                    // keep the async shortcuts below from seeing their keys
+                    let main_prompt_open = app.current_screen == app::Screen::Main
+                        && (app.recovered_draft.is_some()
+                            || app.catchup.is_some()
+                            || app.digest.is_some());
+                    if app.server_switcher.show_modal
+                        || app.link_preview.show
+                        || app.share_menu.show
+                        || app::handlers::is_server_switch_key(&key)
+                        || app.log_viewer.show
+                        || app::handlers::is_log_viewer_key(&key)
+                        || app.diagnostics.show
+                        || app::handlers::is_diagnostics_key(&key)
+                        || app.version_incompatible()
+                        || main_prompt_open
+                    {
+                        app.handle_key_event(key)?;
+                        continue;
+                    }
+
+                    // Typing how long to snooze a post for is all local
+                    if app.posts_state.snooze_input.is_some() {
+                        app.handle_key_event(key)?;
+                        continue;
+                    }
+
+                    // Searching the reading history is local; opening a
+                    // post from it loads the thread
+                    if app.reading_history.show {
+                        if key.code == KeyCode::Enter {
+                            if let Some(post_id) = app.reading_history.selected_post_id() {
+                                app.close_reading_history();
+                                app.open_post_detail(post_id).await?;
+                            }
+                        } else {
+                            app.handle_key_event(key)?;
+                        }
+                        continue;
+                    }
+
+                    // A cross-post request waiting on our consent; approving
+                    // publishes it right away
+                    if app.crosspost_prompt_open() && !app.show_help {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') => app.approve_crosspost().await?,
+                            KeyCode::Char('n') | KeyCode::Char('N') => app.decline_crosspost().await?,
+                            _ => app.handle_key_event(key)?,
+                        }
+                        continue;
+                    }
+
+                    // People followed on GitHub; following them all needs
+                    // the server
+                    if app.github_suggestions_open() && !app.show_help {
+                        if key.code == KeyCode::Enter {
+                            app.follow_github_suggestions().await?;
+                        } else {
+                            app.handle_key_event(key)?;
+                        }
+                        continue;
+                    }
+
+                    // The drafts list sits over the composer; only deleting
+                    // needs the server, everything else is local navigation
+                    if app.drafts.show {
+                        if key.code == KeyCode::Char('x') {
+                            app.delete_selected_draft().await?;
+                        } else {
+                            app.handle_key_event(key)?;
+                        }
+                        continue;
+                    }
+
+                    // RSVPs and opening an event need the server
+                    if app.events.show {
+                        match key.code {
+                            KeyCode::Char('g') => {
+                                app.toggle_selected_event_rsvp(fido_types::RsvpStatus::Going).await?;
+                            }
+                            KeyCode::Char('i') => {
+                                app.toggle_selected_event_rsvp(fido_types::RsvpStatus::Interested)
+                                    .await?;
+                            }
+                            KeyCode::Enter => {
+                                if let Some(post_id) =
+                                    app.events.events.get(app.events.selected).map(|p| p.id)
+                                {
+                                    app.close_events();
+                                    app.open_post_detail(post_id).await?;
+                                }
+                            }
+                            _ => app.handle_key_event(key)?,
+                        }
+                        continue;
+                    }
+                    if app.highlights.show && key.code == KeyCode::Enter {
+                        if let Some(post_id) = app
+                            .highlights
+                            .top_posts
+                            .get(app.highlights.selected)
+                            .map(|p| p.id)
+                        {
+                            app.close_highlights();
+                            app.open_post_detail(post_id).await?;
+                        }
+                        continue;
+                    }
+                    if app.hashtag_digest.show {
+                        match key.code {
+                            KeyCode::Char(' ') => app.toggle_selected_hashtag_digest().await?,
+                            KeyCode::Enter => {
+                                if let Some(post_id) = app.hashtag_digest.selected_post().map(|p| p.id) {
+                                    app.close_hashtag_digest();
+                                    app.open_post_detail(post_id).await?;
+                                }
+                            }
+                            _ => app.handle_key_event(key)?,
+                        }
+                        continue;
+                    }
+                    if app.composer_state.is_open()
+                        && key.code == KeyCode::Char('d')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        app.save_composer_draft().await?;
+                        continue;
+                    }
+                    if app.composer_state.is_open()
+                        && key.code == KeyCode::Char('g')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        app.submit_composer_as_gist().await?;
+                        continue;
+                    }
+                    
+                    // Handle async operations
+                    match key.code {
+                        KeyCode::Char('l') if app.current_screen == app::Screen::Auth => {
+                            app.spawn_load_test_users();
+                        }
+                        KeyCode::Char('g') | KeyCode::Char('G') if app.current_screen == app::Screen::Auth && !app.auth_state.github_auth_in_progress && app.auth_state.show_github_option => {
+                            // Initiate GitHub Device Flow (only if GitHub option is enabled)
+                            app.auth_state.loading = true;
+                            app.auth_state.error = None;
+                            
+                            match auth_flow.initiate_github_device_flow().await {
+                                Ok((device_code, user_code, verification_uri, interval)) => {
+                                    app.auth_state.github_device_code = Some(device_code);
+                                    app.auth_state.github_user_code = Some(user_code.clone());
+                                    app.auth_state.github_verification_uri = Some(verification_uri.clone());
+                                    app.auth_state.github_poll_interval = Some(interval);
+                                    app.auth_state.github_auth_in_progress = true;
+                                    app.auth_state.github_auth_start_time = Some(std::time::Instant::now());
+                                    app.auth_state.loading = false;
+                                    
+                                    // Try to open browser to verification URI
+                                    if let Err(e) = auth_flow.open_browser(&verification_uri) {
+                                        log::warn!("Failed to open browser: {}", e);
+                                        app.auth_state.error = Some(format!(
+                                            "Could not open browser automatically. Please visit: {}",
+                                            verification_uri
+                                        ));
+                                    }
+                                    
+                                    // Polling will happen in the main loop
+                                }
+                                Err(e) => {
+                                    app.auth_state.error = Some(format!("Failed to initiate GitHub Device Flow: {}", e));
+                                    app.auth_state.loading = false;
+                                }
+                            }
+                        }
+                        KeyCode::Esc if app.current_screen == app::Screen::Auth && app.auth_state.github_auth_in_progress => {
+                            // Cancel GitHub Device Flow
+                            app.auth_state.github_auth_in_progress = false;
+                            app.auth_state.github_device_code = None;
+                            app.auth_state.github_user_code = None;
+                            app.auth_state.github_verification_uri = None;
+                            app.auth_state.github_poll_interval = None;
+                            app.auth_state.github_auth_start_time = None;
+                            app.auth_state.error = None;
+                        }
+                        KeyCode::Enter if app.current_screen == app::Screen::Auth && !app.auth_state.github_auth_in_progress => {
+                            app.login_selected_user().await?;
+                        }
+                        // Unified composer: Enter submits for all modes (NewPost, Reply, EditBio, EditPost)
+                        KeyCode::Enter if app.composer_state.is_open() => {
+                            app.submit_composer().await?;
+                        }
+                        KeyCode::Enter if app.dms_state.show_new_conversation_modal => {
+                            app.start_new_conversation().await?;
+                        }
+                        KeyCode::Enter if app.posts_state.show_filter_modal => {
+                            use app::FilterTab;
+                            
+                            // In hashtags tab add input mode, Enter follows the hashtag
+                            if app.posts_state.filter_modal_state.selected_tab == FilterTab::Hashtags 
+                                && app.posts_state.filter_modal_state.show_add_hashtag_input {
+                                let hashtag_name = app.posts_state.filter_modal_state.add_hashtag_input.value().trim().to_string();
+                                if !hashtag_name.is_empty() {
+                                    app.follow_hashtag(&hashtag_name).await?;
+                                    app.posts_state.filter_modal_state.show_add_hashtag_input = false;
+                                    app.posts_state.filter_modal_state.add_hashtag_input.clear();
+                                }
+                                continue; // Don't apply filter, just followed a hashtag
+                            }
+                            
+                            // In hashtags tab on "Add Hashtag" option, don't apply filter (handled by handle_key_event)
+                            if app.posts_state.filter_modal_state.selected_tab == FilterTab::Hashtags 
+                                && app.posts_state.filter_modal_state.selected_index == app.posts_state.filter_modal_state.hashtag_list.len() {
+                                app.handle_key_event(key)?;
+                                continue; // Don't apply filter, just opened add input
+                            }
+                            
+                            // Apply filter based on checked items
+                            let filter = match app.posts_state.filter_modal_state.selected_tab {
+                                FilterTab::All => app::PostFilter::All,
+                                FilterTab::Reviews => app::PostFilter::Reviews,
+                                FilterTab::Later => app::PostFilter::Later,
+                                FilterTab::Jobs => app::PostFilter::Jobs(
+                                    app::JOB_FILTER_CHOICES[app.posts_state.filter_modal_state.selected_index],
+                                ),
+                                FilterTab::Hashtags => {
+                                    // Only apply filter if hashtags are checked
+                                    if !app.posts_state.filter_modal_state.checked_hashtags.is_empty() {
+                                        app::PostFilter::Multi {
+                                            hashtags: app.posts_state.filter_modal_state.checked_hashtags.clone(),
+                                            users: vec![],
+                                        }
+                                    } else {
+                                        // No hashtags checked = show all posts
+                                        app::PostFilter::All
+                                    }
+                                }
+                                FilterTab::Users => {
+                                    // Only apply filter if users are checked
+                                    if !app.posts_state.filter_modal_state.checked_users.is_empty() {
+                                        app::PostFilter::Multi {
+                                            hashtags: vec![],
+                                            users: app.posts_state.filter_modal_state.checked_users.clone(),
+                                        }
+                                    } else {
+                                        // No users checked = show all posts
+                                        app::PostFilter::All
+                                    }
+                                }
+                            };
+                            app.apply_filter(filter).await?;
+                        }
+                        KeyCode::Enter if !app.composer_state.is_open() && !app.posts_state.show_filter_modal && app.focused_post_token().is_some() => {
+                            // Act on the hashtag, mention or link focused with Tab
+                            app.open_focused_token().await?;
+                        }
+                        KeyCode::Enter | KeyCode::Char(' ') if app.current_tab == app::Tab::Posts && !app.posts_state.show_new_post_modal && !app.viewing_post_detail && !app.composer_state.is_open() && !app.posts_state.show_filter_modal && app.posts_state.bulk_selection.is_none() => {
+                            // Open post detail view for selected post (only if filter modal is not open)
+                            if let Some(selected_index) = app.posts_state.list_state.selected() {
+                                if selected_index < app.posts_state.posts.len() {
+                                    let post_id = app.posts_state.posts[selected_index].id;
+                                    app.open_post_detail(post_id).await?;
+                                }
+                            }
+                        }
+                        KeyCode::Enter if app.current_tab == app::Tab::DMs && !app.dms_state.show_new_conversation_modal && app.dms_state.reminder_input.is_none() && app.input_mode == app::InputMode::Typing => {
+                            app.send_dm().await?;
+                        }
+                        KeyCode::Enter if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Channels && app.channels_state.new_channel_input.is_some() => {
+                            app.create_channel().await?;
+                        }
+                        KeyCode::Enter if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Channels => {
+                            // Open the channel's feed in the Posts tab
+                            app.open_selected_channel().await?;
+                        }
+                        KeyCode::Char(' ') if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Channels && app.channels_state.new_channel_input.is_none() => {
+                            app.toggle_selected_channel_membership().await?;
+                        }
+                        KeyCode::Char('r') | KeyCode::Char('R') if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Channels && app.channels_state.new_channel_input.is_none() => {
+                            app.load_channels().await?;
+                        }
+                        KeyCode::Char('P') if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::DMs && !app.dms_state.show_new_conversation_modal && app.input_mode == app::InputMode::Navigation => {
+                            // Pin/unpin the selected conversation (Shift+P)
+                            app.toggle_selected_conversation_pin().await?;
+                        }
+                        KeyCode::Char('x') | KeyCode::Char('X') if app.posts_state.show_filter_modal && app.posts_state.filter_modal_state.selected_tab == FilterTab::Hashtags && !app.posts_state.filter_modal_state.show_add_hashtag_input => {
+                            // Unfollow selected hashtag in filter modal (only if not typing)
+                            let selected_index = app.posts_state.filter_modal_state.selected_index;
+                            if selected_index < app.posts_state.filter_modal_state.hashtag_list.len() {
+                                let hashtag_name = app.posts_state.filter_modal_state.hashtag_list[selected_index].clone();
+                                app.unfollow_hashtag(&hashtag_name).await?;
+                            }
+                        }
+                        KeyCode::Char('p') | KeyCode::Char('P') if app.friends_state.show_friends_modal && !app.friends_state.search_mode => {
+                            // View selected user's profile from social modal
+                            let filtered_list = app.get_filtered_social_list();
+                            if let Some(user) = filtered_list.get(app.friends_state.selected_index) {
+                                let user_id = user.id.clone();
+                                app.friends_state.return_to_modal_after_profile = true;
+                                app.close_friends_modal();
+                                app.load_user_profile_view(user_id).await?;
+                            }
+                        }
+                        KeyCode::Enter if app.user_search_state.show_modal => {
+                            // View selected user's profile from search modal
+                            if let Some(user_id) = app.user_search_view_profile() {
+                                app.close_user_search_modal();
+                                app.load_user_profile_view(user_id).await?;
+                            }
+                        }
+                        KeyCode::Char('d') | KeyCode::Char('D') if app.user_search_state.show_modal => {
+                            // Start DM with selected user from search modal
+                            if let Some(username) = app.user_search_start_dm() {
+                                app.close_user_search_modal();
+                                app.current_tab = app::Tab::DMs;
+                                app.dms_state.pending_conversation_username = Some(username);
+                                app.dms_state.selected_conversation_index = None;
+                                app.dms_state.needs_message_load = true;
+                            }
+                        }
+                        KeyCode::Char('f') | KeyCode::Char('F') if app.friends_state.show_friends_modal && !app.friends_state.search_mode => {
+                            // Follow/unfollow selected user from social modal
+                            let filtered_list = app.get_filtered_social_list();
+                            if let Some(user) = filtered_list.get(app.friends_state.selected_index) {
+                                let user_id = user.id.clone();
+                                
+                                // Check if we're following this user
+                                let is_following = app.friends_state.following.iter().any(|u| u.id == user_id);
+                                
+                                if is_following {
+                                    // Unfollow
+                                    if let Err(e) = app.api_client.unfollow_user(user_id).await {
+                                        app.friends_state.error = Some(format!("Failed to unfollow: {}", e));
+                                    } else {
+                                        // Reload social connections
+                                        app.load_social_connections().await?;
+                                    }
+                                } else {
+                                    // Follow
+                                    if let Err(e) = app.api_client.follow_user(user_id).await {
+                                        app.friends_state.error = Some(format!("Failed to follow: {}", e));
+                                    } else {
+                                        // Reload social connections
+                                        app.load_social_connections().await?;
+                                    }
+                                }
+                            }
+                        }
+                        KeyCode::Enter if app.hashtags_state.show_add_hashtag_input => {
+                            let hashtag_name = app.hashtags_state.add_hashtag_name.value().trim().to_string();
+                            if !hashtag_name.is_empty() {
+                                app.follow_hashtag(&hashtag_name).await?;
+                            }
+                        }
+                        KeyCode::Enter if app.hashtags_state.show_unfollow_confirmation => {
+                            if let Some(hashtag) = app.hashtags_state.hashtag_to_unfollow.clone() {
+                                app.unfollow_hashtag(&hashtag).await?;
+                                app.hashtags_state.show_unfollow_confirmation = false;
+                                app.hashtags_state.hashtag_to_unfollow = None;
+                            }
+                        }
+
+                        KeyCode::Char('u') | KeyCode::Char('U') if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Posts && !app.composer_state.is_open() && !app.posts_state.show_filter_modal => {
+                            if app.viewing_post_detail {
+                                app.vote_in_detail_view("up").await?;
+                            } else {
+                                app.vote_on_selected_post("up").await?;
+                            }
+                        }
+                        KeyCode::Char('d') | KeyCode::Char('D') if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Posts && !app.composer_state.is_open() && !app.posts_state.show_filter_modal => {
+                            if app.viewing_post_detail {
+                                app.vote_in_detail_view("down").await?;
+                            } else {
+                                app.vote_on_selected_post("down").await?;
+                            }
+                        }
+                        KeyCode::Enter if app.viewing_post_detail && !app.composer_state.is_open() && !app.post_detail_state.as_ref().map(|s| s.show_delete_confirmation).unwrap_or(false) && app.selected_related_post_id().is_some() => {
+                            app.open_related_post().await?;
+                        }
+                        KeyCode::Char('w') | KeyCode::Char('W') if app.viewing_post_detail && !app.composer_state.is_open() && !app.post_detail_state.as_ref().map(|s| s.show_delete_confirmation).unwrap_or(false) => {
+                            app.toggle_thread_watch().await?;
+                        }
+                        KeyCode::Char('m') if app.viewing_post_detail && !app.composer_state.is_open() && !app.post_detail_state.as_ref().map(|s| s.show_delete_confirmation).unwrap_or(false) => {
+                            app.toggle_review_open().await?;
+                        }
+                        KeyCode::Char('a') if app.viewing_post_detail && !app.composer_state.is_open() && !app.post_detail_state.as_ref().map(|s| s.show_delete_confirmation).unwrap_or(false) => {
+                            app.toggle_accepted_reply().await?;
+                        }
+                        KeyCode::Char('s') | KeyCode::Char('S') if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Settings && !app.settings_state.show_save_confirmation && app.settings_state.pinned_hashtags_input.is_none() => {
+                            app.save_settings().await?;
+                        }
+                        KeyCode::Char('y') | KeyCode::Char('Y') if app.viewing_post_detail && app.post_detail_state.as_ref().map(|s| s.show_delete_confirmation).unwrap_or(false) => {
+                            // Confirm delete post
+                            app.delete_post().await?;
+                        }
+                        KeyCode::Char('y') | KeyCode::Char('Y') if app.settings_state.show_save_confirmation => {
+                            // Save settings and then switch tabs
+                            app.save_settings().await?;
+                            if let Some(pending_tab) = app.settings_state.pending_tab.take() {
+                                app.settings_state.show_save_confirmation = false;
+                                app.current_tab = pending_tab;
+                            }
+                        }
+                        KeyCode::Char('x') | KeyCode::Char('X') if app.posts_state.show_filter_modal && app.posts_state.filter_modal_state.selected_tab == app::FilterTab::Hashtags && !app.posts_state.filter_modal_state.show_add_hashtag_input => {
+                            // Unfollow selected hashtag (only if not on "Add Hashtag" option)
+                            let selected_idx = app.posts_state.filter_modal_state.selected_index;
+                            if selected_idx < app.posts_state.filter_modal_state.hashtag_list.len() {
+                                if let Some(hashtag) = app.posts_state.filter_modal_state.hashtag_list.get(selected_idx).cloned() {
+                                    app.unfollow_hashtag(&hashtag).await?;
+                                }
+                            }
+                        }
+                        KeyCode::Char('p') | KeyCode::Char('P') if app.current_screen == app::Screen::Main && app.current_tab == app::Tab::Posts && !app.composer_state.is_open() && !app.posts_state.show_filter_modal && app.user_profile_view.is_none() => {
+                            // Open user profile view from posts feed or post detail
+                            let author_id = if app.viewing_post_detail {
+                                app.get_post_detail_author_id()
+                            } else {
+                                app.get_selected_post_author_id()
+                            };
+                            
+                            if author_id == Some(uuid::Uuid::nil().to_string()) {
+                                app.toasts.info("Anonymous posts don't link to a profile");
+                            } else if let Some(user_id) = author_id {
+                                app.load_user_profile_view(user_id).await?;
+                            }
+                        }
+                        KeyCode::Char('f') | KeyCode::Char('F') if app.user_profile_view.is_some() => {
+                            // Toggle follow/unfollow in profile view
+                            if let Some(profile) = &app.user_profile_view {
+                                let user_id = profile.user_id.clone();
+                                let is_following = matches!(
+                                    profile.relationship,
+                                    app::RelationshipStatus::Following | app::RelationshipStatus::MutualFriends
+                                );
+                                
+                                if is_following {
+                                    app.unfollow_user_in_profile_view(user_id).await?;
+                                } else if !matches!(profile.relationship, app::RelationshipStatus::Self_) {
+                                    app.follow_user_in_profile_view(user_id).await?;
+                                }
+                            }
+                        }
+                        KeyCode::Char('m') | KeyCode::Char('M') if app.user_profile_view.is_some() => {
+                            // Open DM only if mutual friends
+                            if let Some(profile) = &app.user_profile_view {
+                                if matches!(profile.relationship, app::RelationshipStatus::MutualFriends) {
+                                    let username = profile.username.clone();
+                                    let user_id_str = profile.user_id.clone();
+                                    app.close_user_profile_view();
+                                    
+                                    // Switch to DMs tab
+                                    app.current_tab = app::Tab::DMs;
+                                    
+                                    // Try to open existing conversation or create new one
+                                    app.open_or_create_dm_conversation(username, user_id_str).await?;
+                                }
+                            }
+                        }
+                        KeyCode::Char('L') if app.current_screen == app::Screen::Main && app.settings_state.pinned_hashtags_input.is_none() && app.channels_state.new_channel_input.is_none() && app.dms_state.reminder_input.is_none() => {
+                            // Logout (Shift+L)
+                            app.logout().await?;
+                        }
+                        _ => {
+                            app.handle_key_event(key)?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Where the feed was left is where it opens next time
+    app.remember_feed_position();
+
+    Ok(())
+}
@@ -0,0 +1,331 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::session::{SessionCipher, SessionStore};
+
+/// A named server the TUI can connect to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerProfile {
+    pub name: String,
+    pub url: String,
+}
+
+/// Contents of `~/.fido/servers.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ServerProfiles {
+    active: Option<String>,
+    profiles: Vec<ServerProfile>,
+    /// Encrypt every stored session with a key from the OS keychain
+    #[serde(default)]
+    encrypt_sessions: bool,
+}
+
+/// Stores named server profiles, each with its own session file.
+///
+/// Profile sessions live under `~/.fido/profiles/<name>/session` so that
+/// `SessionStore`'s stale-file cleanup in one profile never touches another.
+/// Without a profile, the TUI keeps using the default `~/.fido/session`.
+///
+/// Every account logged into on a server also keeps its token in
+/// `~/.fido/accounts/<profile or "default">/<username>/session`, which is
+/// what lets the auth screen switch accounts without logging in again.
+pub struct ServerConfigManager {
+    config_dir: PathBuf,
+    profiles: ServerProfiles,
+}
+
+impl ServerConfigManager {
+    /// Load profiles from `~/.fido/servers.json`
+    pub fn new() -> Result<Self> {
+        let home_dir = dirs::home_dir().context("Could not determine home directory")?;
+        Ok(Self::with_dir(home_dir.join(".fido")))
+    }
+
+    /// Load profiles from a specific config directory. A missing or
+    /// unreadable servers file just means there are no profiles yet.
+    pub fn with_dir(config_dir: PathBuf) -> Self {
+        let path = config_dir.join("servers.json");
+        let profiles = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                log::warn!("Ignoring malformed {}: {}", path.display(), e);
+                ServerProfiles::default()
+            }),
+            Err(_) => ServerProfiles::default(),
+        };
+
+        Self {
+            config_dir,
+            profiles,
+        }
+    }
+
+    /// Write profiles back to disk
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(&self.config_dir).context("Failed to create .fido directory")?;
+        let json = serde_json::to_string_pretty(&self.profiles)
+            .context("Failed to serialize server profiles")?;
+        fs::write(self.config_dir.join("servers.json"), json)
+            .context("Failed to write servers file")?;
+        Ok(())
+    }
+
+    /// All profiles, in the order they were added
+    pub fn profiles(&self) -> &[ServerProfile] {
+        &self.profiles.profiles
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ServerProfile> {
+        self.profiles.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// The profile used when no `--profile` or `--server` is given
+    pub fn active(&self) -> Option<&ServerProfile> {
+        self.profiles.active.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// Add a profile or point an existing one at a new URL
+    pub fn upsert(&mut self, name: &str, url: &str) -> Result<()> {
+        Self::validate_name(name)?;
+        match self.profiles.profiles.iter_mut().find(|p| p.name == name) {
+            Some(profile) => profile.url = url.to_string(),
+            None => self.profiles.profiles.push(ServerProfile {
+                name: name.to_string(),
+                url: url.to_string(),
+            }),
+        }
+        Ok(())
+    }
+
+    /// Make `name` the active profile
+    pub fn set_active(&mut self, name: &str) -> Result<()> {
+        if self.get(name).is_none() {
+            bail!("Unknown server profile '{}'", name);
+        }
+        self.profiles.active = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Session store for a profile, or the default store for `None`
+    pub fn session_store(&self, profile: Option<&str>) -> Result<SessionStore> {
+        let path = match profile {
+            Some(name) => {
+                Self::validate_name(name)?;
+                self.config_dir.join("profiles").join(name).join("session")
+            }
+            None => self.config_dir.join("session"),
+        };
+        self.store_at(path)
+    }
+
+    /// A store for `path` in the configured format
+    fn store_at(&self, path: PathBuf) -> Result<SessionStore> {
+        let store = SessionStore::at(path);
+        if self.profiles.encrypt_sessions {
+            Ok(store.encrypted(SessionCipher::from_keychain()?))
+        } else {
+            Ok(store)
+        }
+    }
+
+    pub fn encrypt_sessions(&self) -> bool {
+        self.profiles.encrypt_sessions
+    }
+
+    /// Turn session encryption on or off and rewrite every stored session
+    /// in the new format. Fails without changing anything if the OS
+    /// keychain can't be reached.
+    pub fn set_encrypt_sessions(&mut self, enabled: bool) -> Result<()> {
+        self.set_encrypt_sessions_with(enabled, SessionCipher::from_keychain)
+    }
+
+    fn set_encrypt_sessions_with(
+        &mut self,
+        enabled: bool,
+        cipher: impl FnOnce() -> Result<SessionCipher>,
+    ) -> Result<()> {
+        let cipher = cipher()?;
+        self.profiles.encrypt_sessions = enabled;
+        self.save()?;
+
+        for path in self.session_paths() {
+            // A store with the cipher reads both formats
+            let reader = SessionStore::at(path.clone()).encrypted(cipher.clone());
+            let writer = if enabled {
+                reader.clone()
+            } else {
+                SessionStore::at(path)
+            };
+            let migrated = reader
+                .load()
+                .and_then(|token| token.map_or(Ok(()), |token| writer.save(&token)));
+            if let Err(e) = migrated {
+                log::warn!("Failed to migrate {}: {}", writer.path().display(), e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Every session file on disk: default, per-profile and saved accounts
+    fn session_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.config_dir.join("session")];
+        paths.extend(
+            self.profiles
+                .profiles
+                .iter()
+                .map(|p| self.config_dir.join("profiles").join(&p.name).join("session")),
+        );
+        if let Ok(profiles) = fs::read_dir(self.config_dir.join("accounts")) {
+            for profile in profiles.filter_map(|entry| entry.ok()) {
+                if let Ok(accounts) = fs::read_dir(profile.path()) {
+                    paths.extend(
+                        accounts
+                            .filter_map(|entry| entry.ok())
+                            .map(|entry| entry.path().join("session")),
+                    );
+                }
+            }
+        }
+        paths.retain(|path| path.exists());
+        paths
+    }
+
+    /// Directory holding the saved accounts for a profile
+    fn accounts_dir(&self, profile: Option<&str>) -> PathBuf {
+        self.config_dir
+            .join("accounts")
+            .join(profile.unwrap_or("default"))
+    }
+
+    /// Session store for one saved account on a profile
+    pub fn account_store(&self, profile: Option<&str>, username: &str) -> Result<SessionStore> {
+        if let Some(name) = profile {
+            Self::validate_name(name)?;
+        }
+        Self::validate_name(username)?;
+        self.store_at(self.accounts_dir(profile).join(username).join("session"))
+    }
+
+    /// Usernames with a saved session on a profile, sorted
+    pub fn saved_accounts(&self, profile: Option<&str>) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.accounts_dir(profile)) else {
+            return Vec::new();
+        };
+        let mut accounts: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join("session").exists())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        accounts.sort();
+        accounts
+    }
+
+    /// Profile and account names become directory names, so keep them simple
+    fn validate_name(name: &str) -> Result<()> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            bail!(
+                "Invalid profile name '{}': use letters, digits, '-' or '_'",
+                name
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_profiles_round_trip_with_active() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+        manager.upsert("work", "https://fido.work.example").unwrap();
+        manager.upsert("public", "https://fido.example").unwrap();
+        manager.upsert("work", "https://fido.corp.example").unwrap();
+        manager.set_active("public").unwrap();
+        assert!(manager.set_active("missing").is_err());
+        manager.save().unwrap();
+
+        let reloaded = ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+        assert_eq!(reloaded.profiles().len(), 2);
+        assert_eq!(reloaded.get("work").unwrap().url, "https://fido.corp.example");
+        assert_eq!(reloaded.active().unwrap().name, "public");
+    }
+
+    #[test]
+    fn test_profile_sessions_are_separate() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+
+        let work = manager.session_store(Some("work")).unwrap();
+        let public = manager.session_store(Some("public")).unwrap();
+        work.save("work-token-123").unwrap();
+        public.save("public-token-456").unwrap();
+
+        assert_eq!(work.load().unwrap(), Some("work-token-123".to_string()));
+        assert_eq!(public.load().unwrap(), Some("public-token-456".to_string()));
+        assert!(manager.session_store(Some("../escape")).is_err());
+    }
+
+    #[test]
+    fn test_saved_accounts_are_listed_per_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+
+        manager.account_store(None, "bob").unwrap().save("bob-token-123").unwrap();
+        manager.account_store(None, "alice").unwrap().save("alice-token-123").unwrap();
+        manager
+            .account_store(Some("work"), "carol")
+            .unwrap()
+            .save("carol-token-123")
+            .unwrap();
+
+        assert_eq!(manager.saved_accounts(None), vec!["alice", "bob"]);
+        assert_eq!(manager.saved_accounts(Some("work")), vec!["carol"]);
+
+        // Logging out of one account leaves the others alone
+        manager.account_store(None, "bob").unwrap().delete().unwrap();
+        assert_eq!(manager.saved_accounts(None), vec!["alice"]);
+        assert_eq!(
+            manager.account_store(None, "alice").unwrap().load().unwrap(),
+            Some("alice-token-123".to_string())
+        );
+    }
+
+    #[test]
+    fn test_toggling_encryption_migrates_every_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = ServerConfigManager::with_dir(temp_dir.path().to_path_buf());
+        manager.upsert("work", "https://fido.work.example").unwrap();
+        manager.session_store(Some("work")).unwrap().save("work-token-123").unwrap();
+        manager.account_store(Some("work"), "alice").unwrap().save("alice-token-123").unwrap();
+
+        let cipher = SessionCipher::new([3; 32]);
+        manager
+            .set_encrypt_sessions_with(true, || Ok(cipher.clone()))
+            .unwrap();
+        assert!(ServerConfigManager::with_dir(temp_dir.path().to_path_buf()).encrypt_sessions());
+
+        let work_file = temp_dir.path().join("profiles/work/session");
+        let alice_file = temp_dir.path().join("accounts/work/alice/session");
+        for file in [&work_file, &alice_file] {
+            let on_disk = fs::read_to_string(file).unwrap();
+            assert!(!on_disk.contains("token-123"), "{} is still plaintext", file.display());
+        }
+        let alice = SessionStore::at(alice_file.clone()).encrypted(cipher.clone());
+        assert_eq!(alice.load().unwrap(), Some("alice-token-123".to_string()));
+
+        manager
+            .set_encrypt_sessions_with(false, || Ok(cipher.clone()))
+            .unwrap();
+        assert_eq!(fs::read_to_string(&work_file).unwrap(), "work-token-123");
+        assert_eq!(fs::read_to_string(&alice_file).unwrap(), "alice-token-123");
+    }
+}
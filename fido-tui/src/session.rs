@@ -1,31 +1,122 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Marks a session file whose token is encrypted with [`SessionCipher`]
+const ENCRYPTED_PREFIX: &str = "fido-enc-v1:";
+const KEYCHAIN_SERVICE: &str = "fido";
+const KEYCHAIN_ACCOUNT: &str = "session-encryption-key";
+const NONCE_LEN: usize = 12;
+
+/// Encrypts session tokens with ChaCha20-Poly1305 under a key kept in the
+/// OS keychain, so a copied `.fido` directory is useless on its own.
+#[derive(Clone)]
+pub struct SessionCipher {
+    key: [u8; 32],
+}
+
+impl std::fmt::Debug for SessionCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SessionCipher(..)")
+    }
+}
+
+impl SessionCipher {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    /// The key stored in the OS keychain, generated there on first use.
+    /// Read once per process so the keychain isn't asked on every load.
+    pub fn from_keychain() -> Result<Self> {
+        static KEY: OnceLock<[u8; 32]> = OnceLock::new();
+        if let Some(key) = KEY.get() {
+            return Ok(Self::new(*key));
+        }
+
+        let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+            .context("Failed to open the OS keychain")?;
+        let key = match entry.get_password() {
+            Ok(encoded) => {
+                let bytes = STANDARD
+                    .decode(encoded.trim())
+                    .context("Session key in the OS keychain is not valid base64")?;
+                <[u8; 32]>::try_from(bytes.as_slice())
+                    .map_err(|_| anyhow!("Session key in the OS keychain has the wrong length"))?
+            }
+            Err(keyring::Error::NoEntry) => {
+                let mut key = [0u8; 32];
+                key.copy_from_slice(&ChaCha20Poly1305::generate_key(&mut OsRng));
+                entry
+                    .set_password(&STANDARD.encode(key))
+                    .context("Failed to store the session key in the OS keychain")?;
+                key
+            }
+            Err(e) => {
+                return Err(e).context("Failed to read the session key from the OS keychain")
+            }
+        };
+        Ok(Self::new(*KEY.get_or_init(|| key)))
+    }
+
+    fn encrypt(&self, token: &str) -> Result<String> {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&self.key));
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, token.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt session token"))?;
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(payload)))
+    }
+
+    fn decrypt(&self, encoded: &str) -> Result<String> {
+        let payload = STANDARD
+            .decode(encoded)
+            .context("Encrypted session is not valid base64")?;
+        if payload.len() <= NONCE_LEN {
+            bail!("Encrypted session is truncated");
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&self.key));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Session was encrypted with a different key"))?;
+        String::from_utf8(plaintext).context("Decrypted session is not UTF-8")
+    }
+}
 
 /// Manages session token storage in the user's home directory.
 /// 
 /// The session token is stored in `~/.fido/session` with 0600 permissions
-/// to ensure only the owner can read/write the file.
+/// to ensure only the owner can read/write the file. With a cipher the
+/// token is also encrypted; files in either format are read, and rewritten
+/// in the store's format, so toggling encryption migrates them on load.
 #[derive(Debug, Clone)]
 pub struct SessionStore {
     file_path: PathBuf,
+    cipher: Option<SessionCipher>,
 }
 
 impl SessionStore {
-    /// Creates a new SessionStore with the default path `~/.fido/session`.
-    /// 
-    /// # Returns
-    /// 
-    /// Returns an error if the home directory cannot be determined.
-    pub fn new() -> Result<Self> {
-        let home_dir = dirs::home_dir()
-            .context("Failed to determine home directory")?;
-        
-        let fido_dir = home_dir.join(".fido");
-        let file_path = fido_dir.join("session");
-        
-        Ok(Self { file_path })
+    /// Creates a SessionStore backed by a specific file, e.g. a server
+    /// profile's session.
+    pub fn at(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            cipher: None,
+        }
+    }
+
+    /// Encrypt tokens written by this store
+    pub fn encrypted(mut self, cipher: SessionCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
     }
 
     /// Loads the session token from the file.
@@ -42,9 +133,28 @@ impl SessionStore {
 
         let content = fs::read_to_string(&self.file_path)
             .context("Failed to read session file")?;
+        let content = content.trim();
+
+        let was_encrypted = content.starts_with(ENCRYPTED_PREFIX);
+        let decrypted = match content.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(encoded) => {
+                let cipher = match &self.cipher {
+                    Some(cipher) => cipher.clone(),
+                    None => SessionCipher::from_keychain()?,
+                };
+                match cipher.decrypt(encoded) {
+                    Ok(token) => token,
+                    Err(e) => {
+                        log::warn!("{}, treating as no session", e);
+                        return Ok(None);
+                    }
+                }
+            }
+            None => content.to_string(),
+        };
         
         // Validate session file format
-        let token = content.trim();
+        let token = decrypted.trim();
         
         if token.is_empty() {
             log::warn!("Session file is empty, treating as no session");
@@ -64,6 +174,14 @@ impl SessionStore {
             return Ok(None);
         }
         
+        // Rewrite in this store's format so turning encryption on or off
+        // migrates existing sessions
+        if was_encrypted != self.cipher.is_some() {
+            if let Err(e) = self.save(token) {
+                log::warn!("Failed to migrate session file {}: {}", self.file_path.display(), e);
+            }
+        }
+
         log::debug!("Successfully loaded session token from {}", self.file_path.display());
         Ok(Some(token.to_string()))
     }
@@ -96,7 +214,11 @@ impl SessionStore {
         let mut file = fs::File::create(&temp_path)
             .context("Failed to create temporary session file")?;
         
-        file.write_all(token.as_bytes())
+        let contents = match &self.cipher {
+            Some(cipher) => cipher.encrypt(token)?,
+            None => token.to_string(),
+        };
+        file.write_all(contents.as_bytes())
             .context("Failed to write session token")?;
         
         file.sync_all()
@@ -190,7 +312,7 @@ mod tests {
 
     fn create_test_store(temp_dir: &TempDir) -> SessionStore {
         let file_path = temp_dir.path().join("session");
-        SessionStore { file_path }
+        SessionStore::at(file_path)
     }
 
     #[test]
@@ -325,4 +447,23 @@ mod tests {
         let loaded = store.load().unwrap();
         assert_eq!(loaded, None);
     }
+
+    #[test]
+    fn test_encrypted_store_hides_token_and_migrates_plaintext() {
+        let temp_dir = TempDir::new().unwrap();
+        let plain = create_test_store(&temp_dir);
+        let encrypted = create_test_store(&temp_dir).encrypted(SessionCipher::new([7; 32]));
+
+        // An existing plaintext session is read and rewritten encrypted
+        plain.save("test-token-12345").unwrap();
+        assert_eq!(encrypted.load().unwrap(), Some("test-token-12345".to_string()));
+        let on_disk = fs::read_to_string(&encrypted.file_path).unwrap();
+        assert!(on_disk.starts_with(ENCRYPTED_PREFIX));
+        assert!(!on_disk.contains("test-token-12345"));
+        assert_eq!(encrypted.load().unwrap(), Some("test-token-12345".to_string()));
+
+        // A different key can't read it
+        let wrong_key = create_test_store(&temp_dir).encrypted(SessionCipher::new([8; 32]));
+        assert_eq!(wrong_key.load().unwrap(), None);
+    }
 }
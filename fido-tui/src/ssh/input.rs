@@ -0,0 +1,212 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Decode bytes typed into an SSH client's terminal into key events.
+///
+/// Covers what the TUI binds: printable text, control keys, arrows,
+/// Home/End/PageUp/PageDown/Delete and Shift+Tab, including xterm modifier
+/// parameters. Unrecognised escape sequences are dropped rather than being
+/// replayed as stray keystrokes.
+pub fn parse_keys(bytes: &[u8]) -> Vec<KeyEvent> {
+    let chars: Vec<char> = String::from_utf8_lossy(bytes).chars().collect();
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (key, used) = parse_one(&chars[i..]);
+        keys.extend(key);
+        i += used;
+    }
+    keys
+}
+
+/// Decodes one channel's keystrokes. Channel data can end partway through a
+/// multi-byte character, as when an input method commits a phrase of CJK
+/// text, so an incomplete tail waits for the next chunk instead of
+/// decoding to replacement characters.
+#[derive(Default)]
+pub struct KeyDecoder {
+    pending: Vec<u8>,
+}
+
+impl KeyDecoder {
+    pub fn decode(&mut self, bytes: &[u8]) -> Vec<KeyEvent> {
+        self.pending.extend_from_slice(bytes);
+        let complete = match std::str::from_utf8(&self.pending) {
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            // Valid, or broken beyond repair and decoded lossily
+            _ => self.pending.len(),
+        };
+        let rest = self.pending.split_off(complete);
+        parse_keys(&std::mem::replace(&mut self.pending, rest))
+    }
+}
+
+fn parse_one(input: &[char]) -> (Option<KeyEvent>, usize) {
+    let key = match input[0] {
+        '\x1b' => return parse_escape(input),
+        '\r' | '\n' => KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        '\t' => KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE),
+        '\x7f' | '\x08' => KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
+        '\0' => KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL),
+        c @ '\x01'..='\x1a' => KeyEvent::new(
+            KeyCode::Char((c as u8 - 1 + b'a') as char),
+            KeyModifiers::CONTROL,
+        ),
+        // Match crossterm, which reports capitals with SHIFT held
+        c if c.is_uppercase() => KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT),
+        c => KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+    };
+    (Some(key), 1)
+}
+
+fn parse_escape(input: &[char]) -> (Option<KeyEvent>, usize) {
+    let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+    match input.get(1) {
+        None | Some('\x1b') => (Some(esc), 1),
+        Some('[') => parse_csi(input),
+        Some('O') => match input.get(2) {
+            Some(&c) => (cursor_key(c).map(|code| KeyEvent::new(code, KeyModifiers::NONE)), 3),
+            None => (Some(esc), 1),
+        },
+        // ESC followed by a key is how terminals send Alt+key
+        Some(_) => {
+            let (key, used) = parse_one(&input[1..]);
+            let key = key.map(|mut key| {
+                key.modifiers |= KeyModifiers::ALT;
+                key
+            });
+            (key, used + 1)
+        }
+    }
+}
+
+/// `ESC [ <params> <final>`
+fn parse_csi(input: &[char]) -> (Option<KeyEvent>, usize) {
+    let Some(end) = input
+        .iter()
+        .skip(2)
+        .position(|c| ('\x40'..='\x7e').contains(c))
+        .map(|offset| offset + 2)
+    else {
+        // Incomplete sequence; drop it
+        return (None, input.len());
+    };
+
+    let params: String = input[2..end].iter().collect();
+    let mut params = params.split(';');
+    let number = params.next().unwrap_or("");
+    let modifiers = params
+        .next()
+        .and_then(|m| m.parse::<u8>().ok())
+        .map(decode_modifiers)
+        .unwrap_or(KeyModifiers::NONE);
+
+    let code = match input[end] {
+        'Z' => Some(KeyCode::BackTab),
+        '~' => match number {
+            "1" | "7" => Some(KeyCode::Home),
+            "2" => Some(KeyCode::Insert),
+            "3" => Some(KeyCode::Delete),
+            "4" | "8" => Some(KeyCode::End),
+            "5" => Some(KeyCode::PageUp),
+            "6" => Some(KeyCode::PageDown),
+            _ => None,
+        },
+        c => cursor_key(c),
+    };
+    let modifiers = if code == Some(KeyCode::BackTab) {
+        KeyModifiers::SHIFT
+    } else {
+        modifiers
+    };
+    (code.map(|code| KeyEvent::new(code, modifiers)), end + 1)
+}
+
+fn cursor_key(c: char) -> Option<KeyCode> {
+    match c {
+        'A' => Some(KeyCode::Up),
+        'B' => Some(KeyCode::Down),
+        'C' => Some(KeyCode::Right),
+        'D' => Some(KeyCode::Left),
+        'H' => Some(KeyCode::Home),
+        'F' => Some(KeyCode::End),
+        _ => None,
+    }
+}
+
+/// xterm encodes modifiers as `1 + bits` (Shift = 1, Alt = 2, Ctrl = 4)
+fn decode_modifiers(param: u8) -> KeyModifiers {
+    let bits = param.saturating_sub(1);
+    let mut modifiers = KeyModifiers::NONE;
+    if bits & 1 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if bits & 2 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if bits & 4 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(bytes: &[u8]) -> Vec<(KeyCode, KeyModifiers)> {
+        parse_keys(bytes)
+            .into_iter()
+            .map(|key| (key.code, key.modifiers))
+            .collect()
+    }
+
+    #[test]
+    fn test_parses_text_and_control_keys() {
+        assert_eq!(
+            codes(b"jK\r\t\x7f\x03"),
+            vec![
+                (KeyCode::Char('j'), KeyModifiers::NONE),
+                (KeyCode::Char('K'), KeyModifiers::SHIFT),
+                (KeyCode::Enter, KeyModifiers::NONE),
+                (KeyCode::Tab, KeyModifiers::NONE),
+                (KeyCode::Backspace, KeyModifiers::NONE),
+                (KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ]
+        );
+        assert_eq!(
+            codes("é🐕".as_bytes()),
+            vec![
+                (KeyCode::Char('é'), KeyModifiers::NONE),
+                (KeyCode::Char('🐕'), KeyModifiers::NONE),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parses_escape_sequences() {
+        assert_eq!(
+            codes(b"\x1b[A\x1bOB\x1b[Z\x1b[3~\x1b[1;5C\x1b"),
+            vec![
+                (KeyCode::Up, KeyModifiers::NONE),
+                (KeyCode::Down, KeyModifiers::NONE),
+                (KeyCode::BackTab, KeyModifiers::SHIFT),
+                (KeyCode::Delete, KeyModifiers::NONE),
+                (KeyCode::Right, KeyModifiers::CONTROL),
+                (KeyCode::Esc, KeyModifiers::NONE),
+            ]
+        );
+        // Unknown sequences vanish instead of typing "[99x"
+        assert_eq!(codes(b"\x1b[99xq"), vec![(KeyCode::Char('q'), KeyModifiers::NONE)]);
+    }
+
+    #[test]
+    fn test_characters_split_across_chunks_are_joined() {
+        let bytes = "日本".as_bytes();
+        let mut decoder = KeyDecoder::default();
+        assert!(decoder.decode(&bytes[..2]).is_empty());
+        let keys: Vec<KeyCode> = decoder.decode(&bytes[2..4]).into_iter().map(|k| k.code).collect();
+        assert_eq!(keys, vec![KeyCode::Char('日')]);
+        let keys: Vec<KeyCode> = decoder.decode(&bytes[4..]).into_iter().map(|k| k.code).collect();
+        assert_eq!(keys, vec![KeyCode::Char('本')]);
+    }
+}
@@ -0,0 +1,364 @@
+//! Serve the TUI over SSH, so `ssh -p 2222 fido.example.com` gets the client
+//! without installing anything.
+//!
+//! Each SSH channel gets its own [`App`] driven by the same event loop as the
+//! local terminal. Keystrokes arrive as channel data and are decoded by
+//! [`input::KeyDecoder`]; frames are rendered with the crossterm backend into
+//! a writer that forwards to the channel.
+//!
+//! Visitors share the host, so nothing they do may touch its `~/.fido`: every
+//! session keeps its profile and session files in a scratch directory that is
+//! removed on disconnect, saved accounts are off, and GitHub login is hidden
+//! because it would try to open a browser on the host.
+
+mod input;
+
+use anyhow::{Context, Result};
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::Event;
+use crossterm::execute;
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use russh::server::{self, Auth, Handle, Msg, Server as _, Session};
+use russh::{Channel, ChannelId, CryptoVec, Pty};
+use russh_keys::key::{KeyPair, PublicKey};
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::app::App;
+use crate::runner::{self, EventSource};
+use crate::server_config::ServerConfigManager;
+
+/// Profile name each SSH session uses inside its scratch directory
+const SSH_PROFILE: &str = "ssh";
+
+/// Size used until the client sends a pty request
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+pub struct SshOptions {
+    pub listen: String,
+    pub host_key: PathBuf,
+    pub server_url: String,
+}
+
+/// Accept SSH connections until the process is stopped
+pub async fn serve(options: SshOptions) -> Result<()> {
+    let key = load_or_generate_host_key(&options.host_key)?;
+    let config = server::Config {
+        inactivity_timeout: Some(Duration::from_secs(3600)),
+        auth_rejection_time: Duration::from_secs(1),
+        auth_rejection_time_initial: Some(Duration::from_secs(0)),
+        keys: vec![key],
+        ..Default::default()
+    };
+
+    log::info!(
+        "Serving fido over SSH on {} (API server {})",
+        options.listen,
+        options.server_url
+    );
+    println!("fido SSH server listening on {}", options.listen);
+
+    let mut server = SshServer {
+        server_url: options.server_url,
+    };
+    server
+        .run_on_address(Arc::new(config), options.listen.as_str())
+        .await
+        .with_context(|| format!("SSH server on {} stopped", options.listen))?;
+    Ok(())
+}
+
+fn load_or_generate_host_key(path: &Path) -> Result<KeyPair> {
+    if path.exists() {
+        return russh_keys::load_secret_key(path, None)
+            .with_context(|| format!("Failed to load SSH host key {}", path.display()));
+    }
+
+    let key = KeyPair::generate_ed25519();
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create SSH host key {}", path.display()))?;
+    russh_keys::encode_pkcs8_pem(&key, file).context("Failed to write SSH host key")?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    log::info!("Generated SSH host key at {}", path.display());
+    Ok(key)
+}
+
+struct SshServer {
+    server_url: String,
+}
+
+impl server::Server for SshServer {
+    type Handler = Connection;
+
+    fn new_client(&mut self, peer_addr: Option<SocketAddr>) -> Connection {
+        log::info!("SSH connection from {:?}", peer_addr);
+        Connection {
+            server_url: self.server_url.clone(),
+            size: DEFAULT_SIZE,
+            input: None,
+            keys: input::KeyDecoder::default(),
+        }
+    }
+}
+
+/// One SSH connection. Only a single shell per connection is served.
+struct Connection {
+    server_url: String,
+    size: (u16, u16),
+    /// Feeds the running session's event loop; dropped on disconnect
+    input: Option<mpsc::UnboundedSender<Event>>,
+    keys: input::KeyDecoder,
+}
+
+impl Connection {
+    fn send(&self, event: Event) {
+        if let Some(input) = &self.input {
+            let _ = input.send(event);
+        }
+    }
+}
+
+fn clamp_size(cols: u32, rows: u32) -> (u16, u16) {
+    (
+        cols.clamp(1, u16::MAX as u32) as u16,
+        rows.clamp(1, u16::MAX as u32) as u16,
+    )
+}
+
+#[async_trait::async_trait]
+impl server::Handler for Connection {
+    type Error = anyhow::Error;
+
+    // Anyone may connect; logging in happens inside the TUI
+    async fn auth_none(&mut self, _user: &str) -> Result<Auth, Self::Error> {
+        Ok(Auth::Accept)
+    }
+
+    async fn auth_publickey(
+        &mut self,
+        _user: &str,
+        _public_key: &PublicKey,
+    ) -> Result<Auth, Self::Error> {
+        Ok(Auth::Accept)
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        _channel: Channel<Msg>,
+        _session: &mut Session,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.input.is_none())
+    }
+
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        _term: &str,
+        col_width: u32,
+        row_height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _modes: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.size = clamp_size(col_width, row_height);
+        session.channel_success(channel);
+        Ok(())
+    }
+
+    async fn shell_request(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let (input_tx, input_rx) = mpsc::unbounded_channel();
+        self.input = Some(input_tx);
+        session.channel_success(channel);
+
+        let handle = session.handle();
+        let server_url = self.server_url.clone();
+        let size = self.size;
+        tokio::spawn(async move {
+            if let Err(e) = run_session(handle.clone(), channel, server_url, size, input_rx).await
+            {
+                log::warn!("SSH session ended with an error: {}", e);
+            }
+            let _ = handle.close(channel).await;
+        });
+        Ok(())
+    }
+
+    async fn data(
+        &mut self,
+        _channel: ChannelId,
+        data: &[u8],
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        for key in self.keys.decode(data) {
+            self.send(Event::Key(key));
+        }
+        Ok(())
+    }
+
+    async fn window_change_request(
+        &mut self,
+        _channel: ChannelId,
+        col_width: u32,
+        row_height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let (cols, rows) = clamp_size(col_width, row_height);
+        self.size = (cols, rows);
+        self.send(Event::Resize(cols, rows));
+        Ok(())
+    }
+
+    async fn channel_eof(
+        &mut self,
+        _channel: ChannelId,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.input = None;
+        Ok(())
+    }
+
+    async fn channel_close(
+        &mut self,
+        _channel: ChannelId,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.input = None;
+        Ok(())
+    }
+}
+
+/// Events decoded from the channel by [`Connection`]
+struct ChannelEvents {
+    input: mpsc::UnboundedReceiver<Event>,
+}
+
+#[async_trait::async_trait]
+impl EventSource for ChannelEvents {
+    async fn next_event(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        match tokio::time::timeout(timeout, self.input.recv()).await {
+            Ok(Some(event)) => Ok(Some(event)),
+            Ok(None) => anyhow::bail!("SSH client disconnected"),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// Buffers terminal output and hands it to the channel on flush
+struct ChannelWriter {
+    buffer: Vec<u8>,
+    output: mpsc::UnboundedSender<Vec<u8>>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.output
+            .send(std::mem::take(&mut self.buffer))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "SSH channel closed"))
+    }
+}
+
+/// Scratch `.fido` directory for one session, removed when dropped
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("fido-ssh-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).context("Failed to create SSH session directory")?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// An `App` for one visitor, isolated from the host's config
+fn session_app(server_url: &str, scratch: &ScratchDir) -> Result<App> {
+    let mut app = App::with_server_url(server_url.to_string());
+    app.server_config = ServerConfigManager::with_dir(scratch.0.clone());
+    app.server_config.upsert(SSH_PROFILE, server_url)?;
+    let profile = app
+        .server_config
+        .get(SSH_PROFILE)
+        .cloned()
+        .context("SSH profile missing")?;
+    app.use_profile(&profile);
+    app.auth_state.show_github_option = false;
+    app.auth_state.remember_accounts = false;
+    // Speech would come out of the server's speakers
+    app.accessibility.speech_available = false;
+    Ok(app)
+}
+
+async fn run_session(
+    handle: Handle,
+    channel: ChannelId,
+    server_url: String,
+    (cols, rows): (u16, u16),
+    input: mpsc::UnboundedReceiver<Event>,
+) -> Result<()> {
+    // Frames go out in order on their own task so rendering never waits on
+    // the network
+    let (output_tx, mut output_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+    let forward = tokio::spawn(async move {
+        while let Some(bytes) = output_rx.recv().await {
+            if handle.data(channel, CryptoVec::from_slice(&bytes)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let writer = ChannelWriter {
+        buffer: Vec::new(),
+        output: output_tx,
+    };
+    let mut tui = Terminal::with_options(
+        CrosstermBackend::new(writer),
+        TerminalOptions {
+            viewport: Viewport::Fixed(Rect::new(0, 0, cols, rows)),
+        },
+    )?;
+    execute!(tui.backend_mut(), EnterAlternateScreen, Hide)?;
+    tui.clear()?;
+
+    let scratch = ScratchDir::new()?;
+    let mut app = session_app(&server_url, &scratch)?;
+    let auth_flow = crate::auth::AuthFlow::new(app.api_client.clone(), app.session_store()?);
+    app.spawn_load_test_users();
+    app.spawn_version_check();
+
+    let result = runner::run(&mut tui, &mut app, auth_flow, &mut ChannelEvents { input }).await;
+
+    let _ = execute!(tui.backend_mut(), Show, LeaveAlternateScreen);
+    drop(tui);
+    let _ = forward.await;
+    result
+}
@@ -0,0 +1,116 @@
+//! User status lines: parsing what's typed in the status composer and
+//! formatting a status for display.
+//!
+//! The composer takes a single line such as `🚀 Shipping the beta for 2h`:
+//! a leading emoji becomes the status emoji and a trailing `for <n>m|h|d|w`
+//! sets the expiry.
+
+use chrono::{DateTime, Local, Utc};
+use fido_types::{SetStatusRequest, UserStatus};
+
+/// Turn a composer line (shortcodes already expanded) into a status request
+pub fn parse_status_input(input: &str) -> SetStatusRequest {
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+
+    let emoji = match words.first() {
+        Some(first) if is_emoji_token(first) => Some(words.remove(0).to_string()),
+        _ => None,
+    };
+
+    let mut expires_in_minutes = None;
+    if words.len() >= 3 && words[words.len() - 2].eq_ignore_ascii_case("for") {
+        if let Some(minutes) = parse_duration(words[words.len() - 1]) {
+            expires_in_minutes = Some(minutes);
+            words.truncate(words.len() - 2);
+        }
+    }
+
+    SetStatusRequest {
+        text: words.join(" "),
+        emoji,
+        expires_in_minutes,
+    }
+}
+
+/// A word made only of symbols outside ASCII, i.e. an emoji
+fn is_emoji_token(word: &str) -> bool {
+    word.chars().all(|c| !c.is_ascii() && !c.is_alphanumeric())
+}
+
+/// `30m`, `2h`, `1d` or `1w` in minutes
+pub(crate) fn parse_duration(word: &str) -> Option<u32> {
+    let unit = word.chars().last()?;
+    let amount: u32 = word[..word.len() - unit.len_utf8()].parse().ok()?;
+    let minutes = match unit.to_ascii_lowercase() {
+        'm' => amount,
+        'h' => amount.checked_mul(60)?,
+        'd' => amount.checked_mul(60 * 24)?,
+        'w' => amount.checked_mul(60 * 24 * 7)?,
+        _ => return None,
+    };
+    (minutes > 0).then_some(minutes)
+}
+
+/// The status as shown under a username: `🚀 Shipping the beta · until 17:30`
+pub fn format_status(status: &UserStatus) -> String {
+    let mut line = match &status.emoji {
+        Some(emoji) => format!("{} {}", emoji, status.text),
+        None => status.text.clone(),
+    };
+    if let Some(expires_at) = status.expires_at {
+        line.push_str(&format!(" · until {}", format_expiry(expires_at)));
+    }
+    line
+}
+
+/// Local time for expiries today, otherwise the date as well
+pub(crate) fn format_expiry(expires_at: DateTime<Utc>) -> String {
+    let local = expires_at.with_timezone(&Local);
+    if local.date_naive() == Local::now().date_naive() {
+        local.format("%H:%M").to_string()
+    } else {
+        local.format("%b %d %H:%M").to_string()
+    }
+}
+
+/// Composer text for editing an existing status (the expiry isn't kept)
+pub fn status_input(status: &UserStatus) -> String {
+    match &status.emoji {
+        Some(emoji) => format!("{} {}", emoji, status.text),
+        None => status.text.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_input_splits_emoji_and_expiry() {
+        let request = parse_status_input("🚀 Shipping the beta for 2h");
+        assert_eq!(request.emoji.as_deref(), Some("🚀"));
+        assert_eq!(request.text, "Shipping the beta");
+        assert_eq!(request.expires_in_minutes, Some(120));
+
+        let request = parse_status_input("Waiting for review");
+        assert_eq!(request.emoji, None);
+        assert_eq!(request.text, "Waiting for review");
+        assert_eq!(request.expires_in_minutes, None);
+
+        assert_eq!(parse_status_input("Out for 1w").expires_in_minutes, Some(7 * 24 * 60));
+        assert_eq!(parse_status_input("Out for 0h").expires_in_minutes, None);
+    }
+
+    #[test]
+    fn test_status_round_trips_through_composer_text() {
+        let status = UserStatus {
+            text: "Hacking on fido".to_string(),
+            emoji: Some("🛠".to_string()),
+            expires_at: None,
+        };
+        assert_eq!(format_status(&status), "🛠 Hacking on fido");
+        let request = parse_status_input(&status_input(&status));
+        assert_eq!(request.emoji, status.emoji);
+        assert_eq!(request.text, status.text);
+    }
+}
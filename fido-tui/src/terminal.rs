@@ -1,5 +1,6 @@
 use anyhow::Result;
 use crossterm::{
+    event::{DisableBracketedPaste, EnableBracketedPaste},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -9,10 +10,12 @@ use std::io::{self, Stdout, Write};
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
 /// Initialize the terminal
-/// Note: Mouse capture is intentionally NOT enabled for keyboard-only navigation
+/// Note: Mouse capture is intentionally NOT enabled for keyboard-only navigation.
+/// Bracketed paste is, so a pasted block arrives as one event instead of a
+/// burst of key presses.
 pub fn init() -> Result<Tui> {
     enable_raw_mode()?;
-    execute!(io::stdout(), EnterAlternateScreen)?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableBracketedPaste)?;
     
     // Explicitly disable mouse tracking with ANSI escape sequences
     // This prevents the terminal from sending mouse events entirely
@@ -60,7 +63,7 @@ fn disable_windows_mouse_input() -> Result<()> {
 /// Restore the terminal to its original state
 pub fn restore() -> Result<()> {
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), DisableBracketedPaste, LeaveAlternateScreen)?;
     
     // Windows-specific: Re-enable mouse input to restore original console state
     #[cfg(windows)]
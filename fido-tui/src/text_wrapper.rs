@@ -1,81 +1,300 @@
 /// Text wrapping utilities for terminal UI
+///
+/// Widths here are terminal columns, not characters: CJK text and most
+/// emoji take two columns, and a grapheme (an emoji with a skin tone, a
+/// letter with combining accents) is never split.
 use tui_textarea::TextArea;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 /// Configuration for text wrapping behavior
 pub struct WrapConfig {
-    /// Maximum width before wrapping (in characters)
+    /// Maximum width before wrapping (in terminal columns)
     pub wrap_width: usize,
 }
 
 impl WrapConfig {
-    /// Standard width for composer modals (70% of typical terminal)
-    pub const COMPOSER: Self = Self { wrap_width: 100 };
-    
     /// Standard width for DM panels (70% of typical terminal)
     pub const DM_PANEL: Self = Self { wrap_width: 110 };
 }
 
-/// Wrap text in a TextArea if the current line exceeds the configured width
+/// Wrap text in a TextArea if the current line is wider than the configured width
 /// 
 /// This function:
 /// - Finds the current cursor position
-/// - Checks if the current line exceeds wrap_width
-/// - Splits at the last space before wrap_width
+/// - Checks if the current line takes more than wrap_width columns
+/// - Splits at the last space that fits
 /// - Rebuilds the textarea with wrapped content
 /// - Repositions the cursor appropriately
 pub fn wrap_textarea_if_needed(textarea: &mut TextArea<'static>, config: WrapConfig) {
     let (row, col) = textarea.cursor();
-    let lines: Vec<String> = textarea.lines().to_vec();
+    let mut lines: Vec<String> = textarea.lines().to_vec();
     
-    if row >= lines.len() {
+    let Some(current_line) = lines.get(row) else {
         return;
-    }
-    
-    let current_line = &lines[row];
-    let char_count = current_line.chars().count();
-    
-    if char_count <= config.wrap_width {
+    };
+    let Some((end, start)) = break_point(current_line, config.wrap_width) else {
         return;
-    }
-    
-    // Collect characters for safe indexing
-    let chars: Vec<char> = current_line.chars().collect();
-    
-    // Find the last space before wrap_width
-    let mut wrap_point = config.wrap_width;
-    for i in (0..config.wrap_width.min(chars.len())).rev() {
-        if chars[i] == ' ' {
-            wrap_point = i;
-            break;
-        }
-    }
+    };
     
     // Split at character boundary
-    let first_part: String = chars[..wrap_point].iter().collect();
-    let second_part: String = chars[wrap_point..].iter().collect();
-    let first_part = first_part.trim_end();
-    let second_part = second_part.trim_start();
+    let chars: Vec<char> = current_line.chars().collect();
+    let first_part: String = chars[..end].iter().collect();
+    let second_part: String = chars[start..].iter().collect();
+    let second_len = chars.len() - start;
     
     // Rebuild all lines with the wrapped text
-    let mut new_lines = Vec::new();
-    for (i, line) in lines.iter().enumerate() {
-        if i == row {
-            new_lines.push(first_part.to_string());
-            new_lines.push(second_part.to_string());
-        } else {
-            new_lines.push(line.clone());
-        }
-    }
+    lines[row] = first_part;
+    lines.insert(row + 1, second_part);
     
     // Replace textarea content
-    *textarea = TextArea::from(new_lines.iter().map(|s| s.as_str()));
+    *textarea = TextArea::from(lines.iter().map(|s| s.as_str()));
     textarea.set_hard_tab_indent(true);
     
     // Move cursor to the second line at the appropriate position
-    let new_col = if col > wrap_point {
-        col - wrap_point - 1
-    } else {
-        second_part.chars().count()
-    };
+    let new_col = if col >= start { col - start } else { second_len };
     textarea.move_cursor(tui_textarea::CursorMove::Jump(row as u16 + 1, new_col as u16));
 }
+
+/// Wrap `lines` to `width` columns for drawing only, leaving the text itself
+/// alone so no newlines end up in what's submitted
+///
+/// Lines break where [`wrap_lines`] would break them, by display width and
+/// never inside a grapheme. Returns the display lines and where `cursor`
+/// lands in them; a cursor in the spaces at a break stays at the end of the
+/// line above.
+pub fn wrap_for_display(
+    lines: &[String],
+    cursor: (usize, usize),
+    width: usize,
+) -> (Vec<String>, (usize, usize)) {
+    let width = width.max(1);
+    let mut wrapped = Vec::new();
+    let mut display_cursor = (0, 0);
+
+    for (row, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut pos = 0;
+        loop {
+            let rest: String = chars[pos..].iter().collect();
+            let on_row = row == cursor.0 && cursor.1 >= pos;
+            match break_point(&rest, width) {
+                Some((end, start)) => {
+                    if on_row && cursor.1 < pos + start {
+                        display_cursor = (wrapped.len(), (cursor.1 - pos).min(end));
+                    }
+                    wrapped.push(chars[pos..pos + end].iter().collect());
+                    pos += start;
+                }
+                None => {
+                    if on_row {
+                        display_cursor = (wrapped.len(), cursor.1 - pos);
+                    }
+                    wrapped.push(rest);
+                    break;
+                }
+            }
+        }
+    }
+
+    (wrapped, display_cursor)
+}
+
+/// Insert pasted text at the cursor in one edit. With `wrap`, every line the
+/// paste left longer than its `wrap_width` is then wrapped, keeping the
+/// cursor after the pasted text; without, wrapping is left to the drawing.
+///
+/// Line breaks may arrive as `\n`, `\r\n` or a lone `\r` (what many
+/// terminals paste), and all become new lines.
+pub fn paste_into_textarea(
+    textarea: &mut TextArea<'static>,
+    text: &str,
+    wrap: Option<WrapConfig>,
+) {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    if !textarea.insert_str(&text) {
+        return;
+    }
+    let Some(config) = wrap else {
+        return;
+    };
+
+    let (lines, (row, col)) = wrap_lines(textarea.lines(), textarea.cursor(), config.wrap_width);
+    if lines.len() == textarea.lines().len() {
+        return; // Nothing needed wrapping
+    }
+    *textarea = TextArea::from(lines.iter().map(|s| s.as_str()));
+    textarea.set_hard_tab_indent(true);
+    textarea.move_cursor(tui_textarea::CursorMove::Jump(row as u16, col as u16));
+}
+
+/// Split every line longer than `width` at its last space within the width
+/// (or at the width, for a single long word), the same way typing wraps,
+/// and move the `(row, col)` cursor along with its text
+fn wrap_lines(
+    lines: &[String],
+    cursor: (usize, usize),
+    width: usize,
+) -> (Vec<String>, (usize, usize)) {
+    let mut wrapped = Vec::with_capacity(lines.len());
+    let mut new_cursor = cursor;
+
+    for (row, line) in lines.iter().enumerate() {
+        let mut rest = line.clone();
+        // Cursor column within `rest`, while the cursor is on this line
+        let mut col = (row == cursor.0).then_some(cursor.1);
+        if let Some(col) = col {
+            new_cursor = (wrapped.len(), col);
+        }
+
+        while let Some((end, start)) = break_point(&rest, width) {
+            let chars: Vec<char> = rest.chars().collect();
+            wrapped.push(chars[..end].iter().collect::<String>());
+            rest = chars[start..].iter().collect();
+
+            if let Some(c) = col {
+                if c >= start {
+                    col = Some(c - start);
+                    new_cursor = (wrapped.len(), c - start);
+                } else {
+                    new_cursor = (wrapped.len() - 1, c.min(end));
+                    col = None;
+                }
+            }
+        }
+        wrapped.push(rest);
+    }
+
+    (wrapped, new_cursor)
+}
+
+/// Where to break `line` so its first part fits in `width` columns, as char
+/// indexes (textarea columns count chars): where the first part ends and
+/// where the rest starts, skipping the spaces between. Breaks at the last
+/// space that fits, or mid-word when a word is wider than the line; None
+/// when the line already fits or can't be broken.
+fn break_point(line: &str, width: usize) -> Option<(usize, usize)> {
+    if line.width() <= width {
+        return None;
+    }
+
+    // Chars in the graphemes that fit, always at least one so wrapping
+    // makes progress
+    let mut fit = 0;
+    let mut used = 0;
+    for grapheme in line.graphemes(true) {
+        used += grapheme.width();
+        if used > width && fit > 0 {
+            break;
+        }
+        fit += grapheme.chars().count();
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    if fit >= chars.len() {
+        return None;
+    }
+    let mut end = (1..=fit).rev().find(|&i| chars[i] == ' ').unwrap_or(fit);
+    let start = end + chars[end..].iter().take_while(|&&c| c == ' ').count();
+    while end > 0 && chars[end - 1] == ' ' {
+        end -= 1;
+    }
+    Some((end, start))
+}
+
+/// The longest start of `text` that fits in `width` columns, without
+/// splitting a grapheme
+pub fn take_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+/// `text` cut to fit in `width` columns, ending with "…" when anything
+/// was cut
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    format!("{}…", take_width(text, width - 1).trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_long_lines_wrap_at_spaces_and_keep_the_cursor_with_its_text() {
+        let (wrapped, cursor) = wrap_lines(&lines(&["one two three four", "five"]), (0, 18), 9);
+        assert_eq!(wrapped, lines(&["one two", "three", "four", "five"]));
+        assert_eq!(cursor, (2, 4));
+
+        // A cursor on a later line moves down by the lines added above it
+        let (_, cursor) = wrap_lines(&lines(&["one two three four", "five"]), (1, 2), 9);
+        assert_eq!(cursor, (3, 2));
+
+        // A cursor before the break stays put
+        let (_, cursor) = wrap_lines(&lines(&["one two three four"]), (0, 2), 9);
+        assert_eq!(cursor, (0, 2));
+    }
+
+    #[test]
+    fn test_words_longer_than_the_width_are_broken() {
+        let (wrapped, cursor) = wrap_lines(&lines(&["abcdefghij"]), (0, 10), 4);
+        assert_eq!(wrapped, lines(&["abcd", "efgh", "ij"]));
+        assert_eq!(cursor, (2, 2));
+    }
+
+    #[test]
+    fn test_short_lines_are_left_alone() {
+        let text = lines(&["short", "", "lines"]);
+        let (wrapped, cursor) = wrap_lines(&text, (1, 0), 10);
+        assert_eq!(wrapped, text);
+        assert_eq!(cursor, (1, 0));
+    }
+
+    #[test]
+    fn test_wide_characters_wrap_by_columns() {
+        // Each of these takes two columns, so only three fit in six
+        let (wrapped, cursor) = wrap_lines(&lines(&["日本語のテキスト"]), (0, 8), 6);
+        assert_eq!(wrapped, lines(&["日本語", "のテキ", "スト"]));
+        assert_eq!(cursor, (2, 2));
+
+        let (wrapped, _) = wrap_lines(&lines(&["ok 🦀🦀🦀 done"]), (0, 0), 8);
+        assert_eq!(wrapped, lines(&["ok", "🦀🦀🦀", "done"]));
+        assert!(wrapped.iter().all(|l| l.width() <= 8));
+    }
+
+    #[test]
+    fn test_graphemes_are_never_split() {
+        // A thumbs up with a skin tone is two chars but one grapheme
+        let thumbs = "👍🏽";
+        let line = thumbs.repeat(4);
+        let (wrapped, _) = wrap_lines(&lines(&[line.as_str()]), (0, 0), 5);
+        assert!(wrapped.iter().all(|l| l.graphemes(true).all(|g| g == thumbs)));
+        assert_eq!(wrapped.concat(), line);
+
+        assert_eq!(take_width("e\u{301}tude", 1), "e\u{301}");
+    }
+
+    #[test]
+    fn test_truncating_counts_columns() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("漢字漢字漢字", 7), "漢字漢…");
+        assert_eq!(truncate_to_width("hello world", 7), "hello…");
+        assert_eq!(truncate_to_width("🦀🦀", 2), "…");
+        assert_eq!(take_width("🦀🦀", 3), "🦀");
+    }
+}
@@ -0,0 +1,397 @@
+//! The reply tree of the full post modal, and how it flattens into the
+//! modal's list: the thread root sits at index 0, followed by the visible
+//! replies depth first, each node's children shown only while it is
+//! expanded. Navigation, voting, deleting and rendering all map a selection
+//! index to a post through here, so they always agree on what is selected.
+
+use fido_types::Post;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Which posts in the modal have their replies shown
+pub type Expansion = HashMap<Uuid, bool>;
+
+/// A root post and its descendants among a thread's replies
+pub struct ThreadTree<'a> {
+    root: &'a Post,
+    children: HashMap<Uuid, Vec<&'a Post>>,
+    len: usize,
+}
+
+/// A reply as it appears in the flattened list
+#[derive(Debug, Clone, Copy)]
+pub struct VisibleReply<'a> {
+    pub post: &'a Post,
+    /// 0 for direct replies to the root
+    pub depth: usize,
+    pub has_children: bool,
+}
+
+impl<'a> ThreadTree<'a> {
+    /// Build the tree under `root`, ignoring replies that don't descend from it.
+    /// Siblings keep the order they have in `replies`.
+    pub fn new(root: &'a Post, replies: &'a [Post]) -> Self {
+        let mut all_children: HashMap<Uuid, Vec<&'a Post>> = HashMap::new();
+        for reply in replies.iter().filter(|reply| reply.id != root.id) {
+            if let Some(parent_id) = reply.parent_post_id {
+                all_children.entry(parent_id).or_default().push(reply);
+            }
+        }
+
+        // Keep only what is reachable from the root. Every reply has a single
+        // parent, so the walk can't revisit a post.
+        let mut children = HashMap::new();
+        let mut len = 0;
+        let mut pending = vec![root.id];
+        while let Some(id) = pending.pop() {
+            if let Some(kids) = all_children.remove(&id) {
+                len += kids.len();
+                pending.extend(kids.iter().map(|kid| kid.id));
+                children.insert(id, kids);
+            }
+        }
+
+        Self {
+            root,
+            children,
+            len,
+        }
+    }
+
+    /// Number of replies under the root, visible or not
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The replies shown below the root, in list order
+    pub fn visible(&self, expanded: &Expansion) -> Vec<VisibleReply<'a>> {
+        let mut result = Vec::new();
+        if is_expanded(expanded, self.root.id) {
+            self.push_visible(self.root.id, 0, expanded, &mut result);
+        }
+        result
+    }
+
+    fn push_visible(
+        &self,
+        parent_id: Uuid,
+        depth: usize,
+        expanded: &Expansion,
+        result: &mut Vec<VisibleReply<'a>>,
+    ) {
+        for &post in self.children.get(&parent_id).into_iter().flatten() {
+            let has_children = self.children.contains_key(&post.id);
+            result.push(VisibleReply {
+                post,
+                depth,
+                has_children,
+            });
+            if has_children && is_expanded(expanded, post.id) {
+                self.push_visible(post.id, depth + 1, expanded, result);
+            }
+        }
+    }
+
+    /// The last selectable index; 0 when only the root is shown
+    pub fn max_index(&self, expanded: &Expansion) -> usize {
+        self.visible(expanded).len()
+    }
+
+    /// The post at list index `index`, the root being at 0
+    pub fn post_at(&self, index: usize, expanded: &Expansion) -> Option<&'a Post> {
+        match index {
+            0 => Some(self.root),
+            _ => self
+                .visible(expanded)
+                .get(index - 1)
+                .map(|reply| reply.post),
+        }
+    }
+
+    /// The list index of `post_id`, if it is currently shown
+    pub fn index_of(&self, post_id: Uuid, expanded: &Expansion) -> Option<usize> {
+        if post_id == self.root.id {
+            return Some(0);
+        }
+        self.visible(expanded)
+            .iter()
+            .position(|reply| reply.post.id == post_id)
+            .map(|position| position + 1)
+    }
+
+    /// The posts that must be expanded for `post_id` to be shown, nearest
+    /// first and ending with the root; empty if it isn't in the tree
+    pub fn ancestors(&self, post_id: Uuid) -> Vec<Uuid> {
+        let parent_of: HashMap<Uuid, Uuid> = self
+            .children
+            .iter()
+            .flat_map(|(&parent_id, kids)| kids.iter().map(move |kid| (kid.id, parent_id)))
+            .collect();
+        let mut ancestors = Vec::new();
+        let mut current = post_id;
+        while let Some(&parent_id) = parent_of.get(&current) {
+            ancestors.push(parent_id);
+            current = parent_id;
+        }
+        ancestors
+    }
+
+    /// Expand everything above `post_id` and return its list index
+    pub fn reveal(&self, post_id: Uuid, expanded: &mut Expansion) -> Option<usize> {
+        for ancestor_id in self.ancestors(post_id) {
+            expanded.insert(ancestor_id, true);
+        }
+        self.index_of(post_id, expanded)
+    }
+}
+
+pub fn is_expanded(expanded: &Expansion, post_id: Uuid) -> bool {
+    expanded.get(&post_id).copied().unwrap_or(false)
+}
+
+/// Show or hide the replies to `post_id`
+pub fn toggle(expanded: &mut Expansion, post_id: Uuid) {
+    let was_expanded = is_expanded(expanded, post_id);
+    expanded.insert(post_id, !was_expanded);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fido_types::ReplyPolicy;
+    use proptest::prelude::*;
+
+    fn post(id: Uuid, parent_post_id: Option<Uuid>) -> Post {
+        Post {
+            id,
+            author_id: Uuid::new_v4(),
+            author_username: "alice".to_string(),
+            content: "reply".to_string(),
+            created_at: chrono::Utc::now(),
+            upvotes: 0,
+            downvotes: 0,
+            hashtags: Vec::new(),
+            user_vote: None,
+            parent_post_id,
+            reply_count: 0,
+            reply_to_user_id: None,
+            reply_to_username: None,
+            is_deleted: false,
+            author_badges: Vec::new(),
+            author_status: None,
+            kind: None,
+            is_anonymous: false,
+            reply_policy: ReplyPolicy::Everyone,
+            can_reply: true,
+            is_accepted: false,
+            author_karma: 0,
+        }
+    }
+
+    /// A root and replies whose parents are given as indices into the posts
+    /// before them (0 being the root), plus a chain of replies to some other
+    /// post mixed in
+    fn thread(parents: &[usize], strays: usize) -> (Post, Vec<Post>) {
+        let ids: Vec<Uuid> = (0..=parents.len()).map(|_| Uuid::new_v4()).collect();
+        let root = post(ids[0], None);
+        let mut replies: Vec<Post> = parents
+            .iter()
+            .enumerate()
+            .map(|(i, &parent)| post(ids[i + 1], Some(ids[parent])))
+            .collect();
+        let mut stray_parent = Uuid::new_v4();
+        for i in 0..strays {
+            let stray = post(Uuid::new_v4(), Some(stray_parent));
+            stray_parent = stray.id;
+            replies.insert(i * 2 % (replies.len() + 1), stray);
+        }
+        (root, replies)
+    }
+
+    fn thread_strategy() -> impl Strategy<Value = (Vec<usize>, usize)> {
+        (
+            prop::collection::vec(any::<prop::sample::Index>(), 0..40),
+            0..4usize,
+        )
+            .prop_map(|(picks, strays)| {
+                let parents = picks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pick)| pick.index(i + 1))
+                    .collect();
+                (parents, strays)
+            })
+    }
+
+    fn expansion_strategy() -> impl Strategy<Value = Vec<bool>> {
+        prop::collection::vec(any::<bool>(), 41)
+    }
+
+    fn expansion(root: &Post, replies: &[Post], flags: &[bool]) -> Expansion {
+        std::iter::once(root)
+            .chain(replies)
+            .zip(flags)
+            .map(|(post, &flag)| (post.id, flag))
+            .collect()
+    }
+
+    fn fully_expanded(root: &Post, replies: &[Post]) -> Expansion {
+        std::iter::once(root)
+            .chain(replies)
+            .map(|post| (post.id, true))
+            .collect()
+    }
+
+    #[test]
+    fn test_collapsed_root_shows_only_the_root() {
+        let (root, replies) = thread(&[0, 0, 1], 0);
+        let tree = ThreadTree::new(&root, &replies);
+
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.max_index(&Expansion::new()), 0);
+        assert_eq!(
+            tree.post_at(0, &Expansion::new()).map(|p| p.id),
+            Some(root.id)
+        );
+        assert!(tree.post_at(1, &Expansion::new()).is_none());
+    }
+
+    #[test]
+    fn test_replies_flatten_depth_first() {
+        let (root, replies) = thread(&[0, 1, 0, 2], 0);
+        let tree = ThreadTree::new(&root, &replies);
+        let expanded = fully_expanded(&root, &replies);
+
+        let order: Vec<(Uuid, usize)> = tree
+            .visible(&expanded)
+            .iter()
+            .map(|reply| (reply.post.id, reply.depth))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (replies[0].id, 0),
+                (replies[1].id, 1),
+                (replies[3].id, 2),
+                (replies[2].id, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reveal_expands_the_path_to_a_reply() {
+        let (root, replies) = thread(&[0, 1, 2, 0], 0);
+        let tree = ThreadTree::new(&root, &replies);
+        let mut expanded = Expansion::new();
+
+        assert_eq!(tree.reveal(replies[2].id, &mut expanded), Some(3));
+        assert!(!is_expanded(&expanded, replies[2].id));
+        assert_eq!(tree.reveal(Uuid::new_v4(), &mut expanded), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_every_index_up_to_max_selects_a_post(
+            (parents, strays) in thread_strategy(),
+            flags in expansion_strategy(),
+        ) {
+            let (root, replies) = thread(&parents, strays);
+            let tree = ThreadTree::new(&root, &replies);
+            let expanded = expansion(&root, &replies, &flags);
+
+            let max = tree.max_index(&expanded);
+            prop_assert!(max <= tree.len());
+            for index in 0..=max {
+                let post = tree.post_at(index, &expanded);
+                prop_assert!(post.is_some());
+                prop_assert_eq!(tree.index_of(post.unwrap().id, &expanded), Some(index));
+            }
+            prop_assert!(tree.post_at(max + 1, &expanded).is_none());
+        }
+
+        #[test]
+        fn prop_only_descendants_are_shown(
+            (parents, strays) in thread_strategy(),
+        ) {
+            let (root, replies) = thread(&parents, strays);
+            let tree = ThreadTree::new(&root, &replies);
+            let expanded = fully_expanded(&root, &replies);
+
+            prop_assert_eq!(tree.len(), parents.len());
+            prop_assert_eq!(tree.max_index(&expanded), parents.len());
+        }
+
+        #[test]
+        fn prop_toggling_twice_restores_the_list(
+            (parents, strays) in thread_strategy(),
+            flags in expansion_strategy(),
+            pick in any::<prop::sample::Index>(),
+        ) {
+            let (root, replies) = thread(&parents, strays);
+            let tree = ThreadTree::new(&root, &replies);
+            let mut expanded = expansion(&root, &replies, &flags);
+            let before: Vec<Uuid> = tree.visible(&expanded).iter().map(|r| r.post.id).collect();
+
+            let index = pick.index(tree.max_index(&expanded) + 1);
+            let id = tree.post_at(index, &expanded).unwrap().id;
+            toggle(&mut expanded, id);
+            // The toggled post keeps its place in the list
+            prop_assert_eq!(tree.index_of(id, &expanded), Some(index));
+            toggle(&mut expanded, id);
+
+            let after: Vec<Uuid> = tree.visible(&expanded).iter().map(|r| r.post.id).collect();
+            prop_assert_eq!(before, after);
+        }
+
+        #[test]
+        fn prop_flatten_order_is_stable(
+            (parents, strays) in thread_strategy(),
+            flags in expansion_strategy(),
+        ) {
+            let (root, replies) = thread(&parents, strays);
+            let expanded = expansion(&root, &replies, &flags);
+            let visible = ThreadTree::new(&root, &replies).visible(&expanded);
+            let again = ThreadTree::new(&root, &replies).visible(&expanded);
+            let ids = |list: &[VisibleReply]| list.iter().map(|r| r.post.id).collect::<Vec<_>>();
+            prop_assert_eq!(ids(&visible), ids(&again));
+
+            // Siblings appear in reply order and children right under their parent
+            let position = |id: Uuid| replies.iter().position(|p| p.id == id).unwrap();
+            for (i, reply) in visible.iter().enumerate() {
+                if i == 0 {
+                    prop_assert_eq!(reply.depth, 0);
+                    continue;
+                }
+                let previous = &visible[i - 1];
+                prop_assert!(reply.depth <= previous.depth + 1);
+                if reply.depth == previous.depth + 1 {
+                    prop_assert_eq!(reply.post.parent_post_id, Some(previous.post.id));
+                } else if reply.depth == previous.depth {
+                    prop_assert!(position(reply.post.id) > position(previous.post.id));
+                }
+            }
+        }
+
+        #[test]
+        fn prop_revealed_replies_are_selectable(
+            (parents, strays) in thread_strategy(),
+            flags in expansion_strategy(),
+            pick in any::<prop::sample::Index>(),
+        ) {
+            prop_assume!(!parents.is_empty());
+            let (root, replies) = thread(&parents, strays);
+            let tree = ThreadTree::new(&root, &replies);
+            let mut expanded = expansion(&root, &replies, &flags);
+            let all = tree.visible(&fully_expanded(&root, &replies));
+            let target = all[pick.index(all.len())].post.id;
+
+            let index = tree.reveal(target, &mut expanded);
+            prop_assert!(index.is_some());
+            prop_assert_eq!(tree.post_at(index.unwrap(), &expanded).map(|p| p.id), Some(target));
+        }
+    }
+}
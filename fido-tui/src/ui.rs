@@ -1,8 +1,17 @@
 // UI module - split into cohesive submodules for maintainability
+pub mod text_input;
 pub mod theme;
+mod diff;
 mod formatting;
+mod linear;
 mod tabs;
 mod modals;
+mod status_bar;
+mod toasts;
+#[cfg(test)]
+pub mod testing;
+#[cfg(test)]
+mod snapshot_tests;
 
 // Re-export main render function
 pub use self::render_main::render;
@@ -19,7 +28,16 @@ mod render_main {
 
     use crate::app::{App, Screen};
     use super::theme::get_theme_colors;
+    use super::modals::{
+        render_catchup_modal, render_crosspost_prompt, render_diagnostics_modal, render_digest_modal, render_draft_recovery_modal, render_drafts_modal,
+        render_events_modal,
+        render_github_suggestions, render_hashtag_digest_modal, render_highlights_modal, render_link_preview_modal,
+        render_log_viewer, render_reading_history_modal, render_reauth_modal, render_server_switcher_modal,
+        render_share_menu,
+        render_version_mismatch_modal,
+    };
     use super::tabs::{render_auth_screen, render_main_screen};
+    use super::toasts::render_toasts;
 
     /// Render the UI
     pub fn render(app: &mut App, frame: &mut Frame) {
@@ -74,5 +92,79 @@ mod render_main {
             Screen::Auth => render_auth_screen(frame, app),
             Screen::Main => render_main_screen(frame, app),
         }
+
+        // Opened from the composer, so drawn over it
+        if app.drafts.show && app.current_screen == Screen::Main {
+            render_drafts_modal(frame, app, area);
+        }
+
+        if app.events.show && app.current_screen == Screen::Main {
+            render_events_modal(frame, app, area);
+        }
+
+        if app.highlights.show && app.current_screen == Screen::Main {
+            render_highlights_modal(frame, app, area);
+        }
+
+        if app.hashtag_digest.show && app.current_screen == Screen::Main {
+            render_hashtag_digest_modal(frame, app, area);
+        }
+
+        if app.reading_history.show && app.current_screen == Screen::Main {
+            render_reading_history_modal(frame, app, area);
+        }
+
+        if app.crosspost_prompt_open() {
+            render_crosspost_prompt(frame, app, area);
+        }
+
+        if app.github_suggestions_open() {
+            render_github_suggestions(frame, app, area);
+        }
+
+        // Server switcher overlays whichever screen is showing
+        if app.server_switcher.show_modal {
+            render_server_switcher_modal(frame, app, area);
+        }
+
+        if app.digest.is_some() && app.current_screen == Screen::Main {
+            render_digest_modal(frame, app, area);
+        }
+
+        // Drawn over the digest; it's dismissed first
+        if app.catchup.is_some() && app.current_screen == Screen::Main {
+            render_catchup_modal(frame, app, area);
+        }
+
+        if app.recovered_draft.is_some() && app.current_screen == Screen::Main {
+            render_draft_recovery_modal(frame, app, area);
+        }
+
+        if app.link_preview.show {
+            render_link_preview_modal(frame, app, area);
+        }
+
+        if app.share_menu.show {
+            render_share_menu(frame, app, area);
+        }
+
+        if app.reauth.show && app.current_screen == Screen::Main {
+            render_reauth_modal(frame, app, area);
+        }
+
+        // Above every prompt except the server switcher, which is the way out
+        if app.version_incompatible() && !app.server_switcher.show_modal {
+            render_version_mismatch_modal(frame, app, area);
+        }
+
+        if app.diagnostics.show {
+            render_diagnostics_modal(frame, app, area);
+        }
+
+        if app.log_viewer.show {
+            render_log_viewer(frame, app, area);
+        }
+
+        render_toasts(frame, app, area);
     }
 }
@@ -0,0 +1,149 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::theme::ThemeColors;
+
+/// What a line of a unified diff is, which decides its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine {
+    /// `diff --git`, `index`, `---` and `+++` lines naming the files
+    FileHeader,
+    /// `@@ -12,7 +12,8 @@`, where a hunk starts
+    Hunk,
+    Added,
+    Removed,
+    Context,
+}
+
+fn is_hunk_header(line: &str) -> bool {
+    line.starts_with("@@ -") && line[4..].contains(" @@")
+}
+
+fn is_file_header(line: &str) -> bool {
+    line.starts_with("diff --git ") || line.starts_with("--- ") || line.starts_with("+++ ")
+}
+
+/// Whether `content` holds a unified diff: a hunk header with at least one
+/// added or removed line, so a bulleted list isn't taken for one
+pub fn contains_diff(content: &str) -> bool {
+    let has_hunk = content.lines().any(is_hunk_header);
+    let has_change = content.lines().any(|line| {
+        !is_file_header(line) && (line.starts_with('+') || line.starts_with('-'))
+    });
+    has_hunk && has_change
+}
+
+/// Classify `line`, given whether the line before it was part of a diff.
+/// Headers start a diff; inside one, changed, context and blank lines
+/// continue it, and anything else is text around it (None).
+pub fn classify(line: &str, in_diff: bool) -> Option<DiffLine> {
+    if is_hunk_header(line) {
+        return Some(DiffLine::Hunk);
+    }
+    if is_file_header(line) || (in_diff && line.starts_with("index ")) {
+        return Some(DiffLine::FileHeader);
+    }
+    if !in_diff {
+        return None;
+    }
+    match line.chars().next() {
+        Some('+') => Some(DiffLine::Added),
+        Some('-') => Some(DiffLine::Removed),
+        // `\ No newline at end of file` reads as context
+        None | Some(' ') | Some('\\') => Some(DiffLine::Context),
+        _ => None,
+    }
+}
+
+fn style_for(kind: DiffLine, theme: &ThemeColors) -> Style {
+    match kind {
+        DiffLine::FileHeader => Style::default()
+            .fg(theme.text_dim)
+            .add_modifier(Modifier::BOLD),
+        DiffLine::Hunk => Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+        DiffLine::Added => Style::default().fg(theme.success),
+        DiffLine::Removed => Style::default().fg(theme.error),
+        DiffLine::Context => Style::default().fg(theme.text),
+    }
+}
+
+/// A diff line in its color, cut into pieces of `width` characters rather
+/// than wrapped at words so code keeps its spacing
+pub fn diff_lines(line: &str, kind: DiffLine, theme: &ThemeColors, width: usize) -> Vec<Line<'static>> {
+    let style = style_for(kind, theme);
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        return vec![Line::from(Span::raw("  "))];
+    }
+    chars
+        .chunks(width.max(1))
+        .map(|piece| {
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(piece.iter().collect::<String>(), style),
+            ])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "Fixed the off-by-one:\n\
+        --- a/src/lib.rs\n\
+        +++ b/src/lib.rs\n\
+        @@ -1,3 +1,3 @@\n \
+        fn last(items: &[u8]) -> u8 {\n\
+        -    items[items.len()]\n\
+        +    items[items.len() - 1]\n \
+        }\n\
+        Thanks @ana";
+
+    #[test]
+    fn test_diffs_need_a_hunk_and_a_change() {
+        assert!(contains_diff(PATCH));
+        assert!(!contains_diff("Shopping:\n- milk\n- eggs\n+ bread"));
+        assert!(!contains_diff("@@ -1,3 +1,3 @@\n context only"));
+    }
+
+    #[test]
+    fn test_lines_around_the_diff_stay_text() {
+        let mut in_diff = false;
+        let kinds: Vec<Option<DiffLine>> = PATCH
+            .lines()
+            .map(|line| {
+                let kind = classify(line, in_diff);
+                in_diff = kind.is_some();
+                kind
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                None,
+                Some(DiffLine::FileHeader),
+                Some(DiffLine::FileHeader),
+                Some(DiffLine::Hunk),
+                Some(DiffLine::Context),
+                Some(DiffLine::Removed),
+                Some(DiffLine::Added),
+                Some(DiffLine::Context),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_diff_lines_are_cut_not_wrapped() {
+        let theme = crate::ui::theme::get_theme_colors(&crate::app::App::new());
+        let lines = diff_lines("+    let total = a + b;", DiffLine::Added, &theme, 10);
+        let text: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        assert_eq!(text, vec!["  +    let t", "  otal = a +", "   b;"]);
+        assert_eq!(lines[0].spans[1].style.fg, Some(theme.success));
+    }
+}
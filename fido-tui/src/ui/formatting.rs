@@ -1,16 +1,517 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Local, Utc};
+use fido_types::TimestampStyle;
 use ratatui::{
     style::{Modifier, Style},
     text::{Line, Span},
 };
 
+use super::diff;
+use crate::text_wrapper::truncate_to_width;
 use super::theme::ThemeColors;
 
 // Layout constants
 pub const BORDER_PADDING: u16 = 4; // Total horizontal padding from borders (2 per side)
 
-/// Format timestamp for display
-pub fn format_timestamp(timestamp: &chrono::DateTime<chrono::Utc>) -> String {
-    timestamp.format("%Y-%m-%d %H:%M").to_string()
+/// Format timestamp for display, in the local timezone and the locale's date order
+pub fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format(absolute_format())
+        .to_string()
+}
+
+/// A post's time in the user's timestamp style, or its exact time if it's
+/// the post revealed with `T`
+pub fn post_time(
+    post: &fido_types::Post,
+    style: TimestampStyle,
+    exact_time_post: Option<uuid::Uuid>,
+) -> String {
+    if exact_time_post == Some(post.id) {
+        return exact_timestamp(&post.created_at);
+    }
+    match style {
+        TimestampStyle::Absolute => format_timestamp(&post.created_at),
+        TimestampStyle::Relative => relative_timestamp(&post.created_at, Utc::now()),
+    }
+}
+
+/// Local time to the second, with the UTC offset
+pub fn exact_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S (UTC%:z)")
+        .to_string()
+}
+
+/// "just now", "5m ago", "3h ago", "2d ago"; older than a week shows the date
+pub fn relative_timestamp(timestamp: &DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let age = now.signed_duration_since(*timestamp);
+    // Clock skew can put a post slightly in the future, which also reads "just now"
+    if age.num_minutes() < 1 {
+        "just now".to_string()
+    } else if age.num_hours() < 1 {
+        format!("{}m ago", age.num_minutes())
+    } else if age.num_days() < 1 {
+        format!("{}h ago", age.num_hours())
+    } else if age.num_days() < 7 {
+        format!("{}d ago", age.num_days())
+    } else {
+        format_timestamp(timestamp)
+    }
+}
+
+/// strftime layout for absolute times, picked once from LC_ALL / LC_TIME / LANG
+fn absolute_format() -> &'static str {
+    static FORMAT: OnceLock<&'static str> = OnceLock::new();
+    FORMAT.get_or_init(|| {
+        let locale = ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty());
+        absolute_format_for(locale.as_deref())
+    })
+}
+
+/// Date order and clock for a POSIX locale such as `en_US.UTF-8`; ISO 8601
+/// for the C/POSIX locale or anything without a region
+fn absolute_format_for(locale: Option<&str>) -> &'static str {
+    let region = locale
+        .and_then(|locale| locale.split(['.', '@']).next())
+        .and_then(|name| name.split_once('_'))
+        .map(|(_, region)| region);
+    match region {
+        None => "%Y-%m-%d %H:%M",
+        Some("US" | "PH") => "%m/%d/%Y %-I:%M %p",
+        Some("CN" | "JP" | "KR" | "TW" | "HU" | "LT" | "SE") => "%Y-%m-%d %H:%M",
+        Some("AT" | "CH" | "CZ" | "DE" | "DK" | "FI" | "NO" | "PL" | "RU" | "SK" | "TR" | "UA") => {
+            "%d.%m.%Y %H:%M"
+        }
+        Some(_) => "%d/%m/%Y %H:%M",
+    }
+}
+
+/// Badge glyphs shown after a username (" ★⚙"), empty without badges
+pub fn badge_suffix(badges: &[fido_types::Badge]) -> String {
+    if badges.is_empty() {
+        return String::new();
+    }
+    let glyphs: String = badges.iter().map(|badge| badge.glyph()).collect();
+    format!(" {}", glyphs)
+}
+
+/// Header label for a post's author ("[deleted]" for tombstones, "anon"
+/// for anonymous posts, which only their author sees with a real ID)
+pub fn post_author_label(post: &fido_types::Post) -> String {
+    if post.is_deleted {
+        "[deleted]".to_string()
+    } else if post.is_anonymous && post.author_id.is_nil() {
+        "anon".to_string()
+    } else if post.is_anonymous {
+        "anon (you)".to_string()
+    } else {
+        format!("@{}{}", post.author_username, badge_suffix(&post.author_badges))
+    }
+}
+
+/// Karma shown after an author's name (" (karma: 42)"); empty for
+/// tombstones and anonymous posts, which don't name anyone
+pub fn karma_suffix(post: &fido_types::Post) -> String {
+    if post.is_deleted || post.is_anonymous {
+        String::new()
+    } else {
+        format!(" (karma: {})", post.author_karma)
+    }
+}
+
+/// One block character per value, scaled to the largest ("▁▃█"); all
+/// zeros stay on the baseline
+pub fn sparkline(values: &[u32]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| {
+            if max == 0 {
+                BARS[0]
+            } else {
+                BARS[(value as usize * (BARS.len() - 1) + max as usize / 2) / max as usize]
+            }
+        })
+        .collect()
+}
+
+/// `style` for the author label, in italics for anonymous posts
+pub fn post_author_style(post: &fido_types::Post, style: Style) -> Style {
+    if post.is_anonymous && !post.is_deleted {
+        style.add_modifier(Modifier::ITALIC)
+    } else {
+        style
+    }
+}
+
+/// Up/down vote count spans; the user's own vote gets a filled arrow as well
+/// as color so it reads without relying on red/green
+pub fn vote_spans(post: &fido_types::Post, theme: &ThemeColors) -> (Span<'static>, Span<'static>) {
+    let dim = Style::default().fg(theme.text_dim);
+    let (up_glyph, up_style) = if post.user_vote.as_deref() == Some("up") {
+        ("▲", Style::default().fg(theme.success).add_modifier(Modifier::BOLD))
+    } else {
+        ("↑", dim)
+    };
+    let (down_glyph, down_style) = if post.user_vote.as_deref() == Some("down") {
+        ("▼", Style::default().fg(theme.error).add_modifier(Modifier::BOLD))
+    } else {
+        ("↓", dim)
+    };
+    (
+        Span::styled(format!("{} {}", up_glyph, post.upvotes), up_style),
+        Span::styled(format!("{} {}", down_glyph, post.downvotes), down_style),
+    )
+}
+
+/// Format a post's body, rendering tombstoned posts as a dim placeholder.
+/// `focused_token` highlights that hashtag, mention or link (Tab in the feed);
+/// `show_diff` colors a diff in the post, false shows it as typed ('v')
+pub fn format_post_body(
+    post: &fido_types::Post,
+    is_selected: bool,
+    focused_token: Option<usize>,
+    show_diff: bool,
+    theme: &ThemeColors,
+    max_width: usize,
+) -> Vec<Line<'static>> {
+    if post.is_deleted {
+        return vec![Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                "[deleted]",
+                Style::default()
+                    .fg(theme.text_dim)
+                    .add_modifier(Modifier::ITALIC),
+            ),
+        ])];
+    }
+    let mut lines = Vec::new();
+    if let Some(kind) = &post.kind {
+        lines.push(post_kind_line(kind, theme));
+    }
+    lines.extend(format_post_content_focused(
+        &post.content,
+        is_selected,
+        focused_token,
+        show_diff,
+        theme,
+        max_width,
+    ));
+    lines
+}
+
+/// The line above a special post's text that sets it apart from plain posts
+pub fn post_kind_line(kind: &fido_types::PostKind, theme: &ThemeColors) -> Line<'static> {
+    match kind {
+        fido_types::PostKind::Event(event) => event_line(event, theme),
+        fido_types::PostKind::ReviewRequest(review) => review_line(review, theme),
+        fido_types::PostKind::Job(job) => job_line(job, theme),
+    }
+}
+
+/// "Title · Company · Remote · link" summary of a job post
+pub fn job_line(job: &fido_types::JobDetails, theme: &ThemeColors) -> Line<'static> {
+    let dim = Style::default().fg(theme.text_dim);
+    Line::from(vec![
+        Span::raw("  💼 "),
+        Span::styled(
+            job.title.clone(),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" · ", dim),
+        Span::styled(job.company.clone(), Style::default().fg(theme.text)),
+        Span::styled(" · ", dim),
+        Span::styled(job.work_mode.label(), Style::default().fg(theme.success)),
+        Span::styled(" · ", dim),
+        Span::styled(
+            job.url.clone(),
+            Style::default().fg(theme.text).add_modifier(Modifier::UNDERLINED),
+        ),
+    ])
+}
+
+/// "Review requested · language · link", or "Reviewed" once the author closed it
+pub fn review_line(review: &fido_types::ReviewRequestDetails, theme: &ThemeColors) -> Line<'static> {
+    let dim = Style::default().fg(theme.text_dim);
+    let (label, label_style) = if review.is_open {
+        (
+            "🔍 Review requested",
+            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        ("✓ Reviewed", Style::default().fg(theme.success))
+    };
+    Line::from(vec![
+        Span::raw("  "),
+        Span::styled(label, label_style),
+        Span::styled(" · ", dim),
+        Span::styled(review.language.clone(), Style::default().fg(theme.accent)),
+        Span::styled(" · ", dim),
+        Span::styled(
+            review.url.clone(),
+            Style::default().fg(theme.text).add_modifier(Modifier::UNDERLINED),
+        ),
+    ])
+}
+
+/// "When · what · where" summary of an event, with its RSVP counts
+pub fn event_line(event: &fido_types::EventDetails, theme: &ThemeColors) -> Line<'static> {
+    let dim = Style::default().fg(theme.text_dim);
+    let mut spans = vec![
+        Span::raw("  📅 "),
+        Span::styled(
+            event.starts_at.with_timezone(&Local).format("%a %-d %b %H:%M").to_string(),
+            Style::default().fg(theme.accent),
+        ),
+        Span::styled(" · ", dim),
+        Span::styled(
+            event.title.clone(),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" · ", dim),
+        Span::styled(event.location.clone(), Style::default().fg(theme.text)),
+        Span::styled(format!(" · {}", rsvp_counts(event)), dim),
+    ];
+    if let Some(rsvp) = event.my_rsvp {
+        spans.push(Span::styled(
+            format!(" ✓ {}", rsvp_label(rsvp)),
+            Style::default().fg(theme.success).add_modifier(Modifier::BOLD),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// "3 going · 1 interested"
+pub fn rsvp_counts(event: &fido_types::EventDetails) -> String {
+    format!("{} going · {} interested", event.going_count, event.interested_count)
+}
+
+pub fn rsvp_label(status: fido_types::RsvpStatus) -> &'static str {
+    match status {
+        fido_types::RsvpStatus::Going => "Going",
+        fido_types::RsvpStatus::Interested => "Interested",
+    }
+}
+
+/// `1234` as `1.2k`, `3400000` as `3.4M`
+fn compact_count(n: u64) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => format!("{:.1}k", n as f64 / 1_000.0),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
+/// The card for the first GitHub link in `post` that has one loaded
+pub fn github_card_of<'a>(
+    post: &fido_types::Post,
+    cards: &'a HashMap<String, Option<fido_types::GitHubCard>>,
+) -> Option<&'a fido_types::GitHubCard> {
+    if post.is_deleted {
+        return None;
+    }
+    crate::links::extract_urls(&post.content)
+        .iter()
+        .find_map(|url| cards.get(url).and_then(Option::as_ref))
+}
+
+/// A GitHub repository, issue or pull request as two lines set off by a
+/// bar: what it is with its stars or status, then its description or title
+pub fn github_card_lines(
+    card: &fido_types::GitHubCard,
+    theme: &ThemeColors,
+    max_width: usize,
+) -> Vec<Line<'static>> {
+    use fido_types::{GitHubCardKind, GitHubState};
+
+    let width = max_width.saturating_sub(6);
+    let bar = Span::styled("  ▌ ", Style::default().fg(theme.accent));
+    let dim = Style::default().fg(theme.text_dim);
+    let name = Style::default()
+        .fg(theme.primary)
+        .add_modifier(Modifier::BOLD);
+    let state_span = |state: GitHubState, what: &str| {
+        let color = match state {
+            GitHubState::Open => theme.success,
+            GitHubState::Closed => theme.error,
+            GitHubState::Merged => theme.accent,
+            GitHubState::Draft => theme.text_dim,
+        };
+        Span::styled(format!("{} {}", state.label(), what), Style::default().fg(color))
+    };
+
+    let (header, detail) = match &card.kind {
+        GitHubCardKind::Repo {
+            description,
+            stars,
+            language,
+            archived,
+        } => {
+            let mut facts = vec![format!("★ {}", compact_count(*stars))];
+            facts.extend(language.clone());
+            if *archived {
+                facts.push("archived".to_string());
+            }
+            let header = vec![
+                Span::styled(card.repo.clone(), name),
+                Span::styled(format!("  {}", facts.join(" · ")), dim),
+            ];
+            (header, description.clone())
+        }
+        GitHubCardKind::Issue {
+            number,
+            title,
+            state,
+            comments,
+        } => {
+            let header = vec![
+                Span::styled(format!("{}#{}", card.repo, number), name),
+                Span::raw("  "),
+                state_span(*state, "issue"),
+                Span::styled(format!(" · 💬 {}", comments), dim),
+            ];
+            (header, Some(title.clone()))
+        }
+        GitHubCardKind::PullRequest {
+            number,
+            title,
+            state,
+            comments,
+        } => {
+            let header = vec![
+                Span::styled(format!("{}#{}", card.repo, number), name),
+                Span::raw("  "),
+                state_span(*state, "pull request"),
+                Span::styled(format!(" · 💬 {}", comments), dim),
+            ];
+            (header, Some(title.clone()))
+        }
+    };
+
+    let mut first = vec![bar.clone()];
+    first.extend(header);
+    let mut lines = vec![Line::from(first)];
+    if let Some(detail) = detail {
+        lines.push(Line::from(vec![
+            bar,
+            Span::styled(truncate_to_width(&detail, width), Style::default().fg(theme.text)),
+        ]));
+    }
+    lines
+}
+
+/// The gist linked from `post`, once loaded
+pub fn gist_of<'a>(
+    post: &fido_types::Post,
+    gists: &'a HashMap<String, Option<fido_types::Gist>>,
+) -> Option<&'a fido_types::Gist> {
+    if post.is_deleted {
+        return None;
+    }
+    crate::links::extract_urls(&post.content)
+        .iter()
+        .find_map(|url| gists.get(url).and_then(Option::as_ref))
+}
+
+/// A gist read inline, set off by a bar: who made it and what it is, then
+/// each file under its name
+pub fn gist_lines(gist: &fido_types::Gist, theme: &ThemeColors, max_width: usize) -> Vec<Line<'static>> {
+    let width = max_width.saturating_sub(6).max(1);
+    let bar = Span::styled("  ▌ ", Style::default().fg(theme.accent));
+    let dim = Style::default().fg(theme.text_dim);
+    let text = Style::default().fg(theme.text);
+
+    let mut header = vec![
+        bar.clone(),
+        Span::styled(
+            "Gist",
+            Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some(owner) = &gist.owner {
+        header.push(Span::styled(format!(" by {}", owner), dim));
+    }
+    let mut lines = vec![Line::from(header)];
+    if let Some(description) = &gist.description {
+        lines.push(Line::from(vec![bar.clone(), Span::styled(truncate_to_width(description, width), dim)]));
+    }
+
+    for file in &gist.files {
+        lines.push(Line::from(bar.clone()));
+        lines.push(Line::from(vec![
+            bar.clone(),
+            Span::styled(file.filename.clone(), dim.add_modifier(Modifier::BOLD)),
+        ]));
+        for line in file.content.lines() {
+            if line.trim().is_empty() {
+                lines.push(Line::from(bar.clone()));
+                continue;
+            }
+            for piece in textwrap::wrap(line, width) {
+                lines.push(Line::from(vec![bar.clone(), Span::styled(piece.to_string(), text)]));
+            }
+        }
+        if file.truncated {
+            lines.push(Line::from(vec![
+                bar.clone(),
+                Span::styled("… cut short, open the link for the rest", dim),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Keep the first `max_lines` lines of a post body (all of them when 0),
+/// ending with a dim "… more" when any were cut
+pub fn limit_preview(
+    mut lines: Vec<Line<'static>>,
+    max_lines: usize,
+    theme: &ThemeColors,
+) -> Vec<Line<'static>> {
+    if max_lines > 0 && lines.len() > max_lines {
+        lines.truncate(max_lines);
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("… more", Style::default().fg(theme.text_dim)),
+        ]));
+    }
+    lines
+}
+
+/// A post body collapsed onto one line of at most `width` characters, for
+/// the compact feed
+pub fn one_line_preview(post: &fido_types::Post, width: usize) -> String {
+    if post.is_deleted {
+        return "[deleted]".to_string();
+    }
+    let mut text = post
+        .content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    match &post.kind {
+        Some(fido_types::PostKind::Event(event)) => {
+            text = format!("📅 {} — {}", event.title, text);
+        }
+        Some(fido_types::PostKind::ReviewRequest(review)) if review.is_open => {
+            text = format!("🔍 {} — {}", review.language, text);
+        }
+        Some(fido_types::PostKind::Job(job)) => {
+            text = format!("💼 {} at {} — {}", job.title, job.company, text);
+        }
+        _ => {}
+    }
+    truncate_to_width(&text, width)
 }
 
 /// Format post content with hashtag highlighting and text wrapping
@@ -29,11 +530,41 @@ pub fn format_post_content_with_width(
     is_selected: bool,
     theme: &ThemeColors,
     max_width: usize,
+) -> Vec<Line<'static>> {
+    format_post_content_focused(content, is_selected, None, false, theme, max_width)
+}
+
+/// Wrapped, highlighted post content with the `focused_token`-th token
+/// (counted as in `post_tokens::extract_tokens`) shown reversed. With
+/// `show_diff`, a unified diff in the content is colored line by line.
+fn format_post_content_focused(
+    content: &str,
+    is_selected: bool,
+    focused_token: Option<usize>,
+    show_diff: bool,
+    theme: &ThemeColors,
+    max_width: usize,
 ) -> Vec<Line<'static>> {
     let mut lines = vec![];
     let wrap_width = max_width.saturating_sub(4);
+    let mut token_index = 0;
+    let show_diff = show_diff && diff::contains_diff(content);
+    let mut in_diff = false;
 
     for line in content.lines() {
+        if show_diff {
+            if let Some(kind) = diff::classify(line, in_diff) {
+                in_diff = true;
+                // Keep counting tokens so focus after the diff lands right
+                for word in line.split_whitespace() {
+                    is_focused_word(word, focused_token, &mut token_index);
+                }
+                lines.extend(diff::diff_lines(line, kind, theme, wrap_width));
+                continue;
+            }
+            in_diff = false;
+        }
+
         let wrapped = textwrap::wrap(line, wrap_width);
 
         for wrapped_line in wrapped {
@@ -46,7 +577,9 @@ pub fn format_post_content_with_width(
             for ch in line_str.chars() {
                 if ch.is_whitespace() {
                     if !current_word.is_empty() {
-                        push_styled_word(&mut spans, &current_word, is_selected, theme);
+                        let focused =
+                            is_focused_word(&current_word, focused_token, &mut token_index);
+                        push_styled_word(&mut spans, &current_word, is_selected, focused, theme);
                         current_word.clear();
                     }
                     whitespace_buffer.push(ch);
@@ -59,7 +592,8 @@ pub fn format_post_content_with_width(
             }
             
             if !current_word.is_empty() {
-                push_styled_word(&mut spans, &current_word, is_selected, theme);
+                let focused = is_focused_word(&current_word, focused_token, &mut token_index);
+                push_styled_word(&mut spans, &current_word, is_selected, focused, theme);
             }
             if !whitespace_buffer.is_empty() {
                 spans.push(Span::raw(whitespace_buffer));
@@ -72,8 +606,24 @@ pub fn format_post_content_with_width(
     lines
 }
 
+/// Whether `word` is the focused token, advancing the running token count
+fn is_focused_word(word: &str, focused_token: Option<usize>, token_index: &mut usize) -> bool {
+    if crate::post_tokens::word_token(word).is_none() {
+        return false;
+    }
+    let focused = focused_token == Some(*token_index);
+    *token_index += 1;
+    focused
+}
+
 /// Push a styled word to spans with appropriate formatting
-fn push_styled_word(spans: &mut Vec<Span<'static>>, word: &str, is_selected: bool, theme: &ThemeColors) {
+fn push_styled_word(
+    spans: &mut Vec<Span<'static>>,
+    word: &str,
+    is_selected: bool,
+    focused: bool,
+    theme: &ThemeColors,
+) {
     let (color, should_bold) = if word.starts_with('#') {
         (if is_selected { theme.accent } else { theme.secondary }, true)
     } else if word.starts_with('@') {
@@ -86,10 +636,108 @@ fn push_styled_word(spans: &mut Vec<Span<'static>>, word: &str, is_selected: boo
     if should_bold {
         style = style.add_modifier(Modifier::BOLD);
     }
+    if focused {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
     
     spans.push(Span::styled(word.to_string(), style));
 }
 
+/// Style for a word typed into the composer: hashtags, mentions and URLs
+/// stand out the same way they will once posted
+fn composer_word_style(word: &str, theme: &ThemeColors) -> Style {
+    if word.starts_with("http://") || word.starts_with("https://") {
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::UNDERLINED)
+    } else if word.len() > 1 && word.starts_with('#') {
+        Style::default()
+            .fg(theme.secondary)
+            .add_modifier(Modifier::BOLD)
+    } else if word.len() > 1 && word.starts_with('@') {
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.text)
+    }
+}
+
+/// Render composer lines with live highlighting and a block cursor at
+/// `cursor` (row, char column), as reported by the TextArea
+pub fn format_composer_lines(
+    lines: &[String],
+    cursor: (usize, usize),
+    theme: &ThemeColors,
+) -> Vec<Line<'static>> {
+    let cursor_style = Style::default().fg(theme.background).bg(theme.primary);
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(row, line)| {
+            // Split the line into runs of whitespace and words, each with a style
+            let mut runs: Vec<(String, Style)> = Vec::new();
+            let mut current = String::new();
+            let mut in_word = false;
+            let run_style = |text: &str, is_word: bool| {
+                if is_word {
+                    composer_word_style(text, theme)
+                } else {
+                    Style::default()
+                }
+            };
+            for ch in line.chars() {
+                if ch.is_whitespace() == in_word && !current.is_empty() {
+                    let style = run_style(&current, in_word);
+                    runs.push((std::mem::take(&mut current), style));
+                }
+                in_word = !ch.is_whitespace();
+                current.push(ch);
+            }
+            if !current.is_empty() {
+                let style = run_style(&current, in_word);
+                runs.push((current, style));
+            }
+
+            if row != cursor.0 {
+                return Line::from(
+                    runs.into_iter()
+                        .map(|(text, style)| Span::styled(text, style))
+                        .collect::<Vec<_>>(),
+                );
+            }
+
+            // Carve the cursor character out of whichever run contains it
+            let mut spans = Vec::new();
+            let mut col = 0;
+            for (text, style) in runs {
+                let len = text.chars().count();
+                if (col..col + len).contains(&cursor.1) {
+                    let offset = cursor.1 - col;
+                    let before: String = text.chars().take(offset).collect();
+                    let at: String = text.chars().skip(offset).take(1).collect();
+                    let after: String = text.chars().skip(offset + 1).collect();
+                    if !before.is_empty() {
+                        spans.push(Span::styled(before, style));
+                    }
+                    spans.push(Span::styled(at, cursor_style));
+                    if !after.is_empty() {
+                        spans.push(Span::styled(after, style));
+                    }
+                } else {
+                    spans.push(Span::styled(text, style));
+                }
+                col += len;
+            }
+            if cursor.1 >= col {
+                spans.push(Span::styled(" ", cursor_style));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
 /// Format post content for input box (no indent, simpler formatting)
 #[allow(dead_code)]
 pub fn format_post_content_for_input(content: &str) -> Vec<Line<'static>> {
@@ -125,6 +773,183 @@ pub fn format_bio_content_with_width(
     lines
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use fido_types::ReplyPolicy;
+
+    #[test]
+    fn test_relative_timestamp_buckets() {
+        let now = Utc::now();
+        assert_eq!(relative_timestamp(&(now + Duration::seconds(5)), now), "just now");
+        assert_eq!(relative_timestamp(&(now - Duration::seconds(30)), now), "just now");
+        assert_eq!(relative_timestamp(&(now - Duration::minutes(5)), now), "5m ago");
+        assert_eq!(relative_timestamp(&(now - Duration::hours(2)), now), "2h ago");
+        assert_eq!(relative_timestamp(&(now - Duration::days(3)), now), "3d ago");
+
+        let old = now - Duration::days(30);
+        assert_eq!(relative_timestamp(&old, now), format_timestamp(&old));
+    }
+
+    #[test]
+    fn test_previews_cut_long_posts() {
+        let theme = crate::ui::theme::get_theme_colors(&crate::app::App::new());
+        let lines = |n: usize| (0..n).map(|i| Line::from(i.to_string())).collect::<Vec<_>>();
+        assert_eq!(limit_preview(lines(5), 0, &theme).len(), 5);
+        assert_eq!(limit_preview(lines(3), 3, &theme).len(), 3);
+        let cut = limit_preview(lines(5), 2, &theme);
+        assert_eq!(cut.len(), 3);
+        assert_eq!(cut[2].to_string(), "  … more");
+
+        let mut post = fido_types::Post {
+            id: uuid::Uuid::nil(),
+            author_id: uuid::Uuid::nil(),
+            author_username: "bob".to_string(),
+            content: "first line\n\nsecond   line".to_string(),
+            created_at: Utc::now(),
+            upvotes: 0,
+            downvotes: 0,
+            hashtags: Vec::new(),
+            user_vote: None,
+            parent_post_id: None,
+            reply_count: 0,
+            reply_to_user_id: None,
+            reply_to_username: None,
+            is_deleted: false,
+            author_badges: Vec::new(),
+            author_status: None,
+            kind: None,
+            is_anonymous: false,
+            reply_policy: ReplyPolicy::Everyone,
+            can_reply: true,
+            is_accepted: false,
+            author_karma: 0,
+        };
+        assert_eq!(one_line_preview(&post, 40), "first line second line");
+        assert_eq!(one_line_preview(&post, 12), "first line…");
+        // Wide characters take two columns each
+        post.content = "日本語のテキストです".to_string();
+        assert_eq!(one_line_preview(&post, 9), "日本語の…");
+        post.is_deleted = true;
+        assert_eq!(one_line_preview(&post, 40), "[deleted]");
+    }
+
+    #[test]
+    fn test_sparkline_scales_to_the_busiest_week() {
+        assert_eq!(sparkline(&[0, 1, 2, 4, 8]), "▁▂▃▅█");
+        assert_eq!(sparkline(&[0, 0, 0]), "▁▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_anonymous_authors_show_as_anon() {
+        let mut post = fido_types::Post {
+            id: uuid::Uuid::new_v4(),
+            author_id: uuid::Uuid::nil(),
+            author_username: "anon".to_string(),
+            content: "psst".to_string(),
+            created_at: Utc::now(),
+            upvotes: 0,
+            downvotes: 0,
+            hashtags: Vec::new(),
+            user_vote: None,
+            parent_post_id: None,
+            reply_count: 0,
+            reply_to_user_id: None,
+            reply_to_username: None,
+            is_deleted: false,
+            author_badges: Vec::new(),
+            author_status: None,
+            kind: None,
+            is_anonymous: true,
+            reply_policy: ReplyPolicy::Everyone,
+            can_reply: true,
+            is_accepted: false,
+            author_karma: 0,
+        };
+        assert_eq!(post_author_label(&post), "anon");
+        assert!(post_author_style(&post, Style::default())
+            .add_modifier
+            .contains(Modifier::ITALIC));
 
+        // Only the author gets their own ID back
+        post.author_id = uuid::Uuid::new_v4();
+        post.author_username = "alice".to_string();
+        assert_eq!(post_author_label(&post), "anon (you)");
 
+        assert_eq!(karma_suffix(&post), "");
 
+        post.is_anonymous = false;
+        post.author_karma = -3;
+        assert_eq!(post_author_label(&post), "@alice");
+        assert_eq!(karma_suffix(&post), " (karma: -3)");
+    }
+
+    #[test]
+    fn test_absolute_format_follows_locale_region() {
+        assert_eq!(absolute_format_for(None), "%Y-%m-%d %H:%M");
+        assert_eq!(absolute_format_for(Some("C.UTF-8")), "%Y-%m-%d %H:%M");
+        assert_eq!(absolute_format_for(Some("en_US.UTF-8")), "%m/%d/%Y %-I:%M %p");
+        assert_eq!(absolute_format_for(Some("de_DE.UTF-8")), "%d.%m.%Y %H:%M");
+        assert_eq!(absolute_format_for(Some("en_GB")), "%d/%m/%Y %H:%M");
+        assert_eq!(absolute_format_for(Some("ja_JP.UTF-8")), "%Y-%m-%d %H:%M");
+    }
+
+    #[test]
+    fn test_star_counts_are_compact() {
+        assert_eq!(compact_count(999), "999");
+        assert_eq!(compact_count(1234), "1.2k");
+        assert_eq!(compact_count(3_400_000), "3.4M");
+    }
+
+    #[test]
+    fn test_gists_read_inline_under_a_bar() {
+        let theme = crate::ui::theme::get_theme_colors(&crate::app::App::new());
+        let gist = fido_types::Gist {
+            url: "https://gist.github.com/ferris/0123abcd".to_string(),
+            owner: Some("ferris".to_string()),
+            description: None,
+            files: vec![fido_types::GistFile {
+                filename: "post.md".to_string(),
+                content: "First paragraph\n\nSecond".to_string(),
+                truncated: true,
+            }],
+        };
+        let text: Vec<String> = gist_lines(&gist, &theme, 80)
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "  ▌ Gist by ferris",
+                "  ▌ ",
+                "  ▌ post.md",
+                "  ▌ First paragraph",
+                "  ▌ ",
+                "  ▌ Second",
+                "  ▌ … cut short, open the link for the rest",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diffs_are_colored_unless_shown_raw() {
+        let theme = crate::ui::theme::get_theme_colors(&crate::app::App::new());
+        let content = "Fix:\n@@ -1 +1 @@\n-let x = 1;\n+let x = 2;";
+        let colored = |lines: &[Line]| {
+            lines
+                .iter()
+                .flat_map(|line| &line.spans)
+                .any(|span| span.style.fg == Some(theme.error))
+        };
+        let diff = format_post_content_focused(content, false, None, true, &theme, 40);
+        assert!(colored(&diff));
+        assert_eq!(diff[3].to_string(), "  +let x = 2;");
+
+        let raw = format_post_content_focused(content, false, None, false, &theme, 40);
+        assert!(!colored(&raw));
+        assert_eq!(raw.len(), diff.len());
+    }
+}
@@ -0,0 +1,236 @@
+//! Plain single-column layouts for the accessible layout setting.
+//!
+//! No borders, columns or glyph-only information: each line reads as text
+//! from left to right, the selection is marked with "> " as well as color,
+//! and the last line always names the keys for the screen.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use super::formatting::{format_timestamp, post_time};
+use super::modals::render_new_conversation_modal;
+use super::tabs::get_action_bar_text;
+use super::theme::{get_theme_colors, ThemeColors};
+use crate::app::{App, DeliveryState};
+
+/// Split off the bottom line for the keys reminder
+fn with_keys_line(frame: &mut Frame, app: &App, area: Rect) -> Rect {
+    let theme = get_theme_colors(app);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    let keys = Paragraph::new(format!("Keys: {} | ?: every key", get_action_bar_text(app)))
+        .style(Style::default().fg(theme.text));
+    frame.render_widget(keys, chunks[1]);
+    chunks[0]
+}
+
+fn item_style(theme: &ThemeColors, selected: bool) -> Style {
+    if selected {
+        Style::default()
+            .fg(theme.success)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.text)
+    }
+}
+
+fn marker(selected: bool) -> &'static str {
+    if selected {
+        "> "
+    } else {
+        "  "
+    }
+}
+
+/// `text` wrapped to `width`, each line indented to sit under the marker
+fn indented(text: &str, width: usize, style: Style) -> Vec<Line<'static>> {
+    text.lines()
+        .flat_map(|line| textwrap::wrap(line, width.max(10)))
+        .map(|line| Line::from(Span::styled(format!("  {}", line), style)))
+        .collect()
+}
+
+pub fn render_linear_posts(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let area = with_keys_line(frame, app, area);
+    let width = (area.width as usize).saturating_sub(2);
+
+    if let Some(error) = &app.posts_state.error {
+        let error = Paragraph::new(format!("Error: {}", error))
+            .style(Style::default().fg(theme.error))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(error, area);
+        return;
+    }
+    if app.posts_state.posts.is_empty() {
+        let message = if app.posts_state.loading {
+            "Loading posts"
+        } else {
+            "No posts"
+        };
+        frame.render_widget(Paragraph::new(message), area);
+        return;
+    }
+
+    let posts_state = &app.posts_state;
+    let selected = posts_state
+        .list_state
+        .selected()
+        .and_then(|list_index| posts_state.list_index_to_post_index(list_index));
+    let total = posts_state.posts.len();
+    // Same leading items as the normal feed, so list indices line up
+    let mut items: Vec<ListItem> = (0..posts_state.items_before_posts())
+        .map(|_| ListItem::new("  Loading new posts"))
+        .collect();
+    items.extend(posts_state.posts.iter().enumerate().map(|(index, post)| {
+        let is_selected = selected == Some(index);
+        let style = item_style(&theme, is_selected);
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{}Post {} of {}, by {}, {}",
+                marker(is_selected),
+                index + 1,
+                total,
+                post.author_username,
+                post_time(post, app.timestamp_style(), app.exact_time_post)
+            ),
+            style,
+        ))];
+        if post.is_deleted {
+            lines.push(Line::from(Span::styled("  Deleted", style)));
+        } else {
+            lines.extend(indented(&post.content, width, style));
+        }
+        let mut stats = format!(
+            "  {} up, {} down, {} {}",
+            post.upvotes,
+            post.downvotes,
+            post.reply_count,
+            if post.reply_count == 1 { "reply" } else { "replies" }
+        );
+        if let Some(vote) = &post.user_vote {
+            stats.push_str(&format!(", you voted {}", vote));
+        }
+        lines.push(Line::from(Span::styled(stats, style)));
+        if let Some(hidden) = posts_state.similar.get(&post.id) {
+            lines.push(Line::from(Span::styled(
+                format!("  {} similar posts hidden, press e to show them", hidden.len()),
+                style,
+            )));
+        }
+        lines.push(Line::from(""));
+        ListItem::new(Text::from(lines))
+    }));
+
+    frame.render_stateful_widget(List::new(items), area, &mut app.posts_state.list_state);
+}
+
+pub fn render_linear_dms(frame: &mut Frame, app: &mut App, full_area: Rect) {
+    let theme = get_theme_colors(app);
+    let area = with_keys_line(frame, app, full_area);
+    let width = (area.width as usize).saturating_sub(2);
+    let dms = &app.dms_state;
+
+    let mut lines = vec![Line::from("Conversations:")];
+    if dms.conversations.is_empty() {
+        let message = if dms.loading { "  Loading" } else { "  None yet" };
+        lines.push(Line::from(message));
+    }
+    for (index, convo) in dms.conversations.iter().enumerate() {
+        let is_selected = dms.selected_conversation_index == Some(index);
+        let mut text = format!("{}{}", marker(is_selected), convo.other_username);
+        if convo.pinned {
+            text.push_str(", pinned");
+        }
+        if convo.unread_count > 0 {
+            text.push_str(&format!(", {} unread", convo.unread_count));
+        }
+        lines.push(Line::from(Span::styled(text, item_style(&theme, is_selected))));
+    }
+
+    let selected = dms
+        .selected_conversation_index
+        .and_then(|index| dms.conversations.get(index));
+    if let Some(convo) = selected {
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Messages with {}:", convo.other_username)));
+        let outgoing: Vec<_> = dms
+            .outbox
+            .iter()
+            .filter(|m| m.to_user_id == convo.other_user_id)
+            .collect();
+        if dms.messages.is_empty() && outgoing.is_empty() {
+            lines.push(Line::from("  None yet"));
+        }
+        let text_style = Style::default().fg(theme.text);
+        for message in &dms.messages {
+            lines.extend(indented(
+                &format!(
+                    "{}, {}: {}",
+                    message.from_username,
+                    format_timestamp(&message.created_at),
+                    message.content
+                ),
+                width,
+                text_style,
+            ));
+            if let Some(attachment) = &message.attachment {
+                lines.push(Line::from(format!(
+                    "  Attached file {}, {} lines",
+                    attachment.filename,
+                    attachment.content.lines().count()
+                )));
+                if dms.expanded_attachments.contains(&message.id) {
+                    for code_line in attachment.content.lines() {
+                        lines.extend(indented(code_line, width, text_style));
+                    }
+                }
+            }
+        }
+        for message in outgoing {
+            let status = match message.state {
+                DeliveryState::Pending => "sending".to_string(),
+                DeliveryState::Failed => format!(
+                    "not sent, press r to retry{}",
+                    message
+                        .error
+                        .as_ref()
+                        .map(|e| format!(" ({})", e))
+                        .unwrap_or_default()
+                ),
+            };
+            lines.extend(indented(
+                &format!("You, {}: {}", status, message.content),
+                width,
+                text_style,
+            ));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "Your message: {}",
+            app.get_dm_message_content()
+        )));
+    }
+    if let Some(error) = &dms.error {
+        lines.push(Line::from(Span::styled(
+            format!("Error: {}", error),
+            Style::default().fg(theme.error),
+        )));
+    }
+
+    // Keep the end (newest messages and the input) in view
+    let overflow = (lines.len() as u16).saturating_sub(area.height);
+    let view = Paragraph::new(lines).scroll((overflow, 0));
+    frame.render_widget(view, area);
+
+    if app.dms_state.show_new_conversation_modal {
+        render_new_conversation_modal(frame, app, full_area);
+    }
+}
@@ -2,11 +2,13 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use crate::app::App;
+use crate::text_wrapper::take_width;
+use super::super::formatting::{format_composer_lines, format_timestamp, relative_timestamp};
 use super::super::theme::get_theme_colors;
 use super::utils::centered_rect;
 
@@ -19,25 +21,35 @@ pub fn render_unified_composer_modal(frame: &mut Frame, app: &mut App, area: Rec
     // Determine modal configuration based on mode
     let (title, has_context, context_lines, max_chars, instructions) =
         match &app.composer_state.mode {
-            Some(ComposerMode::NewPost) => (
-                "New Post",
-                false,
-                vec![],
-                280,
-                "✨ Type to compose | Enter: Submit | Esc: Cancel ✨",
-            ),
+            Some(ComposerMode::NewPost) => {
+                let anonymous_allowed = app
+                    .instance_features
+                    .as_ref()
+                    .is_some_and(|features| features.anonymous_posting);
+                (
+                    if app.composer_state.anonymous {
+                        "New Anonymous Post"
+                    } else {
+                        "New Post"
+                    },
+                    false,
+                    vec![],
+                    280,
+                    if anonymous_allowed {
+                        "Enter: Submit | Ctrl+T: Anonymous | Ctrl+R: Who can reply | Ctrl+D: Draft | Ctrl+O: Drafts | Esc: Cancel"
+                    } else {
+                        "Enter: Submit | Ctrl+R: Who can reply | Ctrl+D: Save draft | Ctrl+O: Drafts | Esc: Cancel"
+                    },
+                )
+            }
             Some(ComposerMode::Reply {
                 parent_author,
                 parent_content,
                 ..
             }) => {
                 let context_width = area.width.saturating_sub(24).min(66) as usize;
-                let truncated_content = if parent_content.chars().count() > context_width {
-                    let truncated: String = parent_content.chars().take(context_width.saturating_sub(3)).collect();
-                    format!("{}...", truncated)
-                } else {
-                    parent_content.clone()
-                };
+                let truncated_content =
+                    crate::text_wrapper::truncate_to_width(parent_content, context_width);
 
                 let lines = vec![
                     Line::from(vec![
@@ -75,6 +87,75 @@ pub fn render_unified_composer_modal(frame: &mut Frame, app: &mut App, area: Rec
             Some(ComposerMode::EditBio) => {
                 ("Edit Bio", false, vec![], 160, "Type to edit | Enter: Submit | Esc: Cancel")
             }
+            Some(ComposerMode::SetStatus) => (
+                "Set Status",
+                false,
+                vec![],
+                100,
+                "Emoji first, \"for 2h\" last to expire | Enter: Save (empty clears) | Esc: Cancel",
+            ),
+            Some(ComposerMode::CrossPost { other_username, .. }) => {
+                let lines = vec![
+                    Line::from(Span::styled(
+                        "Cross-posting a direct message to the feed",
+                        Style::default().fg(theme.text_dim),
+                    )),
+                    Line::from(vec![
+                        Span::styled(
+                            format!("@{}", other_username),
+                            Style::default()
+                                .fg(theme.primary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            " has to approve it before it's published",
+                            Style::default().fg(theme.text_dim),
+                        ),
+                    ]),
+                ];
+                (
+                    "Cross-post to Feed",
+                    true,
+                    lines,
+                    280,
+                    "Enter: Ask for approval | Esc: Cancel",
+                )
+            }
+            Some(ComposerMode::NewJob(form)) => {
+                // The answers so far, so the poster can see what they've said
+                let mut answered = Vec::new();
+                if !form.title.is_empty() {
+                    answered.push(form.title.clone());
+                }
+                if !form.company.is_empty() {
+                    answered.push(form.company.clone());
+                }
+                if let Some(work_mode) = form.work_mode {
+                    answered.push(work_mode.label().to_string());
+                }
+                let summary = if answered.is_empty() {
+                    "Title, company, remote or onsite, link, then a description".to_string()
+                } else {
+                    answered.join(" · ")
+                };
+                let mut lines = vec![Line::from(Span::styled(
+                    format!("💼 {}", summary),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ))];
+                if !form.url.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        form.url.clone(),
+                        Style::default().fg(theme.text_dim),
+                    )));
+                }
+                (
+                    "New Job",
+                    true,
+                    lines,
+                    form.step.max_chars(),
+                    "Enter: Next | Esc: Cancel",
+                )
+            }
             None => return, // Should never happen
         };
 
@@ -138,19 +219,37 @@ pub fn render_unified_composer_modal(frame: &mut Frame, app: &mut App, area: Rec
     let content_area = modal_chunks[chunk_idx];
 
     // Create a block for the content area
+    let content_title = match &app.composer_state.mode {
+        Some(ComposerMode::NewJob(form)) => form.step.prompt(),
+        _ => "Content",
+    };
     let content_block = Block::default()
         .borders(Borders::ALL)
-        .title("Content")
+        .title(content_title)
         .border_style(Style::default().fg(theme.primary));
     
     let inner_content_area = content_block.inner(content_area);
     frame.render_widget(content_block, content_area);
 
-    // Render TextArea directly - styling should be set when composer opens, not during render
-    frame.render_widget(&app.composer_state.textarea, inner_content_area);
+    // Draw the TextArea's contents ourselves so hashtags, mentions and URLs
+    // are highlighted as they're typed. The TextArea still owns editing.
+    // Long lines are wrapped here rather than in the text, leaving a column
+    // for the cursor.
+    let textarea = &app.composer_state.textarea;
+    let (lines, cursor) = crate::text_wrapper::wrap_for_display(
+        textarea.lines(),
+        textarea.cursor(),
+        (inner_content_area.width as usize).saturating_sub(1),
+    );
+    let visible_rows = inner_content_area.height as usize;
+    let scroll = (cursor.0 + 1).saturating_sub(visible_rows) as u16;
+    let content = Paragraph::new(format_composer_lines(&lines, cursor, &theme))
+        .style(Style::default().bg(theme.background))
+        .scroll((scroll, 0));
+    frame.render_widget(content, inner_content_area);
     chunk_idx += 1;
 
-    // Character counter
+    // Character counter, measured after emoji shortcodes expand
     let char_count = app.composer_state.char_count();
     let counter_style = if char_count >= max_chars {
         Style::default()
@@ -162,7 +261,55 @@ pub fn render_unified_composer_modal(frame: &mut Frame, app: &mut App, area: Rec
         Style::default().fg(theme.success)
     };
 
-    let counter_text = format!("{}/{} characters", char_count, max_chars);
+    let mut counter_text = if char_count > max_chars && app.can_post_as_gist() {
+        format!(
+            "{}/{} characters ({} over limit, Ctrl+G: post as a gist)",
+            char_count,
+            max_chars,
+            char_count - max_chars
+        )
+    } else if char_count > max_chars {
+        format!(
+            "{}/{} characters ({} over limit)",
+            char_count,
+            max_chars,
+            char_count - max_chars
+        )
+    } else {
+        format!(
+            "{}/{} characters ({} remaining)",
+            char_count,
+            max_chars,
+            max_chars - char_count
+        )
+    };
+    // Instance quota, for anything that creates a new post
+    if matches!(
+        app.composer_state.mode,
+        Some(ComposerMode::NewPost)
+            | Some(ComposerMode::Reply { .. })
+            | Some(ComposerMode::CrossPost { .. })
+            | Some(ComposerMode::NewJob(_))
+    ) {
+        if let Some(features) = &app.instance_features {
+            if let (Some(limit), Some(remaining)) =
+                (features.max_posts_per_day, features.posts_remaining_today)
+            {
+                counter_text.push_str(&format!(" · {}/{} posts left today", remaining, limit));
+            }
+        }
+    }
+    if app.composer_state.anonymous {
+        counter_text.push_str(" · posting as anon");
+    }
+    if app.composer_state.mode == Some(ComposerMode::NewPost)
+        && app.composer_state.reply_policy != fido_types::ReplyPolicy::Everyone
+    {
+        counter_text.push_str(&format!(
+            " · replies: {}",
+            app.composer_state.reply_policy.label()
+        ));
+    }
     let counter = Paragraph::new(counter_text)
         .style(counter_style)
         .alignment(Alignment::Center)
@@ -185,3 +332,153 @@ pub fn render_unified_composer_modal(frame: &mut Frame, app: &mut App, area: Rec
         );
     frame.render_widget(instructions_widget, modal_chunks[chunk_idx]);
 }
+
+/// Offer back the composer draft saved when fido last crashed
+pub fn render_draft_recovery_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(draft) = &app.recovered_draft else {
+        return;
+    };
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(60, 40, area);
+    frame.render_widget(Clear, modal_area);
+
+    let what = match &draft.mode {
+        crate::app::ComposerMode::NewPost => "a new post".to_string(),
+        crate::app::ComposerMode::Reply { parent_author, .. } => {
+            format!("a reply to @{}", parent_author)
+        }
+        crate::app::ComposerMode::EditPost { .. } => "a post edit".to_string(),
+        crate::app::ComposerMode::EditBio => "your bio".to_string(),
+        crate::app::ComposerMode::SetStatus => "your status".to_string(),
+        crate::app::ComposerMode::CrossPost { other_username, .. } => {
+            format!("a cross-post of your messages with @{}", other_username)
+        }
+        crate::app::ComposerMode::NewJob(_) => "a job post".to_string(),
+    };
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("fido closed unexpectedly while you were writing {}.", what),
+            Style::default().fg(theme.text),
+        )),
+        Line::from(""),
+    ];
+    for line in draft.content.lines().take(4) {
+        content.push(Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(theme.text_dim),
+        )));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled("Y", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+        Span::styled(": Restore draft  ", Style::default().fg(theme.text)),
+        Span::styled("N", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+        Span::styled(": Discard", Style::default().fg(theme.text)),
+    ]));
+
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .title(" Unsent Draft ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
+
+/// Render the server-side drafts list opened from the new-post composer
+pub fn render_drafts_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Drafts ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Draft list
+            Constraint::Length(3), // Footer
+        ])
+        .split(inner);
+
+    let drafts = &app.drafts.drafts;
+    let timestamp_style = app.timestamp_style();
+    let status = if app.drafts.loading {
+        Some(("Loading drafts...", theme.text_dim))
+    } else if let Some(error) = &app.drafts.error {
+        Some((error.as_str(), theme.error))
+    } else if drafts.is_empty() {
+        Some(("No saved drafts. Press Ctrl+D in the composer to save one.", theme.text_dim))
+    } else {
+        None
+    };
+
+    if let Some((message, color)) = status {
+        let message = Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(color));
+        frame.render_widget(message, chunks[0]);
+    } else {
+        let items: Vec<ListItem> = drafts
+            .iter()
+            .map(|draft| {
+                let first_line = draft.content.lines().next().unwrap_or_default();
+                let mut excerpt = take_width(first_line, 60).to_string();
+                if excerpt.len() < first_line.len() || draft.content.lines().nth(1).is_some() {
+                    excerpt.push('…');
+                }
+                let edited = match timestamp_style {
+                    fido_types::TimestampStyle::Absolute => format_timestamp(&draft.updated_at),
+                    fido_types::TimestampStyle::Relative => {
+                        relative_timestamp(&draft.updated_at, chrono::Utc::now())
+                    }
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<18}", edited),
+                        Style::default().fg(theme.text_dim),
+                    ),
+                    Span::styled(excerpt, Style::default().fg(theme.text)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(">> ");
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.drafts.selected.min(drafts.len() - 1)));
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+    }
+
+    let footer = Paragraph::new("↑/↓/j/k: Navigate | Enter: Open | x: Delete | Esc: Back to composer")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    frame.render_widget(footer, chunks[1]);
+}
@@ -0,0 +1,65 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Ask for consent to publish a post quoting our direct messages
+pub fn render_crosspost_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(request) = &app.crossposts.prompt else {
+        return;
+    };
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, modal_area);
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("@{}", request.requester_username),
+                Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " wants to post part of your conversation to the feed:",
+                Style::default().fg(theme.text),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    for line in request.content.lines().take(8) {
+        content.push(Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(theme.text_dim).add_modifier(Modifier::ITALIC),
+        )));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled("Y", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+        Span::styled(": Approve and publish  ", Style::default().fg(theme.text)),
+        Span::styled("N", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+        Span::styled(": Decline  ", Style::default().fg(theme.text)),
+        Span::styled("Esc", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        Span::styled(": Later", Style::default().fg(theme.text)),
+    ]));
+
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(" Cross-post Request ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
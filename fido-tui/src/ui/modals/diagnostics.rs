@@ -0,0 +1,132 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Connection diagnostics for when nothing loads
+pub fn render_diagnostics_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let diagnostics = &app.diagnostics;
+
+    let modal_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, modal_area);
+
+    let row = |label: &str, value: String, color: Color| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<14}", label),
+                Style::default()
+                    .fg(theme.text_dim)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+
+    let server = match &app.active_profile {
+        Some(profile) => format!("{} ({})", app.api_client.base_url(), profile),
+        None => app.api_client.base_url().to_string(),
+    };
+
+    let version = match &diagnostics.server_version {
+        Some(version) => (
+            format!("{} (client {})", version, crate::app::CLIENT_VERSION),
+            theme.text,
+        ),
+        None if diagnostics.error.is_some() => ("unknown".to_string(), theme.text_dim),
+        None => ("checking…".to_string(), theme.text_dim),
+    };
+
+    let latency = match (&diagnostics.error, diagnostics.latencies.back()) {
+        (Some(error), _) => (format!("ping failed: {}", error), theme.error),
+        (None, Some(last)) => {
+            let average = diagnostics.average_latency().unwrap_or(*last);
+            (
+                format!(
+                    "{} ms (avg {} ms over {} pings)",
+                    last.as_millis(),
+                    average.as_millis(),
+                    diagnostics.latencies.len()
+                ),
+                if last.as_millis() > 1000 { theme.warning } else { theme.success },
+            )
+        }
+        (None, None) => ("measuring…".to_string(), theme.text_dim),
+    };
+
+    let connection = if app.api_client.is_offline() {
+        ("Offline, retrying in the background".to_string(), theme.error)
+    } else {
+        ("Online".to_string(), theme.success)
+    };
+
+    let rate_limit = match app.api_client.rate_limit_remaining() {
+        Some(remaining) => (
+            format!("Backing off, {}s left", remaining.as_secs().max(1)),
+            theme.warning,
+        ),
+        None => ("Not limited".to_string(), theme.success),
+    };
+
+    let session = match &app.auth_state.current_user {
+        Some(user) => (format!("Signed in as @{}", user.username), theme.text),
+        None => ("Not signed in".to_string(), theme.text_dim),
+    };
+
+    let content = vec![
+        Line::from(""),
+        row("Server", server, theme.text),
+        row("Version", version.0, version.1),
+        row("Latency", latency.0, latency.1),
+        row("Connection", connection.0, connection.1),
+        row("Rate limit", rate_limit.0, rate_limit.1),
+        row("Session", session.0, session.1),
+        row(
+            "WebSocket",
+            "Not connected (updates are polled)".to_string(),
+            theme.text_dim,
+        ),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "  r",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": Ping now  ", Style::default().fg(theme.text)),
+            Span::styled(
+                "Esc",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": Close", Style::default().fg(theme.text)),
+        ]),
+    ];
+
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(" Diagnostics ")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
@@ -0,0 +1,174 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::text_wrapper::take_width;
+use fido_types::{Post, UnreadFrom};
+use super::super::formatting::format_timestamp;
+use super::super::theme::{get_theme_colors, ThemeColors};
+use super::utils::centered_rect;
+
+/// Longest post excerpt shown per digest entry, in terminal columns
+const EXCERPT_WIDTH: usize = 60;
+
+/// Render the "While you were away" digest shown after login
+pub fn render_digest_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(digest) = &app.digest else {
+        return;
+    };
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, modal_area);
+
+    let mut content = vec![
+        Line::from(Span::styled(
+            format!(
+                "{} – {}",
+                format_timestamp(&digest.period_start),
+                format_timestamp(&digest.period_end)
+            ),
+            Style::default().fg(theme.text_dim),
+        )),
+        Line::from(""),
+    ];
+
+    push_top_posts(&mut content, &theme, "Top posts", &digest.top_posts);
+    push_unread_messages(&mut content, &theme, &digest.unread_messages);
+    content.push(dismiss_hint(&theme, "Dismiss"));
+
+    render_summary(frame, &theme, modal_area, " While You Were Away ", content);
+}
+
+/// Render the catch-up screen shown after logging in from a long absence
+pub fn render_catchup_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(catchup) = &app.catchup else {
+        return;
+    };
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(80, 80, area);
+    frame.render_widget(Clear, modal_area);
+
+    let heading = Style::default().fg(theme.primary).add_modifier(Modifier::BOLD);
+    let mut content = vec![
+        Line::from(Span::styled(
+            format!("Since you were last here, {}", format_timestamp(&catchup.since)),
+            Style::default().fg(theme.text_dim),
+        )),
+        Line::from(""),
+    ];
+
+    if !catchup.new_followers.is_empty() {
+        content.push(Line::from(Span::styled(
+            format!("New followers ({})", catchup.new_followers.len()),
+            heading,
+        )));
+        let names: Vec<String> = catchup
+            .new_followers
+            .iter()
+            .map(|name| format!("@{}", name))
+            .collect();
+        content.push(Line::from(Span::styled(
+            names.join(", "),
+            Style::default().fg(theme.accent),
+        )));
+        content.push(Line::from(""));
+    }
+
+    push_top_posts(&mut content, &theme, "Top posts from followed hashtags", &catchup.top_posts);
+    push_unread_messages(&mut content, &theme, &catchup.unread_messages);
+    content.push(dismiss_hint(&theme, "Continue to feed"));
+
+    render_summary(frame, &theme, modal_area, " While You Were Away ", content);
+}
+
+/// A heading plus one line per post: score, author and an excerpt
+fn push_top_posts(content: &mut Vec<Line<'static>>, theme: &ThemeColors, title: &str, posts: &[Post]) {
+    if posts.is_empty() {
+        return;
+    }
+    content.push(Line::from(Span::styled(
+        title.to_string(),
+        Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+    )));
+    for post in posts {
+        let first_line = post.content.lines().next().unwrap_or_default();
+        let mut excerpt = take_width(first_line, EXCERPT_WIDTH).to_string();
+        if excerpt.len() < first_line.len() || post.content.lines().nth(1).is_some() {
+            excerpt.push('…');
+        }
+        content.push(Line::from(vec![
+            Span::styled(
+                format!("{:+} ", post.upvotes - post.downvotes),
+                Style::default().fg(theme.success),
+            ),
+            Span::styled(
+                format!("@{}: ", post.author_username),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(excerpt, Style::default().fg(theme.text)),
+        ]));
+    }
+    content.push(Line::from(""));
+}
+
+fn push_unread_messages(content: &mut Vec<Line<'static>>, theme: &ThemeColors, unread: &[UnreadFrom]) {
+    if unread.is_empty() {
+        return;
+    }
+    content.push(Line::from(Span::styled(
+        "Unread messages",
+        Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+    )));
+    for from in unread {
+        content.push(Line::from(vec![
+            Span::styled(format!("@{}", from.username), Style::default().fg(theme.accent)),
+            Span::styled(
+                format!(
+                    " — {} unread {}",
+                    from.count,
+                    if from.count == 1 { "message" } else { "messages" }
+                ),
+                Style::default().fg(theme.text),
+            ),
+        ]));
+    }
+    content.push(Line::from(""));
+}
+
+fn dismiss_hint(theme: &ThemeColors, action: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            "Enter/Esc",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(": {}", action), Style::default().fg(theme.text)),
+    ])
+}
+
+fn render_summary(
+    frame: &mut Frame,
+    theme: &ThemeColors,
+    area: Rect,
+    title: &'static str,
+    content: Vec<Line<'static>>,
+) {
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, area);
+}
@@ -0,0 +1,128 @@
+use chrono::Local;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::formatting::{rsvp_counts, rsvp_label};
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Render upcoming events as a day-by-day agenda
+pub fn render_events_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(70, 70, area);
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Upcoming Events ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Agenda
+            Constraint::Length(3), // Footer
+        ])
+        .split(inner);
+
+    let events = &app.events.events;
+    let status = if app.events.loading {
+        Some(("Loading events...", theme.text_dim))
+    } else if let Some(error) = &app.events.error {
+        Some((error.as_str(), theme.error))
+    } else if events.is_empty() {
+        Some((
+            "Nothing coming up. Post one with /event Title | YYYY-MM-DD HH:MM | Location",
+            theme.text_dim,
+        ))
+    } else {
+        None
+    };
+
+    if let Some((message, color)) = status {
+        let message = Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(color));
+        frame.render_widget(message, chunks[0]);
+    } else {
+        let dim = Style::default().fg(theme.text_dim);
+        let mut lines: Vec<Line> = Vec::new();
+        let mut selected_line = 0;
+        let mut current_day = None;
+
+        for (index, post) in events.iter().enumerate() {
+            let Some(fido_types::PostKind::Event(event)) = &post.kind else {
+                continue;
+            };
+            let starts_at = event.starts_at.with_timezone(&Local);
+            let day = starts_at.date_naive();
+            if current_day != Some(day) {
+                if current_day.is_some() {
+                    lines.push(Line::from(""));
+                }
+                current_day = Some(day);
+                lines.push(Line::from(Span::styled(
+                    starts_at.format("%A %-d %B").to_string(),
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                )));
+            }
+
+            let is_selected = index == app.events.selected;
+            if is_selected {
+                selected_line = lines.len();
+            }
+            let row_style = if is_selected {
+                Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let mut title_line = vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, row_style.fg(theme.accent)),
+                Span::styled(starts_at.format("%H:%M  ").to_string(), row_style.fg(theme.text_dim)),
+                Span::styled(event.title.clone(), row_style.fg(theme.text)),
+            ];
+            if let Some(rsvp) = event.my_rsvp {
+                title_line.push(Span::styled(
+                    format!("  ✓ {}", rsvp_label(rsvp)),
+                    row_style.fg(theme.success),
+                ));
+            }
+            lines.push(Line::from(title_line));
+            lines.push(Line::from(vec![
+                Span::raw("         "),
+                Span::styled(event.location.clone(), Style::default().fg(theme.text)),
+                Span::styled(
+                    format!(" · @{} · {}", post.author_username, rsvp_counts(event)),
+                    dim,
+                ),
+            ]));
+        }
+
+        // Keep the selected event and its details on screen
+        let height = chunks[0].height as usize;
+        let scroll = (selected_line + 2).saturating_sub(height);
+        let agenda = Paragraph::new(lines).scroll((scroll as u16, 0));
+        frame.render_widget(agenda, chunks[0]);
+    }
+
+    let footer = Paragraph::new("↑/↓/j/k: Navigate | g: Going | i: Interested | Enter: Open | Esc: Close")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    frame.render_widget(footer, chunks[1]);
+}
@@ -51,6 +51,9 @@ pub fn render_filter_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         crate::app::FilterTab::All => 0,
         crate::app::FilterTab::Hashtags => 1,
         crate::app::FilterTab::Users => 2,
+        crate::app::FilterTab::Reviews => 3,
+        crate::app::FilterTab::Jobs => 4,
+        crate::app::FilterTab::Later => 5,
     };
 
     let mut tab_spans = Vec::new();
@@ -77,6 +80,30 @@ pub fn render_filter_modal(frame: &mut Frame, app: &mut App, area: Rect) {
     } else {
         tab_spans.push(Span::styled("  Users  ", Style::default().fg(theme.text_dim)));
     }
+    tab_spans.push(Span::raw(" | "));
+
+    // Review requests tab
+    if selected_tab_idx == 3 {
+        tab_spans.push(Span::styled(" [Reviews] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)));
+    } else {
+        tab_spans.push(Span::styled("  Reviews  ", Style::default().fg(theme.text_dim)));
+    }
+    tab_spans.push(Span::raw(" | "));
+
+    // Job posts tab
+    if selected_tab_idx == 4 {
+        tab_spans.push(Span::styled(" [Jobs] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)));
+    } else {
+        tab_spans.push(Span::styled("  Jobs  ", Style::default().fg(theme.text_dim)));
+    }
+    tab_spans.push(Span::raw(" | "));
+
+    // Read-later queue tab
+    if selected_tab_idx == 5 {
+        tab_spans.push(Span::styled(" [Later] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)));
+    } else {
+        tab_spans.push(Span::styled("  Later  ", Style::default().fg(theme.text_dim)));
+    }
 
     let tab_widget = Paragraph::new(Line::from(tab_spans))
         .alignment(Alignment::Center)
@@ -144,6 +171,71 @@ pub fn render_filter_modal(frame: &mut Frame, app: &mut App, area: Rect) {
                     )),
                 ]
             }
+            crate::app::FilterTab::Reviews => {
+                vec![
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        "Open code review requests",
+                        Style::default().fg(theme.text),
+                    )),
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        "In the languages you follow as hashtags, or all of them",
+                        Style::default().fg(theme.text_dim),
+                    )),
+                    Line::from(Span::styled(
+                        "Ask for one with /review URL | language in a new post",
+                        Style::default().fg(theme.text_dim),
+                    )),
+                ]
+            }
+            crate::app::FilterTab::Jobs => {
+                let mut lines = vec![Line::from("")];
+                for (i, work_mode) in crate::app::JOB_FILTER_CHOICES.iter().enumerate() {
+                    let is_selected = i == app.posts_state.filter_modal_state.selected_index;
+                    let prefix = if is_selected { "▶ " } else { "  " };
+                    let style = if is_selected {
+                        Style::default()
+                            .fg(theme.success)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.text)
+                    };
+                    let label = match work_mode {
+                        Some(work_mode) => format!("{} jobs", work_mode.label()),
+                        None => "All jobs".to_string(),
+                    };
+                    lines.push(Line::from(Span::styled(format!("{}{}", prefix, label), style)));
+                }
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    "Post one by typing /job alone in a new post",
+                    Style::default().fg(theme.text_dim),
+                )));
+                lines
+            }
+            crate::app::FilterTab::Later => {
+                let queue = &app.posts_state.read_later;
+                let back = queue.iter().filter(|snoozed| snoozed.resurfaced).count();
+                let summary = match queue.len() {
+                    0 => "Nothing snoozed".to_string(),
+                    1 => "1 snoozed post".to_string(),
+                    count => format!("{} snoozed posts", count),
+                };
+                vec![
+                    Line::from(""),
+                    Line::from(Span::styled(summary, Style::default().fg(theme.text))),
+                    Line::from(Span::styled(
+                        format!("{} back already", back),
+                        Style::default().fg(theme.text_dim),
+                    )),
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        "Snooze a post with z in the feed; z here puts it back",
+                        Style::default().fg(theme.text_dim),
+                    )),
+                ]
+            }
             crate::app::FilterTab::Hashtags => {
                 // Check if in add hashtag input mode
                 if app.posts_state.filter_modal_state.show_add_hashtag_input {
@@ -154,10 +246,16 @@ pub fn render_filter_modal(frame: &mut Frame, app: &mut App, area: Rect) {
                             Style::default().add_modifier(Modifier::BOLD),
                         )),
                         Line::from(""),
-                        Line::from(Span::styled(
-                            format!("#{}", app.posts_state.filter_modal_state.add_hashtag_input),
-                            Style::default().fg(theme.primary),
-                        )),
+                        Line::from(
+                            std::iter::once(Span::styled("#", Style::default().fg(theme.primary)))
+                                .chain(
+                                    app.posts_state
+                                        .filter_modal_state
+                                        .add_hashtag_input
+                                        .spans(Style::default().fg(theme.primary)),
+                                )
+                                .collect::<Vec<_>>(),
+                        ),
                         Line::from(""),
                         Line::from(Span::styled(
                             "Enter: Follow | Esc: Cancel",
@@ -335,6 +433,9 @@ pub fn render_filter_modal(frame: &mut Frame, app: &mut App, area: Rect) {
     } else {
         match app.posts_state.filter_modal_state.selected_tab {
             crate::app::FilterTab::All => "Enter: Show All Posts | Esc: Cancel",
+            crate::app::FilterTab::Reviews => "Enter: Show Review Requests | Tab: Switch | Esc: Cancel",
+            crate::app::FilterTab::Jobs => "↑/↓/j/k: Navigate | Enter: Show Jobs | Tab: Switch | Esc: Cancel",
+            crate::app::FilterTab::Later => "Enter: Show Snoozed Posts | Tab: Switch | Esc: Cancel",
             crate::app::FilterTab::Hashtags => {
                 "↑/↓/j/k: Navigate | Space: Toggle | Enter: Apply | X: Unfollow | Tab: Switch | Esc: Cancel"
             }
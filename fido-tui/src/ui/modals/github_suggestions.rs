@@ -0,0 +1,78 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Names listed before "and N more"
+const MAX_LISTED: usize = 8;
+
+/// Offer to follow the people the user follows on GitHub who are on Fido
+pub fn render_github_suggestions(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(suggestions) = &app.github_suggestions else {
+        return;
+    };
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(60, 60, area);
+    frame.render_widget(Clear, modal_area);
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "{} {} you follow on GitHub {} on Fido:",
+                suggestions.len(),
+                if suggestions.len() == 1 { "person" } else { "people" },
+                if suggestions.len() == 1 { "is" } else { "are" }
+            ),
+            Style::default().fg(theme.text),
+        )),
+        Line::from(""),
+    ];
+    for suggestion in suggestions.iter().take(MAX_LISTED) {
+        let mut line = vec![Span::styled(
+            format!("@{}", suggestion.username),
+            Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+        )];
+        if !suggestion.github_login.eq_ignore_ascii_case(&suggestion.username) {
+            line.push(Span::styled(
+                format!("  (GitHub: {})", suggestion.github_login),
+                Style::default().fg(theme.text_dim),
+            ));
+        }
+        content.push(Line::from(line));
+    }
+    if suggestions.len() > MAX_LISTED {
+        content.push(Line::from(Span::styled(
+            format!("and {} more", suggestions.len() - MAX_LISTED),
+            Style::default().fg(theme.text_dim),
+        )));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+        Span::styled(": Follow all  ", Style::default().fg(theme.text)),
+        Span::styled("Esc", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        Span::styled(": Not now", Style::default().fg(theme.text)),
+    ]));
+
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(" Find People from GitHub ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
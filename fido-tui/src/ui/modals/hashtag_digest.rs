@@ -0,0 +1,152 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, HashtagDigestRow};
+use super::super::formatting::{one_line_preview, post_author_label};
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Render the Digest section: each followed hashtag with its weekly
+/// changelog summary and top posts, or a note that it's turned off
+pub fn render_hashtag_digest_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(70, 75, area);
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Digest: Your Hashtags This Week ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Changelogs
+            Constraint::Length(3), // Footer
+        ])
+        .split(inner);
+
+    let digest = &app.hashtag_digest;
+    let status = if digest.loading && digest.entries.is_empty() {
+        Some(("Loading digest...", theme.text_dim))
+    } else if let Some(error) = &digest.error {
+        Some((error.as_str(), theme.error))
+    } else if digest.entries.is_empty() {
+        Some((
+            "Follow hashtags to get a weekly changelog of their top posts here",
+            theme.text_dim,
+        ))
+    } else {
+        None
+    };
+
+    if let Some((message, color)) = status {
+        let message = Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(color));
+        frame.render_widget(message, chunks[0]);
+    } else {
+        let dim = Style::default().fg(theme.text_dim);
+        let width = (chunks[0].width as usize).saturating_sub(6);
+        let selected = digest.selected_row();
+        let mut lines: Vec<Line> = Vec::new();
+        let mut selected_line = 0;
+
+        for row in digest.rows() {
+            let is_selected = Some(row) == selected;
+            if is_selected {
+                selected_line = lines.len();
+            }
+            let row_style = if is_selected {
+                Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let marker = Span::styled(if is_selected { "▶ " } else { "  " }, row_style.fg(theme.accent));
+
+            match row {
+                HashtagDigestRow::Hashtag(index) => {
+                    let entry = &digest.entries[index];
+                    if index > 0 {
+                        lines.push(Line::from(""));
+                    }
+                    let note = match (&entry.changelog, entry.enabled) {
+                        (_, false) => "off".to_string(),
+                        (Some(changelog), true) => format!(
+                            "{} – {}",
+                            changelog.period_start.format("%b %-d"),
+                            changelog.period_end.format("%b %-d")
+                        ),
+                        (None, true) => "first changelog comes at the end of the week".to_string(),
+                    };
+                    lines.push(Line::from(vec![
+                        marker,
+                        Span::styled(
+                            format!("#{}", entry.hashtag),
+                            row_style.fg(theme.accent).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(format!("  {}", note), row_style.fg(theme.text_dim)),
+                    ]));
+                    if let Some(changelog) = entry.changelog.as_ref().filter(|_| entry.enabled) {
+                        lines.push(Line::from(vec![
+                            Span::raw("    "),
+                            Span::styled(changelog.summary.clone(), Style::default().fg(theme.text)),
+                        ]));
+                    }
+                }
+                HashtagDigestRow::Post(index, post_index) => {
+                    let Some(post) = digest.entries[index]
+                        .changelog
+                        .as_ref()
+                        .and_then(|changelog| changelog.top_posts.get(post_index))
+                    else {
+                        continue;
+                    };
+                    lines.push(Line::from(vec![
+                        marker,
+                        Span::styled(format!("{:>2}. ", post_index + 1), row_style.fg(theme.text_dim)),
+                        Span::styled(post_author_label(post), row_style.fg(theme.primary)),
+                        Span::styled(
+                            format!("  ↑ {}  💬 {}", post.upvotes, post.reply_count),
+                            row_style.fg(theme.text_dim),
+                        ),
+                    ]));
+                    lines.push(Line::from(vec![
+                        Span::raw("      "),
+                        Span::styled(one_line_preview(post, width), Style::default().fg(theme.text)),
+                    ]));
+                }
+            }
+        }
+        if digest.loading {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("  Refreshing...", dim)));
+        }
+
+        // Keep the selected line and the preview under it on screen
+        let height = chunks[0].height as usize;
+        let scroll = (selected_line + 2).saturating_sub(height);
+        let changelogs = Paragraph::new(lines).scroll((scroll as u16, 0));
+        frame.render_widget(changelogs, chunks[0]);
+    }
+
+    let footer = Paragraph::new("↑/↓/j/k: Navigate | Enter: Open post | Space: Hashtag on/off | Esc: Close")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    frame.render_widget(footer, chunks[1]);
+}
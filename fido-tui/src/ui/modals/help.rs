@@ -116,12 +116,22 @@ pub fn get_shortcuts_for_context(
     // Global shortcuts (always shown)
     shortcuts.push((
         "Global",
-        vec![("q / Esc", "Quit application"), ("?", "Toggle this help")],
+        vec![
+            ("q / Esc", "Quit application"),
+            ("?", "Toggle this help"),
+            ("Ctrl+Shift+S", "Switch server profile"),
+            ("Ctrl+L", "View logs"),
+            ("Ctrl+H", "Reading history: posts you've opened, searchable"),
+            ("Ctrl+Shift+D", "Connection diagnostics"),
+        ],
     ));
 
     // Add logout for main screen
     if matches!(app.current_screen, crate::app::Screen::Main) {
-        shortcuts.push(("Account", vec![("Shift+L", "Logout")]));
+        shortcuts.push((
+            "Account",
+            vec![("Shift+A", "Switch account"), ("Shift+L", "Logout")],
+        ));
     }
 
     match app.current_screen {
@@ -145,6 +155,7 @@ pub fn add_auth_shortcuts(shortcuts: &mut Vec<(&'static str, Vec<(&'static str,
             ("↑/k", "Move up"),
             ("↓/j", "Move down"),
             ("Enter", "Login with selected user"),
+            ("1-9", "Continue as a saved account"),
         ],
     ));
 }
@@ -163,6 +174,7 @@ pub fn add_main_screen_shortcuts(
     // Context-specific shortcuts based on current tab
     match app.current_tab {
         crate::app::Tab::Posts => add_posts_tab_shortcuts(app, shortcuts),
+        crate::app::Tab::Channels => add_channels_tab_shortcuts(shortcuts),
         crate::app::Tab::DMs => add_dms_tab_shortcuts(shortcuts),
         crate::app::Tab::Profile => add_profile_tab_shortcuts(app, shortcuts),
         crate::app::Tab::Settings => add_settings_tab_shortcuts(shortcuts),
@@ -209,6 +221,13 @@ pub fn add_post_detail_shortcuts(
                 ("u", "Upvote post/reply"),
                 ("d", "Downvote post/reply"),
                 ("p", "View author profile"),
+                ("o", "Open a link"),
+                ("t", "Translate / show original"),
+                ("T", "Show / hide exact time"),
+                ("v", "Show a diff as typed / colored"),
+                ("S", "Share: copy link or quote, open in browser, send as DM"),
+                ("w", "Watch / unwatch thread for new replies"),
+                ("Tab", "Focus related posts (Enter opens)"),
             ],
         ));
 
@@ -216,10 +235,14 @@ pub fn add_post_detail_shortcuts(
         if let Some(post) = &detail_state.post {
             if let Some(user) = &app.auth_state.current_user {
                 if post.author_id == user.id {
-                    shortcuts.push((
-                        "Post Owner Actions",
-                        vec![("x", "Delete post")],
-                    ));
+                    let mut actions = vec![
+                        ("x", "Delete post"),
+                        ("a", "Accept / clear the selected reply as the answer"),
+                    ];
+                    if matches!(post.kind, Some(fido_types::PostKind::ReviewRequest(_))) {
+                        actions.push(("m", "Close / reopen review request"));
+                    }
+                    shortcuts.push(("Post Owner Actions", actions));
                 }
             }
         }
@@ -257,12 +280,33 @@ pub fn add_posts_feed_shortcuts(
             ("↓/j", "Next post"),
             ("↑/k", "Previous post"),
             ("Space/Enter", "Open post detail"),
+            ("Tab/Shift+Tab", "Step through hashtags, mentions and links"),
+            ("Enter (on a token)", "Filter by hashtag, open profile or link"),
             ("u", "Upvote selected post"),
             ("d", "Downvote selected post"),
             ("n", "New post"),
+            ("Ctrl+D", "Save new post as a draft (in composer)"),
+            ("Ctrl+O", "Open saved drafts (in composer)"),
+            ("Ctrl+T", "Post anonymously, where allowed (in composer)"),
+            ("Ctrl+R", "Choose who can reply to a new post (in composer)"),
+            ("Ctrl+G", "Post text over the limit as a gist with a link (in composer)"),
+            ("/event", "Post an event: /event Title | YYYY-MM-DD HH:MM | Place"),
+            ("/review", "Ask for a code review: /review URL | language"),
+            ("/job", "Post a job, asked for one field at a time"),
+            ("c", "Upcoming events (g: going, i: interested)"),
+            ("H", "This week's top posts and contributors"),
+            ("W", "Digest: weekly changelogs of followed hashtags"),
+            ("z", "Snooze post (30m, 3h, 1d, 1w); back at the top when due"),
             ("f", "Filter posts"),
+            ("1-9", "Switch to a pinned hashtag (again for the global feed)"),
             ("s", "Search users"),
+            ("S", "Share: copy link or quote, open in browser, send as DM"),
+            ("V", "Mark posts (Space), then b bookmark, m mute authors, o open all"),
             ("p", "View author profile"),
+            ("o", "Open a link"),
+            ("T", "Show / hide exact time"),
+            ("v", "Show a diff as typed / colored"),
+            ("e", "Show posts folded as \"+N similar\""),
         ],
     ));
 
@@ -300,15 +344,41 @@ pub fn add_dms_tab_shortcuts(shortcuts: &mut Vec<(&'static str, Vec<(&'static st
             ("↓/j", "Next conversation"),
             ("↑/k", "Previous conversation / New button"),
             ("Enter", "Open conversation / Start new"),
+            ("Shift+P", "Pin / unpin conversation"),
+            ("Ctrl+U", "Mark conversation unread"),
+            ("Ctrl+R", "Remind me later (30m, 3h, 1d; empty clears)"),
+            ("[ / ]", "Pick an older / newer message"),
+            ("Ctrl+X", "Cross-post message (asks the other person)"),
+            ("/attach <path>", "Attach a text or code file (alone: remove it)"),
+            ("Ctrl+E", "Expand / collapse attachment"),
+            ("Ctrl+S", "Save attachment to Downloads"),
+            ("/export [json]", "Save conversation to Downloads (Markdown or JSON)"),
+            ("r", "Retry a message that failed to send"),
             ("Type", "Compose message"),
             ("Enter", "Send message"),
-            ("Esc", "Clear message / Stop typing"),
+            ("Esc", "Stop typing (keeps a draft)"),
             (":emoji:", "Use emoji shortcodes"),
         ],
     ));
 }
 
 /// Add Profile tab shortcuts
+pub fn add_channels_tab_shortcuts(
+    shortcuts: &mut Vec<(&'static str, Vec<(&'static str, &'static str)>)>,
+) {
+    shortcuts.push((
+        "Channels Tab",
+        vec![
+            ("↓/j", "Next channel"),
+            ("↑/k", "Previous channel"),
+            ("Enter", "Open the channel's feed (new posts go into it)"),
+            ("Space", "Join or leave the channel"),
+            ("n", "Create a channel"),
+            ("r", "Refresh channels and unread counts"),
+        ],
+    ));
+}
+
 pub fn add_profile_tab_shortcuts(
     app: &mut App,
     shortcuts: &mut Vec<(&'static str, Vec<(&'static str, &'static str)>)>,
@@ -318,7 +388,9 @@ pub fn add_profile_tab_shortcuts(
         vec![
             ("↓/j", "Next post"),
             ("↑/k", "Previous post"),
+            ("←/→", "Switch between posts, replies and upvoted posts"),
             ("e", "Edit bio"),
+            ("s", "Set or clear status"),
         ],
     ));
 
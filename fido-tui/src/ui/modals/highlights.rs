@@ -0,0 +1,122 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::formatting::{badge_suffix, one_line_preview, post_author_label};
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Render the past week's top posts, then its top contributors
+pub fn render_highlights_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(70, 75, area);
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" This Week's Highlights ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Leaderboard
+            Constraint::Length(3), // Footer
+        ])
+        .split(inner);
+
+    let highlights = &app.highlights;
+    let status = if highlights.loading {
+        Some(("Loading highlights...", theme.text_dim))
+    } else {
+        highlights
+            .error
+            .as_ref()
+            .map(|error| (error.as_str(), theme.error))
+    };
+
+    if let Some((message, color)) = status {
+        let message = Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(color));
+        frame.render_widget(message, chunks[0]);
+    } else {
+        let heading = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD);
+        let dim = Style::default().fg(theme.text_dim);
+        let width = (chunks[0].width as usize).saturating_sub(6);
+        let mut lines: Vec<Line> = vec![Line::from(Span::styled("Top posts", heading))];
+        let mut selected_line = 0;
+
+        if highlights.top_posts.is_empty() {
+            lines.push(Line::from(Span::styled("  No upvoted posts this week yet", dim)));
+        }
+        for (index, post) in highlights.top_posts.iter().enumerate() {
+            let is_selected = index == highlights.selected;
+            if is_selected {
+                selected_line = lines.len();
+            }
+            let row_style = if is_selected {
+                Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, row_style.fg(theme.accent)),
+                Span::styled(format!("{:>2}. ", index + 1), row_style.fg(theme.text_dim)),
+                Span::styled(post_author_label(post), row_style.fg(theme.primary)),
+                Span::styled(
+                    format!("  ↑ {}  ↓ {}  💬 {}", post.upvotes, post.downvotes, post.reply_count),
+                    row_style.fg(theme.text_dim),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("      "),
+                Span::styled(one_line_preview(post, width), Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(contributors) = &highlights.top_contributors {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Top contributors", heading)));
+            if contributors.is_empty() {
+                lines.push(Line::from(Span::styled("  Nobody has earned karma this week yet", dim)));
+            }
+            for (index, contributor) in contributors.iter().enumerate() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:>2}. ", index + 1), dim),
+                    Span::styled(
+                        format!("@{}{}", contributor.username, badge_suffix(&contributor.badges)),
+                        Style::default().fg(theme.primary),
+                    ),
+                    Span::styled(format!("  +{} karma", contributor.karma), Style::default().fg(theme.success)),
+                ]));
+            }
+        }
+
+        // Keep the selected post and its preview on screen
+        let height = chunks[0].height as usize;
+        let scroll = (selected_line + 2).saturating_sub(height);
+        let leaderboard = Paragraph::new(lines).scroll((scroll as u16, 0));
+        frame.render_widget(leaderboard, chunks[0]);
+    }
+
+    let footer = Paragraph::new("↑/↓/j/k: Navigate posts | Enter: Open | Esc: Close")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    frame.render_widget(footer, chunks[1]);
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::formatting::{one_line_preview, post_author_label};
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// When a post was opened: the time today, "Yesterday" with the time, or
+/// the date further back
+fn opened_label(opened_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let opened = opened_at.with_timezone(&Local);
+    let today = now.with_timezone(&Local).date_naive();
+    if opened.date_naive() == today {
+        opened.format("%H:%M").to_string()
+    } else if today.pred_opt() == Some(opened.date_naive()) {
+        opened.format("Yesterday %H:%M").to_string()
+    } else {
+        opened.format("%b %d %H:%M").to_string()
+    }
+}
+
+/// Render the reading history: a search line, then the posts opened in
+/// the thread view that match it, most recent first
+pub fn render_reading_history_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(70, 75, area);
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Reading History ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Search
+            Constraint::Min(0),    // Posts
+            Constraint::Length(3), // Footer
+        ])
+        .split(inner);
+
+    let history = &app.reading_history;
+    let search = Paragraph::new(Line::from(vec![
+        Span::styled("Search: ", Style::default().fg(theme.text_dim)),
+        Span::styled(format!("{}█", history.search), Style::default().fg(theme.text)),
+    ]));
+    frame.render_widget(search, chunks[0]);
+
+    let matches = history.matches();
+    let empty = if history.entries.is_empty() {
+        Some("Posts you open will be listed here")
+    } else if matches.is_empty() {
+        Some("No posts you've read match")
+    } else {
+        None
+    };
+
+    if let Some(message) = empty {
+        let message = Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.text_dim));
+        frame.render_widget(message, chunks[1]);
+    } else {
+        let now = Utc::now();
+        let width = (chunks[1].width as usize).saturating_sub(6);
+        let mut lines: Vec<Line> = Vec::new();
+
+        for (index, entry) in matches.iter().enumerate() {
+            let is_selected = index == history.selected;
+            let row_style = if is_selected {
+                Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, row_style.fg(theme.accent)),
+                Span::styled(
+                    format!("{:<17}", opened_label(entry.opened_at, now)),
+                    row_style.fg(theme.text_dim),
+                ),
+                Span::styled(post_author_label(&entry.post), row_style.fg(theme.primary)),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(one_line_preview(&entry.post, width), Style::default().fg(theme.text)),
+            ]));
+        }
+
+        // Keep the selected post and its preview on screen
+        let height = chunks[1].height as usize;
+        let scroll = (history.selected * 2 + 2).saturating_sub(height);
+        let posts = Paragraph::new(lines).scroll((scroll as u16, 0));
+        frame.render_widget(posts, chunks[1]);
+    }
+
+    let footer = Paragraph::new("Type to search | ↑/↓: Navigate | Enter: Open post | Esc: Close")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    frame.render_widget(footer, chunks[2]);
+}
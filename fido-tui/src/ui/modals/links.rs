@@ -0,0 +1,127 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Render the confirmation shown before a link from a post is opened
+pub fn render_link_preview_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let state = &app.link_preview;
+
+    let modal_area = centered_rect(70, 45, area);
+    frame.render_widget(Clear, modal_area);
+
+    let label = Style::default().fg(theme.text_dim);
+    let mut content = vec![Line::from("")];
+
+    match &state.preview {
+        None => {
+            content.push(Line::from(Span::styled(
+                state.urls.get(state.selected).cloned().unwrap_or_default(),
+                Style::default().fg(theme.text),
+            )));
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled("Checking link…", label)));
+        }
+        Some(preview) => {
+            let domain_color = if preview.blocked {
+                theme.error
+            } else {
+                theme.accent
+            };
+            content.push(Line::from(vec![
+                Span::styled("Domain: ", label),
+                Span::styled(
+                    preview.domain.clone(),
+                    Style::default().fg(domain_color).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled("Opens:", label)));
+            content.push(Line::from(Span::styled(
+                preview.resolved_url.clone(),
+                Style::default().fg(theme.text),
+            )));
+            if preview.expanded {
+                content.push(Line::from(""));
+                content.push(Line::from(vec![
+                    Span::styled("Expanded from ", label),
+                    Span::styled(preview.url.clone(), Style::default().fg(theme.text_dim)),
+                ]));
+            }
+            content.push(Line::from(""));
+            if preview.blocked {
+                content.push(Line::from(Span::styled(
+                    "⚠ This domain is blocked on this server",
+                    Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+                )));
+            } else if state.unchecked {
+                content.push(Line::from(Span::styled(
+                    "⚠ Couldn't check this link with the server",
+                    Style::default().fg(theme.warning),
+                )));
+                if state.confirm_unchecked {
+                    content.push(Line::from(Span::styled(
+                        "Shorteners and the blocklist weren't checked. Open it anyway?",
+                        Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+                    )));
+                }
+            }
+        }
+    }
+
+    content.push(Line::from(""));
+    let mut keys = Vec::new();
+    if state.preview.as_ref().is_some_and(|preview| !preview.blocked) {
+        keys.push(Span::styled(
+            "Enter",
+            Style::default().fg(theme.success).add_modifier(Modifier::BOLD),
+        ));
+        let action = if !state.unchecked {
+            ": Open  "
+        } else if state.confirm_unchecked {
+            ": Open anyway  "
+        } else {
+            ": Continue  "
+        };
+        keys.push(Span::styled(action, Style::default().fg(theme.text)));
+    }
+    if state.urls.len() > 1 {
+        keys.push(Span::styled(
+            "Tab",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+        keys.push(Span::styled(": Next link  ", Style::default().fg(theme.text)));
+    }
+    keys.push(Span::styled(
+        "Esc",
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+    ));
+    keys.push(Span::styled(": Cancel", Style::default().fg(theme.text)));
+    content.push(Line::from(keys));
+
+    let title = if state.urls.len() > 1 {
+        format!(" Open Link ({}/{}) ", state.selected + 1, state.urls.len())
+    } else {
+        " Open Link ".to_string()
+    };
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
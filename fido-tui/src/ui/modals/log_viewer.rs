@@ -0,0 +1,111 @@
+use log::Level;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Render the log viewer over the logging ring buffer
+pub fn render_log_viewer(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let viewer = &app.log_viewer;
+
+    let modal_area = centered_rect(90, 85, area);
+    frame.render_widget(Clear, modal_area);
+
+    let lines: Vec<_> = crate::logging::recent_lines()
+        .into_iter()
+        .filter(|line| viewer.matches(line))
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .title(format!(
+            " Logs ({} lines, level ≤ {}) ",
+            lines.len(),
+            viewer.level
+        ))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(theme.background));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    // Newest lines at the bottom; scrolling moves the window up
+    let height = chunks[0].height as usize;
+    let scroll = viewer.scroll.min(lines.len().saturating_sub(height));
+    let end = lines.len() - scroll;
+    let start = end.saturating_sub(height);
+
+    let rendered: Vec<Line> = lines[start..end]
+        .iter()
+        .map(|line| {
+            let level_color = match line.level {
+                Level::Error => theme.error,
+                Level::Warn => theme.warning,
+                Level::Info => theme.success,
+                Level::Debug | Level::Trace => theme.text_dim,
+            };
+            Line::from(vec![
+                Span::styled(
+                    line.time.format("%H:%M:%S%.3f ").to_string(),
+                    Style::default().fg(theme.text_dim),
+                ),
+                Span::styled(
+                    format!("{:<5} ", line.level),
+                    Style::default().fg(level_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{}: ", line.target), Style::default().fg(theme.secondary)),
+                Span::styled(line.message.clone(), Style::default().fg(theme.text)),
+            ])
+        })
+        .collect();
+
+    let content = if rendered.is_empty() {
+        Paragraph::new(Span::styled(
+            if app.log_config.enabled {
+                "No matching log lines"
+            } else {
+                "Logging is disabled"
+            },
+            Style::default().fg(theme.text_dim),
+        ))
+        .alignment(Alignment::Center)
+    } else {
+        Paragraph::new(rendered)
+    };
+    frame.render_widget(content, chunks[0]);
+
+    let footer = if viewer.searching {
+        Line::from(vec![
+            Span::styled("Search: ", Style::default().fg(theme.accent)),
+            Span::styled(format!("{}█", viewer.search), Style::default().fg(theme.text)),
+            Span::styled("  Enter/Esc: done", Style::default().fg(theme.text_dim)),
+        ])
+    } else {
+        let mut spans = Vec::new();
+        if !viewer.search.is_empty() {
+            spans.push(Span::styled(
+                format!("Search: {}  ", viewer.search),
+                Style::default().fg(theme.accent),
+            ));
+        }
+        spans.push(Span::styled(
+            "/: search  f: level  j/k: scroll  g/G: oldest/newest  Esc: close",
+            Style::default().fg(theme.text_dim),
+        ));
+        Line::from(spans)
+    };
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
@@ -1,15 +1,43 @@
 // Modal rendering modules
 mod utils;
 mod composer;
+mod crossposts;
+mod diagnostics;
+mod digest;
+mod events;
+mod github_suggestions;
+mod hashtag_digest;
+mod highlights;
+mod history;
 mod posts;
+mod reauth;
 mod social;
 mod social_components;
 mod filters;
 mod help;
+mod links;
+mod log_viewer;
+mod servers;
+mod share;
+mod version;
 
 // Re-export all public functions
 pub use composer::*;
+pub use crossposts::*;
+pub use diagnostics::*;
+pub use digest::*;
+pub use events::*;
+pub use github_suggestions::*;
+pub use hashtag_digest::*;
+pub use highlights::*;
+pub use history::*;
 pub use posts::*;
+pub use reauth::*;
 pub use social::*;
 pub use filters::*;
 pub use help::*;
+pub use links::*;
+pub use log_viewer::*;
+pub use servers::*;
+pub use share::*;
+pub use version::*;
@@ -8,10 +8,11 @@ use ratatui::{
 use uuid::Uuid;
 use fido_types::Post;
 
-use crate::app::App;
-use super::super::theme::get_theme_colors;
+use crate::app::{App, TranslationState};
+use super::super::theme::{get_theme_colors, ThemeColors};
 use super::super::formatting::*;
 use super::utils::centered_rect;
+use crate::thread_tree::{self, ThreadTree};
 
 /// Render delete confirmation modal (matches unsaved changes modal style)
 pub fn render_delete_confirmation_modal(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -115,6 +116,14 @@ pub fn render_delete_confirmation_modal(frame: &mut Frame, app: &mut App, area:
 pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
     let theme = get_theme_colors(app);
 
+    let translations = &app.translations;
+    let raw_diff_posts = &app.raw_diff_posts;
+    let github_cards = &app.github_cards;
+    let gists = &app.gists;
+    let timestamp_style = app.timestamp_style();
+    let exact_time_post = app.exact_time_post;
+    let show_karma = app.show_karma();
+
     // Get post detail state
     let detail_state = match &mut app.post_detail_state {
         Some(state) => state,
@@ -182,17 +191,25 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         }
     };
 
-    // Filter replies to only show descendants of the selected post (excluding the root itself)
-    let modal_replies: Vec<Post> = detail_state.replies.iter()
-        .filter(|reply| {
-            // Exclude the root post itself and only include its descendants
-            reply.id != root_post.id && is_descendant_of(reply, &root_post.id, &detail_state.replies)
-        })
-        .cloned()
-        .collect();
+    // Only the descendants of the selected post are shown below it
+    let thread = ThreadTree::new(&root_post, &detail_state.replies);
 
     // modal_area and Clear already rendered above
-    let title_text = format!(" Thread by @{} ({} replies) ", root_post.author_username, modal_replies.len());
+    let watching = detail_state
+        .full_post_modal_id
+        .is_some_and(|id| app.watched_threads.contains_key(&id));
+    // Who may reply is set on the thread's top post
+    let thread_post = detail_state.post.as_ref();
+    let replies_limited =
+        thread_post.is_some_and(|post| post.reply_policy != fido_types::ReplyPolicy::Everyone);
+    let can_reply = thread_post.is_none_or(|post| post.can_reply);
+    let title_text = format!(
+        " Thread by {} ({} replies){}{} ",
+        post_author_label(&root_post),
+        thread.len(),
+        if watching { " · 👁 Watching" } else { "" },
+        if replies_limited { " · 🔒 Limited replies" } else { "" }
+    );
     
     let block = Block::default()
         .title(title_text)
@@ -203,61 +220,67 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
     let inner = block.inner(modal_area);
     frame.render_widget(block, modal_area);
 
-    // Create modal layout
+    // Create modal layout; the related section only takes space once there is something to show
+    let related_height = if detail_state.related.is_empty() {
+        0
+    } else {
+        detail_state.related.len() as u16 + 2
+    };
     let modal_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(0),    // Content (root post + nested replies)
-            Constraint::Length(3), // Footer (needs 3 for border + text)
+            Constraint::Min(0),                 // Content (root post + nested replies)
+            Constraint::Length(related_height), // Related posts
+            Constraint::Length(3),              // Footer (needs 3 for border + text)
         ])
         .split(inner);
 
     // Content: Root post + nested replies tree
-    let content_width = (modal_chunks[1].width as usize).saturating_sub(4);
+    let content_width = (modal_chunks[0].width as usize).saturating_sub(4);
     
-    if modal_replies.is_empty() {
+    if thread.is_empty() {
         // Just show the root post with no replies
         let mut content_lines = vec![];
         
         // Root post header
         content_lines.push(Line::from(vec![
             Span::styled(
-                format!("@{}", root_post.author_username),
+                post_author_label(&root_post),
                 Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
             ),
             Span::raw(" • "),
             Span::styled(
-                format_timestamp(&root_post.created_at),
+                post_time(&root_post, timestamp_style, exact_time_post),
                 Style::default().fg(theme.text_dim),
             ),
         ]));
         content_lines.push(Line::from(""));
         
         // Full post content
-        let post_content_lines = format_post_content_with_width(&root_post.content, false, &theme, content_width);
+        let post_content_lines = post_body_lines(
+            translations,
+            raw_diff_posts,
+            github_cards,
+            &root_post,
+            false,
+            &theme,
+            content_width,
+        );
         content_lines.extend(post_content_lines);
+        // A post too long for the feed reads in full here
+        if let Some(gist) = gist_of(&root_post, gists) {
+            content_lines.push(Line::from(""));
+            content_lines.extend(gist_lines(gist, &theme, content_width));
+        }
         content_lines.push(Line::from(""));
         
         // Vote counts
-        let user_voted_up = root_post.user_vote.as_deref() == Some("up");
-        let user_voted_down = root_post.user_vote.as_deref() == Some("down");
-
-        let upvote_style = if user_voted_up {
-            Style::default().fg(theme.success).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(theme.text_dim)
-        };
-
-        let downvote_style = if user_voted_down {
-            Style::default().fg(theme.error).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(theme.text_dim)
-        };
+        let (upvotes, downvotes) = vote_spans(&root_post, &theme);
 
         content_lines.push(Line::from(vec![
-            Span::styled(format!("↑ {}", root_post.upvotes), upvote_style),
+            upvotes,
             Span::raw("  "),
-            Span::styled(format!("↓ {}", root_post.downvotes), downvote_style),
+            downvotes,
             Span::raw("  "),
             Span::styled(
                 format!("💬 {}", root_post.reply_count),
@@ -299,17 +322,9 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
             .wrap(ratatui::widgets::Wrap { trim: false });
         frame.render_widget(content, modal_chunks[0]);
     } else {
-        // Build tree and render with replies
-        let reply_tree = build_reply_tree_from_root(&root_post, &modal_replies);
-        
-        // IMPORTANT: Only show children if root is expanded
-        // Check if root post is expanded to show its direct children
-        let root_is_expanded = detail_state.modal_expanded_posts.get(&root_post.id).copied().unwrap_or(false);
-        let flattened = if root_is_expanded {
-            flatten_tree(&reply_tree, &detail_state.modal_expanded_posts)
-        } else {
-            vec![] // Don't show any children if root is collapsed
-        };
+        // Replies are only shown while the root is expanded
+        let root_is_expanded = thread_tree::is_expanded(&detail_state.modal_expanded_posts, root_post.id);
+        let flattened = thread.visible(&detail_state.modal_expanded_posts);
         
         // Build list items for root post + replies
         let mut all_items = vec![];
@@ -326,25 +341,39 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         };
         
         // Show expand/collapse indicator for root if it has children
-        let has_replies = !modal_replies.is_empty();
+        let has_replies = !thread.is_empty();
         let expansion_indicator = if has_replies {
             if root_is_expanded { "[-] " } else { "[+] " }
         } else {
             ""
         };
         
-        root_lines.push(Line::from(vec![
+        let mut root_header = vec![
             Span::styled(root_prefix, root_style),
             Span::styled(expansion_indicator, root_style),
-            Span::styled(format!("@{}", root_post.author_username), root_style),
+            Span::styled(post_author_label(&root_post), post_author_style(&root_post, root_style)),
+        ];
+        if show_karma {
+            root_header.push(Span::styled(karma_suffix(&root_post), Style::default().fg(theme.text_dim)));
+        }
+        root_header.extend([
             Span::raw(" • "),
             Span::styled(
-                format_timestamp(&root_post.created_at),
+                post_time(&root_post, timestamp_style, exact_time_post),
                 Style::default().fg(theme.text_dim),
             ),
-        ]));
+        ]);
+        root_lines.push(Line::from(root_header));
         
-        let root_content_lines = format_post_content_with_width(&root_post.content, root_is_selected, &theme, content_width);
+        let root_content_lines = post_body_lines(
+            translations,
+            raw_diff_posts,
+            github_cards,
+            &root_post,
+            root_is_selected,
+            &theme,
+            content_width,
+        );
         for line in root_content_lines {
             let mut spans = vec![Span::raw("  ")];
             spans.extend(line.spans);
@@ -352,20 +381,13 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         }
         
         // Vote counts for root
-        let user_voted_up = root_post.user_vote.as_deref() == Some("up");
-        let user_voted_down = root_post.user_vote.as_deref() == Some("down");
+        let (upvotes, downvotes) = vote_spans(&root_post, &theme);
         
         root_lines.push(Line::from(vec![
             Span::raw("  "),
-            Span::styled(
-                format!("↑ {}", root_post.upvotes),
-                if user_voted_up { Style::default().fg(theme.success).add_modifier(Modifier::BOLD) } else { Style::default().fg(theme.text_dim) }
-            ),
+            upvotes,
             Span::raw("  "),
-            Span::styled(
-                format!("↓ {}", root_post.downvotes),
-                if user_voted_down { Style::default().fg(theme.error).add_modifier(Modifier::BOLD) } else { Style::default().fg(theme.text_dim) }
-            ),
+            downvotes,
             Span::raw("  "),
             Span::styled(
                 format!("💬 {}", root_post.reply_count),
@@ -386,6 +408,10 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
                         "x: Delete",
                         Style::default().fg(theme.error),
                     ),
+                    Span::styled(
+                        " | a: Accept the selected reply as the answer",
+                        Style::default().fg(theme.text_dim),
+                    ),
                 ]));
             }
         }
@@ -405,19 +431,19 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         all_items.push(ListItem::new(root_lines));
         
         // Add reply items (indices 1+)
-        for (flat_idx, (node, has_children)) in flattened.iter().enumerate() {
+        for (flat_idx, node) in flattened.iter().enumerate() {
             let item_index = flat_idx + 1; // +1 because root is index 0
             let is_selected = detail_state.modal_list_state.selected() == Some(item_index);
             let mut reply_lines = vec![];
             
-            let reply = &node.post;
+            let reply = node.post;
             let depth = node.depth;
             let visual_depth = depth.min(5);
             let indent = "  ".repeat(visual_depth);
             
             let tree_char = if depth > 0 { "├─ " } else { "" };
-            let is_expanded = detail_state.modal_expanded_posts.get(&reply.id).copied().unwrap_or(false);
-            let expansion_indicator = if *has_children {
+            let is_expanded = thread_tree::is_expanded(&detail_state.modal_expanded_posts, reply.id);
+            let expansion_indicator = if node.has_children {
                 if is_expanded { "[-] " } else { "[+] " }
             } else {
                 "    "
@@ -430,22 +456,37 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
                 Style::default().fg(theme.primary)
             };
             
-            reply_lines.push(Line::from(vec![
+            let mut reply_header = vec![
                 Span::styled(prefix, header_style),
                 Span::styled(indent.clone(), Style::default().fg(theme.text_dim)),
                 Span::styled(tree_char, Style::default().fg(theme.text_dim)),
                 Span::styled(expansion_indicator, Style::default().fg(theme.accent)),
-                Span::styled(format!("@{}", reply.author_username), header_style),
+                Span::styled(post_author_label(reply), post_author_style(reply, header_style)),
+            ];
+            if show_karma {
+                reply_header.push(Span::styled(karma_suffix(reply), Style::default().fg(theme.text_dim)));
+            }
+            reply_header.extend([
                 Span::raw(" • "),
                 Span::styled(
-                    format_timestamp(&reply.created_at),
+                    post_time(reply, timestamp_style, exact_time_post),
                     Style::default().fg(theme.text_dim),
                 ),
-            ]));
+            ]);
+            if reply.is_accepted {
+                reply_header.push(Span::styled(
+                    " ✓ Accepted",
+                    Style::default().fg(theme.success).add_modifier(Modifier::BOLD),
+                ));
+            }
+            reply_lines.push(Line::from(reply_header));
             
             // Reply content
-            let reply_content_lines = format_post_content_with_width(
-                &reply.content,
+            let reply_content_lines = post_body_lines(
+                translations,
+                raw_diff_posts,
+                github_cards,
+                reply,
                 is_selected,
                 &theme,
                 content_width.saturating_sub(2 + visual_depth * 2),
@@ -461,22 +502,15 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
             }
             
             // Vote counts
-            let reply_voted_up = reply.user_vote.as_deref() == Some("up");
-            let reply_voted_down = reply.user_vote.as_deref() == Some("down");
+            let (upvotes, downvotes) = vote_spans(reply, &theme);
             
             reply_lines.push(Line::from(vec![
                 Span::raw("  "),
                 Span::raw(indent.clone()),
                 Span::raw("   "),
-                Span::styled(
-                    format!("↑ {}", reply.upvotes),
-                    if reply_voted_up { Style::default().fg(theme.success).add_modifier(Modifier::BOLD) } else { Style::default().fg(theme.text_dim) }
-                ),
+                upvotes,
                 Span::raw("  "),
-                Span::styled(
-                    format!("↓ {}", reply.downvotes),
-                    if reply_voted_down { Style::default().fg(theme.error).add_modifier(Modifier::BOLD) } else { Style::default().fg(theme.text_dim) }
-                ),
+                downvotes,
                 Span::raw("  "),
                 Span::styled(
                     format!("💬 {}", reply.reply_count),
@@ -495,9 +529,62 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         frame.render_stateful_widget(replies_list, modal_chunks[0], &mut detail_state.modal_list_state);
     }
 
+    if !detail_state.related.is_empty() {
+        let related_lines: Vec<Line> = detail_state
+            .related
+            .iter()
+            .enumerate()
+            .map(|(i, post)| {
+                let is_selected = detail_state.related_selected == Some(i);
+                let style = if is_selected {
+                    Style::default().fg(theme.success).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let preview = post.content.lines().next().unwrap_or("");
+                Line::from(vec![
+                    Span::styled(if is_selected { "▶ " } else { "  " }, style),
+                    Span::styled(
+                        post_author_label(post),
+                        Style::default().fg(theme.primary),
+                    ),
+                    Span::raw(": "),
+                    Span::styled(truncate_for_related(preview, content_width), style),
+                ])
+            })
+            .collect();
+        let related = Paragraph::new(related_lines).block(
+            Block::default()
+                .title(" Related ")
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(theme.border)),
+        );
+        frame.render_widget(related, modal_chunks[1]);
+    }
+
     // Footer with keyboard shortcuts (context-sensitive and detailed)
-    let footer_text = "↑/↓/j/k: Navigate | Space: Expand/Collapse | u/d: Vote | r: Reply | x: Delete | p: View Profile | Esc: Close";
-    let footer = Paragraph::new(footer_text)
+    let footer_text = if detail_state.related_selected.is_some() {
+        "Tab/Shift+Tab: Related posts | Enter: Open related post | Esc: Close"
+    } else if detail_state.related.is_empty() {
+        "↑/↓/j/k: Navigate | Space: Expand/Collapse | u/d: Vote | r: Reply | t: Translate | T: Exact time | x: Delete | p: View Profile | Esc: Close"
+    } else {
+        "↑/↓/j/k: Navigate | Space: Expand/Collapse | u/d: Vote | r: Reply | Tab: Related | x: Delete | p: View Profile | Esc: Close"
+    };
+    // The reply key is grayed out where the author doesn't take the viewer's replies
+    let footer_spans: Vec<Span> = footer_text
+        .split(" | ")
+        .enumerate()
+        .flat_map(|(index, hint)| {
+            let style = if hint == "r: Reply" && !can_reply {
+                Style::default().fg(theme.text_dim).add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let separator = (index > 0).then(|| Span::styled(" | ", Style::default().fg(theme.text)));
+            separator.into_iter().chain(std::iter::once(Span::styled(hint, style)))
+        })
+        .collect();
+    let footer = Paragraph::new(Line::from(footer_spans))
         .style(Style::default().fg(theme.text))
         .alignment(Alignment::Center)
         .block(
@@ -505,105 +592,62 @@ pub fn render_full_post_modal(frame: &mut Frame, app: &mut App, area: Rect) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(theme.border)),
         );
-    frame.render_widget(footer, modal_chunks[1]);
-}
-
-/// Check if a reply is a descendant of a given post
-fn is_descendant_of(reply: &Post, ancestor_id: &Uuid, all_replies: &[Post]) -> bool {
-    let mut current_parent = reply.parent_post_id;
-    
-    while let Some(parent_id) = current_parent {
-        if parent_id == *ancestor_id {
-            return true;
-        }
-        // Find the parent in all_replies and continue up the chain
-        current_parent = all_replies.iter()
-            .find(|r| r.id == parent_id)
-            .and_then(|r| r.parent_post_id);
-    }
-    
-    false
+    frame.render_widget(footer, modal_chunks[2]);
 }
 
-/// Reply tree node for nested rendering
-#[derive(Clone)]
-struct ReplyNode {
-    post: Post,
-    depth: usize,
-    children: Vec<ReplyNode>,
-    flat_index: usize,
+/// Cut a related post preview to one line of the footer
+fn truncate_for_related(text: &str, width: usize) -> String {
+    let max = width.saturating_sub(20).max(10);
+    crate::text_wrapper::truncate_to_width(text, max)
 }
 
-/// Flatten tree for rendering with expansion state
-fn flatten_tree(nodes: &[ReplyNode], expanded_posts: &std::collections::HashMap<Uuid, bool>) -> Vec<(ReplyNode, bool)> {
-    let mut result = Vec::new();
-    
-    fn flatten_recursive(
-        node: &ReplyNode,
-        expanded_posts: &std::collections::HashMap<Uuid, bool>,
-        result: &mut Vec<(ReplyNode, bool)>,
-    ) {
-        let is_expanded = expanded_posts.get(&node.post.id).copied().unwrap_or(false);
-        let has_children = !node.children.is_empty();
-        
-        result.push((node.clone(), has_children));
-        
-        if is_expanded {
-            for child in &node.children {
-                flatten_recursive(child, expanded_posts, result);
+/// A post's body, or its translation with attribution once 't' fetched
+/// one, followed by the card of a GitHub link in it
+fn post_body_lines(
+    translations: &TranslationState,
+    raw_diff_posts: &std::collections::HashSet<Uuid>,
+    github_cards: &std::collections::HashMap<String, Option<fido_types::GitHubCard>>,
+    post: &Post,
+    is_selected: bool,
+    theme: &ThemeColors,
+    width: usize,
+) -> Vec<Line<'static>> {
+    let note_style = Style::default()
+        .fg(theme.text_dim)
+        .add_modifier(Modifier::ITALIC);
+    let mut lines = match translations.shown.get(&post.id) {
+        None => {
+            let show_diff = !raw_diff_posts.contains(&post.id);
+            let mut lines = format_post_body(post, is_selected, None, show_diff, theme, width);
+            if translations.pending == Some(post.id) {
+                lines.push(Line::from(Span::styled("  Translating…", note_style)));
             }
+            lines
         }
-    }
-    
-    for node in nodes {
-        flatten_recursive(node, expanded_posts, &mut result);
-    }
-    
-    result
-}
-
-/// Build reply tree starting from a specific root post
-fn build_reply_tree_from_root(root: &Post, replies: &[Post]) -> Vec<ReplyNode> {
-    use std::collections::HashMap;
-    
-    // Group replies by parent
-    let mut children_map: HashMap<Uuid, Vec<&Post>> = HashMap::new();
-    for reply in replies {
-        if let Some(parent_id) = reply.parent_post_id {
-            children_map.entry(parent_id).or_default().push(reply);
-        }
-    }
-    
-    // Build tree recursively
-    fn build_node(post: &Post, depth: usize, children_map: &HashMap<Uuid, Vec<&Post>>, flat_index: &mut usize) -> ReplyNode {
-        let current_index = *flat_index;
-        *flat_index += 1;
-        
-        let children = children_map
-            .get(&post.id)
-            .map(|kids| {
-                kids.iter()
-                    .map(|child| build_node(child, depth + 1, children_map, flat_index))
-                    .collect()
-            })
-            .unwrap_or_default();
-        
-        ReplyNode {
-            post: post.clone(),
-            depth,
-            children,
-            flat_index: current_index,
+        Some(translation) => {
+            let mut lines = format_post_content_with_width(
+                &translation.translated_content,
+                is_selected,
+                theme,
+                width,
+            );
+            let source = translation
+                .source_language
+                .as_deref()
+                .map(|language| format!(" from {}", language))
+                .unwrap_or_default();
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  Translated{} by {} · t: show original",
+                    source, translation.provider
+                ),
+                note_style,
+            )));
+            lines
         }
+    };
+    if let Some(card) = github_card_of(post, github_cards) {
+        lines.extend(github_card_lines(card, theme, width));
     }
-    
-    // Get direct children of root
-    let mut flat_index = 0;
-    children_map
-        .get(&root.id)
-        .map(|kids| {
-            kids.iter()
-                .map(|child| build_node(child, 0, &children_map, &mut flat_index))
-                .collect()
-        })
-        .unwrap_or_default()
+    lines
 }
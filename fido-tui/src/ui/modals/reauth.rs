@@ -0,0 +1,111 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Ask to sign back in after the session expired, over the current screen
+pub fn render_reauth_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let Some(user) = &app.auth_state.current_user else {
+        return;
+    };
+
+    let modal_area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, modal_area);
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Your session as @{} has expired.", user.username),
+            Style::default().fg(theme.text),
+        )),
+        Line::from(Span::styled(
+            "Sign in again to carry on where you were.",
+            Style::default().fg(theme.text_dim),
+        )),
+        Line::from(""),
+    ];
+
+    match (&app.reauth.user_code, &app.reauth.verification_uri) {
+        (Some(code), Some(uri)) => {
+            content.push(Line::from(vec![
+                Span::styled("Enter ", Style::default().fg(theme.text)),
+                Span::styled(
+                    code.clone(),
+                    Style::default()
+                        .fg(theme.success)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" at", Style::default().fg(theme.text)),
+            ]));
+            content.push(Line::from(Span::styled(
+                uri.clone(),
+                Style::default().fg(theme.primary),
+            )));
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                "Waiting for GitHub…",
+                Style::default().fg(theme.text_dim),
+            )));
+        }
+        _ => {
+            let action = if user.is_test_user {
+                ": Sign in again  "
+            } else {
+                ": Sign in with GitHub  "
+            };
+            content.push(Line::from(vec![
+                Span::styled(
+                    "Enter",
+                    Style::default()
+                        .fg(theme.success)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(action, Style::default().fg(theme.text)),
+            ]));
+        }
+    }
+
+    if let Some(error) = &app.reauth.error {
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(theme.error),
+        )));
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled(
+            "Esc",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(": Log out", Style::default().fg(theme.text)),
+    ]));
+
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(" Session Expired ")
+                .borders(Borders::ALL)
+                .border_style(
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
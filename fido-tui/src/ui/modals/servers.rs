@@ -0,0 +1,88 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Render the server profile quick-switch modal
+pub fn render_server_switcher_modal(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Switch Server ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Profile list
+            Constraint::Length(1), // Error
+            Constraint::Length(3), // Footer
+        ])
+        .split(inner);
+
+    let profiles = app.server_config.profiles();
+    let active = app.active_profile.as_deref();
+    let items: Vec<ListItem> = profiles
+        .iter()
+        .map(|profile| {
+            let marker = if Some(profile.name.as_str()) == active { "● " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.success)),
+                Span::styled(
+                    profile.name.clone(),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  "),
+                Span::styled(profile.url.clone(), Style::default().fg(theme.text_dim)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.highlight_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    let mut list_state = ListState::default();
+    if !profiles.is_empty() {
+        list_state.select(Some(
+            app.server_switcher.selected_index.min(profiles.len() - 1),
+        ));
+    }
+    frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+    if let Some(error) = &app.server_switcher.error {
+        let error = Paragraph::new(error.clone())
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.error));
+        frame.render_widget(error, chunks[1]);
+    }
+
+    let footer = Paragraph::new("↑/↓/j/k: Navigate | Enter: Switch | Esc: Close")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    frame.render_widget(footer, chunks[2]);
+}
@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{App, ShareAction};
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Render the share menu for a post ('S')
+pub fn render_share_menu(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(post) = &app.share_menu.post else {
+        return;
+    };
+    let theme = get_theme_colors(app);
+
+    let modal_area = centered_rect(50, 40, area);
+    frame.render_widget(Clear, modal_area);
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            app.post_permalink(post.id),
+            Style::default().fg(theme.text_dim),
+        )),
+        Line::from(""),
+    ];
+    for (i, action) in ShareAction::ALL.iter().enumerate() {
+        let style = if i == app.share_menu.selected {
+            Style::default()
+                .fg(theme.background)
+                .bg(theme.primary)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        content.push(Line::from(Span::styled(format!(" {} ", action.label()), style)));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+        Span::styled(": Choose  ", Style::default().fg(theme.text)),
+        Span::styled("Esc", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        Span::styled(": Cancel", Style::default().fg(theme.text)),
+    ]));
+
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(format!(" Share @{}'s Post ", post.author_username))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
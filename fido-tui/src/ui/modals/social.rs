@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::app::App;
+use super::super::formatting::badge_suffix;
 use super::super::theme::get_theme_colors;
 use super::utils::centered_rect;
 use super::social_components::*;
@@ -131,7 +132,7 @@ pub fn render_friends_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         frame.render_widget(empty, chunks[2]);
     } else {
         // Build user list
-        let items: Vec<ListItem> = filtered_users
+        let mut items: Vec<ListItem> = filtered_users
             .iter()
             .map(|user| {
                 let content = format!(
@@ -141,6 +142,18 @@ pub fn render_friends_modal(frame: &mut Frame, app: &mut App, area: Rect) {
                 ListItem::new(content)
             })
             .collect();
+        let has_more = app
+            .friends_state
+            .pages
+            .get(&app.friends_state.selected_tab)
+            .is_some_and(|page| page.has_more);
+        if has_more {
+            // Never selectable: the selection stops at the last user
+            items.push(
+                ListItem::new("   … more, ↓ at the end to load")
+                    .style(Style::default().fg(theme.text_dim)),
+            );
+        }
 
         let list = List::new(items)
             .highlight_style(
@@ -205,20 +218,38 @@ pub fn render_user_profile_view(frame: &mut Frame, app: &App, area: Rect) {
             Constraint::Length(5), // Header with username and stats
             Constraint::Length(4), // Bio
             Constraint::Length(3), // Relationship status
-            Constraint::Min(0),    // Spacer
+            Constraint::Min(0),    // Recent posts
             Constraint::Length(3), // Actions footer (needs 3 for border + text)
         ])
         .split(inner);
 
     // Render header with username and stats
+    let badge_labels: Vec<String> = profile
+        .badges
+        .iter()
+        .map(|badge| format!("{} {}", badge.glyph(), badge.label()))
+        .collect();
     let header_lines = vec![
         Line::from(Span::styled(
-            format!("@{}", profile.username),
+            format!("@{}{}", profile.username, badge_suffix(&profile.badges)),
             Style::default()
                 .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )),
-        Line::from(""),
+        // The status takes the second line when set; the badge glyphs are
+        // still on the username
+        match &profile.status {
+            Some(status) => Line::from(Span::styled(
+                crate::status::format_status(status),
+                Style::default()
+                    .fg(theme.text)
+                    .add_modifier(Modifier::ITALIC),
+            )),
+            None => Line::from(Span::styled(
+                badge_labels.join("  "),
+                Style::default().fg(theme.text_dim),
+            )),
+        },
         Line::from(vec![
             Span::styled(
                 format!("{} ", profile.follower_count),
@@ -234,7 +265,12 @@ pub fn render_user_profile_view(frame: &mut Frame, app: &App, area: Rect) {
                 format!("{} ", profile.post_count),
                 Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("Posts", Style::default().fg(theme.text_dim)),
+            Span::styled("Posts  ", Style::default().fg(theme.text_dim)),
+            Span::styled(
+                format!("{} ", profile.karma),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("Karma", Style::default().fg(theme.text_dim)),
         ]),
     ];
 
@@ -269,12 +305,20 @@ pub fn render_user_profile_view(frame: &mut Frame, app: &App, area: Rect) {
         crate::app::RelationshipStatus::None => ("Not Following", theme.text_dim),
     };
 
-    let status = Paragraph::new(Line::from(Span::styled(
+    let mut status_spans = vec![Span::styled(
         status_text,
         Style::default()
             .fg(status_color)
             .add_modifier(Modifier::BOLD),
-    )))
+    )];
+    // An account that left this instance points at its new home
+    if let Some(moved_to) = &profile.moved_to {
+        status_spans.push(Span::styled(
+            format!("  ·  Moved to {}", moved_to),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    let status = Paragraph::new(Line::from(status_spans))
     .alignment(Alignment::Center)
     .block(
         Block::default()
@@ -283,6 +327,35 @@ pub fn render_user_profile_view(frame: &mut Frame, app: &App, area: Rect) {
     );
     frame.render_widget(status, modal_chunks[2]);
 
+    // Render recent posts (one line each, newest first)
+    let posts_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .title(" Recent Posts ");
+    if profile.recent_posts.is_empty() {
+        let empty = Paragraph::new("No posts yet")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.text_dim))
+            .block(posts_block);
+        frame.render_widget(empty, modal_chunks[3]);
+    } else {
+        let items: Vec<ListItem> = profile
+            .recent_posts
+            .iter()
+            .map(|post| {
+                let first_line = post.content.lines().next().unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("↑{} ", post.upvotes),
+                        Style::default().fg(theme.success),
+                    ),
+                    Span::styled(first_line.to_string(), Style::default().fg(theme.text)),
+                ]))
+            })
+            .collect();
+        frame.render_widget(List::new(items).block(posts_block), modal_chunks[3]);
+    }
+
     // Render actions footer with context-sensitive shortcuts
     let actions_text = match &profile.relationship {
         crate::app::RelationshipStatus::Self_ => "Esc: Cancel",
@@ -333,16 +406,20 @@ pub fn render_new_conversation_modal(frame: &mut Frame, app: &mut App, area: Rec
         .split(inner);
 
     // Render search bar
+    let username = &app.dms_state.new_conversation_username;
+    let search_style = Style::default().fg(if app.dms_state.new_conversation_search_mode { theme.accent } else { theme.text_dim });
     let search_text = if app.dms_state.new_conversation_search_mode {
-        format!("/{}", app.dms_state.new_conversation_search_query)
-    } else if !app.dms_state.new_conversation_search_query.is_empty() {
-        format!("Filter: {}", app.dms_state.new_conversation_search_query)
+        let mut spans = vec![Span::styled("/", search_style)];
+        spans.extend(username.spans(search_style));
+        Line::from(spans)
+    } else if !username.is_empty() {
+        Line::from(format!("Filter: {}", username.value()))
     } else {
-        "Press / to search".to_string()
+        Line::from("Press / to search")
     };
 
     let search_bar = Paragraph::new(search_text)
-        .style(Style::default().fg(if app.dms_state.new_conversation_search_mode { theme.accent } else { theme.text_dim }))
+        .style(search_style)
         .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.border)));
     frame.render_widget(search_bar, chunks[0]);
 
@@ -350,7 +427,7 @@ pub fn render_new_conversation_modal(frame: &mut Frame, app: &mut App, area: Rec
     let filtered_users = app.get_filtered_mutual_friends();
 
     if filtered_users.is_empty() {
-        let empty_msg = if app.dms_state.new_conversation_search_query.is_empty() {
+        let empty_msg = if app.dms_state.new_conversation_username.is_empty() {
             "No mutual friends available for messaging"
         } else {
             "No users match your search"
@@ -0,0 +1,76 @@
+use fido_types::version::Compatibility;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{App, CLIENT_VERSION};
+use super::super::theme::get_theme_colors;
+use super::utils::centered_rect;
+
+/// Explain why this client won't talk to the server, on any screen
+pub fn render_version_mismatch_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let (headline, advice) = match &app.version_check {
+        Some(Compatibility::ClientTooOld { required }) => (
+            format!(
+                "This server needs fido {} or newer; you have {}.",
+                required, CLIENT_VERSION
+            ),
+            "Update with: cargo install fido".to_string(),
+        ),
+        Some(Compatibility::ServerTooOld { server, required }) => (
+            format!(
+                "This server runs fido {}; this client needs {} or newer.",
+                server, required
+            ),
+            "Ask the server's operator to upgrade, or use an older client.".to_string(),
+        ),
+        _ => return,
+    };
+
+    let modal_area = centered_rect(60, 40, area);
+    frame.render_widget(Clear, modal_area);
+
+    let key = |label: &'static str| {
+        Span::styled(
+            label,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let content = vec![
+        Line::from(""),
+        Line::from(Span::styled(headline, Style::default().fg(theme.text))),
+        Line::from(""),
+        Line::from(Span::styled(advice, Style::default().fg(theme.primary))),
+        Line::from(""),
+        Line::from(vec![
+            key("Ctrl+Shift+S"),
+            Span::styled(": Switch server  ", Style::default().fg(theme.text)),
+            key("q"),
+            Span::styled(": Quit", Style::default().fg(theme.text)),
+        ]),
+    ];
+
+    let modal = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(" Incompatible Server ")
+                .borders(Borders::ALL)
+                .border_style(
+                    Style::default()
+                        .fg(theme.error)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(theme.background)),
+        );
+
+    frame.render_widget(modal, modal_area);
+}
@@ -0,0 +1,490 @@
+//! Snapshot tests for the main screens, rendered with [`TuiHarness`].
+
+use chrono::{DateTime, TimeZone, Utc};
+use fido_types::{DirectMessage, Post, ReplyPolicy, User, UserConfig};
+use ratatui::widgets::ListState;
+use uuid::Uuid;
+
+use super::testing::TuiHarness;
+use crate::app::{App, Conversation, PostDetailState, Screen, SettingsField, Tab};
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 32;
+
+fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 12, 1, hour, minute, 0).unwrap()
+}
+
+fn user_id(username: &str) -> Uuid {
+    match username {
+        "alice" => Uuid::from_u128(1),
+        "bob" => Uuid::from_u128(2),
+        _ => Uuid::from_u128(3),
+    }
+}
+
+fn post(id: u128, author: &str, content: &str, parent: Option<u128>) -> Post {
+    Post {
+        id: Uuid::from_u128(100 + id),
+        author_id: user_id(author),
+        author_username: author.to_string(),
+        content: content.to_string(),
+        created_at: at(9, id as u32),
+        upvotes: 2,
+        downvotes: 0,
+        hashtags: Vec::new(),
+        user_vote: None,
+        parent_post_id: parent.map(|p| Uuid::from_u128(100 + p)),
+        reply_count: 0,
+        reply_to_user_id: None,
+        reply_to_username: None,
+        is_deleted: false,
+        author_badges: Vec::new(),
+        author_status: None,
+        kind: None,
+        is_anonymous: false,
+        reply_policy: ReplyPolicy::Everyone,
+        can_reply: true,
+        is_accepted: false,
+        author_karma: 0,
+    }
+}
+
+fn message(from: &str, to: &str, content: &str, minute: u32) -> DirectMessage {
+    DirectMessage {
+        id: Uuid::from_u128(200 + minute as u128),
+        from_user_id: user_id(from),
+        to_user_id: user_id(to),
+        from_username: from.to_string(),
+        to_username: to.to_string(),
+        content: content.to_string(),
+        created_at: at(10, minute),
+        is_read: true,
+        attachment: None,
+    }
+}
+
+/// Alice, logged in on the main screen with nothing loading
+fn logged_in_app() -> App {
+    let mut app = App::new();
+    app.current_screen = Screen::Main;
+    app.auth_state.current_user = Some(User {
+        id: user_id("alice"),
+        username: "alice".to_string(),
+        bio: Some("Rustacean".to_string()),
+        join_date: at(8, 0),
+        is_test_user: true,
+        badges: Vec::new(),
+        karma: 0,
+    });
+    app.posts_state.loading = false;
+    app.settings_state.loading = false;
+    app.dms_state.loading = false;
+    app
+}
+
+fn feed_harness() -> TuiHarness {
+    let mut app = logged_in_app();
+    app.posts_state.posts = vec![
+        post(3, "bob", "Shipping the new parser today #rust", None),
+        post(2, "charlie", "Anyone tried ratatui 0.28?", None),
+        post(1, "alice", "Hello fido!", None),
+    ];
+    app.posts_state.list_state.select(Some(0));
+    TuiHarness::new(app, WIDTH, HEIGHT)
+}
+
+#[test]
+fn test_feed_snapshot() {
+    let mut harness = feed_harness();
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("Shipping the new parser today"));
+    assert!(screen.contains("Hello fido!"));
+    harness.assert_snapshot("feed");
+}
+
+#[test]
+fn test_accessible_feed_snapshot() {
+    let mut harness = feed_harness();
+    harness.app.settings_state.original_config = Some(UserConfig {
+        accessible_layout: true,
+        ..UserConfig::default()
+    });
+    harness.render().run_script("j").unwrap();
+
+    let screen = harness.screen();
+    assert!(screen.contains("  Post 1 of 3, by bob"));
+    assert!(screen.contains("> Post 2 of 3, by charlie"));
+    assert!(screen.contains("2 up, 0 down, 0 replies"));
+    assert!(screen.contains("Keys: "));
+    harness.assert_snapshot("feed_accessible");
+}
+
+#[test]
+fn test_compact_feed_without_vote_counts() {
+    let mut harness = feed_harness();
+    harness.app.posts_state.posts[0].content =
+        "Shipping the new parser today #rust\nIt handles every edge case we found".to_string();
+    harness.app.settings_state.original_config = Some(UserConfig {
+        feed_density: fido_types::FeedDensity::Compact,
+        show_vote_counts: false,
+        ..UserConfig::default()
+    });
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("Shipping the new parser today #rust It handles every edge case"));
+    assert!(screen.contains("💬 0"));
+    assert!(!screen.contains("↑ 2"));
+}
+
+#[test]
+fn test_feed_shows_karma_when_enabled() {
+    let mut harness = feed_harness();
+    harness.app.posts_state.posts[0].author_karma = 42;
+    harness.render();
+    assert!(!harness.screen().contains("(karma: 42)"));
+
+    harness.app.settings_state.original_config = Some(UserConfig {
+        show_karma: true,
+        ..UserConfig::default()
+    });
+    harness.render();
+    assert!(harness.screen().contains("(karma: 42)"));
+}
+
+#[test]
+fn test_feed_shows_github_cards_once_loaded() {
+    let mut harness = feed_harness();
+    let link = "https://github.com/rust-lang/rust/pull/7";
+    harness.app.posts_state.posts[0].content = format!("Finally landed: {}", link);
+    harness.render();
+    assert!(!harness.screen().contains("Merged pull request"));
+
+    harness.app.github_cards.insert(
+        link.to_string(),
+        Some(fido_types::GitHubCard {
+            url: link.to_string(),
+            repo: "rust-lang/rust".to_string(),
+            kind: fido_types::GitHubCardKind::PullRequest {
+                number: 7,
+                title: "Stabilize let chains".to_string(),
+                state: fido_types::GitHubState::Merged,
+                comments: 12,
+            },
+        }),
+    );
+    harness.render();
+    let screen = harness.screen();
+    assert!(screen.contains("rust-lang/rust#7  Merged pull request"));
+    assert!(screen.contains("Stabilize let chains"));
+}
+
+#[test]
+fn test_long_posts_end_in_more() {
+    let mut harness = feed_harness();
+    harness.app.posts_state.posts[0].content = "one\ntwo\nthree\nfour".to_string();
+    harness.app.settings_state.original_config = Some(UserConfig {
+        preview_lines: 2,
+        ..UserConfig::default()
+    });
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("two"));
+    assert!(screen.contains("… more"));
+    assert!(!screen.contains("three"));
+}
+
+#[test]
+fn test_feed_navigation_script_snapshot() {
+    let mut harness = feed_harness();
+    harness.render().run_script("j j k j").unwrap();
+
+    assert_eq!(harness.app.posts_state.list_state.selected(), Some(2));
+    harness.assert_snapshot("feed_after_navigation");
+}
+
+#[test]
+fn test_thread_modal_snapshot() {
+    let mut harness = feed_harness();
+    let root = post(3, "bob", "Shipping the new parser today #rust", None);
+    let mut root_with_replies = root.clone();
+    root_with_replies.reply_count = 2;
+
+    let mut modal_list_state = ListState::default();
+    modal_list_state.select(Some(0));
+    harness.app.post_detail_state = Some(PostDetailState {
+        post: Some(root_with_replies),
+        replies: vec![
+            post(4, "alice", "Congrats! Benchmarks?", Some(3)),
+            post(5, "bob", "About 2x faster on big files", Some(4)),
+        ],
+        reply_list_state: ListState::default(),
+        loading: false,
+        error: None,
+        show_reply_composer: false,
+        reply_content: String::new(),
+        show_delete_confirmation: false,
+        previous_feed_position: Some(0),
+        expanded_posts: Default::default(),
+        show_full_post_modal: true,
+        full_post_modal_id: Some(root.id),
+        modal_list_state,
+        modal_expanded_posts: [(root.id, true)].into_iter().collect(),
+        related: Vec::new(),
+        related_selected: None,
+    });
+    harness.app.viewing_post_detail = true;
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("Congrats! Benchmarks?"));
+    harness.assert_snapshot("thread_modal");
+}
+
+#[test]
+fn test_dms_snapshot() {
+    let mut app = logged_in_app();
+    app.current_tab = Tab::DMs;
+    app.dms_state.conversations = vec![
+        Conversation {
+            other_user_id: user_id("bob"),
+            other_username: "bob".to_string(),
+            last_message: "See you at standup".to_string(),
+            last_message_time: at(10, 2),
+            unread_count: 0,
+            pinned: false,
+        },
+        Conversation {
+            other_user_id: user_id("charlie"),
+            other_username: "charlie".to_string(),
+            last_message: "Thanks!".to_string(),
+            last_message_time: at(9, 30),
+            unread_count: 1,
+            pinned: false,
+        },
+    ];
+    app.dms_state.selected_conversation_index = Some(0);
+    app.dms_state.current_conversation_user = Some(user_id("bob"));
+    app.dms_state.messages = vec![
+        message("bob", "alice", "Is the release out?", 1),
+        message("alice", "bob", "See you at standup", 2),
+    ];
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("Is the release out?"));
+    assert!(screen.contains("charlie"));
+    harness.assert_snapshot("dms");
+}
+
+#[test]
+fn test_settings_snapshot() {
+    let mut app = logged_in_app();
+    app.current_tab = Tab::Settings;
+    let config = UserConfig {
+        user_id: user_id("alice"),
+        ..UserConfig::default()
+    };
+    app.settings_state
+        .max_posts_input
+        .set_value(&config.max_posts_display.to_string());
+    app.settings_state.original_max_posts_input = config.max_posts_display.to_string();
+    app.settings_state.config = Some(config.clone());
+    app.settings_state.original_config = Some(config);
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render().run_script("j j j").unwrap();
+
+    assert_eq!(
+        harness.app.settings_state.selected_field,
+        SettingsField::VotePrivacy
+    );
+    assert!(harness.screen().contains("Vote Visibility"));
+    harness.assert_snapshot("settings");
+}
+
+#[test]
+fn test_own_votes_use_filled_arrows() {
+    let mut harness = feed_harness();
+    harness.app.posts_state.posts[0].user_vote = Some("up".to_string());
+    harness.app.posts_state.posts[1].user_vote = Some("down".to_string());
+    harness.app.settings_state.config = Some(UserConfig {
+        color_scheme: fido_types::ColorScheme::Deuteranopia,
+        ..UserConfig::default()
+    });
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("▲ 2 ↓ 0"));
+    assert!(screen.contains("↑ 2 ▼ 0"));
+    assert!(screen.contains("↑ 2 ↓ 0"));
+}
+
+#[test]
+fn test_status_bar_shows_unread_and_filter() {
+    let mut app = logged_in_app();
+    app.api_client = crate::api::ApiClient::new("http://localhost:3000");
+    app.dms_state.unread_counts.insert(user_id("bob"), 2);
+    app.posts_state.current_filter = crate::app::PostFilter::Hashtag("rust".to_string());
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render();
+
+    let screen = harness.screen();
+    let status_bar = screen.lines().last().unwrap();
+    assert!(status_bar.contains("● online │ localhost:3000"));
+    assert!(status_bar.contains("✉ 2 unread"));
+    assert!(status_bar.contains("Feed: #rust"));
+}
+
+#[test]
+fn test_toasts_stack_in_the_corner() {
+    let mut app = logged_in_app();
+    app.toasts.success("✓ Post deleted");
+    app.toasts.error("Failed to load DMs");
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render();
+
+    let screen = harness.screen();
+    let lines: Vec<_> = screen.lines().collect();
+    let row_of = |text: &str| lines.iter().position(|line| line.contains(text)).unwrap();
+    // Newest at the bottom, above the footer and status bar
+    assert!(row_of("✓ Post deleted") < row_of("Failed to load DMs"));
+    assert!(row_of("Failed to load DMs") < lines.len() - 5);
+    assert!(lines[row_of("Failed to load DMs")].trim_end().ends_with("│"));
+}
+
+#[test]
+fn test_profile_shows_posting_activity() {
+    let mut app = logged_in_app();
+    app.current_tab = Tab::Profile;
+    app.profile_state.profile = Some(fido_types::UserProfile {
+        user_id: user_id("alice"),
+        username: "alice".to_string(),
+        bio: Some("Rustacean".to_string()),
+        karma: 12,
+        post_count: 9,
+        join_date: at(8, 0),
+        recent_hashtags: vec!["rust".to_string()],
+        badges: Vec::new(),
+        status: None,
+        moved_to: None,
+    });
+    app.profile_state.stats = Some(fido_types::UserStats {
+        posts_per_week: vec![0, 1, 2, 4, 8],
+        current_streak: 3,
+        longest_streak: 7,
+        top_hashtags: vec![fido_types::HashtagUse {
+            name: "rust".to_string(),
+            count: 6,
+        }],
+        upvotes_received: 12,
+        downvotes_received: 1,
+        total_posts: 9,
+        total_words: 180,
+        total_characters: 1024,
+    });
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("Posts/week: ▁▂▃▅█ 8 this week"));
+    assert!(screen.contains("Streak: 3 days (best 7)"));
+    assert!(screen.contains("Votes received: ↑ 12  ↓ 1"));
+    assert!(screen.contains("Words: 180 in 9 posts, ~20 each"));
+    assert!(screen.contains("Most used: #rust 6"));
+}
+
+#[test]
+fn test_highlights_list_top_posts_and_contributors() {
+    let mut app = logged_in_app();
+    app.highlights.show = true;
+    app.highlights.top_posts = vec![
+        post(3, "bob", "Shipping the new parser today #rust", None),
+        post(2, "charlie", "Anyone tried ratatui 0.28?", None),
+    ];
+    app.highlights.top_contributors = Some(vec![fido_types::TopContributor {
+        user_id: user_id("bob"),
+        username: "bob".to_string(),
+        badges: Vec::new(),
+        karma: 14,
+    }]);
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("This Week's Highlights"));
+    assert!(screen.contains("▶  1. @bob"));
+    assert!(screen.contains("Anyone tried ratatui 0.28?"));
+    assert!(screen.contains("@bob  +14 karma"));
+
+    // Instances that don't rank people only list posts
+    harness.app.highlights.top_contributors = None;
+    harness.render();
+    assert!(!harness.screen().contains("Top contributors"));
+}
+
+#[test]
+fn test_hashtag_digest_lists_changelogs_and_turned_off_hashtags() {
+    let mut app = logged_in_app();
+    app.hashtag_digest.show = true;
+    app.hashtag_digest.entries = vec![
+        fido_types::HashtagDigestEntry {
+            hashtag: "rust".to_string(),
+            enabled: true,
+            changelog: Some(fido_types::HashtagChangelog {
+                id: Uuid::from_u128(300),
+                hashtag: "rust".to_string(),
+                period_start: at(8, 0),
+                period_end: at(9, 0),
+                summary: "#rust this week: 2 posts from 2 people".to_string(),
+                top_posts: vec![
+                    post(3, "bob", "Shipping the new parser today #rust", None),
+                    post(2, "charlie", "Anyone tried ratatui 0.28? #rust", None),
+                ],
+            }),
+        },
+        fido_types::HashtagDigestEntry {
+            hashtag: "go".to_string(),
+            enabled: false,
+            changelog: None,
+        },
+    ];
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("Digest: Your Hashtags This Week"));
+    assert!(screen.contains("▶ #rust  Dec 1 – Dec 1"));
+    assert!(screen.contains("#rust this week: 2 posts from 2 people"));
+    assert!(screen.contains("Anyone tried ratatui 0.28?"));
+    assert!(screen.contains("#go  off"));
+}
+
+#[test]
+fn test_reading_history_lists_matching_posts() {
+    let mut app = logged_in_app();
+    app.reading_history.show = true;
+    app.reading_history.entries = vec![
+        crate::config::HistoryEntry {
+            post: post(1, "bob", "Lunch plans for the meetup", None),
+            opened_at: at(9, 0),
+        },
+        crate::config::HistoryEntry {
+            post: post(2, "alice", "Moving the server to tokio", None),
+            opened_at: at(8, 0),
+        },
+    ];
+    app.reading_history.search = "tokio".to_string();
+    let mut harness = TuiHarness::new(app, WIDTH, HEIGHT);
+    harness.render();
+
+    let screen = harness.screen();
+    assert!(screen.contains("Reading History"));
+    assert!(screen.contains("Search: tokio"));
+    assert!(screen.contains("Moving the server to tokio"));
+    assert!(!screen.contains("Lunch plans"));
+}
@@ -0,0 +1,95 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::app::{App, ConnectionState, QuietReason};
+use super::theme::get_theme_colors;
+
+/// One-line status bar along the bottom of the main screen: connection,
+/// server, unread DMs, notification muting, active feed filter, available
+/// upgrades and rate limiting.
+pub fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let separator = Span::styled(" │ ", Style::default().fg(theme.border));
+
+    let (dot, state) = match app.connection_state() {
+        ConnectionState::Online => (Style::default().fg(theme.success), "online"),
+        ConnectionState::Reconnecting => (Style::default().fg(theme.warning), "reconnecting"),
+        ConnectionState::Offline => (
+            Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+            "offline",
+        ),
+    };
+
+    let mut spans = vec![
+        Span::raw(" "),
+        Span::styled("●", dot),
+        Span::styled(format!(" {}", state), dot),
+        separator.clone(),
+        Span::styled(server_label(app), Style::default().fg(theme.text_dim)),
+    ];
+
+    let unread = app.visible_unread_dm_count();
+    if unread > 0 {
+        spans.push(separator.clone());
+        spans.push(Span::styled(
+            format!("✉ {} unread", unread),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(reason) = app.quiet_reason() {
+        spans.push(separator.clone());
+        spans.push(Span::styled(
+            match reason {
+                QuietReason::DoNotDisturb => "🔕 Do not disturb",
+                QuietReason::QuietHours => "🔕 Quiet hours",
+            },
+            Style::default().fg(theme.warning),
+        ));
+    }
+
+    spans.push(separator.clone());
+    spans.push(Span::styled(
+        format!("Feed: {}", app.posts_state.current_filter.label()),
+        Style::default().fg(theme.text_dim),
+    ));
+
+    if let Some(fido_types::version::Compatibility::UpgradeAvailable { latest }) = &app.version_check {
+        spans.push(separator.clone());
+        spans.push(Span::styled(
+            format!("⬆ fido {} available", latest),
+            Style::default().fg(theme.primary),
+        ));
+    }
+
+    if app.api_client.is_rate_limited() {
+        spans.push(separator);
+        spans.push(Span::styled(
+            "⚠ Rate limited",
+            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.background)),
+        area,
+    );
+}
+
+/// "profile · host" for named profiles, otherwise just the host
+fn server_label(app: &App) -> String {
+    let url = app.api_client.base_url();
+    let host = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .trim_end_matches('/');
+    match &app.active_profile {
+        Some(profile) => format!("{} · {}", profile, host),
+        None => host.to_string(),
+    }
+}
@@ -2,15 +2,18 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{block::Title, Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, DeliveryState, OutgoingMessage};
+use crate::text_wrapper::take_width;
 use crate::{log_modal_state, log_rendering};
+use unicode_width::UnicodeWidthStr;
 use super::theme::{ThemeColors, get_theme_colors};
 use super::formatting::*;
 use super::modals::*;
+use super::status_bar::render_status_bar;
 
 pub fn render_auth_screen(frame: &mut Frame, app: &mut App) {
     let area = frame.area();
@@ -181,6 +184,34 @@ pub fn render_auth_screen(frame: &mut Frame, app: &mut App) {
         }
     }
 
+    let show_saved_accounts = !app.auth_state.github_auth_in_progress
+        && !app.auth_state.loading
+        && app.auth_state.error.is_none()
+        && !app.auth_state.saved_accounts.is_empty();
+    if show_saved_accounts {
+        lines.push(Line::from(""));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Saved accounts:",
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        for (i, username) in app.auth_state.saved_accounts.iter().take(9).enumerate() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("@{}", username),
+                    Style::default().fg(theme.primary),
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Press 1-9 to continue as a saved account",
+            Style::default().fg(Color::White),
+        )));
+    }
+
     let content = Paragraph::new(lines).alignment(Alignment::Center).block(
         Block::default()
             .borders(Borders::ALL)
@@ -204,6 +235,11 @@ pub fn render_auth_screen(frame: &mut Frame, app: &mut App) {
             "↑/k: Up | ↓/j: Down | Enter: Login | q/Esc: Quit"
         }
     };
+    let footer_text = if show_saved_accounts {
+        format!("1-9: Saved account | {}", footer_text)
+    } else {
+        footer_text.to_string()
+    };
 
     let footer = Paragraph::new(footer_text)
         .style(Style::default().fg(Color::White))
@@ -230,6 +266,7 @@ pub fn render_main_screen(frame: &mut Frame, app: &mut App) {
             Constraint::Min(0),                // Content (flexible)
             Constraint::Length(1),             // Page-specific actions
             Constraint::Length(footer_height), // Global footer
+            Constraint::Length(1),             // Status bar
         ])
         .split(area);
 
@@ -237,12 +274,18 @@ pub fn render_main_screen(frame: &mut Frame, app: &mut App) {
     render_tab_header(frame, app, chunks[0]);
 
     // Render tab content
+    let accessible = app.accessible_layout();
     match app.current_tab {
+        crate::app::Tab::Posts if accessible => {
+            super::linear::render_linear_posts(frame, app, chunks[1])
+        }
+        crate::app::Tab::DMs if accessible => super::linear::render_linear_dms(frame, app, chunks[1]),
         crate::app::Tab::Posts => {
             // Always render the feed
             render_posts_tab_with_data(frame, app, chunks[1]);
             // Modal is rendered later at the top level (after all tabs)
         }
+        crate::app::Tab::Channels => render_channels_tab(frame, app, chunks[1]),
         crate::app::Tab::DMs => render_dms_tab(frame, app, chunks[1]),
         crate::app::Tab::Profile => render_profile_tab(frame, app, chunks[1]),
         crate::app::Tab::Settings => render_settings_tab(frame, app, chunks[1]),
@@ -254,6 +297,8 @@ pub fn render_main_screen(frame: &mut Frame, app: &mut App) {
     // Render global footer
     render_global_footer(frame, app, chunks[3]);
 
+    render_status_bar(frame, app, chunks[4]);
+
     // Render modals (in priority order - LAST rendered = TOP of stack)
     
     // ============================================================================
@@ -392,20 +437,11 @@ pub fn render_main_screen(frame: &mut Frame, app: &mut App) {
 pub fn render_tab_header(frame: &mut Frame, app: &mut App, area: Rect) {
     let theme = get_theme_colors(app);
 
-    // Calculate total unread count for DMs
-    let total_unread: usize = app.dms_state.unread_counts.values().sum();
-
-    let tabs = ["Posts", "DMs", "Profile", "Settings"];
-    let current_index = match app.current_tab {
-        crate::app::Tab::Posts => 0,
-        crate::app::Tab::DMs => 1,
-        crate::app::Tab::Profile => 2,
-        crate::app::Tab::Settings => 3,
-    };
+    let tabs = crate::app::Tab::ALL;
 
     let mut tab_spans = vec![];
     for (i, tab) in tabs.iter().enumerate() {
-        let style = if i == current_index {
+        let style = if *tab == app.current_tab {
             Style::default()
                 .fg(theme.success)
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
@@ -413,11 +449,12 @@ pub fn render_tab_header(frame: &mut Frame, app: &mut App, area: Rect) {
             Style::default().fg(theme.text_dim)
         };
 
-        // Add unread badge for DMs tab
-        let tab_text = if i == 1 && total_unread > 0 {
-            format!(" {} ({}) ", tab, total_unread)
+        // Add unread badge for tabs with something new
+        let unread = app.tab_unread_count(*tab);
+        let tab_text = if unread > 0 {
+            format!(" {} ({}) ", tab.label(), unread)
         } else {
-            format!(" {} ", tab)
+            format!(" {} ", tab.label())
         };
 
         tab_spans.push(Span::styled(tab_text, style));
@@ -447,6 +484,12 @@ pub fn get_action_bar_text(app: &App) -> &'static str {
         crate::app::Tab::Posts => {
             "u/d: Vote | n: Post | f: Filter | s: Search | Space: View | p: Profile"
         }
+        crate::app::Tab::Channels if app.channels_state.new_channel_input.is_some() => {
+            "Type a name | Enter: Create | Esc: Cancel"
+        }
+        crate::app::Tab::Channels => {
+            "↑/↓/j/k: Navigate | Enter: Open | Space: Join/Leave | n: New Channel | r: Refresh"
+        }
         crate::app::Tab::DMs => {
             // Check if user can compose (active conversation or pending draft)
             let has_active_conversation = app.dms_state.selected_conversation_index
@@ -455,15 +498,19 @@ pub fn get_action_bar_text(app: &App) -> &'static str {
             let has_pending_draft = app.dms_state.pending_conversation_username.is_some();
             let can_compose = has_active_conversation || has_pending_draft;
             
-            if app.dms_state.selected_conversation_index == Some(usize::MAX) {
+            if app.dms_state.reminder_input.is_some() {
+                "Remind me in 30m, 3h, 1d... | Enter: Set (empty clears) | Esc: Cancel"
+            } else if app.dms_state.selected_conversation_index == Some(usize::MAX) {
                 "Enter: Start New Conversation | ↑/↓/j/k: Navigate | Esc: Back"
+            } else if can_compose && app.input_mode == crate::app::InputMode::Navigation {
+                "↑/↓/j/k: Navigate | Type to compose | Ctrl+U: Mark unread | Ctrl+R: Remind me"
             } else if can_compose {
                 "↑/↓/j/k: Navigate | Type to compose | Enter: Send | Esc: Clear"
             } else {
                 "↑/↓/j/k: Navigate | Enter: Select conversation | n: New Conversation"
             }
         }
-        crate::app::Tab::Profile => "e: Edit Bio | f: Friends",
+        crate::app::Tab::Profile => "←/→: Posts/Replies/Upvoted | e: Edit Bio | f: Friends",
         crate::app::Tab::Settings => "←/→/h/l: Change | s: Save",
     }
 }
@@ -514,62 +561,29 @@ pub fn render_posts_tab_with_data(frame: &mut Frame, app: &mut App, area: Rect)
     
     let theme = get_theme_colors(app);
     
-    // Check if we need to show message or error banners
-    let has_message = app.posts_state.message.is_some();
+    // Action messages live in the status bar; only errors get a banner here
     let has_error = app.posts_state.error.is_some();
 
-    // Layout: Message banner (if present), Error banner (if present), posts feed
-    let chunks = match (has_message, has_error) {
-        (true, true) => {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(3), // Message banner
-                    Constraint::Length(3), // Error banner
-                    Constraint::Min(0),    // Posts feed
-                ])
-                .split(area)
-        }
-        (true, false) => {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(3), // Message banner
-                    Constraint::Min(0),    // Posts feed
-                ])
-                .split(area)
-        }
-        (false, true) => {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(3), // Error banner
-                    Constraint::Min(0),    // Posts feed
-                ])
-                .split(area)
-        }
-        (false, false) => {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Min(0), // Posts feed
-                ])
-                .split(area)
-        }
+    // Layout: Error banner (if present), posts feed
+    let chunks = if has_error {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Error banner
+                Constraint::Min(0),    // Posts feed
+            ])
+            .split(area)
+    } else {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0), // Posts feed
+            ])
+            .split(area)
     };
 
     let mut chunk_idx = 0;
 
-    // Message banner (success messages - auto-clear after 3 seconds)
-    if let Some((message, _)) = &app.posts_state.message {
-        let message_banner = Paragraph::new(message.clone())
-            .style(Style::default().fg(theme.success).add_modifier(Modifier::BOLD))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("Message").border_style(Style::default().fg(theme.border)).style(Style::default().bg(theme.background)));
-        frame.render_widget(message_banner, chunks[chunk_idx]);
-        chunk_idx += 1;
-    }
-
     // Error banner (if present)
     if let Some(error) = &app.posts_state.error {
         let error_banner = Paragraph::new(error.clone())
@@ -583,12 +597,25 @@ pub fn render_posts_tab_with_data(frame: &mut Frame, app: &mut App, area: Rect)
     // Main posts area (no inline compose box - use 'n' to open modal)
     let posts_area = chunks[chunk_idx];
 
-    // Only show full-page loading on initial load (when there are no posts yet)
+    // On initial load (no posts yet) show skeleton cards in the shape of the feed,
+    // or a single line of text with reduced motion
     if app.posts_state.loading && app.posts_state.posts.is_empty() {
-        let loading = Paragraph::new(create_loading_display("Loading posts...", &theme))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("Global Feed"));
-        frame.render_widget(loading, posts_area);
+        let placeholder = if app.posts_state.reduced_motion {
+            Paragraph::new(Line::from(Span::styled(
+                "Loading posts...",
+                Style::default().fg(theme.text_dim),
+            )))
+            .block(Block::default().borders(Borders::ALL).title("Global Feed"))
+        } else {
+            let skeleton_width = (posts_area.width as usize).saturating_sub(4);
+            Paragraph::new(create_skeleton_posts(
+                posts_area.height as usize,
+                skeleton_width,
+                &theme,
+            ))
+            .block(Block::default().borders(Borders::ALL).title("Global Feed ⟳"))
+        };
+        frame.render_widget(placeholder, posts_area);
         
         // Render filter modal if open (even when loading)
         if app.posts_state.show_filter_modal {
@@ -631,8 +658,9 @@ pub fn render_posts_tab_with_data(frame: &mut Frame, app: &mut App, area: Rect)
 
     let available_width = posts_area.width.saturating_sub(BORDER_PADDING) as usize;
 
-    // Add loading spinner at top if refreshing (when posts already exist)
-    if app.posts_state.loading && !app.posts_state.posts.is_empty() {
+    // Add loading spinner at top if refreshing (when posts already exist);
+    // reduced motion keeps the list still and marks the title instead
+    if app.posts_state.items_before_posts() > 0 {
         let style = Style::default()
             .fg(theme.warning)
             .add_modifier(Modifier::BOLD);
@@ -646,6 +674,9 @@ pub fn render_posts_tab_with_data(frame: &mut Frame, app: &mut App, area: Rect)
     // Get the currently selected post index (if any)
     let selected_post_index = app.posts_state.list_state.selected()
         .and_then(|list_idx| app.posts_state.list_index_to_post_index(list_idx));
+    let (density, preview_lines, show_vote_counts) = app.feed_layout();
+    let show_karma = app.show_karma();
+    let compact = density == fido_types::FeedDensity::Compact;
 
     // Add posts
     let post_items: Vec<ListItem> = app
@@ -669,52 +700,111 @@ pub fn render_posts_tab_with_data(frame: &mut Frame, app: &mut App, area: Rect)
             };
 
             let prefix = if is_selected { "▶ " } else { "  " };
-            let timestamp = format_timestamp(&post.created_at);
+            let timestamp = post_time(post, app.timestamp_style(), app.exact_time_post);
 
-            post_lines.push(Line::from(vec![
+            // Checkboxes while marking posts in visual selection mode
+            let mark = match &app.posts_state.bulk_selection {
+                Some(marked) if marked.contains(&post.id) => Span::styled(
+                    "[x] ",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Some(_) => Span::styled("[ ] ", Style::default().fg(theme.text_dim)),
+                None => Span::raw(""),
+            };
+
+            let mut header = vec![
                 Span::styled(prefix, header_style),
-                Span::styled(format!("@{}", post.author_username), header_style),
+                mark,
+                Span::styled(post_author_label(post), post_author_style(post, header_style)),
+            ];
+            if show_karma {
+                header.push(Span::styled(karma_suffix(post), Style::default().fg(theme.text_dim)));
+            }
+            header.extend([
                 Span::raw(" • "),
                 Span::styled(timestamp, Style::default().fg(theme.text_dim)),
-            ]));
+            ]);
 
-            // Post content with hashtag highlighting and wrapping
-            let content_lines =
-                format_post_content_with_width(&post.content, is_selected, &theme, post_width);
-            post_lines.extend(content_lines);
-
-            // Vote counts with highlighting for user's vote
-            let user_voted_up = post.user_vote.as_deref() == Some("up");
-            let user_voted_down = post.user_vote.as_deref() == Some("down");
+            // Vote counts with highlighting for user's vote, then replies
+            let mut counts = Vec::new();
+            if show_vote_counts {
+                let (upvotes, downvotes) = vote_spans(post, &theme);
+                counts.extend([upvotes, Span::raw(" "), downvotes, Span::raw(" ")]);
+            }
+            counts.push(Span::styled(
+                format!("💬 {}", post.reply_count),
+                Style::default().fg(theme.text_dim),
+            ));
 
-            let upvote_style = if user_voted_up {
-                Style::default()
-                    .fg(theme.success)
-                    .add_modifier(Modifier::BOLD)
+            if compact {
+                // Counts ride on the header, followed by one line of the body
+                header.push(Span::raw("  "));
+                header.extend(counts);
+                post_lines.push(Line::from(header));
+                let preview_style = if is_selected {
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                post_lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        one_line_preview(post, post_width.saturating_sub(2)),
+                        preview_style,
+                    ),
+                ]));
             } else {
-                Style::default().fg(theme.text_dim)
-            };
+                post_lines.push(Line::from(header));
+
+                // Post content with hashtag highlighting and wrapping, cut to
+                // the preview length
+                let content_lines = format_post_body(
+                    post,
+                    is_selected,
+                    app.focused_token_in(post.id),
+                    app.shows_diff(post.id),
+                    &theme,
+                    post_width,
+                );
+                post_lines.extend(limit_preview(content_lines, preview_lines as usize, &theme));
+                if let Some(card) = github_card_of(post, &app.github_cards) {
+                    post_lines.extend(github_card_lines(card, &theme, post_width));
+                }
+
+                let mut counts_line = vec![Span::raw("  ")];
+                counts_line.extend(counts);
+                post_lines.push(Line::from(counts_line));
+            }
 
-            let downvote_style = if user_voted_down {
-                Style::default()
-                    .fg(theme.error)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(theme.text_dim)
-            };
+            // Back from a snooze, or still snoozed in the Later feed
+            if let Some(snoozed) = app.posts_state.read_later.iter().find(|s| s.post.id == post.id) {
+                let note = if snoozed.until <= chrono::Utc::now() {
+                    "⏰ Back from snooze".to_string()
+                } else {
+                    format!("⏰ Snoozed until {}", crate::status::format_expiry(snoozed.until))
+                };
+                post_lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(note, Style::default().fg(theme.warning)),
+                ]));
+            }
 
-            post_lines.push(Line::from(vec![
-                Span::raw("  "),
-                Span::styled(format!("↑ {} ", post.upvotes), upvote_style),
-                Span::styled(format!("↓ {} ", post.downvotes), downvote_style),
-                Span::styled(
-                    format!("💬 {}", post.reply_count),
-                    Style::default().fg(theme.text_dim),
-                ),
-            ]));
+            // Folded near-duplicates
+            if let Some(hidden) = app.posts_state.similar.get(&post.id) {
+                post_lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("+{} similar", hidden.len()),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(" · e: show", Style::default().fg(theme.text_dim)),
+                ]));
+            }
 
-            // Separator
-            if i < app.posts_state.posts.len() - 1 {
+            // Separator (compact posts sit back to back)
+            if !compact && i < app.posts_state.posts.len() - 1 {
                 post_lines.push(Line::from(""));
             }
 
@@ -740,18 +830,59 @@ pub fn render_posts_tab_with_data(frame: &mut Frame, app: &mut App, area: Rect)
     }
 
     // Build title with current filter
-    let title = match &app.posts_state.current_filter {
-        crate::app::PostFilter::All => "Global Feed".to_string(),
-        crate::app::PostFilter::Hashtag(tag) => format!("#{}", tag),
-        crate::app::PostFilter::User(username) => format!("@{}", username),
-        crate::app::PostFilter::Multi { hashtags, users } => {
-            let total = hashtags.len() + users.len();
-            format!("Filtered ({} items)", total)
+    let mut title = app.posts_state.current_filter.label();
+    if app.posts_state.loading && app.posts_state.reduced_motion {
+        title.push_str(" (refreshing)");
+    }
+    if let Some(input) = &app.posts_state.snooze_input {
+        title.push_str(&format!(
+            " · SNOOZE for: {}█ (30m, 3h, 1d, 1w · Enter · Esc)",
+            input
+        ));
+    }
+    if let Some(marked) = &app.posts_state.bulk_selection {
+        title.push_str(&format!(
+            " · VISUAL: {} marked (Space mark · b bookmark · m mute authors · o open · Esc)",
+            marked.len()
+        ));
+    }
+
+    // Show the selected author's status on the right of the header
+    let mut posts_block = Block::default().borders(Borders::ALL).title(title);
+    // Pinned hashtag shortcuts along the bottom, the active one highlighted
+    if let Some(config) = &app.settings_state.original_config {
+        if !config.pinned_hashtags.is_empty() {
+            let mut shortcuts = vec![Span::raw(" ")];
+            for (i, tag) in config.pinned_hashtags.iter().enumerate() {
+                let active = matches!(
+                    &app.posts_state.current_filter,
+                    crate::app::PostFilter::Hashtag(current) if current == tag
+                );
+                let style = if active {
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text_dim)
+                };
+                shortcuts.push(Span::styled(format!("{} #{}", i + 1, tag), style));
+                shortcuts.push(Span::raw(" "));
+            }
+            posts_block = posts_block.title_bottom(Line::from(shortcuts));
         }
-    };
+    }
+    if let Some(post) = selected_post_index.and_then(|i| app.posts_state.posts.get(i)) {
+        if let Some(status) = &post.author_status {
+            posts_block = posts_block.title(
+                Title::from(Span::styled(
+                    format!(" @{}: {} ", post.author_username, crate::status::format_status(status)),
+                    Style::default().fg(theme.text_dim),
+                ))
+                .alignment(Alignment::Right),
+            );
+        }
+    }
 
     let posts_widget = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(title))
+        .block(posts_block)
         .highlight_style(Style::default().bg(theme.highlight_bg));
 
     frame.render_stateful_widget(posts_widget, posts_area, &mut app.posts_state.list_state);
@@ -762,6 +893,30 @@ pub fn render_posts_tab_with_data(frame: &mut Frame, app: &mut App, area: Rect)
     }
 }
 
+/// One "▶ Label: value  (hint)" line of the settings list
+fn settings_row<'a>(
+    theme: &ThemeColors,
+    selected: bool,
+    label: &'a str,
+    value: &str,
+    hint: &'a str,
+) -> Line<'a> {
+    let style = if selected {
+        Style::default()
+            .fg(theme.success)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.text)
+    };
+    Line::from(vec![
+        Span::styled(if selected { "▶ " } else { "  " }, style),
+        Span::styled(label, Style::default().fg(theme.primary)),
+        Span::styled(value.to_string(), style),
+        Span::raw("  "),
+        Span::styled(hint, Style::default().fg(theme.text_dim)),
+    ])
+}
+
 /// Create a formatted error message display with optional help text
 /// 
 /// # Arguments
@@ -811,6 +966,38 @@ fn create_loading_display(message: &str, theme: &ThemeColors) -> Vec<Line<'stati
     ]
 }
 
+/// Create skeleton placeholder cards shaped like feed posts
+///
+/// Shown while the first page of posts is loading so the layout doesn't jump
+/// when real data arrives.
+///
+/// # Arguments
+/// * `height` - Rows available; enough cards are generated to fill them
+/// * `width` - Width available for each placeholder line
+/// * `theme` - The theme colors to use
+fn create_skeleton_posts(height: usize, width: usize, theme: &ThemeColors) -> Vec<Line<'static>> {
+    // Vary line lengths so the placeholders read as text rather than a solid block
+    const CONTENT_WIDTHS: [usize; 4] = [90, 70, 80, 55];
+    let style = Style::default()
+        .fg(theme.text_dim)
+        .add_modifier(Modifier::DIM);
+    let bar = |percent: usize| "░".repeat(width * percent / 100);
+
+    let mut lines = Vec::new();
+    let mut card = 0;
+    while lines.len() + 4 <= height {
+        lines.push(Line::from(Span::styled(format!("  {}", bar(25)), style)));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", bar(CONTENT_WIDTHS[card % CONTENT_WIDTHS.len()])),
+            style,
+        )));
+        lines.push(Line::from(Span::styled(format!("  {}", bar(15)), style)));
+        lines.push(Line::from(""));
+        card += 1;
+    }
+    lines
+}
+
 /// Create a centered indicator item for the feed
 /// 
 /// # Arguments
@@ -818,7 +1005,7 @@ fn create_loading_display(message: &str, theme: &ThemeColors) -> Vec<Line<'stati
 /// * `style` - The style to apply to the text
 /// * `available_width` - The available width for centering
 fn create_centered_indicator(text: &str, style: Style, available_width: usize) -> Vec<Line<'static>> {
-    let padding = (available_width.saturating_sub(text.len())) / 2;
+    let padding = (available_width.saturating_sub(text.width())) / 2;
     vec![
         Line::from(""),
         Line::from(Span::styled(
@@ -831,25 +1018,97 @@ fn create_centered_indicator(text: &str, style: Style, available_width: usize) -
 
 
 
-/// Format timestamp for display
-fn format_timestamp(timestamp: &chrono::DateTime<chrono::Utc>) -> String {
-    // Format as date and time
-    timestamp.format("%Y-%m-%d %H:%M").to_string()
-}
 
 /// Render DMs tab
+pub fn render_channels_tab(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = get_theme_colors(app);
+    let state = &app.channels_state;
+    let title = if state.loading { "Channels ⟳" } else { "Channels" };
+    let mut lines = vec![Line::from("")];
+
+    if let Some(input) = &state.new_channel_input {
+        lines.push(Line::from(vec![
+            Span::styled("  New channel: #", Style::default().fg(theme.primary)),
+            Span::styled(format!("{}█", input), Style::default().fg(theme.text)),
+        ]));
+        lines.push(Line::from(""));
+    }
+    if let Some(error) = &state.error {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            Style::default().fg(theme.error),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    if state.channels.is_empty() && !state.loading {
+        lines.push(Line::from(Span::styled(
+            "  No channels yet. Press n to start one.",
+            Style::default().fg(theme.text_dim),
+        )));
+    }
+
+    for (index, channel) in state.channels.iter().enumerate() {
+        let selected = index == state.selected;
+        let name_style = if selected {
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD)
+        } else if channel.joined {
+            Style::default().fg(theme.primary)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let mut spans = vec![
+            Span::styled(if selected { "▶ " } else { "  " }, name_style),
+            Span::styled(format!("#{}", channel.name), name_style),
+        ];
+        if channel.unread_count > 0 {
+            spans.push(Span::styled(
+                format!(" ({})", channel.unread_count),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            ));
+        }
+        spans.push(Span::styled(
+            format!(
+                "  {} member{}{}",
+                channel.member_count,
+                if channel.member_count == 1 { "" } else { "s" },
+                if channel.joined { " · joined" } else { "" }
+            ),
+            Style::default().fg(theme.text_dim),
+        ));
+        lines.push(Line::from(spans));
+        if !channel.description.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", channel.description),
+                Style::default().fg(theme.text_dim),
+            )));
+        }
+    }
+
+    let list = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(list, area);
+}
+
 pub fn render_dms_tab(frame: &mut Frame, app: &mut App, area: Rect) {
     let theme = get_theme_colors(app);
 
-    if app.dms_state.loading {
-        let loading = Paragraph::new(create_loading_display("Loading conversations...", &theme))
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Direct Messages"),
-            );
-        frame.render_widget(loading, area);
+    if app.dms_state.loading && app.dms_state.conversations.is_empty() {
+        let skeleton_width = (area.width as usize).saturating_sub(4);
+        let skeleton = Paragraph::new(create_skeleton_posts(
+            area.height as usize,
+            skeleton_width,
+            &theme,
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Direct Messages ⟳"),
+        );
+        frame.render_widget(skeleton, area);
         return;
     }
 
@@ -976,8 +1235,11 @@ pub fn render_conversations_list(frame: &mut Frame, app: &App, area: Rect) {
 
         let prefix = if is_selected { "▶ " } else { "  " };
 
-        // Username with unread indicator
+        // Username with pin and unread indicators
         let mut username_spans = vec![Span::styled(prefix, style)];
+        if convo.pinned {
+            username_spans.push(Span::styled("📌 ", Style::default().fg(theme.accent)));
+        }
         username_spans.push(Span::styled(&convo.other_username, style));
 
         if convo.unread_count > 0 {
@@ -989,16 +1251,26 @@ pub fn render_conversations_list(frame: &mut Frame, app: &App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ));
         }
+        if let Some(reminder) = app.dms_state.follow_ups.reminders.get(&convo.other_user_id) {
+            username_spans.push(Span::styled(
+                format!(" ⏰ {}", crate::status::format_expiry(reminder.due)),
+                Style::default().fg(theme.warning),
+            ));
+        }
 
         lines.push(Line::from(username_spans));
 
+        if is_selected {
+            if let Some(input) = &app.dms_state.reminder_input {
+                lines.push(Line::from(vec![
+                    Span::styled("  Remind me in: ", Style::default().fg(theme.primary)),
+                    Span::styled(format!("{}█", input), Style::default().fg(theme.text)),
+                ]));
+            }
+        }
+
         // Last message preview
-        let preview = if convo.last_message.chars().count() > 30 {
-            let truncated: String = convo.last_message.chars().take(30).collect();
-            format!("  {}", truncated)
-        } else {
-            format!("  {}", convo.last_message)
-        };
+        let preview = format!("  {}", take_width(&convo.last_message, 30));
 
         lines.push(Line::from(Span::styled(
             preview,
@@ -1091,7 +1363,20 @@ pub fn render_messages(frame: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
-    if app.dms_state.messages.is_empty() {
+    // Messages still on their way to the server go after the rest
+    let open_user_id = app
+        .dms_state
+        .selected_conversation_index
+        .and_then(|index| app.dms_state.conversations.get(index))
+        .map(|convo| convo.other_user_id);
+    let outgoing: Vec<&OutgoingMessage> = app
+        .dms_state
+        .outbox
+        .iter()
+        .filter(|m| Some(m.to_user_id) == open_user_id)
+        .collect();
+
+    if app.dms_state.messages.is_empty() && outgoing.is_empty() {
         let empty = Paragraph::new("No messages yet. Start the conversation!")
             .style(Style::default().fg(theme.text_dim))
             .alignment(Alignment::Center)
@@ -1106,9 +1391,13 @@ pub fn render_messages(frame: &mut Frame, app: &mut App, area: Rect) {
     let lines_per_message = 3; // header + content + blank
     let messages_per_screen = viewport_height / lines_per_message;
 
-    // Always show most recent messages (scroll to bottom by default)
+    // Show the most recent messages, or end at the one picked with [ / ]
     let total_messages = app.dms_state.messages.len();
-    let start_index = total_messages.saturating_sub(messages_per_screen);
+    let selected = app.dms_state.selected_message.filter(|&i| i < total_messages);
+    let end_index = selected.map_or(total_messages, |i| i + 1);
+    let shown_outgoing = if selected.is_some() { 0 } else { outgoing.len() };
+    let start_index =
+        end_index.saturating_sub(messages_per_screen.saturating_sub(shown_outgoing));
 
     let current_user_id = app.auth_state.current_user.as_ref().map(|u| u.id);
 
@@ -1118,7 +1407,14 @@ pub fn render_messages(frame: &mut Frame, app: &mut App, area: Rect) {
     let message_width = (area.width as usize).saturating_sub(6);
 
     // Render only visible messages (performance optimization for long conversations)
-    for msg in app.dms_state.messages.iter().skip(start_index) {
+    for (index, msg) in app
+        .dms_state
+        .messages
+        .iter()
+        .enumerate()
+        .take(end_index)
+        .skip(start_index)
+    {
         let is_from_me = Some(msg.from_user_id) == current_user_id;
 
         let timestamp = msg.created_at.format("%H:%M").to_string();
@@ -1131,13 +1427,27 @@ pub fn render_messages(frame: &mut Frame, app: &mut App, area: Rect) {
             Style::default().fg(theme.success)
         };
 
-        lines.push(Line::from(vec![
+        let mut header = vec![
             Span::styled(
                 format!("[{}] ", timestamp),
                 Style::default().fg(theme.text_dim),
             ),
             Span::styled(sender, header_style.add_modifier(Modifier::BOLD)),
-        ]));
+        ];
+        if is_from_me {
+            header.push(Span::styled(" ✓", Style::default().fg(theme.text_dim)));
+        }
+        if selected == Some(index) {
+            header.insert(
+                0,
+                Span::styled("▶ ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            );
+            header.push(Span::styled(
+                "  Ctrl+X: cross-post",
+                Style::default().fg(theme.text_dim),
+            ));
+        }
+        lines.push(Line::from(header));
 
         // Message content with wrapping
         for content_line in msg.content.lines() {
@@ -1151,9 +1461,97 @@ pub fn render_messages(frame: &mut Frame, app: &mut App, area: Rect) {
             }
         }
 
+        // Attachments start collapsed to a one-line summary (Ctrl+E)
+        if let Some(attachment) = &msg.attachment {
+            let expanded = app.dms_state.expanded_attachments.contains(&msg.id);
+            let line_count = attachment.content.lines().count();
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {} 📎 {} ({} line{})",
+                    if expanded { "▾" } else { "▸" },
+                    attachment.filename,
+                    line_count,
+                    if line_count == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(theme.accent),
+            )));
+            if expanded {
+                let code_width = message_width.saturating_sub(2);
+                for code_line in attachment.content.lines() {
+                    let shown = take_width(code_line, code_width).to_string();
+                    lines.push(Line::from(vec![
+                        Span::styled("  │ ", Style::default().fg(theme.border)),
+                        Span::styled(shown, Style::default().fg(theme.text)),
+                    ]));
+                }
+            }
+        }
+
         lines.push(Line::from(""));
     }
 
+    if shown_outgoing > 0 {
+        let me = app
+            .auth_state
+            .current_user
+            .as_ref()
+            .map(|u| u.username.as_str())
+            .unwrap_or("you");
+        let now = std::time::Instant::now();
+        for message in outgoing {
+            let status = match message.state {
+                DeliveryState::Pending => {
+                    Span::styled(" ⟳ sending", Style::default().fg(theme.text_dim))
+                }
+                DeliveryState::Failed => {
+                    let retry = match message.retry_at {
+                        Some(at) => format!(
+                            " · retrying in {}s",
+                            at.saturating_duration_since(now).as_secs() + 1
+                        ),
+                        None => String::new(),
+                    };
+                    Span::styled(
+                        format!(" ! not sent{} · r: retry now", retry),
+                        Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+                    )
+                }
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("[{}] ", message.queued_at.format("%H:%M")),
+                    Style::default().fg(theme.text_dim),
+                ),
+                Span::styled(
+                    me.to_string(),
+                    Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+                ),
+                status,
+            ]));
+            for content_line in message.content.lines() {
+                for wrapped_line in textwrap::wrap(content_line, message_width) {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", wrapped_line),
+                        Style::default().fg(theme.text_dim),
+                    )));
+                }
+            }
+            if let Some(attachment) = &message.attachment {
+                lines.push(Line::from(Span::styled(
+                    format!("  📎 {}", attachment.filename),
+                    Style::default().fg(theme.text_dim),
+                )));
+            }
+            if let Some(error) = &message.error {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", error),
+                    Style::default().fg(theme.error),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+    }
+
     let messages =
         Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Messages"));
     frame.render_widget(messages, area);
@@ -1189,9 +1587,13 @@ pub fn render_message_input(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Set block on textarea before rendering
     let title = if app.dms_state.pending_conversation_username.is_some() {
-        "Type your first message (Enter to send)"
+        "Type your first message (Enter to send)".to_string()
     } else {
-        "Message Input (Enter to send)"
+        "Message Input (Enter to send)".to_string()
+    };
+    let title = match &app.dms_state.pending_attachment {
+        Some(attachment) => format!("{} · 📎 {} (/attach to remove)", title, attachment.filename),
+        None => title,
     };
 
     // Apply theme styling to textarea - use primary color for text to ensure visibility
@@ -1224,20 +1626,7 @@ pub fn render_profile_tab(frame: &mut Frame, app: &mut App, area: Rect) {
     let theme = get_theme_colors(app);
     
     if app.profile_state.loading {
-        let loading = Paragraph::new(vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                "⟳ Loading profile...",
-                Style::default()
-                    .fg(theme.warning)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(Span::styled(
-                "Please wait",
-                Style::default().fg(theme.text_dim),
-            )),
-        ])
+        let loading = Paragraph::new(create_loading_display("Loading profile...", &theme))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).title("Profile").border_style(Style::default().fg(theme.border)).style(Style::default().bg(theme.background)));
         frame.render_widget(loading, area);
@@ -1257,13 +1646,27 @@ pub fn render_profile_tab(frame: &mut Frame, app: &mut App, area: Rect) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(10), // Profile stats
+                // Profile stats, plus a line for badges
+                Constraint::Length(
+                    10 + u16::from(!profile.badges.is_empty()) + u16::from(profile.status.is_some()),
+                ),
                 Constraint::Min(0),     // User posts (no footer - now in page actions bar)
             ])
             .split(area);
 
-        // Profile stats
-        render_profile_stats(frame, app, profile, chunks[0]);
+        // Profile stats, with posting activity beside them once loaded and
+        // if there's room
+        match &app.profile_state.stats {
+            Some(stats) if chunks[0].width >= 80 => {
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(chunks[0]);
+                render_profile_stats(frame, app, profile, columns[0]);
+                render_profile_activity(frame, app, stats, columns[1]);
+            }
+            _ => render_profile_stats(frame, app, profile, chunks[0]),
+        }
 
         // User posts
         render_user_posts(frame, app, chunks[1]);
@@ -1289,11 +1692,40 @@ pub fn render_profile_stats(
     lines.push(Line::from(vec![
         Span::styled("Username: ", Style::default().fg(theme.primary)),
         Span::styled(
-            &profile.username,
+            format!("{}{}", profile.username, badge_suffix(&profile.badges)),
             Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
     ]));
 
+    if !profile.badges.is_empty() {
+        let labels: Vec<String> = profile
+            .badges
+            .iter()
+            .map(|badge| format!("{} {}", badge.glyph(), badge.label()))
+            .collect();
+        lines.push(Line::from(vec![
+            Span::styled("Badges: ", Style::default().fg(theme.primary)),
+            Span::styled(labels.join("  "), Style::default().fg(theme.text)),
+        ]));
+    }
+
+    if let Some(status) = &profile.status {
+        lines.push(Line::from(vec![
+            Span::styled("Status: ", Style::default().fg(theme.primary)),
+            Span::styled(
+                crate::status::format_status(status),
+                Style::default().fg(theme.text).add_modifier(Modifier::ITALIC),
+            ),
+        ]));
+    }
+
+    if let Some(moved_to) = &profile.moved_to {
+        lines.push(Line::from(vec![
+            Span::styled("Moved to: ", Style::default().fg(theme.warning)),
+            Span::styled(moved_to.clone(), Style::default().fg(theme.text)),
+        ]));
+    }
+
     lines.push(Line::from(vec![
         Span::styled("Bio: ", Style::default().fg(theme.primary)),
         Span::styled(
@@ -1352,16 +1784,109 @@ pub fn render_profile_stats(
     frame.render_widget(stats, area);
 }
 
+/// Render the posting activity panel: posts per week, streaks, votes
+/// received, word counts and most used hashtags
+pub fn render_profile_activity(
+    frame: &mut Frame,
+    app: &App,
+    stats: &fido_types::UserStats,
+    area: Rect,
+) {
+    let theme = get_theme_colors(app);
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.primary));
+    let value = |text: String| {
+        Span::styled(text, Style::default().fg(theme.text).add_modifier(Modifier::BOLD))
+    };
+    let dim = |text: String| Span::styled(text, Style::default().fg(theme.text_dim));
+
+    let this_week = stats.posts_per_week.last().copied().unwrap_or(0);
+    let mut lines = vec![
+        Line::from(vec![
+            label("Posts/week: "),
+            Span::styled(sparkline(&stats.posts_per_week), Style::default().fg(theme.accent)),
+            dim(format!(" {} this week", this_week)),
+        ]),
+        Line::from(vec![
+            label("Streak: "),
+            value(format!(
+                "{} day{}",
+                stats.current_streak,
+                if stats.current_streak == 1 { "" } else { "s" }
+            )),
+            dim(format!(" (best {})", stats.longest_streak)),
+        ]),
+        Line::from(vec![
+            label("Votes received: "),
+            Span::styled(format!("↑ {}", stats.upvotes_received), Style::default().fg(theme.success)),
+            Span::raw("  "),
+            Span::styled(format!("↓ {}", stats.downvotes_received), Style::default().fg(theme.error)),
+        ]),
+    ];
+
+    let average = stats.total_words.checked_div(stats.total_posts).unwrap_or(0);
+    lines.push(Line::from(vec![
+        label("Words: "),
+        value(stats.total_words.to_string()),
+        dim(format!(
+            " in {} posts, ~{} each · {} chars",
+            stats.total_posts, average, stats.total_characters
+        )),
+    ]));
+
+    if !stats.top_hashtags.is_empty() {
+        let mut spans = vec![label("Most used: ")];
+        for (i, tag) in stats.top_hashtags.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(", "));
+            }
+            spans.push(Span::styled(format!("#{}", tag.name), Style::default().fg(theme.accent)));
+            spans.push(dim(format!(" {}", tag.count)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let panel = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Activity"));
+    frame.render_widget(panel, area);
+}
+
 /// Render user posts
 pub fn render_user_posts(frame: &mut Frame, app: &mut App, area: Rect) {
     // Get theme colors
     let theme = get_theme_colors(app);
-    
+    let tab = app.profile_state.posts_tab;
+
+    // Sub-tabs in the title, the open one highlighted
+    let mut title = vec![Span::raw(" ")];
+    for candidate in crate::app::ProfilePostsTab::ALL {
+        let style = if candidate == tab {
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(theme.text_dim)
+        };
+        title.push(Span::styled(candidate.label(), style));
+        title.push(Span::raw("  "));
+    }
+    title.push(Span::styled("(←/→) ", Style::default().fg(theme.text_dim)));
+    let title = Line::from(title);
+
     if app.profile_state.user_posts.is_empty() {
-        let empty = Paragraph::new("No posts yet")
+        let message = if app.task_runner.is_running(crate::app::TaskKind::ProfilePosts) {
+            "Loading..."
+        } else {
+            match tab {
+                crate::app::ProfilePostsTab::Posts => "No posts yet",
+                crate::app::ProfilePostsTab::Replies => "No replies yet",
+                crate::app::ProfilePostsTab::Upvoted => "No upvoted posts yet",
+            }
+        };
+        let empty = Paragraph::new(message)
             .style(Style::default().fg(theme.text_dim))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("Your Posts").border_style(Style::default().fg(theme.border)).style(Style::default().bg(theme.background)));
+            .block(Block::default().borders(Borders::ALL).title(title).border_style(Style::default().fg(theme.border)).style(Style::default().bg(theme.background)));
         frame.render_widget(empty, area);
         return;
     }
@@ -1380,7 +1905,7 @@ pub fn render_user_posts(frame: &mut Frame, app: &mut App, area: Rect) {
             let mut post_lines: Vec<Line> = Vec::new();
 
             let prefix = if is_selected { "▶ " } else { "  " };
-            let timestamp = post.created_at.format("%Y-%m-%d %H:%M").to_string();
+            let timestamp = post_time(post, app.timestamp_style(), app.exact_time_post);
 
             let header_style = if is_selected {
                 Style::default()
@@ -1390,40 +1915,39 @@ pub fn render_user_posts(frame: &mut Frame, app: &mut App, area: Rect) {
                 Style::default().fg(theme.primary)
             };
 
-            post_lines.push(Line::from(vec![
-                Span::styled(prefix, header_style),
-                Span::styled(timestamp, Style::default().fg(theme.text_dim)),
-            ]));
+            // Upvoted posts are other people's, so they name their author;
+            // replies name who they answered
+            let mut header = vec![Span::styled(prefix, header_style)];
+            match tab {
+                crate::app::ProfilePostsTab::Posts => {}
+                crate::app::ProfilePostsTab::Replies => {
+                    if let Some(username) = &post.reply_to_username {
+                        header.push(Span::styled(format!("↳ @{}", username), header_style));
+                        header.push(Span::raw(" • "));
+                    }
+                }
+                crate::app::ProfilePostsTab::Upvoted => {
+                    header.push(Span::styled(post_author_label(post), post_author_style(post, header_style)));
+                    header.push(Span::raw(" • "));
+                }
+            }
+            header.push(Span::styled(timestamp, Style::default().fg(theme.text_dim)));
+            post_lines.push(Line::from(header));
 
             // Post content with wrapping
             let content_lines =
-                format_post_content_with_width(&post.content, is_selected, &theme, post_width);
+                format_post_body(post, is_selected, None, app.shows_diff(post.id), &theme, post_width);
             post_lines.extend(content_lines);
 
             // Vote counts with highlighting for user's vote
-            let user_voted_up = post.user_vote.as_deref() == Some("up");
-            let user_voted_down = post.user_vote.as_deref() == Some("down");
-
-            let upvote_style = if user_voted_up {
-                Style::default()
-                    .fg(theme.success)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(theme.text_dim)
-            };
-
-            let downvote_style = if user_voted_down {
-                Style::default()
-                    .fg(theme.error)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(theme.text_dim)
-            };
+            let (upvotes, downvotes) = vote_spans(post, &theme);
 
             post_lines.push(Line::from(vec![
                 Span::raw("  "),
-                Span::styled(format!("↑ {} ", post.upvotes), upvote_style),
-                Span::styled(format!("↓ {} ", post.downvotes), downvote_style),
+                upvotes,
+                Span::raw(" "),
+                downvotes,
+                Span::raw(" "),
                 Span::styled(
                     format!("💬 {}", post.reply_count),
                     Style::default().fg(theme.text_dim),
@@ -1439,7 +1963,7 @@ pub fn render_user_posts(frame: &mut Frame, app: &mut App, area: Rect) {
         .collect();
 
     let posts_widget = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title("Your Posts"))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(Style::default().bg(theme.highlight_bg));
 
     frame.render_stateful_widget(posts_widget, area, &mut app.profile_state.list_state);
@@ -1450,20 +1974,7 @@ pub fn render_settings_tab(frame: &mut Frame, app: &mut App, area: Rect) {
     let theme = get_theme_colors(app);
 
     if app.settings_state.loading {
-        let loading = Paragraph::new(vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                "⟳ Loading settings...",
-                Style::default()
-                    .fg(theme.warning)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(Span::styled(
-                "Please wait",
-                Style::default().fg(theme.text_dim),
-            )),
-        ])
+        let loading = Paragraph::new(create_loading_display("Loading settings...", &theme))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).title("Settings"));
         frame.render_widget(loading, area);
@@ -1473,22 +1984,15 @@ pub fn render_settings_tab(frame: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Error/Success message
+            Constraint::Length(3), // Error message
             Constraint::Min(0), // Settings form (no instructions footer - now in page actions bar)
         ])
         .split(area);
 
-    // Error/Success message
+    // Error message
     if let Some(error) = &app.settings_state.error {
-        let is_success = error.contains("successfully");
-        let style = if is_success {
-            Style::default().fg(theme.success)
-        } else {
-            Style::default().fg(theme.error)
-        };
-
         let message = Paragraph::new(error.clone())
-            .style(style)
+            .style(Style::default().fg(theme.error))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
         frame.render_widget(message, chunks[0]);
@@ -1555,9 +2059,9 @@ pub fn render_settings_tab(frame: &mut Frame, app: &mut App, area: Rect) {
 
         let max_posts_value =
             if max_posts_selected && !app.settings_state.max_posts_input.is_empty() {
-                &app.settings_state.max_posts_input
+                app.settings_state.max_posts_input.value().to_string()
             } else {
-                &config.max_posts_display.to_string()
+                config.max_posts_display.to_string()
             };
 
         lines.push(Line::from(vec![
@@ -1573,6 +2077,223 @@ pub fn render_settings_tab(frame: &mut Frame, app: &mut App, area: Rect) {
 
         lines.push(Line::from(""));
 
+        // Vote Visibility
+        let vote_privacy_selected =
+            app.settings_state.selected_field == crate::app::SettingsField::VotePrivacy;
+        let vote_privacy_style = if vote_privacy_selected {
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                if vote_privacy_selected { "▶ " } else { "  " },
+                vote_privacy_style,
+            ),
+            Span::styled("Vote Visibility: ", Style::default().fg(theme.primary)),
+            Span::styled(
+                if config.votes_visible_to_authors {
+                    "Visible to authors"
+                } else {
+                    "Hidden from authors"
+                },
+                vote_privacy_style,
+            ),
+            Span::raw("  "),
+            Span::styled("(←/→ to toggle)", Style::default().fg(theme.text_dim)),
+        ]));
+
+        lines.push(Line::from(""));
+
+        // Do not disturb and quiet hours (muting is applied by this client)
+        let field = &app.settings_state.selected_field;
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::DoNotDisturb,
+            "Do Not Disturb: ",
+            if config.do_not_disturb { "On" } else { "Off" },
+            "(←/→ to toggle)",
+        ));
+        lines.push(Line::from(""));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::QuietHours,
+            "Quiet Hours: ",
+            if config.quiet_hours_enabled { "On" } else { "Off" },
+            "(←/→ to toggle, mutes toasts and unread badges)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::QuietHoursStart,
+            "  From: ",
+            &format!("{:02}:00", config.quiet_hours_start),
+            "(←/→ to change)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::QuietHoursEnd,
+            "  Until: ",
+            &format!("{:02}:00", config.quiet_hours_end),
+            "(←/→ to change)",
+        ));
+        lines.push(Line::from(""));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::TabUnreadCounts,
+            "Tab Unread Counts: ",
+            if config.show_unread_counts { "Shown" } else { "Hidden" },
+            "(←/→ to toggle)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::Digest,
+            "Digest: ",
+            config.digest_frequency.as_str(),
+            "(←/→ to change: Off, Daily, Weekly; shown as \"While you were away\" on login)",
+        ));
+
+        lines.push(Line::from(""));
+
+        // Accessibility (applied by this client)
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::AccessibleLayout,
+            "Accessible Layout: ",
+            if config.accessible_layout { "On" } else { "Off" },
+            "(←/→ to toggle, plain single-column feed and DMs)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::Announcements,
+            "Announcements: ",
+            config.announcements.as_str(),
+            "(←/→ to change: Off, Terminal notifications, Speech via spd-say)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::ReducedMotion,
+            "Reduced Motion: ",
+            if config.reduced_motion { "On" } else { "Off" },
+            "(←/→ to toggle, no loading animations, fewer redraws over SSH)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::Timestamps,
+            "Timestamps: ",
+            config.timestamp_style.as_str(),
+            "(←/→ to toggle: local date and time, or \"2h ago\"; T shows a post's exact time)",
+        ));
+
+        lines.push(Line::from(""));
+
+        // Feed layout (applied by this client)
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::FeedDensity,
+            "Feed Density: ",
+            config.feed_density.as_str(),
+            "(←/→ to toggle: wrapped posts, or one line each)",
+        ));
+        let preview_lines = match config.preview_lines {
+            0 => "All".to_string(),
+            limit => limit.to_string(),
+        };
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::PreviewLines,
+            "Preview Lines: ",
+            &preview_lines,
+            "(←/→ to change, longer posts end in \"… more\"; comfortable feed only)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::VoteCounts,
+            "Vote Counts: ",
+            if config.show_vote_counts { "Shown" } else { "Hidden" },
+            "(←/→ to toggle, in the feed)",
+        ));
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::Karma,
+            "Karma: ",
+            if config.show_karma { "Shown" } else { "Hidden" },
+            "(←/→ to toggle, next to usernames in the feed and threads)",
+        ));
+        let pinned = match &app.settings_state.pinned_hashtags_input {
+            Some(input) => input.value().to_string(),
+            None if config.pinned_hashtags.is_empty() => "None".to_string(),
+            None => config
+                .pinned_hashtags
+                .iter()
+                .enumerate()
+                .map(|(i, tag)| format!("{} #{}", i + 1, tag))
+                .collect::<Vec<_>>()
+                .join("  "),
+        };
+        let mut pinned_row = settings_row(
+            &theme,
+            *field == crate::app::SettingsField::PinnedHashtags,
+            "Pinned Hashtags: ",
+            &pinned,
+            if app.settings_state.pinned_hashtags_input.is_some() {
+                "(up to 9, separated by spaces; Enter to keep, Esc to cancel)"
+            } else {
+                "(Enter to edit; keys 1-9 switch the feed to them)"
+            },
+        );
+        if let Some(input) = &app.settings_state.pinned_hashtags_input {
+            // Swap the plain value for the text with its cursor
+            let style = pinned_row.spans[2].style;
+            pinned_row.spans.splice(2..3, input.spans(style));
+        }
+        lines.push(pinned_row);
+        lines.push(settings_row(
+            &theme,
+            *field == crate::app::SettingsField::Discoverable,
+            "Search Engines: ",
+            if config.discoverable { "May index" } else { "Hidden" },
+            "(←/→ to toggle, for your /u and /p web pages)",
+        ));
+
+        lines.push(Line::from(""));
+
+        // Session encryption (this machine only, applies immediately)
+        let encryption_selected =
+            app.settings_state.selected_field == crate::app::SettingsField::SessionEncryption;
+        let encryption_style = if encryption_selected {
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                if encryption_selected { "▶ " } else { "  " },
+                encryption_style,
+            ),
+            Span::styled("Saved Sessions: ", Style::default().fg(theme.primary)),
+            Span::styled(
+                if app.server_config.encrypt_sessions() {
+                    "Encrypted (OS keychain)"
+                } else {
+                    "Plain files"
+                },
+                encryption_style,
+            ),
+            Span::raw("  "),
+            Span::styled(
+                "(←/→ to toggle, this device only)",
+                Style::default().fg(theme.text_dim),
+            ),
+        ]));
+
+        lines.push(Line::from(""));
+
         // Show unsaved changes indicator
         if app.settings_state.has_unsaved_changes {
             lines.push(Line::from(vec![
@@ -0,0 +1,260 @@
+//! Render the TUI into memory and drive it with scripted input, for tests.
+//!
+//! [`TuiHarness`] owns an [`App`] and a ratatui `TestBackend`. Input scripts
+//! list keystrokes separated by whitespace:
+//!
+//! ```text
+//! # whole-line comments start with '#'
+//! j j k                 each character of a bare word is one keypress
+//! "hello world #rust"   quoted text is typed as-is, spaces included
+//! <Enter> <Esc> <Up>    named keys
+//! <C-s> <S-Tab> <A-x>   Ctrl, Shift and Alt, combinable as <C-S-s>
+//! ```
+//!
+//! Keys go through [`App::handle_key_event`], the synchronous handler. The
+//! few shortcuts `main.rs` runs asynchronously (logging in, opening a thread,
+//! submitting) are set up directly on `harness.app` instead.
+//!
+//! Snapshots are plain-text screen dumps under `src/ui/snapshots/`. A missing
+//! snapshot is recorded on first run; set `UPDATE_SNAPSHOTS=1` to accept
+//! intentional changes.
+
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::App;
+
+pub struct TuiHarness {
+    pub app: App,
+    terminal: Terminal<TestBackend>,
+}
+
+impl TuiHarness {
+    pub fn new(app: App, width: u16, height: u16) -> Self {
+        let terminal =
+            Terminal::new(TestBackend::new(width, height)).expect("test terminal never fails");
+        Self { app, terminal }
+    }
+
+    /// Draw one frame, as the event loop does after every event
+    pub fn render(&mut self) -> &mut Self {
+        let app = &mut self.app;
+        self.terminal
+            .draw(|frame| super::render(app, frame))
+            .expect("test terminal never fails");
+        self
+    }
+
+    /// Feed an input script to the app, rendering after each key
+    pub fn run_script(&mut self, script: &str) -> Result<&mut Self> {
+        for key in parse_script(script)? {
+            self.app.handle_key_event(key)?;
+            self.render();
+        }
+        Ok(self)
+    }
+
+    /// The last frame as text, one line per row with trailing spaces removed
+    pub fn screen(&self) -> String {
+        let buffer = self.terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        let mut lines = Vec::new();
+        for row in buffer.content.chunks(width) {
+            let mut line = String::new();
+            let mut skip = 0;
+            for cell in row {
+                // Wide glyphs cover the cells after them
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                line.push_str(cell.symbol());
+                skip = cell.symbol().width().saturating_sub(1);
+            }
+            lines.push(line.trim_end().to_string());
+        }
+        lines.join("\n")
+    }
+
+    /// Compare the last frame with `src/ui/snapshots/<name>.snap`
+    pub fn assert_snapshot(&self, name: &str) {
+        let path = snapshot_path(name);
+        let actual = format!("{}\n", self.screen());
+        let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) if !update => panic!(
+                "Snapshot '{}' changed. Rerun with UPDATE_SNAPSHOTS=1 if this is intended.\n\
+                 --- expected ---\n{}\n--- actual ---\n{}",
+                name, expected, actual
+            ),
+            _ => {
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(&path, &actual).unwrap();
+                eprintln!("Recorded snapshot {}", path.display());
+            }
+        }
+    }
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{}.snap", name))
+}
+
+/// Turn an input script into key events
+pub fn parse_script(script: &str) -> Result<Vec<KeyEvent>> {
+    let mut keys = Vec::new();
+    for line in script.lines() {
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                c if c.is_whitespace() => {}
+                '"' => {
+                    let mut closed = false;
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '"' => {
+                                closed = true;
+                                break;
+                            }
+                            '\\' => {
+                                let escaped = chars
+                                    .next()
+                                    .ok_or_else(|| anyhow!("Dangling '\\' in {:?}", line))?;
+                                keys.push(char_key(escaped));
+                            }
+                            c => keys.push(char_key(c)),
+                        }
+                    }
+                    if !closed {
+                        bail!("Unclosed quote in {:?}", line);
+                    }
+                }
+                '<' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('>') => break,
+                            Some(c) => name.push(c),
+                            None => bail!("Unclosed '<' in {:?}", line),
+                        }
+                    }
+                    keys.push(named_key(&name)?);
+                }
+                c => {
+                    keys.push(char_key(c));
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                        keys.push(char_key(c));
+                    }
+                }
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// A typed character; capitals carry SHIFT like crossterm reports them
+fn char_key(c: char) -> KeyEvent {
+    let modifiers = if c.is_uppercase() {
+        KeyModifiers::SHIFT
+    } else {
+        KeyModifiers::NONE
+    };
+    KeyEvent::new(KeyCode::Char(c), modifiers)
+}
+
+/// `<Name>` with optional `C-`, `S-` and `A-` prefixes
+fn named_key(name: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name;
+    loop {
+        if let Some(stripped) = rest.strip_prefix("C-") {
+            modifiers |= KeyModifiers::CONTROL;
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix("S-") {
+            modifiers |= KeyModifiers::SHIFT;
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix("A-") {
+            modifiers |= KeyModifiers::ALT;
+            rest = stripped;
+        } else {
+            break;
+        }
+    }
+
+    let code = match rest {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::BackTab
+        }
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Space" => KeyCode::Char(' '),
+        single if single.chars().count() == 1 => {
+            let c = single.chars().next().unwrap();
+            if c.is_uppercase() {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            KeyCode::Char(c)
+        }
+        _ => bail!("Unknown key <{}>", name),
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_script() {
+        let keys = parse_script(
+            "# open the thread\n\
+             jk <Enter>\n\
+             \"a #b\" <C-S-s> <S-Tab> K",
+        )
+        .unwrap();
+        let keys: Vec<_> = keys.into_iter().map(|k| (k.code, k.modifiers)).collect();
+        assert_eq!(
+            keys,
+            vec![
+                (KeyCode::Char('j'), KeyModifiers::NONE),
+                (KeyCode::Char('k'), KeyModifiers::NONE),
+                (KeyCode::Enter, KeyModifiers::NONE),
+                (KeyCode::Char('a'), KeyModifiers::NONE),
+                (KeyCode::Char(' '), KeyModifiers::NONE),
+                (KeyCode::Char('#'), KeyModifiers::NONE),
+                (KeyCode::Char('b'), KeyModifiers::NONE),
+                (
+                    KeyCode::Char('s'),
+                    KeyModifiers::CONTROL | KeyModifiers::SHIFT
+                ),
+                (KeyCode::BackTab, KeyModifiers::SHIFT),
+                (KeyCode::Char('K'), KeyModifiers::SHIFT),
+            ]
+        );
+        assert!(parse_script("<Nope>").is_err());
+        assert!(parse_script("\"open").is_err());
+    }
+}
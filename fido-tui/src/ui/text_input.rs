@@ -0,0 +1,240 @@
+//! Single-line text input shared by the small fields around the app (bio,
+//! hashtag names, usernames, numbers). The cursor counts characters, not
+//! bytes, so editing never splits a multi-byte character, and each field can
+//! cap its length and say which characters it takes.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Span;
+
+/// Text being typed into a one-line field
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Cursor position in characters, 0..=value.chars().count()
+    cursor: usize,
+    max_chars: Option<usize>,
+    accepts: Option<fn(char) -> bool>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignore typing past `max_chars` characters
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = Some(max_chars);
+        self
+    }
+
+    /// Only take the characters `accepts` allows; others are dropped as typed
+    pub fn with_filter(mut self, accepts: fn(char) -> bool) -> Self {
+        self.accepts = Some(accepts);
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Replace the text, keeping only what the field accepts, and put the
+    /// cursor at the end
+    pub fn set_value(&mut self, value: &str) {
+        self.clear();
+        self.insert_str(value);
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    fn char_count(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    fn accepts(&self, c: char) -> bool {
+        !c.is_control() && self.accepts.is_none_or(|accepts| accepts(c))
+    }
+
+    /// Type `c` at the cursor; false if the field refused it
+    pub fn insert_char(&mut self, c: char) -> bool {
+        if !self.accepts(c) || self.max_chars.is_some_and(|max| self.char_count() >= max) {
+            return false;
+        }
+        let at = self.byte_index(self.cursor);
+        self.value.insert(at, c);
+        self.cursor += 1;
+        true
+    }
+
+    /// Insert pasted text at the cursor. Line breaks become spaces; whatever
+    /// else the field doesn't take, or doesn't have room for, is dropped.
+    pub fn insert_str(&mut self, text: &str) -> bool {
+        let mut changed = false;
+        for c in text.chars() {
+            let c = if c == '\n' || c == '\t' { ' ' } else { c };
+            changed |= self.insert_char(c);
+        }
+        changed
+    }
+
+    /// Delete the character before the cursor
+    pub fn backspace(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        let at = self.byte_index(self.cursor);
+        self.value.remove(at);
+        true
+    }
+
+    /// Delete the character under the cursor
+    pub fn delete(&mut self) -> bool {
+        if self.cursor >= self.char_count() {
+            return false;
+        }
+        let at = self.byte_index(self.cursor);
+        self.value.remove(at);
+        true
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.char_count());
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.char_count();
+    }
+
+    /// Apply an editing key. Returns true when the text changed; keys it
+    /// doesn't use (Enter, Esc, Up/Down) are left to the caller.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.move_home(),
+            KeyCode::Char('e') if ctrl => self.move_end(),
+            KeyCode::Char('u') if ctrl => {
+                let changed = !self.is_empty();
+                self.clear();
+                return changed;
+            }
+            KeyCode::Char(_) if ctrl => {}
+            KeyCode::Char(c) => return self.insert_char(c),
+            KeyCode::Backspace => return self.backspace(),
+            KeyCode::Delete => return self.delete(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.move_home(),
+            KeyCode::End => self.move_end(),
+            _ => {}
+        }
+        false
+    }
+
+    /// The text with a block cursor, for fields that are being edited
+    pub fn spans(&self, style: Style) -> Vec<Span<'static>> {
+        let at = self.byte_index(self.cursor);
+        let (before, rest) = self.value.split_at(at);
+        let mut chars = rest.chars();
+        let under_cursor = chars.next();
+        let after = chars.as_str();
+        vec![
+            Span::styled(before.to_string(), style),
+            match under_cursor {
+                Some(c) => Span::styled(c.to_string(), style.add_modifier(Modifier::REVERSED)),
+                None => Span::styled("█", style),
+            },
+            Span::styled(after.to_string(), style),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_editing_in_the_middle_of_multibyte_text() {
+        let mut input = TextInput::new();
+        input.set_value("héllo 🦀");
+        let spans = input.spans(Style::default());
+        assert_eq!(spans[0].content, "héllo 🦀");
+        assert_eq!(spans[1].content, "█");
+
+        input.move_home();
+        input.move_right();
+        input.move_right();
+        assert!(input.backspace());
+        assert!(input.insert_char('e'));
+        assert_eq!(input.value(), "hello 🦀");
+
+        input.move_end();
+        input.move_left();
+        assert!(input.delete());
+        assert!(!input.delete());
+        assert_eq!(input.value(), "hello ");
+    }
+
+    #[test]
+    fn test_limits_and_filters_apply_to_typing_and_pasting() {
+        let mut input = TextInput::new()
+            .with_max_chars(3)
+            .with_filter(|c| c.is_ascii_digit());
+        assert!(!input.handle_key(key(KeyCode::Char('x'))));
+        assert!(input.handle_key(key(KeyCode::Char('4'))));
+        assert!(input.insert_str("2a07\n9"));
+        assert_eq!(input.value(), "420");
+        assert!(!input.insert_char('1'));
+
+        // Clearing keeps the field's rules
+        input.clear();
+        input.set_value("12345");
+        assert_eq!(input.value(), "123");
+    }
+
+    #[test]
+    fn test_pasted_line_breaks_become_spaces() {
+        let mut input = TextInput::new();
+        input.insert_str("one\ntwo\r\n");
+        assert_eq!(input.value(), "one two ");
+    }
+
+    #[test]
+    fn test_control_keys_move_and_clear() {
+        let mut input = TextInput::new();
+        input.set_value("rust");
+        input.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL));
+        input.insert_char('>');
+        input.handle_key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL));
+        input.insert_char('<');
+        assert_eq!(input.value(), ">rust<");
+        assert!(input.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL)));
+        assert!(input.is_empty());
+    }
+}
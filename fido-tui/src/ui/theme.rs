@@ -85,5 +85,50 @@ pub fn get_theme_colors(app: &App) -> ThemeColors {
             error: Color::Rgb(220, 50, 47),      // Solarized red
             highlight_bg: Color::Rgb(7, 54, 66), // Solarized base02
         },
+
+        // High Contrast - Pure black and white with saturated accents
+        fido_types::ColorScheme::HighContrast => ThemeColors {
+            primary: Color::Rgb(255, 255, 0),    // Yellow
+            secondary: Color::Rgb(0, 255, 255),  // Cyan
+            accent: Color::Rgb(255, 0, 255),     // Magenta
+            text: Color::Rgb(255, 255, 255),     // White
+            text_dim: Color::Rgb(200, 200, 200), // Light gray, still legible
+            background: Color::Black,
+            border: Color::Rgb(255, 255, 255),
+            success: Color::Rgb(0, 255, 255),    // Cyan
+            warning: Color::Rgb(255, 255, 0),    // Yellow
+            error: Color::Rgb(255, 128, 0),      // Orange
+            highlight_bg: Color::Rgb(0, 0, 160), // Deep blue
+        },
+
+        // Deuteranopia - Okabe-Ito blue/orange pairs instead of red/green
+        fido_types::ColorScheme::Deuteranopia => ThemeColors {
+            primary: Color::Rgb(86, 180, 233),   // Sky blue
+            secondary: Color::Rgb(0, 114, 178),  // Blue
+            accent: Color::Rgb(204, 121, 167),   // Reddish purple
+            text: Color::Rgb(230, 230, 230),     // Light gray
+            text_dim: Color::Rgb(140, 140, 140), // Medium gray
+            background: Color::Rgb(15, 15, 20),  // Near black
+            border: Color::Rgb(70, 70, 80),      // Dark gray
+            success: Color::Rgb(86, 180, 233),   // Sky blue
+            warning: Color::Rgb(240, 228, 66),   // Yellow
+            error: Color::Rgb(230, 159, 0),      // Orange
+            highlight_bg: Color::Rgb(35, 45, 60), // Slate
+        },
+
+        // Protanopia - Okabe-Ito blue/yellow pairs, avoiding dim reds entirely
+        fido_types::ColorScheme::Protanopia => ThemeColors {
+            primary: Color::Rgb(0, 158, 115),    // Bluish green
+            secondary: Color::Rgb(86, 180, 233), // Sky blue
+            accent: Color::Rgb(240, 228, 66),    // Yellow
+            text: Color::Rgb(230, 230, 230),     // Light gray
+            text_dim: Color::Rgb(140, 140, 140), // Medium gray
+            background: Color::Rgb(15, 15, 20),  // Near black
+            border: Color::Rgb(70, 70, 80),      // Dark gray
+            success: Color::Rgb(0, 114, 178),    // Blue
+            warning: Color::Rgb(240, 228, 66),   // Yellow
+            error: Color::Rgb(213, 94, 0),       // Vermillion
+            highlight_bg: Color::Rgb(35, 45, 60), // Slate
+        },
     }
 }
@@ -0,0 +1,60 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::{App, ToastLevel};
+use super::theme::get_theme_colors;
+
+/// Rows kept clear under the toasts so they sit above the footer and status bar
+const BOTTOM_MARGIN: u16 = 5;
+const TOAST_HEIGHT: u16 = 3;
+
+/// Stack the visible toasts in the bottom-right corner, newest at the bottom
+pub fn render_toasts(frame: &mut Frame, app: &App, area: Rect) {
+    if app.toasts.is_empty() {
+        return;
+    }
+    let theme = get_theme_colors(app);
+    let max_width = (area.width / 2).max(20).min(area.width);
+
+    let mut bottom = area.bottom().saturating_sub(BOTTOM_MARGIN);
+    for toast in app.toasts.iter().rev() {
+        if bottom < area.y + TOAST_HEIGHT {
+            break;
+        }
+        let width = (toast.message.width() as u16 + 4).min(max_width);
+        let rect = Rect {
+            x: area.right().saturating_sub(width + 1),
+            y: bottom - TOAST_HEIGHT,
+            width,
+            height: TOAST_HEIGHT,
+        };
+        bottom -= TOAST_HEIGHT;
+
+        let color = match toast.level {
+            ToastLevel::Info => theme.primary,
+            ToastLevel::Success => theme.success,
+            ToastLevel::Error => theme.error,
+        };
+        frame.render_widget(Clear, rect);
+        frame.render_widget(
+            Paragraph::new(toast.message.as_str())
+                .style(
+                    Style::default()
+                        .fg(color)
+                        .bg(theme.background)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(color)),
+                ),
+            rect,
+        );
+    }
+}
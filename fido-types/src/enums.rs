@@ -31,6 +31,9 @@ pub enum ColorScheme {
     Dark,
     Light,
     Solarized,
+    HighContrast,
+    Deuteranopia,
+    Protanopia,
 }
 
 impl ColorScheme {
@@ -40,6 +43,9 @@ impl ColorScheme {
             ColorScheme::Dark => "Dark",
             ColorScheme::Light => "Light",
             ColorScheme::Solarized => "Solarized",
+            ColorScheme::HighContrast => "HighContrast",
+            ColorScheme::Deuteranopia => "Deuteranopia",
+            ColorScheme::Protanopia => "Protanopia",
         }
     }
 
@@ -49,6 +55,9 @@ impl ColorScheme {
             "Dark" => Some(ColorScheme::Dark),
             "Light" => Some(ColorScheme::Light),
             "Solarized" => Some(ColorScheme::Solarized),
+            "HighContrast" => Some(ColorScheme::HighContrast),
+            "Deuteranopia" => Some(ColorScheme::Deuteranopia),
+            "Protanopia" => Some(ColorScheme::Protanopia),
             _ => None,
         }
     }
@@ -80,3 +89,380 @@ impl SortOrder {
         }
     }
 }
+
+/// How post times are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TimestampStyle {
+    /// Local date and time in the terminal's locale
+    #[default]
+    Absolute,
+    /// "5m ago", "2h ago", falling back to the date after a week
+    Relative,
+}
+
+impl TimestampStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimestampStyle::Absolute => "Absolute",
+            TimestampStyle::Relative => "Relative",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Absolute" => Some(TimestampStyle::Absolute),
+            "Relative" => Some(TimestampStyle::Relative),
+            _ => None,
+        }
+    }
+}
+
+/// How much room each post takes in the feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum FeedDensity {
+    /// Header, wrapped body and a line of counts
+    #[default]
+    Comfortable,
+    /// Header with counts and a single line of the body
+    Compact,
+}
+
+impl FeedDensity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeedDensity::Comfortable => "Comfortable",
+            FeedDensity::Compact => "Compact",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Comfortable" => Some(FeedDensity::Comfortable),
+            "Compact" => Some(FeedDensity::Compact),
+            _ => None,
+        }
+    }
+}
+
+/// How often the server compiles a digest of missed activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DigestFrequency {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestFrequency::Off => "Off",
+            DigestFrequency::Daily => "Daily",
+            DigestFrequency::Weekly => "Weekly",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Off" => Some(DigestFrequency::Off),
+            "Daily" => Some(DigestFrequency::Daily),
+            "Weekly" => Some(DigestFrequency::Weekly),
+            _ => None,
+        }
+    }
+}
+
+/// Where the TUI sends spoken-style announcements of state changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AnnounceOutput {
+    #[default]
+    Off,
+    /// Desktop notifications through the terminal (OSC 9)
+    Terminal,
+    /// Read aloud with speech-dispatcher's `spd-say`
+    Speech,
+}
+
+impl AnnounceOutput {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnounceOutput::Off => "Off",
+            AnnounceOutput::Terminal => "Terminal",
+            AnnounceOutput::Speech => "Speech",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Off" => Some(AnnounceOutput::Off),
+            "Terminal" => Some(AnnounceOutput::Terminal),
+            "Speech" => Some(AnnounceOutput::Speech),
+            _ => None,
+        }
+    }
+}
+
+/// Role or recognition badge shown next to a username
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Badge {
+    Admin,
+    Moderator,
+    Bot,
+    EarlyAdopter,
+}
+
+impl Badge {
+    pub const ALL: [Badge; 4] = [
+        Badge::Admin,
+        Badge::Moderator,
+        Badge::Bot,
+        Badge::EarlyAdopter,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Badge::Admin => "admin",
+            Badge::Moderator => "moderator",
+            Badge::Bot => "bot",
+            Badge::EarlyAdopter => "early_adopter",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "admin" => Some(Badge::Admin),
+            "moderator" => Some(Badge::Moderator),
+            "bot" => Some(Badge::Bot),
+            "early_adopter" => Some(Badge::EarlyAdopter),
+            _ => None,
+        }
+    }
+
+    /// Single-width glyph rendered after the username
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Badge::Admin => "★",
+            Badge::Moderator => "◆",
+            Badge::Bot => "⚙",
+            Badge::EarlyAdopter => "✦",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Badge::Admin => "Admin",
+            Badge::Moderator => "Moderator",
+            Badge::Bot => "Bot",
+            Badge::EarlyAdopter => "Early adopter",
+        }
+    }
+}
+
+/// Where a request to cross-post a DM into the feed stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrosspostStatus {
+    /// Waiting for the other participant
+    Pending,
+    /// Published to the feed
+    Approved,
+    Declined,
+}
+
+impl CrosspostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrosspostStatus::Pending => "pending",
+            CrosspostStatus::Approved => "approved",
+            CrosspostStatus::Declined => "declined",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(CrosspostStatus::Pending),
+            "approved" => Some(CrosspostStatus::Approved),
+            "declined" => Some(CrosspostStatus::Declined),
+            _ => None,
+        }
+    }
+}
+
+/// File format of a conversation export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Readable transcript
+    #[default]
+    Markdown,
+    /// Every message with its metadata, for other tools
+    Json,
+}
+
+impl ExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "markdown",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Some(ExportFormat::Markdown),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// File extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Someone's answer to an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RsvpStatus {
+    Going,
+    Interested,
+}
+
+impl RsvpStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RsvpStatus::Going => "going",
+            RsvpStatus::Interested => "interested",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "going" => Some(RsvpStatus::Going),
+            "interested" => Some(RsvpStatus::Interested),
+            _ => None,
+        }
+    }
+}
+
+/// Where a job is done
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkMode {
+    Remote,
+    Onsite,
+}
+
+impl WorkMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkMode::Remote => "remote",
+            WorkMode::Onsite => "onsite",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "remote" => Some(WorkMode::Remote),
+            "onsite" | "on-site" => Some(WorkMode::Onsite),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WorkMode::Remote => "Remote",
+            WorkMode::Onsite => "On-site",
+        }
+    }
+}
+
+/// Who may reply to a post, set by its author for the whole thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplyPolicy {
+    #[default]
+    Everyone,
+    /// People the author follows
+    Following,
+    /// People @mentioned in the post
+    Mentioned,
+}
+
+impl ReplyPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReplyPolicy::Everyone => "everyone",
+            ReplyPolicy::Following => "following",
+            ReplyPolicy::Mentioned => "mentioned",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "everyone" => Some(ReplyPolicy::Everyone),
+            "following" => Some(ReplyPolicy::Following),
+            "mentioned" => Some(ReplyPolicy::Mentioned),
+            _ => None,
+        }
+    }
+
+    /// The next policy, for toggling through them in the composer
+    pub fn next(&self) -> Self {
+        match self {
+            ReplyPolicy::Everyone => ReplyPolicy::Following,
+            ReplyPolicy::Following => ReplyPolicy::Mentioned,
+            ReplyPolicy::Mentioned => ReplyPolicy::Everyone,
+        }
+    }
+
+    /// Who can reply, from the author's side ("people I follow")
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReplyPolicy::Everyone => "everyone",
+            ReplyPolicy::Following => "people I follow",
+            ReplyPolicy::Mentioned => "mentioned only",
+        }
+    }
+
+    /// Why someone else can't reply to `author`'s post
+    pub fn explain(&self, author: &str) -> String {
+        match self {
+            ReplyPolicy::Everyone => format!("@{} takes replies from everyone", author),
+            ReplyPolicy::Following => {
+                format!("@{} only takes replies from people they follow", author)
+            }
+            ReplyPolicy::Mentioned => {
+                format!("@{} only takes replies from people mentioned in the post", author)
+            }
+        }
+    }
+}
+
+/// Where a GitHub issue or pull request stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubState {
+    Open,
+    Closed,
+    /// Pull requests only
+    Merged,
+    /// An open pull request not yet ready for review
+    Draft,
+}
+
+impl GitHubState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitHubState::Open => "Open",
+            GitHubState::Closed => "Closed",
+            GitHubState::Merged => "Merged",
+            GitHubState::Draft => "Draft",
+        }
+    }
+}
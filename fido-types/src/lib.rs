@@ -1,5 +1,7 @@
 pub mod models;
 pub mod enums;
+pub mod pow;
+pub mod version;
 
 pub use models::*;
 pub use enums::*;
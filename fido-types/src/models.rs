@@ -2,7 +2,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::enums::{ColorScheme, SortOrder, VoteDirection};
+use crate::enums::{
+    AnnounceOutput, Badge, ColorScheme, CrosspostStatus, DigestFrequency, ExportFormat, FeedDensity,
+    GitHubState, ReplyPolicy, RsvpStatus, SortOrder, TimestampStyle, VoteDirection, WorkMode,
+};
 
 // Custom serde module for DateTime to ensure RFC3339 string format
 mod datetime_format {
@@ -34,6 +37,12 @@ pub struct User {
     #[serde(with = "datetime_format")]
     pub join_date: DateTime<Utc>,
     pub is_test_user: bool,
+    #[serde(default)]
+    pub badges: Vec<Badge>,
+    /// Votes received on the user's posts (up minus down) plus a bonus for
+    /// each of their replies a thread's author accepted
+    #[serde(default)]
+    pub karma: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +71,83 @@ pub struct Post {
     /// Username being replied to (for display purposes)
     #[serde(default)]
     pub reply_to_username: Option<String>,
+    /// Deleted post kept as a "[deleted]" placeholder so its replies stay attached
+    #[serde(default)]
+    pub is_deleted: bool,
+    /// Badges held by the author
+    #[serde(default)]
+    pub author_badges: Vec<Badge>,
+    /// The author's current status, if set and not expired
+    #[serde(default)]
+    pub author_status: Option<UserStatus>,
+    /// Structured details for posts that are more than text, like events
+    #[serde(default)]
+    pub kind: Option<PostKind>,
+    /// Posted without showing the author. Everyone but the author gets a
+    /// nil `author_id` and "anon" as the username.
+    #[serde(default)]
+    pub is_anonymous: bool,
+    /// Who the author takes replies from, for the whole thread
+    #[serde(default)]
+    pub reply_policy: ReplyPolicy,
+    /// Whether the viewer may reply under `reply_policy`
+    #[serde(default = "default_can_reply")]
+    pub can_reply: bool,
+    /// Reply the thread's author marked as the accepted answer
+    #[serde(default)]
+    pub is_accepted: bool,
+    /// The author's karma (0 on anonymous posts)
+    #[serde(default)]
+    pub author_karma: i32,
+}
+
+fn default_can_reply() -> bool {
+    true
+}
+
+/// The special post types, each with its own fields next to the text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PostKind {
+    Event(EventDetails),
+    ReviewRequest(ReviewRequestDetails),
+    Job(JobDetails),
+}
+
+/// An event or meetup; the post's text is its description
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventDetails {
+    pub title: String,
+    #[serde(with = "datetime_format")]
+    pub starts_at: DateTime<Utc>,
+    /// A place or a URL to join from
+    pub location: String,
+    pub going_count: i32,
+    pub interested_count: i32,
+    /// The caller's own RSVP, where the server knows who's asking
+    #[serde(default)]
+    pub my_rsvp: Option<RsvpStatus>,
+}
+
+/// A request for someone to review a repository or pull request; the
+/// post's text says what to look at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewRequestDetails {
+    pub url: String,
+    /// Programming language, named like the hashtag for it (`rust`)
+    pub language: String,
+    /// Still looking for reviewers; the author closes it when done
+    pub is_open: bool,
+}
+
+/// A job opening; the post's text describes it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobDetails {
+    pub title: String,
+    pub company: String,
+    pub work_mode: WorkMode,
+    /// Where to read more and apply
+    pub url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +159,24 @@ pub struct Vote {
     pub created_at: DateTime<Utc>,
 }
 
+/// Vote totals for a post, plus the voters the viewer is allowed to see
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostVoteSummary {
+    pub post_id: Uuid,
+    pub upvotes: i32,
+    pub downvotes: i32,
+    /// Only populated for the post's author, and only with voters who opted in
+    #[serde(default)]
+    pub visible_voters: Vec<VisibleVote>,
+}
+
+/// A single vote whose voter chose to be visible to the post author
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisibleVote {
+    pub username: String,
+    pub direction: VoteDirection,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DirectMessage {
     pub id: Uuid,
@@ -82,10 +186,82 @@ pub struct DirectMessage {
     pub from_username: String,
     #[serde(default)]
     pub to_username: String,
+    /// Empty when the message is only an attachment
     pub content: String,
     #[serde(with = "datetime_format")]
     pub created_at: DateTime<Utc>,
     pub is_read: bool,
+    #[serde(default)]
+    pub attachment: Option<DmAttachment>,
+}
+
+/// A small text or code file sent with a direct message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DmAttachment {
+    pub filename: String,
+    pub content: String,
+}
+
+/// A conversation written out as a file, ready to save
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationExport {
+    /// Suggested file name, e.g. `fido-dm-bob-2024-01-31.md`
+    pub filename: String,
+    pub format: ExportFormat,
+    pub content: String,
+}
+
+/// Version of the `AccountExport` layout this build writes and reads
+pub const ACCOUNT_EXPORT_VERSION: u32 = 1;
+
+/// An account in a portable form, for moving from one Fido instance to
+/// another: exported on the old server and imported on the new one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountExport {
+    pub version: u32,
+    pub username: String,
+    #[serde(default)]
+    pub bio: Option<String>,
+    pub exported_at: DateTime<Utc>,
+    /// Usernames the account follows
+    #[serde(default)]
+    pub follows: Vec<String>,
+    /// The account's own posts and replies, oldest first
+    #[serde(default)]
+    pub posts: Vec<ExportedPost>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedPost {
+    pub id: Uuid,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    /// Set when the post replies to another post in the same export
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+}
+
+/// What importing an `AccountExport` did
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountImportSummary {
+    pub posts_imported: usize,
+    pub replies_threaded: usize,
+    /// Already on this instance from an earlier import
+    pub duplicates_skipped: usize,
+    /// Rejected by this instance's content rules, e.g. too long
+    pub posts_rejected: usize,
+    pub follows_imported: usize,
+    /// Follows with no account of that name here
+    pub follows_unmatched: usize,
+    /// Whether the bio was copied (only when this account had none)
+    pub bio_imported: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetAccountMovedRequest {
+    /// Where the account lives now (`alice@fido.example` or a URL);
+    /// `None` clears the marker
+    pub moved_to: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,11 +269,56 @@ pub struct UserProfile {
     pub user_id: Uuid,
     pub username: String,
     pub bio: Option<String>,
+    /// Same score as `User::karma`
     pub karma: i32,
     pub post_count: i32,
     #[serde(with = "datetime_format")]
     pub join_date: DateTime<Utc>,
     pub recent_hashtags: Vec<String>,
+    #[serde(default)]
+    pub badges: Vec<Badge>,
+    #[serde(default)]
+    pub status: Option<UserStatus>,
+    /// Set when the account has moved to another instance
+    #[serde(default)]
+    pub moved_to: Option<String>,
+}
+
+/// Short "what I'm up to" line shown under a username, like a GitHub status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserStatus {
+    pub text: String,
+    #[serde(default)]
+    pub emoji: Option<String>,
+    /// When the status clears itself; `None` keeps it until replaced
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Writing statistics for a profile (`GET /users/:id/stats`), counted over
+/// the user's signed posts and replies
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserStats {
+    /// Posts and replies in each of the last weeks (rolling 7-day windows),
+    /// oldest first; the last entry is the past 7 days
+    pub posts_per_week: Vec<u32>,
+    /// Days in a row with a post or reply, ending today or yesterday (UTC)
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    /// Hashtags used most, most used first
+    pub top_hashtags: Vec<HashtagUse>,
+    pub upvotes_received: u32,
+    pub downvotes_received: u32,
+    pub total_posts: u32,
+    pub total_words: u32,
+    pub total_characters: u32,
+}
+
+/// A hashtag and how many of someone's posts used it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HashtagUse {
+    pub name: String,
+    pub count: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +331,14 @@ pub struct UserProfileView {
     pub following_count: usize,
     pub post_count: usize,
     pub relationship: RelationshipStatus,
+    #[serde(default)]
+    pub badges: Vec<Badge>,
+    #[serde(default)]
+    pub status: Option<UserStatus>,
+    #[serde(default)]
+    pub moved_to: Option<String>,
+    #[serde(default)]
+    pub karma: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -130,6 +359,130 @@ pub struct UserConfig {
     pub sort_order: SortOrder,
     pub max_posts_display: i32,
     pub emoji_enabled: bool,
+    /// Whether authors may see that this user voted on their posts.
+    /// Off by default: others only ever see aggregate counts.
+    #[serde(default)]
+    pub votes_visible_to_authors: bool,
+    /// Mute notifications until switched off again
+    #[serde(default)]
+    pub do_not_disturb: bool,
+    /// Mute notifications every day between the quiet hours below
+    #[serde(default)]
+    pub quiet_hours_enabled: bool,
+    /// Local hour (0-23) quiet hours begin
+    #[serde(default = "default_quiet_hours_start")]
+    pub quiet_hours_start: u8,
+    /// Local hour (0-23) quiet hours end; may be earlier than the start to
+    /// span midnight
+    #[serde(default = "default_quiet_hours_end")]
+    pub quiet_hours_end: u8,
+    /// Show unread counts next to tab names, e.g. "DMs (3)"
+    #[serde(default = "default_show_unread_counts")]
+    pub show_unread_counts: bool,
+    /// Plain single-column layouts for screen readers
+    #[serde(default)]
+    pub accessible_layout: bool,
+    /// Where state changes are announced
+    #[serde(default)]
+    pub announcements: AnnounceOutput,
+    /// No loading animations, and redraw only when something changed
+    #[serde(default)]
+    pub reduced_motion: bool,
+    /// Relative ("2h ago") or absolute post times
+    #[serde(default)]
+    pub timestamp_style: TimestampStyle,
+    /// Comfortable (wrapped body) or compact (one-line preview) feed posts
+    #[serde(default)]
+    pub feed_density: FeedDensity,
+    /// Body lines shown per comfortable feed post before "… more"; 0 shows
+    /// them all
+    #[serde(default)]
+    pub preview_lines: u8,
+    /// Show up/down vote counts next to feed posts
+    #[serde(default = "default_show_vote_counts")]
+    pub show_vote_counts: bool,
+    /// Show authors' karma next to their usernames
+    #[serde(default)]
+    pub show_karma: bool,
+    /// Hashtags bound to the 1-9 keys in the feed, in key order
+    #[serde(default)]
+    pub pinned_hashtags: Vec<String>,
+    /// Opt-in digest of missed activity, shown by the TUI on login
+    #[serde(default)]
+    pub digest_frequency: DigestFrequency,
+    /// Let search engines index the public profile and post pages
+    #[serde(default)]
+    pub discoverable: bool,
+}
+
+fn default_show_unread_counts() -> bool {
+    true
+}
+
+fn default_show_vote_counts() -> bool {
+    true
+}
+
+fn default_quiet_hours_start() -> u8 {
+    22
+}
+
+fn default_quiet_hours_end() -> u8 {
+    7
+}
+
+impl UserConfig {
+    /// Largest `preview_lines` limit a user may pick
+    pub const MAX_PREVIEW_LINES: u8 = 10;
+
+    /// Number keys available for pinned hashtags
+    pub const MAX_PINNED_HASHTAGS: usize = 9;
+
+    /// Tidy hashtags for pinning: `#` stripped, lowercased and duplicates
+    /// dropped, order kept. Errors on the first entry that isn't a hashtag
+    /// or when there are more than the number keys can hold.
+    pub fn normalize_pinned_hashtags<S: AsRef<str>>(tags: &[S]) -> Result<Vec<String>, String> {
+        let mut pinned: Vec<String> = Vec::new();
+        for tag in tags {
+            let raw = tag.as_ref().trim();
+            let name = raw.trim_start_matches('#').to_lowercase();
+            if name.chars().count() < 2
+                || !name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            {
+                return Err(format!("'{}' is not a hashtag", raw));
+            }
+            if !pinned.contains(&name) {
+                pinned.push(name);
+            }
+        }
+        if pinned.len() > Self::MAX_PINNED_HASHTAGS {
+            return Err(format!(
+                "At most {} hashtags can be pinned",
+                Self::MAX_PINNED_HASHTAGS
+            ));
+        }
+        Ok(pinned)
+    }
+
+    /// Hashtag pinned to number key `key` (1-9)
+    pub fn pinned_hashtag(&self, key: usize) -> Option<&str> {
+        key.checked_sub(1)
+            .and_then(|slot| self.pinned_hashtags.get(slot))
+            .map(String::as_str)
+    }
+
+    /// Whether quiet hours cover the given local hour
+    pub fn in_quiet_hours(&self, hour: u8) -> bool {
+        let (start, end) = (self.quiet_hours_start, self.quiet_hours_end);
+        if !self.quiet_hours_enabled || start == end {
+            return false;
+        }
+        if start < end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
 }
 
 impl Default for UserConfig {
@@ -140,6 +493,23 @@ impl Default for UserConfig {
             sort_order: SortOrder::default(),
             max_posts_display: 25,
             emoji_enabled: true,
+            votes_visible_to_authors: false,
+            do_not_disturb: false,
+            quiet_hours_enabled: false,
+            quiet_hours_start: default_quiet_hours_start(),
+            quiet_hours_end: default_quiet_hours_end(),
+            show_unread_counts: default_show_unread_counts(),
+            accessible_layout: false,
+            announcements: AnnounceOutput::default(),
+            reduced_motion: false,
+            timestamp_style: TimestampStyle::default(),
+            feed_density: FeedDensity::default(),
+            preview_lines: 0,
+            show_vote_counts: default_show_vote_counts(),
+            show_karma: false,
+            pinned_hashtags: Vec::new(),
+            digest_frequency: DigestFrequency::default(),
+            discoverable: false,
         }
     }
 }
@@ -148,6 +518,50 @@ impl Default for UserConfig {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreatePostRequest {
     pub content: String,
+    /// Hide the author, where the instance allows it
+    #[serde(default)]
+    pub anonymous: bool,
+    /// Who may reply; everyone unless the author limits it
+    #[serde(default)]
+    pub reply_policy: ReplyPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateEventRequest {
+    pub title: String,
+    #[serde(with = "datetime_format")]
+    pub starts_at: DateTime<Utc>,
+    pub location: String,
+    /// The post's text: what the event is about
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RsvpRequest {
+    pub status: RsvpStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateReviewRequest {
+    pub url: String,
+    pub language: String,
+    /// What reviewers should look at
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateReviewRequest {
+    pub is_open: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateJobRequest {
+    pub title: String,
+    pub company: String,
+    pub work_mode: WorkMode,
+    pub url: String,
+    /// About the role
+    pub content: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -169,6 +583,8 @@ pub struct VoteRequest {
 pub struct SendMessageRequest {
     pub to_username: String,
     pub content: String,
+    #[serde(default)]
+    pub attachment: Option<DmAttachment>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -176,17 +592,386 @@ pub struct UpdateBioRequest {
     pub bio: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetStatusRequest {
+    pub text: String,
+    #[serde(default)]
+    pub emoji: Option<String>,
+    /// Minutes until the status clears; omit to keep it
+    #[serde(default)]
+    pub expires_in_minutes: Option<u32>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateConfigRequest {
     pub color_scheme: Option<String>,
     pub sort_order: Option<String>,
     pub max_posts_display: Option<i32>,
     pub emoji_enabled: Option<bool>,
+    #[serde(default)]
+    pub votes_visible_to_authors: Option<bool>,
+    #[serde(default)]
+    pub do_not_disturb: Option<bool>,
+    #[serde(default)]
+    pub quiet_hours_enabled: Option<bool>,
+    #[serde(default)]
+    pub quiet_hours_start: Option<u8>,
+    #[serde(default)]
+    pub quiet_hours_end: Option<u8>,
+    #[serde(default)]
+    pub show_unread_counts: Option<bool>,
+    #[serde(default)]
+    pub accessible_layout: Option<bool>,
+    #[serde(default)]
+    pub announcements: Option<String>,
+    #[serde(default)]
+    pub reduced_motion: Option<bool>,
+    #[serde(default)]
+    pub timestamp_style: Option<String>,
+    #[serde(default)]
+    pub feed_density: Option<String>,
+    #[serde(default)]
+    pub preview_lines: Option<u8>,
+    #[serde(default)]
+    pub show_vote_counts: Option<bool>,
+    #[serde(default)]
+    pub show_karma: Option<bool>,
+    #[serde(default)]
+    pub pinned_hashtags: Option<Vec<String>>,
+    #[serde(default)]
+    pub digest_frequency: Option<String>,
+    #[serde(default)]
+    pub discoverable: Option<bool>,
+}
+
+/// An instance-specific emoji: `:shortcode:` expands to `replacement`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomEmoji {
+    pub shortcode: String,
+    pub replacement: String,
+}
+
+/// Which build of the server is answering (`GET /version`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerVersion {
+    pub version: String,
+    /// Oldest TUI release the server still works with
+    #[serde(default)]
+    pub min_client_version: Option<String>,
+}
+
+/// Instance limits the TUI shows to users (`GET /features`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstanceFeatures {
+    /// Posts and replies allowed per user in any 24 hours; `None` is unlimited
+    pub max_posts_per_day: Option<u32>,
+    /// Posts older than this many days are deleted; `None` keeps them forever
+    pub post_retention_days: Option<u32>,
+    /// How many more posts the caller may create today, when logged in and
+    /// a daily limit is set
+    #[serde(default)]
+    pub posts_remaining_today: Option<u32>,
+    /// `GET /posts/:id/translate` is backed by a translation service
+    #[serde(default)]
+    pub translation_enabled: bool,
+    /// Logging in needs a solved `GET /auth/challenge` of this difficulty
+    #[serde(default)]
+    pub login_pow_difficulty: Option<u32>,
+    /// Posts may be made anonymously
+    #[serde(default)]
+    pub anonymous_posting: bool,
+    /// Anonymous posts allowed per user in any 24 hours; `None` is unlimited
+    #[serde(default)]
+    pub max_anonymous_posts_per_day: Option<u32>,
+    /// `GET /leaderboard/posts` is available
+    #[serde(default)]
+    pub leaderboard: bool,
+    /// `GET /leaderboard/contributors` ranks people too
+    #[serde(default)]
+    pub contributor_leaderboard: bool,
+}
+
+/// What a link in a post really points at, checked before it's opened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkPreview {
+    /// The URL as written in the post
+    pub url: String,
+    /// Where it ends up after expanding known shorteners
+    pub resolved_url: String,
+    pub domain: String,
+    /// The URL went through a link shortener
+    pub expanded: bool,
+    /// The domain is on this instance's blocklist
+    pub blocked: bool,
+}
+
+/// What a GitHub link in a post points at, shown as a card under the post
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubCard {
+    /// The link as written in the post
+    pub url: String,
+    /// `owner/name` of the repository
+    pub repo: String,
+    #[serde(flatten)]
+    pub kind: GitHubCardKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GitHubCardKind {
+    Repo {
+        description: Option<String>,
+        stars: u64,
+        /// The repository's main language, as GitHub detects it
+        language: Option<String>,
+        archived: bool,
+    },
+    Issue {
+        number: u64,
+        title: String,
+        state: GitHubState,
+        comments: u64,
+    },
+    PullRequest {
+        number: u64,
+        title: String,
+        state: GitHubState,
+        comments: u64,
+    },
+}
+
+/// Text too long for a post, to be published as a gist on the author's
+/// GitHub account and posted as its opening words and a link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateGistPostRequest {
+    pub content: String,
+}
+
+/// A gist linked from a post, for reading it without leaving the terminal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Gist {
+    /// The link as written in the post
+    pub url: String,
+    /// GitHub login of whoever made it
+    pub owner: Option<String>,
+    pub description: Option<String>,
+    pub files: Vec<GistFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GistFile {
+    pub filename: String,
+    pub content: String,
+    /// Whether `content` stops short of the whole file
+    pub truncated: bool,
+}
+
+/// A post's content machine-translated by the instance's translation service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostTranslation {
+    pub post_id: Uuid,
+    pub translated_content: String,
+    /// Language the service detected the post was written in, if it said
+    pub source_language: Option<String>,
+    pub target_language: String,
+    /// Who translated it (the service's host), for attribution
+    pub provider: String,
+}
+
+/// Unread direct messages from one sender
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnreadFrom {
+    pub username: String,
+    pub count: i32,
+}
+
+/// Activity a user missed over one digest period, compiled by the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Digest {
+    pub id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Best-voted top-level posts from followed users and hashtags
+    pub top_posts: Vec<Post>,
+    /// Unread DMs at the time the digest was compiled, busiest sender first
+    pub unread_messages: Vec<UnreadFrom>,
+}
+
+/// What happened since a given time, for the catch-up screen after a long
+/// absence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Catchup {
+    pub since: DateTime<Utc>,
+    /// Usernames of new followers, newest first
+    pub new_followers: Vec<String>,
+    /// Best-voted top-level posts carrying a followed hashtag
+    pub top_posts: Vec<Post>,
+    pub unread_messages: Vec<UnreadFrom>,
+}
+
+/// A hashtag's week, compiled by the server for everyone following it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashtagChangelog {
+    pub id: Uuid,
+    pub hashtag: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// One line on how busy the week was, like "#rust this week: 12 posts
+    /// from 7 people"
+    pub summary: String,
+    /// Best-voted top-level posts of the week carrying the hashtag
+    pub top_posts: Vec<Post>,
+}
+
+/// A followed hashtag in the Digest section, with its latest changelog
+/// unless the user turned them off
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashtagDigestEntry {
+    pub hashtag: String,
+    pub enabled: bool,
+    /// `None` while off, or before the first week was compiled
+    pub changelog: Option<HashtagChangelog>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateHashtagDigestRequest {
+    pub enabled: bool,
+}
+
+/// Someone who earned karma over the past week, for the leaderboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopContributor {
+    pub user_id: Uuid,
+    pub username: String,
+    #[serde(default)]
+    pub badges: Vec<Badge>,
+    /// Karma earned over the week: votes from others on their posts and
+    /// replies, plus the bonus for accepted answers
+    pub karma: i32,
+}
+
+/// An unposted post kept on the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostDraft {
+    pub id: Uuid,
+    pub content: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body for creating or updating a draft
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveDraftRequest {
+    pub content: String,
+}
+
+/// A request to publish a post quoting a DM, which the conversation's
+/// other participant has to approve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Crosspost {
+    pub id: Uuid,
+    pub message_id: Uuid,
+    pub requester_username: String,
+    pub approver_username: String,
+    /// The post as it will be published
+    pub content: String,
+    pub status: CrosspostStatus,
+    pub created_at: DateTime<Utc>,
+    /// The published post, once approved
+    pub post_id: Option<Uuid>,
+}
+
+/// Body for asking to cross-post a DM
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateCrosspostRequest {
+    pub message_id: Uuid,
+    pub content: String,
+}
+
+/// A named room posts can be made in, e.g. `#rust-help`. Its posts stay out
+/// of the global feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Channel {
+    pub name: String,
+    pub description: String,
+    #[serde(with = "datetime_format")]
+    pub created_at: DateTime<Utc>,
+    pub member_count: i32,
+    /// Whether the caller has joined
+    #[serde(default)]
+    pub joined: bool,
+    /// Top-level posts by others since the caller last opened the channel
+    #[serde(default)]
+    pub unread_count: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateChannelRequest {
+    /// Lowercase letters, digits and dashes; a leading `#` is dropped
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A thread the user is watching, with replies from others they haven't seen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedThread {
+    /// The watched post; replies anywhere beneath it count
+    pub post_id: Uuid,
+    pub author_username: String,
+    pub content: String,
+    /// Replies by other users since the thread was last opened
+    pub new_replies: i32,
+}
+
+/// Someone the user follows on GitHub who is on Fido but not followed here
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubSuggestion {
+    pub user_id: Uuid,
+    pub username: String,
+    pub github_login: String,
+}
+
+/// Body for bookmarking several posts at once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BookmarkPostsRequest {
+    pub post_ids: Vec<Uuid>,
+}
+
+/// Body for muting several users at once; their posts leave the caller's feed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MuteUsersRequest {
+    pub user_ids: Vec<Uuid>,
+}
+
+/// Result of a batch request: how many items were newly bookmarked or muted
+/// (already applied ones and unknown IDs are skipped)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub applied: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoginRequest {
     pub username: String,
+    /// Required when `/features` reports a `login_pow_difficulty`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pow: Option<PowSolution>,
+}
+
+/// A proof-of-work challenge from `GET /auth/challenge` (see [`crate::pow`])
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowChallenge {
+    pub challenge: String,
+    /// Leading zero bits the hash must have
+    pub difficulty: u32,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A solved challenge, sent with the request it unlocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowSolution {
+    pub challenge: String,
+    pub nonce: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
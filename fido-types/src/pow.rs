@@ -0,0 +1,48 @@
+//! Proof-of-work for anonymous endpoints
+//!
+//! The server hands out a random challenge and a difficulty; the client
+//! finds a nonce such that SHA-256 of `"{challenge}:{nonce}"` starts with at
+//! least `difficulty` zero bits. Each extra bit doubles the expected work.
+
+use sha2::{Digest, Sha256};
+
+/// Zero bits at the start of the hash of `challenge` with `nonce`
+pub fn leading_zero_bits(challenge: &str, nonce: u64) -> u32 {
+    let digest = Sha256::digest(format!("{}:{}", challenge, nonce).as_bytes());
+    let mut bits = 0;
+    for byte in digest {
+        if byte == 0 {
+            bits += 8;
+        } else {
+            bits += byte.leading_zeros();
+            break;
+        }
+    }
+    bits
+}
+
+/// Whether `nonce` solves `challenge` at `difficulty`
+pub fn is_solution(challenge: &str, nonce: u64, difficulty: u32) -> bool {
+    leading_zero_bits(challenge, nonce) >= difficulty
+}
+
+/// The first nonce that solves `challenge`; expect about `2^difficulty` tries
+pub fn solve(challenge: &str, difficulty: u32) -> u64 {
+    (0..u64::MAX)
+        .find(|&nonce| is_solution(challenge, nonce, difficulty))
+        .unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solved_nonce_meets_the_difficulty() {
+        let nonce = solve("challenge", 8);
+        assert!(is_solution("challenge", nonce, 8));
+        assert!(leading_zero_bits("challenge", nonce) >= 8);
+        // Any nonce meets difficulty 0
+        assert_eq!(solve("challenge", 0), 0);
+    }
+}
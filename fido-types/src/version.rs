@@ -0,0 +1,143 @@
+//! Version handshake between the TUI and a server
+//!
+//! `GET /version` reports the server's release and the oldest client it
+//! still works with; the client keeps its own floor for servers. Versions
+//! are `major.minor.patch`; anything after a `-` or `+` is ignored.
+
+use crate::ServerVersion;
+
+/// How a client and server release fit together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Compatibility {
+    Compatible,
+    /// The server runs a newer release; this client still works with it
+    UpgradeAvailable { latest: String },
+    /// The server no longer supports this client
+    ClientTooOld { required: String },
+    /// This client no longer supports the server
+    ServerTooOld { server: String, required: String },
+}
+
+impl Compatibility {
+    /// Requests would fail in confusing ways, so the client shouldn't go on
+    pub fn is_incompatible(&self) -> bool {
+        matches!(
+            self,
+            Compatibility::ClientTooOld { .. } | Compatibility::ServerTooOld { .. }
+        )
+    }
+}
+
+/// `(major, minor, patch)`, or `None` when `version` isn't in that form
+pub fn parse(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+/// Compare `client_version` with what the server reported. Versions that
+/// don't parse are given the benefit of the doubt.
+pub fn check(client_version: &str, server: &ServerVersion, min_server_version: &str) -> Compatibility {
+    let Some(client) = parse(client_version) else {
+        return Compatibility::Compatible;
+    };
+
+    if let Some(required) = server.min_client_version.as_deref() {
+        if parse(required).is_some_and(|required| client < required) {
+            return Compatibility::ClientTooOld {
+                required: required.to_string(),
+            };
+        }
+    }
+
+    match parse(&server.version) {
+        Some(version) if parse(min_server_version).is_some_and(|min| version < min) => {
+            Compatibility::ServerTooOld {
+                server: server.version.clone(),
+                required: min_server_version.to_string(),
+            }
+        }
+        Some(version) if version > client => Compatibility::UpgradeAvailable {
+            latest: server.version.clone(),
+        },
+        _ => Compatibility::Compatible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(version: &str, min_client_version: Option<&str>) -> ServerVersion {
+        ServerVersion {
+            version: version.to_string(),
+            min_client_version: min_client_version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_ignores_prefix_and_suffix() {
+        assert_eq!(parse("0.1.5"), Some((0, 1, 5)));
+        assert_eq!(parse("v1.2.3-beta.1"), Some((1, 2, 3)));
+        assert_eq!(parse("1.2.3+build"), Some((1, 2, 3)));
+        assert_eq!(parse("1.2"), None);
+        assert_eq!(parse("1.2.3.4"), None);
+        assert_eq!(parse("latest"), None);
+    }
+
+    #[test]
+    fn test_check_compares_numerically() {
+        assert_eq!(
+            check("0.1.5", &server("0.1.5", Some("0.1.0")), "0.1.0"),
+            Compatibility::Compatible
+        );
+        assert_eq!(
+            check("0.1.9", &server("0.1.10", None), "0.1.0"),
+            Compatibility::UpgradeAvailable {
+                latest: "0.1.10".to_string()
+            }
+        );
+        // A newer client talking to an older, still supported server is fine
+        assert_eq!(
+            check("0.2.0", &server("0.1.8", None), "0.1.0"),
+            Compatibility::Compatible
+        );
+    }
+
+    #[test]
+    fn test_check_refuses_outside_either_floor() {
+        let too_old_client = check("0.1.5", &server("0.3.0", Some("0.2.0")), "0.1.0");
+        assert_eq!(
+            too_old_client,
+            Compatibility::ClientTooOld {
+                required: "0.2.0".to_string()
+            }
+        );
+        assert!(too_old_client.is_incompatible());
+
+        let too_old_server = check("0.3.0", &server("0.1.2", None), "0.2.0");
+        assert_eq!(
+            too_old_server,
+            Compatibility::ServerTooOld {
+                server: "0.1.2".to_string(),
+                required: "0.2.0".to_string()
+            }
+        );
+        assert!(too_old_server.is_incompatible());
+
+        // Unparseable versions never lock anyone out
+        assert_eq!(
+            check("dev", &server("0.9.0", Some("0.5.0")), "0.1.0"),
+            Compatibility::Compatible
+        );
+        assert_eq!(
+            check("0.1.5", &server("nightly", Some("soon")), "0.1.0"),
+            Compatibility::Compatible
+        );
+    }
+}